@@ -14,6 +14,13 @@
 --      current file (flushing the OS buffer) and opens a new one, which the
 --      companion app's filesystem watcher detects as a Create event within ~50 ms.
 --
+--   3. Two-way settings bridge with the app (see addon_bridge.rs):
+--      - CombatCoachStatus is written by the app (intensity, mute, pause,
+--        app version).  WoW loads it at login, so it is shown once per session.
+--      - /cc intensity N, /cc mute and /cc unmute queue a request in
+--        CombatCoachDB; WoW writes it to disk at the next /reload or logout,
+--        and the app applies it and acknowledges it in CombatCoachStatus.
+--
 -- This addon does NO combat log processing — all parsing and analysis happens
 -- in the companion app (Rust backend).  Keeping this addon minimal ensures it
 -- is compatible across patches and cannot cause taint issues.
@@ -24,9 +31,13 @@
 -- VERSION HISTORY:
 --   1.0.0 — Identity-only (GUID/name/class/spec on login/spec-change)
 --   1.1.0 — Added 1-second combat-log flush for real-time coaching
+--   1.2.0 — App status display and in-game intensity/mute requests
 
 local ADDON_NAME = "CombatCoach"
-local VERSION    = "1.1.0"
+local VERSION    = "1.2.0"
+
+-- Status older than this at login means the app was not running recently.
+local STATUS_STALE_SECONDS = 12 * 60 * 60
 
 -- ============================================================
 -- Combat-log flush state
@@ -93,6 +104,53 @@ local function WriteIdentity()
     CombatCoachDB["updatedAt"]    = GetServerTime()
 end
 
+-- ============================================================
+-- App bridge — status display and queued requests
+-- ============================================================
+local PREFIX = "|cff7c5cffCombatCoach|r"
+
+-- One-line description of CombatCoachStatus as loaded at login.
+local function DescribeAppStatus()
+    local st = CombatCoachStatus
+    if not st or not st["writtenAt"] then
+        return "|cffff0000app not seen|r — is CombatLedger running with the addon path set?"
+    end
+    local text = string.format(
+        "app v%s — intensity %d, sound %s%s",
+        st["appVersion"] or "?",
+        st["intensity"] or 0,
+        st["muted"] and "|cffff8800muted|r" or "on",
+        st["paused"] and ", |cffff8800coaching paused|r" or ""
+    )
+    local age = GetServerTime() - st["writtenAt"]
+    if age > STATUS_STALE_SECONDS then
+        text = text .. string.format(" |cff888888(last seen %d h ago)|r", math.floor(age / 3600))
+    end
+    return text
+end
+
+-- Whether the request queued in CombatCoachDB is still waiting for the app.
+local function RequestPending()
+    local db = CombatCoachDB or {}
+    local ack = CombatCoachStatus and CombatCoachStatus["ackRequestId"] or 0
+    return db["requestId"] ~= nil and db["requestId"] > ack
+end
+
+-- Queue a settings change for the app; merged with any pending request.
+local function QueueRequest(intensity, muted)
+    if not CombatCoachDB then
+        CombatCoachDB = {}
+    end
+    if not RequestPending() then
+        CombatCoachDB["requestIntensity"] = nil
+        CombatCoachDB["requestMuted"]     = nil
+    end
+    if intensity ~= nil then CombatCoachDB["requestIntensity"] = intensity end
+    if muted     ~= nil then CombatCoachDB["requestMuted"]     = muted     end
+    CombatCoachDB["requestId"] = GetServerTime()
+    print(PREFIX .. " — Request queued. Type /reload to send it to the app.")
+end
+
 -- ============================================================
 -- Event registration
 -- ============================================================
@@ -109,6 +167,9 @@ frame:SetScript("OnEvent", function(self, event, ...)
         -- Small delay: UnitFullName can return nil realm during the login sequence
         -- before the client has fully initialised the character data.
         C_Timer.After(2.0, WriteIdentity)
+        C_Timer.After(3.0, function()
+            print(PREFIX .. " " .. DescribeAppStatus())
+        end)
 
         -- Sync combat state in case the player logged in while already in combat
         -- (e.g. a disconnect/reconnect during a fight).
@@ -170,6 +231,10 @@ SlashCmdList["COMBATCOACH"] = function(msg)
             LoggingCombat() and "|cff00ff00active|r" or "|cffff0000disabled|r",
             inCombat        and "|cffff8800yes|r"    or "|cff888888no|r"
         ))
+        print("  App: " .. DescribeAppStatus())
+        if RequestPending() then
+            print("  |cffff8800Request pending|r — /reload to send it to the app.")
+        end
         print("|cff7c5cffTip:|r Use /cc flush to manually flush the combat log.")
 
     elseif cmd == "flush" then
@@ -180,6 +245,17 @@ SlashCmdList["COMBATCOACH"] = function(msg)
             print("|cff7c5cffCombatCoach|r — Combat logging is not enabled. Use /combatlog to enable it first.")
         end
 
+    elseif cmd:match("^intensity") then
+        local n = tonumber(cmd:match("^intensity%s+(%d)$"))
+        if n and n >= 1 and n <= 5 then
+            QueueRequest(n, nil)
+        else
+            print(PREFIX .. " — Usage: /cc intensity 1-5")
+        end
+
+    elseif cmd == "mute" or cmd == "unmute" then
+        QueueRequest(nil, cmd == "mute")
+
     elseif cmd == "reset" then
         CombatCoachDB = {}
         print("|cff7c5cffCombatCoach|r — Database reset.")
@@ -188,6 +264,8 @@ SlashCmdList["COMBATCOACH"] = function(msg)
         print("|cff7c5cffCombatCoach|r commands:")
         print("  /cc status  — refresh and display identity + flush status")
         print("  /cc flush   — manually flush the combat log to disk")
+        print("  /cc intensity 1-5 — set coaching intensity (applies after /reload)")
+        print("  /cc mute | unmute — silence audio cues (applies after /reload)")
         print("  /cc reset   — clear saved data")
     end
 end
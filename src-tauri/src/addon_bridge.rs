@@ -0,0 +1,368 @@
+/// Two-way settings bridge with the CombatCoach addon, via its SavedVariables.
+///
+/// WoW addons cannot talk to other processes; the only shared channel is the
+/// SavedVariables file the client reads at login or /reload and writes at
+/// logout or /reload.  The protocol uses two tables in that one file:
+///
+///   App → addon — `CombatCoachStatus`, owned by the app:
+///     CombatCoachStatus = {
+///         ["intensity"]    = 3,
+///         ["muted"]        = false,
+///         ["paused"]       = false,
+///         ["appVersion"]   = "1.2.8",
+///         ["writtenAt"]    = 1760000000,   -- unix seconds
+///         ["ackRequestId"] = 1759999000,
+///     }
+///
+///   Addon → app — request fields inside `CombatCoachDB`, set by
+///   `/cc intensity N`, `/cc mute` and `/cc unmute`:
+///     ["requestId"]        = 1759999000,   -- GetServerTime() when queued
+///     ["requestIntensity"] = 4,            -- optional
+///     ["requestMuted"]     = true,         -- optional
+///
+/// Timing: WoW writes its in-memory copy of both tables on every save, so a
+/// status block the app writes while the game is running is overwritten at
+/// the next /reload or logout.  The identity watcher therefore rewrites the
+/// block after every client save (see `sync_status`); the addon sees the
+/// fresh status at the next login, and requests reach the app at the next
+/// /reload.  Requests are applied once — `requestId` is compared against
+/// `AppConfig.addon_request_id` and echoed back as `ackRequestId`.
+///
+/// Version compatibility: the addon's `addonVersion` is checked against
+/// `ADDON_REQUIREMENTS`.  An older addon is flagged in the connection status
+/// (`addon_outdated`) and the user is told once per addon version to update
+/// it — otherwise the fields it does not write just go missing.
+use anyhow::Result;
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::config::{self, AppConfig};
+use crate::engine::Severity;
+use crate::{changelog, clock::unix_now_ms, ipc, profile, rules};
+
+/// Global table name of the app-owned status block.
+const STATUS_TABLE: &str = "CombatCoachStatus";
+
+/// Values the app publishes to the addon.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddonStatus {
+    pub intensity:      u8,
+    pub muted:          bool,
+    pub paused:         bool,
+    pub app_version:    String,
+    pub ack_request_id: u64,
+}
+
+/// A settings change queued in-game.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AddonRequest {
+    pub id:        u64,
+    pub intensity: Option<u8>,
+    pub muted:     Option<bool>,
+}
+
+// ---------------------------------------------------------------------------
+// Parsing (addon → app)
+// ---------------------------------------------------------------------------
+
+/// Raw right-hand side of a `["key"] = value,` line, trailing comma removed.
+fn extract_lua_raw<'a>(content: &'a str, key: &str) -> Option<&'a str> {
+    let needle = format!("[\"{}\"]", key);
+    let line = content.lines().find(|l| l.contains(&needle))?;
+    let eq_pos = line.find('=')?;
+    Some(line[eq_pos + 1..].trim().trim_end_matches(',').trim())
+}
+
+fn extract_lua_number(content: &str, key: &str) -> Option<u64> {
+    extract_lua_raw(content, key)?.parse().ok()
+}
+
+fn extract_lua_bool(content: &str, key: &str) -> Option<bool> {
+    match extract_lua_raw(content, key)? {
+        "true"  => Some(true),
+        "false" => Some(false),
+        _       => None,
+    }
+}
+
+/// The pending request in `content`, if the addon queued one.
+pub fn parse_request(content: &str) -> Option<AddonRequest> {
+    let id = extract_lua_number(content, "requestId").filter(|id| *id > 0)?;
+    Some(AddonRequest {
+        id,
+        intensity: extract_lua_number(content, "requestIntensity")
+            .map(|i| i.clamp(1, 5) as u8),
+        muted:     extract_lua_bool(content, "requestMuted"),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Writing (app → addon)
+// ---------------------------------------------------------------------------
+
+/// Render the status table in the same layout WoW uses when it saves.
+fn render_status(status: &AddonStatus, written_at: u64) -> String {
+    format!(
+        "{STATUS_TABLE} = {{\n\
+         \t[\"intensity\"] = {},\n\
+         \t[\"muted\"] = {},\n\
+         \t[\"paused\"] = {},\n\
+         \t[\"appVersion\"] = \"{}\",\n\
+         \t[\"writtenAt\"] = {},\n\
+         \t[\"ackRequestId\"] = {},\n\
+         }}\n",
+        status.intensity, status.muted, status.paused,
+        status.app_version, written_at, status.ack_request_id,
+    )
+}
+
+/// Byte range of the existing status block (its opening line through the
+/// closing `}` at column 0), if present.
+fn status_block_range(content: &str) -> Option<std::ops::Range<usize>> {
+    let start = content.find(&format!("{STATUS_TABLE} = {{"))?;
+    let close = content[start..].find("\n}")? + start + 2;
+    let end = content[close..].find('\n').map_or(content.len(), |n| close + n + 1);
+    Some(start..end)
+}
+
+/// `content` with the status block replaced (or appended when missing).
+fn with_status_block(content: &str, block: &str) -> String {
+    match status_block_range(content) {
+        Some(range) => format!("{}{}{}", &content[..range.start], block, &content[range.end..]),
+        None => {
+            let sep = if content.is_empty() || content.ends_with('\n') { "" } else { "\n" };
+            format!("{content}{sep}{block}")
+        }
+    }
+}
+
+/// Whether the block in `content` already carries `status` (ignoring `writtenAt`).
+fn status_matches(content: &str, status: &AddonStatus) -> bool {
+    let Some(range) = status_block_range(content) else { return false };
+    let block = &content[range];
+    extract_lua_number(block, "intensity") == Some(status.intensity as u64)
+        && extract_lua_bool(block, "muted") == Some(status.muted)
+        && extract_lua_bool(block, "paused") == Some(status.paused)
+        && extract_lua_number(block, "ackRequestId") == Some(status.ack_request_id)
+        && extract_lua_raw(block, "appVersion") == Some(format!("\"{}\"", status.app_version).as_str())
+}
+
+/// Write `status` into the SavedVariables file unless it is already there.
+///
+/// Returns whether the file was written.  Skipping unchanged content keeps the
+/// identity watcher from looping on its own Modify events.  A missing file is
+/// created (the addon has not been loaded yet; WoW keeps unknown globals only
+/// if the addon declares them, which it does).
+pub fn sync_status(sv_path: &Path, status: &AddonStatus) -> Result<bool> {
+    let content = match std::fs::read_to_string(sv_path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    if status_matches(&content, status) {
+        return Ok(false);
+    }
+    let updated = with_status_block(&content, &render_status(status, unix_now_ms() / 1000));
+
+    // Write-then-rename so WoW never reads a half-written file.
+    let tmp = sv_path.with_extension("lua.tmp");
+    std::fs::write(&tmp, updated)?;
+    std::fs::rename(&tmp, sv_path)?;
+    Ok(true)
+}
+
+// ---------------------------------------------------------------------------
+// Entry points
+// ---------------------------------------------------------------------------
+
+/// Write the status block for `cfg` (no-op until the addon path is known).
+/// Called on every config change via `push_config_update`.
+pub fn publish_status(app: &AppHandle, cfg: &AppConfig) {
+    if cfg.addon_sv_path.as_os_str().is_empty() {
+        return;
+    }
+    let paused = app.try_state::<Mutex<ipc::StateSnapshot>>()
+        .and_then(|s| s.lock().ok().map(|s| s.coaching_paused))
+        .unwrap_or(false);
+    let status = AddonStatus {
+        intensity:      cfg.intensity,
+        muted:          cfg.audio_muted,
+        paused,
+        app_version:    app.package_info().version.to_string(),
+        ack_request_id: cfg.addon_request_id,
+    };
+    if let Err(e) = sync_status(&cfg.addon_sv_path, &status) {
+        tracing::warn!("Addon bridge: status write to {:?} failed: {}", cfg.addon_sv_path, e);
+    }
+}
+
+/// Handle a fresh read of the SavedVariables file (called by the identity
+/// watcher): apply a new in-game request, then restore the status block the
+/// client may just have overwritten.
+pub fn on_saved_variables(app: &AppHandle, content: &str) {
+    let Ok(dir) = app.path().app_config_dir() else { return };
+    let Ok(mut cfg) = config::load_or_default(&dir) else { return };
+
+    match parse_request(content) {
+        Some(req) if req.id > cfg.addon_request_id => {
+            tracing::info!("Addon bridge: applying in-game request {:?}", req);
+            // Applied to the config as saved now, not the copy read above,
+            // so a settings-window save in between is not undone.
+            let updated = config::update(&dir, |cfg| {
+                if let Some(i) = req.intensity {
+                    cfg.intensity = i;
+                }
+                if let Some(m) = req.muted {
+                    cfg.audio_muted = m;
+                }
+                cfg.addon_request_id = req.id;
+            });
+            cfg = match updated {
+                Ok(cfg) => cfg,
+                Err(e)  => { tracing::warn!("Addon bridge: save failed: {}", e); return; }
+            };
+            profile::write_sync_copy(&cfg, &dir);
+            // Also publishes the status block with the new ack.
+            crate::push_background_config_update(app, cfg);
+        }
+        _ => publish_status(app, &cfg),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Version compatibility
+// ---------------------------------------------------------------------------
+
+/// What the app relies on from the addon, by the addon version that added
+/// it, oldest first.  The last entry is the minimum supported version.
+const ADDON_REQUIREMENTS: &[(&str, &str)] = &[
+    ("1.0.0", "player identity"),
+    ("1.1.0", "real-time combat-log flushing"),
+    ("1.2.0", "in-game status and /cc requests"),
+];
+
+/// Features the addon lacks, oldest first.  Addons that predate
+/// `addonVersion` report "" and lack everything.
+pub fn missing_features(addon_version: &str) -> Vec<&'static str> {
+    let installed = changelog::version_key(addon_version);
+    ADDON_REQUIREMENTS
+        .iter()
+        .filter(|(added_in, _)| installed < changelog::version_key(added_in))
+        .map(|(_, feature)| *feature)
+        .collect()
+}
+
+/// Update advisory for an outdated addon; None when it is supported.
+pub fn outdated_message(addon_version: &str) -> Option<String> {
+    let missing = missing_features(addon_version);
+    if missing.is_empty() {
+        return None;
+    }
+    let (min, _) = ADDON_REQUIREMENTS[ADDON_REQUIREMENTS.len() - 1];
+    let installed = if addon_version.is_empty() {
+        "an old version".to_owned()
+    } else {
+        format!("v{}", addon_version)
+    };
+    Some(format!(
+        "CombatCoach addon is {} — update to v{} or newer for {}.",
+        installed, min, missing.join(", "),
+    ))
+}
+
+/// Check the addon version from a SavedVariables read; returns true when it
+/// is outdated.  The advisory is pushed once per addon version
+/// (`AppConfig.addon_outdated_notice`).
+pub fn check_addon_version(app: &AppHandle, addon_version: &str) -> bool {
+    let Some(message) = outdated_message(addon_version) else { return false };
+    let Ok(dir) = app.path().app_config_dir() else { return true };
+    let Ok(cfg) = config::load_or_default(&dir) else { return true };
+    // Pre-`addonVersion` addons are recorded as "unknown".
+    let notice = if addon_version.is_empty() { "unknown" } else { addon_version };
+    if cfg.addon_outdated_notice == notice {
+        return true;
+    }
+
+    tracing::warn!("Addon bridge: {}", message);
+    let advice = rules::advice(
+        "addon_outdated",
+        "Update the Addon",
+        message,
+        Severity::Warn,
+        vec![("addon_version".to_owned(), addon_version.to_owned())],
+        0,
+    );
+    ipc::push_notification(app, advice);
+
+    if let Err(e) = config::update(&dir, |cfg| cfg.addon_outdated_notice = notice.to_owned()) {
+        tracing::warn!("Addon bridge: could not record addon notice: {}", e);
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const SAVED: &str = "\nCombatCoachDB = {\n\
+        \t[\"playerGUID\"] = \"Player-1234-ABCDEF\",\n\
+        \t[\"requestId\"] = 1759999000,\n\
+        \t[\"requestIntensity\"] = 9,\n\
+        \t[\"requestMuted\"] = true,\n\
+        }\n";
+
+    fn status() -> AddonStatus {
+        AddonStatus {
+            intensity:      4,
+            muted:          true,
+            paused:         false,
+            app_version:    "1.2.8".into(),
+            ack_request_id: 1759999000,
+        }
+    }
+
+    #[test]
+    fn parses_request_and_clamps_intensity() {
+        let req = parse_request(SAVED).expect("request");
+        assert_eq!(req.id, 1759999000);
+        assert_eq!(req.intensity, Some(5));
+        assert_eq!(req.muted, Some(true));
+        assert!(parse_request("CombatCoachDB = {\n}\n").is_none());
+    }
+
+    #[test]
+    fn status_block_is_replaced_not_duplicated() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("CombatCoach.lua");
+        std::fs::write(&path, SAVED).unwrap();
+
+        assert!(sync_status(&path, &status()).unwrap());
+        // Unchanged status: no rewrite (no watcher feedback loop).
+        assert!(!sync_status(&path, &status()).unwrap());
+
+        let quiet = AddonStatus { muted: false, ..status() };
+        assert!(sync_status(&path, &quiet).unwrap());
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content.matches("CombatCoachStatus = {").count(), 1);
+        assert!(content.contains("[\"playerGUID\"] = \"Player-1234-ABCDEF\""));
+        assert_eq!(extract_lua_bool(&content, "muted"), Some(false));
+    }
+
+    #[test]
+    fn flags_addons_older_than_the_requirements() {
+        assert!(missing_features("1.2.0").is_empty());
+        assert!(missing_features("1.10.0").is_empty());
+        assert!(outdated_message("1.2.0").is_none());
+
+        assert_eq!(missing_features("1.1.0"), vec!["in-game status and /cc requests"]);
+        assert_eq!(missing_features("").len(), ADDON_REQUIREMENTS.len());
+        assert_eq!(
+            outdated_message("1.0.0").as_deref(),
+            Some("CombatCoach addon is v1.0.0 — update to v1.2.0 or newer for \
+                  real-time combat-log flushing, in-game status and /cc requests."),
+        );
+    }
+}
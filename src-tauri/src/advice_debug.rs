@@ -0,0 +1,187 @@
+/// Advice debug capture — the raw log lines and tracker values behind each
+/// advice, so a user reporting "this advice was wrong" can attach something
+/// actionable.
+///
+/// With `AppConfig.advice_debug` on:
+///   - parser::run keeps the last RECENT_LINES raw lines (`capture`)
+///   - the engine gives every advice a `debug_id` and stores an
+///     `AdviceDebug` row (db `advice_debug`): the raw lines of the
+///     triggering event, the advice's key/values and a snapshot of the
+///     coaching state at the time
+///   - `get_advice_debug` returns the row for the live feed's "Copy debug"
+///
+/// The triggering lines are the ones sharing the event's timestamp, narrowed
+/// to those naming its source or target when several do.  Advice fired by
+/// the engine tick has no triggering event and carries state only.  Nothing
+/// is shown on the overlay.  Off by default: keeping the lines copies every
+/// line read.
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::{
+    engine::AdviceEvent,
+    parser::{self, LogEvent},
+    state::CombatState,
+};
+
+/// Raw lines kept: the parser runs ahead of the engine by up to the event
+/// channel's capacity.
+const RECENT_LINES: usize = 5_000;
+/// Triggering lines stored per advice at most.
+const MAX_LINES: usize = 8;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static RECENT: Lazy<Mutex<VecDeque<(u64, String)>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+
+/// What `get_advice_debug` returns.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdviceDebug {
+    pub debug_id:  String,
+    pub key:       String,
+    pub title:     String,
+    pub message:   String,
+    pub severity:  String,
+    /// Log timestamp (ms) the advice fired at.
+    pub fired_at:  u64,
+    pub raw_lines: Vec<String>,
+    pub kv:        Vec<(String, String)>,
+    pub state:     Vec<(String, String)>,
+    pub app_version: String,
+}
+
+/// Follow the config flag (engine, on start and config updates).
+pub fn set_enabled(enabled: bool) {
+    if !ENABLED.swap(enabled, Ordering::Relaxed) || enabled {
+        return;
+    }
+    if let Ok(mut recent) = RECENT.lock() {
+        recent.clear();
+    }
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Keep a raw line while capture is on (parser::run).
+pub fn capture(line: &str) {
+    if !enabled() {
+        return;
+    }
+    let Some(ts) = parser::line_timestamp_ms(line) else { return };
+    if let Ok(mut recent) = RECENT.lock() {
+        if recent.len() >= RECENT_LINES {
+            recent.pop_front();
+        }
+        recent.push_back((ts, line.to_owned()));
+    }
+}
+
+/// Raw lines of `event` (see module docs).
+fn triggering_lines(event: &LogEvent) -> Vec<String> {
+    let Ok(recent) = RECENT.lock() else { return vec![] };
+    let ts = event.timestamp_ms();
+    let same_time: Vec<&String> = recent.iter().filter(|(t, _)| *t == ts).map(|(_, l)| l).collect();
+    let units: Vec<&str> = [event.source_guid(), event.dest_guid()].into_iter().flatten().collect();
+    let naming: Vec<&String> = same_time.iter()
+        .copied()
+        .filter(|l| units.iter().any(|u| l.contains(u)))
+        .collect();
+    let lines = if naming.is_empty() { same_time } else { naming };
+    // The latest ones: an earlier pull may share the timestamp on a re-read.
+    lines.into_iter().rev().take(MAX_LINES).rev().cloned().collect()
+}
+
+/// Coaching state worth seeing next to a disputed advice.
+fn state_values(state: &CombatState, now_ms: u64) -> Vec<(String, String)> {
+    vec![
+        ("in_combat".to_owned(),        state.in_combat.to_string()),
+        ("pull_elapsed_ms".to_owned(),  state.pull_elapsed_ms(now_ms).to_string()),
+        ("encounter".to_owned(),        state.encounter_name.clone().unwrap_or_default()),
+        ("player_guid".to_owned(),      state.player_guid.clone().unwrap_or_default()),
+        ("group_coaching".to_owned(),   state.group_coaching.to_string()),
+        ("gcd_gap_ms".to_owned(),       state.gcd.current_gap_ms.to_string()),
+        ("gap_cause".to_owned(),        format!("{:?}", state.gcd.gap_cause)),
+        ("damage_taken_5s".to_owned(),  state.damage_taken.recent_damage(now_ms, 5_000).to_string()),
+        ("interrupts".to_owned(),       state.interrupt_count.to_string()),
+        ("player_deaths".to_owned(),    state.player_deaths.to_string()),
+        ("active_time_pct".to_owned(),  format!("{:.1}", state.activity.active_pct(state.pull_elapsed_ms(now_ms)))),
+        ("cancelled_casts".to_owned(),  state.hardcasts.cancelled.len().to_string()),
+        ("potions".to_owned(),          state.consumables.potions.to_string()),
+    ]
+}
+
+/// Debug row of a fired advice; `trigger` is None for tick-fired advice.
+/// `engine` holds the engine's own values (intensity, profile, role).
+pub fn record(
+    advice:  &AdviceEvent,
+    trigger: Option<&LogEvent>,
+    state:   &CombatState,
+    engine:  Vec<(String, String)>,
+    now_ms:  u64,
+) -> AdviceDebug {
+    let mut values = engine;
+    values.extend(state_values(state, now_ms));
+    AdviceDebug {
+        debug_id:    advice.debug_id.clone().unwrap_or_default(),
+        key:         advice.key.clone(),
+        title:       advice.title.clone(),
+        message:     advice.message.clone(),
+        severity:    format!("{:?}", advice.severity).to_lowercase(),
+        fired_at:    now_ms,
+        raw_lines:   trigger.map(triggering_lines).unwrap_or_default(),
+        kv:          advice.kv.clone(),
+        state:       values,
+        app_version: env!("CARGO_PKG_VERSION").to_owned(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// The debug row of an advice; None when it was fired without capture on or
+/// has been pruned.
+#[tauri::command]
+pub async fn get_advice_debug(app: AppHandle, advice_id: String) -> Result<Option<AdviceDebug>, String> {
+    let db_path = crate::sessions_db_path(&app)?;
+    if !db_path.exists() {
+        return Ok(None);
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = rusqlite::Connection::open_with_flags(&db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        crate::db::advice_debug(&conn, &advice_id)
+    })
+        .await
+        .map_err(|e| format!("Task error: {}", e))?
+        .map_err(|e| e.to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_the_lines_of_the_triggering_event() {
+        set_enabled(true);
+        let hit = r#"5/21 20:14:33.456  SPELL_DAMAGE,Creature-0-1-2-3-4-0000000001,"Boss",0xa48,0x0,Player-1-ABC,"Stonebraid",0x511,0x0,12345,"Shadow Surge",0x20,0,0,55000,0,0,0,nil,nil,nil"#;
+        let other = r#"5/21 20:14:33.456  SPELL_CAST_SUCCESS,Player-2-DEF,"Other",0x511,0x0,0000000000000000,"",0x80,0x0,31884,"Avenging Wrath",0x2"#;
+        capture(r#"5/21 20:14:30.000  SPELL_CAST_SUCCESS,Player-1-ABC,"Stonebraid",0x511,0x0,0000000000000000,"",0x80,0x0,1,"X",0x2"#);
+        capture(hit);
+        capture(other);
+
+        let event = parser::parse_line(hit).unwrap();
+        assert_eq!(triggering_lines(&event), vec![hit.to_owned()]);
+
+        set_enabled(false);
+        capture(hit);
+        assert!(RECENT.lock().unwrap().is_empty(), "turning capture off drops the lines");
+    }
+}
@@ -0,0 +1,254 @@
+/// Idle-time precomputation of the history dashboard's heavy aggregates.
+///
+/// The dodge trends (a window function over every `mechanic_dodges` row) and
+/// the cross-character mechanic stats (mechanic_stats.rs) get slower with
+/// every raid night.  Once the pipeline has seen no combat for `IDLE_AFTER`,
+/// `run` computes them on a read-only connection and stores the JSON results
+/// in `analytics_cache` through the DB writer, so opening the History tab
+/// after a raid reads one row per aggregate.
+///
+/// Every cached row carries the `data_stamp` it was computed from.  Readers
+/// (`read`) use a row only while the stamp still matches and otherwise
+/// compute on demand — and cache that result too — so a stale aggregate is
+/// never shown.
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{de::DeserializeOwned, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+use crate::{clock::unix_now_ms, config, db::{self, DbWriter}, ipc, mechanic_stats};
+
+/// How often the idle check runs.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// Time without combat before the job runs.
+const IDLE_AFTER: Duration = Duration::from_secs(5 * 60);
+
+/// Cache row names.
+pub const DODGE_TRENDS: &str = "dodge_trends";
+
+/// Cache row of the mechanic stats for one account.
+pub fn mechanic_stats_name(account: &str) -> String {
+    format!("mechanic_stats:{}", account)
+}
+
+// ---------------------------------------------------------------------------
+// Aggregates
+// ---------------------------------------------------------------------------
+
+/// One row of the dodge trends — a mechanic's dodge rate across pulls.
+#[derive(Debug, Clone, Serialize, serde::Deserialize, PartialEq)]
+pub struct DodgeTrendRow {
+    pub encounter:      String,
+    pub spell_id:       u32,
+    pub spell_name:     String,
+    pub pulls:          u32,
+    pub avoided:        u32,
+    pub hit:            u32,
+    /// Totals over the mechanic's 5 most recent pulls (improvement indicator).
+    pub recent_avoided: u32,
+    pub recent_hit:     u32,
+}
+
+/// Per-encounter, per-mechanic dodge totals across all stored pulls.
+pub fn dodge_trends(conn: &Connection) -> Result<Vec<DodgeTrendRow>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT enc, spell_id, MAX(spell_name), COUNT(*), \
+                SUM(avoided), SUM(hit), \
+                SUM(CASE WHEN rn <= 5 THEN avoided ELSE 0 END), \
+                SUM(CASE WHEN rn <= 5 THEN hit ELSE 0 END) \
+         FROM (SELECT *, ROW_NUMBER() OVER \
+                 (PARTITION BY enc, spell_id ORDER BY pull_id DESC) AS rn \
+               FROM (SELECT *, {} AS enc FROM mechanic_dodges)) \
+         GROUP BY enc, spell_id \
+         ORDER BY enc, spell_id",
+        db::canonical_encounter("encounter"),
+    ))?;
+    let rows = stmt.query_map([], |row| {
+        Ok(DodgeTrendRow {
+            encounter:      row.get(0)?,
+            spell_id:       row.get::<_, i64>(1)? as u32,
+            spell_name:     row.get(2)?,
+            pulls:          row.get::<_, i64>(3)? as u32,
+            avoided:        row.get::<_, i64>(4)? as u32,
+            hit:            row.get::<_, i64>(5)? as u32,
+            recent_avoided: row.get::<_, i64>(6)? as u32,
+            recent_hit:     row.get::<_, i64>(7)? as u32,
+        })
+    })?;
+    Ok(rows.collect::<Result<_, _>>()?)
+}
+
+// ---------------------------------------------------------------------------
+// Cache
+// ---------------------------------------------------------------------------
+
+/// Identifies the data the aggregates are computed from: changes with every
+/// pull stored or discarded, every dodge row, every session that learns its
+/// character and every change to the encounter aliases.
+pub fn data_stamp(conn: &Connection) -> Result<String> {
+    Ok(conn.query_row(
+        "SELECT (SELECT COUNT(*) FROM pulls), (SELECT COALESCE(MAX(id), 0) FROM pulls), \
+                (SELECT COALESCE(MAX(id), 0) FROM mechanic_dodges), \
+                (SELECT COUNT(*) FROM sessions WHERE player_guid != ''), \
+                (SELECT COUNT(*) || ':' || COALESCE(SUM(LENGTH(alias) + LENGTH(canonical)), 0) FROM encounter_aliases)",
+        [],
+        |row| Ok(format!("{}/{}/{}/{}/{}",
+            row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, row.get::<_, i64>(2)?, row.get::<_, i64>(3)?,
+            row.get::<_, String>(4)?)),
+    )?)
+}
+
+/// The cached aggregate `name` if it was computed from `stamp`.
+fn cached<T: DeserializeOwned>(conn: &Connection, name: &str, stamp: &str) -> Option<T> {
+    let payload: String = conn
+        .query_row(
+            "SELECT payload FROM analytics_cache WHERE name = ?1 AND stamp = ?2",
+            params![name, stamp],
+            |row| row.get(0),
+        )
+        .optional()
+        .ok()??;
+    serde_json::from_str(&payload).ok()
+}
+
+/// The aggregate `name`: cached when current, otherwise computed now and
+/// handed to the DB writer for next time.
+pub fn read<T: Serialize + DeserializeOwned>(
+    conn:    &Connection,
+    db:      Option<&DbWriter>,
+    name:    &str,
+    compute: impl FnOnce(&Connection) -> Result<T>,
+) -> Result<T> {
+    let stamp = data_stamp(conn)?;
+    if let Some(value) = cached(conn, name, &stamp) {
+        return Ok(value);
+    }
+    let value = compute(conn)?;
+    if let (Some(db), Ok(payload)) = (db, serde_json::to_string(&value)) {
+        db.store_analytics(name.to_owned(), stamp, unix_now_ms(), payload);
+    }
+    Ok(value)
+}
+
+/// Bring every aggregate up to date; returns how many were recomputed.
+fn precompute(db_path: &Path, account: &str, db: &DbWriter) -> Result<usize> {
+    let conn = Connection::open_with_flags(db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let stamp = data_stamp(&conn)?;
+    let mut computed = 0;
+    if cached::<Vec<DodgeTrendRow>>(&conn, DODGE_TRENDS, &stamp).is_none() {
+        read(&conn, Some(db), DODGE_TRENDS, dodge_trends)?;
+        computed += 1;
+    }
+    let stats_name = mechanic_stats_name(account);
+    if cached::<Vec<mechanic_stats::MechanicStat>>(&conn, &stats_name, &stamp).is_none() {
+        read(&conn, Some(db), &stats_name, |c| mechanic_stats::compute(c, account))?;
+        computed += 1;
+    }
+    Ok(computed)
+}
+
+// ---------------------------------------------------------------------------
+// Entry point
+// ---------------------------------------------------------------------------
+
+/// Watch for idle periods forever (spawned by `try_start_pipeline`).
+pub async fn run(db: DbWriter, app: AppHandle) {
+    let mut ticker = tokio::time::interval(CHECK_INTERVAL);
+    let mut last_combat = Instant::now();
+    let mut last_stamp  = String::new();
+
+    loop {
+        ticker.tick().await;
+        let in_combat = app.try_state::<Mutex<ipc::StateSnapshot>>()
+            .and_then(|s| s.lock().ok().map(|snap| snap.in_combat))
+            .unwrap_or(false);
+        if in_combat {
+            last_combat = Instant::now();
+            continue;
+        }
+        if last_combat.elapsed() < IDLE_AFTER {
+            continue;
+        }
+        let Ok(db_path) = crate::sessions_db_path(&app) else { continue };
+        if !db_path.exists() {
+            continue;
+        }
+        let account = app.path().app_config_dir().ok()
+            .and_then(|dir| config::load_or_default(&dir).ok())
+            .and_then(|cfg| config::account_of(&cfg.addon_sv_path))
+            .unwrap_or_default();
+
+        let db = db.clone();
+        let seen = last_stamp.clone();
+        let result = tauri::async_runtime::spawn_blocking(move || -> Result<(String, usize)> {
+            let stamp = data_stamp(&Connection::open_with_flags(&db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?)?;
+            if stamp == seen {
+                return Ok((stamp, 0));
+            }
+            let computed = precompute(&db_path, &account, &db)?;
+            Ok((stamp, computed))
+        })
+        .await;
+        match result {
+            Ok(Ok((stamp, computed))) => {
+                if computed > 0 {
+                    tracing::info!("Analytics: {} history aggregates precomputed", computed);
+                }
+                last_stamp = stamp;
+            }
+            Ok(Err(e)) => tracing::warn!("Analytics precompute failed: {}", e),
+            Err(e)     => tracing::warn!("Analytics task failed: {}", e),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::apply_schema(&conn).unwrap();
+        conn.execute("INSERT INTO sessions (started_at) VALUES (0)", []).unwrap();
+        conn
+    }
+
+    fn add_pull(conn: &Connection, hit: u32) {
+        conn.execute("INSERT INTO pulls (session_id, pull_number, started_at) VALUES (1, 1, 0)", []).unwrap();
+        conn.execute(
+            "INSERT INTO mechanic_dodges (pull_id, encounter, spell_id, spell_name, avoided, hit) \
+             VALUES (last_insert_rowid(), 'Boss', 7, 'Flames', 1, ?1)",
+            [hit],
+        ).unwrap();
+    }
+
+    #[test]
+    fn cached_aggregates_are_used_until_the_data_changes() {
+        let conn = test_db();
+        add_pull(&conn, 2);
+        let stamp = data_stamp(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO analytics_cache (name, stamp, computed_at, payload) VALUES (?1, ?2, 0, '[]')",
+            params![DODGE_TRENDS, stamp],
+        ).unwrap();
+
+        // Current cache row: the query is not run.
+        let rows = read(&conn, None, DODGE_TRENDS, |_| -> Result<Vec<DodgeTrendRow>> {
+            panic!("cache should answer")
+        }).unwrap();
+        assert!(rows.is_empty());
+
+        // A new pull makes it stale.
+        add_pull(&conn, 0);
+        assert_ne!(data_stamp(&conn).unwrap(), stamp);
+        let rows = read(&conn, None, DODGE_TRENDS, dodge_trends).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!((rows[0].pulls, rows[0].avoided, rows[0].hit), (2, 2, 2));
+    }
+}
@@ -0,0 +1,196 @@
+/// Headless log analysis — a whole combat log through the parser and the
+/// engine's rule passes in one synchronous call, without the tailer, the
+/// overlay or the database.
+///
+/// The result is a per-pull report: outcome, duration, the debrief summary
+/// and every advice event the pull got, with counts per rule.  Meant for
+/// post-raid review of a log recorded without the app running, and for
+/// checking rule changes against golden logs in automated tests.
+///
+/// Exposed as the `analyze_log` command (uses the saved config) and as a dev
+/// CLI that prints the report as JSON:
+///   combat-ledger-live-coach --analyze WoWCombatLog.txt [--player Name-Realm] [--spec key]
+/// Without `--player` the GUID cannot be inferred and only pass 1 (enemy
+/// events) produces advice.  Archived `.gz` logs are read directly.
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    config::{self, AppConfig},
+    db::StoredAdvice,
+    engine::{self, AnalyzedPull},
+    parser, recoach, tailer,
+};
+
+#[derive(Debug, Serialize)]
+pub struct LogReport {
+    /// Lines read from the log.
+    pub lines:  usize,
+    /// Lines the parser turned into events.
+    pub events: usize,
+    pub pulls:  Vec<PullReport>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PullReport {
+    pub pull_number: u32,
+    /// Encounter or arena bracket; None for open-world pulls.
+    pub encounter:   Option<String>,
+    /// Log timestamp of the pull start.
+    pub started_ms:  u64,
+    pub duration_ms: u64,
+    /// "kill", "wipe", or "unknown" when the log ends mid-pull.
+    pub outcome:     String,
+    /// Training-dummy pull, scored as practice instead of numbered.
+    pub practice:    bool,
+    /// Debrief summary line, as shown on the overlay.
+    pub summary:     String,
+    /// Advice count per rule key.
+    pub by_rule:     BTreeMap<String, u32>,
+    pub advice:      Vec<StoredAdvice>,
+}
+
+impl From<AnalyzedPull> for PullReport {
+    fn from(pull: AnalyzedPull) -> Self {
+        let advice: Vec<StoredAdvice> = pull.advice.into_iter().map(recoach::to_stored).collect();
+        let mut by_rule = BTreeMap::new();
+        for a in &advice {
+            *by_rule.entry(a.rule_key.clone()).or_insert(0) += 1;
+        }
+        let d = pull.debrief;
+        Self {
+            pull_number: d.pull_number,
+            encounter:   pull.encounter,
+            started_ms:  pull.started_ms,
+            duration_ms: d.pull_elapsed_ms,
+            outcome:     d.outcome,
+            practice:    d.practice.is_some(),
+            summary:     d.summary,
+            by_rule,
+            advice,
+        }
+    }
+}
+
+/// Analyze a combat log file (plain or gzip-compressed).
+pub fn analyze_file(path: &Path, config: AppConfig) -> Result<LogReport> {
+    analyze_reader(tailer::open_log(path)?, config)
+}
+
+/// Analyze a combat log read line by line from `reader`.
+pub fn analyze_reader(reader: impl BufRead, config: AppConfig) -> Result<LogReport> {
+    let mut lines = 0;
+    let mut events = 0;
+    let mut read_error = None;
+    let parsed = reader
+        .lines()
+        .map_while(|line| line.map_err(|e| read_error = Some(e)).ok())
+        .inspect(|_| lines += 1)
+        .filter_map(|line| parser::parse_line(&line))
+        .inspect(|_| events += 1);
+    let pulls = engine::analyze_log(parsed, config);
+    if let Some(e) = read_error {
+        return Err(anyhow::anyhow!("log read failed after {} lines: {}", lines, e));
+    }
+    Ok(LogReport {
+        lines,
+        events,
+        pulls: pulls.into_iter().map(PullReport::from).collect(),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Tauri command
+// ---------------------------------------------------------------------------
+
+/// Analyze a log file with the saved config and return the per-pull report.
+#[tauri::command]
+pub async fn analyze_log(app: AppHandle, path: String) -> Result<LogReport, String> {
+    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let cfg = config::load_or_default(&config_dir).map_err(|e| e.to_string())?;
+    let report = tauri::async_runtime::spawn_blocking(move || analyze_file(Path::new(&path), cfg))
+        .await
+        .map_err(|e| format!("Task error: {}", e))?
+        .map_err(|e| e.to_string())?;
+    tracing::info!("Analyzed {} lines → {} pulls", report.lines, report.pulls.len());
+    Ok(report)
+}
+
+// ---------------------------------------------------------------------------
+// CLI
+// ---------------------------------------------------------------------------
+
+/// `--analyze`: print the report as JSON.  Returns the process exit code.
+pub fn run_cli(path: &str, player: Option<&str>, spec: Option<&str>) -> i32 {
+    let mut cfg = AppConfig::default();
+    if let Some(player) = player {
+        cfg.player_focus = player.to_owned();
+    }
+    if let Some(spec) = spec {
+        cfg.selected_spec = spec.to_owned();
+    }
+    let report = match analyze_file(Path::new(path), cfg) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            return 2;
+        }
+    };
+    match serde_json::to_string_pretty(&report) {
+        Ok(json) => { println!("{}", json); 0 }
+        Err(e)   => { eprintln!("{}", e); 2 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    const LOG: &str = r#"5/21 20:14:30.000  ENCOUNTER_START,2920,"The Necrotic Wake",14,5
+5/21 20:14:31.000  SPELL_CAST_SUCCESS,Player-1234-ABCDEF,"Stonebraid",0x511,0x0,Creature-0-4372-ABCD-000,"Boss",0xa48,0x0,35395,"Crusader Strike",0x1
+5/21 20:14:45.000  SPELL_CAST_SUCCESS,Player-1234-ABCDEF,"Stonebraid",0x511,0x0,Creature-0-4372-ABCD-000,"Boss",0xa48,0x0,35395,"Crusader Strike",0x1
+5/21 20:15:30.000  ENCOUNTER_END,2920,"The Necrotic Wake",14,5,1
+not a combat log line
+5/21 20:20:00.000  ENCOUNTER_START,2921,"Blightbone",14,5
+5/21 20:20:05.000  SPELL_CAST_SUCCESS,Player-1234-ABCDEF,"Stonebraid",0x511,0x0,Creature-0-4372-ABCD-000,"Boss",0xa48,0x0,35395,"Crusader Strike",0x1
+"#;
+
+    fn analyze(player: &str) -> LogReport {
+        let cfg = AppConfig { player_focus: player.to_owned(), ..AppConfig::default() };
+        analyze_reader(Cursor::new(LOG), cfg).unwrap()
+    }
+
+    #[test]
+    fn reports_each_pull_with_outcome() {
+        let report = analyze("Stonebraid-Draenor");
+        assert_eq!(report.lines, 7);
+        assert_eq!(report.events, 6);
+        assert_eq!(report.pulls.len(), 2);
+
+        let kill = &report.pulls[0];
+        assert_eq!(kill.pull_number, 1);
+        assert_eq!(kill.encounter.as_deref(), Some("The Necrotic Wake"));
+        assert_eq!(kill.outcome, "kill");
+        assert_eq!(kill.duration_ms, 60_000);
+
+        // The log ends mid-pull.
+        let cut = &report.pulls[1];
+        assert_eq!(cut.pull_number, 2);
+        assert_eq!(cut.outcome, "unknown");
+        assert_eq!(cut.duration_ms, 5_000);
+    }
+
+    #[test]
+    fn counts_advice_per_rule() {
+        let report = analyze("Stonebraid");
+        let kill = &report.pulls[0];
+        assert!(kill.by_rule.keys().any(|k| k.starts_with("gcd_gap")), "{:?}", kill.by_rule);
+        assert_eq!(kill.by_rule.values().sum::<u32>() as usize, kill.advice.len());
+        assert!(kill.advice.iter().all(|a| a.fired_at >= kill.started_ms));
+    }
+}
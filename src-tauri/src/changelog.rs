@@ -0,0 +1,188 @@
+/// Structured changelog — "what's new" for update checks and first runs.
+///
+/// Every release publishes `changelog.json` next to `latest.json`:
+///   { "entries": [ { "version": "1.3.0", "date": "2026-10-01",
+///                    "new_rules": ["Lust Timing"], "notes": ["…"] } ] }
+///
+/// `check_for_update` fetches the feed (best effort) and caches it in the app
+/// cache dir; `get_changelog` reads the cache and falls back to the copy
+/// embedded at build time (data/changelog.json) when nothing was fetched yet.
+///
+/// The first time a new version runs, `announce_new_version` pushes one
+/// advice-style notification naming the rules added since the version the
+/// user last ran (`AppConfig.last_seen_version`).
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+
+use crate::{config, engine::{Priority, Severity}, ipc, rules};
+
+const EMBEDDED_CHANGELOG: &str = include_str!("../../data/changelog.json");
+/// Published alongside latest.json by CI.
+pub const FEED_URL: &str = "https://github.com/MFredin/CombatCoaching/releases/latest/download/changelog.json";
+/// File name of the cached feed inside the app cache dir.
+const CACHE_FILE: &str = "changelog.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangelogEntry {
+    pub version:   String,
+    #[serde(default)]
+    pub date:      String,
+    /// Display names of coaching rules added in this version.
+    #[serde(default)]
+    pub new_rules: Vec<String>,
+    #[serde(default)]
+    pub notes:     Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Feed {
+    entries: Vec<ChangelogEntry>,
+}
+
+pub fn parse_feed(raw: &str) -> Result<Vec<ChangelogEntry>> {
+    let feed: Feed = serde_json::from_str(raw)
+        .map_err(|e| anyhow::anyhow!("Changelog parse error: {}", e))?;
+    Ok(feed.entries)
+}
+
+/// Numeric components of "v1.2.10" → [1, 2, 10]; non-numeric suffixes ignored.
+pub(crate) fn version_key(v: &str) -> Vec<u32> {
+    v.trim().trim_start_matches('v')
+        .split('.')
+        .map(|part| {
+            let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+            digits.parse().unwrap_or(0)
+        })
+        .collect()
+}
+
+/// Entries newer than `since` (all when empty) and not newer than `up_to`,
+/// newest first.
+pub fn entries_between(entries: &[ChangelogEntry], since: &str, up_to: Option<&str>) -> Vec<ChangelogEntry> {
+    let since = version_key(since);
+    let up_to = up_to.map(version_key);
+    let mut out: Vec<ChangelogEntry> = entries.iter()
+        .filter(|e| {
+            let v = version_key(&e.version);
+            v > since && up_to.as_ref().is_none_or(|max| v <= *max)
+        })
+        .cloned()
+        .collect();
+    out.sort_by_key(|e| std::cmp::Reverse(version_key(&e.version)));
+    out
+}
+
+/// Validate and store a freshly fetched feed.
+pub fn cache_feed(cache_dir: &Path, raw: &str) -> Result<()> {
+    parse_feed(raw)?;
+    std::fs::create_dir_all(cache_dir)?;
+    std::fs::write(cache_dir.join(CACHE_FILE), raw)?;
+    Ok(())
+}
+
+/// Cached feed if present and valid, else the embedded copy.
+fn load_entries(cache_dir: Option<&Path>) -> Vec<ChangelogEntry> {
+    cache_dir
+        .and_then(|d| std::fs::read_to_string(d.join(CACHE_FILE)).ok())
+        .and_then(|raw| parse_feed(&raw).ok())
+        .or_else(|| parse_feed(EMBEDDED_CHANGELOG).ok())
+        .unwrap_or_default()
+}
+
+/// "New coaching rules: A, B" for the given entries; None if none were added.
+pub fn whats_new_message(entries: &[ChangelogEntry]) -> Option<String> {
+    let rules: Vec<&str> = entries.iter()
+        .flat_map(|e| e.new_rules.iter().map(String::as_str))
+        .collect();
+    (!rules.is_empty()).then(|| format!("New coaching rules: {}.", rules.join(", ")))
+}
+
+// ---------------------------------------------------------------------------
+// Entry points
+// ---------------------------------------------------------------------------
+
+/// Tauri command: changelog entries newer than `since_version` (all when
+/// empty), up to the running version.
+#[tauri::command]
+pub fn get_changelog(app: AppHandle, since_version: String) -> Vec<ChangelogEntry> {
+    let cache_dir = app.path().app_cache_dir().ok();
+    let current = app.package_info().version.to_string();
+    entries_between(&load_entries(cache_dir.as_deref()), &since_version, Some(&current))
+}
+
+/// Fire the one-time "what's new" notification if this version has not run
+/// here before, then record it.  Fresh installs (no log path yet, no version
+/// recorded) are only recorded — there is nothing to compare against.
+pub fn announce_new_version(app: &AppHandle) {
+    let Ok(dir) = app.path().app_config_dir() else { return };
+    let Ok(cfg) = config::load_or_default(&dir) else { return };
+    let current = app.package_info().version.to_string();
+    if cfg.last_seen_version == current {
+        return;
+    }
+
+    let upgrade = !cfg.last_seen_version.is_empty() || !cfg.wow_log_path.as_os_str().is_empty();
+    if upgrade {
+        // Upgrading from a build without version tracking: announce this version only.
+        let since = if cfg.last_seen_version.is_empty() { "0" } else { cfg.last_seen_version.as_str() };
+        let mut entries = entries_between(
+            &load_entries(app.path().app_cache_dir().ok().as_deref()), since, Some(&current),
+        );
+        if cfg.last_seen_version.is_empty() {
+            entries.retain(|e| version_key(&e.version) == version_key(&current));
+        }
+        if let Some(message) = whats_new_message(&entries) {
+            tracing::info!("What's new in {}: {}", current, message);
+            push_notification(app, &current, message);
+        }
+    }
+
+    if let Err(e) = config::update(&dir, |cfg| cfg.last_seen_version = current) {
+        tracing::warn!("Changelog: could not record last seen version: {}", e);
+    }
+}
+
+fn push_notification(app: &AppHandle, version: &str, message: String) {
+    let advice = rules::advice(
+        "whats_new",
+        &format!("What's New in v{}", version),
+        message,
+        Severity::Good,
+        vec![("version".to_owned(), version.to_owned())],
+        0,
+    )
+    .with_priority(Priority::Info);
+    ipc::push_notification(app, advice);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_changelog_parses() {
+        let entries = parse_feed(EMBEDDED_CHANGELOG).unwrap();
+        assert!(!entries.is_empty());
+    }
+
+    #[test]
+    fn filters_and_orders_by_version() {
+        let raw = r#"{ "entries": [
+            { "version": "1.2.9",  "new_rules": ["A"] },
+            { "version": "1.2.10", "new_rules": ["B", "C"] },
+            { "version": "1.3.0",  "notes": ["later"] },
+            { "version": "1.2.8" }
+        ] }"#;
+        let entries = parse_feed(raw).unwrap();
+
+        let between = entries_between(&entries, "1.2.8", Some("1.2.10"));
+        let versions: Vec<&str> = between.iter().map(|e| e.version.as_str()).collect();
+        assert_eq!(versions, vec!["1.2.10", "1.2.9"]);
+        assert_eq!(whats_new_message(&between).as_deref(), Some("New coaching rules: B, C, A."));
+
+        assert_eq!(entries_between(&entries, "", None).len(), 4);
+        assert!(whats_new_message(&entries_between(&entries, "1.2.10", None)).is_none());
+    }
+}
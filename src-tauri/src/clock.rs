@@ -0,0 +1,194 @@
+/// Log-to-wallclock alignment.
+///
+/// Combat log timestamps are the game client's local time-of-day, while the
+/// overlay compares everything against the system clock (`Date.now()`).
+/// Processing latency — WoW's stdio write buffer, the tailer's poll interval,
+/// channel queues — means the two drift apart by anywhere from a few ms to
+/// several seconds, so countdowns and card expiry on the overlay wander.
+///
+/// The tailer samples the active log file's modification time every time it
+/// reads new lines and pairs it with the timestamp of the last complete line.
+/// WoW can only write a line *after* the event happened, so
+/// `mtime - line_ts` is always >= the true offset; the smallest sample seen
+/// is the best estimate (the write that landed closest to its event).
+///
+/// Log timestamps are milliseconds-since-midnight, so at midnight every new
+/// sample jumps up by a full day.  A sample more than 12 hours above the
+/// current estimate is therefore treated as a day rollover and replaces the
+/// estimate instead of being discarded.
+///
+/// Offline replay (`tailer::replay`) paces an old log against the system
+/// clock instead, so its own aligner re-bases the offset as it goes.
+///
+/// Shared between the tailer thread (writer) and the engine task (reader)
+/// via `Arc`; the offset is a single atomic so no locking is needed.
+///
+/// `LogClock` is the engine's log time between events: the last event's
+/// timestamp advanced by the wallclock time since.  A replay sets its rate
+/// to the replay speed, and to 0 while paused, so the combat timeout and the
+/// time-driven rules follow the replay rather than the wallclock.
+///
+/// `unix_now_ms` is the wallclock every module stamps stored rows and
+/// advice with.
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Sentinel for "no sample observed yet".
+const UNKNOWN: i64 = i64::MIN;
+/// Jumps larger than this are a midnight rollover, not write latency.
+const ROLLOVER_MS: i64 = 12 * 3_600_000;
+
+#[derive(Debug)]
+pub struct ClockAligner {
+    /// Estimated `wallclock_ms - log_ts_ms`, or `UNKNOWN`.
+    offset_ms: AtomicI64,
+}
+
+impl ClockAligner {
+    pub fn new() -> Self {
+        Self { offset_ms: AtomicI64::new(UNKNOWN) }
+    }
+
+    /// Record one (log timestamp, file-write wallclock) pair.
+    pub fn observe(&self, log_ts_ms: u64, wall_ms: u64) {
+        let sample = wall_ms as i64 - log_ts_ms as i64;
+        let _ = self.offset_ms.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |cur| {
+            if cur == UNKNOWN || sample < cur || sample - cur > ROLLOVER_MS {
+                Some(sample)
+            } else {
+                None
+            }
+        });
+    }
+
+    /// Replace the estimate with one (log timestamp, wallclock) pair,
+    /// regardless of the samples seen so far.
+    pub fn rebase(&self, log_ts_ms: u64, wall_ms: u64) {
+        self.offset_ms.store(wall_ms as i64 - log_ts_ms as i64, Ordering::Relaxed);
+    }
+
+    /// Current offset estimate in ms, or `None` before the first sample.
+    pub fn offset_ms(&self) -> Option<i64> {
+        match self.offset_ms.load(Ordering::Relaxed) {
+            UNKNOWN => None,
+            o       => Some(o),
+        }
+    }
+
+    /// Convert a log timestamp to Unix-epoch wallclock milliseconds.
+    pub fn to_wallclock(&self, log_ts_ms: u64) -> Option<u64> {
+        self.offset_ms().map(|o| (log_ts_ms as i64 + o).max(0) as u64)
+    }
+}
+
+/// Unix-epoch milliseconds of the system clock (0 before 1970).
+pub fn unix_now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Log time extrapolated from the last event.  Instants are passed in so the
+/// extrapolation is testable.
+#[derive(Debug, Clone, Copy)]
+pub struct LogClock {
+    /// Log time at `anchored_at`.
+    anchor_ms:   u64,
+    anchored_at: Instant,
+    /// Log ms per wallclock ms; 0 holds log time at the last event.
+    rate:        u64,
+}
+
+impl LogClock {
+    pub fn new(at: Instant) -> Self {
+        Self { anchor_ms: 0, anchored_at: at, rate: 1 }
+    }
+
+    /// An event with log timestamp `log_ms` arrived at `at`.
+    pub fn event(&mut self, log_ms: u64, at: Instant) {
+        self.anchor_ms   = log_ms;
+        self.anchored_at = at;
+    }
+
+    /// Run at `rate` log ms per wallclock ms from `at` on, keeping the log
+    /// time extrapolated so far.
+    pub fn set_rate(&mut self, rate: u64, at: Instant) {
+        self.anchor_ms   = self.now_ms(at);
+        self.anchored_at = at;
+        self.rate        = rate;
+    }
+
+    /// True while log time only moves with events.
+    pub fn is_frozen(&self) -> bool {
+        self.rate == 0
+    }
+
+    /// Log time at `at`.
+    pub fn now_ms(&self, at: Instant) -> u64 {
+        let elapsed = at.saturating_duration_since(self.anchored_at).as_millis() as u64;
+        self.anchor_ms + elapsed * self.rate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn log_clock_scales_and_freezes() {
+        let t0 = Instant::now();
+        let mut clock = LogClock::new(t0);
+        clock.event(100_000, t0);
+        assert_eq!(clock.now_ms(t0 + Duration::from_secs(2)), 102_000);
+
+        // 5x from two seconds in: the first two seconds still count once.
+        clock.set_rate(5, t0 + Duration::from_secs(2));
+        assert_eq!(clock.now_ms(t0 + Duration::from_secs(3)), 107_000);
+
+        // Paused: the wallclock moves on, log time does not.
+        clock.set_rate(0, t0 + Duration::from_secs(3));
+        assert!(clock.is_frozen());
+        assert_eq!(clock.now_ms(t0 + Duration::from_secs(60)), 107_000);
+
+        // The next event re-anchors.
+        clock.event(107_500, t0 + Duration::from_secs(61));
+        assert_eq!(clock.now_ms(t0 + Duration::from_secs(70)), 107_500);
+    }
+
+    #[test]
+    fn unknown_until_first_sample() {
+        let clock = ClockAligner::new();
+        assert!(clock.offset_ms().is_none());
+        assert!(clock.to_wallclock(1_000).is_none());
+    }
+
+    #[test]
+    fn keeps_smallest_write_latency() {
+        let clock = ClockAligner::new();
+        clock.observe(10_000, 1_700_000_010_400); // 400 ms write delay
+        clock.observe(20_000, 1_700_000_020_050); //  50 ms write delay
+        clock.observe(30_000, 1_700_000_031_000); // 1 s buffered write — ignored
+        assert_eq!(clock.to_wallclock(40_000), Some(1_700_000_040_050));
+    }
+
+    #[test]
+    fn accepts_midnight_rollover() {
+        let clock = ClockAligner::new();
+        // 23:59:59.000 log time
+        clock.observe(86_399_000, 1_700_000_000_000);
+        // 00:00:01.000 log time, two seconds later on the wallclock
+        clock.observe(1_000, 1_700_000_002_000);
+        assert_eq!(clock.to_wallclock(2_000), Some(1_700_000_003_000));
+    }
+
+    #[test]
+    fn rebase_overrides_observed_samples() {
+        let clock = ClockAligner::new();
+        clock.observe(10_000, 1_700_000_010_050);
+        // Replaying last night: a much larger offset still replaces it.
+        clock.rebase(10_000, 1_700_090_000_000);
+        assert_eq!(clock.to_wallclock(11_000), Some(1_700_090_001_000));
+    }
+}
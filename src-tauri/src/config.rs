@@ -7,11 +7,17 @@
 /// First-run: if wow_log_path is empty, the settings window shows a wizard
 /// that calls detect_wow_path and/or opens a directory picker.
 ///
+/// Files written by older builds are upgraded in place on load by ordered
+/// migration steps keyed on the `version` field (see Migrations below).
+///
 /// NOTE: wow_log_path stores the **Logs directory** (e.g. `..\World of Warcraft\_retail_\Logs`),
 /// NOT a specific file. The tailer resolves the newest WoWCombatLog*.txt at runtime.
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use crate::units::NumberFormat;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
 use tauri::Manager; // required for AppHandle::path() and app_config_dir()
 
 // ---------------------------------------------------------------------------
@@ -39,7 +45,7 @@ pub struct AudioCue {
     pub sound_path: String,
 }
 
-fn bool_true() -> bool { true }
+pub(crate) fn bool_true() -> bool { true }
 fn default_volume() -> f32 { 0.7 }
 
 fn default_audio_cues() -> Vec<AudioCue> {
@@ -50,6 +56,43 @@ fn default_audio_cues() -> Vec<AudioCue> {
     ]
 }
 
+// ---------------------------------------------------------------------------
+// Hardware cues
+// ---------------------------------------------------------------------------
+
+/// How a hardware cue reaches its device.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CueTransport {
+    /// Plain UDP datagram carrying `message`.
+    #[default]
+    Udp,
+    /// OSC message to the address in `message`, with the advice title as
+    /// its one string argument.
+    Osc,
+    /// `message` plus a newline written to a serial device.
+    Serial,
+}
+
+/// Per-severity cue for external hardware (keyboard LEDs, Stream Deck,
+/// light strips) — a non-audio, non-overlay signal.  See hardware_cues.rs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HardwareCue {
+    /// Severity this cue applies to: "good", "warn", or "bad"
+    pub severity: String,
+    #[serde(default = "bool_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub transport: CueTransport,
+    /// "host:port" for udp/osc; device for serial ("COM3", "/dev/ttyACM0")
+    #[serde(default)]
+    pub target: String,
+    /// Payload text for udp/serial ({severity}, {title} and {key} are
+    /// filled in); the address ("/keyboard/flash") for osc
+    #[serde(default)]
+    pub message: String,
+}
+
 // ---------------------------------------------------------------------------
 // Hotkeys
 // ---------------------------------------------------------------------------
@@ -60,12 +103,79 @@ pub struct HotkeyConfig {
     /// Key combo string for toggling overlay visibility (e.g. "Ctrl+Shift+O").
     /// Empty string = no hotkey registered.
     #[serde(default)]
-    pub toggle_overlay: String,
+    pub toggle_overlay:    String,
+    /// Key combo that brings back the last pull debrief (`show_last_debrief`).
+    #[serde(default)]
+    pub show_last_debrief: String,
 }
 
 impl Default for HotkeyConfig {
     fn default() -> Self {
-        Self { toggle_overlay: String::new() }
+        Self { toggle_overlay: String::new(), show_last_debrief: String::new() }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Countdowns
+// ---------------------------------------------------------------------------
+
+/// "3, 2, 1" before scheduled boss abilities (countdown.rs).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CountdownConfig {
+    #[serde(default = "bool_true")]
+    pub enabled:    bool,
+    /// Number the countdown starts at (1–5).
+    #[serde(default = "default_countdown_from")]
+    pub from_s:     u32,
+    /// Folder with one sound per number (`1.ogg`, `2.wav`, …); empty = the
+    /// built-in tones.
+    #[serde(default)]
+    pub voice_pack: PathBuf,
+}
+
+fn default_countdown_from() -> u32 { 3 }
+
+impl Default for CountdownConfig {
+    fn default() -> Self {
+        Self { enabled: true, from_s: default_countdown_from(), voice_pack: PathBuf::new() }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Advice cooldowns
+// ---------------------------------------------------------------------------
+
+/// How long the same advice stays quiet after firing, per severity, with
+/// optional per-rule overrides (engine `can_fire`).  0 = no hold-back.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AdviceCooldowns {
+    #[serde(default = "default_bad_cooldown_s")]
+    pub bad_s:  u32,
+    #[serde(default = "default_warn_cooldown_s")]
+    pub warn_s: u32,
+    #[serde(default = "default_good_cooldown_s")]
+    pub good_s: u32,
+    /// Rule name (rules::RULES) → seconds, used instead of the severity's.
+    #[serde(default)]
+    pub rules:  BTreeMap<String, u32>,
+}
+
+/// Longest allowed advice cooldown — anything longer silences a rule for
+/// the whole pull, which `disabled_rules` does more honestly.
+const MAX_ADVICE_COOLDOWN_S: u32 = 300;
+
+fn default_bad_cooldown_s()  -> u32 { 8 }
+fn default_warn_cooldown_s() -> u32 { 12 }
+fn default_good_cooldown_s() -> u32 { 20 }
+
+impl Default for AdviceCooldowns {
+    fn default() -> Self {
+        Self {
+            bad_s:  default_bad_cooldown_s(),
+            warn_s: default_warn_cooldown_s(),
+            good_s: default_good_cooldown_s(),
+            rules:  BTreeMap::new(),
+        }
     }
 }
 
@@ -77,7 +187,8 @@ impl Default for HotkeyConfig {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PanelPosition {
     /// Panel identifier — matches the known panel IDs in the overlay.
-    /// Known IDs: "now_feed", "pull_clock", "timeline", "stat_widgets"
+    /// Known IDs: "now_feed", "pull_clock", "timeline", "stat_widgets",
+    /// "readiness"
     pub id:      String,
     pub x:       i32,
     pub y:       i32,
@@ -95,6 +206,13 @@ fn default_scale()   -> f32 { 1.0 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
+    /// Shape of this file — see `migrate`.  Missing in files written before
+    /// versioning, which read as 0.  Written as `CONFIG_VERSION`, except that
+    /// a file from a newer build keeps its version so that build does not
+    /// migrate it again.
+    #[serde(default)]
+    pub version: u32,
+
     /// Absolute path to the WoW Logs directory (e.g. `..\World of Warcraft\_retail_\Logs`).
     /// The tailer picks the newest WoWCombatLog*.txt in this directory at runtime,
     /// and switches automatically when WoW creates a new timestamped log file.
@@ -138,22 +256,222 @@ pub struct AppConfig {
     /// Empty = auto-detect from the addon identity on first combat.
     #[serde(default)]
     pub selected_spec: String,
+
+    /// Align advice/snapshot timestamps to the wallclock using the log file's
+    /// write times (see clock.rs).  When false, or before the first sample,
+    /// the system time at processing is used instead.
+    #[serde(default = "bool_true")]
+    pub clock_alignment: bool,
+
+    /// Seconds of warning before scheduled unavoidable damage from the
+    /// encounter timeline (spike_forecast rule).  0 disables the pre-warning.
+    #[serde(default = "default_spike_forecast_lead_s")]
+    pub spike_forecast_lead_s: u32,
+
+    /// Audio countdown before scheduled raid-wide damage and tank busters.
+    #[serde(default)]
+    pub countdown: CountdownConfig,
+
+    /// Seconds a major cooldown may sit ready mid-pull before the
+    /// cooldown_available rule calls it out.  0 disables the rule.
+    #[serde(default = "default_cd_unused_alert_s")]
+    pub cd_unused_alert_s: u32,
+
+    /// Percent of generated rage/energy/focus/holy power/… the player may
+    /// lose to overcapping in a pull before the resource_waste rule calls
+    /// it out.  0 disables the rule.
+    #[serde(default = "default_resource_waste_pct")]
+    pub resource_waste_pct: u32,
+
+    /// A hit on the coached player with no defensive up counts as a spike
+    /// (defensive_missing rule) when it deals at least this much damage…
+    /// 0 disables the flat threshold.
+    #[serde(default = "default_defensive_missing_damage")]
+    pub defensive_missing_damage: u64,
+
+    /// …or is at least this percent of the damage they took over the last
+    /// 10 seconds.  0 disables the share threshold.
+    #[serde(default = "default_defensive_missing_pct")]
+    pub defensive_missing_pct: u32,
+
+    /// Number/duration formatting used in advice text ("55k" vs "55 000",
+    /// decimal comma, …).  Applied centrally via `units::NumberFormat`.
+    #[serde(default)]
+    pub number_format: NumberFormat,
+
+    /// User-provided cloud folder (Dropbox, OneDrive, …) for settings sync
+    /// between machines.  Empty = sync disabled.  See profile.rs.
+    #[serde(default)]
+    pub profile_sync_dir: PathBuf,
+
+    /// Folder for the live spectate JSON files read by second-screen
+    /// dashboards.  Empty = off.  See spectate.rs.
+    #[serde(default)]
+    pub spectate_dir: PathBuf,
+
+    /// UDP/OSC/serial messages sent to external hardware when advice of a
+    /// severity fires.  Empty = off.  See hardware_cues.rs.
+    #[serde(default)]
+    pub hardware_cues: Vec<HardwareCue>,
+
+    /// Silence all audio cues without losing the per-severity settings.
+    /// Also toggled in-game with `/cc mute` / `/cc unmute` (addon_bridge.rs).
+    #[serde(default)]
+    pub audio_muted: bool,
+
+    /// `requestId` of the last in-game settings request applied, so a request
+    /// still sitting in SavedVariables is not applied twice.
+    #[serde(default)]
+    pub addon_request_id: u64,
+
+    /// Copy a one-line pull summary to the clipboard at every pull end, for
+    /// pasting into raid chat or Discord.
+    #[serde(default)]
+    pub pull_summary_clipboard: bool,
+
+    /// Squad mode (opt-in): pull debriefs include a damage-taken leaderboard
+    /// for the whole party, not just the coached player.
+    #[serde(default)]
+    pub squad_mode: bool,
+
+    /// Advice debug capture (opt-in): every advice stores the raw log lines
+    /// and coaching state behind it, for reporting a wrong advice
+    /// (advice_debug.rs).  Costs a copy of every log line read.
+    #[serde(default)]
+    pub advice_debug: bool,
+
+    /// Raid-lead mode (opt-in): avoidable hits on the whole group are called
+    /// out in batches, and pull debriefs carry a per-member summary that
+    /// replaces the one-line clipboard summary (rules/raid_callouts.rs).
+    #[serde(default)]
+    pub raid_lead_mode: bool,
+
+    /// Group coaching mode (opt-in, for raid leaders): every player in the
+    /// group gets the avoidable-damage and death coaching, each advice
+    /// tagged with the player it is about (rules/group_coaching.rs).
+    #[serde(default)]
+    pub group_coaching: bool,
+
+    /// Count damage from the player's own side — self-inflicted (Burning
+    /// Rush) or from a party player (reflects) — as avoidable.  Off by
+    /// default: such hits only show in the debrief's friendly-fire line.
+    #[serde(default)]
+    pub avoidable_friendly_fire: bool,
+
+    /// Repeat hold-back of advice, per severity and rule.
+    #[serde(default)]
+    pub advice_cooldowns: AdviceCooldowns,
+
+    /// Fetch signed encounter files from the community data repository for
+    /// bosses that have none locally (encounter_download.rs).  Off until the
+    /// data repository is published.
+    #[serde(default)]
+    pub encounter_downloads: bool,
+
+    /// The user's own Warcraft Logs API client (warcraftlogs.com/api/clients),
+    /// used by the quick-setup character import (wcl_import.rs).  Empty = off.
+    #[serde(default)]
+    pub wcl_client_id: String,
+    #[serde(default)]
+    pub wcl_client_secret: String,
+
+    /// Directory holding the session database.  Empty = the platform app-data
+    /// directory.  Changed via the `move_data_dir` command, which migrates the
+    /// existing database.
+    #[serde(default)]
+    pub data_dir: PathBuf,
+
+    /// Directory for the rolling app log.  Empty = `<APPDATA>/<identifier>/logs`.
+    /// Read once at startup, so a change applies on the next launch.
+    #[serde(default)]
+    pub log_dir: PathBuf,
+
+    /// App version that last ran on this machine — the "what's new"
+    /// notification fires once when it differs (changelog.rs).
+    #[serde(default)]
+    pub last_seen_version: String,
+
+    /// Outdated addon version the user was last told to update — the
+    /// advisory fires once per version (addon_bridge.rs).
+    #[serde(default)]
+    pub addon_outdated_notice: String,
+
+    /// Newer game build (major.minor) the user was last told the coaching
+    /// data predates — the advisory fires once per build (data_version.rs).
+    #[serde(default)]
+    pub stale_data_notice: String,
+
+    /// Coaching overrides per encounter name, applied by the engine between
+    /// ENCOUNTER_START and ENCOUNTER_END.  Usually set from a strategy
+    /// preset (presets.rs).
+    #[serde(default)]
+    pub encounter_settings: BTreeMap<String, EncounterSettings>,
+
+    /// Coaching defaults per character, keyed by player GUID.  Generated
+    /// from the character's role the first time it is identified
+    /// (role_defaults.rs); edits are kept, a removed entry is regenerated.
+    #[serde(default)]
+    pub character_profiles: BTreeMap<String, CharacterProfile>,
+}
+
+/// Coaching overrides for one encounter.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EncounterSettings {
+    /// Strategy preset these settings came from; empty when set by hand.
+    #[serde(default)]
+    pub preset_id:        String,
+    /// Replaces `AppConfig.intensity` during the encounter.
+    #[serde(default)]
+    pub intensity:        Option<u8>,
+    /// Rule names (`rules::RULES`) that stay silent during the encounter.
+    #[serde(default)]
+    pub disabled_rules:   Vec<String>,
+    /// Enemy spell IDs the player is assigned to kick.  When set,
+    /// interrupt_miss only fires for these.
+    #[serde(default)]
+    pub kick_assignments: Vec<u32>,
+}
+
+/// Per-character coaching defaults, applied whenever that character is
+/// coached.  Encounter settings take precedence over the intensity; both
+/// lists of disabled rules apply.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CharacterProfile {
+    /// Character name, for whoever reads the file (the key is the GUID).
+    #[serde(default)]
+    pub name:           String,
+    /// Role the defaults were generated for.
+    #[serde(default)]
+    pub role:           String,
+    /// Replaces `AppConfig.intensity` for this character.
+    #[serde(default)]
+    pub intensity:      Option<u8>,
+    /// Rule names (`rules::RULES`) that stay silent for this character.
+    #[serde(default)]
+    pub disabled_rules: Vec<String>,
 }
 
 fn default_intensity() -> u8 { 3 }
+fn default_spike_forecast_lead_s() -> u32 { 5 }
+fn default_cd_unused_alert_s() -> u32 { 20 }
+fn default_resource_waste_pct() -> u32 { 10 }
+fn default_defensive_missing_damage() -> u64 { 40_000 }
+fn default_defensive_missing_pct() -> u32 { 60 }
 
-fn default_panel_positions() -> Vec<PanelPosition> {
+pub(crate) fn default_panel_positions() -> Vec<PanelPosition> {
     vec![
         PanelPosition { id: "pull_clock".to_owned(),   x: 20,  y: 20,  visible: true, opacity: 1.0, scale: 1.0 },
         PanelPosition { id: "now_feed".to_owned(),     x: 20,  y: 70,  visible: true, opacity: 1.0, scale: 1.0 },
         PanelPosition { id: "timeline".to_owned(),     x: 20,  y: 500, visible: true, opacity: 1.0, scale: 1.0 },
         PanelPosition { id: "stat_widgets".to_owned(), x: 20,  y: 670, visible: true, opacity: 1.0, scale: 1.0 },
+        PanelPosition { id: "readiness".to_owned(),    x: 20,  y: 300, visible: true, opacity: 1.0, scale: 1.0 },
     ]
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
+            version:         CONFIG_VERSION,
             wow_log_path:    PathBuf::new(),
             addon_sv_path:   PathBuf::new(),
             intensity:       default_intensity(),
@@ -164,6 +482,36 @@ impl Default for AppConfig {
             hotkeys:         HotkeyConfig::default(),
             overlay_visible: true,
             selected_spec:   String::new(),
+            clock_alignment: true,
+            spike_forecast_lead_s: default_spike_forecast_lead_s(),
+            countdown:       CountdownConfig::default(),
+            cd_unused_alert_s: default_cd_unused_alert_s(),
+            resource_waste_pct: default_resource_waste_pct(),
+            defensive_missing_damage: default_defensive_missing_damage(),
+            defensive_missing_pct: default_defensive_missing_pct(),
+            number_format:   NumberFormat::default(),
+            profile_sync_dir: PathBuf::new(),
+            spectate_dir:    PathBuf::new(),
+            hardware_cues:   Vec::new(),
+            audio_muted:     false,
+            addon_request_id: 0,
+            pull_summary_clipboard: false,
+            squad_mode:      false,
+            advice_debug:    false,
+            raid_lead_mode:  false,
+            group_coaching:  false,
+            avoidable_friendly_fire: false,
+            advice_cooldowns: AdviceCooldowns::default(),
+            encounter_downloads: false,
+            wcl_client_id:   String::new(),
+            wcl_client_secret: String::new(),
+            data_dir:        PathBuf::new(),
+            log_dir:         PathBuf::new(),
+            last_seen_version: String::new(),
+            addon_outdated_notice: String::new(),
+            stale_data_notice: String::new(),
+            encounter_settings: BTreeMap::new(),
+            character_profiles: BTreeMap::new(),
         }
     }
 }
@@ -172,23 +520,231 @@ impl Default for AppConfig {
 // Load / save
 // ---------------------------------------------------------------------------
 
+/// Load `config.toml`, upgrading an older file in place first (the original
+/// is kept as `config.toml.v<N>.bak`).
 pub fn load_or_default(config_dir: &Path) -> Result<AppConfig> {
+    load_locked(config_dir, &mut lock_saves())
+}
+
+fn load_locked(config_dir: &Path, last: &mut String) -> Result<AppConfig> {
     let path = config_dir.join("config.toml");
     if path.exists() {
         let raw = std::fs::read_to_string(&path)?;
-        let cfg: AppConfig = toml::from_str(&raw)
-            .map_err(|e| anyhow::anyhow!("Config parse error: {}", e))?;
+        let (cfg, from) = parse(&raw)?;
+        if let Some(from) = from {
+            let backup = config_dir.join(format!("config.toml.v{}.bak", from));
+            if !backup.exists() {
+                std::fs::write(&backup, &raw)?;
+            }
+            save_locked(&cfg, config_dir, last)?;
+            tracing::info!("Config upgraded from version {} to {}", from, CONFIG_VERSION);
+        }
         Ok(cfg)
     } else {
         Ok(AppConfig::default())
     }
 }
 
+/// Parse the text of a config file, running migrations in memory.  Returns
+/// the version it was upgraded from, if any; nothing is written.
+pub fn parse(raw: &str) -> Result<(AppConfig, Option<u32>)> {
+    let mut table: toml::Table = toml::from_str(raw)
+        .map_err(|e| anyhow::anyhow!("Config parse error: {}", e))?;
+    let from = migrate(&mut table);
+    let cfg: AppConfig = table.try_into()
+        .map_err(|e| anyhow::anyhow!("Config parse error: {}", e))?;
+    Ok((cfg, from))
+}
+
+/// Text of the last `config.toml` this process wrote, so the file watcher
+/// (config_watch.rs) can tell the app's own saves from hand edits.  Held
+/// for every load and save, so writes from background threads (addon
+/// bridge) and the settings window never interleave.
+static LAST_SAVED: Mutex<String> = Mutex::new(String::new());
+
+fn lock_saves() -> MutexGuard<'static, String> {
+    LAST_SAVED.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
 pub fn save(config: &AppConfig, config_dir: &Path) -> Result<()> {
+    save_locked(config, config_dir, &mut lock_saves())
+}
+
+/// Load the config, apply `change` and save it with no other save in
+/// between.  Returns the saved config.
+pub fn update(config_dir: &Path, change: impl FnOnce(&mut AppConfig)) -> Result<AppConfig> {
+    let mut last = lock_saves();
+    let mut cfg = load_locked(config_dir, &mut last)?;
+    change(&mut cfg);
+    save_locked(&cfg, config_dir, &mut last)?;
+    Ok(cfg)
+}
+
+fn save_locked(config: &AppConfig, config_dir: &Path, last: &mut String) -> Result<()> {
     std::fs::create_dir_all(config_dir)?;
-    let raw = toml::to_string_pretty(config)
+    let config = AppConfig { version: config.version.max(CONFIG_VERSION), ..config.clone() };
+    let raw = toml::to_string_pretty(&config)
         .map_err(|e| anyhow::anyhow!("Config serialize error: {}", e))?;
-    std::fs::write(config_dir.join("config.toml"), raw)?;
+    std::fs::write(config_dir.join("config.toml"), &raw)?;
+    *last = raw;
+    Ok(())
+}
+
+/// True when `raw` is exactly what the last `save` wrote.
+pub fn is_own_write(raw: &str) -> bool {
+    LAST_SAVED.lock().is_ok_and(|last| *last == raw)
+}
+
+// ---------------------------------------------------------------------------
+// Validation
+// ---------------------------------------------------------------------------
+
+impl AppConfig {
+    /// Reject values the settings window would never write — used before a
+    /// hand-edited file is applied.  Lists every problem, not just the first.
+    pub fn validate(&self) -> Result<()> {
+        let mut problems = Vec::new();
+        if !(1..=5).contains(&self.intensity) {
+            problems.push(format!("intensity {} is outside 1–5", self.intensity));
+        }
+        for panel in &self.panel_positions {
+            if !(0.0..=1.0).contains(&panel.opacity) {
+                problems.push(format!("panel {}: opacity {} is outside 0.0–1.0", panel.id, panel.opacity));
+            }
+            if !(0.5..=2.0).contains(&panel.scale) {
+                problems.push(format!("panel {}: scale {} is outside 0.5–2.0", panel.id, panel.scale));
+            }
+        }
+        for cue in &self.audio_cues {
+            if !(0.0..=1.0).contains(&cue.volume) {
+                problems.push(format!("audio cue {}: volume {} is outside 0.0–1.0", cue.severity, cue.volume));
+            }
+        }
+        // A cue without a target is still being set up in the settings window.
+        for cue in self.hardware_cues.iter().filter(|c| !c.target.trim().is_empty()) {
+            if let Err(e) = crate::hardware_cues::check(cue) {
+                problems.push(format!("hardware cue {}: {}", cue.severity, e));
+            }
+        }
+        if !(1..=5).contains(&self.countdown.from_s) {
+            problems.push(format!("countdown from_s {} is outside 1–5", self.countdown.from_s));
+        }
+        for (name, dir) in [
+            ("wow_log_path", &self.wow_log_path),
+            ("countdown.voice_pack", &self.countdown.voice_pack),
+            ("data_dir", &self.data_dir),
+            ("log_dir", &self.log_dir),
+        ] {
+            if !dir.as_os_str().is_empty() && !dir.is_absolute() {
+                problems.push(format!("{} {} is not an absolute path", name, dir.display()));
+            }
+        }
+        let cooldowns = &self.advice_cooldowns;
+        let severity_cooldowns = [("bad_s", cooldowns.bad_s), ("warn_s", cooldowns.warn_s), ("good_s", cooldowns.good_s)];
+        let rule_cooldowns = cooldowns.rules.iter().map(|(rule, secs)| (rule.as_str(), *secs));
+        for (name, secs) in severity_cooldowns.into_iter().chain(rule_cooldowns) {
+            if secs > MAX_ADVICE_COOLDOWN_S {
+                problems.push(format!("advice_cooldowns.{}: {}s is over {}s", name, secs, MAX_ADVICE_COOLDOWN_S));
+            }
+        }
+        for rule in cooldowns.rules.keys() {
+            if !crate::rules::RULES.iter().any(|(name, _)| name == rule) {
+                problems.push(format!("advice_cooldowns: unknown rule \"{}\"", rule));
+            }
+        }
+        let overrides = self.encounter_settings.iter()
+            .map(|(encounter, s)| (encounter, s.intensity, &s.disabled_rules))
+            .chain(self.character_profiles.iter().map(|(guid, p)| (guid, p.intensity, &p.disabled_rules)));
+        for (owner, intensity, disabled_rules) in overrides {
+            if let Some(intensity) = intensity.filter(|i| !(1..=5).contains(i)) {
+                problems.push(format!("{}: intensity {} is outside 1–5", owner, intensity));
+            }
+            for rule in disabled_rules {
+                if !crate::rules::RULES.iter().any(|(name, _)| name == rule) {
+                    problems.push(format!("{}: unknown rule \"{}\"", owner, rule));
+                }
+            }
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(problems.join("; ")))
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Migrations
+// ---------------------------------------------------------------------------
+
+/// One upgrade step on the raw TOML table: `MIGRATIONS[n]` turns a version-n
+/// file into version n + 1.  Steps run before deserialization, so a renamed
+/// or reshaped field can be carried over instead of silently defaulting.
+type Migration = fn(&mut toml::Table);
+
+/// Append-only: never edit or reorder a shipped step.
+const MIGRATIONS: &[Migration] = &[
+    logs_file_to_dir,
+];
+
+/// Version written by this build.
+pub const CONFIG_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// Bring a raw config table up to `CONFIG_VERSION`.  Returns the version it
+/// started at when anything ran.  Files from a newer build are left alone —
+/// unknown fields are ignored and missing ones default.
+pub fn migrate(table: &mut toml::Table) -> Option<u32> {
+    let from = table.get("version").and_then(toml::Value::as_integer).unwrap_or(0).max(0) as u32;
+    if from > CONFIG_VERSION {
+        tracing::warn!("Config version {} is newer than this build ({}) — loading as is", from, CONFIG_VERSION);
+        return None;
+    }
+    if from == CONFIG_VERSION {
+        return None;
+    }
+    for step in &MIGRATIONS[from as usize..] {
+        step(table);
+    }
+    table.insert("version".to_owned(), toml::Value::Integer(CONFIG_VERSION as i64));
+    Some(from)
+}
+
+/// v0 → v1: early builds stored the combat log file itself in `wow_log_path`;
+/// it now holds the Logs directory.
+fn logs_file_to_dir(table: &mut toml::Table) {
+    let Some(toml::Value::String(path)) = table.get_mut("wow_log_path") else { return };
+    let file = Path::new(path.as_str());
+    if file.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("txt")) {
+        if let Some(dir) = file.parent() {
+            *path = dir.to_string_lossy().into_owned();
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Storage locations
+// ---------------------------------------------------------------------------
+
+/// File name of the session database inside the data directory.
+pub const DB_FILE_NAME: &str = "sessions.sqlite";
+
+/// `configured` when set, otherwise the platform default.
+pub fn resolve_dir(configured: &Path, default: PathBuf) -> PathBuf {
+    if configured.as_os_str().is_empty() { default } else { configured.to_path_buf() }
+}
+
+/// Create `dir` if needed and prove it is writable with a probe file —
+/// catches read-only drives and permission problems before anything moves.
+pub fn ensure_writable_dir(dir: &Path) -> Result<()> {
+    if !dir.is_absolute() {
+        anyhow::bail!("{} is not an absolute path", dir.display());
+    }
+    std::fs::create_dir_all(dir)
+        .map_err(|e| anyhow::anyhow!("cannot create {}: {}", dir.display(), e))?;
+    let probe = dir.join(".combatledger-write-test");
+    std::fs::write(&probe, b"ok")
+        .map_err(|e| anyhow::anyhow!("{} is not writable: {}", dir.display(), e))?;
+    let _ = std::fs::remove_file(&probe);
     Ok(())
 }
 
@@ -398,7 +954,13 @@ pub fn apply_spec(app_handle: tauri::AppHandle, spec_key: String) -> Result<AppC
         .app_config_dir()
         .map_err(|e| e.to_string())?;
     let mut cfg = load_or_default(&dir).map_err(|e| e.to_string())?;
+    select_spec(&mut cfg, spec_key)?;
+    save(&cfg, &dir).map_err(|e| e.to_string())?;
+    Ok(cfg)
+}
 
+/// Set `selected_spec` and `major_cds` from a spec key ("" clears both).
+pub fn select_spec(cfg: &mut AppConfig, spec_key: String) -> Result<(), String> {
     if spec_key.is_empty() {
         cfg.selected_spec = String::new();
         cfg.major_cds     = Vec::new();
@@ -412,9 +974,7 @@ pub fn apply_spec(app_handle: tauri::AppHandle, spec_key: String) -> Result<AppC
             cfg.selected_spec, cfg.major_cds.len()
         );
     }
-
-    save(&cfg, &dir).map_err(|e| e.to_string())?;
-    Ok(cfg)
+    Ok(())
 }
 
 // ---------------------------------------------------------------------------
@@ -459,6 +1019,22 @@ pub fn detect_addon_sv_path(logs_dir: &Path) -> Option<PathBuf> {
     None
 }
 
+/// WoW account folder that holds the addon's SavedVariables file, i.e.
+/// `<ACCOUNT>` in `…/WTF/Account/<ACCOUNT>/SavedVariables/CombatCoach.lua`.
+/// Every character on the account shares it, so sessions store it to link
+/// alts (mechanic_stats.rs).
+pub fn account_of(sv_path: &Path) -> Option<String> {
+    let saved_variables = sv_path.parent()?;
+    if saved_variables.file_name()? != "SavedVariables" {
+        return None;
+    }
+    let account = saved_variables.parent()?;
+    if account.parent()?.file_name()? != "Account" {
+        return None;
+    }
+    Some(account.file_name()?.to_string_lossy().into_owned())
+}
+
 /// Tauri command: auto-detect the CombatCoach.lua path from the configured
 /// Logs directory.  Returns the detected path as a string, or null if not found.
 #[tauri::command]
@@ -537,6 +1113,99 @@ mod tests {
         assert_eq!(loaded.major_cds,    vec![31884, 642]);
     }
 
+    #[test]
+    fn concurrent_updates_are_not_lost() {
+        let dir = tempdir().unwrap();
+        save(&AppConfig::default(), dir.path()).unwrap();
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for _ in 0..25 {
+                        update(dir.path(), |cfg| cfg.addon_request_id += 1).unwrap();
+                    }
+                });
+            }
+        });
+        assert_eq!(load_or_default(dir.path()).unwrap().addon_request_id, 100);
+    }
+
+    #[test]
+    fn migrates_old_config_in_place() {
+        let dir = tempdir().unwrap();
+        let old = "wow_log_path = \"C:/WoW/_retail_/Logs/WoWCombatLog.txt\"\nintensity = 4\n";
+        std::fs::write(dir.path().join("config.toml"), old).unwrap();
+
+        let cfg = load_or_default(dir.path()).unwrap();
+        assert_eq!(cfg.version, CONFIG_VERSION);
+        assert_eq!(cfg.wow_log_path, PathBuf::from("C:/WoW/_retail_/Logs"));
+        assert_eq!(cfg.intensity, 4);
+
+        // Upgraded on disk, original kept.
+        let raw = std::fs::read_to_string(dir.path().join("config.toml")).unwrap();
+        assert!(raw.contains(&format!("version = {}", CONFIG_VERSION)));
+        let backup = std::fs::read_to_string(dir.path().join("config.toml.v0.bak")).unwrap();
+        assert_eq!(backup, old);
+    }
+
+    #[test]
+    fn migrate_skips_current_and_newer() {
+        let mut table: toml::Table = toml::from_str("wow_log_path = \"C:/Logs\"").unwrap();
+        assert_eq!(migrate(&mut table), Some(0));
+        assert_eq!(table["wow_log_path"].as_str(), Some("C:/Logs"));
+        assert_eq!(migrate(&mut table), None);
+
+        let mut newer: toml::Table = toml::from_str("version = 999").unwrap();
+        assert_eq!(migrate(&mut newer), None);
+        assert_eq!(newer["version"].as_integer(), Some(999));
+    }
+
+    #[test]
+    fn saving_keeps_a_newer_version() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("config.toml"), "version = 999
+intensity = 4
+").unwrap();
+        update(dir.path(), |cfg| cfg.intensity = 2).unwrap();
+        let cfg = load_or_default(dir.path()).unwrap();
+        assert_eq!((cfg.version, cfg.intensity), (999, 2));
+
+        save(&AppConfig { version: 0, ..cfg }, dir.path()).unwrap();
+        assert_eq!(load_or_default(dir.path()).unwrap().version, CONFIG_VERSION);
+    }
+
+    #[test]
+    fn validate_lists_every_problem() {
+        assert!(AppConfig::default().validate().is_ok());
+
+        let mut cfg = AppConfig { intensity: 9, ..AppConfig::default() };
+        cfg.panel_positions[0].scale = 4.0;
+        cfg.encounter_settings.insert("Sikran".to_owned(), EncounterSettings {
+            disabled_rules: vec!["gcd_gap".to_owned(), "no_such_rule".to_owned()],
+            ..EncounterSettings::default()
+        });
+        cfg.advice_cooldowns.warn_s = 900;
+        cfg.advice_cooldowns.rules.insert("gcd_gap".to_owned(), 30);
+        cfg.advice_cooldowns.rules.insert("gcd_gapp".to_owned(), 30);
+        let err = cfg.validate().unwrap_err().to_string();
+        assert!(err.contains("advice_cooldowns.warn_s: 900s"), "{}", err);
+        assert!(err.contains("advice_cooldowns: unknown rule \"gcd_gapp\""), "{}", err);
+        assert!(err.contains("intensity 9"), "{}", err);
+        assert!(err.contains("pull_clock: scale 4"), "{}", err);
+        assert!(err.contains("unknown rule \"no_such_rule\""), "{}", err);
+        assert!(!err.contains("\"gcd_gap\""), "{}", err);
+    }
+
+    #[test]
+    fn parse_migrates_without_writing() {
+        let dir = tempdir().unwrap();
+        let old = "wow_log_path = \"C:/WoW/_retail_/Logs/WoWCombatLog.txt\"\n";
+        let (cfg, from) = parse(old).unwrap();
+        assert_eq!(from, Some(0));
+        assert_eq!(cfg.wow_log_path, PathBuf::from("C:/WoW/_retail_/Logs"));
+        assert!(std::fs::read_dir(dir.path()).unwrap().next().is_none());
+        assert!(parse("intensity = \"high\"").is_err());
+    }
+
     #[test]
     fn returns_default_when_missing() {
         let dir = tempdir().unwrap();
@@ -545,6 +1214,16 @@ mod tests {
         assert!(cfg.wow_log_path.as_os_str().is_empty());
     }
 
+    #[test]
+    fn ensure_writable_dir_creates_and_rejects_relative() {
+        let dir = tempdir().unwrap();
+        let nested = dir.path().join("a").join("b");
+        ensure_writable_dir(&nested).unwrap();
+        assert!(nested.is_dir());
+        assert!(std::fs::read_dir(&nested).unwrap().next().is_none(), "probe removed");
+        assert!(ensure_writable_dir(Path::new("relative/dir")).is_err());
+    }
+
     #[test]
     fn find_latest_log_picks_newest() {
         let dir = tempdir().unwrap();
@@ -656,4 +1335,12 @@ mod tests {
         // No WTF directory at all
         assert!(scan_wtf_characters(&logs_dir).is_empty());
     }
+
+    #[test]
+    fn account_of_reads_the_account_folder() {
+        let sv = Path::new("/wow/_retail_/WTF/Account/12345678#1/SavedVariables/CombatCoach.lua");
+        assert_eq!(account_of(sv).as_deref(), Some("12345678#1"));
+        assert_eq!(account_of(Path::new("/tmp/CombatCoach.lua")), None);
+        assert_eq!(account_of(Path::new("")), None);
+    }
 }
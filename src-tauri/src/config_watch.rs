@@ -0,0 +1,113 @@
+/// Hot reload of hand edits to `config.toml`.
+///
+/// Some settings (thresholds, per-encounter rule lists) are easiest to tweak
+/// in a text editor.  A watcher thread follows the config directory and,
+/// once the file has been quiet for `DEBOUNCE`, parses and validates it
+/// (`config::parse`, `AppConfig::validate`).  A valid file is handed to the
+/// running pipeline through the same channel the settings window uses; an
+/// invalid one is reported and the running config stays in place until the
+/// file is fixed.
+///
+/// Editors save in several steps (truncate + write, or write a temp file and
+/// rename it over the original), hence the debounce.  The app's own saves
+/// land in the same file and are skipped (`config::is_own_write`).
+use anyhow::Result;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::PathBuf;
+use std::sync::mpsc::{self as std_mpsc, RecvTimeoutError};
+use std::time::Duration;
+
+use crate::config::{self, AppConfig};
+
+const CONFIG_FILE: &str = "config.toml";
+/// Quiet time after the last change before the file is read.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Watch `config_dir` until the app exits.  `on_reload` gets every valid
+/// hand edit, `on_rejected` the reason an edit was not applied.
+pub fn run(config_dir: PathBuf, on_reload: impl Fn(AppConfig), on_rejected: impl Fn(String)) {
+    let (fs_tx, fs_rx) = std_mpsc::channel::<notify::Result<Event>>();
+    let mut watcher = match RecommendedWatcher::new(fs_tx, notify::Config::default()) {
+        Ok(w)  => w,
+        Err(e) => { tracing::warn!("Config watch: cannot create watcher: {}", e); return; }
+    };
+    if let Err(e) = watcher.watch(&config_dir, RecursiveMode::NonRecursive) {
+        tracing::warn!("Config watch: cannot watch {:?}: {}", config_dir, e);
+        return;
+    }
+
+    let path = config_dir.join(CONFIG_FILE);
+    let mut last_raw = std::fs::read_to_string(&path).unwrap_or_default();
+    loop {
+        match fs_rx.recv() {
+            Ok(Ok(event)) if touches_config(&event) => {}
+            Ok(_)  => continue,
+            Err(_) => return,
+        }
+        // Wait for the editor to finish writing.
+        loop {
+            match fs_rx.recv_timeout(DEBOUNCE) {
+                Ok(_)                               => continue,
+                Err(RecvTimeoutError::Timeout)      => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+
+        // Missing mid-rename, or deleted: keep the running config.
+        let Ok(raw) = std::fs::read_to_string(&path) else { continue };
+        if raw == last_raw || config::is_own_write(&raw) {
+            last_raw = raw;
+            continue;
+        }
+        last_raw = raw;
+
+        match load(&last_raw) {
+            Ok(cfg) => {
+                tracing::info!("Config watch: applied hand edit to {:?}", path);
+                on_reload(cfg);
+            }
+            Err(e) => {
+                tracing::warn!("Config watch: edit to {:?} not applied: {}", path, e);
+                on_rejected(e.to_string());
+            }
+        }
+    }
+}
+
+/// A create, write or rename that involves `config.toml`.
+fn touches_config(event: &Event) -> bool {
+    matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+        && event.paths.iter().any(|p| p.file_name().is_some_and(|n| n == CONFIG_FILE))
+}
+
+/// Parse and validate the text of an edited config file.
+fn load(raw: &str) -> Result<AppConfig> {
+    let (cfg, _) = config::parse(raw)?;
+    cfg.validate()?;
+    Ok(cfg)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{CreateKind, ModifyKind, RemoveKind};
+
+    #[test]
+    fn only_config_writes_count() {
+        let event = |kind, file: &str| Event::new(kind).add_path(PathBuf::from("/cfg").join(file));
+        assert!(touches_config(&event(EventKind::Modify(ModifyKind::Any), CONFIG_FILE)));
+        assert!(touches_config(&event(EventKind::Create(CreateKind::File), CONFIG_FILE)));
+        assert!(!touches_config(&event(EventKind::Remove(RemoveKind::File), CONFIG_FILE)));
+        assert!(!touches_config(&event(EventKind::Modify(ModifyKind::Any), "config.toml.v0.bak")));
+    }
+
+    #[test]
+    fn rejects_invalid_edits() {
+        assert_eq!(load("intensity = 5\n").unwrap().intensity, 5);
+        assert!(load("intensity = 0\n").unwrap_err().to_string().contains("intensity 0"));
+        assert!(load("intensity = \n").is_err());
+    }
+}
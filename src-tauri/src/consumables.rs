@@ -0,0 +1,100 @@
+/// Defensive consumables bundle — embedded at compile time from
+/// `data/consumables.toml`.
+///
+/// Maps the spell IDs a health potion or a healthstone triggers to their
+/// kind, and gives each kind its display name and shared cooldown.  The
+/// engine counts the coached player's uses per pull (state::ConsumableTracker);
+/// the death recap names the ones that were ready but not used.
+///
+/// Combat potions are listed separately: they are not defensives, only
+/// looked for in the pre-pull check of encounter pulls.
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+const CONSUMABLES_TOML: &str = include_str!("../../data/consumables.toml");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConsumableKind {
+    Potion,
+    Healthstone,
+}
+
+impl ConsumableKind {
+    pub const ALL: [ConsumableKind; 2] = [ConsumableKind::Potion, ConsumableKind::Healthstone];
+}
+
+#[derive(Deserialize)]
+struct KindInfo {
+    name:       String,
+    cooldown_s: u64,
+}
+
+#[derive(Deserialize)]
+struct TomlFile {
+    kinds:          HashMap<ConsumableKind, KindInfo>,
+    spells:         HashMap<String, ConsumableKind>,
+    /// Spell ID -> potion name (names are for the reader).
+    #[serde(default)]
+    combat_potions: HashMap<String, String>,
+}
+
+struct Bundle {
+    kinds:          HashMap<ConsumableKind, KindInfo>,
+    spells:         HashMap<u32, ConsumableKind>,
+    combat_potions: HashSet<u32>,
+}
+
+static BUNDLE: Lazy<Bundle> = Lazy::new(|| {
+    let file: TomlFile = match toml::from_str(CONSUMABLES_TOML) {
+        Ok(f)  => f,
+        Err(e) => {
+            tracing::warn!("Failed to parse consumables.toml: {}", e);
+            return Bundle { kinds: HashMap::new(), spells: HashMap::new(), combat_potions: HashSet::new() };
+        }
+    };
+    Bundle {
+        kinds:          file.kinds,
+        spells:         file.spells
+            .into_iter()
+            .filter_map(|(id, kind)| Some((id.parse().ok()?, kind)))
+            .collect(),
+        combat_potions: file.combat_potions.keys().filter_map(|id| id.parse().ok()).collect(),
+    }
+});
+
+/// Kind of consumable a spell ID belongs to.
+pub fn kind_of(spell_id: u32) -> Option<ConsumableKind> {
+    BUNDLE.spells.get(&spell_id).copied()
+}
+
+/// Cooldown shared by every consumable of the kind, in milliseconds.
+pub fn cooldown_ms(kind: ConsumableKind) -> u64 {
+    BUNDLE.kinds.get(&kind).map_or(0, |k| k.cooldown_s * 1000)
+}
+
+/// A damage or main-stat potion (the pre-pot).
+pub fn is_combat_potion(spell_id: u32) -> bool {
+    BUNDLE.combat_potions.contains(&spell_id)
+}
+
+/// Display name of the kind ("Healthstone").
+pub fn name(kind: ConsumableKind) -> &'static str {
+    BUNDLE.kinds.get(&kind).map_or("Consumable", |k| k.name.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundle_parses() {
+        assert_eq!(kind_of(6262), Some(ConsumableKind::Healthstone));
+        assert_eq!(kind_of(431416), Some(ConsumableKind::Potion));
+        assert_eq!(kind_of(1), None);
+        assert!(is_combat_potion(431932) && !is_combat_potion(431416));
+        assert_eq!(cooldown_ms(ConsumableKind::Potion), 300_000);
+        assert_eq!(name(ConsumableKind::Healthstone), "Healthstone");
+    }
+}
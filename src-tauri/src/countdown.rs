@@ -0,0 +1,144 @@
+/// Boss-mod style audio countdowns — "3, 2, 1" before the encounter
+/// timeline's high-priority abilities (encounters.rs): raid_aoe for every
+/// role, tank_buster for tanks.
+///
+/// The engine puts the next countdown into each in-combat snapshot once it
+/// is within `LOOKAHEAD_MS` of its first step.  Steps are relative to the
+/// snapshot, and the overlay queues each one on its audio clock as soon as it
+/// sees it, so the 300 ms snapshot poll does not make the count drift.
+///
+/// Sounds: a voice pack is a folder with one file per number (`3.ogg`,
+/// `2.wav`, …), looked up once per config change (`voice_files`).  Numbers
+/// without a file play the overlay's built-in tone, pitched up on "1".
+use std::path::Path;
+
+use crate::{
+    config::CountdownConfig,
+    encounters::{AbilityKind, TimelineEntry},
+    ipc::{Countdown, CountdownStep},
+};
+
+/// A countdown is sent this long before its first step is due.
+const LOOKAHEAD_MS: u64 = 1_500;
+/// Voice pack file extensions, in lookup order.
+const EXTENSIONS: &[&str] = &["ogg", "mp3", "wav"];
+
+/// Voice pack file per number 1..=`from_s` (index 0 is "1"); "" where the
+/// pack has none.
+pub fn voice_files(cfg: &CountdownConfig) -> Vec<String> {
+    (1..=cfg.from_s)
+        .map(|n| voice_file(&cfg.voice_pack, n))
+        .collect()
+}
+
+fn voice_file(pack: &Path, number: u32) -> String {
+    if pack.as_os_str().is_empty() {
+        return String::new();
+    }
+    EXTENSIONS.iter()
+        .map(|ext| pack.join(format!("{}.{}", number, ext)))
+        .find(|path| path.is_file())
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// The countdown to the soonest counted ability, if it starts within the
+/// lookahead.  `sounds` comes from `voice_files`.
+pub fn next(
+    timeline:   &[TimelineEntry],
+    elapsed_ms: u64,
+    role:       &str,
+    cfg:        &CountdownConfig,
+    sounds:     &[String],
+) -> Option<Countdown> {
+    if !cfg.enabled || cfg.from_s == 0 {
+        return None;
+    }
+    let length_ms = u64::from(cfg.from_s) * 1_000;
+    timeline
+        .iter()
+        .filter(|e| match e.kind {
+            AbilityKind::RaidAoe    => true,
+            AbilityKind::TankBuster => role == "TANK",
+            AbilityKind::Other      => false,
+        })
+        .filter_map(|e| {
+            let (n, due) = e.next_cast(elapsed_ms)?;
+            Some((e, n, due.checked_sub(elapsed_ms).filter(|&ms| ms > 0)?))
+        })
+        .min_by_key(|&(_, _, due_in_ms)| due_in_ms)
+        .filter(|&(_, _, due_in_ms)| due_in_ms <= length_ms + LOOKAHEAD_MS)
+        .map(|(e, n, due_in_ms)| Countdown {
+            key:       format!("{}_{}", e.spell_id, n),
+            ability:   e.name.clone(),
+            due_in_ms,
+            steps:     (1..=cfg.from_s)
+                .rev()
+                .filter_map(|number| {
+                    let in_ms = due_in_ms.checked_sub(u64::from(number) * 1_000)?;
+                    Some(CountdownStep {
+                        number,
+                        in_ms,
+                        sound_path: sounds.get(number as usize - 1).cloned().unwrap_or_default(),
+                    })
+                })
+                .collect(),
+        })
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(spell_id: u32, kind: AbilityKind, first_s: f32, repeat_s: Option<f32>) -> TimelineEntry {
+        TimelineEntry { spell_id, name: format!("Ability {}", spell_id), kind, first_s, repeat_s }
+    }
+
+    #[test]
+    fn counts_down_to_the_soonest_counted_ability() {
+        let timeline = [
+            entry(1, AbilityKind::Other,      10.0, None),
+            entry(2, AbilityKind::TankBuster, 12.0, None),
+            entry(3, AbilityKind::RaidAoe,    14.0, Some(30.0)),
+        ];
+        let cfg = CountdownConfig::default();
+
+        // 13.5 s before the AoE: too early.
+        assert!(next(&timeline, 500, "DAMAGER", &cfg, &[]).is_none());
+
+        // 2.5 s out: "3" has passed, "2" and "1" are queued.
+        let c = next(&timeline, 11_500, "HEALER", &cfg, &[]).expect("countdown");
+        assert_eq!((c.key.as_str(), c.due_in_ms), ("3_0", 2_500));
+        let steps: Vec<_> = c.steps.iter().map(|s| (s.number, s.in_ms)).collect();
+        assert_eq!(steps, vec![(2, 500), (1, 1_500)]);
+
+        // Tanks also count down to the buster, which comes first.
+        let c = next(&timeline, 10_000, "TANK", &cfg, &[]).expect("countdown");
+        assert_eq!(c.key, "2_0");
+        assert_eq!(c.steps[0].number, 2);
+
+        // The repeat cast gets its own key.
+        assert_eq!(next(&timeline, 43_000, "DAMAGER", &cfg, &[]).unwrap().key, "3_1");
+    }
+
+    #[test]
+    fn uses_voice_pack_files_where_present() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("1.wav"), b"").unwrap();
+        std::fs::write(dir.path().join("3.ogg"), b"").unwrap();
+        let cfg = CountdownConfig { voice_pack: dir.path().to_path_buf(), ..CountdownConfig::default() };
+
+        let files = voice_files(&cfg);
+        assert!(files[0].ends_with("1.wav"));
+        assert_eq!(files[1], "");
+        assert!(files[2].ends_with("3.ogg"));
+
+        let timeline = [entry(3, AbilityKind::RaidAoe, 10.0, None)];
+        let c = next(&timeline, 6_000, "DAMAGER", &cfg, &files).unwrap();
+        assert!(c.steps[0].sound_path.ends_with("3.ogg"));
+        assert!(next(&timeline, 6_000, "DAMAGER", &CountdownConfig { enabled: false, ..cfg }, &files).is_none());
+    }
+}
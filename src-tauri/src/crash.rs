@@ -0,0 +1,257 @@
+/// Crash reports — panics and native crashes saved to disk and offered for
+/// sending on the next start.
+///
+/// The panic hook (lib.rs) calls `write_report`, which saves
+/// `<data dir>/crashes/crash-<unix ms>.txt` with the app version, thread,
+/// location, message and a forced backtrace.  A panic in one pipeline task
+/// (engine, tailer, ipc relay) kills only that task, so the app keeps running
+/// with a dead pipeline — the report is the only trace beyond one log line.
+///
+/// Native crashes (0xc0000409 aborts, access violations) never reach the
+/// panic hook.  On Windows `start_minidump_watcher` relaunches the app as a
+/// small watcher process (minidumper-child) before anything else starts;
+/// when the app dies natively, the watcher writes a minidump of it and
+/// `write_native_report` saves it as `crash-<unix ms>.dmp` next to a
+/// `crash-<unix ms>.txt` report, so it is listed like a panic.
+///
+/// On the next start the settings window lists pending reports
+/// (`get_crash_reports`).  "Send crash report" (`send_crash_report`) moves the
+/// file to `crashes/sent/` and returns a prefilled GitHub issue URL for the
+/// UI to open; `dismiss_crash_report` only moves it, along with its minidump.
+/// A URL cannot carry the minidump, so the report asks for it as an upload.
+use anyhow::Result;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+use crate::{clock::unix_now_ms, config};
+
+/// Crash reports, inside the data dir (`AppConfig.data_dir`, else the app
+/// data dir) next to the session database.  Reports written before the data
+/// dir was moved stay in the old one.
+pub const CRASH_DIR: &str = "crashes";
+/// Reports already sent or dismissed, inside CRASH_DIR.
+const SENT_DIR: &str = "sent";
+const ISSUE_URL: &str = "https://github.com/MFredin/CombatCoaching/issues/new";
+/// Report characters put in the issue body — browsers cap URL length.
+const ISSUE_BODY_CHARS: usize = 6_000;
+
+/// A crash report waiting to be sent or dismissed.
+#[derive(Debug, Clone, Serialize)]
+pub struct CrashReport {
+    pub file_name:  String,
+    /// Unix ms of the crash (from the file name).
+    pub crashed_at: u64,
+    /// "location: message" line.
+    pub summary:    String,
+    pub contents:   String,
+}
+
+fn format_report(now_ms: u64, thread: &str, location: &str, message: &str, backtrace: &str) -> String {
+    format!(
+        "CombatLedger Live Coach {} crash report\n\
+         time:     {}\n\
+         os:       {} {}\n\
+         thread:   {}\n\
+         location: {}\n\
+         message:  {}\n\
+         \n\
+         backtrace:\n{}\n",
+        env!("CARGO_PKG_VERSION"), now_ms, std::env::consts::OS, std::env::consts::ARCH,
+        thread, location, message, backtrace,
+    )
+}
+
+/// Save a report for a panic at `location` (called from the panic hook, so
+/// it must not panic itself).
+pub fn write_report(dir: &Path, location: &str, message: &str) -> std::io::Result<PathBuf> {
+    let now_ms = unix_now_ms();
+    let thread = std::thread::current().name().unwrap_or("unnamed").to_owned();
+    let backtrace = std::backtrace::Backtrace::force_capture().to_string();
+
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(format!("crash-{}.txt", now_ms));
+    std::fs::write(&path, format_report(now_ms, &thread, location, message, &backtrace))?;
+    Ok(path)
+}
+
+/// Save a minidump the watcher process captured, with a report pointing at
+/// it (called from the watcher, after the app is gone).
+#[cfg_attr(not(windows), allow(dead_code))]
+pub fn write_native_report(dir: &Path, minidump: &[u8]) -> std::io::Result<PathBuf> {
+    let now_ms = unix_now_ms();
+    std::fs::create_dir_all(dir)?;
+    let dump_name = format!("crash-{}.dmp", now_ms);
+    std::fs::write(dir.join(&dump_name), minidump)?;
+
+    let path = dir.join(format!("crash-{}.txt", now_ms));
+    let message = format!("native crash, minidump {} ({} KB)", dump_name, minidump.len() / 1024);
+    let note = format!("(no Rust backtrace — please attach {} from the crashes\\sent folder)", dump_name);
+    std::fs::write(&path, format_report(now_ms, "-", "native", &message, &note))?;
+    Ok(path)
+}
+
+/// Run the minidump watcher for native crashes, writing into `dir`.  In the
+/// watcher process this never returns; in the app it returns the handle
+/// that keeps the crash handler attached (None when it could not start).
+#[cfg(windows)]
+pub fn start_minidump_watcher(dir: PathBuf) -> Option<minidumper_child::ClientHandle> {
+    let watcher = minidumper_child::MinidumperChild::new().on_minidump(move |minidump, _path| {
+        let _ = write_native_report(&dir, &minidump);
+    });
+    match watcher.spawn() {
+        Ok(handle) => Some(handle),
+        Err(e) => {
+            eprintln!("Native crash capture not started: {}", e);
+            None
+        }
+    }
+}
+
+/// Report names become paths, so only `crash-<digits>.txt` is accepted.
+fn crashed_at(file_name: &str) -> Option<u64> {
+    file_name.strip_prefix("crash-")?.strip_suffix(".txt")?.parse().ok()
+}
+
+/// Pending reports in `dir`, newest first.
+fn pending(dir: &Path) -> Vec<CrashReport> {
+    let Ok(entries) = std::fs::read_dir(dir) else { return Vec::new() };
+    let mut reports: Vec<CrashReport> = entries
+        .flatten()
+        .filter_map(|e| {
+            let file_name = e.file_name().to_string_lossy().into_owned();
+            let crashed_at = crashed_at(&file_name)?;
+            let contents = std::fs::read_to_string(e.path()).ok()?;
+            let field = |name: &str| contents
+                .lines()
+                .find_map(|l| l.strip_prefix(name))
+                .map(str::trim)
+                .unwrap_or_default()
+                .to_owned();
+            let summary = format!("{}: {}", field("location:"), field("message:"));
+            Some(CrashReport { file_name, crashed_at, summary, contents })
+        })
+        .collect();
+    reports.sort_by_key(|r| std::cmp::Reverse(r.crashed_at));
+    reports
+}
+
+/// Move a report out of the pending list.
+fn archive(dir: &Path, file_name: &str) -> Result<()> {
+    if crashed_at(file_name).is_none() {
+        anyhow::bail!("invalid crash report name '{}'", file_name);
+    }
+    let sent = dir.join(SENT_DIR);
+    std::fs::create_dir_all(&sent)?;
+    std::fs::rename(dir.join(file_name), sent.join(file_name))?;
+    let dump_name = file_name.replace(".txt", ".dmp");
+    if dir.join(&dump_name).exists() {
+        std::fs::rename(dir.join(&dump_name), sent.join(&dump_name))?;
+    }
+    Ok(())
+}
+
+/// Percent-encode a query-string value.
+fn encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// New-issue URL with the report as the body (truncated to fit a URL).
+fn issue_url(report: &CrashReport) -> String {
+    let mut body: String = report.contents.chars().take(ISSUE_BODY_CHARS).collect();
+    if body.len() < report.contents.len() {
+        body.push_str("\n… (truncated — full report in %APPDATA%\\com.combatledger.livecoach\\crashes\\sent)");
+    }
+    format!(
+        "{}?title={}&labels=crash&body={}",
+        ISSUE_URL,
+        encode(&format!("Crash: {}", report.summary)),
+        encode(&format!("```\n{}\n```", body)),
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+fn crash_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let cfg = config::load_or_default(&config_dir).map_err(|e| e.to_string())?;
+    let default = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(config::resolve_dir(&cfg.data_dir, default).join(CRASH_DIR))
+}
+
+/// Crash reports from earlier runs that were neither sent nor dismissed.
+#[tauri::command]
+pub fn get_crash_reports(app: AppHandle) -> Result<Vec<CrashReport>, String> {
+    Ok(pending(&crash_dir(&app)?))
+}
+
+/// Mark a report sent and return the GitHub issue URL to open.
+#[tauri::command]
+pub fn send_crash_report(app: AppHandle, file_name: String) -> Result<String, String> {
+    let dir = crash_dir(&app)?;
+    let report = pending(&dir)
+        .into_iter()
+        .find(|r| r.file_name == file_name)
+        .ok_or_else(|| format!("No crash report '{}'", file_name))?;
+    archive(&dir, &file_name).map_err(|e| e.to_string())?;
+    tracing::info!("Crash report {} sent", file_name);
+    Ok(issue_url(&report))
+}
+
+/// Drop a report from the pending list without sending it.
+#[tauri::command]
+pub fn dismiss_crash_report(app: AppHandle, file_name: String) -> Result<(), String> {
+    archive(&crash_dir(&app)?, &file_name).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_round_trip_and_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_report(dir.path(), "src/engine.rs:42", "index out of bounds").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "not a report").unwrap();
+
+        let reports = pending(dir.path());
+        assert_eq!(reports.len(), 1);
+        let r = &reports[0];
+        assert_eq!(path.file_name().unwrap().to_string_lossy(), r.file_name);
+        assert_eq!(r.summary, "src/engine.rs:42: index out of bounds");
+        assert!(r.contents.contains("backtrace:"));
+
+        let url = issue_url(r);
+        assert!(url.starts_with(ISSUE_URL));
+        assert!(url.contains("title=Crash%3A%20src%2Fengine.rs%3A42"));
+
+        assert!(archive(dir.path(), "../config.toml").is_err());
+        archive(dir.path(), &r.file_name).unwrap();
+        assert!(pending(dir.path()).is_empty());
+        assert!(dir.path().join(SENT_DIR).join(&r.file_name).exists());
+    }
+
+    #[test]
+    fn native_report_is_listed_and_archived_with_its_minidump() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_native_report(dir.path(), &[0u8; 4096]).unwrap();
+        let dump = path.with_extension("dmp");
+        assert_eq!(std::fs::read(&dump).unwrap().len(), 4096);
+
+        let reports = pending(dir.path());
+        assert_eq!(reports.len(), 1);
+        assert!(reports[0].summary.starts_with("native: native crash, minidump crash-"), "{}", reports[0].summary);
+        assert!(reports[0].contents.contains("please attach"));
+
+        archive(dir.path(), &reports[0].file_name).unwrap();
+        assert!(!dump.exists());
+        assert!(dir.path().join(SENT_DIR).join(dump.file_name().unwrap()).exists());
+    }
+}
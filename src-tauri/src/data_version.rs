@@ -0,0 +1,139 @@
+/// Data versioning — which game build and season the embedded spec and
+/// encounter data was checked against (`data/data_version.toml`), and a
+/// warning when the combat log comes from a newer one.
+///
+/// Patches renumber spells: after a season change a spec's cooldowns or a
+/// boss's avoidable spells can silently stop matching, and the coaching goes
+/// quiet without saying why.  The tailer reads the BUILD_VERSION of every
+/// log it tails from the COMBAT_LOG_VERSION header line and hands it to
+/// `check_log_build`, which:
+///   - puts the build and, when the data is older, the warning into the
+///     connection status (settings window)
+///   - logs the mismatch and pushes a one-time advisory per game build
+///     (`AppConfig.stale_data_notice`)
+///
+/// Builds are compared by major.minor: a hotfix (12.0.5 → 12.0.7) does not
+/// change the season, a content patch (12.0 → 12.1) may.
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::{config, engine::Severity, ipc, rules};
+
+const DATA_VERSION_TOML: &str = include_str!("../../data/data_version.toml");
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DataVersion {
+    pub season:     String,
+    pub game_build: String,
+}
+
+static BUNDLE: Lazy<DataVersion> = Lazy::new(|| {
+    toml::from_str(DATA_VERSION_TOML).unwrap_or_else(|e| {
+        tracing::warn!("Failed to parse data_version.toml: {}", e);
+        DataVersion { season: String::new(), game_build: String::new() }
+    })
+});
+
+/// Last build handed to `check_log_build` — logs switch every few seconds
+/// while the addon cycles logging, all with the same build.
+static LAST_CHECKED: Mutex<String> = Mutex::new(String::new());
+
+/// BUILD_VERSION from a COMBAT_LOG_VERSION header line.
+fn parse_header(line: &str) -> Option<String> {
+    let payload = line.split_once("  ").map_or(line, |(_, p)| p);
+    let fields: Vec<&str> = payload.trim().split(',').collect();
+    if fields.first() != Some(&"COMBAT_LOG_VERSION") {
+        return None;
+    }
+    let at = fields.iter().position(|f| *f == "BUILD_VERSION")?;
+    fields.get(at + 1).map(|b| b.trim().to_owned()).filter(|b| !b.is_empty())
+}
+
+/// Game build of a combat log file; None until WoW has written the header.
+pub fn log_build(path: &Path) -> Option<String> {
+    let mut first = String::new();
+    BufReader::new(std::fs::File::open(path).ok()?).read_line(&mut first).ok()?;
+    parse_header(first.trim_start_matches('\u{feff}'))
+}
+
+fn major_minor(build: &str) -> Option<(u32, u32)> {
+    let mut parts = build.split('.').map(|p| p.parse::<u32>().ok());
+    Some((parts.next()??, parts.next().flatten().unwrap_or(0)))
+}
+
+/// Warning text when `data` predates the log's build; None when current or
+/// either build is unreadable.
+fn stale_message(data: &DataVersion, log_build: &str) -> Option<String> {
+    let (have, seen) = (major_minor(&data.game_build)?, major_minor(log_build)?);
+    (seen > have).then(|| format!(
+        "Coaching data is for an older season ({}, build {}) — this log is from {}. \
+         Spells changed by the patch may go unrecognised until the app's data is updated.",
+        data.season, data.game_build, log_build,
+    ))
+}
+
+/// Compare a tailed log's build with the embedded data (see module docs).
+pub fn check_log_build(app: &AppHandle, build: &str) {
+    if let Ok(mut last) = LAST_CHECKED.lock() {
+        if *last == build {
+            return;
+        }
+        *last = build.to_owned();
+    }
+    let stale = stale_message(&BUNDLE, build);
+    ipc::update_connection(app, |s| {
+        s.log_build  = build.to_owned();
+        s.stale_data = stale.clone().unwrap_or_default();
+    });
+    let Some(message) = stale else { return };
+    tracing::warn!("Data version: {}", message);
+
+    let Ok(dir) = app.path().app_config_dir() else { return };
+    let Ok(cfg) = config::load_or_default(&dir) else { return };
+    let notice = major_minor(build).map(|(major, minor)| format!("{}.{}", major, minor)).unwrap_or_default();
+    if cfg.stale_data_notice == notice {
+        return;
+    }
+    let advice = rules::advice(
+        "stale_data",
+        "Coaching Data Outdated",
+        message,
+        Severity::Warn,
+        vec![
+            ("log_build".to_owned(),  build.to_owned()),
+            ("data_build".to_owned(), BUNDLE.game_build.clone()),
+        ],
+        0,
+    );
+    ipc::push_notification(app, advice);
+
+    if let Err(e) = config::update(&dir, |cfg| cfg.stale_data_notice = notice) {
+        tracing::warn!("Data version: could not record notice: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_log_build_with_the_data() {
+        assert!(!BUNDLE.game_build.is_empty() && !BUNDLE.season.is_empty());
+        assert_eq!(
+            parse_header("1/14/2026 20:01:02.1234  COMBAT_LOG_VERSION,22,ADVANCED_LOG_ENABLED,1,BUILD_VERSION,12.0.7,PROJECT_ID,1"),
+            Some("12.0.7".to_owned()),
+        );
+        assert_eq!(parse_header("1/14/2026 20:01:02.1234  SPELL_CAST_SUCCESS,Player-1"), None);
+
+        let data = DataVersion { season: "Midnight Season 1".into(), game_build: "12.0".into() };
+        assert_eq!(stale_message(&data, "12.0.7"), None, "hotfix, same season");
+        assert_eq!(stale_message(&data, "11.2.5"), None);
+        assert_eq!(stale_message(&data, "garbage"), None);
+        let message = stale_message(&data, "12.1.0").unwrap();
+        assert!(message.contains("(Midnight Season 1, build 12.0) — this log is from 12.1.0"), "{}", message);
+    }
+}
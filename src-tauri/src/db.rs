@@ -4,21 +4,88 @@
 /// no system installation required.
 ///
 /// The writer runs on a dedicated `std::thread` (rusqlite::Connection is !Send
-/// across await points) and receives commands via a bounded sync channel.
+/// across await points) and receives commands via an unbounded channel, so a
+/// slow disk never back-pressures the engine.  `DbWriter` counts queued
+/// commands; past `LOSSY_QUEUE_LIMIT` the low-value writes (advice rows,
+/// diagnostics samples) are dropped instead of queued.  Advice inserts are
+/// coalesced into one transaction per `ADVICE_BATCH_WINDOW`.
 /// Callers hold a cheap `DbWriter` handle that is Clone + Send + Sync.
 ///
 /// Read queries (e.g. pull history) open their own short-lived read-only
 /// connection from a Tauri command handler via `spawn_blocking`, keeping the
-/// writer thread focused on writes only.
+/// writer thread focused on writes only.  The one exception is the engine's
+/// previous-attempt and wipe-forecast lookups (`last_attempt`,
+/// `encounter_pulls`): they must see the writes queued before them, which
+/// only the writer's own connection guarantees.
+use crate::{
+    advice_debug::AdviceDebug,
+    encounters,
+    identity::PlayerIdentity,
+    ipc::{DeathRecap, DodgeStat, KeyDebrief},
+    parser::LogEvent,
+    practice::PracticeReport,
+    records::{self, Kill, RecordKind, RecordUpdate},
+    rotation::SpellCastRate,
+    runs::InstanceRun,
+    state::{Keystone, PullDeath},
+};
 use anyhow::Result;
-use rusqlite::{params, Connection};
-use std::path::Path;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::oneshot;
 
+/// Queued commands above which lossy writes (advice, diagnostics) are dropped.
+const LOSSY_QUEUE_LIMIT: usize = 10_000;
+/// Advice inserts arriving within this window share one transaction.
+const ADVICE_BATCH_WINDOW: Duration = Duration::from_millis(100);
+/// Advice debug rows kept; older ones are pruned on insert.
+const ADVICE_DEBUG_KEEP: i64 = 5_000;
+
 // ---------------------------------------------------------------------------
 // Commands sent to the writer thread
 // ---------------------------------------------------------------------------
 
+/// One advice row of a pull (live or re-coached).
+#[derive(Debug, Clone, Serialize)]
+pub struct StoredAdvice {
+    pub fired_at:   u64,
+    pub rule_key:   String,
+    pub severity:   String,
+    pub message:    String,
+    /// Nth fire of `rule_key` in the pull (0 when not counted); with
+    /// `escalation` the rows of one key form its escalation chain.
+    pub repeat:     u32,
+    /// Repeat-offense escalation (engine::escalate): 0 none, 1 raised
+    /// severity, 2 also urgent.
+    pub escalation: u8,
+}
+
+/// The latest finished pull of an encounter in a session.
+#[derive(Debug, Clone)]
+pub struct PreviousAttempt {
+    /// Finished pulls of the encounter in the session so far.
+    pub attempts:    u32,
+    pub outcome:     String,
+    pub duration_ms: u64,
+    pub death:       Option<PullDeath>,
+}
+
+/// A finished pull of an encounter, as the wipe forecast learns from it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PullSample {
+    pub kill:           bool,
+    pub duration_ms:    u64,
+    pub group_deaths:   u32,
+    /// None on pulls stored before these were recorded.
+    pub avoidable_hits: Option<u32>,
+    pub boss_damage:    Option<u64>,
+}
+
 pub enum DbCommand {
     InsertSession {
         reply:       oneshot::Sender<Result<i64>>,
@@ -30,24 +97,189 @@ pub enum DbCommand {
         session_id:  i64,
         player_name: String,
         player_guid: String,
+        /// WoW account folder, "" when unknown.
+        account:     String,
+    },
+    EndSession {
+        session_id: i64,
+        ended_at:   u64,
     },
     InsertPull {
         reply:       oneshot::Sender<Result<i64>>,
         session_id:  i64,
         pull_number: u32,
         started_at:  u64,
+        /// Instance run the pull belongs to (runs.rs), if any.
+        run_id:      Option<i64>,
+        /// Mythic+ key the pull was fought in, if any.
+        keystone:    Option<Keystone>,
     },
     EndPull {
-        pull_id:  i64,
+        pull_id:         i64,
+        ended_at:        u64,
+        outcome:         String,
+        active_time_pct: f32,
+        boss_damage_pct: Option<f32>,
+        interrupt_efficiency: Option<f32>,
+    },
+    InsertArenaMatch {
+        reply:       oneshot::Sender<Result<i64>>,
+        session_id:  i64,
+        started_at:  u64,
+        instance_id: u32,
+        bracket:     String,
+        team_id:     u32,
+    },
+    EndArenaMatch {
+        match_id:   i64,
+        ended_at:   u64,
+        outcome:    String,
+        duration_s: u32,
+        rating:     u32,
+    },
+    InsertInstanceRun {
+        reply:         oneshot::Sender<Result<i64>>,
+        session_id:    i64,
+        started_at:    u64,
+        instance_id:   u32,
+        zone_name:     String,
+        difficulty_id: u32,
+        kind:          String,
+    },
+    EndInstanceRun {
+        run_id:   i64,
         ended_at: u64,
-        outcome:  String,
+    },
+    BeginPause {
+        session_id: i64,
+        paused_at:  u64,
+    },
+    EndPause {
+        session_id: i64,
+        resumed_at: u64,
+    },
+    InsertDodgeStats {
+        pull_id:   i64,
+        encounter: String,
+        stats:     Vec<DodgeStat>,
+    },
+    InsertCastRates {
+        pull_id: i64,
+        rates:   Vec<SpellCastRate>,
     },
     InsertAdvice {
+        pull_id: i64,
+        advice:  StoredAdvice,
+    },
+    InsertAdviceDebug {
+        pull_id: Option<i64>,
+        debug:   AdviceDebug,
+    },
+    /// Tag a finished pull with its encounter, the player's first death and
+    /// the group's deaths and battle resurrections.
+    RecordAttempt {
+        pull_id:      i64,
+        encounter_id: Option<u32>,
+        encounter:    String,
+        death:        Option<PullDeath>,
+        group_deaths: u32,
+        battle_res:   u32,
+    },
+    /// Group avoidable hits and everyone's damage on the boss over a
+    /// finished pull (wipe forecast).
+    RecordPullStats {
+        pull_id:        i64,
+        avoidable_hits: u32,
+        boss_damage:    u64,
+    },
+    /// The coached player's death recap (rules::death_recap).
+    InsertDeathRecap {
+        pull_id: i64,
+        recap:   DeathRecap,
+    },
+    /// A finished Mythic+ key's summary (key_run.rs).
+    InsertKeyRun {
+        session_id: i64,
+        run_id:     Option<i64>,
+        ended_at:   u64,
+        debrief:    KeyDebrief,
+    },
+    /// Latest finished pull of `encounter` in the session, if any.
+    LastAttempt {
+        reply:      oneshot::Sender<Result<Option<PreviousAttempt>>>,
+        session_id: i64,
+        encounter:  String,
+    },
+    /// The latest finished kills and wipes of `encounter`, every session.
+    EncounterPulls {
+        reply:     oneshot::Sender<Result<Vec<PullSample>>>,
+        encounter: String,
+    },
+    /// Check a boss kill against the personal records and keep the better
+    /// values; replies with the records it set or beat.
+    UpdateRecords {
+        reply: oneshot::Sender<Result<Vec<RecordUpdate>>>,
+        kill:  Kill,
+    },
+    /// Delete a pull and, by cascade, its advice (training-dummy pulls).
+    DiscardPull {
+        pull_id: i64,
+    },
+    /// Insert or rewrite a practice session's report.
+    SavePracticeSession {
+        session_id: i64,
+        started_at: u64,
+        ended_at:   u64,
+        spec:       String,
+        report:     PracticeReport,
+    },
+    /// Store a pull's raw events for re-coaching.
+    ArchivePullEvents {
         pull_id:  i64,
-        fired_at: u64,
-        rule_key: String,
-        severity: String,
-        message:  String,
+        identity: PlayerIdentity,
+        events:   Vec<LogEvent>,
+    },
+    /// Store a re-coached advice set as the pull's next version; replies with
+    /// the version number.
+    InsertAdviceSet {
+        reply:   oneshot::Sender<Result<u32>>,
+        pull_id: i64,
+        advice:  Vec<StoredAdvice>,
+    },
+    /// One self-monitoring sample (see diagnostics.rs).
+    InsertDiagnostics {
+        recorded_at: u64,
+        rss_bytes:   Option<u64>,
+        cpu_pct:     Option<f32>,
+        channels:    String,
+        state_sizes: String,
+    },
+    /// Replace a precomputed history aggregate (see analytics.rs).
+    StoreAnalytics {
+        name:        String,
+        stamp:       String,
+        computed_at: u64,
+        payload:     String,
+    },
+    /// Replace a session's note (journal.rs).
+    SetSessionNote {
+        session_id: i64,
+        text:       String,
+    },
+    /// Add a journal entry to a session; replies with its row id.
+    AddJournalEntry {
+        reply:      oneshot::Sender<Result<i64>>,
+        session_id: i64,
+        created_at: u64,
+        text:       String,
+    },
+    DeleteJournalEntry {
+        entry_id: i64,
+    },
+    /// Copy the database to `path` and continue writing there.
+    Relocate {
+        reply: oneshot::Sender<Result<()>>,
+        path:  PathBuf,
     },
 }
 
@@ -57,10 +289,46 @@ pub enum DbCommand {
 
 #[derive(Clone)]
 pub struct DbWriter {
-    tx: std::sync::mpsc::SyncSender<DbCommand>,
+    tx:      std::sync::mpsc::Sender<DbCommand>,
+    /// Commands sent but not yet taken by the writer thread.
+    queued:  Arc<AtomicUsize>,
+    /// Lossy writes dropped because the queue was over LOSSY_QUEUE_LIMIT.
+    dropped: Arc<AtomicU64>,
 }
 
 impl DbWriter {
+    fn send(&self, cmd: DbCommand) -> Result<()> {
+        self.queued.fetch_add(1, Ordering::Relaxed);
+        self.tx.send(cmd).map_err(|_| {
+            self.queued.fetch_sub(1, Ordering::Relaxed);
+            anyhow::anyhow!("DB writer channel closed")
+        })
+    }
+
+    /// `send` for writes that may be lost: dropped while the writer is more
+    /// than LOSSY_QUEUE_LIMIT commands behind.
+    fn send_lossy(&self, cmd: DbCommand) {
+        let depth = self.queue_depth();
+        if depth >= LOSSY_QUEUE_LIMIT {
+            let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+            if dropped.is_power_of_two() {
+                tracing::warn!("DB writer {} commands behind — {} low-priority writes dropped", depth, dropped);
+            }
+            return;
+        }
+        let _ = self.send(cmd);
+    }
+
+    /// Commands waiting for the writer thread (diagnostics).
+    pub fn queue_depth(&self) -> usize {
+        self.queued.load(Ordering::Relaxed)
+    }
+
+    /// Lossy writes dropped so far because the queue was full (diagnostics).
+    pub fn dropped_writes(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
     /// Insert a new session row; returns the auto-generated row id.
     pub async fn insert_session(
         &self,
@@ -69,15 +337,19 @@ impl DbWriter {
         player_guid: String,
     ) -> Result<i64> {
         let (reply_tx, reply_rx) = oneshot::channel();
-        self.tx
-            .send(DbCommand::InsertSession { reply: reply_tx, started_at, player_name, player_guid })
-            .map_err(|_| anyhow::anyhow!("DB writer channel closed"))?;
+        self.send(DbCommand::InsertSession { reply: reply_tx, started_at, player_name, player_guid })?;
         reply_rx.await.map_err(|_| anyhow::anyhow!("DB reply channel closed"))?
     }
 
     /// Back-fill player identity into the session row (fire-and-forget).
-    pub fn update_session(&self, session_id: i64, player_name: String, player_guid: String) {
-        let _ = self.tx.send(DbCommand::UpdateSession { session_id, player_name, player_guid });
+    pub fn update_session(&self, session_id: i64, player_name: String, player_guid: String, account: String) {
+        let _ = self.send(DbCommand::UpdateSession { session_id, player_name, player_guid, account });
+    }
+
+    /// Move the session's end (unix ms) forward (fire-and-forget); set at
+    /// every pull end and when the engine stops.
+    pub fn end_session(&self, session_id: i64, ended_at: u64) {
+        let _ = self.send(DbCommand::EndSession { session_id, ended_at });
     }
 
     /// Insert a new pull row; returns the auto-generated row id.
@@ -86,29 +358,231 @@ impl DbWriter {
         session_id:  i64,
         pull_number: u32,
         started_at:  u64,
+        run_id:      Option<i64>,
+        keystone:    Option<Keystone>,
+    ) -> Result<i64> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.send(DbCommand::InsertPull { reply: reply_tx, session_id, pull_number, started_at, run_id, keystone })?;
+        reply_rx.await.map_err(|_| anyhow::anyhow!("DB reply channel closed"))?
+    }
+
+    /// Update a pull's end time, outcome, active-time % and boss-damage share
+    /// (fire-and-forget).
+    pub fn end_pull(
+        &self,
+        pull_id:         i64,
+        ended_at:        u64,
+        outcome:         String,
+        active_time_pct: f32,
+        boss_damage_pct: Option<f32>,
+        interrupt_efficiency: Option<f32>,
+    ) {
+        let _ = self.send(DbCommand::EndPull {
+            pull_id, ended_at, outcome, active_time_pct, boss_damage_pct, interrupt_efficiency,
+        });
+    }
+
+    /// Insert a new arena match row; returns the auto-generated row id.
+    pub async fn insert_arena_match(
+        &self,
+        session_id:  i64,
+        started_at:  u64,
+        instance_id: u32,
+        bracket:     String,
+        team_id:     u32,
     ) -> Result<i64> {
         let (reply_tx, reply_rx) = oneshot::channel();
-        self.tx
-            .send(DbCommand::InsertPull { reply: reply_tx, session_id, pull_number, started_at })
-            .map_err(|_| anyhow::anyhow!("DB writer channel closed"))?;
+        self.send(DbCommand::InsertArenaMatch {
+            reply: reply_tx, session_id, started_at, instance_id, bracket, team_id,
+        })?;
         reply_rx.await.map_err(|_| anyhow::anyhow!("DB reply channel closed"))?
     }
 
-    /// Update a pull's end time and outcome (fire-and-forget).
-    pub fn end_pull(&self, pull_id: i64, ended_at: u64, outcome: String) {
-        let _ = self.tx.send(DbCommand::EndPull { pull_id, ended_at, outcome });
+    /// Close an arena match with outcome, duration and the team's new rating
+    /// (fire-and-forget).
+    pub fn end_arena_match(&self, match_id: i64, ended_at: u64, outcome: String, duration_s: u32, rating: u32) {
+        let _ = self.send(DbCommand::EndArenaMatch { match_id, ended_at, outcome, duration_s, rating });
+    }
+
+    /// Insert a new instance run row; returns the auto-generated row id.
+    pub async fn insert_instance_run(&self, session_id: i64, started_at: u64, run: &InstanceRun) -> Result<i64> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.send(DbCommand::InsertInstanceRun {
+            reply:         reply_tx,
+            session_id,
+            started_at,
+            instance_id:   run.instance_id,
+            zone_name:     run.zone_name.clone(),
+            difficulty_id: run.difficulty_id,
+            kind:          run.kind.as_str().to_owned(),
+        })?;
+        reply_rx.await.map_err(|_| anyhow::anyhow!("DB reply channel closed"))?
+    }
+
+    /// Close an instance run (fire-and-forget).
+    pub fn end_instance_run(&self, run_id: i64, ended_at: u64) {
+        let _ = self.send(DbCommand::EndInstanceRun { run_id, ended_at });
+    }
+
+    /// Open a paused interval for the session (fire-and-forget).
+    pub fn begin_pause(&self, session_id: i64, paused_at: u64) {
+        let _ = self.send(DbCommand::BeginPause { session_id, paused_at });
+    }
+
+    /// Close the session's open paused interval (fire-and-forget).
+    pub fn end_pause(&self, session_id: i64, resumed_at: u64) {
+        let _ = self.send(DbCommand::EndPause { session_id, resumed_at });
+    }
+
+    /// Store a pull's per-mechanic dodge stats (fire-and-forget).
+    pub fn insert_dodge_stats(&self, pull_id: i64, encounter: String, stats: Vec<DodgeStat>) {
+        let _ = self.send(DbCommand::InsertDodgeStats { pull_id, encounter, stats });
+    }
+
+    /// Store a pull's priority-spell cast rates (fire-and-forget).
+    pub fn insert_cast_rates(&self, pull_id: i64, rates: Vec<SpellCastRate>) {
+        let _ = self.send(DbCommand::InsertCastRates { pull_id, rates });
+    }
+
+    /// Delete a pull that turned out to be dummy practice (fire-and-forget).
+    pub fn discard_pull(&self, pull_id: i64) {
+        let _ = self.send(DbCommand::DiscardPull { pull_id });
+    }
+
+    /// Store the running report of a practice session, keyed by its start
+    /// (fire-and-forget).
+    pub fn save_practice_session(
+        &self,
+        session_id: i64,
+        started_at: u64,
+        ended_at:   u64,
+        spec:       String,
+        report:     PracticeReport,
+    ) {
+        let _ = self.send(DbCommand::SavePracticeSession { session_id, started_at, ended_at, spec, report });
     }
 
     /// Insert an advice event (fire-and-forget).
-    pub fn insert_advice(
+    pub fn insert_advice(&self, pull_id: i64, advice: StoredAdvice) {
+        self.send_lossy(DbCommand::InsertAdvice { pull_id, advice });
+    }
+
+    /// Insert an advice's debug row (fire-and-forget, lossy); `pull_id` is
+    /// None for advice fired outside a recorded pull.
+    pub fn insert_advice_debug(&self, pull_id: Option<i64>, debug: AdviceDebug) {
+        self.send_lossy(DbCommand::InsertAdviceDebug { pull_id, debug });
+    }
+
+    /// Record a finished pull's encounter, first death and group death /
+    /// battle-res counts (fire-and-forget).  `encounter_id` is the canonical
+    /// ID, when the pull ended with ENCOUNTER_END.
+    pub fn record_attempt(
         &self,
-        pull_id:  i64,
-        fired_at: u64,
-        rule_key: String,
-        severity: String,
-        message:  String,
+        pull_id:      i64,
+        encounter_id: Option<u32>,
+        encounter:    String,
+        death:        Option<PullDeath>,
+        group_deaths: u32,
+        battle_res:   u32,
     ) {
-        let _ = self.tx.send(DbCommand::InsertAdvice { pull_id, fired_at, rule_key, severity, message });
+        let _ = self.send(DbCommand::RecordAttempt { pull_id, encounter_id, encounter, death, group_deaths, battle_res });
+    }
+
+    /// Record a finished pull's group avoidable hits and boss damage
+    /// (fire-and-forget).
+    pub fn record_pull_stats(&self, pull_id: i64, avoidable_hits: u32, boss_damage: u64) {
+        let _ = self.send(DbCommand::RecordPullStats { pull_id, avoidable_hits, boss_damage });
+    }
+
+    /// Store a death recap with its pull (fire-and-forget).
+    pub fn insert_death_recap(&self, pull_id: i64, recap: DeathRecap) {
+        let _ = self.send(DbCommand::InsertDeathRecap { pull_id, recap });
+    }
+
+    /// Store a Mythic+ key summary (fire-and-forget).  `run_id` is the
+    /// dungeon's instance run, if one is open.
+    pub fn insert_key_run(&self, session_id: i64, run_id: Option<i64>, ended_at: u64, debrief: KeyDebrief) {
+        let _ = self.send(DbCommand::InsertKeyRun { session_id, run_id, ended_at, debrief });
+    }
+
+    /// Latest finished pull of `encounter` in the session (progression nudges).
+    pub async fn last_attempt(&self, session_id: i64, encounter: String) -> Result<Option<PreviousAttempt>> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.send(DbCommand::LastAttempt { reply: reply_tx, session_id, encounter })?;
+        reply_rx.await.map_err(|_| anyhow::anyhow!("DB reply channel closed"))?
+    }
+
+    /// Stored kills and wipes of `encounter` to train the wipe forecast on
+    /// (wipe_forecast.rs).
+    pub async fn encounter_pulls(&self, encounter: String) -> Result<Vec<PullSample>> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.send(DbCommand::EncounterPulls { reply: reply_tx, encounter })?;
+        reply_rx.await.map_err(|_| anyhow::anyhow!("DB reply channel closed"))?
+    }
+
+    /// Check a kill against the personal records (records.rs); returns the
+    /// records it set or beat.
+    pub async fn update_records(&self, kill: Kill) -> Result<Vec<RecordUpdate>> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.send(DbCommand::UpdateRecords { reply: reply_tx, kill })?;
+        reply_rx.await.map_err(|_| anyhow::anyhow!("DB reply channel closed"))?
+    }
+
+    /// Archive a finished pull's events for `recoach_pull` (fire-and-forget).
+    pub fn archive_pull_events(&self, pull_id: i64, identity: PlayerIdentity, events: Vec<LogEvent>) {
+        let _ = self.send(DbCommand::ArchivePullEvents { pull_id, identity, events });
+    }
+
+    /// Store a re-coached advice set; returns its version (live advice is 0).
+    pub async fn insert_advice_set(&self, pull_id: i64, advice: Vec<StoredAdvice>) -> Result<u32> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.send(DbCommand::InsertAdviceSet { reply: reply_tx, pull_id, advice })?;
+        reply_rx.await.map_err(|_| anyhow::anyhow!("DB reply channel closed"))?
+    }
+
+    /// Store a self-monitoring sample (fire-and-forget).  `channels` and
+    /// `state_sizes` are JSON objects of name -> count.
+    pub fn insert_diagnostics(
+        &self,
+        recorded_at: u64,
+        rss_bytes:   Option<u64>,
+        cpu_pct:     Option<f32>,
+        channels:    String,
+        state_sizes: String,
+    ) {
+        self.send_lossy(DbCommand::InsertDiagnostics {
+            recorded_at, rss_bytes, cpu_pct, channels, state_sizes,
+        });
+    }
+
+    /// Cache an aggregate computed from the pull data identified by `stamp`
+    /// (fire-and-forget; a lost write only means recomputing it).
+    pub fn store_analytics(&self, name: String, stamp: String, computed_at: u64, payload: String) {
+        self.send_lossy(DbCommand::StoreAnalytics { name, stamp, computed_at, payload });
+    }
+
+    /// Replace a session's note (fire-and-forget).
+    pub fn set_session_note(&self, session_id: i64, text: String) {
+        let _ = self.send(DbCommand::SetSessionNote { session_id, text });
+    }
+
+    /// Add a journal entry to a session; returns the entry's row id.
+    pub async fn add_journal_entry(&self, session_id: i64, created_at: u64, text: String) -> Result<i64> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.send(DbCommand::AddJournalEntry { reply: reply_tx, session_id, created_at, text })?;
+        reply_rx.await.map_err(|_| anyhow::anyhow!("DB reply channel closed"))?
+    }
+
+    /// Delete a journal entry (fire-and-forget).
+    pub fn delete_journal_entry(&self, entry_id: i64) {
+        let _ = self.send(DbCommand::DeleteJournalEntry { entry_id });
+    }
+
+    /// Copy the database to `path` and switch all further writes to it.
+    pub async fn relocate(&self, path: PathBuf) -> Result<()> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.send(DbCommand::Relocate { reply: reply_tx, path })?;
+        reply_rx.await.map_err(|_| anyhow::anyhow!("DB reply channel closed"))?
     }
 }
 
@@ -126,15 +600,24 @@ pub fn spawn_db_writer(db_path: &Path) -> Result<DbWriter> {
     let conn = Connection::open(db_path)?;
     apply_schema(&conn)?;
 
-    let (tx, rx) = std::sync::mpsc::sync_channel::<DbCommand>(512);
+    let (tx, rx) = std::sync::mpsc::channel::<DbCommand>();
+    let queued = Arc::new(AtomicUsize::new(0));
 
-    std::thread::spawn(move || db_writer_loop(rx, conn));
+    let writer_queued = queued.clone();
+    std::thread::spawn(move || db_writer_loop(rx, conn, writer_queued));
 
     tracing::info!("SQLite writer started at {:?}", db_path);
-    Ok(DbWriter { tx })
+    Ok(DbWriter { tx, queued, dropped: Arc::new(AtomicU64::new(0)) })
 }
 
-fn apply_schema(conn: &Connection) -> Result<()> {
+/// A `DbWriter` with no database behind it: writes are discarded and queries
+/// fail.  For running the engine headless (analyze.rs).
+pub fn detached_db_writer() -> DbWriter {
+    let (tx, _) = std::sync::mpsc::channel::<DbCommand>();
+    DbWriter { tx, queued: Arc::new(AtomicUsize::new(0)), dropped: Arc::new(AtomicU64::new(0)) }
+}
+
+pub(crate) fn apply_schema(conn: &Connection) -> Result<()> {
     conn.execute_batch("
         PRAGMA journal_mode = WAL;
         PRAGMA foreign_keys = ON;
@@ -143,11 +626,17 @@ fn apply_schema(conn: &Connection) -> Result<()> {
         CREATE TABLE IF NOT EXISTS sessions (
             id          INTEGER PRIMARY KEY AUTOINCREMENT,
             started_at  INTEGER NOT NULL,
+            -- Unix ms of the last pull end, then of the engine stopping.
             ended_at    INTEGER,
             player_name TEXT    NOT NULL DEFAULT '',
             player_guid TEXT    NOT NULL DEFAULT '',
             player_spec TEXT,
-            realm       TEXT
+            realm       TEXT,
+            -- WoW account folder of the addon SavedVariables (config::account_of);
+            -- '' when unknown.  Links a player's characters.
+            account     TEXT    NOT NULL DEFAULT '',
+            -- Free-form context the player wrote for the session (journal.rs).
+            note        TEXT    NOT NULL DEFAULT ''
         );
 
         CREATE TABLE IF NOT EXISTS pulls (
@@ -157,32 +646,573 @@ fn apply_schema(conn: &Connection) -> Result<()> {
             started_at  INTEGER NOT NULL,
             ended_at    INTEGER,
             outcome     TEXT,
-            encounter   TEXT
+            encounter   TEXT,
+            active_time_pct REAL,
+            boss_damage_pct REAL,
+            interrupt_efficiency REAL,
+            -- Coached player's first death: ms since pull start and the last
+            -- ability that hit them (progression nudges).
+            death_at_ms INTEGER,
+            death_cause TEXT,
+            run_id      INTEGER REFERENCES instance_runs(id) ON DELETE SET NULL,
+            -- Mythic+ key (CHALLENGE_MODE_START) active at pull start;
+            -- affixes as display names, comma-separated.
+            key_dungeon TEXT,
+            key_level   INTEGER,
+            key_affixes TEXT,
+            -- Group players' deaths and battle resurrections this pull.
+            group_deaths INTEGER,
+            battle_res   INTEGER,
+            -- Group avoidable hits and everyone's damage on the boss units
+            -- (wipe forecast).
+            avoidable_hits INTEGER,
+            boss_damage    INTEGER,
+            -- Canonical encounter ID from ENCOUNTER_END (mechanic learning
+            -- curves); NULL for pulls that ended otherwise.
+            encounter_id   INTEGER
+        );
+
+        -- Dungeon runs / raid nights: a stay in one instance, bounded by
+        -- ZONE_CHANGE (see runs.rs).  kind is 'dungeon' or 'raid'.
+        CREATE TABLE IF NOT EXISTS instance_runs (
+            id            INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id    INTEGER NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+            started_at    INTEGER NOT NULL,
+            ended_at      INTEGER,
+            instance_id   INTEGER NOT NULL,
+            zone_name     TEXT    NOT NULL,
+            difficulty_id INTEGER NOT NULL,
+            kind          TEXT    NOT NULL
         );
 
+        -- version 0 is the advice that fired live; re-coaching a pull
+        -- (recoach_pull) adds versions 1, 2, … next to it.  repeat numbers
+        -- the fires of a rule_key within the pull and escalation marks the
+        -- repeats that were escalated (1 severity raised, 2 also urgent).
         CREATE TABLE IF NOT EXISTS advice_events (
             id         INTEGER PRIMARY KEY AUTOINCREMENT,
             pull_id    INTEGER NOT NULL REFERENCES pulls(id) ON DELETE CASCADE,
             fired_at   INTEGER NOT NULL,
             rule_key   TEXT    NOT NULL,
             severity   TEXT    NOT NULL,
-            message    TEXT    NOT NULL
+            message    TEXT    NOT NULL,
+            version    INTEGER NOT NULL DEFAULT 0,
+            repeat     INTEGER NOT NULL DEFAULT 0,
+            escalation INTEGER NOT NULL DEFAULT 0
+        );
+
+        -- Raw log lines and coaching state behind an advice (JSON of
+        -- advice_debug::AdviceDebug), kept while AppConfig.advice_debug is on.
+        CREATE TABLE IF NOT EXISTS advice_debug (
+            debug_id   TEXT    PRIMARY KEY,
+            pull_id    INTEGER REFERENCES pulls(id) ON DELETE CASCADE,
+            rule_key   TEXT    NOT NULL,
+            data       TEXT    NOT NULL
+        );
+
+        -- Raw events of each recorded pull (JSON array of LogEvent) plus the
+        -- coached player's identity, replayed by recoach_pull.
+        CREATE TABLE IF NOT EXISTS pull_events (
+            pull_id      INTEGER PRIMARY KEY REFERENCES pulls(id) ON DELETE CASCADE,
+            player_guid  TEXT    NOT NULL,
+            player_class TEXT    NOT NULL,
+            player_spec  TEXT    NOT NULL,
+            events       TEXT    NOT NULL
+        );
+
+        -- Arena matches bounded by ARENA_MATCH_START/END.  rating is the
+        -- coached player's team rating after the match (0 when unrated).
+        CREATE TABLE IF NOT EXISTS arena_matches (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id  INTEGER NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+            started_at  INTEGER NOT NULL,
+            ended_at    INTEGER,
+            instance_id INTEGER NOT NULL,
+            bracket     TEXT    NOT NULL,
+            team_id     INTEGER NOT NULL,
+            outcome     TEXT,
+            duration_s  INTEGER,
+            rating      INTEGER
+        );
+
+        -- Intervals where coaching was paused; resumed_at is NULL while the
+        -- pause is still open (or the app closed mid-pause).
+        CREATE TABLE IF NOT EXISTS session_pauses (
+            id         INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id INTEGER NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+            paused_at  INTEGER NOT NULL,
+            resumed_at INTEGER
+        );
+
+        -- Avoided vs hit per avoidable mechanic per pull.  encounter is
+        -- stored here because dodge trends are grouped per encounter.
+        CREATE TABLE IF NOT EXISTS mechanic_dodges (
+            id         INTEGER PRIMARY KEY AUTOINCREMENT,
+            pull_id    INTEGER NOT NULL REFERENCES pulls(id) ON DELETE CASCADE,
+            encounter  TEXT    NOT NULL,
+            spell_id   INTEGER NOT NULL,
+            spell_name TEXT    NOT NULL,
+            avoided    INTEGER NOT NULL,
+            hit        INTEGER NOT NULL
+        );
+
+        -- Casts per minute of each rotation priority spell per pull
+        -- (rotation.rs).
+        CREATE TABLE IF NOT EXISTS pull_cast_rates (
+            id         INTEGER PRIMARY KEY AUTOINCREMENT,
+            pull_id    INTEGER NOT NULL REFERENCES pulls(id) ON DELETE CASCADE,
+            spell_id   INTEGER NOT NULL,
+            spell_name TEXT    NOT NULL,
+            casts      INTEGER NOT NULL,
+            cpm        REAL    NOT NULL,
+            underused  INTEGER NOT NULL
+        );
+
+        -- Target-dummy practice: one row per run of consecutive dummy pulls,
+        -- rewritten after each pull.  report is a JSON PracticeReport.
+        -- Practice pulls are not kept in `pulls`.
+        CREATE TABLE IF NOT EXISTS practice_sessions (
+            id         INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id INTEGER NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+            started_at INTEGER NOT NULL,
+            ended_at   INTEGER NOT NULL,
+            spec       TEXT    NOT NULL,
+            pulls      INTEGER NOT NULL,
+            score      REAL    NOT NULL,
+            report     TEXT    NOT NULL,
+            UNIQUE (session_id, started_at)
+        );
+
+        -- Per-minute self-monitoring samples.  channels / state_sizes are
+        -- JSON objects of name -> count (queued messages, collection lengths).
+        CREATE TABLE IF NOT EXISTS diagnostics (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            recorded_at INTEGER NOT NULL,
+            rss_bytes   INTEGER,
+            cpu_pct     REAL,
+            channels    TEXT    NOT NULL,
+            state_sizes TEXT    NOT NULL
+        );
+
+        -- Coached player deaths: one row per death (battle res → several
+        -- per pull).  recap is a JSON DeathRecap (last 10s of damage,
+        -- heals and defensives).
+        CREATE TABLE IF NOT EXISTS death_recaps (
+            id           INTEGER PRIMARY KEY AUTOINCREMENT,
+            pull_id      INTEGER NOT NULL REFERENCES pulls(id) ON DELETE CASCADE,
+            elapsed_ms   INTEGER NOT NULL,
+            killing_blow TEXT    NOT NULL,
+            recap        TEXT    NOT NULL
+        );
+
+        -- Best kill values per character, encounter and difficulty
+        -- (records.rs).  record is a RecordKind key.
+        CREATE TABLE IF NOT EXISTS personal_records (
+            player_guid   TEXT    NOT NULL,
+            player_name   TEXT    NOT NULL,
+            encounter     TEXT    NOT NULL,
+            difficulty_id INTEGER NOT NULL,
+            record        TEXT    NOT NULL,
+            value         REAL    NOT NULL,
+            pull_id       INTEGER REFERENCES pulls(id) ON DELETE SET NULL,
+            set_at        INTEGER NOT NULL,
+            PRIMARY KEY (player_guid, encounter, difficulty_id, record)
+        );
+
+        -- Mythic+ keys, CHALLENGE_MODE_START to END (key_run.rs).  debrief
+        -- is the JSON KeyDebrief, incl. the longest GCD gaps and affixes.
+        CREATE TABLE IF NOT EXISTS runs (
+            id               INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id       INTEGER NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+            instance_run_id  INTEGER REFERENCES instance_runs(id) ON DELETE SET NULL,
+            ended_at         INTEGER NOT NULL,
+            dungeon          TEXT    NOT NULL,
+            key_level        INTEGER NOT NULL,
+            success          INTEGER NOT NULL,
+            total_time_ms    INTEGER NOT NULL,
+            pulls            INTEGER NOT NULL,
+            deaths           INTEGER NOT NULL,
+            avoidable_hits   INTEGER NOT NULL,
+            interrupts       INTEGER NOT NULL,
+            out_of_combat_ms INTEGER NOT NULL,
+            debrief          TEXT    NOT NULL
+        );
+
+        -- History aggregates precomputed while the app is idle (analytics.rs).
+        -- stamp identifies the pull data they were computed from; payload is
+        -- the JSON result, stale once the stamp no longer matches.
+        CREATE TABLE IF NOT EXISTS analytics_cache (
+            name        TEXT    PRIMARY KEY,
+            stamp       TEXT    NOT NULL,
+            computed_at INTEGER NOT NULL,
+            payload     TEXT    NOT NULL
+        );
+
+        -- Raid-night journal: timestamped free-form entries of a session
+        -- (journal.rs).
+        CREATE TABLE IF NOT EXISTS journal_entries (
+            id         INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id INTEGER NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+            created_at INTEGER NOT NULL,
+            text       TEXT    NOT NULL
+        );
+
+        -- Old encounter names of renamed bosses (encounters.rs aliases),
+        -- rewritten at every open; queries group through
+        -- canonical_encounter() so history survives the rename.
+        CREATE TABLE IF NOT EXISTS encounter_aliases (
+            alias     TEXT PRIMARY KEY COLLATE NOCASE,
+            canonical TEXT NOT NULL
         );
 
         CREATE INDEX IF NOT EXISTS idx_pulls_session ON pulls(session_id);
+        CREATE INDEX IF NOT EXISTS idx_dodges_pull   ON mechanic_dodges(pull_id);
+        CREATE INDEX IF NOT EXISTS idx_casts_pull    ON pull_cast_rates(pull_id);
+        CREATE INDEX IF NOT EXISTS idx_arena_session ON arena_matches(session_id);
+        CREATE INDEX IF NOT EXISTS idx_pauses_session ON session_pauses(session_id);
         CREATE INDEX IF NOT EXISTS idx_advice_pull   ON advice_events(pull_id);
         CREATE INDEX IF NOT EXISTS idx_advice_rule   ON advice_events(rule_key);
+        CREATE INDEX IF NOT EXISTS idx_deaths_pull   ON death_recaps(pull_id);
+        CREATE INDEX IF NOT EXISTS idx_runs_session  ON instance_runs(session_id);
+        CREATE INDEX IF NOT EXISTS idx_keys_session  ON runs(session_id);
+        CREATE INDEX IF NOT EXISTS idx_journal_session ON journal_entries(session_id);
     ")?;
+
+    // Columns added after the initial schema — CREATE TABLE IF NOT EXISTS
+    // leaves existing databases untouched, so add them explicitly.
+    ensure_column(conn, "pulls", "active_time_pct", "REAL")?;
+    ensure_column(conn, "pulls", "boss_damage_pct", "REAL")?;
+    ensure_column(conn, "pulls", "interrupt_efficiency", "REAL")?;
+    ensure_column(conn, "advice_events", "version", "INTEGER NOT NULL DEFAULT 0")?;
+    ensure_column(conn, "pulls", "death_at_ms", "INTEGER")?;
+    ensure_column(conn, "pulls", "death_cause", "TEXT")?;
+    ensure_column(conn, "pulls", "run_id", "INTEGER REFERENCES instance_runs(id) ON DELETE SET NULL")?;
+    ensure_column(conn, "pulls", "key_dungeon", "TEXT")?;
+    ensure_column(conn, "pulls", "key_level", "INTEGER")?;
+    ensure_column(conn, "pulls", "key_affixes", "TEXT")?;
+    ensure_column(conn, "sessions", "account", "TEXT NOT NULL DEFAULT ''")?;
+    ensure_column(conn, "advice_events", "repeat", "INTEGER NOT NULL DEFAULT 0")?;
+    ensure_column(conn, "advice_events", "escalation", "INTEGER NOT NULL DEFAULT 0")?;
+    ensure_column(conn, "sessions", "note", "TEXT NOT NULL DEFAULT ''")?;
+    ensure_column(conn, "pulls", "group_deaths", "INTEGER")?;
+    ensure_column(conn, "pulls", "battle_res", "INTEGER")?;
+    ensure_column(conn, "pulls", "avoidable_hits", "INTEGER")?;
+    ensure_column(conn, "pulls", "boss_damage", "INTEGER")?;
+    ensure_column(conn, "pulls", "encounter_id", "INTEGER")?;
+    sync_encounter_aliases(conn)
+}
+
+/// Replace `encounter_aliases` with the app's current alias table.
+fn sync_encounter_aliases(conn: &Connection) -> Result<()> {
+    let mut wanted = encounters::alias_names();
+    wanted.sort();
+    let mut current: Vec<(String, String)> = conn
+        .prepare("SELECT alias, canonical FROM encounter_aliases")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+    current.sort();
+    if current == wanted {
+        return Ok(());
+    }
+    conn.execute("DELETE FROM encounter_aliases", [])?;
+    for (alias, canonical) in wanted {
+        conn.execute(
+            "INSERT OR REPLACE INTO encounter_aliases (alias, canonical) VALUES (?1, ?2)",
+            params![alias, canonical],
+        )?;
+    }
+    Ok(())
+}
+
+/// SQL expression for the encounter name in `column` under its canonical
+/// name — use it wherever history is matched or grouped by encounter.
+pub fn canonical_encounter(column: &str) -> String {
+    format!("COALESCE((SELECT canonical FROM encounter_aliases WHERE alias = {0}), {0})", column)
+}
+
+/// Snapshot the live database into `path` (VACUUM INTO is consistent even
+/// mid-WAL) and swap the writer's connection over to the copy.  The old file
+/// is left in place for the user to delete.
+fn relocate(conn: &mut Connection, path: &Path) -> Result<()> {
+    if path.exists() {
+        anyhow::bail!("{} already exists — refusing to overwrite it", path.display());
+    }
+    conn.execute("VACUUM INTO ?1", params![path.to_string_lossy()])?;
+    let new_conn = Connection::open(path)?;
+    apply_schema(&new_conn)?;
+    *conn = new_conn;
+    tracing::info!("SQLite writer moved to {:?}", path);
     Ok(())
 }
 
+/// `ALTER TABLE … ADD COLUMN` if `column` is not already present on `table`.
+fn ensure_column(conn: &Connection, table: &str, column: &str, decl: &str) -> Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let exists = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|r| r.ok())
+        .any(|name| name == column);
+    if !exists {
+        conn.execute_batch(&format!("ALTER TABLE {} ADD COLUMN {} {};", table, column, decl))?;
+        tracing::info!("DB migration: added {}.{}", table, column);
+    }
+    Ok(())
+}
+
+/// Write `advice` as the pull's next advice version in one transaction.
+fn insert_advice_set(conn: &mut Connection, pull_id: i64, advice: &[StoredAdvice]) -> Result<u32> {
+    let tx = conn.transaction()?;
+    let version: u32 = tx.query_row(
+        "SELECT COALESCE(MAX(version), 0) + 1 FROM advice_events WHERE pull_id = ?1",
+        params![pull_id],
+        |row| row.get(0),
+    )?;
+    for a in advice {
+        tx.execute(
+            "INSERT INTO advice_events (pull_id, fired_at, rule_key, severity, message, version, repeat, escalation) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![pull_id, a.fired_at, a.rule_key, a.severity, a.message, version, a.repeat, a.escalation],
+        )?;
+    }
+    tx.commit()?;
+    Ok(version)
+}
+
+/// Latest finished pull of `encounter` in the session, with the number of
+/// finished pulls of it so far.
+fn last_attempt(conn: &Connection, session_id: i64, encounter: &str) -> Result<Option<PreviousAttempt>> {
+    let attempts: u32 = conn.query_row(
+        "SELECT COUNT(*) FROM pulls WHERE session_id = ?1 AND encounter = ?2 AND ended_at IS NOT NULL",
+        params![session_id, encounter],
+        |row| row.get(0),
+    )?;
+    if attempts == 0 {
+        return Ok(None);
+    }
+    let previous = conn.query_row(
+        "SELECT outcome, started_at, ended_at, death_at_ms, death_cause FROM pulls \
+         WHERE session_id = ?1 AND encounter = ?2 AND ended_at IS NOT NULL \
+         ORDER BY id DESC LIMIT 1",
+        params![session_id, encounter],
+        |row| {
+            let started_at: u64 = row.get(1)?;
+            let ended_at:   u64 = row.get(2)?;
+            let death_at:   Option<u64>    = row.get(3)?;
+            let cause:      Option<String> = row.get(4)?;
+            Ok(PreviousAttempt {
+                attempts,
+                outcome:     row.get::<_, Option<String>>(0)?.unwrap_or_default(),
+                duration_ms: ended_at.saturating_sub(started_at),
+                death:       death_at.zip(cause).map(|(elapsed_ms, cause)| PullDeath { elapsed_ms, cause }),
+            })
+        },
+    )?;
+    Ok(Some(previous))
+}
+
+/// Pulls of an encounter the wipe forecast learns from, newest first.
+const FORECAST_SAMPLES: u32 = 100;
+
+fn encounter_pulls(conn: &Connection, encounter: &str) -> Result<Vec<PullSample>> {
+    let mut stmt = conn.prepare_cached(&format!(
+        "SELECT outcome, started_at, ended_at, group_deaths, avoidable_hits, boss_damage FROM pulls \
+         WHERE {} = ?1 AND ended_at IS NOT NULL AND outcome IN ('kill', 'wipe') \
+         ORDER BY id DESC LIMIT ?2",
+        canonical_encounter("encounter"),
+    ))?;
+    let rows = stmt.query_map(params![encounter, FORECAST_SAMPLES], |row| {
+        let started_at: u64 = row.get(1)?;
+        let ended_at:   u64 = row.get(2)?;
+        Ok(PullSample {
+            kill:           row.get::<_, String>(0)? == "kill",
+            duration_ms:    ended_at.saturating_sub(started_at),
+            group_deaths:   row.get::<_, Option<u32>>(3)?.unwrap_or(0),
+            avoidable_hits: row.get(4)?,
+            boss_damage:    row.get(5)?,
+        })
+    })?;
+    Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+}
+
+/// Compare a kill with the stored records and write the ones it set or beat.
+fn update_records(conn: &Connection, kill: &Kill) -> Result<Vec<RecordUpdate>> {
+    // Rows under a renamed boss's old name count too; the best value wins.
+    let mut stmt = conn.prepare_cached(&format!(
+        "SELECT record, value FROM personal_records \
+         WHERE player_guid = ?1 AND {} = ?2 AND difficulty_id = ?3",
+        canonical_encounter("encounter"),
+    ))?;
+    let mut current = std::collections::HashMap::new();
+    let rows = stmt
+        .query_map(params![kill.player_guid, kill.encounter, kill.difficulty_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for (kind, value) in rows.into_iter().filter_map(|(key, value)| RecordKind::from_key(&key).map(|kind| (kind, value))) {
+        let best = current.entry(kind).or_insert(value);
+        if kind.beats(value, *best) {
+            *best = value;
+        }
+    }
+
+    let updates = records::improvements(&kill.values, &current);
+    for u in &updates {
+        conn.execute(
+            "INSERT INTO personal_records \
+             (player_guid, player_name, encounter, difficulty_id, record, value, pull_id, set_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8) \
+             ON CONFLICT (player_guid, encounter, difficulty_id, record) DO UPDATE SET \
+             player_name = ?2, value = ?6, pull_id = ?7, set_at = ?8",
+            params![
+                kill.player_guid, kill.player_name, kill.encounter, kill.difficulty_id,
+                u.kind.key(), u.value, kill.pull_id, kill.killed_at,
+            ],
+        )?;
+    }
+    Ok(updates)
+}
+
+// ---------------------------------------------------------------------------
+// Pull archive reads (read-only connection, called from command handlers)
+// ---------------------------------------------------------------------------
+
+/// A recorded pull's archived events and its live advice.
+pub struct PullArchive {
+    pub identity: PlayerIdentity,
+    pub events:   Vec<LogEvent>,
+    /// Advice that fired live (version 0), oldest first.
+    pub live:     Vec<StoredAdvice>,
+}
+
+/// Read a pull's archive (`pull_events` row + version-0 advice).  Used by
+/// re-coaching and the pull image export.
+pub fn load_pull_archive(db_path: &Path, pull_id: i64) -> Result<PullArchive> {
+    let conn = rusqlite::Connection::open_with_flags(
+        db_path,
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY,
+    )?;
+
+    let row = conn.query_row(
+        "SELECT player_guid, player_class, player_spec, events FROM pull_events WHERE pull_id = ?1",
+        [pull_id],
+        |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?,
+                  row.get::<_, String>(2)?, row.get::<_, String>(3)?)),
+    );
+    let (guid, class, spec, json) = match row {
+        Ok(r) => r,
+        Err(rusqlite::Error::QueryReturnedNoRows) => anyhow::bail!(
+            "Pull {} has no archived events — it was recorded before re-coaching was available",
+            pull_id
+        ),
+        Err(e) => return Err(e.into()),
+    };
+    let events: Vec<LogEvent> = serde_json::from_str(&json)
+        .map_err(|e| anyhow::anyhow!("Archived events of pull {} are unreadable: {}", pull_id, e))?;
+
+    let mut stmt = conn.prepare(
+        "SELECT fired_at, rule_key, severity, message, repeat, escalation FROM advice_events \
+         WHERE pull_id = ?1 AND version = 0 ORDER BY fired_at, id",
+    )?;
+    let live = stmt
+        .query_map([pull_id], |row| {
+            Ok(StoredAdvice {
+                fired_at:   row.get::<_, i64>(0)? as u64,
+                rule_key:   row.get(1)?,
+                severity:   row.get(2)?,
+                message:    row.get(3)?,
+                repeat:     row.get(4)?,
+                escalation: row.get(5)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let identity = PlayerIdentity { guid, class, spec, ..PlayerIdentity::unknown() };
+    Ok(PullArchive { identity, events, live })
+}
+
+/// An advice's debug row (`advice_debug`); None when there is none.
+pub fn advice_debug(conn: &Connection, debug_id: &str) -> Result<Option<AdviceDebug>> {
+    let json: Option<String> = conn
+        .query_row("SELECT data FROM advice_debug WHERE debug_id = ?1", [debug_id], |row| row.get(0))
+        .optional()?;
+    Ok(json.map(|j| serde_json::from_str(&j)).transpose()?)
+}
+
 // ---------------------------------------------------------------------------
 // Writer loop (runs on its own std::thread)
 // ---------------------------------------------------------------------------
 
-fn db_writer_loop(rx: std::sync::mpsc::Receiver<DbCommand>, conn: Connection) {
-    while let Ok(cmd) = rx.recv() {
+/// Advice rows buffered for one transaction (see ADVICE_BATCH_WINDOW).
+#[derive(Default)]
+struct AdviceBatch {
+    rows:     Vec<(i64, StoredAdvice)>,
+    /// When to flush; set by the first buffered row.
+    deadline: Option<Instant>,
+}
+
+impl AdviceBatch {
+    fn push(&mut self, pull_id: i64, advice: StoredAdvice) {
+        self.deadline.get_or_insert_with(|| Instant::now() + ADVICE_BATCH_WINDOW);
+        self.rows.push((pull_id, advice));
+    }
+
+    fn flush(&mut self, conn: &mut Connection) {
+        self.deadline = None;
+        if self.rows.is_empty() {
+            return;
+        }
+        let rows = std::mem::take(&mut self.rows);
+        if let Err(e) = insert_advice_rows(conn, &rows) {
+            tracing::warn!("DB insert_advice error ({} rows): {}", rows.len(), e);
+        }
+    }
+}
+
+fn insert_advice_rows(conn: &mut Connection, rows: &[(i64, StoredAdvice)]) -> Result<()> {
+    let tx = conn.transaction()?;
+    {
+        let mut stmt = tx.prepare_cached(
+            "INSERT INTO advice_events (pull_id, fired_at, rule_key, severity, message, repeat, escalation) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        )?;
+        for (pull_id, a) in rows {
+            stmt.execute(params![pull_id, a.fired_at, a.rule_key, a.severity, a.message, a.repeat, a.escalation])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+fn db_writer_loop(rx: std::sync::mpsc::Receiver<DbCommand>, mut conn: Connection, queued: Arc<AtomicUsize>) {
+    let mut batch = AdviceBatch::default();
+    loop {
+        let cmd = match batch.deadline {
+            Some(deadline) => match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(cmd) => cmd,
+                Err(RecvTimeoutError::Timeout) => {
+                    batch.flush(&mut conn);
+                    continue;
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            },
+            None => match rx.recv() {
+                Ok(cmd) => cmd,
+                Err(_)  => break,
+            },
+        };
+        queued.fetch_sub(1, Ordering::Relaxed);
+
+        // Everything else (pull ends, re-coach versions, reads) must see the
+        // advice queued before it.
+        if !matches!(cmd, DbCommand::InsertAdvice { .. }) {
+            batch.flush(&mut conn);
+        }
+
         match cmd {
+            DbCommand::Relocate { reply, path } => {
+                let _ = reply.send(relocate(&mut conn, &path));
+            }
+
             DbCommand::InsertSession { reply, started_at, player_name, player_guid } => {
                 let result = conn
                     .execute(
@@ -194,44 +1224,325 @@ fn db_writer_loop(rx: std::sync::mpsc::Receiver<DbCommand>, conn: Connection) {
                 let _ = reply.send(result);
             }
 
-            DbCommand::UpdateSession { session_id, player_name, player_guid } => {
+            DbCommand::UpdateSession { session_id, player_name, player_guid, account } => {
                 if let Err(e) = conn.execute(
-                    "UPDATE sessions SET player_name = ?1, player_guid = ?2 WHERE id = ?3",
-                    params![player_name, player_guid, session_id],
+                    "UPDATE sessions SET player_name = ?1, player_guid = ?2, account = ?3 WHERE id = ?4",
+                    params![player_name, player_guid, account, session_id],
                 ) {
                     tracing::warn!("DB update_session error: {}", e);
                 }
             }
 
-            DbCommand::InsertPull { reply, session_id, pull_number, started_at } => {
+            DbCommand::EndSession { session_id, ended_at } => {
+                if let Err(e) = conn.execute(
+                    "UPDATE sessions SET ended_at = ?1 WHERE id = ?2",
+                    params![ended_at, session_id],
+                ) {
+                    tracing::warn!("DB end_session error: {}", e);
+                }
+            }
+
+            DbCommand::InsertPull { reply, session_id, pull_number, started_at, run_id, keystone } => {
+                let key_affixes = keystone.as_ref().map(|k| k.affix_names().join(", "));
                 let result = conn
                     .execute(
-                        "INSERT INTO pulls (session_id, pull_number, started_at) VALUES (?1, ?2, ?3)",
-                        params![session_id, pull_number, started_at],
+                        "INSERT INTO pulls (session_id, pull_number, started_at, run_id, key_dungeon, key_level, key_affixes) \
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                        params![
+                            session_id, pull_number, started_at, run_id,
+                            keystone.as_ref().map(|k| &k.dungeon), keystone.as_ref().map(|k| k.level), key_affixes,
+                        ],
                     )
                     .map(|_| conn.last_insert_rowid())
                     .map_err(anyhow::Error::from);
                 let _ = reply.send(result);
             }
 
-            DbCommand::EndPull { pull_id, ended_at, outcome } => {
+            DbCommand::EndPull {
+                pull_id, ended_at, outcome, active_time_pct, boss_damage_pct, interrupt_efficiency,
+            } => {
                 if let Err(e) = conn.execute(
-                    "UPDATE pulls SET ended_at = ?1, outcome = ?2 WHERE id = ?3",
-                    params![ended_at, outcome, pull_id],
+                    "UPDATE pulls SET ended_at = ?1, outcome = ?2, active_time_pct = ?3, \
+                     boss_damage_pct = ?4, interrupt_efficiency = ?5 WHERE id = ?6",
+                    params![
+                        ended_at, outcome, active_time_pct as f64,
+                        boss_damage_pct.map(|p| p as f64), interrupt_efficiency.map(|p| p as f64),
+                        pull_id,
+                    ],
                 ) {
                     tracing::warn!("DB end_pull error: {}", e);
                 }
             }
 
-            DbCommand::InsertAdvice { pull_id, fired_at, rule_key, severity, message } => {
+            DbCommand::RecordAttempt { pull_id, encounter_id, encounter, death, group_deaths, battle_res } => {
                 if let Err(e) = conn.execute(
-                    "INSERT INTO advice_events (pull_id, fired_at, rule_key, severity, message) \
+                    "UPDATE pulls SET encounter = ?1, death_at_ms = ?2, death_cause = ?3, \
+                     group_deaths = ?4, battle_res = ?5, encounter_id = ?6 WHERE id = ?7",
+                    params![
+                        encounter,
+                        death.as_ref().map(|d| d.elapsed_ms),
+                        death.as_ref().map(|d| d.cause.as_str()),
+                        group_deaths,
+                        battle_res,
+                        encounter_id,
+                        pull_id,
+                    ],
+                ) {
+                    tracing::warn!("DB record_attempt error: {}", e);
+                }
+            }
+
+            DbCommand::RecordPullStats { pull_id, avoidable_hits, boss_damage } => {
+                if let Err(e) = conn.execute(
+                    "UPDATE pulls SET avoidable_hits = ?1, boss_damage = ?2 WHERE id = ?3",
+                    params![avoidable_hits, boss_damage, pull_id],
+                ) {
+                    tracing::warn!("DB record_pull_stats error: {}", e);
+                }
+            }
+
+            DbCommand::LastAttempt { reply, session_id, encounter } => {
+                let _ = reply.send(last_attempt(&conn, session_id, &encounter));
+            }
+
+            DbCommand::EncounterPulls { reply, encounter } => {
+                let _ = reply.send(encounter_pulls(&conn, &encounter));
+            }
+
+            DbCommand::UpdateRecords { reply, kill } => {
+                let _ = reply.send(update_records(&conn, &kill));
+            }
+
+            DbCommand::InsertArenaMatch { reply, session_id, started_at, instance_id, bracket, team_id } => {
+                let result = conn
+                    .execute(
+                        "INSERT INTO arena_matches (session_id, started_at, instance_id, bracket, team_id) \
+                         VALUES (?1, ?2, ?3, ?4, ?5)",
+                        params![session_id, started_at, instance_id, bracket, team_id],
+                    )
+                    .map(|_| conn.last_insert_rowid())
+                    .map_err(anyhow::Error::from);
+                let _ = reply.send(result);
+            }
+
+            DbCommand::EndArenaMatch { match_id, ended_at, outcome, duration_s, rating } => {
+                if let Err(e) = conn.execute(
+                    "UPDATE arena_matches SET ended_at = ?1, outcome = ?2, duration_s = ?3, rating = ?4 \
+                     WHERE id = ?5",
+                    params![ended_at, outcome, duration_s, rating, match_id],
+                ) {
+                    tracing::warn!("DB end_arena_match error: {}", e);
+                }
+            }
+
+            DbCommand::InsertDiagnostics { recorded_at, rss_bytes, cpu_pct, channels, state_sizes } => {
+                if let Err(e) = conn.execute(
+                    "INSERT INTO diagnostics (recorded_at, rss_bytes, cpu_pct, channels, state_sizes) \
                      VALUES (?1, ?2, ?3, ?4, ?5)",
-                    params![pull_id, fired_at, rule_key, severity, message],
+                    params![recorded_at, rss_bytes, cpu_pct.map(|p| p as f64), channels, state_sizes],
                 ) {
-                    tracing::warn!("DB insert_advice error: {}", e);
+                    tracing::warn!("DB insert_diagnostics error: {}", e);
+                }
+            }
+
+            DbCommand::StoreAnalytics { name, stamp, computed_at, payload } => {
+                if let Err(e) = conn.execute(
+                    "INSERT INTO analytics_cache (name, stamp, computed_at, payload) VALUES (?1, ?2, ?3, ?4) \
+                     ON CONFLICT (name) DO UPDATE SET stamp = ?2, computed_at = ?3, payload = ?4",
+                    params![name, stamp, computed_at, payload],
+                ) {
+                    tracing::warn!("DB store_analytics error: {}", e);
+                }
+            }
+
+            DbCommand::InsertInstanceRun {
+                reply, session_id, started_at, instance_id, zone_name, difficulty_id, kind,
+            } => {
+                let result = conn
+                    .execute(
+                        "INSERT INTO instance_runs \
+                         (session_id, started_at, instance_id, zone_name, difficulty_id, kind) \
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                        params![session_id, started_at, instance_id, zone_name, difficulty_id, kind],
+                    )
+                    .map(|_| conn.last_insert_rowid())
+                    .map_err(anyhow::Error::from);
+                let _ = reply.send(result);
+            }
+
+            DbCommand::EndInstanceRun { run_id, ended_at } => {
+                if let Err(e) = conn.execute(
+                    "UPDATE instance_runs SET ended_at = ?1 WHERE id = ?2",
+                    params![ended_at, run_id],
+                ) {
+                    tracing::warn!("DB end_instance_run error: {}", e);
+                }
+            }
+
+            DbCommand::BeginPause { session_id, paused_at } => {
+                if let Err(e) = conn.execute(
+                    "INSERT INTO session_pauses (session_id, paused_at) VALUES (?1, ?2)",
+                    params![session_id, paused_at],
+                ) {
+                    tracing::warn!("DB begin_pause error: {}", e);
+                }
+            }
+
+            DbCommand::EndPause { session_id, resumed_at } => {
+                if let Err(e) = conn.execute(
+                    "UPDATE session_pauses SET resumed_at = ?1 \
+                     WHERE session_id = ?2 AND resumed_at IS NULL",
+                    params![resumed_at, session_id],
+                ) {
+                    tracing::warn!("DB end_pause error: {}", e);
+                }
+            }
+
+            DbCommand::InsertDodgeStats { pull_id, encounter, stats } => {
+                for s in stats {
+                    if let Err(e) = conn.execute(
+                        "INSERT INTO mechanic_dodges (pull_id, encounter, spell_id, spell_name, avoided, hit) \
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                        params![pull_id, encounter, s.spell_id, s.spell_name, s.avoided, s.hit],
+                    ) {
+                        tracing::warn!("DB insert_dodge_stats error: {}", e);
+                    }
+                }
+            }
+
+            DbCommand::InsertCastRates { pull_id, rates } => {
+                for r in rates {
+                    if let Err(e) = conn.execute(
+                        "INSERT INTO pull_cast_rates (pull_id, spell_id, spell_name, casts, cpm, underused) \
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                        params![pull_id, r.spell_id, r.spell_name, r.casts, r.cpm, r.underused],
+                    ) {
+                        tracing::warn!("DB insert_cast_rates error: {}", e);
+                    }
+                }
+            }
+
+            DbCommand::InsertAdvice { pull_id, advice } => {
+                batch.push(pull_id, advice);
+            }
+
+            DbCommand::DiscardPull { pull_id } => {
+                if let Err(e) = conn.execute("DELETE FROM pulls WHERE id = ?1", [pull_id]) {
+                    tracing::warn!("DB discard_pull error: {}", e);
+                }
+            }
+
+            DbCommand::InsertDeathRecap { pull_id, recap } => {
+                let result = serde_json::to_string(&recap)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|json| conn.execute(
+                        "INSERT INTO death_recaps (pull_id, elapsed_ms, killing_blow, recap) \
+                         VALUES (?1, ?2, ?3, ?4)",
+                        params![pull_id, recap.elapsed_ms, recap.killing_blow, json],
+                    ).map_err(anyhow::Error::from));
+                if let Err(e) = result {
+                    tracing::warn!("DB insert_death_recap error: {}", e);
+                }
+            }
+
+            DbCommand::InsertKeyRun { session_id, run_id, ended_at, debrief } => {
+                let result = serde_json::to_string(&debrief)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|json| conn.execute(
+                        "INSERT INTO runs (session_id, instance_run_id, ended_at, dungeon, key_level, success, \
+                         total_time_ms, pulls, deaths, avoidable_hits, interrupts, out_of_combat_ms, debrief) \
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                        params![
+                            session_id, run_id, ended_at, debrief.dungeon, debrief.level, debrief.success,
+                            debrief.total_time_ms, debrief.pulls, debrief.deaths, debrief.avoidable_count,
+                            debrief.interrupt_count, debrief.out_of_combat_ms, json,
+                        ],
+                    ).map_err(anyhow::Error::from));
+                if let Err(e) = result {
+                    tracing::warn!("DB insert_key_run error: {}", e);
+                }
+            }
+
+            DbCommand::SavePracticeSession { session_id, started_at, ended_at, spec, report } => {
+                let result = serde_json::to_string(&report)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|json| conn.execute(
+                        "INSERT INTO practice_sessions \
+                         (session_id, started_at, ended_at, spec, pulls, score, report) \
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7) \
+                         ON CONFLICT (session_id, started_at) DO UPDATE SET \
+                         ended_at = ?3, spec = ?4, pulls = ?5, score = ?6, report = ?7",
+                        params![session_id, started_at, ended_at, spec, report.pulls, report.score as f64, json],
+                    ).map_err(anyhow::Error::from));
+                if let Err(e) = result {
+                    tracing::warn!("DB save_practice_session error: {}", e);
+                }
+            }
+
+            DbCommand::ArchivePullEvents { pull_id, identity, events } => {
+                let result = serde_json::to_string(&events)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|json| conn.execute(
+                        "INSERT OR REPLACE INTO pull_events \
+                         (pull_id, player_guid, player_class, player_spec, events) \
+                         VALUES (?1, ?2, ?3, ?4, ?5)",
+                        params![pull_id, identity.guid, identity.class, identity.spec, json],
+                    ).map_err(anyhow::Error::from));
+                if let Err(e) = result {
+                    tracing::warn!("DB archive_pull_events error: {}", e);
+                }
+            }
+
+            DbCommand::InsertAdviceDebug { pull_id, debug } => {
+                let result = serde_json::to_string(&debug)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|json| {
+                        conn.execute(
+                            "INSERT OR REPLACE INTO advice_debug (debug_id, pull_id, rule_key, data) \
+                             VALUES (?1, ?2, ?3, ?4)",
+                            params![debug.debug_id, pull_id, debug.key, json],
+                        )?;
+                        conn.execute(
+                            "DELETE FROM advice_debug WHERE rowid <= (SELECT MAX(rowid) FROM advice_debug) - ?1",
+                            [ADVICE_DEBUG_KEEP],
+                        )?;
+                        Ok(())
+                    });
+                if let Err(e) = result {
+                    tracing::warn!("DB insert_advice_debug error: {}", e);
+                }
+            }
+
+            DbCommand::InsertAdviceSet { reply, pull_id, advice } => {
+                let _ = reply.send(insert_advice_set(&mut conn, pull_id, &advice));
+            }
+
+            DbCommand::SetSessionNote { session_id, text } => {
+                if let Err(e) = conn.execute(
+                    "UPDATE sessions SET note = ?1 WHERE id = ?2",
+                    params![text, session_id],
+                ) {
+                    tracing::warn!("DB set_session_note error: {}", e);
+                }
+            }
+
+            DbCommand::AddJournalEntry { reply, session_id, created_at, text } => {
+                let result = conn
+                    .execute(
+                        "INSERT INTO journal_entries (session_id, created_at, text) VALUES (?1, ?2, ?3)",
+                        params![session_id, created_at, text],
+                    )
+                    .map(|_| conn.last_insert_rowid())
+                    .map_err(anyhow::Error::from);
+                let _ = reply.send(result);
+            }
+
+            DbCommand::DeleteJournalEntry { entry_id } => {
+                if let Err(e) = conn.execute("DELETE FROM journal_entries WHERE id = ?1", [entry_id]) {
+                    tracing::warn!("DB delete_journal_entry error: {}", e);
                 }
             }
         }
     }
+    batch.flush(&mut conn);
 }
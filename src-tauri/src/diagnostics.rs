@@ -0,0 +1,235 @@
+/// Long-running session self-monitoring.
+///
+/// Raid nights keep the app running for six hours or more, and several
+/// subsystems (the rolling event window, per-pull trackers, name caches) are
+/// suspected of slow growth.  Once a minute this task samples:
+///
+///   - process RSS (memory-stats) and CPU % since the previous sample
+///     (cpu-time; normalised to all cores, like Task Manager)
+///   - messages queued on each pipeline channel, plus the DB writer's queue
+///     (`db_queue`) and its count of dropped low-priority writes (`db_dropped`)
+///   - the engine's collection sizes (via `EngineCommand::ReportSizes`) and
+///     the IPC queues in managed state
+///
+/// Every sample is stored in the `diagnostics` table.  `LeakWatch` logs a
+/// warning when a metric has grown steadily across the last `LEAK_WINDOW`
+/// samples — a coarse signal that says which subsystem to profile, not proof
+/// of a leak.
+use cpu_time::ProcessTime;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::{clock::unix_now_ms, db::DbWriter, engine, ipc};
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(60);
+/// How long to wait for the engine to answer `ReportSizes`.
+const ENGINE_REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+/// Samples (minutes) a metric must keep growing over before it is reported.
+const LEAK_WINDOW: usize = 30;
+/// Growth factor between the start and end of the window that counts as a leak.
+const LEAK_GROWTH: f64 = 1.5;
+/// Minimum absolute growth before alerting — ignores small collections.
+const LEAK_MIN_RSS_BYTES: u64 = 64 * 1024 * 1024;
+const LEAK_MIN_ENTRIES:   u64 = 1_000;
+
+// ---------------------------------------------------------------------------
+// Channel probes
+// ---------------------------------------------------------------------------
+
+/// Queue-depth reader for one pipeline channel.  Holds a weak sender so the
+/// probe never keeps a channel open after its real senders are gone.
+pub struct ChannelProbe {
+    name:  &'static str,
+    depth: Box<dyn Fn() -> Option<usize> + Send>,
+}
+
+impl ChannelProbe {
+    pub fn new<T: Send + 'static>(name: &'static str, tx: &mpsc::Sender<T>) -> Self {
+        let weak = tx.downgrade();
+        Self {
+            name,
+            depth: Box::new(move || weak.upgrade().map(|tx| tx.max_capacity() - tx.capacity())),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// CPU sampling
+// ---------------------------------------------------------------------------
+
+struct CpuSampler {
+    last:  Option<(ProcessTime, Instant)>,
+    cores: f64,
+}
+
+impl CpuSampler {
+    fn new() -> Self {
+        let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+        Self { last: None, cores: cores as f64 }
+    }
+
+    /// Process CPU % since the previous call (None on the first call).
+    fn sample(&mut self) -> Option<f32> {
+        let now = (ProcessTime::try_now().ok()?, Instant::now());
+        let pct = self.last.and_then(|(cpu, wall)| {
+            let wall_s = now.1.duration_since(wall).as_secs_f64();
+            (wall_s > 0.0).then(|| {
+                (now.0.duration_since(cpu).as_secs_f64() / wall_s / self.cores * 100.0) as f32
+            })
+        });
+        self.last = Some(now);
+        pct
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Leak heuristic
+// ---------------------------------------------------------------------------
+
+/// Sliding history per metric.  A metric is flagged when the lowest value in
+/// the last third of the window is at least `LEAK_GROWTH` times the highest
+/// value in the first third — sustained growth, not a spike.  Per-pull
+/// trackers that reset between pulls form a sawtooth and never qualify.
+#[derive(Debug, Default)]
+struct LeakWatch {
+    history: HashMap<String, VecDeque<u64>>,
+}
+
+impl LeakWatch {
+    /// Record `value`; returns a warning message when `name` looks like a leak.
+    fn observe(&mut self, name: &str, value: u64, min_growth: u64) -> Option<String> {
+        let h = self.history.entry(name.to_owned()).or_default();
+        h.push_back(value);
+        if h.len() > LEAK_WINDOW {
+            h.pop_front();
+        }
+        if h.len() < LEAK_WINDOW {
+            return None;
+        }
+        let third = LEAK_WINDOW / 3;
+        let early = h.iter().take(third).copied().max()?;
+        let late  = h.iter().skip(LEAK_WINDOW - third).copied().min()?;
+        if late < early.saturating_add(min_growth) || (late as f64) < early as f64 * LEAK_GROWTH {
+            return None;
+        }
+        // Start over so the same growth is reported once per window.
+        h.clear();
+        Some(format!(
+            "{} grew from {} to {} over the last {} min — possible leak",
+            name, early, late, LEAK_WINDOW * SAMPLE_INTERVAL.as_secs() as usize / 60
+        ))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Entry point
+// ---------------------------------------------------------------------------
+
+/// Sample forever (spawned by `try_start_pipeline` alongside the pipeline).
+pub async fn run(probes: Vec<ChannelProbe>, db: DbWriter, app: AppHandle) {
+    let mut ticker = tokio::time::interval(SAMPLE_INTERVAL);
+    ticker.tick().await; // the first tick completes immediately
+    let mut cpu   = CpuSampler::new();
+    let mut watch = LeakWatch::default();
+
+    loop {
+        ticker.tick().await;
+
+        let rss_bytes = memory_stats::memory_stats().map(|m| m.physical_mem as u64);
+        let cpu_pct   = cpu.sample();
+        let mut channels: Vec<(&str, usize)> = probes.iter()
+            .filter_map(|p| Some((p.name, (p.depth)()?)))
+            .collect();
+        channels.push(("db_queue",   db.queue_depth()));
+        channels.push(("db_dropped", db.dropped_writes() as usize));
+        let mut sizes = engine_sizes(&app).await;
+        sizes.extend(ipc_sizes(&app));
+
+        if let Some(rss) = rss_bytes {
+            if let Some(msg) = watch.observe("rss_bytes", rss, LEAK_MIN_RSS_BYTES) {
+                tracing::warn!("Diagnostics: {}", msg);
+            }
+        }
+        for (name, len) in &sizes {
+            if let Some(msg) = watch.observe(name, *len as u64, LEAK_MIN_ENTRIES) {
+                tracing::warn!("Diagnostics: {}", msg);
+            }
+        }
+
+        tracing::debug!(
+            "Diagnostics: rss={:?} cpu={:?}% channels={:?} sizes={:?}",
+            rss_bytes, cpu_pct, channels, sizes
+        );
+        db.insert_diagnostics(unix_now_ms(), rss_bytes, cpu_pct, to_json(&channels), to_json(&sizes));
+    }
+}
+
+/// Ask the engine for its collection sizes (empty if it does not answer).
+async fn engine_sizes(app: &AppHandle) -> Vec<(&'static str, usize)> {
+    let tx = app.try_state::<Mutex<Option<mpsc::Sender<engine::EngineCommand>>>>()
+        .and_then(|s| s.lock().ok().and_then(|g| g.clone()));
+    let Some(tx) = tx else { return Vec::new() };
+
+    let (reply_tx, reply_rx) = oneshot::channel();
+    if tx.send(engine::EngineCommand::ReportSizes(reply_tx)).await.is_err() {
+        return Vec::new();
+    }
+    match tokio::time::timeout(ENGINE_REPLY_TIMEOUT, reply_rx).await {
+        Ok(Ok(sizes)) => sizes,
+        _ => {
+            tracing::warn!("Diagnostics: engine did not report state sizes");
+            Vec::new()
+        }
+    }
+}
+
+/// Lengths of the IPC queues the frontend drains.
+fn ipc_sizes(app: &AppHandle) -> Vec<(&'static str, usize)> {
+    let mut out = Vec::new();
+    if let Some(q) = app.try_state::<Mutex<VecDeque<engine::AdviceEvent>>>() {
+        if let Ok(q) = q.lock() {
+            out.push(("advice_queue", q.len()));
+        }
+    }
+    if let Some(q) = app.try_state::<Mutex<ipc::EventLogQueue>>() {
+        if let Ok(q) = q.lock() {
+            out.push(("event_log_queue", q.len()));
+        }
+    }
+    out
+}
+
+fn to_json(pairs: &[(&str, usize)]) -> String {
+    let map: serde_json::Map<String, serde_json::Value> = pairs.iter()
+        .map(|(k, v)| ((*k).to_owned(), (*v).into()))
+        .collect();
+    serde_json::Value::Object(map).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steady_growth_is_flagged_once() {
+        let mut watch = LeakWatch::default();
+        let alerts: Vec<usize> = (0..LEAK_WINDOW * 2)
+            .filter(|i| watch.observe("event_window", 1_000 + *i as u64 * 200, LEAK_MIN_ENTRIES).is_some())
+            .collect();
+        // Flagged as soon as the window fills; the history restarts afterwards.
+        assert_eq!(alerts, vec![LEAK_WINDOW - 1]);
+    }
+
+    #[test]
+    fn per_pull_sawtooth_is_not_flagged() {
+        let mut watch = LeakWatch::default();
+        for i in 0..LEAK_WINDOW as u64 * 3 {
+            // Grows for 8 minutes, resets at the next pull.
+            let len = (i % 8) * 2_000;
+            assert!(watch.observe("damage_taken", len, LEAK_MIN_ENTRIES).is_none());
+        }
+    }
+}
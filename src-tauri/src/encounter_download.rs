@@ -0,0 +1,203 @@
+/// On-demand encounter data — fetches the encounter file of a boss with no
+/// local one from the community data repository, mid-raid if need be.
+///
+/// At an ENCOUNTER_START that `encounters::load` cannot resolve, the engine
+/// sends an `EncounterRequest` (once per encounter ID and session) to `run`.
+/// The repository publishes
+///
+///   encounters/<encounter_id>.toml      the encounter file
+///   encounters/<encounter_id>.toml.sig  raw 64-byte Ed25519 signature of it
+///
+/// A file is only used when the signature verifies against `SIGNING_KEY` and
+/// it parses with the `encounter_id` it was requested for.  Verified files
+/// are cached with their signature under `<app config>/encounters/` and
+/// checked again when read back, so the network is hit once per boss.
+/// `encounters::register` makes the profile visible to the engine, which
+/// switches to it on its next event — the rest of the pull is coached with
+/// the boss's avoidable spells and timeline.
+///
+/// Off when `AppConfig.encounter_downloads` is false, which is the default:
+/// the data repository is not published yet.
+///
+/// Signing key: the private half is an Ed25519 PKCS#8 PEM kept only as the
+/// `ENCOUNTER_SIGNING_KEY` secret of this repository, never in the tree.
+/// The release workflow (.github/workflows/build.yml, "Sign encounter
+/// files") checks it against `SIGNING_KEY`, signs every `data/encounters`
+/// file that sets an `encounter_id`, and attaches them in the layout above
+/// as `encounter-data.zip`, which is what the data repository publishes.
+/// Without the secret the step is skipped with a warning.
+///
+/// `SIGNING_KEY` is a development key whose private half no maintainer
+/// holds.  Before the data repository goes live, the maintainers replace it
+/// with their own pair as below.
+///
+/// Setting up or rotating the key (on a leak, or to hand over signing):
+///   1. `openssl genpkey -algorithm ed25519 -out key.pem`
+///   2. `openssl pkey -in key.pem -pubout -outform DER | tail -c 32 | xxd -i`
+///      gives the bytes for `SIGNING_KEY`
+///   3. store key.pem as the `ENCOUNTER_SIGNING_KEY` secret, delete the
+///      local copy, and cut a release — it ships the new key and re-signs
+///      the files
+///   4. publish that release's `encounter-data.zip` to the data repository
+///
+/// Builds with the old key reject the re-signed files and simply coach
+/// without boss data until updated; their cached files keep verifying.
+use anyhow::Result;
+use ring::signature::{UnparsedPublicKey, ED25519};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use tokio::sync::mpsc::Receiver;
+
+use crate::{config, encounters, ipc};
+
+/// Root of the community data repository (raw file access).
+const REPO_RAW_URL: &str = "https://raw.githubusercontent.com/MFredin/CombatCoaching-data/main";
+/// Verified downloads, inside the app config dir.
+const CACHE_DIR: &str = "encounters";
+/// Ed25519 public key the repository's encounter files are signed with
+/// (raw 32 bytes; see the module doc for where the private key lives).
+const SIGNING_KEY: [u8; 32] = [
+    0x6f, 0xf7, 0x70, 0x82, 0xe2, 0xfc, 0xfa, 0x4e, 0x57, 0x69, 0x62, 0xc2, 0xae, 0xa7, 0x77, 0x52,
+    0x36, 0xcb, 0x74, 0xbd, 0xcd, 0x52, 0x7b, 0xac, 0xaa, 0xbc, 0xc8, 0xb7, 0x1e, 0x15, 0xfd, 0xc4,
+];
+/// Encounter files are a few KB; anything far larger is not one.
+const MAX_FILE_BYTES: u64 = 256 * 1024;
+
+/// An encounter the engine has no data for.
+#[derive(Debug, Clone)]
+pub struct EncounterRequest {
+    pub encounter_id: u32,
+    /// ENCOUNTER_START name, for the Event Feed.
+    pub name:         String,
+}
+
+/// Check `signature` over `data` against an Ed25519 public key.
+fn verify_with(public_key: &[u8], data: &[u8], signature: &[u8]) -> Result<()> {
+    UnparsedPublicKey::new(&ED25519, public_key)
+        .verify(data, signature)
+        .map_err(|_| anyhow::anyhow!("signature does not match"))
+}
+
+/// Verify and parse an encounter file fetched for `encounter_id`.
+fn accept(public_key: &[u8], encounter_id: u32, raw: &[u8], signature: &[u8]) -> Result<encounters::EncounterProfile> {
+    verify_with(public_key, raw, signature)?;
+    let (id, profile) = encounters::parse_str(std::str::from_utf8(raw)?)?;
+    if id != encounter_id {
+        anyhow::bail!("file is for encounter {}, not {}", id, encounter_id);
+    }
+    Ok(profile)
+}
+
+fn cache_paths(dir: &Path, encounter_id: u32) -> (PathBuf, PathBuf) {
+    (dir.join(format!("{}.toml", encounter_id)), dir.join(format!("{}.toml.sig", encounter_id)))
+}
+
+/// GET `url`; None when the repository has no such file.
+fn fetch(url: &str) -> Result<Option<Vec<u8>>> {
+    let response = match ureq::get(url).call() {
+        Ok(r) => r,
+        Err(ureq::Error::Status(404, _)) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let mut body = Vec::new();
+    response.into_reader().take(MAX_FILE_BYTES + 1).read_to_end(&mut body)?;
+    if body.len() as u64 > MAX_FILE_BYTES {
+        anyhow::bail!("{} is larger than {} bytes", url, MAX_FILE_BYTES);
+    }
+    Ok(Some(body))
+}
+
+/// The encounter's profile from the cache, else from the repository (and
+/// then cached).  None when the repository has no file for it.
+fn obtain(dir: &Path, encounter_id: u32) -> Result<Option<encounters::EncounterProfile>> {
+    let (toml_path, sig_path) = cache_paths(dir, encounter_id);
+    if let (Ok(raw), Ok(sig)) = (std::fs::read(&toml_path), std::fs::read(&sig_path)) {
+        match accept(&SIGNING_KEY, encounter_id, &raw, &sig) {
+            Ok(profile) => return Ok(Some(profile)),
+            Err(e) => tracing::warn!("Encounter cache {:?} rejected, downloading again: {}", toml_path, e),
+        }
+    }
+
+    let url = format!("{}/{}/{}.toml", REPO_RAW_URL, CACHE_DIR, encounter_id);
+    let Some(raw) = fetch(&url)? else { return Ok(None) };
+    let sig = fetch(&format!("{}.sig", url))?
+        .ok_or_else(|| anyhow::anyhow!("{} has no signature", url))?;
+    let profile = accept(&SIGNING_KEY, encounter_id, &raw, &sig)?;
+
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(&toml_path, &raw)?;
+    std::fs::write(&sig_path, &sig)?;
+    Ok(Some(profile))
+}
+
+/// Fetch the files of `encounter_ids` ahead of the first pull (quick
+/// setup) and register them.  Returns how many were loaded; bosses the
+/// repository has no file for are skipped.
+pub fn prefetch(config_dir: &Path, encounter_ids: &[u32]) -> usize {
+    let dir = config_dir.join(CACHE_DIR);
+    encounter_ids.iter()
+        .filter(|&&id| match obtain(&dir, id) {
+            Ok(Some(profile)) => { encounters::register(id, profile); true }
+            Ok(None)          => false,
+            Err(e)            => { tracing::warn!("Encounter data for {} not used: {}", id, e); false }
+        })
+        .count()
+}
+
+/// Serve the engine's requests until the pipeline shuts down.
+pub async fn run(mut request_rx: Receiver<EncounterRequest>, app: AppHandle) {
+    while let Some(request) = request_rx.recv().await {
+        tauri::async_runtime::spawn(handle(app.clone(), request));
+    }
+}
+
+/// Resolve a request off the async runtime and register what it finds.
+async fn handle(app: AppHandle, request: EncounterRequest) {
+    let Ok(config_dir) = app.path().app_config_dir() else { return };
+    let enabled = config::load_or_default(&config_dir).is_ok_and(|cfg| cfg.encounter_downloads);
+    if !enabled {
+        return;
+    }
+    let dir = config_dir.join(CACHE_DIR);
+    let id = request.encounter_id;
+    let result = tauri::async_runtime::spawn_blocking(move || obtain(&dir, id)).await;
+    match result {
+        Ok(Ok(Some(profile))) => {
+            encounters::register(id, profile);
+            ipc::log_event(&app, &format!("📥 Encounter data for {} loaded — boss coaching is on", request.name));
+        }
+        Ok(Ok(None)) => tracing::info!("No community encounter file for {} ({})", request.name, id),
+        Ok(Err(e))   => tracing::warn!("Encounter data for {} ({}) not used: {}", request.name, id, e),
+        Err(e)       => tracing::warn!("Encounter download task failed: {}", e),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::{rand::SystemRandom, signature::{Ed25519KeyPair, KeyPair}};
+
+    const FILE: &[u8] = b"[encounter]\nname = \"Test Boss\"\nencounter_id = 4242\n";
+
+    #[test]
+    fn accepts_only_signed_files_for_the_requested_encounter() {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let keys = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let public = keys.public_key().as_ref();
+        let sig = keys.sign(FILE);
+
+        let profile = accept(public, 4242, FILE, sig.as_ref()).expect("valid file");
+        assert_eq!(profile.name, "Test Boss");
+
+        // Wrong encounter, tampered file, production key.
+        assert!(accept(public, 1, FILE, sig.as_ref()).is_err());
+        let mut tampered = FILE.to_vec();
+        tampered.extend_from_slice(b"boss_npc_ids = [1]\n");
+        assert!(accept(public, 4242, &tampered, sig.as_ref()).is_err());
+        assert!(accept(&SIGNING_KEY, 4242, FILE, sig.as_ref()).is_err());
+    }
+}
@@ -0,0 +1,558 @@
+/// Encounter library — embedded at compile time from `data/encounters/*.toml`.
+///
+/// Each file describes one boss encounter: which spells are avoidable or
+/// interruptible, and an optional ability timeline (`[[encounter.timeline]]`)
+/// listing when the boss casts its scheduled abilities relative to
+/// ENCOUNTER_START.  The timeline drives pre-emptive coaching such as the
+/// spike_forecast rule ("big damage in 5s") and the overlay pull clock's
+/// phase label / next-event countdown ("P2 — Breath in 00:08").  An
+/// optional `lust_phase` names the phase the group should Bloodlust in
+/// (lust_timing rule).
+///
+/// Encounters are matched by ENCOUNTER_START's encounter ID when the file
+/// sets `encounter_id`, otherwise by encounter name (case-insensitive).
+///
+/// Bosses without an embedded file can get one at runtime: the engine asks
+/// encounter_download.rs for it, which registers the verified file here
+/// (`register`).  Embedded files win over downloaded ones.
+///
+/// Dungeon files (`data/dungeons/*.toml`, a `[dungeon]` table keyed by the
+/// ZONE_CHANGE / CHALLENGE_MODE_START instance ID) list the avoidable
+/// mechanics of a dungeon's trash, so avoidable_repeat can be gated outside
+/// boss fights too.
+///
+/// Bosses renamed or re-IDed by a patch are mapped to one canonical name and
+/// ID by `data/encounter_aliases.toml` (`canonical`): the engine files pulls
+/// under the canonical name, `load` matches files by it, and the database
+/// groups older rows by it (db::canonical_encounter).
+///
+/// Users can add or override both kinds in `<app config>/encounter_data/`
+/// (`load_user_dir` + `install_user`, read with the rule files at pipeline
+/// start and on every config reload); a user file wins over the embedded
+/// one for the same encounter or instance.
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    RwLock,
+};
+
+// ---------------------------------------------------------------------------
+// Embedded TOML data — one const per encounter, alphabetical by file name
+// ---------------------------------------------------------------------------
+
+const EXAMPLE_ENCOUNTER: &str = include_str!("../../data/encounters/example_encounter.toml");
+
+static ALL_ENCOUNTER_DATA: &[&str] = &[
+    EXAMPLE_ENCOUNTER,
+];
+
+const EXAMPLE_DUNGEON: &str = include_str!("../../data/dungeons/example_dungeon.toml");
+
+static ALL_DUNGEON_DATA: &[&str] = &[
+    EXAMPLE_DUNGEON,
+];
+
+const ENCOUNTER_ALIASES: &str = include_str!("../../data/encounter_aliases.toml");
+
+/// User encounter and dungeon files, inside the app config dir.
+pub const USER_DIR: &str = "encounter_data";
+
+// ---------------------------------------------------------------------------
+// TOML deserialization structs (private)
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+struct TomlFile {
+    encounter: TomlEncounter,
+}
+
+#[derive(Deserialize)]
+struct TomlEncounter {
+    name:         String,
+    #[serde(default)]
+    encounter_id: u32,
+    #[serde(default)]
+    timeline:     Vec<TimelineEntry>,
+    #[serde(default)]
+    phases:       Vec<Phase>,
+    #[serde(default)]
+    avoidable_spells: TomlAvoidable,
+    #[serde(default)]
+    boss_npc_ids: Vec<u32>,
+    #[serde(default)]
+    lust_phase:   Option<String>,
+    #[serde(default)]
+    cc_spell_ids: Vec<u32>,
+    #[serde(default)]
+    priority_npc_ids: Vec<u32>,
+    #[serde(default)]
+    tank_heal_share_pct: Option<f32>,
+}
+
+#[derive(Deserialize, Default)]
+struct TomlAvoidable {
+    #[serde(default)]
+    avoidable_spell_ids: Vec<u32>,
+}
+
+#[derive(Deserialize)]
+struct TomlDungeonFile {
+    dungeon: DungeonProfile,
+}
+
+// ---------------------------------------------------------------------------
+// Public types
+// ---------------------------------------------------------------------------
+
+/// What kind of damage a scheduled ability deals — decides who gets warned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AbilityKind {
+    /// Unavoidable group-wide damage: healers and tanks are pre-warned.
+    RaidAoe,
+    /// Heavy hit on the active tank: tanks are pre-warned.
+    TankBuster,
+    #[default]
+    Other,
+}
+
+/// One scheduled boss ability.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TimelineEntry {
+    pub spell_id: u32,
+    pub name:     String,
+    #[serde(default)]
+    pub kind:     AbilityKind,
+    /// Seconds after ENCOUNTER_START of the first cast.
+    pub first_s:  f32,
+    /// Seconds between repeat casts; absent for one-off abilities.
+    #[serde(default)]
+    pub repeat_s: Option<f32>,
+}
+
+impl TimelineEntry {
+    /// Index and encounter-relative time (ms) of the first cast at or after
+    /// `elapsed_ms`, or None if the ability will not be cast again.
+    pub fn next_cast(&self, elapsed_ms: u64) -> Option<(u32, u64)> {
+        let first = (self.first_s * 1_000.0) as u64;
+        if elapsed_ms <= first {
+            return Some((0, first));
+        }
+        let repeat = (self.repeat_s? * 1_000.0) as u64;
+        if repeat == 0 {
+            return None;
+        }
+        let n = (elapsed_ms - first).div_ceil(repeat);
+        Some((n as u32, first + n * repeat))
+    }
+}
+
+/// A named encounter phase, entered `start_s` seconds after ENCOUNTER_START.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Phase {
+    pub name:    String,
+    pub start_s: f32,
+}
+
+/// A fully-parsed encounter profile used by the engine.
+#[derive(Debug, Clone)]
+pub struct EncounterProfile {
+    pub name:     String,
+    pub timeline: Vec<TimelineEntry>,
+    /// Phases; the one with the latest `start_s` already reached is active.
+    pub phases:   Vec<Phase>,
+    /// Avoidable mechanics — drive the per-mechanic dodge-rate statistic.
+    pub avoidable_spell_ids: Vec<u32>,
+    /// NPC IDs of the boss unit(s) — boss-damage share; empty = guess.
+    pub boss_npc_ids: Vec<u32>,
+    /// Phase name in which Bloodlust/Heroism should be used (lust_timing rule).
+    pub lust_phase:   Option<String>,
+    /// Stuns, fears etc. the boss puts on players — GCD gaps under them are
+    /// attributed to crowd control rather than idling.
+    pub cc_spell_ids: Vec<u32>,
+    /// Adds that must die first — damage on anything else while one is up
+    /// is tunnel vision (tunnel_vision rule).
+    pub priority_npc_ids: Vec<u32>,
+    /// Share of a healer's effective healing expected on the tanks, in %
+    /// (tank_healing rule); None = the rule's default.
+    pub tank_heal_share_pct: Option<f32>,
+}
+
+/// Avoidable trash mechanics of one dungeon (`[dungeon]` table).
+#[derive(Debug, Clone, Deserialize)]
+pub struct DungeonProfile {
+    pub name:        String,
+    /// ZONE_CHANGE / CHALLENGE_MODE_START instance ID.
+    pub instance_id: u32,
+    #[serde(default)]
+    pub avoidable_spell_ids: Vec<u32>,
+}
+
+impl EncounterProfile {
+    /// Label of the phase active at `elapsed_ms`, if the encounter defines phases.
+    pub fn phase_at(&self, elapsed_ms: u64) -> Option<&str> {
+        self.phases
+            .iter()
+            .filter(|p| (p.start_s * 1_000.0) as u64 <= elapsed_ms)
+            .max_by(|a, b| a.start_s.total_cmp(&b.start_s))
+            .map(|p| p.name.as_str())
+    }
+
+    /// Soonest scheduled ability at or after `elapsed_ms`, with ms until it lands.
+    pub fn next_event(&self, elapsed_ms: u64) -> Option<(&TimelineEntry, u64)> {
+        self.timeline
+            .iter()
+            .filter_map(|e| e.next_cast(elapsed_ms).map(|(_, due)| (e, due - elapsed_ms)))
+            .min_by_key(|(_, remaining)| *remaining)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+/// Parse one encounter TOML document into (encounter_id, profile).
+/// Also used by the data-file validator.
+pub fn parse_str(toml_str: &str) -> Result<(u32, EncounterProfile), toml::de::Error> {
+    let file: TomlFile = toml::from_str(toml_str)?;
+    Ok((file.encounter.encounter_id, EncounterProfile {
+        name:     file.encounter.name,
+        timeline: file.encounter.timeline,
+        phases:   file.encounter.phases,
+        avoidable_spell_ids: file.encounter.avoidable_spells.avoidable_spell_ids,
+        boss_npc_ids: file.encounter.boss_npc_ids,
+        lust_phase:   file.encounter.lust_phase,
+        cc_spell_ids: file.encounter.cc_spell_ids,
+        priority_npc_ids: file.encounter.priority_npc_ids,
+        tank_heal_share_pct: file.encounter.tank_heal_share_pct,
+    }))
+}
+
+/// Parse one dungeon TOML document.
+pub fn parse_dungeon_str(toml_str: &str) -> Result<DungeonProfile, toml::de::Error> {
+    Ok(toml::from_str::<TomlDungeonFile>(toml_str)?.dungeon)
+}
+
+fn parse_all() -> Vec<(u32, EncounterProfile)> {
+    ALL_ENCOUNTER_DATA
+        .iter()
+        .filter_map(|toml_str| {
+            parse_str(toml_str)
+                .map_err(|e| tracing::warn!("Failed to parse encounter TOML: {}", e))
+                .ok()
+        })
+        .collect()
+}
+
+/// Raw TOML of every embedded encounter (the validator's self-check).
+#[cfg(test)]
+pub fn embedded_sources() -> &'static [&'static str] {
+    ALL_ENCOUNTER_DATA
+}
+
+/// Raw TOML of every embedded dungeon file (the validator's self-check).
+#[cfg(test)]
+pub fn embedded_dungeon_sources() -> &'static [&'static str] {
+    ALL_DUNGEON_DATA
+}
+
+/// Encounter files downloaded this run, by encounter ID.
+static DOWNLOADED: Lazy<RwLock<HashMap<u32, EncounterProfile>>> = Lazy::new(Default::default);
+/// Bumped by every `register`, so a waiting engine knows to look again.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Make a downloaded encounter file available to `load`.
+pub fn register(encounter_id: u32, profile: EncounterProfile) {
+    if let Ok(mut downloaded) = DOWNLOADED.write() {
+        downloaded.insert(encounter_id, profile);
+        GENERATION.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Changes whenever a downloaded encounter file is registered or the user
+/// files are reloaded.
+pub fn generation() -> u64 {
+    GENERATION.load(Ordering::Relaxed)
+}
+
+/// The profile for an ENCOUNTER_START among `all` (ID first, then name,
+/// both compared after alias resolution).
+fn find(all: &[(u32, EncounterProfile)], encounter_id: u32, encounter_name: &str) -> Option<EncounterProfile> {
+    let (id, name) = canonical(encounter_id, encounter_name);
+    all.iter()
+        .find(|(file_id, p)| id != 0 && canonical(*file_id, &p.name).0 == id)
+        .or_else(|| all.iter().find(|(file_id, p)| canonical(*file_id, &p.name).1.eq_ignore_ascii_case(&name)))
+        .map(|(_, p)| p.clone())
+}
+
+/// Load the encounter profile for an ENCOUNTER_START (user files, embedded
+/// files, then downloaded ones).
+pub fn load(encounter_id: u32, encounter_name: &str) -> Option<EncounterProfile> {
+    if let Some(profile) = USER.read().ok().and_then(|u| find(&u.encounters, encounter_id, encounter_name)) {
+        return Some(profile);
+    }
+    find(&parse_all(), encounter_id, encounter_name)
+        .or_else(|| DOWNLOADED.read().ok()?.get(&encounter_id).cloned())
+}
+
+/// The dungeon file for an instance ID (user files, then embedded ones).
+pub fn load_dungeon(instance_id: u32) -> Option<DungeonProfile> {
+    if instance_id == 0 {
+        return None;
+    }
+    if let Some(dungeon) = USER.read().ok().and_then(|u| u.dungeons.iter().find(|d| d.instance_id == instance_id).cloned()) {
+        return Some(dungeon);
+    }
+    ALL_DUNGEON_DATA
+        .iter()
+        .filter_map(|toml_str| {
+            parse_dungeon_str(toml_str)
+                .map_err(|e| tracing::warn!("Failed to parse dungeon TOML: {}", e))
+                .ok()
+        })
+        .find(|d| d.instance_id == instance_id)
+}
+
+// ---------------------------------------------------------------------------
+// Aliases
+// ---------------------------------------------------------------------------
+
+/// One renamed or re-IDed boss (`[[alias]]`).
+#[derive(Debug, Clone, Deserialize)]
+struct Alias {
+    canonical:     String,
+    #[serde(default)]
+    canonical_id:  u32,
+    #[serde(default)]
+    names:         Vec<String>,
+    #[serde(default)]
+    encounter_ids: Vec<u32>,
+}
+
+#[derive(Deserialize)]
+struct AliasFile {
+    #[serde(default)]
+    alias: Vec<Alias>,
+}
+
+fn parse_aliases(toml_str: &str) -> Vec<Alias> {
+    toml::from_str::<AliasFile>(toml_str)
+        .map(|f| f.alias)
+        .map_err(|e| tracing::warn!("Failed to parse encounter aliases: {}", e))
+        .unwrap_or_default()
+}
+
+static ALIASES: Lazy<Vec<Alias>> = Lazy::new(|| parse_aliases(ENCOUNTER_ALIASES));
+
+fn resolve(aliases: &[Alias], encounter_id: u32, encounter_name: &str) -> (u32, String) {
+    let alias = aliases.iter().find(|a| {
+        (encounter_id != 0 && (a.encounter_ids.contains(&encounter_id) || a.canonical_id == encounter_id))
+            || a.canonical.eq_ignore_ascii_case(encounter_name)
+            || a.names.iter().any(|n| n.eq_ignore_ascii_case(encounter_name))
+    });
+    match alias {
+        Some(a) => (if a.canonical_id != 0 { a.canonical_id } else { encounter_id }, a.canonical.clone()),
+        None    => (encounter_id, encounter_name.to_owned()),
+    }
+}
+
+/// Canonical (encounter ID, name) of an encounter; unchanged when it has no
+/// alias entry.
+pub fn canonical(encounter_id: u32, encounter_name: &str) -> (u32, String) {
+    resolve(&ALIASES, encounter_id, encounter_name)
+}
+
+/// (old name, canonical name) of every aliased name, for the database's
+/// `encounter_aliases` table.
+pub fn alias_names() -> Vec<(String, String)> {
+    ALIASES.iter()
+        .flat_map(|a| a.names.iter().map(move |n| (n.clone(), a.canonical.clone())))
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// User files
+// ---------------------------------------------------------------------------
+
+/// Encounter and dungeon files read from `USER_DIR`.
+#[derive(Default)]
+pub struct UserData {
+    encounters: Vec<(u32, EncounterProfile)>,
+    dungeons:   Vec<DungeonProfile>,
+}
+
+impl UserData {
+    /// Number of files loaded.
+    pub fn files(&self) -> usize {
+        self.encounters.len() + self.dungeons.len()
+    }
+}
+
+static USER: Lazy<RwLock<UserData>> = Lazy::new(Default::default);
+
+/// Parse every `*.toml` in `dir` (alphabetical) as an encounter (`[encounter]`)
+/// or dungeon (`[dungeon]`) file.  Returns what parsed and one message per
+/// file left out.
+pub fn load_user_dir(dir: &Path) -> (UserData, Vec<String>) {
+    let mut paths: Vec<_> = std::fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|e| e.path()).collect())
+        .unwrap_or_default();
+    paths.retain(|p| p.extension().is_some_and(|ext| ext == "toml"));
+    paths.sort();
+
+    let mut data   = UserData::default();
+    let mut errors = Vec::new();
+    for path in paths {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        let raw = match std::fs::read_to_string(&path) {
+            Ok(raw) => raw,
+            Err(e)  => { errors.push(format!("{}: {}", file_name, e)); continue; }
+        };
+        let parsed = if raw.lines().any(|l| l.trim() == "[dungeon]") {
+            parse_dungeon_str(&raw).map(|d| data.dungeons.push(d))
+        } else {
+            parse_str(&raw).map(|e| data.encounters.push(e))
+        };
+        if let Err(e) = parsed {
+            errors.push(format!("{}: {}", file_name, e));
+        }
+    }
+    (data, errors)
+}
+
+/// Replace the user files `load` and `load_dungeon` consult.  An encounter
+/// still waiting for data (see `generation`) picks them up on its next event.
+pub fn install_user(data: UserData) {
+    if let Ok(mut user) = USER.write() {
+        *user = data;
+        GENERATION.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMELINE_TOML: &str = r#"
+        [encounter]
+        name         = "Test Boss"
+        encounter_id = 9999
+        lust_phase   = "P2"
+        cc_spell_ids = [3]
+        priority_npc_ids = [214000]
+        tank_heal_share_pct = 40.0
+
+        [[encounter.timeline]]
+        spell_id = 1
+        name     = "Nova"
+        kind     = "raid_aoe"
+        first_s  = 10.0
+        repeat_s = 30.0
+
+        [[encounter.timeline]]
+        spell_id = 2
+        name     = "Enrage"
+        first_s  = 300.0
+
+        [encounter.avoidable_spells]
+        avoidable_spell_ids = [1]
+
+        [[encounter.phases]]
+        name    = "P1"
+        start_s = 0.0
+
+        [[encounter.phases]]
+        name    = "P2"
+        start_s = 120.0
+    "#;
+
+    fn test_profile() -> EncounterProfile {
+        parse_str(TIMELINE_TOML).unwrap().1
+    }
+
+    #[test]
+    fn example_encounter_parses() {
+        let p = load(0, "training dummy").expect("example should load");
+        assert!(p.timeline.is_empty());
+    }
+
+    #[test]
+    fn parses_timeline_entries() {
+        let file: TomlFile = toml::from_str(TIMELINE_TOML).unwrap();
+        let tl = file.encounter.timeline;
+        assert_eq!(tl.len(), 2);
+        assert_eq!(tl[0].kind, AbilityKind::RaidAoe);
+        assert_eq!(tl[1].kind, AbilityKind::Other);
+        assert!(tl[1].repeat_s.is_none());
+        assert_eq!(file.encounter.avoidable_spells.avoidable_spell_ids, vec![1]);
+    }
+
+    #[test]
+    fn next_cast_follows_repeat_schedule() {
+        let file: TomlFile = toml::from_str(TIMELINE_TOML).unwrap();
+        let nova = &file.encounter.timeline[0];
+        assert_eq!(nova.next_cast(0),      Some((0, 10_000)));
+        assert_eq!(nova.next_cast(10_001), Some((1, 40_000)));
+        assert_eq!(nova.next_cast(40_000), Some((1, 40_000)));
+        let enrage = &file.encounter.timeline[1];
+        assert_eq!(enrage.next_cast(300_001), None);
+    }
+
+    #[test]
+    fn phase_and_next_event() {
+        let p = test_profile();
+        assert_eq!(p.lust_phase.as_deref(), Some("P2"));
+        assert_eq!(p.cc_spell_ids, vec![3]);
+        assert_eq!(p.priority_npc_ids, vec![214000]);
+        assert_eq!(p.tank_heal_share_pct, Some(40.0));
+        assert_eq!(p.phase_at(5_000),   Some("P1"));
+        assert_eq!(p.phase_at(130_000), Some("P2"));
+        let (next, in_ms) = p.next_event(32_000).unwrap();
+        assert_eq!(next.name, "Nova");
+        assert_eq!(in_ms, 8_000);
+        let (next, in_ms) = p.next_event(295_000).unwrap();
+        assert_eq!(next.name, "Enrage");
+        assert_eq!(in_ms, 5_000);
+    }
+
+    #[test]
+    fn user_dir_reads_encounter_and_dungeon_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("boss.toml"), TIMELINE_TOML).unwrap();
+        std::fs::write(
+            dir.path().join("dungeon.toml"),
+            "[dungeon]\nname = \"Vault\"\ninstance_id = 2652\navoidable_spell_ids = [5, 6]\n",
+        ).unwrap();
+        std::fs::write(dir.path().join("broken.toml"), "[dungeon]\nname = \"No ID\"\n").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let (data, errors) = load_user_dir(dir.path());
+        assert_eq!(data.files(), 2);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("broken.toml"));
+        assert_eq!(data.dungeons[0].avoidable_spell_ids, vec![5, 6]);
+        assert_eq!(find(&data.encounters, 0, "test boss").map(|p| p.name), Some("Test Boss".to_owned()));
+    }
+
+    #[test]
+    fn aliases_resolve_old_names_and_ids() {
+        let aliases = parse_aliases(r#"
+            [[alias]]
+            canonical     = "New Name"
+            canonical_id  = 20
+            names         = ["Old Name"]
+            encounter_ids = [10]
+        "#);
+        assert_eq!(resolve(&aliases, 10, "Whatever"), (20, "New Name".to_owned()));
+        assert_eq!(resolve(&aliases, 0, "old name"), (20, "New Name".to_owned()));
+        assert_eq!(resolve(&aliases, 20, "New Name"), (20, "New Name".to_owned()));
+        assert_eq!(resolve(&aliases, 7, "Other Boss"), (7, "Other Boss".to_owned()));
+        assert!(toml::from_str::<AliasFile>(ENCOUNTER_ALIASES).is_ok());
+    }
+}
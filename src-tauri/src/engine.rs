@@ -8,34 +8,171 @@
 ///   bad    → 8s minimum between firings of the same key
 ///   warn   → 12s
 ///   good   → 20s
+/// Urgent-priority advice (spike_forecast, self_sustain) ignores a cooldown
+/// left by a lower-priority firing of the same key, and ipc.rs delivers it
+/// ahead of queued advice.
+///
+/// Repeat offenses escalate: the third fire of the same Warn/Bad key in a
+/// pull goes out as Bad with a terse message ("VOID NOVA — 3 times this
+/// pull"), the fifth also as urgent.  Each row's repeat number and
+/// escalation level are stored with the advice.
 ///
 /// GUID inference: if the addon is not installed, the engine infers the
 /// player GUID from the first SPELL_CAST_SUCCESS whose source_name matches
 /// the `player_focus` character name stored in AppConfig.
 ///
-/// Two evaluation passes per event:
-///   Pass 1 — enemy events (interrupt_miss): runs on all in-combat events,
-///             the rule itself filters for enemy SpellCastSuccess.
-///   Pass 2 — coached player events: gated by is_coached_event(), includes
-///             avoidable_repeat, gcd_gap, cooldown_drift, interrupt_success,
-///             defensive_timing.
+/// Spec detection: COMBATANT_INFO (logged for the group at every encounter
+/// start) carries each member's spec ID.  Once the player's GUID is known,
+/// its spec is taken from there and the matching profile auto-loaded — no
+/// addon needed, and a respec is picked up at the next pull instead of the
+/// next SavedVariables write.
+///
+/// Encounter data: a boss with no encounter file is requested once per
+/// session from encounter_download.rs; if a file arrives while the pull is
+/// still running, the engine switches to it on the next event or tick.
+///
+/// Wallclock alignment: every AdviceEvent and StateSnapshot carries a
+/// `wallclock_ms` derived from the log timestamp via the shared ClockAligner
+/// (see clock.rs), so the overlay's Date.now()-based timers don't drift.
+///
+/// Pause/resume: `EngineCommand::Pause` suspends rule evaluation, pull
+/// debriefs and DB pull rows while the state machine keeps tracking combat,
+/// so coaching resumes cleanly mid-session.  Paused intervals are stored in
+/// `session_pauses`; the journal subtracts them from a session's active time
+/// and pulls per hour (journal.rs).
+///
+/// Open-world combat timeout: outside an encounter nothing in the log marks
+/// the end of a pull, so one ends after COMBAT_TIMEOUT_MS without a player
+/// cast.  The check runs on every event and on a timer armed for the
+/// deadline, with log time extrapolated from the last event, so the pull
+/// closes (and gets its debrief) even when the log goes quiet.  A replay
+/// scales that extrapolation by its speed and holds it while paused.
+///
+/// Pull archive: the events of every recorded pull are buffered and stored
+/// at pull end (`pull_events`), so `replay_pull` can re-run the rule passes
+/// over them later with updated rules (recoach.rs).
+///
+/// Pre-pull check: the coached player's casts outside encounters are kept
+/// for the last 30 seconds, across pull boundaries (state::PrepullTracker).
+/// An encounter pull's debrief says whether a combat potion and one of the
+/// spec's pre-cast spells (`prepull_spell_ids`) went out before
+/// ENCOUNTER_START.
+///
+/// Wipe forecast: at ENCOUNTER_START a WipeModel is trained on the stored
+/// pulls of the encounter (wipe_forecast.rs), and every in-combat snapshot
+/// carries its estimate for the pull so far (`wipe_pct`).
+///
+/// Instance runs: ZONE_CHANGE events feed a RunTracker (runs.rs); each new
+/// dungeon run or raid gets an `instance_runs` row and the pulls recorded
+/// inside it carry its id, so history can group them by instance.
+///
+/// Mythic+ keys: from CHALLENGE_MODE_START to CHALLENGE_MODE_END every
+/// tracked pull is added to a key summary (key_run.rs); the key's end sends
+/// a KeyDebrief and stores it in the `runs` table.
+///
+/// Encounter settings: while an encounter listed in
+/// `AppConfig.encounter_settings` is active (usually from a strategy preset,
+/// presets.rs), its intensity, disabled rules and kick assignments apply.
+/// The coached character's `CharacterProfile` applies underneath at all
+/// times; a character identified for the first time gets one generated from
+/// its role (role_defaults.rs), which `run` sends out to be saved.
+///
+/// Practice: a pull fought only against training dummies runs under the
+/// Practice rule profile (gcd_gap and cooldown_drift only) and, at pull end,
+/// is scored into a practice session (practice.rs) — its pull row is deleted
+/// and it gets no pull number, attempt record or archive.
+///
+/// Five evaluation passes per event.  Passes 1–3 are the built-in rules,
+/// dispatched by the rule registry (rules/registry.rs) to the rules
+/// subscribed to the event's kind and scope; rules disabled for the
+/// encounter are skipped up front:
+///   Pass 1 — enemy events: interrupt_miss on enemy SpellCastSuccess, and
+///             kick_alert (a kickable enemy cast in progress while the kick
+///             is ready).  Skipped under the Solo and Practice rule
+///             profiles.
+///   Pass 2 — coached player events (Scope::Player): avoidable_repeat,
+///             avoidable_dodged (an avoidable mechanic that hit the player
+///             earlier now avoided), resource_waste, gcd_gap,
+///             cooldown_drift, cancelled_cast (a hard cast abandoned part
+///             way), moving_cast (casts failing because the player moved),
+///             interrupt_success, defensive_timing, defensive_missing (a big
+///             hit taken with no defensive up and one ready),
+///             self_sustain (Solo profile only), consumable_usage (potion
+///             or healthstone left unused at low health or on death), and
+///             death_recap on the player's own death.  Practice keeps gcd_gap and
+///             cooldown_drift.  In group coaching mode, group_coaching
+///             (Scope::Party) gives every other player in the group the same
+///             avoidable and death coaching, tagged with their name.
+///   Pass 3 — time-driven rules, on every in-combat event: spike_forecast
+///             (encounter timeline pre-warnings for healers/tanks),
+///             dot_uptime (DoTs dropped off the boss), tunnel_vision
+///             (priority adds left alone), melee_uptime (a melee player out
+///             of range of the boss), cooldown_available (a major CD sitting
+///             ready) and, on encounter pulls, tank_healing (healer's tank
+///             share below the encounter's expectation) and raid_buffs (a
+///             raid buff the group brings missing a few seconds in).
+///             lust_timing also runs here: the lust buff comes from another
+///             player's cast.
+///             A one-second engine tick re-runs the time-driven rules (and
+///             kick_alert) while the log is quiet, with log time
+///             extrapolated from the last event.
+///   Pass 4 — user-authored rules (rules/custom.rs) and rule scripts
+///             (rules/script.rs) on every in-combat event, except under the
+///             Practice profile.
+///   Pass 5 — raid-lead callouts (rules/raid_callouts.rs): avoidable hits on
+///             the whole group, batched; only with `raid_lead_mode` on.  The
+///             engine tick flushes a waiting batch too, and the pull's
+///             per-member summary goes into the debrief (`raid_summary`).
+///
+/// Outside the passes, a pull that starts with ENCOUNTER_START looks up the
+/// session's previous attempt at that encounter and fires progression_nudge
+/// ("Last pull: died to X at 2:10") alongside the first event's advice, and
+/// a kill is checked against the personal records (records.rs), firing
+/// personal_record when one is beaten.
 use crate::{
-    config::AppConfig,
-    db::DbWriter,
+    advice_debug,
+    clock::{unix_now_ms, ClockAligner, LogClock},
+    config::{self, AdviceCooldowns, AppConfig, CharacterProfile, EncounterSettings},
+    consumables,
+    countdown,
+    db::{self, DbWriter, PreviousAttempt, StoredAdvice},
+    encounter_download::EncounterRequest,
+    encounters::{self, DungeonProfile, EncounterProfile},
+    guid::{self, UnitKind},
     identity::PlayerIdentity,
-    ipc::{PullDebrief, StateSnapshot},
-    parser::LogEvent,
+    ipc::{
+        BattleResEntry, BossDamage, BurstAlignment, CancelledCasts, ConsumableUse, DeathRecap, DodgeStat,
+        FailedCastCount, FriendlyFire, GapCauses, GroupDeathEntry, HealingDistribution, MeleeUptime, PartyDamage,
+        KeyDebrief, KeystoneInfo, PrepullCheck, PullDebrief, ScheduledEvent, StateSnapshot, TargetSwitch,
+    },
+    key_run::KeyRun,
+    parser::{self, average_item_level, LogEvent},
+    practice::{PracticeReport, PracticeSession},
+    raid_buffs,
+    readiness,
+    records::{self, Kill, RecordKind, RecordUpdate},
+    role_defaults::{self, GeneratedProfile},
+    rotation,
     rules::{
-        avoidable_repeat, cooldown_drift, defensive_timing, gcd_gap,
-        interrupt_miss, interrupt_success, RuleContext, RuleInput,
+        self, custom::CustomRules, death_recap, personal_record, progression_nudge, raid_callouts::RaidCallouts,
+        registry::{RuleBus, RuleParams}, script::ScriptRules, RuleContext, RuleInput, RuleProfile,
     },
-    specs,
-    state::{CombatState, PullOutcome},
+    runs::RunTracker,
+    specs::{self, PrioritySpell},
+    spells,
+    state::{self, ArenaMatch, CombatState, DamageSource, Keystone, PullOutcome},
+    units::NumberFormat,
+    wipe_forecast::{LiveProgress, WipeModel},
 };
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use tokio::sync::mpsc::{Receiver, Sender};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::{
+    sync::{mpsc::{Receiver, Sender}, oneshot},
+    time::MissedTickBehavior,
+};
 
 // ---------------------------------------------------------------------------
 // Public types
@@ -49,56 +186,290 @@ pub enum Severity {
     Bad,
 }
 
+/// Delivery priority, independent of severity.  Urgent advice (lethal
+/// mechanic incoming, about to die) is not held back by cooldowns set by
+/// lower-priority advice and is delivered ahead of anything queued.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    /// Informational notices (what's new, praise) — never preempt.
+    Info,
+    #[default]
+    Normal,
+    Urgent,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdviceEvent {
     pub key:          String,
     pub title:        String,
     pub message:      String,
     pub severity:     Severity,
+    #[serde(default)]
+    pub priority:     Priority,
     pub kv:           Vec<(String, String)>,
     pub timestamp_ms: u64,
+    /// Unix-epoch ms of the triggering log line (clock-aligned when possible).
+    #[serde(default)]
+    pub wallclock_ms: u64,
+    /// Optional positioning hint so the overlay can draw an arrow instead of
+    /// relying on text alone.  None for rules without positional meaning.
+    #[serde(default)]
+    pub hint:         Option<PositionHint>,
+    /// Group member the advice is about (group coaching mode); None for the
+    /// coached player.
+    #[serde(default)]
+    pub player:       Option<String>,
+    /// Nth fire of this key in the pull (0 for advice fired outside the
+    /// per-event dedup: pull-end notices, nudges).
+    #[serde(default)]
+    pub repeat:       u32,
+    /// Repeat-offense escalation applied (see `escalate`): 0 none, 1 severity
+    /// raised with a terse message, 2 also urgent.
+    #[serde(default)]
+    pub escalation:   u8,
+    /// Key of the advice's debug row (`get_advice_debug`); None unless
+    /// advice debug capture is on.
+    #[serde(default)]
+    pub debug_id:     Option<String>,
+}
+
+impl AdviceEvent {
+    /// Attach a positioning hint (builder-style, used by rules after `advice()`).
+    /// No rule attaches one yet: a useful hint ("behind boss", "spread 8yd")
+    /// needs per-mechanic data the encounter files do not carry.
+    #[allow(dead_code)]
+    pub fn with_hint(mut self, hint: PositionHint) -> Self {
+        self.hint = Some(hint);
+        self
+    }
+
+    /// Tag the group member the advice is about (builder-style).
+    pub fn with_player(mut self, name: &str) -> Self {
+        self.player = Some(name.to_owned());
+        self
+    }
+
+    /// Set the delivery priority (builder-style; `advice()` defaults to Normal).
+    pub fn with_priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+/// Which way the player should move relative to a mechanic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HintDirection {
+    /// Move out of a ground effect / away from the hit source.
+    Out,
+    /// Move into a soak zone or towards the group.
+    In,
+    Behind,
+    Front,
+    Spread,
+    Stack,
 }
 
+/// Structured positioning hint rendered by the overlay as a minimal arrow.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionHint {
+    pub direction:   HintDirection,
+    /// Suggested distance in yards, if the mechanic radius is known.
+    pub distance_yd: Option<u32>,
+    /// Short mechanic/zone descriptor, e.g. the spell name or "behind boss".
+    pub zone:        Option<String>,
+}
+
+/// Control messages from Tauri commands (and the diagnostics task) to the
+/// running engine.
+#[derive(Debug)]
+pub enum EngineCommand {
+    Pause,
+    Resume,
+    /// Reply with the sizes of the engine's long-lived collections.
+    ReportSizes(oneshot::Sender<Vec<(&'static str, usize)>>),
+    /// Advance log time between events at this many log ms per wallclock
+    /// ms; 0 holds it.  Sent by a replay on pause, resume and speed changes.
+    LogRate(u64),
+}
+
+/// Upper bound on events archived per pull (roughly a long raid pull); keeps
+/// the archive row and the in-memory buffer bounded.
+const MAX_ARCHIVED_EVENTS: usize = 200_000;
+
+/// Open-world pulls end after this long without a player cast.
+const COMBAT_TIMEOUT_MS: u64 = 10_000;
+
+/// Interval of the engine tick that runs the time-driven rules between events.
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+
 // ---------------------------------------------------------------------------
 // Advice dedup / cooldown
 // ---------------------------------------------------------------------------
 
-fn advice_cooldown_ms(severity: &Severity) -> u64 {
-    match severity {
-        Severity::Bad  =>  8_000,
-        Severity::Warn => 12_000,
-        Severity::Good => 20_000,
+/// Hold-back of an advice key: its rule's override from the config, else
+/// its severity's.
+fn advice_cooldown_ms(cooldowns: &AdviceCooldowns, advice: &AdviceEvent) -> u64 {
+    let rule_s = rules::rule_of(&advice.key).and_then(|rule| cooldowns.rules.get(rule));
+    let secs = rule_s.copied().unwrap_or(match advice.severity {
+        Severity::Bad  => cooldowns.bad_s,
+        Severity::Warn => cooldowns.warn_s,
+        Severity::Good => cooldowns.good_s,
+    });
+    u64::from(secs) * 1_000
+}
+
+/// Fire of the same key within a pull from which it escalates: severity one
+/// step up (Warn → Bad) and a terse, emphasized message.
+const ESCALATE_AT: u32 = 3;
+/// Fire from which escalated advice is also delivered as urgent.
+const ESCALATE_URGENT_AT: u32 = 5;
+
+/// Praise and informational notices never escalate — repeating those is
+/// not an offense.
+fn escalates(advice: &AdviceEvent) -> bool {
+    !matches!(advice.severity, Severity::Good) && advice.priority != Priority::Info
+}
+
+/// Priority the `repeat`-th fire of a key this pull is delivered with.
+fn escalated_priority(advice: &AdviceEvent, repeat: u32) -> Priority {
+    if repeat >= ESCALATE_URGENT_AT && escalates(advice) {
+        Priority::Urgent
+    } else {
+        advice.priority
     }
 }
 
+/// Escalate the `repeat`-th fire of a key this pull.
+fn escalate(mut advice: AdviceEvent, repeat: u32) -> AdviceEvent {
+    advice.repeat = repeat;
+    if repeat < ESCALATE_AT || !escalates(&advice) {
+        return advice;
+    }
+    let subject = advice.kv.iter()
+        .find(|(k, _)| k == "spell")
+        .map_or(advice.title.as_str(), |(_, v)| v.as_str())
+        .to_uppercase();
+    advice.message    = format!("{} — {} times this pull. Fix it now.", subject, repeat);
+    advice.severity   = Severity::Bad;
+    advice.escalation = 1;
+    if repeat >= ESCALATE_URGENT_AT {
+        advice.priority   = Priority::Urgent;
+        advice.escalation = 2;
+    }
+    advice
+}
+
 struct EngineState {
     combat:              CombatState,
     identity:            PlayerIdentity,
     config:              AppConfig,
-    advice_last_ms:      HashMap<String, u64>,
+    /// Last fire time and priority per advice key.
+    advice_last_ms:      HashMap<String, (u64, Priority)>,
     db:                  DbWriter,
     session_id:          i64,
     current_pull_id:     Option<i64>,
+    /// DB row of the arena match in progress (ARENA_MATCH_START → END).
+    current_match_id:    Option<i64>,
+    /// Dungeon run / raid the session is in (fed by ZONE_CHANGE).
+    runs:                RunTracker,
+    /// DB row of the current instance run; pulls inside it are tagged with it.
+    current_run_id:      Option<i64>,
     pull_number:         u32,
     /// Resolved major CD IDs — from spec profile (auto-detected or user-selected).
     /// Falls back to `config.major_cds` if no spec profile is loaded.
     effective_major_cds: Vec<u32>,
     /// Resolved active mitigation IDs — from spec profile.
     effective_am_spells: Vec<u32>,
+    /// Opener of the loaded spec profile (practice scoring), or empty.
+    opener_spell_ids:    Vec<u32>,
+    /// DoTs of the loaded spec profile (dot_uptime rule), or empty.
+    dot_spell_ids:       Vec<u32>,
+    /// Pre-cast spells of the loaded spec profile (pre-pull check), or empty.
+    prepull_spell_ids:   Vec<u32>,
+    /// Base cooldowns (s) of the loaded spec profile (cooldown_available rule).
+    cooldown_s:          HashMap<u32, u32>,
+    /// Rotation priority spells of the loaded spec profile (cast efficiency).
+    priority_spells:     Vec<PrioritySpell>,
+    /// Role from the loaded spec profile ("TANK" / "HEALER" / "DAMAGER"), or empty.
+    role:                String,
+    /// Profile (incl. ability timeline) of the active encounter, if one is known.
+    encounter:           Option<EncounterProfile>,
+    /// Dungeon file of the current instance (trash avoidable mechanics).
+    dungeon:             Option<DungeonProfile>,
+    /// Avoidable spells of the active encounter and dungeon; None when
+    /// neither lists any, and avoidable_repeat counts every repeated hit.
+    known_avoidable:     Option<Vec<u32>>,
+    /// Active encounter with no profile yet, and the `encounters::generation`
+    /// it was last looked up at; a download may still supply it.
+    awaiting_encounter:  Option<(EncounterRequest, u64)>,
+    /// Encounter IDs already asked for this session (one request each).
+    requested_encounters: HashSet<u32>,
+    /// Encounter data request left by `apply_event` for `run` to send out.
+    pending_fetch:       Option<EncounterRequest>,
     /// Character name extracted from `config.player_focus` for GUID inference.
     focus_name:          String,
     /// Passive name→GUID cache for all Player-* sources seen while player is unidentified.
     /// Populated from SpellCastSuccess events; checked on config hot-update so the GUID
     /// can be resolved immediately when player_focus is set after combat has already begun.
     player_name_cache:   HashMap<String, String>,
+    /// Latest COMBATANT_INFO spec ID per player GUID (spec detection).
+    combatant_specs:     HashMap<String, u32>,
     /// Total advice events fired this pull (for debrief).
     pull_advice_count:   u32,
     /// GCD gap advice events fired this pull (for debrief).
     pull_gcd_gap_count:  u32,
+    /// The same, by likely cause.
+    pull_gap_causes:     GapCauses,
+    /// Summed time major cooldowns sat ready before being used this pull
+    /// (for debrief; `CooldownTracker::drift_ms`).
+    pull_cd_drift_ms:    u64,
+    /// Shared log→wallclock offset estimate, fed by the tailer.
+    clock:               Arc<ClockAligner>,
+    /// Rule profile used for the last evaluation (logged on change).
+    rule_profile:        RuleProfile,
+    /// Advice given a `debug_id` so far (`AppConfig.advice_debug`).
+    debug_seq:           u64,
+    /// Unix ms when coaching was paused; None while coaching is active.
+    paused_since:        Option<u64>,
+    /// False when the current pull started while paused — it gets no DB row,
+    /// no pull number, and no debrief.
+    pull_tracked:        bool,
+    /// Log timestamp of the most recent event (for out-of-band snapshots).
+    last_log_ms:         u64,
+    /// When the most recent event arrived (tick scheduling).
+    last_event_at:       Instant,
+    /// Log time between events (`log_now_ms`).
+    log_clock:           LogClock,
+    /// Events of the tracked pull in progress, archived at pull end so the
+    /// pull can be re-coached later (`replay_pull`).
+    pull_events:         Vec<LogEvent>,
+    /// Consecutive training-dummy pulls so far; None after a real pull.
+    practice:            Option<PracticeSession>,
+    /// Boss kill `finish_pull` left for `run` to check against the personal
+    /// records (the check awaits the DB).
+    pending_kill:        Option<Kill>,
+    /// Trained at ENCOUNTER_START from the encounter's stored pulls.
+    wipe_model:          Option<WipeModel>,
+    /// Mythic+ key in progress (CHALLENGE_MODE_START → END).
+    key_run:             Option<KeyRun>,
+    /// Role defaults generated by `set_identity`, left for `run` to send out.
+    pending_profile:     Option<GeneratedProfile>,
+    /// Voice pack files of the countdown, resolved per config change.
+    countdown_sounds:    Vec<String>,
+    /// Built-in rules indexed by the events they subscribe to.
+    rule_bus:            RuleBus,
+    /// User-authored rules (rules/custom.rs) and their per-pull counts.
+    custom_rules:        CustomRules,
+    /// User rule scripts (rules/script.rs) and their per-pull state.
+    script_rules:        ScriptRules,
+    /// Group avoidable hits for raid-lead mode (rules/raid_callouts.rs).
+    raid_callouts:       RaidCallouts,
 }
 
 impl EngineState {
-    fn new(config: AppConfig, db: DbWriter, session_id: i64) -> Self {
+    fn new(config: AppConfig, db: DbWriter, session_id: i64, clock: Arc<ClockAligner>) -> Self {
         // If a spec was pre-selected in config, resolve CDs immediately.
         let (effective_major_cds, effective_am_spells) = if !config.selected_spec.is_empty() {
             if let Some(profile) = specs::load_by_key(&config.selected_spec) {
@@ -112,6 +483,11 @@ impl EngineState {
             (Vec::new(), Vec::new())
         };
 
+        let (role, opener_spell_ids, dot_spell_ids, prepull_spell_ids, cooldown_s, priority_spells) =
+            specs::load_by_key(&config.selected_spec)
+                .map(|p| (p.role, p.opener_spell_ids, p.dot_spell_ids, p.prepull_spell_ids, p.cooldown_s, p.priority_spells))
+                .unwrap_or_default();
+
         // Extract just the character name from "Name-Realm" format.
         let focus_name = config
             .player_focus
@@ -120,32 +496,944 @@ impl EngineState {
             .unwrap_or("")
             .to_owned();
 
+        let mut combat = CombatState::new();
+        combat.group_coaching = config.group_coaching;
+        combat.friendly_avoidable = config.avoidable_friendly_fire;
+
         Self {
-            combat:              CombatState::new(),
+            combat,
             identity:            PlayerIdentity::unknown(),
             advice_last_ms:      HashMap::new(),
             db,
             session_id,
             current_pull_id:     None,
+            current_match_id:    None,
+            runs:                RunTracker::default(),
+            current_run_id:      None,
             pull_number:         0,
             effective_major_cds,
             effective_am_spells,
+            opener_spell_ids,
+            dot_spell_ids,
+            prepull_spell_ids,
+            cooldown_s,
+            priority_spells,
+            role,
+            encounter:           None,
+            dungeon:             None,
+            known_avoidable:     None,
             focus_name,
             player_name_cache:   HashMap::new(),
+            combatant_specs:     HashMap::new(),
             pull_advice_count:   0,
             pull_gcd_gap_count:  0,
+            pull_gap_causes:     GapCauses::default(),
+            pull_cd_drift_ms:    0,
+            clock,
+            rule_profile:        RuleProfile::Group,
+            debug_seq:           0,
+            paused_since:        None,
+            pull_tracked:        false,
+            last_log_ms:         0,
+            last_event_at:       Instant::now(),
+            log_clock:           LogClock::new(Instant::now()),
+            pull_events:         Vec::new(),
+            practice:            None,
+            pending_kill:        None,
+            wipe_model:          None,
+            key_run:             None,
+            pending_profile:     None,
+            awaiting_encounter:  None,
+            requested_encounters: HashSet::new(),
+            pending_fetch:       None,
+            countdown_sounds:    countdown::voice_files(&config.countdown),
+            rule_bus:            RuleBus::new(),
+            custom_rules:        CustomRules::default(),
+            script_rules:        ScriptRules::default(),
+            raid_callouts:       RaidCallouts::default(),
             config,
         }
     }
 
-    fn can_fire(&self, key: &str, severity: &Severity, now_ms: u64) -> bool {
-        let cooldown = advice_cooldown_ms(severity);
-        let last     = self.advice_last_ms.get(key).copied().unwrap_or(0);
-        now_ms.saturating_sub(last) >= cooldown
+    /// Per-key cooldown.  Advice delivered as urgent — `priority` is the one
+    /// after escalation — is only held back by an earlier urgent fire of the
+    /// same key, never by a lower-priority one.
+    fn can_fire(&self, advice: &AdviceEvent, priority: Priority, now_ms: u64) -> bool {
+        let (last, last_priority) = self.advice_last_ms
+            .get(&advice.key)
+            .copied()
+            .unwrap_or((0, Priority::Normal));
+        if priority == Priority::Urgent && last_priority != Priority::Urgent {
+            return true;
+        }
+        now_ms.saturating_sub(last) >= advice_cooldown_ms(&self.config.advice_cooldowns, advice)
+    }
+
+    fn mark_fired(&mut self, advice: &AdviceEvent, now_ms: u64) {
+        self.advice_last_ms.insert(advice.key.clone(), (now_ms, advice.priority));
+    }
+
+    /// Wallclock time for a log timestamp: clock-aligned if enabled and an
+    /// offset has been observed, otherwise the current system time.
+    fn wallclock_ms(&self, log_ts_ms: u64) -> u64 {
+        if self.config.clock_alignment {
+            if let Some(ms) = self.clock.to_wallclock(log_ts_ms) {
+                return ms;
+            }
+        }
+        unix_now_ms()
+    }
+
+    fn is_paused(&self) -> bool {
+        self.paused_since.is_some()
+    }
+
+    /// Lengths of the collections that live across pulls or grow within one,
+    /// for the diagnostics task's leak check.
+    fn state_sizes(&self) -> Vec<(&'static str, usize)> {
+        let c = &self.combat;
+        vec![
+            ("event_window",      c.event_window.events.len()),
+            ("avoidable_hits",    c.avoidable.hit_timestamps.values().map(Vec::len).sum()),
+            ("dodge_mechanics",   c.dodge.mechanics.len()),
+            ("damage_taken",      c.damage_taken.events.len()),
+            ("damage_targets",    c.damage_done.targets.len()),
+            ("group_guids",       c.group.len()),
+            ("party_members",     c.party_damage.members.len()),
+            ("healed_targets",    c.healing.targets.len()),
+            ("priority_targets",  c.targets.priority.len()),
+            ("burst_seconds",     c.burst.per_second.len()),
+            ("player_auras",      c.auras.active.len() + c.auras.expired.len()),
+            ("aura_uptimes",      c.buffs.auras.len()),
+            ("interruptible",     c.interrupts.interruptible_spells.len()),
+            ("pull_history",      c.pull_history.len()),
+            ("pull_events",       self.pull_events.len()),
+            ("practice_casts",    c.practice.cast_count()),
+            ("advice_last_ms",    self.advice_last_ms.len()),
+            ("player_name_cache", self.player_name_cache.len()),
+            ("combatant_specs",   self.combatant_specs.len()),
+            ("requested_encounters", self.requested_encounters.len()),
+            ("custom_rule_hits",  self.custom_rules.tracked()),
+        ]
+    }
+
+    /// Adopt a new player identity and auto-load its spec profile unless the
+    /// user has selected one explicitly.
+    fn set_identity(&mut self, identity: PlayerIdentity) {
+        tracing::info!("Identity updated → {}/{}", identity.name, identity.spec);
+        self.combat.player_guid = Some(identity.guid.clone());
+
+        // Auto-load spec profile if user has not explicitly selected one.
+        if self.config.selected_spec.is_empty() {
+            if let Some(profile) = specs::load_spec(&identity.class, &identity.spec) {
+                tracing::info!(
+                    "Auto-loaded spec {}: {} major CD IDs, {} AM IDs",
+                    profile.key(),
+                    profile.major_cd_spell_ids.len(),
+                    profile.am_spell_ids.len()
+                );
+                tracing::debug!(
+                    "Spec {} tracks: {}",
+                    profile.key(),
+                    profile.spell_names().iter().map(|(_, name)| *name).collect::<Vec<_>>().join(", ")
+                );
+                self.effective_major_cds = profile.major_cd_spell_ids;
+                self.effective_am_spells = profile.am_spell_ids;
+                self.opener_spell_ids    = profile.opener_spell_ids;
+                self.dot_spell_ids       = profile.dot_spell_ids;
+                self.prepull_spell_ids   = profile.prepull_spell_ids;
+                self.cooldown_s          = profile.cooldown_s;
+                self.priority_spells     = profile.priority_spells;
+                self.role                = profile.role;
+            } else {
+                tracing::debug!(
+                    "No spec profile for {}/{} — cooldown_drift will not fire",
+                    identity.class, identity.spec
+                );
+            }
+        }
+
+        self.bootstrap_character(&identity);
+        self.identity = identity;
+    }
+
+    /// Generate role defaults for a character without a profile.  They apply
+    /// at once; `pending_profile` carries them out to be saved.
+    fn bootstrap_character(&mut self, identity: &PlayerIdentity) {
+        if identity.guid.is_empty() || self.config.character_profiles.contains_key(&identity.guid) {
+            return;
+        }
+        let Some(profile) = role_defaults::for_role(&self.role, &identity.name) else { return };
+        tracing::info!(
+            "Role defaults for {} ({}): intensity {:?}, disabled {:?}",
+            identity.name, profile.role, profile.intensity, profile.disabled_rules
+        );
+        self.config.character_profiles.insert(identity.guid.clone(), profile.clone());
+        self.pending_profile = Some(GeneratedProfile { guid: identity.guid.clone(), profile });
+    }
+
+    /// Advance the combat state machine and the encounter timeline by one event.
+    fn apply_event(&mut self, event: &LogEvent, now_ms: u64) {
+        // Debrief CD drift: how long each major cooldown sat ready before it
+        // was used, read before update_state records the use.
+        if let LogEvent::SpellCastSuccess { source_guid, spell_id, .. } = event {
+            if let Some(pull) = self.combat.current_pull.as_ref().filter(|_| self.combat.in_combat) {
+                if Some(source_guid.as_str()) == self.combat.player_guid.as_deref()
+                    && self.effective_major_cds.contains(spell_id)
+                {
+                    let cooldown_ms = self.cooldown_s.get(spell_id).map(|&s| s as u64 * 1_000);
+                    self.pull_cd_drift_ms += self.combat.cooldowns.drift_ms(*spell_id, cooldown_ms, pull.start_ms, now_ms);
+                }
+            }
+        }
+        update_state(&mut self.combat, event, now_ms);
+
+        // Major cooldowns open burst windows (the spec profile lives here,
+        // not in CombatState).
+        if let LogEvent::SpellCastSuccess { source_guid, spell_id, .. } = event {
+            if self.combat.in_combat
+                && Some(source_guid.as_str()) == self.combat.player_guid.as_deref()
+                && self.effective_major_cds.contains(spell_id)
+            {
+                self.combat.burst.cooldown_used(now_ms);
+            }
+        }
+
+        // ── Encounter timeline ─────────────────────────────────────────
+        match event {
+            LogEvent::EncounterStart { encounter_id, encounter_name, .. } => {
+                let generation = encounters::generation();
+                let profile = encounters::load(*encounter_id, encounter_name);
+                self.awaiting_encounter = None;
+                if profile.is_none() && *encounter_id != 0 {
+                    let request = EncounterRequest { encounter_id: *encounter_id, name: encounter_name.clone() };
+                    if self.requested_encounters.insert(*encounter_id) {
+                        self.pending_fetch = Some(request.clone());
+                    }
+                    self.awaiting_encounter = Some((request, generation));
+                }
+                self.use_encounter(profile, encounter_name);
+            }
+            LogEvent::EncounterEnd { .. } => {
+                // Stop tracking; this pull's dodge stats stay until the next start_pull.
+                self.encounter = None;
+                self.awaiting_encounter = None;
+                self.combat.dodge.set_tracked(&[]);
+                self.combat.boss_npc_ids.clear();
+                self.combat.cc_spell_ids.clear();
+                self.combat.targets.set_priority_npcs(&[]);
+                self.update_known_avoidable();
+            }
+            LogEvent::ZoneChange { instance_id, .. } | LogEvent::ChallengeModeStart { instance_id, .. } => {
+                self.dungeon = encounters::load_dungeon(*instance_id);
+                self.update_known_avoidable();
+            }
+            _ => {}
+        }
+
+        self.check_combat_timeout(now_ms);
+    }
+
+    /// Make `profile` the active encounter's and point the trackers at its
+    /// avoidable spells, bosses, CC and priority adds.
+    fn use_encounter(&mut self, profile: Option<EncounterProfile>, encounter_name: &str) {
+        self.encounter = profile;
+        let avoidable = self.encounter.as_ref().map_or(&[][..], |e| &e.avoidable_spell_ids);
+        self.combat.dodge.set_tracked(avoidable);
+        let boss_ids = self.encounter.as_ref().map_or(&[][..], |e| &e.boss_npc_ids);
+        self.combat.damage_done.set_encounter(boss_ids);
+        self.combat.boss_npc_ids = boss_ids.to_vec();
+        self.combat.cc_spell_ids = self.encounter.as_ref().map_or(Vec::new(), |e| e.cc_spell_ids.clone());
+        self.combat.targets.set_priority_npcs(self.encounter.as_ref().map_or(&[][..], |e| &e.priority_npc_ids));
+        if let Some(enc) = &self.encounter {
+            tracing::info!(
+                "Encounter profile loaded: {} ({} timeline entries, {} avoidable)",
+                encounter_name, enc.timeline.len(), enc.avoidable_spell_ids.len()
+            );
+        }
+        self.update_known_avoidable();
+    }
+
+    /// Recombine the encounter's and the dungeon's avoidable spell lists.
+    fn update_known_avoidable(&mut self) {
+        let mut ids: Vec<u32> = self.encounter.iter().flat_map(|e| e.avoidable_spell_ids.iter().copied())
+            .chain(self.dungeon.iter().flat_map(|d| d.avoidable_spell_ids.iter().copied()))
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        self.combat.party_damage.set_avoidable(&ids);
+        self.known_avoidable = (!ids.is_empty()).then_some(ids);
+    }
+
+    /// Pick up encounter data downloaded since the active encounter started.
+    fn refresh_encounter(&mut self) {
+        let generation = encounters::generation();
+        let Some((request, seen)) = &mut self.awaiting_encounter else { return };
+        if *seen == generation {
+            return;
+        }
+        *seen = generation;
+        if let Some(profile) = encounters::load(request.encounter_id, &request.name) {
+            let name = request.name.clone();
+            self.awaiting_encounter = None;
+            self.use_encounter(Some(profile), &name);
+        }
+    }
+
+    /// Milliseconds until the open-world combat timeout, measured from the
+    /// player's last cast; None outside open-world combat.
+    fn combat_timeout_in(&self, now_ms: u64) -> Option<u64> {
+        if !self.combat.in_combat || self.combat.encounter_name.is_some() {
+            return None;
+        }
+        let idle = now_ms.saturating_sub(self.combat.last_player_cast_ms?);
+        Some(COMBAT_TIMEOUT_MS.saturating_sub(idle))
+    }
+
+    /// End an open-world pull once the player has not cast for
+    /// COMBAT_TIMEOUT_MS (walked away from the dummies, stopped fighting).
+    /// ENCOUNTER_END is authoritative for dungeon/raid pulls; this covers
+    /// everything else.  Returns true when the pull was ended.
+    fn check_combat_timeout(&mut self, now_ms: u64) -> bool {
+        if self.combat_timeout_in(now_ms) != Some(0) {
+            return false;
+        }
+        let idle = now_ms.saturating_sub(self.combat.last_player_cast_ms.unwrap_or(now_ms));
+        tracing::info!("Combat timeout: no player cast for {}ms — ending pull", idle);
+        self.combat.end_pull(now_ms, PullOutcome::Wipe);
+        true
+    }
+
+    /// Log time now, extrapolated from the last event by the wallclock time
+    /// since it arrived — the log is silent between pulls.  A paused replay
+    /// holds it (`EngineCommand::LogRate`).
+    fn log_now_ms(&self) -> u64 {
+        self.log_clock.now_ms(Instant::now())
+    }
+
+    /// Coaching overrides in effect now: the active encounter's settings on
+    /// top of the coached character's profile.
+    fn active_settings(&self) -> EncounterSettings {
+        self.settings_for(self.combat.encounter_name.as_deref())
+    }
+
+    /// Encounter settings for `encounter` (if any) merged with the coached
+    /// character's profile: the encounter's intensity wins, disabled rules
+    /// from both apply.
+    fn settings_for(&self, encounter: Option<&str>) -> EncounterSettings {
+        let mut settings = encounter
+            .and_then(|name| self.config.encounter_settings.get(name))
+            .cloned()
+            .unwrap_or_default();
+        let character = self.combat.player_guid.as_deref()
+            .and_then(|guid| self.config.character_profiles.get(guid));
+        if let Some(CharacterProfile { intensity, disabled_rules, .. }) = character {
+            settings.intensity = settings.intensity.or(*intensity);
+            settings.disabled_rules.extend(disabled_rules.iter().cloned());
+        }
+        settings
+    }
+
+    /// Rule profile for the current content, logged on change.  Dummy
+    /// practice keeps only the rotation rules; solo/follower content switches
+    /// to the lenient rule profile.
+    fn update_profile(&mut self, now_ms: u64) -> RuleProfile {
+        let profile = if self.combat.in_combat && self.combat.practice.is_practice() {
+            RuleProfile::Practice
+        } else if self.combat.group.is_solo_content(now_ms) {
+            RuleProfile::Solo
+        } else {
+            RuleProfile::Group
+        };
+        if profile != self.rule_profile {
+            tracing::info!("Rule profile: {:?} → {:?}", self.rule_profile, profile);
+            self.rule_profile = profile;
+        }
+        profile
+    }
+
+    /// Run every rule pass for one event and return the advice that survives
+    /// the per-key cooldowns.  Debrief counters are updated here.
+    fn evaluate_rules(&mut self, event: &LogEvent, now_ms: u64) -> Vec<AdviceEvent> {
+        let profile = self.update_profile(now_ms);
+
+        // Overrides for the active encounter (strategy presets).
+        let settings = self.active_settings();
+
+        // Build context once — shared by all passes.
+        let ctx = RuleContext {
+            state:     &self.combat,
+            identity:  &self.identity,
+            intensity: settings.intensity.unwrap_or(self.config.intensity),
+            now_ms,
+            profile,
+            fmt:       &self.config.number_format,
+        };
+        let input = RuleInput { event };
+
+        // Passes 1–3: built-in rules subscribed to this event (rules/registry.rs)
+        let params = self.rule_params(&settings);
+        let mut candidates = self.rule_bus.dispatch(event, &ctx, &params, &settings.disabled_rules);
+
+        // Pass 4: user-authored rules and scripts (any in-combat event)
+        if self.combat.in_combat && profile != RuleProfile::Practice {
+            candidates.extend(self.custom_rules.evaluate(&input, &ctx));
+            candidates.extend(self.script_rules.evaluate(&input, &ctx));
+        }
+
+        // Pass 5: raid-lead callouts (any in-combat event)
+        if self.combat.in_combat && profile != RuleProfile::Practice && self.config.raid_lead_mode {
+            candidates.extend(self.raid_callouts.evaluate(&input, &ctx, self.known_avoidable.as_deref()));
+        }
+
+        self.dedup(candidates, &settings, now_ms)
     }
 
-    fn mark_fired(&mut self, key: &str, now_ms: u64) {
-        self.advice_last_ms.insert(key.to_owned(), now_ms);
+    /// Pass 3 without an event, for the engine tick: rules that fire on
+    /// elapsed time still fire while the log is quiet.
+    fn evaluate_tick(&mut self, now_ms: u64) -> Vec<AdviceEvent> {
+        let profile = self.update_profile(now_ms);
+        if !self.combat.in_combat || profile == RuleProfile::Practice {
+            return Vec::new();
+        }
+        let settings = self.active_settings();
+        let ctx = RuleContext {
+            state:     &self.combat,
+            identity:  &self.identity,
+            intensity: settings.intensity.unwrap_or(self.config.intensity),
+            now_ms,
+            profile,
+            fmt:       &self.config.number_format,
+        };
+        let params = self.rule_params(&settings);
+        let mut candidates = self.rule_bus.tick(&ctx, &params, &settings.disabled_rules);
+        if self.config.raid_lead_mode {
+            candidates.extend(self.raid_callouts.flush(&ctx));
+        }
+        self.dedup(candidates, &settings, now_ms)
+    }
+
+    /// Engine-owned inputs of the built-in rules.
+    fn rule_params<'a>(&'a self, settings: &'a EncounterSettings) -> RuleParams<'a> {
+        RuleParams {
+            config:           &self.config,
+            role:             &self.role,
+            major_cds:        &self.effective_major_cds,
+            am_spells:        &self.effective_am_spells,
+            dot_spell_ids:    &self.dot_spell_ids,
+            cooldown_s:       &self.cooldown_s,
+            avoidable:        self.known_avoidable.as_deref(),
+            kick_assignments: &settings.kick_assignments,
+            encounter:        self.encounter.as_ref(),
+        }
+    }
+
+    /// Drop disabled rules and advice still on cooldown; count the rest
+    /// toward the debrief.
+    fn dedup(
+        &mut self,
+        mut candidates: Vec<AdviceEvent>,
+        settings:       &EncounterSettings,
+        now_ms:         u64,
+    ) -> Vec<AdviceEvent> {
+        candidates.retain(|a| !rules::is_disabled(&a.key, &settings.disabled_rules));
+
+        // Dedup
+        let mut fired = Vec::new();
+        for advice in candidates {
+            let repeat = self.combat.advice_repeats.get(&advice.key).copied().unwrap_or(0) + 1;
+            if self.can_fire(&advice, escalated_priority(&advice, repeat), now_ms) {
+                self.combat.advice_repeats.insert(advice.key.clone(), repeat);
+                let advice = escalate(advice, repeat);
+                // Track GCD gap events for debrief
+                if advice.key.starts_with("gcd_gap") {
+                    self.pull_gcd_gap_count += 1;
+                    self.pull_gap_causes.add(self.combat.gcd.gap_cause);
+                }
+
+                self.mark_fired(&advice, now_ms);
+                self.pull_advice_count += 1;
+                fired.push(advice);
+            }
+        }
+        fired
+    }
+
+    /// Learn the player's GUID from the log when no identity is known yet.
+    ///
+    /// Every Player-* caster is cached by character name (lowercased, before
+    /// the first '-') so a player_focus configured later resolves at once;
+    /// the first SPELL_CAST_SUCCESS matching the current focus name sets the
+    /// GUID.  WoW 12.0.1+ source_name is "Name-Realm-Region" (e.g.
+    /// "Stonebraid-Draenor-EU"); older WoW uses just "Name".
+    ///
+    /// COMBATANT_INFO lines are remembered by GUID for `detect_spec`.
+    fn observe_player(&mut self, event: &LogEvent) {
+        if let LogEvent::CombatantInfo { player_guid, spec_id, gear, talents, .. } = event {
+            tracing::debug!(
+                "COMBATANT_INFO {}: spec {}, {} talents, ilvl {:.1}",
+                player_guid, spec_id, talents.len(), average_item_level(gear).unwrap_or(0.0)
+            );
+            self.combatant_specs.insert(player_guid.clone(), *spec_id);
+        }
+        if self.combat.player_guid.is_some() {
+            self.detect_spec();
+            return;
+        }
+        let LogEvent::SpellCastSuccess { source_guid, source_name, .. } = event else { return };
+        if guid::kind(source_guid) == UnitKind::Player {
+            let cache_key = extract_char_name(source_name).to_ascii_lowercase();
+            self.player_name_cache
+                .entry(cache_key)
+                .or_insert_with(|| source_guid.clone());
+        }
+        if !self.focus_name.is_empty()
+            && extract_char_name(source_name).eq_ignore_ascii_case(&self.focus_name)
+        {
+            tracing::info!(
+                "GUID inferred from player_focus '{}': {} (source_name='{}')",
+                self.focus_name, source_guid, source_name
+            );
+            self.combat.player_guid = Some(source_guid.clone());
+            self.detect_spec();
+        }
+    }
+
+    /// Adopt the spec COMBATANT_INFO logged for the player when it differs
+    /// from the current identity.  The log is newer than the addon's
+    /// SavedVariables (written on logout or /reload), so it wins.
+    fn detect_spec(&mut self) {
+        let Some(guid) = self.combat.player_guid.as_deref() else { return };
+        let Some((class, spec)) = self.combatant_specs.get(guid).and_then(|&id| specs::spec_by_id(id)) else {
+            return;
+        };
+        if self.identity.guid == guid && self.identity.class == class && self.identity.spec == spec {
+            return;
+        }
+        tracing::info!("Spec detected from COMBATANT_INFO: {}/{}", class, spec);
+        let mut identity = PlayerIdentity {
+            guid:  guid.to_owned(),
+            class: class.to_owned(),
+            spec:  spec.to_owned(),
+            ..self.identity.clone()
+        };
+        if identity.name == PlayerIdentity::unknown().name && !self.focus_name.is_empty() {
+            identity.name = self.focus_name.clone();
+        }
+        self.set_identity(identity);
+    }
+
+    /// Number a tracked pull that just started and reset its debrief counters.
+    fn begin_pull(&mut self) {
+        self.pull_number       += 1;
+        self.pull_advice_count  = 0;
+        self.pull_gcd_gap_count = 0;
+        self.pull_gap_causes    = GapCauses::default();
+        self.pull_cd_drift_ms   = 0;
+    }
+
+    /// Open or close the Mythic+ run summary; returns the key's debrief at
+    /// CHALLENGE_MODE_END.  Called after `apply_event`, which keeps
+    /// `combat.keystone` current.
+    fn track_key(&mut self, event: &LogEvent, now_ms: u64) -> Option<KeyDebrief> {
+        match event {
+            LogEvent::ChallengeModeStart { .. } => {
+                self.key_run = self.combat.keystone.clone()
+                    .map(|key| KeyRun::start(key, now_ms, self.combat.player_deaths));
+                None
+            }
+            LogEvent::ChallengeModeEnd { success, total_time_ms, .. } => {
+                let run = self.key_run.take()?;
+                Some(run.finish(now_ms, *success, *total_time_ms, self.combat.player_deaths, &self.config.number_format))
+            }
+            // Left the dungeon without a CHALLENGE_MODE_END: the key is gone.
+            _ => {
+                if self.combat.keystone.is_none() {
+                    self.key_run = None;
+                }
+                None
+            }
+        }
+    }
+
+    /// Store a fired advice with the current pull; with advice debug capture
+    /// on, also give it a `debug_id` and store its debug row.  `trigger` is
+    /// None for advice fired by the engine tick.
+    fn store_advice(&mut self, advice: &mut AdviceEvent, trigger: Option<&LogEvent>, now_ms: u64) {
+        if self.config.advice_debug {
+            self.debug_seq += 1;
+            advice.debug_id = Some(format!("{}-{}", advice.wallclock_ms, self.debug_seq));
+            let settings = self.active_settings();
+            let engine_values = vec![
+                ("intensity".to_owned(), settings.intensity.unwrap_or(self.config.intensity).to_string()),
+                ("profile".to_owned(),   format!("{:?}", self.rule_profile)),
+                ("role".to_owned(),      self.role.clone()),
+                ("spec".to_owned(),      self.config.selected_spec.clone()),
+            ];
+            let debug = advice_debug::record(advice, trigger, &self.combat, engine_values, now_ms);
+            self.db.insert_advice_debug(self.current_pull_id, debug);
+        }
+        if let Some(pull_id) = self.current_pull_id {
+            self.db.insert_advice(pull_id, StoredAdvice {
+                fired_at:   now_ms,
+                rule_key:   advice.key.clone(),
+                severity:   format!("{:?}", advice.severity).to_lowercase(),
+                message:    advice.message.clone(),
+                repeat:     advice.repeat,
+                escalation: advice.escalation,
+            });
+        }
+    }
+
+    /// Debrief and store the pull that just ended (the caller has checked
+    /// `pull_tracked`).  `ending` is the event that ended it, or None when
+    /// the open-world combat timeout fired between events.
+    fn finish_pull(&mut self, ending: Option<&LogEvent>, now_ms: u64, wall_ms: u64) -> PullDebrief {
+        // Capture debrief stats BEFORE resetting pull-level counters.
+        // At this point avoidable, interrupt_count, etc. still hold
+        // the just-ended pull's values (reset happens on next start_pull).
+        let pull_elapsed = self.combat.pull_history.last()
+            .and_then(|p| p.end_ms.zip(Some(p.start_ms)))
+            .map(|(end, start)| end.saturating_sub(start))
+            .unwrap_or(0);
+        let outcome_str = self.combat.pull_history.last()
+            .and_then(|p| p.outcome.as_ref())
+            .map(|o| format!("{:?}", o).to_lowercase())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        // Dummy pulls are scored into the practice session
+        // instead of being kept as pulls (see practice.rs).
+        let practice = if self.combat.practice.is_practice() {
+            Some(self.record_practice_pull(pull_elapsed, wall_ms))
+        } else {
+            self.practice = None;
+            None
+        };
+
+        let mut debrief = PullDebrief {
+            pull_number:        self.pull_number,
+            pull_elapsed_ms:    pull_elapsed,
+            outcome:            outcome_str.clone(),
+            avoidable_count:    self.combat.avoidable.total_hits(),
+            interrupt_count:    self.combat.interrupt_count,
+            interrupt_efficiency: state::interrupt_efficiency(
+                self.combat.interrupt_count, self.combat.interrupts.missed_ready,
+            ),
+            session_interrupt_efficiency: state::interrupt_efficiency(
+                self.combat.interrupts.session_landed, self.combat.interrupts.session_missed,
+            ),
+            total_advice_fired: self.pull_advice_count,
+            gcd_gap_count:      self.pull_gcd_gap_count,
+            gcd_gap_causes:     self.pull_gap_causes.clone(),
+            active_time_pct:    self.combat.activity.active_pct(pull_elapsed),
+            dodge_stats:        self.combat.dodge.summary()
+                .into_iter()
+                .map(|(spell_id, m)| DodgeStat {
+                    spell_id,
+                    dodge_pct:  m.dodge_pct().unwrap_or(0.0),
+                    spell_name: m.spell_name,
+                    avoided:    m.avoids,
+                    hit:        m.hits,
+                })
+                .collect(),
+            cd_drift_ms:        self.pull_cd_drift_ms,
+            boss_damage:        self.combat.damage_done.boss_share().map(|b| BossDamage {
+                share_pct:    b.pct(),
+                boss_name:    b.boss_name,
+                boss_damage:  b.boss_damage,
+                total_damage: b.total_damage,
+            }),
+            target_switch:      target_switch(&self.combat.targets.switch_latencies),
+            burst:              self.combat.burst.summary().map(|b| BurstAlignment {
+                in_window_pct:  b.in_window_pct,
+                windows:        b.windows,
+                peak_dps_10s:   b.peak_dps_short,
+                peak_dps_30s:   b.peak_dps_long,
+                peak_in_window: b.peak_in_window,
+            }),
+            melee_uptime:       self.combat.melee.uptime_pct().map(|uptime_pct| MeleeUptime {
+                uptime_pct,
+                out_of_range_ms: self.combat.melee.out_range_ms,
+                longest_gap_ms:  self.combat.melee.longest_gap_ms,
+            }),
+            deaths:             self.combat.group_deaths.deaths.iter()
+                .map(|d| GroupDeathEntry { name: d.name.clone(), elapsed_ms: d.elapsed_ms })
+                .collect(),
+            battle_res:         self.combat.group_deaths.battle_res.iter()
+                .map(|b| BattleResEntry {
+                    caster:     b.caster.clone(),
+                    target:     b.target.clone(),
+                    elapsed_ms: b.elapsed_ms,
+                })
+                .collect(),
+            consumables:        ConsumableUse {
+                potions:      self.combat.consumables.potions,
+                healthstones: self.combat.consumables.healthstones,
+            },
+            prepull:            prepull_check(&self.combat, &self.prepull_spell_ids),
+            cancelled_casts:    cancelled_casts(&self.combat),
+            failed_casts:       self.combat.failed_casts.breakdown().into_iter()
+                .map(|(reason, count)| FailedCastCount { reason, count })
+                .collect(),
+            friendly_fire:      friendly_fire(&self.combat),
+            healing:            (self.role == "HEALER")
+                .then(|| healing_distribution(&self.combat))
+                .flatten(),
+            damage_leaderboard: if self.config.squad_mode {
+                party_leaderboard(&self.combat)
+            } else {
+                Vec::new()
+            },
+            summary:            String::new(),
+            practice,
+            cast_rates:         rotation::cast_rates(
+                self.combat.practice.casts(), &self.priority_spells, pull_elapsed,
+            ),
+            sticky:             false,
+            raid_summary:       None,
+        };
+        debrief.summary = match &debrief.practice {
+            Some(report) => report.summary(),
+            None         => pull_summary(&debrief, &self.config.number_format),
+        };
+        // ENCOUNTER_END has already cleared encounter_name.  Only it carries
+        // the encounter ID; pulls ending otherwise are stored by name alone.
+        let (encounter_id, encounter) = match ending {
+            Some(LogEvent::EncounterEnd { encounter_id, encounter_name, .. }) => {
+                let (id, name) = encounters::canonical(*encounter_id, encounter_name);
+                (Some(id).filter(|&id| id != 0), name)
+            }
+            _ => (None, self.combat.encounter_name.clone().unwrap_or_default()),
+        };
+        if self.config.raid_lead_mode && debrief.practice.is_none() {
+            let header = format!(
+                "Pull {}{} ({} {})",
+                self.pull_number,
+                if encounter.is_empty() { String::new() } else { format!(", {}", encounter) },
+                outcome_str, self.config.number_format.duration(pull_elapsed),
+            );
+            debrief.raid_summary = self.raid_callouts.summary(&header, &self.config.number_format);
+        }
+        tracing::info!(
+            "Pull debrief: {} {}ms outcome={} avoidable={} interrupts={} advice={} active={:.1}%",
+            self.pull_number, pull_elapsed, outcome_str,
+            debrief.avoidable_count, debrief.interrupt_count, debrief.total_advice_fired,
+            debrief.active_time_pct
+        );
+        let debrief_active_pct = debrief.active_time_pct;
+        let boss_pct = debrief.boss_damage.as_ref().map(|b| b.share_pct);
+        let kick_pct = debrief.interrupt_efficiency;
+        let dodge_stats = debrief.dodge_stats.clone();
+        let cast_rates = debrief.cast_rates.clone();
+        let is_practice = debrief.practice.is_some();
+        
+        if is_practice {
+            if let Some(pull_id) = self.current_pull_id.take() {
+                self.db.discard_pull(pull_id);
+            }
+            self.pull_number -= 1;
+            self.pull_events.clear();
+        } else if let Some(pull_id) = self.current_pull_id.take() {
+            self.db.end_pull(pull_id, now_ms, outcome_str, debrief_active_pct, boss_pct, kick_pct);
+            self.db.end_session(self.session_id, wall_ms);
+            self.db.record_attempt(
+                pull_id, encounter_id, encounter.clone(), self.combat.death.death.clone(),
+                debrief.deaths.len() as u32, debrief.battle_res.len() as u32,
+            );
+            self.db.record_pull_stats(
+                pull_id, self.combat.party_damage.avoidable_hits(), self.combat.damage_done.group_boss_damage(),
+            );
+            if let Some(LogEvent::EncounterEnd { difficulty_id, success: true, .. }) = ending {
+                self.pending_kill = self.kill_record(pull_id, &encounter, *difficulty_id, &debrief, wall_ms);
+            }
+            if !dodge_stats.is_empty() {
+                self.db.insert_dodge_stats(pull_id, encounter, dodge_stats);
+            }
+            if !cast_rates.is_empty() {
+                self.db.insert_cast_rates(pull_id, cast_rates);
+            }
+            if let Some(key) = &mut self.key_run {
+                key.add_pull(&debrief, self.combat.gcd.longest_gap_ms);
+            }
+            // Inferred GUIDs (no addon) only live in combat state.
+            let mut identity = self.identity.clone();
+            if let Some(guid) = &self.combat.player_guid {
+                identity.guid = guid.clone();
+            }
+            let events = std::mem::take(&mut self.pull_events);
+            self.db.archive_pull_events(pull_id, identity, events);
+        }
+        // Reset per-pull dedup so rules fire fresh next pull
+        self.advice_last_ms.clear();
+        debrief
+    }
+
+    /// The kill that just ended, for the personal records; None while the
+    /// player's GUID is unknown.
+    fn kill_record(
+        &self,
+        pull_id:       i64,
+        encounter:     &str,
+        difficulty_id: u32,
+        debrief:       &PullDebrief,
+        wall_ms:       u64,
+    ) -> Option<Kill> {
+        let player_guid = self.combat.player_guid.clone()?;
+        let player_name = if self.identity.name.is_empty() {
+            self.config.player_focus.clone()
+        } else {
+            self.identity.name.clone()
+        };
+        let score = records::pull_score(debrief.active_time_pct, debrief.avoidable_count, debrief.interrupt_efficiency);
+        let mut values = vec![
+            (RecordKind::FastestKill,     debrief.pull_elapsed_ms as f64),
+            (RecordKind::FewestAvoidable, debrief.avoidable_count as f64),
+            (RecordKind::BestPullScore,   score as f64),
+        ];
+        if let Some(kick_pct) = debrief.interrupt_efficiency {
+            values.push((RecordKind::BestKickEfficiency, kick_pct as f64));
+        }
+        Some(Kill {
+            player_guid,
+            player_name,
+            encounter: encounter.to_owned(),
+            difficulty_id,
+            pull_id,
+            killed_at: wall_ms,
+            values,
+        })
+    }
+
+    /// Score the training-dummy pull that just ended into the running
+    /// practice session and store the session's report.
+    fn record_practice_pull(&mut self, pull_elapsed: u64, wall_ms: u64) -> PracticeReport {
+        let start_ms = self.combat.pull_history.last().map_or(0, |p| p.start_ms);
+        let score = self.combat.practice.score(
+            start_ms,
+            start_ms + pull_elapsed,
+            &self.opener_spell_ids,
+            &self.effective_major_cds,
+            self.combat.activity.active_pct(pull_elapsed),
+        );
+        let spec = if self.identity.spec.is_empty() {
+            self.config.selected_spec.clone()
+        } else {
+            self.identity.spec.clone()
+        };
+        let session = self.practice
+            .get_or_insert_with(|| PracticeSession::new(wall_ms.saturating_sub(pull_elapsed), spec));
+        session.pulls.push(score);
+        let report = session.report();
+        tracing::info!("Practice pull {}: {}", session.pulls.len(), report.summary());
+        if self.session_id > 0 {
+            self.db.save_practice_session(
+                self.session_id, session.started_at, wall_ms, session.spec.clone(), report.clone(),
+            );
+        }
+        report
+    }
+
+    /// Summary of the previous attempt at a repeated encounter (see
+    /// progression_nudge).  Fires once per pull, so no cooldown applies.
+    fn progression_nudge(&mut self, previous: &PreviousAttempt, now_ms: u64) -> Vec<AdviceEvent> {
+        let settings = self.active_settings();
+        let ctx = RuleContext {
+            state:     &self.combat,
+            identity:  &self.identity,
+            intensity: settings.intensity.unwrap_or(self.config.intensity),
+            now_ms,
+            profile:   self.rule_profile,
+            fmt:       &self.config.number_format,
+        };
+        let mut fired = progression_nudge::evaluate(&ctx, previous);
+        fired.retain(|a| !rules::is_disabled(&a.key, &settings.disabled_rules));
+        self.pull_advice_count += fired.len() as u32;
+        fired
+    }
+
+    /// Advisory for the personal records a kill beat (personal_record).  The
+    /// pull is over, so it does not count toward the debrief.
+    fn personal_record(&self, kill: &Kill, updates: &[RecordUpdate], now_ms: u64) -> Vec<AdviceEvent> {
+        let settings = self.settings_for(Some(&kill.encounter));
+        let ctx = RuleContext {
+            state:     &self.combat,
+            identity:  &self.identity,
+            intensity: settings.intensity.unwrap_or(self.config.intensity),
+            now_ms,
+            profile:   self.rule_profile,
+            fmt:       &self.config.number_format,
+        };
+        let mut fired = personal_record::evaluate(&ctx, kill, updates);
+        fired.retain(|a| !rules::is_disabled(&a.key, &settings.disabled_rules));
+        fired
+    }
+
+    fn snapshot(&self, now_ms: u64, wall_ms: u64) -> StateSnapshot {
+        // Phase / next-event from the encounter timeline, relative to ENCOUNTER_START.
+        let timeline = self.encounter.as_ref().zip(self.combat.encounter_start_ms);
+        let (phase_label, next_scheduled_event, countdown) = match timeline {
+            Some((enc, start)) if self.combat.in_combat => {
+                let elapsed = now_ms.saturating_sub(start);
+                (
+                    enc.phase_at(elapsed).map(str::to_owned),
+                    enc.next_event(elapsed).map(|(e, in_ms)| ScheduledEvent {
+                        name:    e.name.clone(),
+                        seconds: in_ms as f32 / 1_000.0,
+                    }),
+                    countdown::next(&enc.timeline, elapsed, &self.role, &self.config.countdown, &self.countdown_sounds)
+                        .filter(|_| !self.is_paused()),
+                )
+            }
+            _ => (None, None, None),
+        };
+        StateSnapshot {
+            pull_elapsed_ms: self.combat.pull_elapsed_ms(now_ms),
+            gcd_gap_ms:      self.combat.gcd.current_gap_ms,
+            avoidable_count: self.combat.avoidable.total_hits(),
+            in_combat:       self.combat.in_combat,
+            interrupt_count: self.combat.interrupt_count,
+            encounter_name:  self.combat.encounter_name.clone(),
+            wallclock_ms:    wall_ms,
+            active_time_pct: self.combat.active_time_pct(now_ms),
+            dps_10s:         self.combat.outgoing.rolling_dps(now_ms),
+            hps_10s:         self.combat.outgoing.rolling_hps(now_ms),
+            pull_dps:        self.combat.outgoing.pull_dps(now_ms),
+            pull_hps:        self.combat.outgoing.pull_hps(now_ms),
+            group_deaths:    self.combat.group_deaths.deaths.len() as u32,
+            battle_res:      self.combat.group_deaths.battle_res.len() as u32,
+            coaching_paused: self.is_paused(),
+            phase_label,
+            next_scheduled_event,
+            // Between pulls only: the checklist is for the wait before the pull.
+            readiness: (!self.combat.in_combat).then(|| readiness::evaluate(
+                &self.combat,
+                &self.effective_major_cds,
+                self.config.encounter_settings.values().filter(|s| !s.kick_assignments.is_empty()).count(),
+                now_ms,
+            )),
+            keystone: self.combat.keystone.as_ref().map(|k| KeystoneInfo {
+                dungeon: k.dungeon.clone(),
+                level:   k.level,
+                affixes: k.affix_names(),
+            }),
+            countdown,
+            wipe_pct:        self.wipe_forecast(now_ms),
+        }
+    }
+
+    /// Live wipe chance of an encounter pull in % (wipe_forecast.rs); None
+    /// outside encounters or without enough stored pulls.
+    fn wipe_forecast(&self, now_ms: u64) -> Option<f32> {
+        let model = self.wipe_model.as_ref().filter(|_| self.combat.in_combat)?;
+        let start = self.combat.encounter_start_ms?;
+        let live = LiveProgress {
+            elapsed_ms:     now_ms.saturating_sub(start),
+            group_deaths:   self.combat.group_deaths.deaths.len() as u32,
+            group_size:     self.combat.party_damage.members.len() as u32,
+            avoidable_hits: self.combat.party_damage.avoidable_hits(),
+            boss_damage:    self.combat.damage_done.group_boss_damage(),
+        };
+        Some(model.wipe_chance(&live) * 100.0)
     }
 }
 
@@ -157,11 +1445,17 @@ pub async fn run(
     mut event_rx:  Receiver<LogEvent>,
     mut id_rx:     Receiver<PlayerIdentity>,
     mut config_rx: Receiver<AppConfig>,
+    mut ctl_rx:    Receiver<EngineCommand>,
     advice_tx:     Sender<AdviceEvent>,
     snap_tx:       Sender<StateSnapshot>,
     debrief_tx:    Sender<PullDebrief>,
+    recap_tx:      Sender<DeathRecap>,
+    key_tx:        Sender<KeyDebrief>,
+    profile_tx:    Sender<GeneratedProfile>,
+    fetch_tx:      Sender<EncounterRequest>,
     config:        AppConfig,
     db:            DbWriter,
+    clock:         Arc<ClockAligner>,
 ) -> Result<()> {
     // Insert a session row before entering the hot loop.
     let session_start_ms = unix_now_ms();
@@ -174,35 +1468,62 @@ pub async fn run(
         });
     tracing::info!("DB session {} started", session_id);
 
-    let mut eng = EngineState::new(config, db, session_id);
+    let mut eng = EngineState::new(config, db, session_id, clock);
+    advice_debug::set_enabled(eng.config.advice_debug);
+
+    let mut tick = tokio::time::interval(TICK_INTERVAL);
+    tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
 
-    loop {
+    'events: loop {
+        let combat_timeout = eng.combat_timeout_in(eng.log_now_ms());
         tokio::select! {
-            // Identity updates are rare — process immediately
-            Some(identity) = id_rx.recv() => {
-                tracing::info!("Identity updated → {}/{}", identity.name, identity.spec);
-                eng.combat.player_guid = Some(identity.guid.clone());
+            // Open-world combat timeout.  Ends the pull even when the log has
+            // gone quiet, which is the usual case — the player stopped
+            // fighting, so nothing is being written.
+            _ = tokio::time::sleep(Duration::from_millis(combat_timeout.unwrap_or(0))),
+                if combat_timeout.is_some() =>
+            {
+                let now_ms = eng.log_now_ms();
+                if eng.check_combat_timeout(now_ms) {
+                    let wall_ms = eng.wallclock_ms(now_ms);
+                    if eng.pull_tracked {
+                        let debrief = eng.finish_pull(None, now_ms, wall_ms);
+                        let _ = debrief_tx.try_send(debrief);
+                    }
+                    let _ = snap_tx.try_send(eng.snapshot(now_ms, wall_ms));
+                }
+            }
 
-                // Auto-load spec profile if user has not explicitly selected one.
-                if eng.config.selected_spec.is_empty() {
-                    if let Some(profile) = specs::load_spec(&identity.class, &identity.spec) {
-                        tracing::info!(
-                            "Auto-loaded spec {}: {} major CD IDs, {} AM IDs",
-                            profile.key(),
-                            profile.major_cd_spell_ids.len(),
-                            profile.am_spell_ids.len()
-                        );
-                        eng.effective_major_cds = profile.major_cd_spell_ids;
-                        eng.effective_am_spells = profile.am_spell_ids;
-                    } else {
-                        tracing::debug!(
-                            "No spec profile for {}/{} — cooldown_drift will not fire",
-                            identity.class, identity.spec
-                        );
+            // Engine tick: time-driven rules during lulls in the log.  Skipped
+            // when an event arrived within the last tick, since that event
+            // already ran the same pass.
+            _ = tick.tick() => {
+                if eng.is_paused()
+                    || !eng.combat.in_combat
+                    || eng.log_clock.is_frozen()
+                    || eng.last_event_at.elapsed() < TICK_INTERVAL
+                {
+                    continue;
+                }
+                eng.refresh_encounter();
+                let now_ms  = eng.log_now_ms();
+                let wall_ms = eng.wallclock_ms(now_ms);
+                for mut advice in eng.evaluate_tick(now_ms) {
+                    advice.wallclock_ms = wall_ms;
+                    eng.store_advice(&mut advice, None, now_ms);
+                    if advice_tx.send(advice).await.is_err() {
+                        break 'events;
                     }
                 }
+                let _ = snap_tx.try_send(eng.snapshot(now_ms, wall_ms));
+            }
 
-                eng.identity = identity;
+            // Identity updates are rare — process immediately
+            Some(identity) = id_rx.recv() => {
+                eng.set_identity(identity);
+                if let Some(generated) = eng.pending_profile.take() {
+                    let _ = profile_tx.try_send(generated);
+                }
 
                 // Back-fill the DB session row with the now-known player identity.
                 // The session is inserted at startup with empty name/GUID; once the
@@ -213,6 +1534,7 @@ pub async fn run(
                         eng.session_id,
                         eng.identity.name.clone(),
                         eng.identity.guid.clone(),
+                        config::account_of(&eng.config.addon_sv_path).unwrap_or_default(),
                     );
                     tracing::info!(
                         "Session {} back-filled → {}",
@@ -267,224 +1589,550 @@ pub async fn run(
                         );
                         eng.effective_major_cds = profile.major_cd_spell_ids;
                         eng.effective_am_spells = profile.am_spell_ids;
+                        eng.opener_spell_ids    = profile.opener_spell_ids;
+                        eng.dot_spell_ids       = profile.dot_spell_ids;
+                        eng.prepull_spell_ids   = profile.prepull_spell_ids;
+                        eng.cooldown_s          = profile.cooldown_s;
+                        eng.priority_spells     = profile.priority_spells;
+                        eng.role                = profile.role;
                     }
                 }
+                eng.countdown_sounds = countdown::voice_files(&new_cfg.countdown);
+                eng.combat.group_coaching = new_cfg.group_coaching;
+                eng.combat.friendly_avoidable = new_cfg.avoidable_friendly_fire;
+                advice_debug::set_enabled(new_cfg.advice_debug);
                 eng.config = new_cfg;
             }
 
+            // Pause/resume from the settings window.  The paused interval is
+            // opened/closed in the DB and a snapshot is pushed right away so
+            // the UI reflects the change even when no log lines are flowing.
+            Some(cmd) = ctl_rx.recv() => {
+                match cmd {
+                    EngineCommand::ReportSizes(reply) => {
+                        let _ = reply.send(eng.state_sizes());
+                        continue;
+                    }
+                    EngineCommand::LogRate(rate) => {
+                        eng.log_clock.set_rate(rate, Instant::now());
+                        continue;
+                    }
+                    _ => {}
+                }
+                let wall_now = unix_now_ms();
+                match (cmd, eng.paused_since) {
+                    (EngineCommand::Pause, None) => {
+                        tracing::info!("Coaching paused");
+                        eng.paused_since = Some(wall_now);
+                        if eng.session_id > 0 {
+                            eng.db.begin_pause(eng.session_id, wall_now);
+                        }
+                    }
+                    (EngineCommand::Resume, Some(since)) => {
+                        tracing::info!(
+                            "Coaching resumed after {}s",
+                            wall_now.saturating_sub(since) / 1000
+                        );
+                        eng.paused_since = None;
+                        if eng.session_id > 0 {
+                            eng.db.end_pause(eng.session_id, wall_now);
+                        }
+                    }
+                    _ => {} // already in the requested state
+                }
+                let _ = snap_tx.try_send(eng.snapshot(eng.last_log_ms, wall_now));
+            }
+
             // Combat log events — the hot path (break on channel close)
             result = event_rx.recv() => {
-            let Some(event) = result else { break };
+            let Some(event) = result else { break 'events };
                 let now_ms = event.timestamp_ms();
+                let wall_ms = eng.wallclock_ms(now_ms);
+                eng.last_log_ms = now_ms;
+                eng.last_event_at = Instant::now();
+                eng.log_clock.event(now_ms, eng.last_event_at);
+
+                eng.observe_player(&event);
+                if let Some(generated) = eng.pending_profile.take() {
+                    let _ = profile_tx.try_send(generated);
+                }
+                eng.refresh_encounter();
 
-                // Passively cache Player-* name→GUID while player is unidentified.
-                // Key = character name (before first '-'), lowercased.
-                // WoW 12.0.1+ source_name is "Name-Realm-Region" (e.g. "Stonebraid-Draenor-EU");
-                // older WoW uses just "Name" (e.g. "Stonebraid").
-                if eng.combat.player_guid.is_none() {
-                    if let LogEvent::SpellCastSuccess { source_guid, source_name, .. } = &event {
-                        if source_guid.starts_with("Player-") {
-                            let cache_key = extract_char_name(source_name).to_ascii_lowercase();
-                            eng.player_name_cache
-                                .entry(cache_key)
-                                .or_insert_with(|| source_guid.clone());
+                // ── Instance runs ──────────────────────────────────────────────
+                if let LogEvent::ZoneChange { instance_id, zone_name, difficulty_id, .. } = &event {
+                    if let Some(start) = eng.runs.zone_changed(*instance_id, zone_name, *difficulty_id, now_ms) {
+                        if let (Some(run_id), Some(ended_at)) = (eng.current_run_id.take(), start.previous_ended_at) {
+                            eng.db.end_instance_run(run_id, ended_at);
+                        }
+                        let sid = eng.session_id;
+                        match eng.db.insert_instance_run(sid, now_ms, &start.run).await {
+                            Ok(id) => {
+                                tracing::info!("DB instance run {} started ({})", id, start.run.label());
+                                eng.current_run_id = Some(id);
+                            }
+                            Err(e) => tracing::warn!("DB insert_instance_run failed: {}", e),
                         }
                     }
                 }
 
-                // GUID inference: if no identity yet but player_focus is configured,
-                // try to infer GUID from the first matching SPELL_CAST_SUCCESS.
-                // Compares character name only (before first '-') to handle both
-                // old format ("Stonebraid") and WoW 12.0.1+ ("Stonebraid-Draenor-EU").
-                if eng.combat.player_guid.is_none() && !eng.focus_name.is_empty() {
-                    if let LogEvent::SpellCastSuccess { source_guid, source_name, .. } = &event {
-                        if extract_char_name(source_name).eq_ignore_ascii_case(&eng.focus_name) {
-                            tracing::info!(
-                                "GUID inferred from player_focus '{}': {} (source_name='{}')",
-                                eng.focus_name, source_guid, source_name
-                            );
-                            eng.combat.player_guid = Some(source_guid.clone());
+                // ── Arena match records ────────────────────────────────────────
+                // Handled before update_state so the match's team_id is still
+                // available when ARENA_MATCH_END decides win/loss.
+                match &event {
+                    LogEvent::ArenaMatchStart { instance_id, bracket, team_id, .. }
+                        if !eng.is_paused() =>
+                    {
+                        let sid = eng.session_id;
+                        match eng.db.insert_arena_match(sid, now_ms, *instance_id, bracket.clone(), *team_id).await {
+                            Ok(id) => {
+                                tracing::info!("DB arena match {} started ({})", id, bracket);
+                                eng.current_match_id = Some(id);
+                            }
+                            Err(e) => tracing::warn!("DB insert_arena_match failed: {}", e),
+                        }
+                    }
+                    LogEvent::ArenaMatchEnd { winning_team, duration_s, team_ratings, .. } => {
+                        if let Some(match_id) = eng.current_match_id.take() {
+                            let team = eng.combat.arena.as_ref().map_or(0, |a| a.team_id);
+                            let outcome = if team == *winning_team { "win" } else { "loss" };
+                            let rating  = team_ratings.get(team as usize).copied().unwrap_or(0);
+                            eng.db.end_arena_match(match_id, now_ms, outcome.to_owned(), *duration_s, rating);
                         }
                     }
+                    _ => {}
                 }
 
                 // Snapshot in_combat before state mutation to detect transitions
                 let was_in_combat = eng.combat.in_combat;
 
                 // Update the combat state machine for every event
-                update_state(&mut eng.combat, &event, now_ms);
-
-                // ── Open-world combat timeout ──────────────────────────────────
-                // If the player hasn't cast in 10 seconds during non-encounter
-                // combat, assume they've left combat (walked away from target
-                // dummies, stopped fighting, etc.).  ENCOUNTER_END is authoritative
-                // for dungeon/raid pulls; this timeout handles everything else.
-                const COMBAT_TIMEOUT_MS: u64 = 10_000;
-                if eng.combat.in_combat && eng.combat.encounter_name.is_none() {
-                    if let Some(last_cast) = eng.combat.last_player_cast_ms {
-                        if now_ms.saturating_sub(last_cast) > COMBAT_TIMEOUT_MS {
-                            tracing::info!(
-                                "Combat timeout: no player cast for {}ms — ending pull",
-                                now_ms.saturating_sub(last_cast)
-                            );
-                            eng.combat.end_pull(now_ms, PullOutcome::Wipe);
+                eng.apply_event(&event, now_ms);
+                if let Some(request) = eng.pending_fetch.take() {
+                    let _ = fetch_tx.try_send(request);
+                }
+
+                // ── Wipe forecast ──────────────────────────────────────────────
+                if let LogEvent::EncounterStart { encounter_id, encounter_name, .. } = &event {
+                    let (_, encounter) = encounters::canonical(*encounter_id, encounter_name);
+                    eng.wipe_model = match eng.db.encounter_pulls(encounter).await {
+                        Ok(samples) => WipeModel::train(&samples),
+                        Err(e)      => {
+                            tracing::warn!("DB encounter_pulls failed: {}", e);
+                            None
                         }
-                    }
+                    };
                 }
 
                 // ── Pull start ─────────────────────────────────────────────────
+                // Advice produced outside the rule passes, delivered with them.
+                let mut pull_advice = Vec::new();
                 if !was_in_combat && eng.combat.in_combat {
-                    eng.pull_number       += 1;
-                    eng.pull_advice_count  = 0;
-                    eng.pull_gcd_gap_count = 0;
+                    // Pulls that start while paused are tracked in memory only.
+                    eng.pull_tracked = !eng.is_paused();
+                }
+                if !was_in_combat && eng.combat.in_combat && eng.pull_tracked {
+                    eng.begin_pull();
                     let pn  = eng.pull_number;
                     let sid = eng.session_id;
-                    match eng.db.insert_pull(sid, pn, now_ms).await {
+                    let run_id = eng.current_run_id.filter(|_| eng.runs.inside());
+                    let keystone = eng.combat.keystone.clone();
+                    match eng.db.insert_pull(sid, pn, now_ms, run_id, keystone).await {
                         Ok(id) => {
                             tracing::info!("DB pull {} started (id={})", pn, id);
                             eng.current_pull_id = Some(id);
                         }
                         Err(e) => tracing::warn!("DB insert_pull failed: {}", e),
                     }
+                    // Another attempt at an encounter from this session:
+                    // remind the player how the last one went.
+                    if let LogEvent::EncounterStart { encounter_id, encounter_name, .. } = &event {
+                        let (_, encounter) = encounters::canonical(*encounter_id, encounter_name);
+                        match eng.db.last_attempt(sid, encounter).await {
+                            Ok(Some(previous)) => pull_advice = eng.progression_nudge(&previous, now_ms),
+                            Ok(None)           => {}
+                            Err(e)             => tracing::warn!("DB last_attempt failed: {}", e),
+                        }
+                    }
+                }
+                if eng.current_pull_id.is_some() && eng.pull_events.len() < MAX_ARCHIVED_EVENTS {
+                    eng.pull_events.push(event.clone());
+                    if eng.pull_events.len() == MAX_ARCHIVED_EVENTS {
+                        tracing::warn!("Pull archive full ({} events) — the rest of this pull is not archived", MAX_ARCHIVED_EVENTS);
+                    }
+                }
+
+                // ── Death recap ────────────────────────────────────────────────
+                // Built before the pull end below: an open-world death ends
+                // the pull on this same event and releases its DB row.
+                if let LogEvent::UnitDied { dest_guid, .. } = &event {
+                    if was_in_combat && eng.pull_tracked
+                        && Some(dest_guid.as_str()) == eng.combat.player_guid.as_deref()
+                    {
+                        if let Some(recap) = death_recap::build(&eng.combat, &eng.effective_am_spells, now_ms) {
+                            if let Some(pull_id) = eng.current_pull_id {
+                                eng.db.insert_death_recap(pull_id, recap.clone());
+                            }
+                            let _ = recap_tx.try_send(recap);
+                        }
+                    }
                 }
 
                 // ── Pull end ───────────────────────────────────────────────────
-                if was_in_combat && !eng.combat.in_combat {
-                    // Capture debrief stats BEFORE resetting pull-level counters.
-                    // At this point avoidable, interrupt_count, etc. still hold
-                    // the just-ended pull's values (reset happens on next start_pull).
-                    let pull_elapsed = eng.combat.pull_history.last()
-                        .and_then(|p| p.end_ms.zip(Some(p.start_ms)))
-                        .map(|(end, start)| end.saturating_sub(start))
-                        .unwrap_or(0);
-                    let outcome_str = eng.combat.pull_history.last()
-                        .and_then(|p| p.outcome.as_ref())
-                        .map(|o| format!("{:?}", o).to_lowercase())
-                        .unwrap_or_else(|| "unknown".to_string());
-
-                    let debrief = PullDebrief {
-                        pull_number:        eng.pull_number,
-                        pull_elapsed_ms:    pull_elapsed,
-                        outcome:            outcome_str.clone(),
-                        avoidable_count:    eng.combat.avoidable.total_hits(),
-                        interrupt_count:    eng.combat.interrupt_count,
-                        total_advice_fired: eng.pull_advice_count,
-                        gcd_gap_count:      eng.pull_gcd_gap_count,
-                    };
-                    tracing::info!(
-                        "Pull debrief: {} {}ms outcome={} avoidable={} interrupts={} advice={}",
-                        eng.pull_number, pull_elapsed, outcome_str,
-                        debrief.avoidable_count, debrief.interrupt_count, debrief.total_advice_fired
-                    );
+                if was_in_combat && !eng.combat.in_combat && eng.pull_tracked {
+                    let debrief = eng.finish_pull(Some(&event), now_ms, wall_ms);
                     let _ = debrief_tx.try_send(debrief);
+                    if let Some(kill) = eng.pending_kill.take() {
+                        match eng.db.update_records(kill.clone()).await {
+                            Ok(updates) => pull_advice.extend(eng.personal_record(&kill, &updates, now_ms)),
+                            Err(e)      => tracing::warn!("DB update_records failed: {}", e),
+                        }
+                    }
+                }
 
-                    if let Some(pull_id) = eng.current_pull_id.take() {
-                        eng.db.end_pull(pull_id, now_ms, outcome_str);
+                // ── Mythic+ key end ────────────────────────────────────────────
+                if let Some(key) = eng.track_key(&event, now_ms) {
+                    tracing::info!("Key debrief: {}", key.summary);
+                    if eng.session_id > 0 {
+                        eng.db.insert_key_run(eng.session_id, eng.current_run_id, now_ms, key.clone());
                     }
-                    // Reset per-pull dedup so rules fire fresh next pull
-                    eng.advice_last_ms.clear();
+                    let _ = key_tx.try_send(key);
                 }
 
                 // ── Rule evaluation ────────────────────────────────────────────
-                // Build context once — shared by both passes.
-                let ctx = RuleContext {
-                    state:     &eng.combat,
-                    identity:  &eng.identity,
-                    intensity: eng.config.intensity,
-                    now_ms,
-                };
-                let input = RuleInput { event: &event };
-
-                let mut candidates: Vec<AdviceEvent> = Vec::new();
-
-                // Pass 1: enemy event rules (interrupt_miss)
-                // Runs for all in-combat events regardless of GUID.
-                // The rule itself filters for enemy SpellCastSuccess.
-                if eng.combat.in_combat {
-                    candidates.extend(interrupt_miss::evaluate(&input, &ctx));
-                }
-
-                // Pass 2: coached player rules
-                if is_coached_event(&event, &eng.combat.player_guid) {
-                    candidates.extend(
-                        avoidable_repeat::evaluate(&input, &ctx)
-                            .into_iter()
-                            .chain(gcd_gap::evaluate(&input, &ctx))
-                            .chain(cooldown_drift::evaluate(&input, &ctx, &eng.effective_major_cds))
-                            .chain(interrupt_success::evaluate(&input, &ctx))
-                            .chain(defensive_timing::evaluate(&input, &ctx, &eng.effective_am_spells))
-                    );
+                // Suspended while paused; the state machine above keeps running
+                // so trackers are current the moment coaching resumes.
+                if eng.is_paused() {
+                    let _ = snap_tx.try_send(eng.snapshot(now_ms, wall_ms));
+                    continue;
                 }
 
-                // Dedup + fire all candidates
-                for advice in candidates {
-                    if eng.can_fire(&advice.key, &advice.severity, now_ms) {
-                        // Track GCD gap events for debrief
-                        if advice.key.starts_with("gcd_gap") {
-                            eng.pull_gcd_gap_count += 1;
-                        }
-
-                        eng.mark_fired(&advice.key, now_ms);
-                        eng.pull_advice_count += 1;
-
-                        // Persist to DB (fire-and-forget)
-                        if let Some(pull_id) = eng.current_pull_id {
-                            eng.db.insert_advice(
-                                pull_id,
-                                now_ms,
-                                advice.key.clone(),
-                                format!("{:?}", advice.severity).to_lowercase(),
-                                advice.message.clone(),
-                            );
-                        }
-
-                        if advice_tx.send(advice).await.is_err() {
-                            return Ok(());
-                        }
+                let fired = eng.evaluate_rules(&event, now_ms);
+                for mut advice in pull_advice.into_iter().chain(fired) {
+                    advice.wallclock_ms = wall_ms;
+                    eng.store_advice(&mut advice, Some(&event), now_ms);
+                    if advice_tx.send(advice).await.is_err() {
+                        break 'events;
                     }
                 }
 
                 // Emit a state snapshot after every event for the UI widgets
-                let snap = StateSnapshot {
-                    pull_elapsed_ms: eng.combat.pull_elapsed_ms(now_ms),
-                    gcd_gap_ms:      eng.combat.gcd.current_gap_ms,
-                    avoidable_count: eng.combat.avoidable.total_hits(),
-                    in_combat:       eng.combat.in_combat,
-                    interrupt_count: eng.combat.interrupt_count,
-                    encounter_name:  eng.combat.encounter_name.clone(),
-                };
+                let snap = eng.snapshot(now_ms, wall_ms);
                 let _ = snap_tx.try_send(snap); // Non-blocking — drop if UI is slow
             }
 
         }
     }
+    // The app is closing (or the pipeline went down): that ends the session.
+    eng.db.end_session(eng.session_id, unix_now_ms());
     Ok(())
 }
 
-// ---------------------------------------------------------------------------
-// State machine
-// ---------------------------------------------------------------------------
+/// Re-run the current rules over an archived pull (see recoach.rs).
+///
+/// The replay starts from a fresh engine: state learned earlier in the live
+/// session (interruptible spells, group composition, buffs applied before the
+/// pull) is not in the archive, so advice depending on it can differ from
+/// what fired live even when the rules have not changed.
+pub fn replay_pull(
+    events:   &[LogEvent],
+    identity: PlayerIdentity,
+    config:   AppConfig,
+    db:       DbWriter,
+) -> Vec<AdviceEvent> {
+    let mut eng = EngineState::new(config, db, -1, Arc::new(ClockAligner::new()));
+    if !identity.guid.is_empty() {
+        eng.set_identity(identity);
+    }
 
-fn is_coached_event(event: &LogEvent, player_guid: &Option<String>) -> bool {
-    let guid = player_guid.as_deref();
-    match event {
-        LogEvent::SpellCastSuccess { source_guid, .. } => Some(source_guid.as_str()) == guid,
-        LogEvent::SpellDamage { dest_guid, .. }        => Some(dest_guid.as_str()) == guid,
-        LogEvent::SpellHeal { source_guid, .. }        => Some(source_guid.as_str()) == guid,
-        LogEvent::SwingDamage { dest_guid, .. }        => Some(dest_guid.as_str()) == guid,
-        LogEvent::SpellInterrupted { source_guid, .. } => Some(source_guid.as_str()) == guid,
-        LogEvent::UnitDied { .. }                      => true,
-        LogEvent::EncounterStart { .. }                => true,
-        LogEvent::EncounterEnd { .. }                  => true,
-        LogEvent::SpellCastFailed { source_guid, .. } => Some(source_guid.as_str()) == guid,
-        LogEvent::SpellCastStart { source_guid, .. }  => Some(source_guid.as_str()) == guid,
+    let mut fired = Vec::new();
+    for event in events {
+        let now_ms = event.timestamp_ms();
+        let was_in_combat = eng.combat.in_combat;
+        eng.apply_event(event, now_ms);
+        let ended = was_in_combat && !eng.combat.in_combat;
+        if ended {
+            eng.advice_last_ms.clear();
+        }
+        let advice = eng.evaluate_rules(event, now_ms);
+        // Live, the pull row is closed before the ending event's rules run,
+        // so their advice is not stored with the pull.
+        if !ended {
+            fired.extend(advice);
+        }
     }
+    fired
 }
 
+/// One pull of a whole-log analysis (`analyze_log`).
+#[derive(Debug)]
+pub struct AnalyzedPull {
+    pub debrief:    PullDebrief,
+    /// Encounter or arena bracket the pull was fought in; None in the open world.
+    pub encounter:  Option<String>,
+    pub started_ms: u64,
+    /// Advice fired during the pull, in order.
+    pub advice:     Vec<AdviceEvent>,
+}
+
+/// Run a complete log through the engine synchronously and return its pulls
+/// with the advice each one got (see analyze.rs).
+///
+/// Follows `run` with log time as the only clock: engine ticks and the
+/// open-world combat timeout are emulated in the quiet stretches between
+/// events, so time-driven rules fire as they would live.  Nothing is stored,
+/// and progression nudges (which read earlier attempts from the database)
+/// are not produced.  A pull still in progress where the log ends is closed
+/// at its last event with outcome "unknown".
+pub fn analyze_log(events: impl IntoIterator<Item = LogEvent>, config: AppConfig) -> Vec<AnalyzedPull> {
+    let mut eng = EngineState::new(config, db::detached_db_writer(), -1, Arc::new(ClockAligner::new()));
+    let tick_ms = TICK_INTERVAL.as_millis() as u64;
+    let mut pulls = Vec::new();
+    let mut open: Option<OpenPull> = None;
+
+    for event in events {
+        let now_ms = event.timestamp_ms();
+
+        // Ticks and the combat timeout between the previous event and this one.
+        let mut clock_ms = eng.last_log_ms;
+        while eng.combat.in_combat {
+            let next = match eng.combat_timeout_in(clock_ms) {
+                Some(left) => (clock_ms + left).min(clock_ms + tick_ms),
+                None       => clock_ms + tick_ms,
+            };
+            if next >= now_ms {
+                break;
+            }
+            clock_ms = next;
+            if eng.check_combat_timeout(clock_ms) {
+                pulls.extend(close_analyzed_pull(&mut eng, open.take(), None, clock_ms));
+            } else if let Some(pull) = open.as_mut() {
+                pull.advice.extend(eng.evaluate_tick(clock_ms));
+            }
+        }
+        eng.last_log_ms = now_ms;
+
+        eng.observe_player(&event);
+        if let LogEvent::ZoneChange { instance_id, zone_name, difficulty_id, .. } = &event {
+            eng.runs.zone_changed(*instance_id, zone_name, *difficulty_id, now_ms);
+        }
+
+        let was_in_combat = eng.combat.in_combat;
+        eng.apply_event(&event, now_ms);
+        if !was_in_combat && eng.combat.in_combat {
+            eng.pull_tracked = true;
+            eng.begin_pull();
+            open = Some(OpenPull {
+                encounter:  eng.combat.encounter_name.clone(),
+                started_ms: now_ms,
+                advice:     Vec::new(),
+            });
+        }
+        if was_in_combat && !eng.combat.in_combat {
+            pulls.extend(close_analyzed_pull(&mut eng, open.take(), Some(&event), now_ms));
+        }
+
+        let fired = eng.evaluate_rules(&event, now_ms);
+        if let Some(pull) = open.as_mut() {
+            pull.advice.extend(fired);
+        }
+    }
+
+    if eng.combat.in_combat {
+        let last_ms = eng.last_log_ms;
+        let deadline = eng.combat_timeout_in(last_ms).map(|left| last_ms + left);
+        match deadline {
+            // Open world: the log went quiet, so the timeout ends the pull.
+            Some(at) if eng.check_combat_timeout(at) => {
+                pulls.extend(close_analyzed_pull(&mut eng, open.take(), None, at));
+            }
+            _ => {
+                eng.combat.end_pull(last_ms, PullOutcome::Wipe);
+                if let Some(mut pull) = close_analyzed_pull(&mut eng, open.take(), None, last_ms) {
+                    pull.debrief.outcome = "unknown".to_owned();
+                    if pull.debrief.practice.is_none() {
+                        pull.debrief.summary = pull_summary(&pull.debrief, &eng.config.number_format);
+                    }
+                    pulls.push(pull);
+                }
+            }
+        }
+    }
+    pulls
+}
+
+/// A pull `analyze_log` is collecting advice for.
+struct OpenPull {
+    encounter:  Option<String>,
+    started_ms: u64,
+    advice:     Vec<AdviceEvent>,
+}
+
+/// Debrief the pull `analyze_log` had open.
+fn close_analyzed_pull(
+    eng:    &mut EngineState,
+    open:   Option<OpenPull>,
+    ending: Option<&LogEvent>,
+    now_ms: u64,
+) -> Option<AnalyzedPull> {
+    let pull = open?;
+    Some(AnalyzedPull {
+        debrief:    eng.finish_pull(ending, now_ms, now_ms),
+        encounter:  pull.encounter,
+        started_ms: pull.started_ms,
+        advice:     pull.advice,
+    })
+}
+
+/// Squad-mode leaderboard for the debrief, most avoidable damage first.
+fn party_leaderboard(combat: &CombatState) -> Vec<PartyDamage> {
+    combat.party_damage.leaderboard()
+        .into_iter()
+        .map(|(guid, m)| PartyDamage {
+            name:            m.name.clone(),
+            is_player:       Some(guid) == combat.player_guid.as_deref(),
+            damage_taken:    m.total,
+            avoidable_taken: m.avoidable,
+            avoidable_hits:  m.avoidable_hits,
+        })
+        .collect()
+}
+
+/// The coached healer's effective healing by recipient over a pull; None
+/// without healing done.
+fn healing_distribution(combat: &CombatState) -> Option<HealingDistribution> {
+    let player = combat.player_guid.as_deref()?;
+    let tanks = combat.party_damage.tanks();
+    let split = combat.healing.split(player, &tanks);
+    let effective = split.effective();
+    if effective == 0 {
+        return None;
+    }
+    let pct = |part: u64| part as f32 / effective as f32 * 100.0;
+    let mut tank_names: Vec<String> = tanks
+        .iter()
+        .filter(|&&g| g != player)
+        .filter_map(|g| combat.party_damage.members.get(*g))
+        .map(|m| m.name.clone())
+        .collect();
+    tank_names.sort();
+    Some(HealingDistribution {
+        effective,
+        overheal_pct: split.overhealing as f32 / (effective + split.overhealing) as f32 * 100.0,
+        tank_pct:     pct(split.tank),
+        party_pct:    pct(split.party),
+        self_pct:     pct(split.self_heal),
+        tank_names,
+    })
+}
+
+/// Pre-pull preparation from the coached player's casts before
+/// ENCOUNTER_START; None for pulls without one or while the player is
+/// unknown (their casts were not recognised).
+fn prepull_check(combat: &CombatState, prepull_spell_ids: &[u32]) -> Option<PrepullCheck> {
+    combat.player_guid.as_ref()?;
+    let casts = combat.prepull.at_encounter.as_ref()?;
+    let name = |id: u32| spells::name(id).map_or_else(|| id.to_string(), str::to_owned);
+    let potion = casts.iter().any(|&id| consumables::is_combat_potion(id));
+    let precasts: Vec<String> = prepull_spell_ids.iter()
+        .filter(|id| casts.contains(id))
+        .map(|&id| name(id))
+        .collect();
+    let mut missing = Vec::new();
+    if !potion {
+        missing.push("pre-potion".to_owned());
+    }
+    if precasts.is_empty() && !prepull_spell_ids.is_empty() {
+        let options: Vec<String> = prepull_spell_ids.iter().map(|&id| name(id)).collect();
+        missing.push(format!("pre-cast ({})", options.join(" or ")));
+    }
+    Some(PrepullCheck { potion, precasts, missing })
+}
+
+/// Hard casts the coached player abandoned over a pull; None when every cast
+/// went through.
+fn cancelled_casts(combat: &CombatState) -> Option<CancelledCasts> {
+    let hardcasts = &combat.hardcasts;
+    if hardcasts.cancelled.is_empty() {
+        return None;
+    }
+    let mut by_spell: Vec<(&str, u32)> = Vec::new();
+    for c in &hardcasts.cancelled {
+        match by_spell.iter_mut().find(|(name, _)| *name == c.spell_name) {
+            Some((_, n)) => *n += 1,
+            None         => by_spell.push((&c.spell_name, 1)),
+        }
+    }
+    by_spell.sort_by_key(|&(_, n)| std::cmp::Reverse(n));
+    Some(CancelledCasts {
+        count:   hardcasts.cancelled.len() as u32,
+        started: hardcasts.started,
+        lost_ms: hardcasts.lost_ms(),
+        spells:  by_spell.into_iter()
+            .map(|(name, n)| if n > 1 { format!("{} ×{}", name, n) } else { name.to_owned() })
+            .collect(),
+    })
+}
+
+/// Damage the coached player took from their own side over a pull; None
+/// when there was none.
+fn friendly_fire(combat: &CombatState) -> Option<FriendlyFire> {
+    let taken = &combat.damage_taken;
+    if taken.friendly.is_empty() {
+        return None;
+    }
+    let mut spells: Vec<(&String, u64)> = taken.friendly.iter().map(|(name, f)| (name, f.amount)).collect();
+    spells.sort_by_key(|&(_, amount)| std::cmp::Reverse(amount));
+    Some(FriendlyFire {
+        self_inflicted: taken.friendly_total(DamageSource::SelfInflicted),
+        from_party:     taken.friendly_total(DamageSource::Party),
+        counted:        combat.friendly_avoidable,
+        spells:         spells.into_iter().map(|(name, _)| name.clone()).collect(),
+    })
+}
+
+/// Reaction time to kill-priority enemies over a pull; None when none appeared
+/// or the player never switched.
+fn target_switch(latencies: &[u64]) -> Option<TargetSwitch> {
+    let worst_ms = *latencies.iter().max()?;
+    Some(TargetSwitch {
+        switches: latencies.len() as u32,
+        avg_ms:   latencies.iter().sum::<u64>() / latencies.len() as u64,
+        worst_ms,
+    })
+}
+
+/// One-line pull summary for raid chat / Discord, e.g.
+/// "Pull 14 — wipe 4:32 — 2 avoidable, 3 kicks, CD drift 11s".
+fn pull_summary(d: &PullDebrief, fmt: &NumberFormat) -> String {
+    let kick_pct = d.interrupt_efficiency
+        .map(|p| format!(" ({:.0}%)", p))
+        .unwrap_or_default();
+    format!(
+        "Pull {} — {} {} — {} avoidable, {} kicks{}, CD drift {}",
+        d.pull_number, d.outcome, fmt.duration(d.pull_elapsed_ms),
+        d.avoidable_count, d.interrupt_count, kick_pct, fmt.seconds(d.cd_drift_ms, 0),
+    )
+}
+
+// ---------------------------------------------------------------------------
+// State machine
+// ---------------------------------------------------------------------------
+
 fn update_state(state: &mut CombatState, event: &LogEvent, now_ms: u64) {
     match event {
-        LogEvent::SpellCastSuccess { source_guid, spell_id, .. } => {
+        LogEvent::SpellCastSuccess { source_guid, source_name, source_flags, dest_guid, dest_name, spell_id, spell_name, power, .. } => {
+            state.group.record_cast(source_guid, *source_flags, now_ms);
+            if state.in_combat {
+                state.dodge.record_cast(*spell_id, spell_name, dest_guid, state.player_guid.as_deref(), now_ms);
+            }
             let is_player = Some(source_guid.as_str()) == state.player_guid.as_deref();
+            if !is_player && state::is_group_player(*source_flags) {
+                state.interrupts.record_party_cast(source_guid, source_name, *spell_id, now_ms);
+            }
+            if state.in_combat && state::is_group_player(*source_flags) {
+                let elapsed_ms = state.pull_elapsed_ms(now_ms);
+                state.group_deaths.record_cast(*spell_id, source_name, dest_name, elapsed_ms);
+            }
             // Only start a pull from the coached player's own cast.
             // When player GUID is not yet known (player_focus not configured),
             // fall back to any cast so combat is still detected.
@@ -493,19 +2141,82 @@ fn update_state(state: &mut CombatState, event: &LogEvent, now_ms: u64) {
             if !state.in_combat && (is_player || state.player_guid.is_none()) {
                 state.start_pull(now_ms);
             }
+            // Enemy cast that went through: a missed kick if the kick was ready.
+            if state.in_combat && guid::kind(source_guid).is_npc() {
+                state.interrupts.record_completed_cast(*spell_id, now_ms);
+                state.enemy_casts.end(source_guid);
+            }
+            if is_player && state.encounter_start_ms.is_none() {
+                state.prepull.record(*spell_id, now_ms);
+            }
             if is_player {
+                if state.in_combat {
+                    state.practice.record_cast(now_ms, *spell_id, *power);
+                    if guid::kind(dest_guid).is_npc() {
+                        state.targets.player_hit(dest_guid, dest_name, now_ms);
+                    }
+                    if state.damage_done.is_boss(dest_guid) {
+                        state.melee.record_contact(now_ms);
+                    }
+                }
                 state.gcd.record_cast(now_ms);
+                state.interrupts.record_player_cast(*spell_id, now_ms);
                 state.cooldowns.record_cast(*spell_id, now_ms);
+                state.consumables.record_use(*spell_id, now_ms);
+                state.activity.record_cast_success(*spell_id, now_ms);
+                state.hardcasts.record_success(*spell_id);
                 state.last_player_cast_ms = Some(now_ms);
             }
+            // Defensive and consumable casts for the death recap.
+            state.event_window.push(event.clone(), now_ms);
         }
 
-        LogEvent::SpellDamage { source_guid, dest_guid, spell_id, amount, .. } => {
-            if Some(dest_guid.as_str()) == state.player_guid.as_deref() {
-                state.avoidable.record_hit(*spell_id, now_ms);
+        LogEvent::SpellDamage {
+            source_guid, source_name, source_flags, dest_guid, dest_name, dest_flags, dest_raid_flags,
+            spell_id, spell_name, amount, ..
+        } => {
+            let from_player = Some(source_guid.as_str()) == state.player_guid.as_deref();
+            let to_player   = Some(dest_guid.as_str()) == state.player_guid.as_deref();
+            if state.in_combat {
+                state.damage_done.record(dest_guid, dest_name, *amount, from_player);
+                state.party_damage.record(dest_guid, dest_name, *dest_flags, Some(*spell_id), *amount);
+                if state.damage_done.is_boss(dest_guid) {
+                    state.melee.record_boss_hit(now_ms);
+                }
+                if from_player {
+                    state.practice.record_enemy(dest_guid, dest_name);
+                    state.burst.record_damage(now_ms, *amount);
+                    state.outgoing.record_damage(now_ms, *amount);
+                } else if to_player {
+                    state.practice.record_enemy(source_guid, source_name);
+                }
+                if guid::kind(dest_guid).is_npc() {
+                    state.targets.see_enemy(dest_guid, dest_name, *dest_raid_flags, now_ms);
+                } else if guid::kind(source_guid).is_npc() {
+                    state.targets.see_enemy(source_guid, source_name, 0, now_ms);
+                }
+            }
+            if to_player {
+                let source = DamageSource::of(source_guid, *source_flags, Some(dest_guid));
+                if source.is_friendly() {
+                    state.damage_taken.record_friendly(source, spell_name, *amount);
+                }
+                if state.avoidable_source(source) {
+                    state.avoidable.record_hit(*spell_id, now_ms);
+                    state.dodge.record_hit(*spell_id, spell_name, now_ms);
+                }
                 state.damage_taken.record(now_ms, *amount);
+                state.death.record_hit(spell_name);
+            } else {
+                let avoidable = state.avoidable_source(DamageSource::of(source_guid, *source_flags, Some(dest_guid)));
+                if let Some(member) = state.member(dest_guid, dest_name, *dest_flags) {
+                    if avoidable {
+                        member.avoidable.record_hit(*spell_id, now_ms);
+                    }
+                    member.death.record_hit(spell_name);
+                }
             }
-            if Some(source_guid.as_str()) == state.player_guid.as_deref() {
+            if from_player {
                 // DoT ticks and channeled damage keep the combat alive.
                 // This prevents premature timeout when the player is casting
                 // nothing but damage-over-time spells are still ticking.
@@ -514,18 +2225,104 @@ fn update_state(state: &mut CombatState, event: &LogEvent, now_ms: u64) {
             state.event_window.push(event.clone(), now_ms);
         }
 
-        LogEvent::SwingDamage { source_guid, dest_guid, amount, .. } => {
-            if Some(dest_guid.as_str()) == state.player_guid.as_deref() {
+        LogEvent::SwingDamage {
+            source_guid, source_name, source_flags, dest_guid, dest_name, dest_flags, dest_raid_flags, amount, ..
+        } => {
+            let from_player = Some(source_guid.as_str()) == state.player_guid.as_deref();
+            let to_player   = Some(dest_guid.as_str()) == state.player_guid.as_deref();
+            if state.in_combat {
+                state.damage_done.record(dest_guid, dest_name, *amount, from_player);
+                state.party_damage.record(dest_guid, dest_name, *dest_flags, None, *amount);
+                if from_player {
+                    state.practice.record_enemy(dest_guid, dest_name);
+                    state.burst.record_damage(now_ms, *amount);
+                    state.outgoing.record_damage(now_ms, *amount);
+                    state.melee.record_swing();
+                }
+                // Auto-attacks are the melee-range cadence; anyone's hit on
+                // the boss is the uptime clock (MeleeTracker).
+                if state.damage_done.is_boss(dest_guid) {
+                    if from_player {
+                        state.melee.record_contact(now_ms);
+                    }
+                    state.melee.record_boss_hit(now_ms);
+                } else if to_player {
+                    state.practice.record_enemy(source_guid, source_name);
+                }
+                if guid::kind(dest_guid).is_npc() {
+                    state.targets.see_enemy(dest_guid, dest_name, *dest_raid_flags, now_ms);
+                    if from_player {
+                        state.targets.player_hit(dest_guid, dest_name, now_ms);
+                    }
+                } else if guid::kind(source_guid).is_npc() {
+                    state.targets.see_enemy(source_guid, source_name, 0, now_ms);
+                }
+            }
+            if to_player {
+                let source = DamageSource::of(source_guid, *source_flags, Some(dest_guid));
+                if source.is_friendly() {
+                    state.damage_taken.record_friendly(source, "Melee", *amount);
+                }
                 state.damage_taken.record(now_ms, *amount);
+                state.death.record_hit("Melee");
+            } else if let Some(member) = state.member(dest_guid, dest_name, *dest_flags) {
+                member.death.record_hit("Melee");
             }
-            if Some(source_guid.as_str()) == state.player_guid.as_deref() {
+            if from_player {
                 // Auto-attacks keep the combat alive between casts.
                 state.last_player_cast_ms = Some(now_ms);
             }
             state.event_window.push(event.clone(), now_ms);
         }
 
-        LogEvent::UnitDied { dest_guid, .. } => {
+        LogEvent::SpellHeal { source_guid, dest_guid, dest_name, amount, overhealing, .. } => {
+            if state.in_combat && Some(source_guid.as_str()) == state.player_guid.as_deref() {
+                state.healing.record(dest_guid, dest_name, *amount, *overhealing);
+                state.outgoing.record_healing(now_ms, amount.saturating_sub(*overhealing));
+            }
+            state.event_window.push(event.clone(), now_ms);
+        }
+
+        LogEvent::SpellMissed { dest_guid, spell_id, spell_name, .. } => {
+            if Some(dest_guid.as_str()) == state.player_guid.as_deref() {
+                state.dodge.record_miss(*spell_id, spell_name, now_ms);
+            }
+        }
+
+        LogEvent::SpellAbsorbed { dest_guid, amount, .. } => {
+            if Some(dest_guid.as_str()) == state.player_guid.as_deref() {
+                state.damage_taken.record_absorbed(now_ms, *amount);
+            }
+        }
+
+        LogEvent::Energize { dest_guid, amount, overflow, power_type, max_power, .. } => {
+            if state.in_combat && Some(dest_guid.as_str()) == state.player_guid.as_deref() {
+                state.resources.record(*power_type, *amount, *overflow, *max_power);
+            }
+        }
+
+        LogEvent::UnitDied { dest_guid, dest_name, .. } => {
+            state.buffs.unit_died(dest_guid);
+            state.targets.unit_died(dest_guid);
+            state.enemy_casts.end(dest_guid);
+            let elapsed_ms = state.pull_elapsed_ms(now_ms);
+            // UNIT_DIED carries no unit flags: a player counts as group when
+            // seen casting with the group or taking damage in it this pull.
+            let in_group = Some(dest_guid.as_str()) == state.player_guid.as_deref()
+                || state.group.is_present(dest_guid, now_ms)
+                || state.party_damage.members.contains_key(dest_guid);
+            if state.in_combat && guid::kind(dest_guid) == UnitKind::Player && in_group {
+                state.group_deaths.record_death(dest_name, elapsed_ms);
+            }
+            if let Some(member) = state.members.get_mut(dest_guid).filter(|_| state.in_combat) {
+                member.death.record_death(elapsed_ms);
+            }
+            if Some(dest_guid.as_str()) == state.player_guid.as_deref() {
+                state.player_deaths += 1;
+                if state.in_combat {
+                    state.death.record_death(state.pull_elapsed_ms(now_ms));
+                }
+            }
             // In non-encounter combat, only the player's own death ends a pull.
             // ENCOUNTER_END is authoritative for kill/wipe in dungeons/raids.
             //
@@ -544,7 +2341,49 @@ fn update_state(state: &mut CombatState, event: &LogEvent, now_ms: u64) {
             }
         }
 
-        LogEvent::SpellInterrupted { source_guid, interrupted_spell_id, .. } => {
+        LogEvent::AuraApplied { timestamp_ms, source_guid, dest_guid, spell_id, spell_name, .. } => {
+            let player = state.player_guid.as_deref();
+            if Some(dest_guid.as_str()) == player {
+                state.auras.apply(*spell_id, spell_name);
+                state.lust.apply(*spell_id, spell_name, now_ms);
+                if state.cc_spell_ids.contains(spell_id) {
+                    state.gcd.cc_applied(*spell_id);
+                }
+            }
+            if player.is_some_and(|p| p == source_guid || p == dest_guid) {
+                state.buffs.apply(dest_guid, *spell_id, *timestamp_ms);
+            }
+        }
+
+        LogEvent::AuraRefreshed { timestamp_ms, source_guid, dest_guid, spell_id, spell_name, .. } => {
+            let player = state.player_guid.as_deref();
+            // A refresh also proves a buff applied before logging started.
+            if Some(dest_guid.as_str()) == player {
+                state.auras.apply(*spell_id, spell_name);
+                state.lust.apply(*spell_id, spell_name, now_ms);
+            }
+            if player.is_some_and(|p| p == source_guid || p == dest_guid) {
+                state.buffs.refresh(dest_guid, *spell_id, *timestamp_ms);
+            }
+        }
+
+        LogEvent::AuraRemoved { timestamp_ms, source_guid, dest_guid, spell_id, spell_name, .. } => {
+            let player = state.player_guid.as_deref();
+            if Some(dest_guid.as_str()) == player {
+                state.auras.remove(*spell_id, spell_name);
+                state.lust.remove(*spell_id, now_ms);
+                state.gcd.cc_removed(*spell_id);
+            }
+            if player.is_some_and(|p| p == source_guid || p == dest_guid) {
+                state.buffs.remove(dest_guid, *spell_id, *timestamp_ms);
+            }
+        }
+
+        LogEvent::SpellInterrupted { source_guid, target_guid, interrupted_spell_id, .. } => {
+            state.enemy_casts.end(target_guid);
+            if Some(target_guid.as_str()) == state.player_guid.as_deref() {
+                state.hardcasts.kicked();
+            }
             if Some(source_guid.as_str()) == state.player_guid.as_deref() {
                 state.interrupt_count += 1;
                 // Record this spell as interruptible for future interrupt_miss rule
@@ -553,12 +2392,17 @@ fn update_state(state: &mut CombatState, event: &LogEvent, now_ms: u64) {
             state.event_window.push(event.clone(), now_ms);
         }
 
-        LogEvent::EncounterStart { encounter_name, .. } => {
+        LogEvent::EncounterStart { encounter_id, encounter_name, .. } => {
             tracing::info!("ENCOUNTER_START: {}", encounter_name);
-            state.encounter_name = Some(encounter_name.clone());
+            // Renamed bosses are tracked under their canonical name.
+            state.encounter_name = Some(encounters::canonical(*encounter_id, encounter_name).1);
+            state.encounter_start_ms = Some(now_ms);
+            // COMBATANT_INFO for every player follows.
+            state.group_classes.clear();
             if !state.in_combat {
                 state.start_pull(now_ms);
             }
+            state.prepull.encounter_start(now_ms);
         }
 
         LogEvent::EncounterEnd { encounter_name, success, .. } => {
@@ -568,15 +2412,107 @@ fn update_state(state: &mut CombatState, event: &LogEvent, now_ms: u64) {
                 state.end_pull(now_ms, outcome);
             }
             state.encounter_name = None;
+            state.encounter_start_ms = None;
+        }
+
+        // Arena matches are bounded like encounters: the match is the pull,
+        // so the open-world timeout never splits it and the winner decides
+        // the outcome (Kill = win, Wipe = loss).
+        LogEvent::ArenaMatchStart { instance_id, bracket, team_id, .. } => {
+            let map = parser::arena_map_name(*instance_id).unwrap_or("Arena");
+            tracing::info!("ARENA_MATCH_START: {} on {}", bracket, map);
+            if state.in_combat {
+                state.end_pull(now_ms, PullOutcome::Wipe);
+            }
+            state.arena = Some(ArenaMatch {
+                instance_id: *instance_id,
+                bracket:     bracket.clone(),
+                team_id:     *team_id,
+            });
+            state.encounter_name = Some(format!("{} — {}", map, bracket));
+            state.start_pull(now_ms);
         }
 
-        LogEvent::SpellCastFailed { .. } | LogEvent::SpellCastStart { .. } => {
+        LogEvent::ArenaMatchEnd { winning_team, .. } => {
+            let won = state.arena.as_ref().is_some_and(|a| a.team_id == *winning_team);
+            tracing::info!("ARENA_MATCH_END: won={}", won);
+            if state.in_combat {
+                state.end_pull(now_ms, if won { PullOutcome::Kill } else { PullOutcome::Wipe });
+            }
+            state.arena = None;
+            state.encounter_name = None;
+        }
+
+        LogEvent::SpellCastStart { source_guid, spell_id, spell_name, .. } => {
+            if Some(source_guid.as_str()) == state.player_guid.as_deref() {
+                state.activity.record_cast_start(*spell_id, now_ms);
+                state.hardcasts.record_start(*spell_id, spell_name, now_ms);
+                if state.encounter_start_ms.is_none() {
+                    state.prepull.record(*spell_id, now_ms);
+                }
+            } else if state.in_combat && guid::kind(source_guid).is_npc() {
+                state.enemy_casts.start(source_guid, *spell_id, spell_name, now_ms);
+            }
             state.event_window.push(event.clone(), now_ms);
         }
 
-        _ => {
+        LogEvent::SpellCastFailed { source_guid, spell_id, failed_type, .. } => {
+            if Some(source_guid.as_str()) == state.player_guid.as_deref() {
+                state.activity.cancel_cast(*spell_id);
+                state.hardcasts.record_failed(*spell_id, failed_type, now_ms);
+                state.failed_casts.record(failed_type);
+                state.gcd.record_failed(failed_type);
+            } else {
+                state.enemy_casts.end(source_guid);
+            }
             state.event_window.push(event.clone(), now_ms);
         }
+
+        // Session runs are tracked by the engine (runs.rs), not here.  Leaving
+        // the key's dungeon without a CHALLENGE_MODE_END (log started mid-key,
+        // disconnect) drops the stale key.
+        LogEvent::ZoneChange { instance_id, .. } => {
+            if state.keystone.as_ref().is_some_and(|k| k.instance_id != *instance_id) {
+                state.keystone = None;
+            }
+        }
+
+        LogEvent::ChallengeModeStart { zone_name, instance_id, keystone_level, affix_ids, .. } => {
+            tracing::info!("CHALLENGE_MODE_START: {} +{}", zone_name, keystone_level);
+            state.keystone = Some(Keystone {
+                instance_id: *instance_id,
+                dungeon:     zone_name.clone(),
+                level:       *keystone_level,
+                affix_ids:   affix_ids.clone(),
+            });
+        }
+
+        LogEvent::ChallengeModeEnd { instance_id, success, keystone_level, total_time_ms, .. } => {
+            tracing::info!(
+                "CHALLENGE_MODE_END: instance {} +{} success={} time={}ms",
+                instance_id, keystone_level, success, total_time_ms
+            );
+            state.keystone = None;
+        }
+
+        // Spec detection is the engine's (`observe_player`); the auras
+        // logged for the player are the state of the raid buffs at the pull.
+        LogEvent::CombatantInfo { player_guid, spec_id, auras, .. } => {
+            if let Some((class, _)) = specs::spec_by_id(*spec_id) {
+                state.group_classes.insert(class.to_owned());
+            }
+            if Some(player_guid.as_str()) == state.player_guid.as_deref() {
+                for buff in raid_buffs::all() {
+                    for &id in &buff.spell_ids {
+                        if auras.contains(&id) {
+                            state.auras.apply(id, &buff.name);
+                        } else if state.auras.active.contains_key(&id) {
+                            state.auras.remove(id, &buff.name);
+                        }
+                    }
+                }
+            }
+        }
     }
 }
 
@@ -595,9 +2531,65 @@ fn extract_char_name(full_name: &str) -> &str {
     full_name.split('-').next().unwrap_or(full_name)
 }
 
-fn unix_now_ms() -> u64 {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as u64
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engine() -> EngineState {
+        EngineState::new(AppConfig::default(), db::detached_db_writer(), -1, Arc::new(ClockAligner::new()))
+    }
+
+    fn warn(key: &str) -> AdviceEvent {
+        rules::advice(key, "Stood in fire", "Move".to_owned(), Severity::Warn, vec![], 0)
+    }
+
+    #[test]
+    fn urgent_advice_skips_a_normal_fires_cooldown() {
+        let mut eng = engine();
+        let advice = warn("avoidable:1");
+        eng.mark_fired(&advice, 1_000);
+        assert!(!eng.can_fire(&advice, Priority::Normal, 2_000));
+        assert!(eng.can_fire(&advice, Priority::Urgent, 2_000));
+
+        // An urgent fire holds back the next urgent one like any other.
+        let urgent = AdviceEvent { priority: Priority::Urgent, ..warn("avoidable:1") };
+        eng.mark_fired(&urgent, 2_000);
+        assert!(!eng.can_fire(&urgent, Priority::Urgent, 3_000));
+    }
+
+    #[test]
+    fn escalation_to_urgent_gets_past_the_cooldown() {
+        let mut eng = engine();
+        let settings = EncounterSettings::default();
+        eng.combat.advice_repeats.insert("avoidable:1".to_owned(), ESCALATE_URGENT_AT - 2);
+        eng.mark_fired(&warn("avoidable:1"), 1_000);
+
+        // The next repeat is not urgent yet: still on cooldown.
+        assert!(eng.dedup(vec![warn("avoidable:1")], &settings, 2_000).is_empty());
+
+        // The one after escalates to urgent, so the cooldown does not apply.
+        eng.combat.advice_repeats.insert("avoidable:1".to_owned(), ESCALATE_URGENT_AT - 1);
+        let fired = eng.dedup(vec![warn("avoidable:1")], &settings, 2_000);
+        assert_eq!(fired.len(), 1);
+        assert_eq!((fired[0].priority, fired[0].repeat), (Priority::Urgent, ESCALATE_URGENT_AT));
+    }
+
+    #[test]
+    fn paused_replay_does_not_end_the_pull() {
+        let mut eng = EngineState::new(AppConfig::default(), db::detached_db_writer(), -1, Arc::new(ClockAligner::new()));
+        let t0 = Instant::now();
+        eng.combat.in_combat = true;
+        eng.combat.last_player_cast_ms = Some(100_000);
+        eng.log_clock.event(100_000, t0);
+
+        // Paused a minute: log time holds, so the open-world timeout never runs out.
+        eng.log_clock.set_rate(0, t0);
+        assert!(!eng.check_combat_timeout(eng.log_clock.now_ms(t0 + Duration::from_secs(60))));
+        assert!(eng.combat.in_combat);
+
+        // Resumed: the timeout counts from where the replay stood.
+        eng.log_clock.set_rate(1, t0 + Duration::from_secs(60));
+        assert!(!eng.check_combat_timeout(eng.log_clock.now_ms(t0 + Duration::from_secs(65))));
+        assert!(eng.check_combat_timeout(eng.log_clock.now_ms(t0 + Duration::from_secs(71))));
+    }
 }
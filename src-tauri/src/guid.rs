@@ -0,0 +1,90 @@
+/// Combat-log GUID classification.
+///
+/// Unit GUIDs start with their type:
+///   Player-<server>-<id>
+///   Pet-0-<server>-<instance>-<zone>-<npc id>-<spawn>
+///   Creature-0-<server>-<instance>-<zone>-<npc id>-<spawn>
+///   Vehicle-0-<server>-<instance>-<zone>-<npc id>-<spawn>
+///
+/// Bosses are creatures/vehicles whose NPC ID is listed in the active
+/// encounter's `boss_npc_ids` (encounter TOML) — the GUID alone cannot tell a
+/// boss from trash.  Rules go through `CombatState::unit_kind`, which applies
+/// the current encounter's list.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitKind {
+    Player,
+    Pet,
+    /// Creature/vehicle listed in the encounter's `boss_npc_ids`.
+    Boss,
+    Creature,
+    Vehicle,
+    /// GameObjects, empty GUIDs ("0000000000000000") and anything unknown.
+    Other,
+}
+
+impl UnitKind {
+    /// Enemy-side NPC (boss, creature or vehicle).
+    pub fn is_npc(self) -> bool {
+        matches!(self, Self::Boss | Self::Creature | Self::Vehicle)
+    }
+}
+
+/// Type of a GUID by prefix alone (never `Boss`).
+pub fn kind(guid: &str) -> UnitKind {
+    match guid.split('-').next() {
+        Some("Player")   => UnitKind::Player,
+        Some("Pet")      => UnitKind::Pet,
+        Some("Creature") => UnitKind::Creature,
+        Some("Vehicle")  => UnitKind::Vehicle,
+        _                => UnitKind::Other,
+    }
+}
+
+/// Type of a GUID, with creatures/vehicles in `boss_npc_ids` reported as `Boss`.
+pub fn classify(guid: &str, boss_npc_ids: &[u32]) -> UnitKind {
+    let kind = kind(guid);
+    if kind.is_npc() && npc_id(guid).is_some_and(|id| boss_npc_ids.contains(&id)) {
+        UnitKind::Boss
+    } else {
+        kind
+    }
+}
+
+/// NPC ID of a creature/vehicle GUID ("Creature-0-1469-2549-12530-209090-…").
+/// None for players, pets and anything else.
+pub fn npc_id(guid: &str) -> Option<u32> {
+    if !kind(guid).is_npc() {
+        return None;
+    }
+    guid.split('-').nth(5)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOSS: &str = "Creature-0-1469-2549-12530-209090-00002C5C8D";
+    const ADD:  &str = "Vehicle-0-1469-2549-12530-214000-00002C5C8E";
+
+    #[test]
+    fn classifies_by_prefix_and_boss_list() {
+        assert_eq!(kind("Player-1234-ABCDEF"), UnitKind::Player);
+        assert_eq!(kind("Pet-0-1469-2549-12530-165189-0101F2A1B3"), UnitKind::Pet);
+        assert_eq!(kind(ADD), UnitKind::Vehicle);
+        assert_eq!(kind("0000000000000000"), UnitKind::Other);
+
+        assert_eq!(classify(BOSS, &[209090]), UnitKind::Boss);
+        assert_eq!(classify(BOSS, &[]),       UnitKind::Creature);
+        assert_eq!(classify(ADD,  &[209090]), UnitKind::Vehicle);
+        assert!(classify(BOSS, &[209090]).is_npc());
+    }
+
+    #[test]
+    fn extracts_npc_id() {
+        assert_eq!(npc_id(BOSS), Some(209090));
+        assert_eq!(npc_id(ADD),  Some(214000));
+        assert_eq!(npc_id("Pet-0-1469-2549-12530-165189-0101F2A1B3"), None);
+        assert_eq!(npc_id("Player-1234-ABCDEF"), None);
+    }
+}
@@ -0,0 +1,199 @@
+/// Hardware cues — advice turned into messages for external devices, for
+/// players who want a signal that is neither a sound nor the overlay: flash
+/// the keyboard red on Bad, light a Stream Deck key on Warn.
+///
+/// `AppConfig.hardware_cues` maps a severity to a transport and target:
+///   - udp: `message` as one datagram to "host:port" (OpenRGB scripts,
+///     Stream Deck plugins, Home Assistant)
+///   - osc: an OSC message to the `message` address with the advice title
+///     as its string argument (TouchOSC, Chataigne, Companion)
+///   - serial: `message` and a newline written to a serial device (an
+///     Arduino driving an LED strip); the port speed is whatever the OS or
+///     the device has set
+///
+/// ipc::run hands every advice event to the managed `HardwareCues`.  Sends
+/// run on a blocking task so a slow serial device never holds up the
+/// overlay; failures are logged and otherwise ignored.
+use anyhow::{anyhow, Result};
+use std::io::Write;
+use std::net::UdpSocket;
+use std::path::PathBuf;
+
+use crate::{
+    config::{CueTransport, HardwareCue},
+    engine::{AdviceEvent, Severity},
+    rules,
+};
+
+/// Managed state (lib.rs); the cue list follows config updates.
+#[derive(Default)]
+pub struct HardwareCues {
+    cues: Vec<HardwareCue>,
+}
+
+impl HardwareCues {
+    pub fn set(&mut self, cues: &[HardwareCue]) {
+        self.cues = cues.to_vec();
+    }
+
+    /// Send every enabled cue for the advice's severity in the background.
+    pub fn fire(&self, advice: &AdviceEvent) {
+        let severity = format!("{:?}", advice.severity).to_lowercase();
+        let packets: Vec<Packet> = self.cues.iter()
+            .filter(|c| c.enabled && c.severity == severity && !c.target.trim().is_empty())
+            .map(|c| Packet::new(c, advice))
+            .collect();
+        if packets.is_empty() {
+            return;
+        }
+        tauri::async_runtime::spawn_blocking(move || {
+            for p in &packets {
+                if let Err(e) = p.send() {
+                    tracing::warn!("Hardware cue to {}: {}", p.target, e);
+                }
+            }
+        });
+    }
+}
+
+/// One message, encoded for its transport.
+struct Packet {
+    transport: CueTransport,
+    target:    String,
+    bytes:     Vec<u8>,
+}
+
+impl Packet {
+    fn new(cue: &HardwareCue, advice: &AdviceEvent) -> Self {
+        let bytes = match cue.transport {
+            CueTransport::Udp    => fill(&cue.message, advice).into_bytes(),
+            CueTransport::Osc    => osc_message(&cue.message, &advice.title),
+            CueTransport::Serial => format!("{}\n", fill(&cue.message, advice)).into_bytes(),
+        };
+        Self { transport: cue.transport, target: cue.target.trim().to_owned(), bytes }
+    }
+
+    fn send(&self) -> Result<()> {
+        match self.transport {
+            CueTransport::Udp | CueTransport::Osc => {
+                let socket = UdpSocket::bind("0.0.0.0:0")?;
+                socket.send_to(&self.bytes, self.target.as_str())?;
+            }
+            CueTransport::Serial => {
+                let mut port = std::fs::OpenOptions::new().write(true).open(serial_path(&self.target))?;
+                port.write_all(&self.bytes)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `template` with {severity}, {title} and {key} filled in.
+fn fill(template: &str, advice: &AdviceEvent) -> String {
+    template
+        .replace("{severity}", &format!("{:?}", advice.severity).to_lowercase())
+        .replace("{title}", &advice.title)
+        .replace("{key}", &advice.key)
+}
+
+/// OSC 1.0 message with one string argument.
+fn osc_message(address: &str, arg: &str) -> Vec<u8> {
+    let mut out = Vec::new();
+    for s in [address, ",s", arg] {
+        out.extend_from_slice(s.as_bytes());
+        // NUL-terminated, padded to a multiple of 4 bytes.
+        out.resize(out.len() + 4 - s.len() % 4, 0);
+    }
+    out
+}
+
+/// Windows needs the device namespace for COM10 and up; the prefix works for
+/// every COM port.
+fn serial_path(target: &str) -> PathBuf {
+    if cfg!(windows) && target.to_ascii_uppercase().starts_with("COM") {
+        PathBuf::from(format!(r"\\.\{}", target))
+    } else {
+        PathBuf::from(target)
+    }
+}
+
+/// Problems the settings window should not let through (config validation).
+pub fn check(cue: &HardwareCue) -> Result<()> {
+    if !["good", "warn", "bad"].contains(&cue.severity.as_str()) {
+        return Err(anyhow!("severity must be good, warn or bad"));
+    }
+    let target = cue.target.trim();
+    if target.is_empty() {
+        return Err(anyhow!("no target"));
+    }
+    match cue.transport {
+        CueTransport::Udp | CueTransport::Osc => {
+            let port = target.rsplit_once(':').and_then(|(host, port)| {
+                (!host.is_empty()).then(|| port.parse::<u16>().ok()).flatten()
+            });
+            if port.is_none() {
+                return Err(anyhow!("target {} is not host:port", target));
+            }
+        }
+        CueTransport::Serial => {}
+    }
+    if cue.transport == CueTransport::Osc && !cue.message.starts_with('/') {
+        return Err(anyhow!("OSC address {:?} must start with /", cue.message));
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Send one cue right away with a sample advice event (settings "Test").
+#[tauri::command]
+pub async fn test_hardware_cue(cue: HardwareCue) -> Result<(), String> {
+    check(&cue).map_err(|e| e.to_string())?;
+    let advice = rules::advice("test_cue", "Test cue", "Hardware cue test".to_owned(), Severity::Warn, vec![], 0);
+    let packet = Packet::new(&cue, &advice);
+    tauri::async_runtime::spawn_blocking(move || packet.send())
+        .await
+        .map_err(|e| format!("Task error: {}", e))?
+        .map_err(|e| e.to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cue(transport: CueTransport, target: &str, message: &str) -> HardwareCue {
+        HardwareCue {
+            severity: "bad".to_owned(),
+            enabled:  true,
+            transport,
+            target:   target.to_owned(),
+            message:  message.to_owned(),
+        }
+    }
+
+    #[test]
+    fn encodes_and_sends_cues() {
+        let advice = rules::advice("void_nova", "Void Nova", String::new(), Severity::Bad, vec![], 0);
+        assert_eq!(
+            osc_message("/led", "Void Nova"),
+            b"/led\0\0\0\0,s\0\0Void Nova\0\0\0".to_vec(),
+        );
+
+        let listener = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let target = listener.local_addr().unwrap().to_string();
+        Packet::new(&cue(CueTransport::Udp, &target, "flash {severity} {key}"), &advice).send().unwrap();
+        let mut buf = [0u8; 64];
+        let n = listener.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"flash bad void_nova");
+
+        assert!(check(&cue(CueTransport::Udp, &target, "")).is_ok());
+        assert!(check(&cue(CueTransport::Udp, "localhost", "x")).is_err(), "no port");
+        assert!(check(&cue(CueTransport::Osc, &target, "led")).is_err(), "OSC address without /");
+        assert!(check(&cue(CueTransport::Serial, "COM3", "R")).is_ok());
+    }
+}
@@ -16,6 +16,10 @@
 /// WoW only writes SavedVariables on logout or /reload, so identity updates
 /// are infrequent. The engine falls back to inferring the player GUID from
 /// combat log events if the file has not yet been written.
+///
+/// Every read is also handed to addon_bridge.rs, which applies settings
+/// requests queued in-game and restores the app's status block, and whose
+/// version check marks an outdated addon in the connection status.
 use anyhow::Result;
 use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
@@ -24,7 +28,8 @@ use std::sync::mpsc as std_mpsc;
 use tauri::AppHandle;
 use tokio::sync::mpsc::Sender;
 
-use crate::ipc::{self, ConnectionStatus};
+use crate::addon_bridge;
+use crate::ipc;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct PlayerIdentity {
@@ -84,23 +89,26 @@ fn parse_saved_variables(content: &str) -> Option<PlayerIdentity> {
     })
 }
 
+/// Mark the addon connected, with its version and whether it is outdated.
+fn emit_addon_status(app_handle: &AppHandle, id: &PlayerIdentity) {
+    let outdated = addon_bridge::check_addon_version(app_handle, &id.version);
+    ipc::update_connection(app_handle, |s| {
+        s.addon_connected = true;
+        s.addon_version   = id.version.clone();
+        s.addon_outdated  = outdated;
+    });
+}
+
 pub async fn run(sv_path: PathBuf, tx: Sender<PlayerIdentity>, app_handle: AppHandle) -> Result<()> {
     tracing::info!("Identity watcher starting: {:?}", sv_path);
 
-    // Emit initial addon status
-    let mut addon_connected = false;
-
     // Initial parse if file already exists (player was logged in previously)
     if sv_path.exists() {
         let content = std::fs::read_to_string(&sv_path)?;
+        addon_bridge::on_saved_variables(&app_handle, &content);
         if let Some(id) = parse_saved_variables(&content) {
             tracing::info!("Identity loaded: {} ({}/{})", id.name, id.class, id.spec);
-            addon_connected = true;
-            ipc::emit_connection(&app_handle, &ConnectionStatus {
-                log_tailing:     true, // tailer already running at this point
-                addon_connected: true,
-                wow_path:        String::new(), // tailer owns this field
-            });
+            emit_addon_status(&app_handle, &id);
             let _ = tx.send(id).await;
         }
     } else {
@@ -119,16 +127,11 @@ pub async fn run(sv_path: PathBuf, tx: Sender<PlayerIdentity>, app_handle: AppHa
                 if paths.iter().any(|p| p == &sv_path) {
                     match std::fs::read_to_string(&sv_path) {
                         Ok(content) => {
+                            addon_bridge::on_saved_variables(&app_handle, &content);
                             if let Some(id) = parse_saved_variables(&content) {
                                 tracing::info!("Identity updated: {} ({}/{})", id.name, id.class, id.spec);
-                                if !addon_connected {
-                                    addon_connected = true;
-                                    ipc::emit_connection(&app_handle, &ConnectionStatus {
-                                        log_tailing:     true,
-                                        addon_connected: true,
-                                        wow_path:        String::new(),
-                                    });
-                                }
+                                // Re-checked on every save: the addon may have been updated.
+                                emit_addon_status(&app_handle, &id);
                                 if tx.send(id).await.is_err() {
                                     break;
                                 }
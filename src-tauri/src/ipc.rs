@@ -10,12 +10,22 @@
 /// version aligns tauri-build with the runtime.  The primary delivery path for all
 /// events is now managed-state polling via invoke() (get_state_snapshot,
 /// drain_advice_queue, get_connection_status) — all confirmed working.
-use crate::engine::AdviceEvent;
+use crate::{
+    clock::unix_now_ms,
+    engine::{AdviceEvent, Priority},
+    hardware_cues::HardwareCues,
+    practice::PracticeReport,
+    role_defaults::{self, GeneratedProfile},
+    rotation::SpellCastRate,
+    spectate::SpectateWriter,
+    state::GapCause,
+};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::sync::Mutex;
 use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
 use tokio::sync::mpsc::Receiver;
 
 // ---------------------------------------------------------------------------
@@ -35,6 +45,10 @@ impl EventLogQueue {
         Self { inner: VecDeque::new() }
     }
 
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
     /// Push an entry, capping the buffer at 200 entries.
     pub fn push(&mut self, entry: String) {
         self.inner.push_back(entry);
@@ -49,6 +63,22 @@ impl EventLogQueue {
     }
 }
 
+/// The most recent pull debrief, for recalling it after the overlay panel
+/// has been dismissed (`recall_last_debrief`).
+#[derive(Default)]
+pub struct LastDebrief(pub Option<PullDebrief>);
+
+/// Emit the last debrief again, marked sticky.  False when no pull has
+/// ended since the app started.
+pub fn recall_last_debrief(app_handle: &AppHandle) -> bool {
+    let last = app_handle.try_state::<Mutex<LastDebrief>>()
+        .and_then(|state| state.lock().ok().and_then(|last| last.0.clone()));
+    let Some(mut debrief) = last else { return false };
+    debrief.sticky = true;
+    let _ = app_handle.emit(EVENT_DEBRIEF, &debrief);
+    true
+}
+
 // ---------------------------------------------------------------------------
 // Event name constants — must match the TypeScript side in src/types/events.ts
 // ---------------------------------------------------------------------------
@@ -58,6 +88,9 @@ pub const EVENT_CONNECTION: &str = "coach:connection";
 #[allow(dead_code)] // used by TypeScript listener; emitted in future identity phase
 pub const EVENT_IDENTITY:   &str = "coach:identity";
 pub const EVENT_DEBRIEF:    &str = "coach:debrief";
+pub const EVENT_DEATH_RECAP: &str = "coach:death_recap";
+pub const EVENT_KEY_DEBRIEF: &str = "coach:key_debrief";
+pub const EVENT_READINESS:  &str = "coach:readiness";
 
 // ---------------------------------------------------------------------------
 // Payload types (serialised as JSON over the IPC boundary)
@@ -75,14 +108,147 @@ pub struct StateSnapshot {
     pub interrupt_count: u32,
     /// Active encounter name from ENCOUNTER_START, or None between pulls.
     pub encounter_name:  Option<String>,
+    /// Unix-epoch ms of the log event behind this snapshot (clock-aligned).
+    #[serde(default)]
+    pub wallclock_ms:    u64,
+    /// Active-time ("always be casting") percentage for the current pull.
+    #[serde(default)]
+    pub active_time_pct: f32,
+    /// The coached player's damage / effective healing per second over the
+    /// last 10s (state::OutgoingTracker).
+    #[serde(default)]
+    pub dps_10s:         f32,
+    #[serde(default)]
+    pub hps_10s:         f32,
+    /// Pull averages; after the pull they hold its final values.
+    #[serde(default)]
+    pub pull_dps:        f32,
+    #[serde(default)]
+    pub pull_hps:        f32,
+    /// Group players dead and battle resurrections cast this pull.
+    #[serde(default)]
+    pub group_deaths:    u32,
+    #[serde(default)]
+    pub battle_res:      u32,
+    /// True while coaching is paused via `pause_coaching`.
+    #[serde(default)]
+    pub coaching_paused: bool,
+    /// Current encounter phase from the encounter timeline (e.g. "P2").
+    #[serde(default)]
+    pub phase_label:     Option<String>,
+    /// Soonest scheduled boss ability from the encounter timeline.
+    #[serde(default)]
+    pub next_scheduled_event: Option<ScheduledEvent>,
+    /// Pre-pull checklist — present only while out of combat.  Emitted
+    /// apart as EVENT_READINESS when it changes, and left out of EVENT_STATE;
+    /// the polled snapshot keeps it.
+    #[serde(default)]
+    pub readiness:       Option<Readiness>,
+    /// Mythic+ key in progress — context for trash pulls between bosses.
+    #[serde(default)]
+    pub keystone:        Option<KeystoneInfo>,
+    /// Audio countdown to the next high-priority timeline ability, once it
+    /// is close enough to start (countdown.rs).
+    #[serde(default)]
+    pub countdown:       Option<Countdown>,
+    /// Encounter pulls: estimated chance of a wipe in %, from the stored
+    /// pulls of the encounter (wipe_forecast.rs).  None until enough exist.
+    #[serde(default)]
+    pub wipe_pct:        Option<f32>,
+}
+
+/// Dungeon, level and affixes of the active Mythic+ key (state::Keystone).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeystoneInfo {
+    pub dungeon: String,
+    pub level:   u32,
+    pub affixes: Vec<String>,
+}
+
+/// Pre-pull readiness checklist (see readiness.rs).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Readiness {
+    pub items: Vec<ReadinessItem>,
+    /// True when no item is in the `Warn` state.
+    pub ready: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReadinessItem {
+    /// Stable identifier: "flask", "food", "rune", "durability", "spec",
+    /// "cooldowns", "assignments".
+    pub key:    String,
+    pub label:  String,
+    pub status: ReadinessStatus,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReadinessStatus {
+    Ok,
+    Warn,
+    /// The log has not shown enough to tell.
+    Unknown,
+}
+
+/// Upcoming timeline ability shown as a countdown on the pull clock.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledEvent {
+    pub name:    String,
+    /// Seconds until the ability is expected.
+    pub seconds: f32,
+}
+
+/// "3, 2, 1" to a scheduled boss ability.  Steps are relative to the
+/// snapshot, so the overlay can queue each one on its audio clock.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Countdown {
+    /// Stable per cast ("<spell_id>_<cast index>") — a step is played once.
+    pub key:       String,
+    pub ability:   String,
+    pub due_in_ms: u64,
+    /// Highest number first; steps already past are left out.
+    pub steps:     Vec<CountdownStep>,
 }
 
-/// Connection/health status — sent when tailing starts/stops or identity changes.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CountdownStep {
+    pub number:     u32,
+    pub in_ms:      u64,
+    /// Voice pack file for this number; empty = built-in tone.
+    pub sound_path: String,
+}
+
+/// Connection/health status — sent when tailing starts/stops or identity changes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ConnectionStatus {
     pub log_tailing:     bool,
     pub addon_connected: bool,
     pub wow_path:        String,
+    /// `addonVersion` from SavedVariables ("" before the first read).
+    #[serde(default)]
+    pub addon_version:   String,
+    /// The addon is older than the app supports (addon_bridge.rs).
+    #[serde(default)]
+    pub addon_outdated:  bool,
+    /// File name of the log being replayed (`tailer::replay`), "" otherwise.
+    #[serde(default)]
+    pub replaying:       String,
+    /// Config changes made outside the settings window since startup
+    /// (`push_background_config_update`: hand edits, in-game requests,
+    /// synced settings) — the settings window re-reads the config when this
+    /// changes.
+    #[serde(default)]
+    pub config_reloads:  u32,
+    /// Game build of the tailed log (COMBAT_LOG_VERSION header), "" before
+    /// it is read.
+    #[serde(default)]
+    pub log_build:       String,
+    /// Warning when the coaching data predates that build (data_version.rs),
+    /// "" otherwise.
+    #[serde(default)]
+    pub stale_data:      String,
 }
 
 /// End-of-pull summary — emitted on every pull end (kill or wipe).
@@ -98,10 +264,371 @@ pub struct PullDebrief {
     pub avoidable_count:    u32,
     /// Successful interrupts this pull.
     pub interrupt_count:    u32,
+    /// Kicks landed / (landed + interruptible casts that went through while
+    /// the kick was ready), in %.  None without any kick opportunity.
+    #[serde(default)]
+    pub interrupt_efficiency: Option<f32>,
+    /// The same ratio over the whole session.
+    #[serde(default)]
+    pub session_interrupt_efficiency: Option<f32>,
     /// Total advice events that fired this pull.
     pub total_advice_fired: u32,
     /// Number of GCD gap advice events that fired this pull.
     pub gcd_gap_count:      u32,
+    /// The same GCD gaps by likely cause.
+    #[serde(default)]
+    pub gcd_gap_causes:     GapCauses,
+    /// Active-time percentage (cast time covered / pull time minus forced downtime).
+    pub active_time_pct:    f32,
+    /// Dodge rate per avoidable mechanic (empty without an encounter profile).
+    #[serde(default)]
+    pub dodge_stats:        Vec<DodgeStat>,
+    /// Summed first-use drift of major cooldowns (cooldown_drift rule).
+    #[serde(default)]
+    pub cd_drift_ms:        u64,
+    /// One-line shareable summary ("Pull 14 — wipe 4:32 — …").
+    #[serde(default)]
+    pub summary:            String,
+    /// Player damage on the boss vs all targets (encounter pulls only).
+    #[serde(default)]
+    pub boss_damage:        Option<BossDamage>,
+    /// Time from a kill-priority enemy appearing to the player's first hit on it.
+    #[serde(default)]
+    pub target_switch:      Option<TargetSwitch>,
+    /// Player damage inside major cooldown windows and rolling-DPS peaks.
+    #[serde(default)]
+    pub burst:              Option<BurstAlignment>,
+    /// Melee players only: time in melee range of the boss (encounter pulls).
+    #[serde(default)]
+    pub melee_uptime:       Option<MeleeUptime>,
+    /// Group players' deaths in order (coached player included), and the
+    /// battle resurrections cast — how a wipe fell apart.
+    #[serde(default)]
+    pub deaths:             Vec<GroupDeathEntry>,
+    #[serde(default)]
+    pub battle_res:         Vec<BattleResEntry>,
+    /// The coached player's health potions and healthstones this pull.
+    #[serde(default)]
+    pub consumables:        ConsumableUse,
+    /// Encounter pulls only: pre-potion and pre-cast before ENCOUNTER_START.
+    #[serde(default)]
+    pub prepull:            Option<PrepullCheck>,
+    /// Hard casts the coached player cancelled or clipped.
+    #[serde(default)]
+    pub cancelled_casts:    Option<CancelledCasts>,
+    /// The coached player's failed casts by reason, most frequent first.
+    #[serde(default)]
+    pub failed_casts:       Vec<FailedCastCount>,
+    /// Damage the coached player took from their own side.
+    #[serde(default)]
+    pub friendly_fire:      Option<FriendlyFire>,
+    /// Healers only: effective healing split between tanks, party and self.
+    #[serde(default)]
+    pub healing:            Option<HealingDistribution>,
+    /// Damage taken per party member, most avoidable damage first.  Only
+    /// filled in squad mode (`AppConfig.squad_mode`).
+    #[serde(default)]
+    pub damage_leaderboard: Vec<PartyDamage>,
+    /// Running practice-session report when the pull was on a training dummy
+    /// (the pull itself is not stored).
+    #[serde(default)]
+    pub practice:           Option<PracticeReport>,
+    /// Casts per minute of the spec's rotation priority spells (empty
+    /// without a spec profile listing them).
+    #[serde(default)]
+    pub cast_rates:         Vec<SpellCastRate>,
+    /// Recalled with `show_last_debrief`: the overlay keeps the panel up
+    /// until the next pull instead of dismissing it after 10 seconds.
+    #[serde(default)]
+    pub sticky:             bool,
+    /// Raid-lead mode: per-member avoidable hits of the pull, ranked and
+    /// ready to paste (rules/raid_callouts.rs).  None when the mode is off or
+    /// no avoidable spell list applied.
+    #[serde(default)]
+    pub raid_summary:       Option<String>,
+}
+
+/// One party member's damage taken over a pull.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartyDamage {
+    pub name:            String,
+    /// True for the coached player.
+    pub is_player:       bool,
+    pub damage_taken:    u64,
+    /// Damage from the encounter's avoidable mechanics.
+    pub avoidable_taken: u64,
+    pub avoidable_hits:  u32,
+}
+
+/// The coached player's boss-damage share over a pull.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BossDamage {
+    pub boss_name:    String,
+    pub boss_damage:  u64,
+    pub total_damage: u64,
+    /// boss_damage / total_damage × 100.
+    pub share_pct:    f32,
+}
+
+/// Target-switch latency onto kill-priority enemies over a pull.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetSwitch {
+    /// Priority enemies the player switched to.
+    pub switches: u32,
+    pub avg_ms:   u64,
+    pub worst_ms: u64,
+}
+
+/// A group player's death (state::GroupDeath).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupDeathEntry {
+    pub name:       String,
+    /// Milliseconds since pull start.
+    pub elapsed_ms: u64,
+}
+
+/// Defensive consumables used over a pull (state::ConsumableTracker).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConsumableUse {
+    pub potions:      u32,
+    pub healthstones: u32,
+}
+
+/// The coached player's preparation in the 30s before ENCOUNTER_START
+/// (state::PrepullTracker).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrepullCheck {
+    /// A combat potion was drunk.
+    pub potion:   bool,
+    /// The spec's pre-cast spells that were cast.
+    pub precasts: Vec<String>,
+    /// What was left out ("pre-potion", "pre-cast (Frostbolt)"); empty when
+    /// the player was ready.
+    pub missing:  Vec<String>,
+}
+
+/// Hard casts abandoned part way over a pull (state::HardcastTracker).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelledCasts {
+    pub count:   u32,
+    /// Hard casts started, cancelled ones included.
+    pub started: u32,
+    /// Cast time spent on the cancelled casts.
+    pub lost_ms: u64,
+    /// Cancelled spells, most often first ("Chaos Bolt ×2").
+    pub spells:  Vec<String>,
+}
+
+/// Failed casts of one reason over a pull (state::FailedCastTracker).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailedCastCount {
+    /// "movement", "interrupted", "crowd control", or the log's message.
+    pub reason: String,
+    pub count:  u32,
+}
+
+/// Self-inflicted and party-sourced damage on the coached player over a pull
+/// (state::DamageSource).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FriendlyFire {
+    pub self_inflicted: u64,
+    pub from_party:     u64,
+    /// Whether it counted as avoidable (`AppConfig.avoidable_friendly_fire`).
+    pub counted:        bool,
+    /// Spells behind it, most damage first.
+    pub spells:         Vec<String>,
+}
+
+/// A battle resurrection cast (state::BattleRes).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BattleResEntry {
+    pub caster:     String,
+    pub target:     String,
+    pub elapsed_ms: u64,
+}
+
+/// A melee player's time in range of the boss over a pull, estimated from
+/// the cadence of their auto-attacks and boss-targeted casts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeleeUptime {
+    /// In range / time the boss was being hit, in %.
+    pub uptime_pct:      f32,
+    pub out_of_range_ms: u64,
+    /// Longest stretch without touching the boss.
+    pub longest_gap_ms:  u64,
+}
+
+/// How well the player's damage lined up with their major cooldowns.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BurstAlignment {
+    /// Share of the player's damage inside a burst window, in %.
+    pub in_window_pct:  f32,
+    /// Burst windows opened (overlapping cooldowns count once).
+    pub windows:        u32,
+    /// Best rolling 10s / 30s DPS.
+    pub peak_dps_10s:   u64,
+    pub peak_dps_30s:   u64,
+    /// Whether the best 10s fell inside a burst window.
+    pub peak_in_window: bool,
+}
+
+/// The coached healer's effective healing by recipient over a pull.  Tanks
+/// are inferred from melee damage taken.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealingDistribution {
+    /// Healing minus overhealing.
+    pub effective:    u64,
+    /// overhealing / (effective + overhealing) × 100.
+    pub overheal_pct: f32,
+    /// Shares of `effective`, in %.
+    pub tank_pct:     f32,
+    pub party_pct:    f32,
+    pub self_pct:     f32,
+    pub tank_names:   Vec<String>,
+}
+
+/// Avoided vs hit for one avoidable mechanic over a pull.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DodgeStat {
+    pub spell_id:   u32,
+    pub spell_name: String,
+    pub avoided:    u32,
+    pub hit:        u32,
+    /// avoided / (avoided + hit) × 100.
+    pub dodge_pct:  f32,
+}
+
+/// Mythic+ run summary — emitted at CHALLENGE_MODE_END, aggregated over the
+/// key's pulls (key_run.rs).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyDebrief {
+    pub dungeon:          String,
+    pub level:            u32,
+    pub affixes:          Vec<String>,
+    /// The key was completed (in time or not); false when abandoned.
+    pub success:          bool,
+    /// Run time as reported by the game, including death penalties.
+    pub total_time_ms:    u64,
+    /// Tracked pulls fought during the key.
+    pub pulls:            u32,
+    /// Coached player deaths during the key.
+    pub deaths:           u32,
+    pub avoidable_count:  u32,
+    pub interrupt_count:  u32,
+    /// Longest gaps between the player's casts, longest first (one per pull).
+    pub gcd_gaps:         Vec<KeyGcdGap>,
+    /// Time between pulls: routing, waiting on the group, corpse runs.
+    pub out_of_combat_ms: u64,
+    /// One-line summary, as shown in the event feed.
+    pub summary:          String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyGcdGap {
+    pub pull_number: u32,
+    pub gap_ms:      u64,
+}
+
+/// The coached player's last seconds before dying — sent on their UNIT_DIED
+/// and stored with the pull (rules::death_recap).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeathRecap {
+    /// Milliseconds since pull start.
+    pub elapsed_ms:      u64,
+    /// Last ability that hit the player ("Melee" for swings).
+    pub killing_blow:    String,
+    /// Who dealt it (may be empty).
+    pub killer:          String,
+    /// Totals over the recap window.
+    pub damage_taken:    u64,
+    /// Effective healing (overhealing excluded).
+    pub heals_received:  u64,
+    pub defensives_used: u32,
+    /// Recap window length (ms before the death).
+    pub window_ms:       u64,
+    /// Potion / healthstone the player most likely had ready (display names).
+    #[serde(default)]
+    pub unused_consumables: Vec<String>,
+    /// Oldest first.
+    pub entries:         Vec<RecapEntry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecapKind {
+    Damage,
+    Heal,
+    Defensive,
+    Consumable,
+}
+
+/// One line of a death recap.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecapEntry {
+    /// Milliseconds before the death.
+    pub before_ms: u64,
+    pub kind:      RecapKind,
+    pub source:    String,
+    pub ability:   String,
+    /// Damage or effective healing; 0 for defensives and consumables.
+    pub amount:    u64,
+    /// Player health after the event (advanced logging only).
+    pub hp_pct:    Option<f32>,
+}
+
+/// GCD gap advice over a pull, counted by likely cause.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GapCauses {
+    pub movement:      u32,
+    pub silence:       u32,
+    pub crowd_control: u32,
+    pub idle:          u32,
+}
+
+impl GapCauses {
+    pub fn add(&mut self, cause: GapCause) {
+        match cause {
+            GapCause::Movement     => self.movement += 1,
+            GapCause::Silenced     => self.silence += 1,
+            GapCause::CrowdControl => self.crowd_control += 1,
+            GapCause::Idle         => self.idle += 1,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Advice queue
+// ---------------------------------------------------------------------------
+
+/// Capacity of the managed advice ring buffer drained by drain_advice_queue.
+const ADVICE_QUEUE_CAP: usize = 50;
+
+/// Queue advice for the next drain.  Urgent advice jumps ahead of everything
+/// that is not urgent (FIFO among urgent items); when the buffer is full the
+/// oldest non-urgent entry is dropped first.
+pub fn enqueue_advice(q: &mut VecDeque<AdviceEvent>, advice: AdviceEvent) {
+    let urgent = |a: &AdviceEvent| a.priority == Priority::Urgent;
+    if urgent(&advice) {
+        let at = q.iter().position(|a| !urgent(a)).unwrap_or(q.len());
+        q.insert(at, advice);
+    } else {
+        q.push_back(advice);
+    }
+    if q.len() > ADVICE_QUEUE_CAP {
+        let drop_at = q.iter().position(|a| !urgent(a)).unwrap_or(0);
+        q.remove(drop_at);
+    }
+}
+
+/// Queue advice that did not come from the engine (what's new, addon
+/// update) where the overlay drains regular advice, stamped with wall time.
+pub fn push_notification(handle: &AppHandle, mut advice: AdviceEvent) {
+    advice.wallclock_ms = unix_now_ms();
+    if let Some(state) = handle.try_state::<Mutex<VecDeque<AdviceEvent>>>() {
+        if let Ok(mut q) = state.lock() {
+            enqueue_advice(&mut q, advice);
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -113,18 +640,23 @@ pub struct PullDebrief {
 ///
 /// Managed-state side-effects (primary delivery path):
 ///   • Mutex<StateSnapshot>           — overwritten on every snap; polled via get_state_snapshot
-///   • Mutex<VecDeque<AdviceEvent>>   — ring-buffered (cap 50); drained via drain_advice_queue
+///   • Mutex<VecDeque<AdviceEvent>>   — ring-buffered (cap 50, urgent first); drained via drain_advice_queue
 ///
 /// emit() calls are best-effort (succeed only if capabilities work); polling is always reliable.
 pub async fn run(
     mut advice_rx:  Receiver<AdviceEvent>,
     mut snap_rx:    Receiver<StateSnapshot>,
     mut debrief_rx: Receiver<PullDebrief>,
+    mut recap_rx:   Receiver<DeathRecap>,
+    mut key_rx:     Receiver<KeyDebrief>,
+    mut profile_rx: Receiver<GeneratedProfile>,
     app_handle:     AppHandle,
 ) -> Result<()> {
     // Track previous combat state to detect transitions for the event log.
     let mut prev_in_combat     = false;
     let mut prev_encounter:    Option<String> = None;
+    // Last checklist emitted; None in combat.
+    let mut prev_readiness:    Option<Readiness> = None;
 
     loop {
         tokio::select! {
@@ -134,41 +666,50 @@ pub async fn run(
                 // Primary delivery: push to managed ring buffer for drain polling
                 if let Some(state) = app_handle.try_state::<Mutex<VecDeque<AdviceEvent>>>() {
                     if let Ok(mut q) = state.lock() {
-                        q.push_back(advice.clone());
-                        if q.len() > 50 { q.pop_front(); } // cap ring buffer at 50
+                        enqueue_advice(&mut q, advice.clone());
+                    }
+                }
+                spectate(&app_handle, |w, now| w.advice(&advice, now));
+                if let Some(cues) = app_handle.try_state::<Mutex<HardwareCues>>() {
+                    if let Ok(cues) = cues.lock() {
+                        cues.fire(&advice);
                     }
                 }
                 // Event log: record each advice event so the Event Feed shows it
                 if let Some(eq) = app_handle.try_state::<Mutex<EventLogQueue>>() {
                     if let Ok(mut q) = eq.lock() {
-                        let sev_icon = match advice.severity {
-                            crate::engine::Severity::Good => "✅",
-                            crate::engine::Severity::Warn => "⚠️",
-                            crate::engine::Severity::Bad  => "❌",
+                        let sev_icon = match (advice.priority, &advice.severity) {
+                            (Priority::Urgent, _)              => "🚨",
+                            (_, crate::engine::Severity::Good) => "✅",
+                            (_, crate::engine::Severity::Warn) => "⚠️",
+                            (_, crate::engine::Severity::Bad)  => "❌",
                         };
-                        let ts = chrono_hms(advice.timestamp_ms);
+                        let ts = chrono_hms(advice.wallclock_ms);
                         q.push(format!("[{}] {} {} — {}", ts, sev_icon, advice.title, advice.message));
                     }
                 }
             }
-            Some(snap) = snap_rx.recv() => {
+            Some(mut snap) = snap_rx.recv() => {
+                // The checklist only changes between pulls, so it goes out
+                // on its own event when it does rather than with every snapshot.
+                let readiness = snap.readiness.take();
+                if readiness != prev_readiness {
+                    let _ = app_handle.emit(EVENT_READINESS, &readiness);
+                    prev_readiness = readiness.clone();
+                }
                 // Best-effort emit
                 let _ = app_handle.emit(EVENT_STATE, &snap);
                 // Primary delivery: overwrite managed snapshot for poll
                 if let Some(state) = app_handle.try_state::<Mutex<StateSnapshot>>() {
                     if let Ok(mut s) = state.lock() {
-                        *s = snap.clone();
+                        *s = StateSnapshot { readiness, ..snap.clone() };
                     }
                 }
+                spectate(&app_handle, |w, now| w.snapshot(&snap, now));
                 // Event log: combat state transitions + encounter changes
                 if let Some(eq) = app_handle.try_state::<Mutex<EventLogQueue>>() {
                     if let Ok(mut q) = eq.lock() {
-                        let ts = chrono_hms(
-                            std::time::SystemTime::now()
-                                .duration_since(std::time::UNIX_EPOCH)
-                                .unwrap_or_default()
-                                .as_millis() as u64
-                        );
+                        let ts = chrono_hms(unix_now_ms());
                         // Combat start
                         if !prev_in_combat && snap.in_combat {
                             let name = snap.encounter_name.as_deref().unwrap_or("Open World");
@@ -190,15 +731,19 @@ pub async fn run(
                 }
             }
             Some(debrief) = debrief_rx.recv() => {
-                // Best-effort emit only
+                // Best-effort emit; kept for show_last_debrief.
                 let _ = app_handle.emit(EVENT_DEBRIEF, &debrief);
+                if let Some(state) = app_handle.try_state::<Mutex<LastDebrief>>() {
+                    if let Ok(mut last) = state.lock() {
+                        last.0 = Some(debrief.clone());
+                    }
+                }
+                copy_summary_if_enabled(&app_handle, debrief.raid_summary.as_deref().unwrap_or(&debrief.summary));
+                spectate(&app_handle, |w, now| w.debrief(&debrief, now));
                 // Event log: pull summary
                 if let Some(eq) = app_handle.try_state::<Mutex<EventLogQueue>>() {
                     if let Ok(mut q) = eq.lock() {
-                        let ts = std::time::SystemTime::now()
-                            .duration_since(std::time::UNIX_EPOCH)
-                            .unwrap_or_default()
-                            .as_millis() as u64;
+                        let ts = unix_now_ms();
                         let elapsed_s = debrief.pull_elapsed_ms / 1000;
                         let icon = if debrief.outcome == "kill" { "🏆" } else { "💀" };
                         q.push(format!(
@@ -210,12 +755,67 @@ pub async fn run(
                     }
                 }
             }
+            Some(recap) = recap_rx.recv() => {
+                // Best-effort emit only
+                let _ = app_handle.emit(EVENT_DEATH_RECAP, &recap);
+                if let Some(eq) = app_handle.try_state::<Mutex<EventLogQueue>>() {
+                    if let Ok(mut q) = eq.lock() {
+                        let ts = unix_now_ms();
+                        q.push(format!(
+                            "[{}] ☠️ Died to {} — {} damage taken, {} healed in the last {}s",
+                            chrono_hms(ts), recap.killing_blow,
+                            recap.damage_taken, recap.heals_received, recap.window_ms / 1000
+                        ));
+                    }
+                }
+            }
+            Some(key) = key_rx.recv() => {
+                // Best-effort emit only
+                let _ = app_handle.emit(EVENT_KEY_DEBRIEF, &key);
+                let icon = if key.success { "🗝️" } else { "💔" };
+                log_event(&app_handle, &format!("{} {}", icon, key.summary));
+            }
+            Some(generated) = profile_rx.recv() => {
+                let line = format!(
+                    "🧭 {} defaults applied for {} — edit them under character_profiles in config.toml",
+                    generated.profile.role.to_lowercase(), generated.profile.name
+                );
+                match role_defaults::store(&app_handle, generated) {
+                    Ok(true)  => log_event(&app_handle, &line),
+                    Ok(false) => {}
+                    Err(e)    => tracing::warn!("Role defaults not saved: {}", e),
+                }
+            }
             else => break,
         }
     }
     Ok(())
 }
 
+/// Put the pull summary (the raid-lead summary in raid-lead mode) on the
+/// clipboard when `pull_summary_clipboard` is on.
+/// Config is read from disk — once per pull, so the cost is irrelevant.
+fn copy_summary_if_enabled(app_handle: &AppHandle, summary: &str) {
+    let enabled = app_handle.path().app_config_dir().ok()
+        .and_then(|dir| crate::config::load_or_default(&dir).ok())
+        .is_some_and(|cfg| cfg.pull_summary_clipboard);
+    if !enabled || summary.is_empty() {
+        return;
+    }
+    if let Err(e) = app_handle.clipboard().write_text(summary.to_owned()) {
+        tracing::warn!("Pull summary: clipboard write failed: {}", e);
+    }
+}
+
+/// Hand engine output to the spectate file writer (spectate.rs).
+fn spectate(app_handle: &AppHandle, write: impl FnOnce(&mut SpectateWriter, std::time::Instant)) {
+    if let Some(state) = app_handle.try_state::<Mutex<SpectateWriter>>() {
+        if let Ok(mut writer) = state.lock() {
+            write(&mut writer, std::time::Instant::now());
+        }
+    }
+}
+
 /// Format a Unix-epoch millisecond timestamp as "HH:MM:SS" for the event log.
 fn chrono_hms(ts_ms: u64) -> String {
     let total_secs = (ts_ms / 1000) % 86_400; // seconds into the day (UTC)
@@ -225,40 +825,109 @@ fn chrono_hms(ts_ms: u64) -> String {
     format!("{:02}:{:02}:{:02}", h, m, s)
 }
 
-/// Convenience function — emit a connection status update from anywhere
-/// that has an AppHandle (called by tailer and identity watcher).
+/// Push a timestamped line to the Event Feed.
+pub fn log_event(handle: &AppHandle, entry: &str) {
+    if let Some(eq) = handle.try_state::<Mutex<EventLogQueue>>() {
+        if let Ok(mut q) = eq.lock() {
+            let ts = unix_now_ms();
+            q.push(format!("[{}] {}", chrono_hms(ts), entry));
+        }
+    }
+}
+
+/// Latest connection status from managed state.
+pub fn connection_status(handle: &AppHandle) -> ConnectionStatus {
+    handle
+        .try_state::<Mutex<ConnectionStatus>>()
+        .and_then(|state| state.lock().ok().map(|s| s.clone()))
+        .unwrap_or_default()
+}
+
+/// Convenience function — update and emit the connection status from
+/// anywhere that has an AppHandle.  The tailer owns `log_tailing`/`wow_path`
+/// and the identity watcher the addon fields; each changes only its own, in
+/// place under the lock, so neither clobbers the other.
 ///
 /// Also updates the `Mutex<ConnectionStatus>` managed state so that
 /// `get_connection_status` (called by the frontend on mount) always returns
 /// the latest value, even if the webview missed the live event.
-pub fn emit_connection(handle: &AppHandle, status: &ConnectionStatus) {
-    tracing::debug!(
-        "emit_connection: log_tailing={} addon={} path={:?}",
-        status.log_tailing, status.addon_connected, status.wow_path
-    );
+pub fn update_connection(handle: &AppHandle, update: impl FnOnce(&mut ConnectionStatus)) {
     // Update managed state (best-effort; state registered in lib.rs setup()).
-    if let Some(state) = handle.try_state::<Mutex<ConnectionStatus>>() {
-        if let Ok(mut guard) = state.lock() {
+    let state = handle.try_state::<Mutex<ConnectionStatus>>();
+    let (prev, status) = match state.as_ref().map(|s| s.lock()) {
+        Some(Ok(mut guard)) => {
             let prev = guard.clone();
-            *guard = status.clone();
-            // Event log: only log when connection status CHANGES (not every heartbeat)
-            drop(guard);
-            if prev.log_tailing != status.log_tailing || prev.addon_connected != status.addon_connected {
-                if let Some(eq) = handle.try_state::<Mutex<EventLogQueue>>() {
-                    if let Ok(mut q) = eq.lock() {
-                        let ts = std::time::SystemTime::now()
-                            .duration_since(std::time::UNIX_EPOCH)
-                            .unwrap_or_default()
-                            .as_millis() as u64;
-                        let log_str = if status.log_tailing { "🟢 Log connected" } else { "🔴 Log disconnected" };
-                        let addon_str = if status.addon_connected { " · Addon connected" } else { "" };
-                        q.push(format!("[{}] {}{}", chrono_hms(ts), log_str, addon_str));
-                    }
-                }
+            update(&mut guard);
+            (Some(prev), guard.clone())
+        }
+        _ => {
+            let mut status = ConnectionStatus::default();
+            update(&mut status);
+            (None, status)
+        }
+    };
+    tracing::debug!(
+        "update_connection: log_tailing={} addon={} (v{:?}, outdated={}) path={:?}",
+        status.log_tailing, status.addon_connected, status.addon_version,
+        status.addon_outdated, status.wow_path
+    );
+
+    // Event log: only log when connection status CHANGES (not every heartbeat)
+    let changed = prev.is_some_and(|prev| {
+        prev.log_tailing != status.log_tailing
+            || prev.addon_connected != status.addon_connected
+            || prev.addon_outdated != status.addon_outdated
+    });
+    if changed {
+        if let Some(eq) = handle.try_state::<Mutex<EventLogQueue>>() {
+            if let Ok(mut q) = eq.lock() {
+                let ts = unix_now_ms();
+                let log_str = if status.log_tailing { "🟢 Log connected" } else { "🔴 Log disconnected" };
+                let addon_str = match (status.addon_connected, status.addon_outdated) {
+                    (true, true)  => " · Addon connected (outdated)",
+                    (true, false) => " · Addon connected",
+                    _             => "",
+                };
+                q.push(format!("[{}] {}{}", chrono_hms(ts), log_str, addon_str));
             }
         }
     }
-    if let Err(e) = handle.emit(EVENT_CONNECTION, status) {
+    if let Err(e) = handle.emit(EVENT_CONNECTION, &status) {
         tracing::warn!("Failed to emit connection status: {}", e);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::Severity;
+
+    fn advice(key: &str, priority: Priority) -> AdviceEvent {
+        AdviceEvent { priority, ..crate::rules::advice(key, key, String::new(), Severity::Warn, vec![], 0) }
+    }
+
+    fn keys(q: &VecDeque<AdviceEvent>) -> Vec<&str> {
+        q.iter().map(|a| a.key.as_str()).collect()
+    }
+
+    #[test]
+    fn urgent_advice_jumps_queued_normal_advice() {
+        let mut q = VecDeque::new();
+        enqueue_advice(&mut q, advice("a", Priority::Normal));
+        enqueue_advice(&mut q, advice("b", Priority::Normal));
+        enqueue_advice(&mut q, advice("u1", Priority::Urgent));
+        enqueue_advice(&mut q, advice("u2", Priority::Urgent));
+        assert_eq!(keys(&q), ["u1", "u2", "a", "b"]);
+    }
+
+    #[test]
+    fn full_queue_drops_the_oldest_normal_advice() {
+        let mut q = VecDeque::new();
+        enqueue_advice(&mut q, advice("u", Priority::Urgent));
+        for i in 0..ADVICE_QUEUE_CAP {
+            enqueue_advice(&mut q, advice(&i.to_string(), Priority::Normal));
+        }
+        assert_eq!(q.len(), ADVICE_QUEUE_CAP);
+        assert_eq!(keys(&q)[..3], ["u", "1", "2"]);
+    }
+}
@@ -0,0 +1,343 @@
+/// Session notes and the raid-night journal — context the player writes down
+/// ("new strat on boss 3", "played with lag") so an odd pull or a bad night
+/// in the history explains itself.
+///
+/// Every session has one editable note (`sessions.note`) and any number of
+/// timestamped journal entries (`journal_entries`).  Writes go through the DB
+/// writer; reads open their own read-only connection like the other history
+/// commands.  Notes and entries show up in:
+///   - the history dashboard (`get_session_journals`, and the session note on
+///     every `get_pull_history` row)
+///   - the pull image export (a footer line, `session_context`)
+///   - `export_journal`, a Markdown file of the recent sessions
+///
+/// Each journal session also carries its active time — from the session
+/// start to its end, less the intervals coaching was paused
+/// (`session_pauses`, see engine.rs) — and its pull count, so pulls per hour
+/// are not diluted by breaks.  All three are unix ms: the engine moves
+/// `sessions.ended_at` to the wall-clock end of every pull and to the time
+/// it stops.  Pull rows keep log time and are only counted.
+use anyhow::Result;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+
+use crate::{clock::unix_now_ms, db::DbWriter};
+
+/// Sessions listed by the dashboard and the Markdown export.
+const SESSION_LIMIT: u32 = 20;
+/// Longest note or entry kept, in characters.
+const MAX_CHARS: usize = 500;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JournalEntry {
+    pub id:         i64,
+    pub session_id: i64,
+    /// Unix epoch milliseconds.
+    pub created_at: u64,
+    pub text:       String,
+    /// Local "HH:MM" of `created_at` (Markdown export only).
+    #[serde(skip)]
+    pub local_time: String,
+}
+
+/// One session with its note and journal entries (oldest entry first).
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionJournal {
+    pub session_id:  i64,
+    pub started_at:  u64,
+    pub ended_at:    Option<u64>,
+    pub player_name: String,
+    pub note:        String,
+    pub entries:     Vec<JournalEntry>,
+    /// `started_at` to `ended_at`, paused time excluded; None before the
+    /// first pull ends.
+    pub active_ms:   Option<u64>,
+    /// Paused time within that span.
+    pub paused_ms:   u64,
+    pub pulls:       u32,
+    /// Local "YYYY-MM-DD HH:MM" of `started_at` (Markdown export only).
+    #[serde(skip)]
+    pub local_start: String,
+}
+
+/// Trimmed text capped at MAX_CHARS.
+fn clean(text: &str) -> String {
+    text.trim().chars().take(MAX_CHARS).collect()
+}
+
+// ---------------------------------------------------------------------------
+// Queries
+// ---------------------------------------------------------------------------
+
+fn entries_of(conn: &Connection, session_id: i64) -> Result<Vec<JournalEntry>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, session_id, created_at, text, \
+                strftime('%H:%M', created_at / 1000, 'unixepoch', 'localtime') \
+         FROM journal_entries WHERE session_id = ?1 ORDER BY created_at, id",
+    )?;
+    let rows = stmt.query_map([session_id], |row| {
+        Ok(JournalEntry {
+            id:         row.get(0)?,
+            session_id: row.get(1)?,
+            created_at: row.get::<_, i64>(2)? as u64,
+            text:       row.get(3)?,
+            local_time: row.get::<_, Option<String>>(4)?.unwrap_or_default(),
+        })
+    })?;
+    Ok(rows.collect::<Result<_, _>>()?)
+}
+
+/// Recent sessions, newest first: those with pulls, a note or entries, plus
+/// the latest session so the current raid night can be written about before
+/// the first pull.
+pub fn read_journals(conn: &Connection) -> Result<Vec<SessionJournal>> {
+    let mut stmt = conn.prepare(
+        "SELECT s.id, s.started_at, s.ended_at, s.player_name, s.note, \
+                strftime('%Y-%m-%d %H:%M', s.started_at / 1000, 'unixepoch', 'localtime') \
+         FROM sessions s \
+         WHERE s.note != '' \
+            OR EXISTS (SELECT 1 FROM journal_entries j WHERE j.session_id = s.id) \
+            OR EXISTS (SELECT 1 FROM pulls p WHERE p.session_id = s.id) \
+            OR s.id = (SELECT MAX(id) FROM sessions) \
+         ORDER BY s.id DESC LIMIT ?1",
+    )?;
+    let sessions = stmt
+        .query_map([SESSION_LIMIT], |row| {
+            Ok(SessionJournal {
+                session_id:  row.get(0)?,
+                started_at:  row.get::<_, i64>(1)? as u64,
+                ended_at:    row.get::<_, Option<i64>>(2)?.map(|v| v as u64),
+                player_name: row.get(3)?,
+                note:        row.get(4)?,
+                entries:     Vec::new(),
+                active_ms:   None,
+                paused_ms:   0,
+                pulls:       0,
+                local_start: row.get::<_, Option<String>>(5)?.unwrap_or_default(),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    sessions
+        .into_iter()
+        .map(|mut s| {
+            s.entries = entries_of(conn, s.session_id)?;
+            add_activity(conn, &mut s)?;
+            Ok(s)
+        })
+        .collect()
+}
+
+/// Fill in the session's pull count, active and paused time.
+fn add_activity(conn: &Connection, s: &mut SessionJournal) -> Result<()> {
+    s.pulls = conn.query_row(
+        "SELECT COUNT(*) FROM pulls WHERE session_id = ?1 AND ended_at IS NOT NULL",
+        [s.session_id],
+        |row| row.get(0),
+    )?;
+    let Some(end) = s.ended_at else { return Ok(()) };
+
+    let mut stmt = conn.prepare("SELECT paused_at, resumed_at FROM session_pauses WHERE session_id = ?1")?;
+    let pauses = stmt
+        .query_map([s.session_id], |row| {
+            Ok((row.get::<_, i64>(0)? as u64, row.get::<_, Option<i64>>(1)?.map(|v| v as u64)))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    let (active, paused) = active_time(s.started_at, end, &pauses);
+    s.active_ms = Some(active);
+    s.paused_ms = paused;
+    Ok(())
+}
+
+/// Active and paused ms of `[start, end]` given the paused intervals
+/// (`None` = still open, counted to `end`).
+fn active_time(start: u64, end: u64, pauses: &[(u64, Option<u64>)]) -> (u64, u64) {
+    let paused: u64 = pauses
+        .iter()
+        .map(|&(from, to)| {
+            let to = to.unwrap_or(end).min(end);
+            to.saturating_sub(from.max(start))
+        })
+        .sum();
+    let span = end.saturating_sub(start);
+    (span.saturating_sub(paused), paused.min(span))
+}
+
+/// "1h 05m" / "25m".
+fn hours_minutes(ms: u64) -> String {
+    let mins = ms / 60_000;
+    if mins < 60 { format!("{}m", mins) } else { format!("{}h {:02}m", mins / 60, mins % 60) }
+}
+
+/// The session's note and entries as one line, for export captions; empty
+/// when nothing was written.
+pub fn session_context(conn: &Connection, session_id: i64) -> Result<String> {
+    let note: String = conn
+        .query_row("SELECT note FROM sessions WHERE id = ?1", [session_id], |row| row.get(0))
+        .unwrap_or_default();
+    let entries = entries_of(conn, session_id)?;
+    Ok(std::iter::once(note)
+        .chain(entries.into_iter().map(|e| e.text))
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>()
+        .join(" · "))
+}
+
+// ---------------------------------------------------------------------------
+// Markdown export
+// ---------------------------------------------------------------------------
+
+/// Sessions with a note or entries as Markdown, in the given order.
+fn to_markdown(journals: &[SessionJournal]) -> String {
+    let mut out = String::from("# Raid-night journal\n");
+    for s in journals.iter().filter(|s| !s.note.is_empty() || !s.entries.is_empty()) {
+        out.push_str(&format!("\n## {}", s.local_start));
+        if !s.player_name.is_empty() {
+            out.push_str(&format!(" — {}", s.player_name));
+        }
+        out.push('\n');
+        if let Some(active) = s.active_ms.filter(|&ms| ms > 0) {
+            out.push_str(&format!("\n{} active", hours_minutes(active)));
+            if s.paused_ms >= 60_000 {
+                out.push_str(&format!(" ({} paused)", hours_minutes(s.paused_ms)));
+            }
+            out.push_str(&format!(" · {} pulls, {:.1}/h\n", s.pulls, s.pulls as f64 * 3_600_000.0 / active as f64));
+        }
+        if !s.note.is_empty() {
+            out.push_str(&format!("\n> {}\n", s.note.replace('\n', "\n> ")));
+        }
+        if !s.entries.is_empty() {
+            out.push('\n');
+        }
+        for e in &s.entries {
+            out.push_str(&format!("- {} {}\n", e.local_time, e.text.replace('\n', " ")));
+        }
+    }
+    out
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Replace a session's note; an empty text clears it.
+#[tauri::command]
+pub fn set_session_note(app: AppHandle, session_id: i64, text: String) -> Result<(), String> {
+    app.state::<DbWriter>().set_session_note(session_id, clean(&text));
+    Ok(())
+}
+
+/// Add a journal entry to a session, timestamped now; returns the entry.
+#[tauri::command]
+pub async fn add_journal_entry(app: AppHandle, session_id: i64, text: String) -> Result<JournalEntry, String> {
+    let text = clean(&text);
+    if text.is_empty() {
+        return Err("Write something for the entry first.".to_owned());
+    }
+    let db = app.state::<DbWriter>().inner().clone();
+    let created_at = unix_now_ms();
+    let id = db.add_journal_entry(session_id, created_at, text.clone())
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(JournalEntry { id, session_id, created_at, text, local_time: String::new() })
+}
+
+#[tauri::command]
+pub fn delete_journal_entry(app: AppHandle, entry_id: i64) -> Result<(), String> {
+    app.state::<DbWriter>().delete_journal_entry(entry_id);
+    Ok(())
+}
+
+/// Recent sessions with their notes and journal entries, newest first.
+#[tauri::command]
+pub async fn get_session_journals(app: AppHandle) -> Result<Vec<SessionJournal>, String> {
+    let db_path = crate::sessions_db_path(&app)?;
+    if !db_path.exists() {
+        return Ok(vec![]);
+    }
+    tauri::async_runtime::spawn_blocking(move || -> Result<Vec<SessionJournal>> {
+        let conn = Connection::open_with_flags(&db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        read_journals(&conn)
+    })
+    .await
+    .map_err(|e| format!("Task error: {}", e))?
+    .map_err(|e| e.to_string())
+}
+
+/// Write the recent sessions' notes and entries to `path` as Markdown.
+#[tauri::command]
+pub async fn export_journal(app: AppHandle, path: String) -> Result<(), String> {
+    let db_path = crate::sessions_db_path(&app)?;
+    tauri::async_runtime::spawn_blocking(move || -> Result<()> {
+        let conn = Connection::open_with_flags(&db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        std::fs::write(Path::new(&path), to_markdown(&read_journals(&conn)?))?;
+        Ok(())
+    })
+    .await
+    .map_err(|e| format!("Task error: {}", e))?
+    .map_err(|e| e.to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn journal_reads_and_exports_sessions_with_context() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::apply_schema(&conn).unwrap();
+        conn.execute_batch(
+            // Session and pause times are unix ms; pull times are log ms
+            // since midnight (19:05-19:14 and 20:50-21:00).
+            "INSERT INTO sessions (id, started_at, ended_at, player_name, note) VALUES
+                 (1, 1760036400000, 1760043600000, 'Stonebraid', 'played with lag'),
+                 (2, 1760122800000, NULL, 'Stonebraid', ''),
+                 (3, 1760209200000, NULL, '', '');
+             INSERT INTO journal_entries (session_id, created_at, text) VALUES
+                 (1, 1760040000000, 'new strat on boss 3'),
+                 (1, 1760037000000, 'pulled late');
+             INSERT INTO pulls (session_id, pull_number, started_at, ended_at) VALUES
+                 (1, 1, 68700000, 69240000),
+                 (1, 2, 75000000, 75600000),
+                 (3, 1, 68700000, NULL);
+             INSERT INTO session_pauses (session_id, paused_at, resumed_at) VALUES
+                 (1, 1760040000000, 1760041800000);",
+        ).unwrap();
+
+        // Session 2 has nothing; session 3 is listed as the latest one.
+        let journals = read_journals(&conn).unwrap();
+        assert_eq!(journals.iter().map(|s| s.session_id).collect::<Vec<_>>(), vec![3, 1]);
+        let texts: Vec<_> = journals[1].entries.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(texts, vec!["pulled late", "new strat on boss 3"]);
+        // A two-hour session, half an hour of it paused.
+        let s = &journals[1];
+        assert_eq!((s.active_ms, s.paused_ms, s.pulls), (Some(5_400_000), 1_800_000, 2));
+        // Still in its first pull.
+        assert_eq!((journals[0].active_ms, journals[0].pulls), (None, 0));
+
+        assert_eq!(
+            session_context(&conn, 1).unwrap(),
+            "played with lag · pulled late · new strat on boss 3",
+        );
+        assert_eq!(session_context(&conn, 2).unwrap(), "");
+
+        let md = to_markdown(&journals);
+        assert!(md.contains("— Stonebraid\n\n1h 30m active (30m paused) · 2 pulls, 1.3/h\n\n> played with lag\n"), "{}", md);
+        assert!(md.contains(" new strat on boss 3\n"));
+        assert_eq!(md.matches("\n## ").count(), 1, "empty sessions are left out: {}", md);
+        assert_eq!(clean(&format!("  {}  ", "x".repeat(600))).len(), MAX_CHARS);
+    }
+
+    #[test]
+    fn pauses_are_clipped_to_the_session() {
+        // Before the start, open at the end, and one past the end.
+        let pauses = [(0, Some(2_000)), (9_000, None), (12_000, Some(13_000))];
+        assert_eq!(active_time(1_000, 10_000, &pauses), (7_000, 2_000));
+        assert_eq!(active_time(1_000, 10_000, &[(0, None)]), (0, 9_000));
+    }
+}
@@ -0,0 +1,137 @@
+/// Mythic+ run summaries — one debrief per key on top of the per-pull ones.
+///
+/// A `KeyRun` opens at CHALLENGE_MODE_START and takes each tracked pull's
+/// numbers as the pull ends: avoidable hits, interrupts, its longest gap
+/// between casts and its duration.  At CHALLENGE_MODE_END it becomes a
+/// `KeyDebrief` (ipc.rs), sent to the UI and stored in the `runs` table.
+///
+/// Deaths are counted from `CombatState.player_deaths` over the whole key,
+/// so a death between pulls counts too.  Out-of-combat time is the key's
+/// log time minus the time spent in pulls.
+use crate::{
+    ipc::{KeyDebrief, KeyGcdGap, PullDebrief},
+    state::Keystone,
+    units::NumberFormat,
+};
+use std::cmp::Reverse;
+
+/// Longest GCD gaps kept for the debrief.
+const TOP_GAPS: usize = 3;
+
+#[derive(Debug)]
+pub struct KeyRun {
+    keystone:        Keystone,
+    /// Log ms of CHALLENGE_MODE_START.
+    started_ms:      u64,
+    /// `player_deaths` when the key started.
+    deaths_before:   u32,
+    pulls:           u32,
+    avoidable_count: u32,
+    interrupt_count: u32,
+    in_combat_ms:    u64,
+    gcd_gaps:        Vec<KeyGcdGap>,
+}
+
+impl KeyRun {
+    pub fn start(keystone: Keystone, started_ms: u64, player_deaths: u32) -> Self {
+        Self {
+            keystone,
+            started_ms,
+            deaths_before:   player_deaths,
+            pulls:           0,
+            avoidable_count: 0,
+            interrupt_count: 0,
+            in_combat_ms:    0,
+            gcd_gaps:        Vec::new(),
+        }
+    }
+
+    /// Add a finished pull; `longest_gap_ms` is its longest gap between casts.
+    pub fn add_pull(&mut self, debrief: &PullDebrief, longest_gap_ms: u64) {
+        self.pulls           += 1;
+        self.avoidable_count += debrief.avoidable_count;
+        self.interrupt_count += debrief.interrupt_count;
+        self.in_combat_ms    += debrief.pull_elapsed_ms;
+        if longest_gap_ms > 0 {
+            self.gcd_gaps.push(KeyGcdGap { pull_number: debrief.pull_number, gap_ms: longest_gap_ms });
+            self.gcd_gaps.sort_by_key(|g| Reverse(g.gap_ms));
+            self.gcd_gaps.truncate(TOP_GAPS);
+        }
+    }
+
+    /// Close the run at CHALLENGE_MODE_END.
+    pub fn finish(
+        self,
+        ended_ms:      u64,
+        success:       bool,
+        total_time_ms: u64,
+        player_deaths: u32,
+        fmt:           &NumberFormat,
+    ) -> KeyDebrief {
+        let elapsed_ms = ended_ms.saturating_sub(self.started_ms);
+        let mut debrief = KeyDebrief {
+            affixes:          self.keystone.affix_names(),
+            dungeon:          self.keystone.dungeon,
+            level:            self.keystone.level,
+            success,
+            // Abandoned keys report no time.
+            total_time_ms:    if total_time_ms > 0 { total_time_ms } else { elapsed_ms },
+            pulls:            self.pulls,
+            deaths:           player_deaths.saturating_sub(self.deaths_before),
+            avoidable_count:  self.avoidable_count,
+            interrupt_count:  self.interrupt_count,
+            gcd_gaps:         self.gcd_gaps,
+            out_of_combat_ms: elapsed_ms.saturating_sub(self.in_combat_ms),
+            summary:          String::new(),
+        };
+        debrief.summary = summary(&debrief, fmt);
+        debrief
+    }
+}
+
+/// "+12 Ara-Kara, City of Echoes — completed 31:20 — 14 pulls, 2 deaths, …"
+fn summary(d: &KeyDebrief, fmt: &NumberFormat) -> String {
+    let gap = d.gcd_gaps.first()
+        .map(|g| format!(", longest gap {} (pull {})", fmt.seconds(g.gap_ms, 1), g.pull_number))
+        .unwrap_or_default();
+    format!(
+        "+{} {} — {} {} — {} pulls, {} deaths, {} avoidable, {} kicks, {} out of combat{}",
+        d.level, d.dungeon,
+        if d.success { "completed" } else { "abandoned" }, fmt.duration(d.total_time_ms),
+        d.pulls, d.deaths, d.avoidable_count, d.interrupt_count,
+        fmt.duration(d.out_of_combat_ms), gap,
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pull(pull_number: u32, elapsed_ms: u64, avoidable: u32, kicks: u32) -> PullDebrief {
+        serde_json::from_value(serde_json::json!({
+            "pull_number": pull_number, "pull_elapsed_ms": elapsed_ms, "outcome": "unknown",
+            "avoidable_count": avoidable, "interrupt_count": kicks, "total_advice_fired": 0,
+            "gcd_gap_count": 0, "active_time_pct": 90.0, "dodge_stats": [], "cd_drift_ms": 0,
+            "summary": "",
+        })).unwrap()
+    }
+
+    #[test]
+    fn aggregates_pulls_into_the_key() {
+        let key = Keystone { instance_id: 2660, dungeon: "Ara-Kara, City of Echoes".into(), level: 12, affix_ids: vec![10] };
+        let mut run = KeyRun::start(key, 100_000, 4);
+        run.add_pull(&pull(1, 60_000, 2, 3), 2_500);
+        run.add_pull(&pull(2, 90_000, 1, 4), 6_000);
+        run.add_pull(&pull(3, 30_000, 0, 1), 0);
+
+        let d = run.finish(400_000, true, 310_000, 6, &NumberFormat::default());
+        assert_eq!((d.pulls, d.deaths, d.avoidable_count, d.interrupt_count), (3, 2, 3, 8));
+        assert_eq!(d.out_of_combat_ms, 300_000 - 180_000);
+        assert_eq!(d.total_time_ms, 310_000);
+        assert_eq!(d.gcd_gaps.iter().map(|g| g.pull_number).collect::<Vec<_>>(), vec![2, 1]);
+        assert!(d.summary.starts_with("+12 Ara-Kara, City of Echoes — completed 5:10 — 3 pulls, 2 deaths"), "{}", d.summary);
+    }
+}
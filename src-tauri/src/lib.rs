@@ -1,13 +1,50 @@
+mod addon_bridge;
+mod advice_debug;
+mod analytics;
+mod analyze;
+mod changelog;
+mod clock;
 mod config;
+mod config_watch;
+mod consumables;
+mod countdown;
+mod crash;
+mod data_version;
 mod db;
+mod diagnostics;
+mod encounter_download;
+mod encounters;
 mod engine;
+mod guid;
+mod hardware_cues;
 mod identity;
 mod ipc;
+mod journal;
+mod key_run;
+mod mechanic_stats;
 mod parser;
+mod practice;
+mod presets;
+mod profile;
+mod pull_image;
+mod raid_buffs;
+mod readiness;
+mod recoach;
+mod records;
+mod role_defaults;
+mod rotation;
+mod rule_pack;
 mod rules;
+mod runs;
 mod specs;
+mod spectate;
+mod spells;
 mod state;
 mod tailer;
+mod units;
+mod validate;
+mod wcl_import;
+mod wipe_forecast;
 
 use std::sync::{
     Mutex,
@@ -25,8 +62,8 @@ use tokio::sync::mpsc;
 /// Wrapped in `Mutex<Option<…>>` so `Option::take()` in try_start_pipeline
 /// ensures we only ever spawn the pipeline once.
 struct PipelineBundle {
-    raw_tx:     mpsc::Sender<String>,
-    raw_rx:     mpsc::Receiver<String>,
+    raw_tx:     mpsc::Sender<Vec<String>>,
+    raw_rx:     mpsc::Receiver<Vec<String>>,
     event_tx:   mpsc::Sender<parser::LogEvent>,
     event_rx:   mpsc::Receiver<parser::LogEvent>,
     id_tx:      mpsc::Sender<identity::PlayerIdentity>,
@@ -37,9 +74,29 @@ struct PipelineBundle {
     snap_rx:    mpsc::Receiver<ipc::StateSnapshot>,
     debrief_tx: mpsc::Sender<ipc::PullDebrief>,
     debrief_rx: mpsc::Receiver<ipc::PullDebrief>,
+    recap_tx:   mpsc::Sender<ipc::DeathRecap>,
+    recap_rx:   mpsc::Receiver<ipc::DeathRecap>,
+    key_tx:     mpsc::Sender<ipc::KeyDebrief>,
+    key_rx:     mpsc::Receiver<ipc::KeyDebrief>,
+    profile_tx: mpsc::Sender<role_defaults::GeneratedProfile>,
+    profile_rx: mpsc::Receiver<role_defaults::GeneratedProfile>,
+    fetch_tx:   mpsc::Sender<encounter_download::EncounterRequest>,
+    fetch_rx:   mpsc::Receiver<encounter_download::EncounterRequest>,
     db_writer:  db::DbWriter,
 }
 
+/// Dev CLI entry point: validate a spec/encounter TOML (see validate.rs).
+/// Returns the process exit code.
+pub fn validate_cli(path: &str, watch: bool) -> i32 {
+    validate::run_cli(path, watch)
+}
+
+/// Dev CLI entry point: analyze a combat log and print a per-pull JSON
+/// report (see analyze.rs).  Returns the process exit code.
+pub fn analyze_cli(path: &str, player: Option<&str>, spec: Option<&str>) -> i32 {
+    analyze::run_cli(path, player, spec)
+}
+
 pub fn run() {
     // -----------------------------------------------------------------------
     // Logging — write to both stderr (debug) and a rolling log file.
@@ -52,13 +109,22 @@ pub fn run() {
     //
     // NOTE: app_log_dir() is not available before the builder runs, so we
     // derive the path manually using the known Windows APPDATA env var.
-    // Tauri's identifier is "com.combatledger.livecoach".
-    let log_dir = {
-        let base = std::env::var("APPDATA")
-            .map(std::path::PathBuf::from)
-            .unwrap_or_else(|_| std::env::temp_dir());
-        base.join("com.combatledger.livecoach").join("logs")
-    };
+    // Tauri's identifier is "com.combatledger.livecoach"; app_config_dir() is
+    // this same folder (and app_data_dir() too), so a user-configured
+    // `log_dir` and `data_dir` can be honoured here.
+    let app_dir = std::env::var("APPDATA")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| std::env::temp_dir())
+        .join("com.combatledger.livecoach");
+    let startup_cfg = config::load_or_default(&app_dir).unwrap_or_default();
+    let crash_dir = config::resolve_dir(&startup_cfg.data_dir, app_dir.clone()).join(crash::CRASH_DIR);
+
+    // Native crash capture (crash.rs).  First, so the watcher process this
+    // relaunches us as stops here, before logging or Tauri start.
+    #[cfg(windows)]
+    let _minidump_watcher = crash::start_minidump_watcher(crash_dir.clone());
+
+    let log_dir = config::resolve_dir(&startup_cfg.log_dir, app_dir.join("logs"));
     let _ = std::fs::create_dir_all(&log_dir);
 
     let file_appender = tracing_appender::rolling::daily(&log_dir, "coach.log");
@@ -78,10 +144,11 @@ pub fn run() {
         .init();
 
     // -----------------------------------------------------------------------
-    // Panic hook — log panics through tracing before the process dies.
+    // Panic hook — log panics through tracing before the process dies and
+    // save a crash report (crash.rs) offered for sending on the next start.
     // Without this, panic messages only appear on stderr (invisible in prod).
     // -----------------------------------------------------------------------
-    std::panic::set_hook(Box::new(|info| {
+    std::panic::set_hook(Box::new(move |info| {
         let location = info
             .location()
             .map(|l| format!("{}:{}", l.file(), l.line()))
@@ -94,6 +161,10 @@ pub fn run() {
             "non-string panic payload".to_string()
         };
         tracing::error!("PANIC at {}: {}", location, message);
+        match crash::write_report(&crash_dir, &location, &message) {
+            Ok(path) => tracing::error!("Crash report written to {}", path.display()),
+            Err(e)   => tracing::error!("Could not write crash report: {}", e),
+        }
     }));
 
     tracing::info!("CombatLedger Live Coach starting — logs → {}", log_dir.display());
@@ -111,35 +182,46 @@ pub fn run() {
         //
         // Builder::manage() guarantees state is registered before the event loop starts,
         // so there is no window where a command handler can race against setup().
-        .manage(Mutex::new(ipc::ConnectionStatus {
-            log_tailing: false, addon_connected: false, wow_path: String::new()
-        }))
+        .manage(Mutex::new(ipc::ConnectionStatus::default()))
         .manage(Mutex::new(ipc::StateSnapshot {
             pull_elapsed_ms: 0, gcd_gap_ms: 0, avoidable_count: 0,
             in_combat: false, interrupt_count: 0, encounter_name: None,
+            wallclock_ms: 0, active_time_pct: 0.0,
+            dps_10s: 0.0, hps_10s: 0.0, pull_dps: 0.0, pull_hps: 0.0,
+            group_deaths: 0, battle_res: 0, coaching_paused: false,
+            phase_label: None, next_scheduled_event: None, readiness: None,
+            keystone: None, countdown: None, wipe_pct: None,
         }))
         .manage(Mutex::new(std::collections::VecDeque::<engine::AdviceEvent>::new()))
         // Event log ring buffer — filled by ipc::run; drained by drain_event_log command.
         // Uses a newtype wrapper (EventLogQueue) so it doesn't conflict with the advice queue
         // — both are VecDeque<String> internally but registered under different types.
         .manage(Mutex::new(ipc::EventLogQueue::new()))
+        // Last pull debrief — stored by ipc::run, re-emitted by show_last_debrief.
+        .manage(Mutex::new(ipc::LastDebrief::default()))
+        // Spectate file output — fed by ipc::run, directory set from the config.
+        .manage(Mutex::new(spectate::SpectateWriter::default()))
+        // Hardware cue output — fed by ipc::run, cue list set from the config.
+        .manage(Mutex::new(hardware_cues::HardwareCues::default()))
         // Config hot-update sender — None until try_start_pipeline() creates the channel.
         // save_config() uses this to push AppConfig changes to the running engine so
         // player_focus / selected_spec changes take effect without restarting the pipeline.
         .manage(Mutex::new(None::<mpsc::Sender<config::AppConfig>>))
+        // Engine control sender (pause/resume) — same lifecycle as the config sender.
+        .manage(Mutex::new(None::<mpsc::Sender<engine::EngineCommand>>))
+        // Offline replay state shared with the tailer — same lifecycle.
+        .manage(Mutex::new(None::<tailer::ReplayFeed>))
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(
             // v2.3.1 API: handler is registered at build time; register() only
             // takes the shortcut with no callback.
             tauri_plugin_global_shortcut::Builder::new()
-                .with_handler(|app, _shortcut, event| {
+                .with_handler(|app, shortcut, event| {
                     use tauri_plugin_global_shortcut::ShortcutState;
                     if event.state() == ShortcutState::Pressed {
-                        if let Some(ov) = app.get_webview_window("overlay") {
-                            let vis = ov.is_visible().unwrap_or(false);
-                            if vis { let _ = ov.hide(); } else { let _ = ov.show(); }
-                        }
+                        on_global_hotkey(app, shortcut);
                     }
                 })
                 .build()
@@ -178,16 +260,24 @@ pub fn run() {
             // try_start_pipeline() takes the bundle and spawns all tasks atomically,
             // so ipc::run is never live without its corresponding senders being held
             // by the engine/tailer/identity tasks.
-            let (raw_tx,     raw_rx)     = mpsc::channel::<String>(2048);
+            // Raw lines travel in batches (one per tailer read, ≤512 lines each).
+            let (raw_tx,     raw_rx)     = mpsc::channel::<Vec<String>>(64);
             let (event_tx,   event_rx)   = mpsc::channel::<parser::LogEvent>(1024);
             let (advice_tx,  advice_rx)  = mpsc::channel::<engine::AdviceEvent>(128);
             let (id_tx,      id_rx)      = mpsc::channel::<identity::PlayerIdentity>(16);
             let (snap_tx,    snap_rx)    = mpsc::channel::<ipc::StateSnapshot>(128);
             let (debrief_tx, debrief_rx) = mpsc::channel::<ipc::PullDebrief>(16);
+            let (recap_tx,   recap_rx)   = mpsc::channel::<ipc::DeathRecap>(16);
+            let (key_tx,     key_rx)     = mpsc::channel::<ipc::KeyDebrief>(4);
+            let (profile_tx, profile_rx) = mpsc::channel::<role_defaults::GeneratedProfile>(4);
+            let (fetch_tx,   fetch_rx)   = mpsc::channel::<encounter_download::EncounterRequest>(4);
 
             // --- SQLite ---
-            let db_path  = app.path().app_data_dir()?.join("sessions.sqlite");
+            let db_path  = config::resolve_dir(&cfg.data_dir, app.path().app_data_dir()?)
+                .join(config::DB_FILE_NAME);
             let db_writer = db::spawn_db_writer(&db_path)?;
+            // Extra handle for move_data_dir; the bundle's copy goes to the engine.
+            app.manage(db_writer.clone());
 
             // --- Store bundle + ready-flag in managed state ---
             let bundle = PipelineBundle {
@@ -197,6 +287,10 @@ pub fn run() {
                 advice_tx, advice_rx,
                 snap_tx, snap_rx,
                 debrief_tx, debrief_rx,
+                recap_tx, recap_rx,
+                key_tx, key_rx,
+                profile_tx, profile_rx,
+                fetch_tx, fetch_rx,
                 db_writer,
             };
             app.manage(Mutex::new(Some(bundle)));
@@ -208,8 +302,11 @@ pub fn run() {
 
             let handle = app.handle().clone();
 
+            // --- One-time "what's new" notification after an upgrade ---
+            changelog::announce_new_version(&handle);
+
             // --- Register global hotkey from config ---
-            register_global_hotkey(&handle, &cfg.hotkeys.toggle_overlay);
+            register_global_hotkeys(&handle, &cfg.hotkeys);
 
             // --- If path is already configured, start the pipeline immediately ---
             // On first run the path is empty; it will be set by the settings wizard.
@@ -220,6 +317,42 @@ pub fn run() {
                 tracing::info!("No WoW path configured — waiting for first-run setup");
             }
 
+            // --- Settings sync: import profiles written by another machine ---
+            {
+                let handle = handle.clone();
+                std::thread::Builder::new()
+                    .name("profile-sync".into())
+                    .spawn(move || {
+                        profile::run_sync_watcher(config_dir, |cfg| {
+                            register_global_hotkeys(&handle, &cfg.hotkeys);
+                            load_user_files(&handle);
+                            push_background_config_update(&handle, cfg);
+                        });
+                    })?;
+            }
+
+            // --- Hot reload of hand edits to config.toml ---
+            {
+                let handle = handle.clone();
+                let config_dir = app.path().app_config_dir()?;
+                std::thread::Builder::new()
+                    .name("config-watch".into())
+                    .spawn(move || {
+                        config_watch::run(
+                            config_dir,
+                            |cfg| {
+                                register_global_hotkeys(&handle, &cfg.hotkeys);
+                                // A Logs path typed in by hand starts the pipeline like a save would.
+                                try_start_pipeline(&handle);
+                                load_user_files(&handle);
+                                push_background_config_update(&handle, cfg);
+                                ipc::log_event(&handle, "⚙️ config.toml reloaded");
+                            },
+                            |err| ipc::log_event(&handle, &format!("⚠️ config.toml not applied: {}", err)),
+                        );
+                    })?;
+            }
+
             // Show overlay after setup
             overlay.show()?;
 
@@ -239,11 +372,50 @@ pub fn run() {
             config::list_wtf_characters,
             config::list_specs,
             config::apply_spec,
+            validate::validate_spec_file,
             check_for_update,
+            changelog::get_changelog,
+            crash::get_crash_reports,
+            crash::send_crash_report,
+            crash::dismiss_crash_report,
+            recoach::recoach_pull,
+            records::get_records,
+            mechanic_stats::get_mechanic_stats,
+            mechanic_stats::get_mechanic_learning_curve,
+            advice_debug::get_advice_debug,
+            pull_image::render_pull_image,
+            journal::set_session_note,
+            journal::add_journal_entry,
+            journal::delete_journal_entry,
+            journal::get_session_journals,
+            journal::export_journal,
+            hardware_cues::test_hardware_cue,
+            practice::get_practice_reports,
+            tailer::replay_log_file,
+            tailer::stop_log_replay,
+            tailer::get_replay_status,
+            tailer::pause_log_replay,
+            tailer::set_replay_speed,
+            tailer::seek_log_replay,
+            analyze::analyze_log,
+            presets::list_strategy_presets,
+            presets::download_strategy_preset,
+            presets::list_local_presets,
+            presets::apply_strategy_preset,
+            wcl_import::import_wcl_character,
             toggle_overlay,
             get_pull_history,
+            get_dodge_trends,
+            pause_coaching,
+            resume_coaching,
+            export_profile,
+            import_profile,
+            export_rule_pack,
+            import_rule_pack,
+            move_data_dir,
             read_audio_file,
             register_hotkey,
+            show_last_debrief,
             open_url,
         ])
         .run(tauri::generate_context!())
@@ -258,7 +430,8 @@ pub fn run() {
 ///
 /// On first call with a valid path, takes the `PipelineBundle` from managed state
 /// (can only happen once) and spawns all 5 tasks together — including `ipc::run` —
-/// so the IPC relay is always live alongside the tasks that feed it.
+/// so the IPC relay is always live alongside the tasks that feed it.  The
+/// diagnostics sampler starts here too, since it probes the pipeline channels.
 fn try_start_pipeline(app: &tauri::AppHandle) {
     // Re-read config from disk so we get the path saved most recently by save_config.
     let config_dir = match app.path().app_config_dir() {
@@ -325,6 +498,23 @@ fn try_start_pipeline(app: &tauri::AppHandle) {
 
     let wow_path_str = cfg.wow_log_path.to_string_lossy().to_string();
     let h = app.clone();
+    load_user_files(app);
+    set_spectate_dir(app, &cfg);
+    set_hardware_cues(app, &cfg);
+
+    // Self-monitoring: queue depths are read through weak senders.
+    let probes = vec![
+        diagnostics::ChannelProbe::new("raw_lines", &b.raw_tx),
+        diagnostics::ChannelProbe::new("events",    &b.event_tx),
+        diagnostics::ChannelProbe::new("advice",    &b.advice_tx),
+        diagnostics::ChannelProbe::new("snapshots", &b.snap_tx),
+        diagnostics::ChannelProbe::new("debriefs",  &b.debrief_tx),
+        diagnostics::ChannelProbe::new("recaps",    &b.recap_tx),
+        diagnostics::ChannelProbe::new("keys",      &b.key_tx),
+        diagnostics::ChannelProbe::new("profiles",  &b.profile_tx),
+        diagnostics::ChannelProbe::new("encounter_fetch", &b.fetch_tx),
+    ];
+    let diag_db = b.db_writer.clone();
 
     // Config hot-update channel — allows save_config to push AppConfig changes
     // to the running engine after startup (e.g. player_focus, selected_spec).
@@ -333,6 +523,11 @@ fn try_start_pipeline(app: &tauri::AppHandle) {
     if let Ok(mut guard) = app.state::<Mutex<Option<mpsc::Sender<config::AppConfig>>>>().lock() {
         *guard = Some(cfg_update_tx);
     }
+    // Engine control channel — pause_coaching / resume_coaching.
+    let (ctl_tx, ctl_rx) = mpsc::channel::<engine::EngineCommand>(4);
+    if let Ok(mut guard) = app.state::<Mutex<Option<mpsc::Sender<engine::EngineCommand>>>>().lock() {
+        *guard = Some(ctl_tx);
+    }
 
     // Tailer runs on a dedicated OS thread — NOT a tokio async task.
     // tailer::run uses blocking_send + recv_timeout (both blocking calls); spawning
@@ -341,18 +536,30 @@ fn try_start_pipeline(app: &tauri::AppHandle) {
     let tailer_path = cfg.wow_log_path.clone();
     let tailer_tx   = b.raw_tx;
     let tailer_h    = h.clone();
+    // Log→wallclock aligner: sampled by the tailer, read by the engine.
+    let clock       = std::sync::Arc::new(clock::ClockAligner::new());
+    let tailer_clk  = clock.clone();
+    // replay_log_file runs its own pipeline; the tailer pauses while it does.
+    let replay_feed = tailer::ReplayFeed::default();
+    let replaying   = replay_feed.active.clone();
+    if let Ok(mut guard) = app.state::<Mutex<Option<tailer::ReplayFeed>>>().lock() {
+        *guard = Some(replay_feed);
+    }
     std::thread::Builder::new()
         .name("combatlog-tailer".into())
         .spawn(move || {
-            if let Err(e) = tailer::run(tailer_path, tailer_tx, tailer_h, wow_path_str) {
+            if let Err(e) = tailer::run(tailer_path, tailer_tx, tailer_h, wow_path_str, tailer_clk, replaying) {
                 tracing::error!("Tailer exited with error: {}", e);
             }
         })
         .expect("failed to spawn combatlog-tailer thread");
     tauri::async_runtime::spawn(parser::run(b.raw_rx, b.event_tx));
     tauri::async_runtime::spawn(identity::run(cfg.addon_sv_path.clone(), b.id_tx, h.clone()));
-    tauri::async_runtime::spawn(engine::run(b.event_rx, b.id_rx, cfg_update_rx, b.advice_tx, b.snap_tx, b.debrief_tx, cfg, b.db_writer));
-    tauri::async_runtime::spawn(ipc::run(b.advice_rx, b.snap_rx, b.debrief_rx, h));
+    tauri::async_runtime::spawn(engine::run(b.event_rx, b.id_rx, cfg_update_rx, ctl_rx, b.advice_tx, b.snap_tx, b.debrief_tx, b.recap_tx, b.key_tx, b.profile_tx, b.fetch_tx, cfg, b.db_writer, clock));
+    tauri::async_runtime::spawn(ipc::run(b.advice_rx, b.snap_rx, b.debrief_rx, b.recap_rx, b.key_rx, b.profile_rx, h.clone()));
+    tauri::async_runtime::spawn(encounter_download::run(b.fetch_rx, h.clone()));
+    tauri::async_runtime::spawn(analytics::run(diag_db.clone(), h.clone()));
+    tauri::async_runtime::spawn(diagnostics::run(probes, diag_db, h));
 
     tracing::info!("Pipeline started successfully");
 }
@@ -364,16 +571,11 @@ fn try_start_pipeline(app: &tauri::AppHandle) {
 // ---------------------------------------------------------------------------
 
 /// Return the latest connection status stored in managed state.
-/// `ipc::emit_connection()` keeps this in sync every time the tailer or
+/// `ipc::update_connection()` keeps this in sync every time the tailer or
 /// identity module emits a status change.
 #[tauri::command]
 fn get_connection_status(app: tauri::AppHandle) -> ipc::ConnectionStatus {
-    let s = app.state::<Mutex<ipc::ConnectionStatus>>()
-        .lock()
-        .map(|s| s.clone())
-        .unwrap_or_else(|_| ipc::ConnectionStatus {
-            log_tailing: false, addon_connected: false, wow_path: String::new()
-        });
+    let s = ipc::connection_status(&app);
     tracing::debug!(
         "get_connection_status: returning log_tailing={} path={:?}",
         s.log_tailing, s.wow_path
@@ -409,6 +611,21 @@ fn get_state_snapshot(app: tauri::AppHandle) -> ipc::StateSnapshot {
             in_combat:       false,
             interrupt_count: 0,
             encounter_name:  None,
+            wallclock_ms:    0,
+            active_time_pct: 0.0,
+            dps_10s:         0.0,
+            hps_10s:         0.0,
+            pull_dps:        0.0,
+            pull_hps:        0.0,
+            group_deaths:    0,
+            battle_res:      0,
+            coaching_paused: false,
+            phase_label:     None,
+            next_scheduled_event: None,
+            readiness:       None,
+            keystone:        None,
+            countdown:       None,
+            wipe_pct:        None,
         })
 }
 
@@ -479,6 +696,13 @@ fn get_screen_size(app: tauri::AppHandle) -> ScreenSize {
 fn save_config(app: tauri::AppHandle, mut config: config::AppConfig) -> Result<(), String> {
     let dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
 
+    // A new log directory only applies at next launch — reject it now if it
+    // can't be written rather than silently falling back then.
+    let previous = config::load_or_default(&dir).unwrap_or_default();
+    if config.log_dir != previous.log_dir && !config.log_dir.as_os_str().is_empty() {
+        config::ensure_writable_dir(&config.log_dir).map_err(|e| e.to_string())?;
+    }
+
     // Auto-detect addon SavedVariables path if not yet configured.
     if config.addon_sv_path.as_os_str().is_empty()
         && !config.wow_log_path.as_os_str().is_empty()
@@ -489,11 +713,31 @@ fn save_config(app: tauri::AppHandle, mut config: config::AppConfig) -> Result<(
         }
     }
 
-    config::save(&config, &dir).map_err(|e| e.to_string())?;
+    // The addon bridge, the data version check and the changelog record
+    // their bookkeeping from other threads; the window's copy of those may
+    // be stale.
+    let config = config::update(&dir, |saved| {
+        config.addon_request_id      = saved.addon_request_id;
+        config.addon_outdated_notice = std::mem::take(&mut saved.addon_outdated_notice);
+        config.stale_data_notice     = std::mem::take(&mut saved.stale_data_notice);
+        config.last_seen_version     = std::mem::take(&mut saved.last_seen_version);
+        *saved = config;
+    }).map_err(|e| e.to_string())?;
+    profile::write_sync_copy(&config, &dir);
     try_start_pipeline(&app);
     // Push the new config to the running engine for live GUID/spec updates.
     // try_start_pipeline() is a no-op when the pipeline is already running, so
     // changes to player_focus or selected_spec made after startup are delivered here.
+    push_config_update(&app, config);
+    Ok(())
+}
+
+/// Deliver an updated AppConfig to the running engine (no-op before startup)
+/// and refresh the in-game status block (see addon_bridge.rs).
+pub(crate) fn push_config_update(app: &tauri::AppHandle, config: config::AppConfig) {
+    addon_bridge::publish_status(app, &config);
+    set_spectate_dir(app, &config);
+    set_hardware_cues(app, &config);
     if let Ok(guard) = app.state::<Mutex<Option<mpsc::Sender<config::AppConfig>>>>().lock() {
         if let Some(tx) = guard.as_ref() {
             if let Err(e) = tx.try_send(config) {
@@ -501,7 +745,167 @@ fn save_config(app: tauri::AppHandle, mut config: config::AppConfig) -> Result<(
             }
         }
     }
-    Ok(())
+}
+
+/// Deliver a config change made outside the settings window (in-game
+/// request, hand edit, synced settings) and have the window re-read its
+/// copy, so its next save does not revert the change.
+pub(crate) fn push_background_config_update(app: &tauri::AppHandle, config: config::AppConfig) {
+    push_config_update(app, config);
+    ipc::update_connection(app, |s| s.config_reloads += 1);
+}
+
+/// Point the spectate file output at the configured folder (spectate.rs).
+fn set_spectate_dir(app: &tauri::AppHandle, config: &config::AppConfig) {
+    if let Ok(mut writer) = app.state::<Mutex<spectate::SpectateWriter>>().lock() {
+        writer.set_dir(&config.spectate_dir);
+    }
+}
+
+/// Hand the configured hardware cues to the cue output (hardware_cues.rs).
+fn set_hardware_cues(app: &tauri::AppHandle, config: &config::AppConfig) {
+    if let Ok(mut cues) = app.state::<Mutex<hardware_cues::HardwareCues>>().lock() {
+        cues.set(&config.hardware_cues);
+    }
+}
+
+/// (Re)read the user rule files (rules/custom.rs), rule scripts
+/// (rules/script.rs) and encounter/dungeon files (encounters.rs) and hand
+/// them to the engine; files that fail to parse are reported in the Event
+/// Feed.
+fn load_user_files(app: &tauri::AppHandle) {
+    let Ok(config_dir) = app.path().app_config_dir() else { return };
+    let dir = config_dir.join(rules::custom::RULES_DIR);
+    let (loaded, errors) = rules::custom::load_dir(&dir);
+    for error in errors {
+        ipc::log_event(app, &format!("⚠️ Custom rule not loaded — {}", error));
+    }
+    if !loaded.is_empty() {
+        tracing::info!("Custom rules loaded: {}", loaded.len());
+    }
+    rules::custom::install(loaded);
+
+    let (scripts, errors) = rules::script::load_dir(&dir);
+    for error in errors {
+        ipc::log_event(app, &format!("⚠️ Rule script not loaded — {}", error));
+    }
+    if !scripts.is_empty() {
+        tracing::info!("Rule scripts loaded: {}", scripts.len());
+    }
+    rules::script::install(scripts);
+
+    let (data, errors) = encounters::load_user_dir(&config_dir.join(encounters::USER_DIR));
+    for error in errors {
+        ipc::log_event(app, &format!("⚠️ Encounter file not loaded — {}", error));
+    }
+    if data.files() > 0 {
+        tracing::info!("User encounter files loaded: {}", data.files());
+    }
+    encounters::install_user(data);
+}
+
+// ---------------------------------------------------------------------------
+// Profile export / import — see profile.rs
+// ---------------------------------------------------------------------------
+
+/// Write the current settings (without machine-local paths) and the user
+/// rule files to `path`.
+#[tauri::command]
+fn export_profile(app: tauri::AppHandle, path: String) -> Result<(), String> {
+    let dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let cfg = config::load_or_default(&dir).map_err(|e| e.to_string())?;
+    profile::write(&cfg, &dir, std::path::Path::new(&path)).map_err(|e| e.to_string())
+}
+
+/// Replace the current settings with the profile at `path`, keeping this
+/// machine's WoW/addon/sync paths, and add its rule files.  Returns the
+/// merged config for the UI.
+#[tauri::command]
+fn import_profile(app: tauri::AppHandle, path: String) -> Result<config::AppConfig, String> {
+    let dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let local = config::load_or_default(&dir).map_err(|e| e.to_string())?;
+    let file = profile::read(std::path::Path::new(&path)).map_err(|e| e.to_string())?;
+    let merged = profile::merge_into(&local, file.config);
+    profile::install_rules(&file.rules, &dir).map_err(|e| e.to_string())?;
+    config::save(&merged, &dir).map_err(|e| e.to_string())?;
+    profile::write_sync_copy(&merged, &dir);
+    register_global_hotkeys(&app, &merged.hotkeys);
+    load_user_files(&app);
+    push_config_update(&app, merged.clone());
+    Ok(merged)
+}
+
+// ---------------------------------------------------------------------------
+// Rule packs — see rule_pack.rs
+// ---------------------------------------------------------------------------
+
+/// Bundle the custom rule files `files` (all of them when empty) with the
+/// `pack` metadata and write the pack to `path`.
+#[tauri::command]
+fn export_rule_pack(
+    app:   tauri::AppHandle,
+    path:  String,
+    pack:  rule_pack::RulePackMeta,
+    files: Vec<String>,
+) -> Result<(), String> {
+    let dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let rules_dir = dir.join(rules::custom::RULES_DIR);
+    rule_pack::export(&rules_dir, &files, pack, std::path::Path::new(&path)).map_err(|e| e.to_string())
+}
+
+/// Validate the rule pack at `path` against the local rules, install it and
+/// reload the user rules.
+#[tauri::command]
+fn import_rule_pack(app: tauri::AppHandle, path: String) -> Result<rule_pack::ImportedPack, String> {
+    let dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let imported = rule_pack::import(std::path::Path::new(&path), &dir).map_err(|e| e.to_string())?;
+    load_user_files(&app);
+    if let Ok(cfg) = config::load_or_default(&dir) {
+        profile::write_sync_copy(&cfg, &dir);
+    }
+    ipc::log_event(&app, &format!(
+        "📦 Rule pack \"{}\" {} by {} imported — {} rules",
+        imported.pack.name, imported.pack.version, imported.pack.author, imported.rules
+    ));
+    Ok(imported)
+}
+
+// ---------------------------------------------------------------------------
+// Storage location — see AppConfig::data_dir
+// ---------------------------------------------------------------------------
+
+/// Path of the session database, honouring `config.data_dir`.
+pub(crate) fn sessions_db_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let cfg = config::load_or_default(&config_dir).map_err(|e| e.to_string())?;
+    let default = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(config::resolve_dir(&cfg.data_dir, default).join(config::DB_FILE_NAME))
+}
+
+/// Move the session database to `path` (a directory).  The target must be
+/// writable and must not already hold a database; the live writer copies the
+/// database there and keeps writing to the copy, and the old file is left in
+/// place.  Returns the updated config for the UI.
+#[tauri::command]
+async fn move_data_dir(app: tauri::AppHandle, path: String) -> Result<config::AppConfig, String> {
+    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let mut cfg = config::load_or_default(&config_dir).map_err(|e| e.to_string())?;
+    let new_dir = std::path::PathBuf::from(path);
+    let old_db = sessions_db_path(&app)?;
+    let new_db = new_dir.join(config::DB_FILE_NAME);
+    if new_db == old_db {
+        return Ok(cfg);
+    }
+
+    config::ensure_writable_dir(&new_dir).map_err(|e| e.to_string())?;
+    let writer = app.state::<db::DbWriter>().inner().clone();
+    writer.relocate(new_db.clone()).await.map_err(|e| e.to_string())?;
+    tracing::info!("Session database moved {:?} → {:?} (old file kept)", old_db, new_db);
+
+    cfg.data_dir = new_dir;
+    config::save(&cfg, &config_dir).map_err(|e| e.to_string())?;
+    push_config_update(&app, cfg.clone());
+    Ok(cfg)
 }
 
 // ---------------------------------------------------------------------------
@@ -515,10 +919,14 @@ pub struct UpdateInfo {
     pub current_version: String,
     pub new_version:     Option<String>,
     pub notes:           Option<String>,
+    /// Changelog entries newer than the running version (empty when the
+    /// changelog feed could not be fetched).
+    pub changes:         Vec<changelog::ChangelogEntry>,
 }
 
 /// Check GitHub Releases for a newer version by fetching latest.json.
 /// Uses the standard GitHub Releases download URL — no plugin required.
+/// Also refreshes the cached changelog feed (see changelog.rs).
 /// Version comparison: if the remote version string differs from the current
 /// package version, we report an update as available.
 #[tauri::command]
@@ -556,6 +964,7 @@ async fn check_for_update(app: tauri::AppHandle) -> Result<UpdateInfo, String> {
             let notes = parsed["notes"].as_str().map(|s| s.to_string());
 
             let available = !remote_version.is_empty() && remote_version != current;
+            let changes = if available { fetch_changelog(&app, &current).await } else { Vec::new() };
 
             tracing::info!(
                 "Update check: current={} remote={} available={}",
@@ -567,11 +976,40 @@ async fn check_for_update(app: tauri::AppHandle) -> Result<UpdateInfo, String> {
                 current_version: current,
                 new_version:     if available { Some(remote_version) } else { None },
                 notes,
+                changes,
             })
         }
     }
 }
 
+/// Fetch and cache the changelog feed; returns the entries newer than
+/// `current`.  Failures are logged and yield an empty list — the update
+/// check itself still succeeds.
+async fn fetch_changelog(app: &tauri::AppHandle, current: &str) -> Vec<changelog::ChangelogEntry> {
+    let body = tauri::async_runtime::spawn_blocking(|| {
+        ureq::get(changelog::FEED_URL)
+            .call()
+            .map_err(|e| e.to_string())
+            .and_then(|r| r.into_string().map_err(|e| e.to_string()))
+    })
+    .await
+    .map_err(|e| e.to_string())
+    .and_then(|r| r);
+
+    let body = match body {
+        Ok(b)  => b,
+        Err(e) => { tracing::warn!("Changelog fetch failed: {}", e); return Vec::new(); }
+    };
+    if let Ok(cache_dir) = app.path().app_cache_dir() {
+        if let Err(e) = changelog::cache_feed(&cache_dir, &body) {
+            tracing::warn!("Changelog cache write failed: {}", e);
+        }
+    }
+    changelog::parse_feed(&body)
+        .map(|entries| changelog::entries_between(&entries, current, None))
+        .unwrap_or_default()
+}
+
 // ---------------------------------------------------------------------------
 // Overlay visibility toggle — called by the frontend hotkey button and by
 // the global hotkey handler (future: tauri-plugin-global-shortcut).
@@ -662,43 +1100,73 @@ fn user_combo_to_shortcut(
     Ok(Shortcut::new(if mods.is_empty() { None } else { Some(mods) }, c))
 }
 
-/// Register (or clear) the overlay-toggle global hotkey.
+/// Register (or clear) the global hotkeys.
 /// Unregisters all existing hotkeys first to prevent duplicates on re-call.
-fn register_global_hotkey(app: &tauri::AppHandle, combo: &str) {
+fn register_global_hotkeys(app: &tauri::AppHandle, hotkeys: &config::HotkeyConfig) {
     use tauri_plugin_global_shortcut::GlobalShortcutExt;
 
     if let Err(e) = app.global_shortcut().unregister_all() {
         tracing::warn!("Hotkey unregister_all error: {}", e);
     }
 
-    if combo.is_empty() {
-        tracing::info!("Global hotkey cleared");
-        return;
+    for combo in [&hotkeys.toggle_overlay, &hotkeys.show_last_debrief] {
+        if combo.is_empty() {
+            continue;
+        }
+        match user_combo_to_shortcut(combo) {
+            Err(e) => tracing::warn!("Invalid hotkey combo '{}': {}", combo, e),
+            Ok(shortcut) => {
+                // v2.3.1: register() takes only the shortcut; the handler was
+                // supplied to Builder::with_handler() at plugin construction time.
+                if let Err(e) = app.global_shortcut().register(shortcut) {
+                    tracing::warn!("Hotkey register failed for '{}': {}", combo, e);
+                } else {
+                    tracing::info!("Global hotkey registered: {}", combo);
+                }
+            }
+        }
     }
+}
 
-    match user_combo_to_shortcut(combo) {
-        Err(e) => tracing::warn!("Invalid hotkey combo '{}': {}", combo, e),
-        Ok(shortcut) => {
-            // v2.3.1: register() takes only the shortcut; the handler was
-            // supplied to Builder::with_handler() at plugin construction time.
-            if let Err(e) = app.global_shortcut().register(shortcut) {
-                tracing::warn!("Hotkey register failed for '{}': {}", combo, e);
-            } else {
-                tracing::info!("Global hotkey registered: {}", combo);
-            }
+/// Run the action bound to a pressed global hotkey.  Anything that is not
+/// the debrief recall toggles the overlay, as the only hotkey used to.
+fn on_global_hotkey(app: &tauri::AppHandle, shortcut: &tauri_plugin_global_shortcut::Shortcut) {
+    let recall = app.path().app_config_dir().ok()
+        .and_then(|dir| config::load_or_default(&dir).ok())
+        .and_then(|cfg| user_combo_to_shortcut(&cfg.hotkeys.show_last_debrief).ok())
+        .is_some_and(|bound| bound == *shortcut);
+    if recall {
+        if !ipc::recall_last_debrief(app) {
+            tracing::info!("Debrief hotkey: no pull has ended yet");
         }
+        return;
+    }
+    if let Some(ov) = app.get_webview_window("overlay") {
+        let vis = ov.is_visible().unwrap_or(false);
+        if vis { let _ = ov.hide(); } else { let _ = ov.show(); }
     }
 }
 
-/// Re-register the overlay toggle hotkey from the settings window.
-/// Called after the user records a new combo and saves it.
-/// Passing an empty combo clears the hotkey.
+/// Re-register the global hotkeys from the settings window.
+/// Called after the user records a new combo and saves it; empty combos
+/// are left unbound.
 #[tauri::command]
-fn register_hotkey(app: tauri::AppHandle, combo: String) -> Result<(), String> {
-    register_global_hotkey(&app, &combo);
+fn register_hotkey(app: tauri::AppHandle, hotkeys: config::HotkeyConfig) -> Result<(), String> {
+    register_global_hotkeys(&app, &hotkeys);
     Ok(())
 }
 
+/// Show the most recent pull debrief on the overlay again, kept up until
+/// the next pull.  Err when no pull has ended since the app started.
+#[tauri::command]
+fn show_last_debrief(app: tauri::AppHandle) -> Result<(), String> {
+    if ipc::recall_last_debrief(&app) {
+        Ok(())
+    } else {
+        Err("No pull debrief yet".to_string())
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Shell helper — open a URL in the user's default browser
 // ---------------------------------------------------------------------------
@@ -724,7 +1192,7 @@ fn open_url(url: String) -> Result<(), String> {
 // ---------------------------------------------------------------------------
 
 /// One row returned by get_pull_history.
-/// Mirrors the joined pulls + sessions + advice_events query.
+/// Mirrors the joined pulls + sessions + instance_runs + advice_events query.
 #[derive(serde::Serialize)]
 struct PullHistoryRow {
     pull_id:      i64,
@@ -737,17 +1205,33 @@ struct PullHistoryRow {
     encounter:    Option<String>,
     player_name:  String,
     advice_count: u32,
+    /// Active-time percentage recorded at pull end (None for older rows).
+    active_time_pct: Option<f64>,
+    /// Player damage on the boss vs all targets (None for trash / older rows).
+    boss_damage_pct: Option<f64>,
+    /// Kick efficiency % (None without kick opportunities / older rows).
+    interrupt_efficiency: Option<f64>,
+    /// Instance run the pull belongs to and its label, e.g.
+    /// "Ara-Kara, City of Echoes (Mythic+)" (None outside instances).
+    run_id:       Option<i64>,
+    run_label:    Option<String>,
+    /// Mythic+ key active at pull start: dungeon, level and affix names
+    /// (None outside keys / older rows).
+    key_dungeon:  Option<String>,
+    key_level:    Option<u32>,
+    key_affixes:  Option<String>,
+    /// The session's note (journal.rs); "" when none was written.
+    session_note: String,
+    /// Group players' deaths and battle resurrections (None for older rows).
+    group_deaths: Option<u32>,
+    battle_res:   Option<u32>,
 }
 
 /// Return the last 25 pulls (newest first) with advice event counts.
 /// Opens a read-only SQLite connection so the writer thread is never blocked.
 #[tauri::command]
 async fn get_pull_history(app: tauri::AppHandle) -> Result<Vec<PullHistoryRow>, String> {
-    let db_path = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| e.to_string())?
-        .join("sessions.sqlite");
+    let db_path = sessions_db_path(&app)?;
 
     if !db_path.exists() {
         return Ok(vec![]);
@@ -765,10 +1249,16 @@ async fn get_pull_history(app: tauri::AppHandle) -> Result<Vec<PullHistoryRow>,
                 "SELECT p.id, p.session_id, p.pull_number, p.started_at, p.ended_at, \
                         p.outcome, p.encounter, \
                         COALESCE(s.player_name, '') AS player_name, \
-                        COUNT(ae.id) AS advice_count \
+                        COUNT(ae.id) AS advice_count, \
+                        p.active_time_pct, p.boss_damage_pct, p.interrupt_efficiency, \
+                        p.run_id, r.zone_name, r.difficulty_id, \
+                        p.key_dungeon, p.key_level, p.key_affixes, \
+                        COALESCE(s.note, '') AS session_note, \
+                        p.group_deaths, p.battle_res \
                  FROM pulls p \
                  LEFT JOIN sessions s ON s.id = p.session_id \
-                 LEFT JOIN advice_events ae ON ae.pull_id = p.id \
+                 LEFT JOIN instance_runs r ON r.id = p.run_id \
+                 LEFT JOIN advice_events ae ON ae.pull_id = p.id AND ae.version = 0 \
                  GROUP BY p.id \
                  ORDER BY p.id DESC \
                  LIMIT 25",
@@ -778,6 +1268,8 @@ async fn get_pull_history(app: tauri::AppHandle) -> Result<Vec<PullHistoryRow>,
         let rows = stmt
             .query_map([], |row| {
                 let ended_raw: Option<i64> = row.get(4)?;
+                let zone_name: Option<String> = row.get(13)?;
+                let difficulty: Option<u32>   = row.get(14)?;
                 Ok(PullHistoryRow {
                     pull_id:      row.get(0)?,
                     session_id:   row.get(1)?,
@@ -788,6 +1280,17 @@ async fn get_pull_history(app: tauri::AppHandle) -> Result<Vec<PullHistoryRow>,
                     encounter:    row.get(6)?,
                     player_name:  row.get(7)?,
                     advice_count: row.get::<_, i64>(8)? as u32,
+                    active_time_pct: row.get(9)?,
+                    boss_damage_pct: row.get(10)?,
+                    interrupt_efficiency: row.get(11)?,
+                    run_id:       row.get(12)?,
+                    run_label:    zone_name.map(|z| runs::run_label(&z, difficulty.unwrap_or(0))),
+                    key_dungeon:  row.get(15)?,
+                    key_level:    row.get(16)?,
+                    key_affixes:  row.get(17)?,
+                    session_note: row.get(18)?,
+                    group_deaths: row.get(19)?,
+                    battle_res:   row.get(20)?,
                 })
             })
             .map_err(|e| format!("DB query: {}", e))?;
@@ -799,6 +1302,56 @@ async fn get_pull_history(app: tauri::AppHandle) -> Result<Vec<PullHistoryRow>,
     .map_err(|e| format!("Task error: {}", e))?
 }
 
+/// Per-encounter, per-mechanic dodge totals across all stored pulls.
+/// Served from the analytics cache when it is current (analytics.rs).
+#[tauri::command]
+async fn get_dodge_trends(app: tauri::AppHandle) -> Result<Vec<analytics::DodgeTrendRow>, String> {
+    let db_path = sessions_db_path(&app)?;
+
+    if !db_path.exists() {
+        return Ok(vec![]);
+    }
+    let db = app.state::<db::DbWriter>().inner().clone();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = rusqlite::Connection::open_with_flags(
+            &db_path,
+            rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )
+        .map_err(|e| format!("DB open: {}", e))?;
+
+        analytics::read(&conn, Some(&db), analytics::DODGE_TRENDS, analytics::dodge_trends)
+            .map_err(|e| format!("DB query: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task error: {}", e))?
+}
+
+// ---------------------------------------------------------------------------
+// Pause / resume — suspend coaching during breaks without stopping the tailer.
+// ---------------------------------------------------------------------------
+
+/// Push a control message to the running engine.
+fn send_engine_command(app: &tauri::AppHandle, cmd: engine::EngineCommand) -> Result<(), String> {
+    let guard = app.state::<Mutex<Option<mpsc::Sender<engine::EngineCommand>>>>();
+    let guard = guard.lock().map_err(|e| e.to_string())?;
+    let tx = guard.as_ref().ok_or("Pipeline is not running")?;
+    tx.try_send(cmd).map_err(|e| format!("Engine control channel: {}", e))
+}
+
+/// Suspend rule evaluation and pull recording.  The log keeps being tailed
+/// and combat state stays current; the paused interval is stored per session.
+#[tauri::command]
+fn pause_coaching(app: tauri::AppHandle) -> Result<(), String> {
+    send_engine_command(&app, engine::EngineCommand::Pause)
+}
+
+/// Resume coaching after `pause_coaching`.  No-op if not paused.
+#[tauri::command]
+fn resume_coaching(app: tauri::AppHandle) -> Result<(), String> {
+    send_engine_command(&app, engine::EngineCommand::Resume)
+}
+
 // ---------------------------------------------------------------------------
 // Frontend diagnostics — lets JS log errors to coach.log without DevTools
 // ---------------------------------------------------------------------------
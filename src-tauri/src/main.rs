@@ -2,5 +2,29 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    // Dev CLI for data authors:
+    //   combat-ledger-live-coach --validate <file.toml> [--watch]
+    //   combat-ledger-live-coach --analyze <log> [--player Name-Realm] [--spec key]
+    // Run from a debug build — release builds have no console attached.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(pos) = args.iter().position(|a| a == "--validate") {
+        let Some(path) = args.get(pos + 1) else {
+            eprintln!("usage: --validate <file.toml> [--watch]");
+            std::process::exit(2);
+        };
+        let watch = args.iter().any(|a| a == "--watch");
+        std::process::exit(combat_ledger_lib::validate_cli(path, watch));
+    }
+    if let Some(pos) = args.iter().position(|a| a == "--analyze") {
+        let Some(path) = args.get(pos + 1) else {
+            eprintln!("usage: --analyze <log> [--player Name-Realm] [--spec key]");
+            std::process::exit(2);
+        };
+        let option = |name: &str| {
+            args.iter().position(|a| a == name).and_then(|i| args.get(i + 1)).map(String::as_str)
+        };
+        std::process::exit(combat_ledger_lib::analyze_cli(path, option("--player"), option("--spec")));
+    }
+
     combat_ledger_lib::run();
 }
@@ -0,0 +1,354 @@
+/// Mechanic stats across characters — the per-mechanic dodge record of every
+/// character on the player's WoW account, so a pattern like "you get hit by
+/// Surging Flames on every character" shows up even when each alt only has a
+/// few pulls of it.
+///
+/// Sessions are linked to the account through `sessions.account`, the WoW
+/// account folder of the addon's SavedVariables (`config::account_of`).
+/// Sessions recorded before the column existed have no account; they count
+/// towards the current one, since almost every install coaches a single
+/// account.
+///
+/// Mechanics are grouped by spell name rather than ID: the same ability
+/// usually has a separate spell ID per difficulty, and sometimes per
+/// encounter phase.
+///
+/// The learning curve (`get_mechanic_learning_curve`) follows one mechanic
+/// of one encounter over the attempts, oldest first, for charting
+/// improvement across a progression cycle.  Pulls are matched by the
+/// encounter ID stored at ENCOUNTER_END; pulls stored before the ID was
+/// (or that ended otherwise) match by the canonical names seen with it.
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::{AppHandle, Manager};
+
+use crate::{analytics, config, db};
+
+/// A character fails a mechanic when it gets hit at least this share of
+/// the time…
+const FAIL_RATE: f32 = 0.5;
+/// …over at least this many pulls with the mechanic.
+const MIN_PULLS: u32 = 3;
+
+/// One character's totals for a mechanic (`MechanicStat::characters`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CharacterMechanicStat {
+    pub player_guid: String,
+    pub player_name: String,
+    pub pulls:       u32,
+    pub avoided:     u32,
+    pub hit:         u32,
+}
+
+impl CharacterMechanicStat {
+    fn fails(&self) -> bool {
+        let total = self.avoided + self.hit;
+        self.pulls >= MIN_PULLS && total > 0 && self.hit as f32 / total as f32 >= FAIL_RATE
+    }
+}
+
+/// A mechanic's dodge record on each character of the account.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MechanicStat {
+    pub spell_name:       String,
+    /// Every spell ID recorded under the name, ascending.
+    pub spell_ids:        Vec<u32>,
+    /// Encounters the mechanic was recorded in, alphabetical.
+    pub encounters:       Vec<String>,
+    pub avoided:          u32,
+    pub hit:              u32,
+    /// Most pulls first.
+    pub characters:       Vec<CharacterMechanicStat>,
+    /// At least two characters have enough pulls of it, and all of those
+    /// fail it.
+    pub fails_everywhere: bool,
+}
+
+/// One character's totals for one spell ID in one encounter, as queried.
+#[derive(Debug, Clone)]
+struct MechanicRow {
+    spell_id:    u32,
+    spell_name:  String,
+    encounter:   String,
+    player_guid: String,
+    player_name: String,
+    pulls:       u32,
+    avoided:     u32,
+    hit:         u32,
+}
+
+/// Fold the per-spell rows into one stat per mechanic name; mechanics that
+/// fail everywhere come first, then the most hit.
+fn aggregate(rows: Vec<MechanicRow>) -> Vec<MechanicStat> {
+    let mut by_name: BTreeMap<String, MechanicStat> = BTreeMap::new();
+    for row in rows {
+        let stat = by_name.entry(row.spell_name.clone()).or_insert_with(|| MechanicStat {
+            spell_name:       row.spell_name.clone(),
+            spell_ids:        Vec::new(),
+            encounters:       Vec::new(),
+            avoided:          0,
+            hit:              0,
+            characters:       Vec::new(),
+            fails_everywhere: false,
+        });
+        if !stat.spell_ids.contains(&row.spell_id) {
+            stat.spell_ids.push(row.spell_id);
+        }
+        if !row.encounter.is_empty() && !stat.encounters.contains(&row.encounter) {
+            stat.encounters.push(row.encounter);
+        }
+        stat.avoided += row.avoided;
+        stat.hit     += row.hit;
+        match stat.characters.iter_mut().find(|c| c.player_guid == row.player_guid) {
+            Some(c) => {
+                c.pulls   += row.pulls;
+                c.avoided += row.avoided;
+                c.hit     += row.hit;
+            }
+            None => stat.characters.push(CharacterMechanicStat {
+                player_guid: row.player_guid,
+                player_name: row.player_name,
+                pulls:       row.pulls,
+                avoided:     row.avoided,
+                hit:         row.hit,
+            }),
+        }
+    }
+
+    let mut stats: Vec<MechanicStat> = by_name.into_values().collect();
+    for stat in &mut stats {
+        stat.spell_ids.sort_unstable();
+        stat.encounters.sort();
+        stat.characters.sort_by(|a, b| b.pulls.cmp(&a.pulls).then_with(|| a.player_name.cmp(&b.player_name)));
+        let judged: Vec<_> = stat.characters.iter().filter(|c| c.pulls >= MIN_PULLS).collect();
+        stat.fails_everywhere = judged.len() >= 2 && judged.iter().all(|c| c.fails());
+    }
+    stats.sort_by(|a, b| b.fails_everywhere.cmp(&a.fails_everywhere).then_with(|| b.hit.cmp(&a.hit)));
+    stats
+}
+
+/// Stats of every character on `account` (see analytics.rs for the cache).
+pub fn compute(conn: &rusqlite::Connection, account: &str) -> anyhow::Result<Vec<MechanicStat>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT d.spell_id, d.spell_name, {} AS enc, s.player_guid, MAX(s.player_name), \
+                COUNT(DISTINCT d.pull_id), SUM(d.avoided), SUM(d.hit) \
+         FROM mechanic_dodges d \
+         JOIN pulls p    ON p.id = d.pull_id \
+         JOIN sessions s ON s.id = p.session_id \
+         WHERE s.player_guid != '' AND (s.account = ?1 OR s.account = '') \
+         GROUP BY d.spell_id, d.spell_name, enc, s.player_guid",
+        crate::db::canonical_encounter("d.encounter"),
+    ))?;
+    let rows = stmt.query_map([account], |row| {
+        Ok(MechanicRow {
+            spell_id:    row.get::<_, i64>(0)? as u32,
+            spell_name:  row.get(1)?,
+            encounter:   row.get(2)?,
+            player_guid: row.get(3)?,
+            player_name: row.get(4)?,
+            pulls:       row.get::<_, i64>(5)? as u32,
+            avoided:     row.get::<_, i64>(6)? as u32,
+            hit:         row.get::<_, i64>(7)? as u32,
+        })
+    })?;
+    Ok(aggregate(rows.collect::<Result<_, _>>()?))
+}
+
+// ---------------------------------------------------------------------------
+// Learning curve
+// ---------------------------------------------------------------------------
+
+/// Attempts averaged into a point's trend.
+const TREND_PULLS: usize = 5;
+
+/// One attempt in a mechanic's learning curve.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LearningPoint {
+    /// 1-based, over the encounter's pulls that saw the mechanic.
+    pub attempt:     u32,
+    pub pull_id:     i64,
+    /// Unix ms.
+    pub started_at:  u64,
+    pub outcome:     String,
+    pub player_name: String,
+    pub hits:        u32,
+    pub avoided:     u32,
+    /// Mean hits over this attempt and up to TREND_PULLS - 1 before it.
+    pub trend:       f32,
+}
+
+/// Number the attempts and fill in the trend; `points` are oldest first.
+fn number_attempts(points: &mut [LearningPoint]) {
+    for i in 0..points.len() {
+        let window = &points[i.saturating_sub(TREND_PULLS - 1)..=i];
+        let trend = window.iter().map(|p| p.hits).sum::<u32>() as f32 / window.len() as f32;
+        points[i].attempt = i as u32 + 1;
+        points[i].trend   = trend;
+    }
+}
+
+/// The curve of `mechanic` (a spell name) in encounter `encounter_id`.
+pub fn learning_curve(
+    conn:         &rusqlite::Connection,
+    encounter_id: u32,
+    mechanic:     &str,
+) -> anyhow::Result<Vec<LearningPoint>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT p.id, p.started_at, COALESCE(p.outcome, ''), MAX(s.player_name), SUM(d.hit), SUM(d.avoided) \
+         FROM mechanic_dodges d \
+         JOIN pulls p    ON p.id = d.pull_id \
+         JOIN sessions s ON s.id = p.session_id \
+         WHERE d.spell_name = ?2 COLLATE NOCASE \
+           AND (p.encounter_id = ?1 \
+                OR (p.encounter_id IS NULL \
+                    AND {} IN (SELECT {} FROM pulls q WHERE q.encounter_id = ?1))) \
+         GROUP BY p.id \
+         ORDER BY p.started_at, p.id",
+        crate::db::canonical_encounter("p.encounter"),
+        crate::db::canonical_encounter("q.encounter"),
+    ))?;
+    let rows = stmt.query_map(rusqlite::params![encounter_id, mechanic], |row| {
+        Ok(LearningPoint {
+            attempt:     0,
+            pull_id:     row.get(0)?,
+            started_at:  row.get::<_, i64>(1)? as u64,
+            outcome:     row.get(2)?,
+            player_name: row.get(3)?,
+            hits:        row.get::<_, i64>(4)? as u32,
+            avoided:     row.get::<_, i64>(5)? as u32,
+            trend:       0.0,
+        })
+    })?;
+    let mut points = rows.collect::<Result<Vec<_>, _>>()?;
+    number_attempts(&mut points);
+    Ok(points)
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Per-mechanic dodge stats of every character on the configured account.
+#[tauri::command]
+pub async fn get_mechanic_stats(app: AppHandle) -> Result<Vec<MechanicStat>, String> {
+    let db_path = crate::sessions_db_path(&app)?;
+    if !db_path.exists() {
+        return Ok(vec![]);
+    }
+    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let cfg = config::load_or_default(&config_dir).map_err(|e| e.to_string())?;
+    let account = config::account_of(&cfg.addon_sv_path).unwrap_or_default();
+    let db = app.state::<db::DbWriter>().inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = rusqlite::Connection::open_with_flags(&db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        analytics::read(&conn, Some(&db), &analytics::mechanic_stats_name(&account), |c| compute(c, &account))
+    })
+        .await
+        .map_err(|e| format!("Task error: {}", e))?
+        .map_err(|e| e.to_string())
+}
+
+/// Hits per attempt of one mechanic (spell name) in one encounter, oldest
+/// first (see module docs).
+#[tauri::command]
+pub async fn get_mechanic_learning_curve(
+    app:          AppHandle,
+    encounter_id: u32,
+    mechanic:     String,
+) -> Result<Vec<LearningPoint>, String> {
+    let db_path = crate::sessions_db_path(&app)?;
+    if !db_path.exists() {
+        return Ok(vec![]);
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = rusqlite::Connection::open_with_flags(&db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        learning_curve(&conn, encounter_id, &mechanic)
+    })
+        .await
+        .map_err(|e| format!("Task error: {}", e))?
+        .map_err(|e| e.to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(spell_id: u32, name: &str, encounter: &str, guid: &str, pulls: u32, avoided: u32, hit: u32) -> MechanicRow {
+        MechanicRow {
+            spell_id,
+            spell_name:  name.to_owned(),
+            encounter:   encounter.to_owned(),
+            player_guid: guid.to_owned(),
+            player_name: format!("Char{}", guid),
+            pulls,
+            avoided,
+            hit,
+        }
+    }
+
+    #[test]
+    fn groups_by_name_and_flags_mechanics_failed_on_every_character() {
+        let stats = aggregate(vec![
+            // Surging Flames: two spell IDs (difficulties), hit on both alts.
+            row(100, "Surging Flames", "Boss A", "1", 3, 1, 3),
+            row(101, "Surging Flames", "Boss A", "1", 2, 1, 1),
+            row(100, "Surging Flames", "Boss A", "2", 4, 1, 4),
+            // Too few pulls on the third character to judge it.
+            row(100, "Surging Flames", "Boss A", "3", 1, 1, 0),
+            // Dodged fine on one of two characters.
+            row(200, "Falling Rocks",  "Boss B", "1", 5, 5, 1),
+            row(200, "Falling Rocks",  "Boss C", "2", 5, 1, 5),
+        ]);
+
+        assert_eq!(stats.len(), 2);
+        let flames = &stats[0];
+        assert_eq!(flames.spell_name, "Surging Flames");
+        assert!(flames.fails_everywhere);
+        assert_eq!(flames.spell_ids, vec![100, 101]);
+        assert_eq!((flames.avoided, flames.hit), (4, 8));
+        assert_eq!(flames.characters.len(), 3);
+        assert_eq!(flames.characters[0].pulls, 5);
+
+        let rocks = &stats[1];
+        assert!(!rocks.fails_everywhere);
+        assert_eq!(rocks.encounters, vec!["Boss B".to_owned(), "Boss C".to_owned()]);
+    }
+
+    #[test]
+    fn one_character_is_never_everywhere() {
+        let stats = aggregate(vec![row(100, "Surging Flames", "Boss A", "1", 9, 0, 9)]);
+        assert!(!stats[0].fails_everywhere);
+    }
+
+    #[test]
+    fn learning_curve_follows_a_mechanic_over_the_attempts() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        db::apply_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO sessions (id, started_at, player_name) VALUES (1, 0, 'Stonebraid');
+             INSERT INTO pulls (id, session_id, pull_number, started_at, outcome, encounter, encounter_id) VALUES
+                 (1, 1, 1, 3000, 'wipe', 'Boss A', 2900),
+                 (2, 1, 2, 1000, 'wipe', 'Boss A', NULL),
+                 (3, 1, 3, 5000, 'kill', 'Boss A', 2900),
+                 (4, 1, 4, 4000, 'wipe', 'Boss B', 2901);
+             INSERT INTO mechanic_dodges (pull_id, encounter, spell_id, spell_name, avoided, hit) VALUES
+                 (1, 'Boss A', 100, 'Surging Flames', 1, 3),
+                 (1, 'Boss A', 101, 'Surging Flames', 0, 1),
+                 (1, 'Boss A', 200, 'Falling Rocks', 5, 0),
+                 (2, 'Boss A', 100, 'Surging Flames', 0, 6),
+                 (3, 'Boss A', 101, 'Surging Flames', 4, 0),
+                 (4, 'Boss B', 100, 'Surging Flames', 0, 9);",
+        ).unwrap();
+
+        // Pull 2 has no ID but the name seen with it; both spell IDs count.
+        let curve = learning_curve(&conn, 2900, "surging flames").unwrap();
+        let hits: Vec<_> = curve.iter().map(|p| (p.attempt, p.pull_id, p.hits)).collect();
+        assert_eq!(hits, vec![(1, 2, 6), (2, 1, 4), (3, 3, 0)]);
+        assert_eq!(curve[2].trend, 10.0 / 3.0);
+        assert_eq!(curve[2].outcome, "kill");
+        assert!(learning_curve(&conn, 2902, "Surging Flames").unwrap().is_empty());
+    }
+}
@@ -9,7 +9,7 @@
 ///   Payload: SUBEVENT,SOURCEGUID,SOURCENAME,SOURCEFLAGS,SOURCERAIDFLAGS,
 ///                     DESTGUID,DESTNAME,DESTFLAGS,DESTROAIDFLAGS,
 ///                     [SPELLID,SPELLNAME,SPELLSCHOOL,]    ← SPELL_* events only
-///                     [19 advanced unit-state fields,]     ← ADVANCED_LOG_ENABLED=1
+///                     [17 advanced unit-state fields,]     ← ADVANCED_LOG_ENABLED=1
 ///                     [subevent-specific fields...]
 ///
 /// Field indices (0-based, WoW 12.0.1+):
@@ -27,12 +27,20 @@
 ///   [11] spell school
 ///   [12+] advanced unit-state fields (ADVANCED_LOG_ENABLED=1) then subevent-specific
 ///
-/// Note: SWING_* events have no spell prefix. ENCOUNTER_* events have their
-/// own fixed layout that does not follow this header at all.
+/// The advanced block (HP, power, position — see `parse_unit_state`) is kept
+/// as `UnitState` on damage, heal and cast-success events, and shifts the
+/// suffix fields that follow it.
+///
+/// Note: SWING_* events have no spell prefix. ENCOUNTER_*, ARENA_MATCH_*,
+/// ZONE_CHANGE, CHALLENGE_MODE_* and COMBATANT_INFO events have their own
+/// fixed layout that does not follow this header at all.  COMBATANT_INFO
+/// also nests lists in brackets and parentheses (`nested_fields`).
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc::{Receiver, Sender};
 
+use crate::advice_debug;
+
 /// Typed combat log events the coaching engine cares about.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -41,32 +49,152 @@ pub enum LogEvent {
         timestamp_ms: u64,
         source_guid:  String,
         source_name:  String,
+        /// COMBATLOG_OBJECT_* flags of the source (friendly-fire detection).
+        #[serde(default)]
+        source_flags: u32,
         dest_guid:    String,
         dest_name:    String,
+        /// COMBATLOG_OBJECT_* flags of the target (party damage tracking).
+        #[serde(default)]
+        dest_flags:   u32,
+        /// COMBATLOG_OBJECT_RAIDTARGET* marker bits of the target.
+        #[serde(default)]
+        dest_raid_flags: u32,
         spell_id:     u32,
         spell_name:   String,
         amount:       u64,
+        /// State of the damaged unit (advanced logging only).
+        #[serde(default)]
+        unit:         Option<UnitState>,
     },
     SwingDamage {
+        timestamp_ms: u64,
+        source_guid:  String,
+        #[serde(default)]
+        source_name:  String,
+        #[serde(default)]
+        source_flags: u32,
+        dest_guid:    String,
+        #[serde(default)]
+        dest_name:    String,
+        #[serde(default)]
+        dest_flags:   u32,
+        #[serde(default)]
+        dest_raid_flags: u32,
+        amount:       u64,
+        /// State of the damaged unit (advanced logging only).
+        #[serde(default)]
+        unit:         Option<UnitState>,
+    },
+    /// SPELL_MISSED — a spell that did no damage (dodged, immune, absorbed…).
+    SpellMissed {
         timestamp_ms: u64,
         source_guid:  String,
         dest_guid:    String,
+        spell_id:     u32,
+        spell_name:   String,
+        /// Miss type as logged, e.g. "DODGE", "IMMUNE", "ABSORB".
+        miss_type:    String,
+    },
+    /// SPELL_ABSORBED — part of a hit on `dest_guid` soaked by a shield.
+    /// Logged alongside the damage event, which carries only the rest.
+    SpellAbsorbed {
+        timestamp_ms:      u64,
+        source_guid:       String,
+        source_name:       String,
+        dest_guid:         String,
+        dest_name:         String,
+        /// Absorbed spell; 0 and "Melee" for a swing.
+        spell_id:          u32,
+        spell_name:        String,
+        /// Caster of the shield and the shield itself.
+        absorber_guid:     String,
+        absorb_spell_id:   u32,
+        absorb_spell_name: String,
+        amount:            u64,
+    },
+    /// SPELL_ENERGIZE — `dest_guid` gained a resource; `overflow` is the
+    /// part lost to the resource cap.
+    Energize {
+        timestamp_ms: u64,
+        source_guid:  String,
+        dest_guid:    String,
+        spell_id:     u32,
+        spell_name:   String,
+        /// Resource generated, overflow included.
         amount:       u64,
+        overflow:     u64,
+        /// Enum.PowerType (see `power_name`).
+        power_type:   u8,
+        /// The resource's cap when it was generated.
+        max_power:    u32,
+    },
+    /// SPELL_AURA_APPLIED — a buff or debuff landed on `dest_guid`.
+    AuraApplied {
+        timestamp_ms: u64,
+        source_guid:  String,
+        dest_guid:    String,
+        spell_id:     u32,
+        spell_name:   String,
+        /// "BUFF" or "DEBUFF".
+        aura_type:    String,
+    },
+    /// SPELL_AURA_REFRESH — an aura already on `dest_guid` was re-applied
+    /// (duration reset or pandemic-extended) without falling off.
+    AuraRefreshed {
+        timestamp_ms: u64,
+        source_guid:  String,
+        dest_guid:    String,
+        spell_id:     u32,
+        spell_name:   String,
+        aura_type:    String,
+    },
+    /// SPELL_AURA_REMOVED — the aura expired, was dispelled or cancelled.
+    AuraRemoved {
+        timestamp_ms: u64,
+        source_guid:  String,
+        dest_guid:    String,
+        spell_id:     u32,
+        spell_name:   String,
+        aura_type:    String,
     },
     SpellCastSuccess {
         timestamp_ms: u64,
         source_guid:  String,
         source_name:  String,
+        /// COMBATLOG_OBJECT_* unit flags of the caster (affiliation/reaction/type).
+        #[serde(default)]
+        source_flags: u32,
+        /// Cast target; empty for untargeted casts (AoE, self-buffs).
+        #[serde(default)]
+        dest_guid:    String,
+        #[serde(default)]
+        dest_name:    String,
         spell_id:     u32,
         spell_name:   String,
+        /// Caster's resource after the cast (advanced logging only).
+        #[serde(default)]
+        power:        Option<Power>,
+        /// State of the caster (advanced logging only).
+        #[serde(default)]
+        unit:         Option<UnitState>,
     },
     SpellHeal {
         timestamp_ms: u64,
         source_guid:  String,
+        #[serde(default)]
+        source_name:  String,
         dest_guid:    String,
+        #[serde(default)]
+        dest_name:    String,
         spell_id:     u32,
+        #[serde(default)]
+        spell_name:   String,
         amount:       u64,
         overhealing:  u64,
+        /// State of the healed unit (advanced logging only).
+        #[serde(default)]
+        unit:         Option<UnitState>,
     },
     UnitDied {
         timestamp_ms: u64,
@@ -94,6 +222,7 @@ pub enum LogEvent {
         timestamp_ms:  u64,
         encounter_id:  u32,
         encounter_name: String,
+        difficulty_id: u32,
         success:       bool,
     },
     /// SPELL_CAST_FAILED — player cast interrupted by movement/silence/etc.
@@ -113,6 +242,193 @@ pub enum LogEvent {
         spell_id:     u32,
         spell_name:   String,
     },
+    /// ARENA_MATCH_START — zone-in to an arena; authoritative match start.
+    ArenaMatchStart {
+        timestamp_ms: u64,
+        /// Arena map instance ID (see `arena_map_name`).
+        instance_id:  u32,
+        /// Match type as logged, e.g. "2v2", "3v3", "Skirmish", "Rated Solo Shuffle".
+        bracket:      String,
+        /// The coached player's team (0 or 1).
+        team_id:      u32,
+    },
+    /// ARENA_MATCH_END — authoritative match end with winner and new ratings.
+    ArenaMatchEnd {
+        timestamp_ms: u64,
+        winning_team: u32,
+        duration_s:   u32,
+        /// Post-match rating of team 0 / team 1 (0 for unrated matches).
+        team_ratings: [u32; 2],
+    },
+    /// ZONE_CHANGE — the player entered a zone or instance (session runs).
+    ZoneChange {
+        timestamp_ms:  u64,
+        /// Instance (map) ID of the zone.
+        instance_id:   u32,
+        zone_name:     String,
+        /// DifficultyID; 0 in the open world.
+        difficulty_id: u32,
+    },
+    /// CHALLENGE_MODE_START — a Mythic+ keystone was activated.
+    ChallengeModeStart {
+        timestamp_ms:   u64,
+        zone_name:      String,
+        instance_id:    u32,
+        keystone_level: u32,
+        /// Active affixes (see `affix_name`).
+        affix_ids:      Vec<u32>,
+    },
+    /// CHALLENGE_MODE_END — the key was completed, depleted or abandoned.
+    ChallengeModeEnd {
+        timestamp_ms:   u64,
+        instance_id:    u32,
+        /// True for a completed run (in time or not); false when abandoned.
+        success:        bool,
+        keystone_level: u32,
+        /// Run time as reported by the game, including death penalties.
+        total_time_ms:  u64,
+    },
+    /// COMBATANT_INFO — spec, talents and gear of one group member, logged
+    /// for the whole group right after ENCOUNTER_START / ARENA_MATCH_START.
+    CombatantInfo {
+        timestamp_ms: u64,
+        player_guid:  String,
+        /// Specialization ID (see `specs::spec_by_id`).
+        spec_id:      u32,
+        talents:      Vec<Talent>,
+        /// Equipped items in inventory slot order; empty slots have item_id 0.
+        gear:         Vec<GearItem>,
+        /// Spell IDs of the auras on the player when the line was logged.
+        #[serde(default)]
+        auras:        Vec<u32>,
+    },
+}
+
+/// Payload-free variant of a LogEvent — what rules subscribe to
+/// (rules/registry.rs).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    SpellDamage,
+    SwingDamage,
+    SpellMissed,
+    SpellAbsorbed,
+    Energize,
+    AuraApplied,
+    AuraRefreshed,
+    AuraRemoved,
+    SpellCastSuccess,
+    SpellHeal,
+    UnitDied,
+    SpellInterrupted,
+    EncounterStart,
+    EncounterEnd,
+    SpellCastFailed,
+    SpellCastStart,
+    ArenaMatchStart,
+    ArenaMatchEnd,
+    ZoneChange,
+    ChallengeModeStart,
+    ChallengeModeEnd,
+    CombatantInfo,
+}
+
+impl EventKind {
+    /// Every variant in declaration order, so `ALL[kind as usize] == kind`.
+    pub const ALL: [EventKind; 22] = [
+        Self::SpellDamage, Self::SwingDamage, Self::SpellMissed, Self::SpellAbsorbed,
+        Self::Energize, Self::AuraApplied, Self::AuraRefreshed, Self::AuraRemoved,
+        Self::SpellCastSuccess, Self::SpellHeal, Self::UnitDied, Self::SpellInterrupted,
+        Self::EncounterStart, Self::EncounterEnd, Self::SpellCastFailed, Self::SpellCastStart,
+        Self::ArenaMatchStart, Self::ArenaMatchEnd, Self::ZoneChange, Self::ChallengeModeStart,
+        Self::ChallengeModeEnd, Self::CombatantInfo,
+    ];
+    /// Number of variants, for tables indexed by `kind as usize`.
+    pub const COUNT: usize = Self::ALL.len();
+}
+
+// `ALL` must hold each variant at its own index; the match stops the build
+// when a variant is added without being listed there.
+const _: () = {
+    let mut i = 0;
+    while i < EventKind::ALL.len() {
+        assert!(EventKind::ALL[i] as usize == i, "EventKind::ALL is out of declaration order");
+        i += 1;
+    }
+    match EventKind::ALL[0] {
+        EventKind::SpellDamage | EventKind::SwingDamage | EventKind::SpellMissed | EventKind::SpellAbsorbed |
+        EventKind::Energize | EventKind::AuraApplied | EventKind::AuraRefreshed | EventKind::AuraRemoved |
+        EventKind::SpellCastSuccess | EventKind::SpellHeal | EventKind::UnitDied | EventKind::SpellInterrupted |
+        EventKind::EncounterStart | EventKind::EncounterEnd | EventKind::SpellCastFailed | EventKind::SpellCastStart |
+        EventKind::ArenaMatchStart | EventKind::ArenaMatchEnd | EventKind::ZoneChange | EventKind::ChallengeModeStart |
+        EventKind::ChallengeModeEnd | EventKind::CombatantInfo => {}
+    }
+};
+
+/// A unit's primary resource from the advanced unit-state block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Power {
+    /// Enum.PowerType (0 mana, 1 rage, 3 energy, 6 runic power, …).
+    pub power_type: u8,
+    pub current:    u32,
+    pub max:        u32,
+}
+
+/// A unit's state from the advanced unit-state block.  `guid` names the unit
+/// described: the caster of a cast, the target of damage or a heal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnitState {
+    pub guid:     String,
+    pub hp:       u64,
+    pub max_hp:   u64,
+    pub power:    Option<Power>,
+    pub position: Option<Position>,
+}
+
+impl UnitState {
+    /// Current health in % of maximum; None when the maximum is unknown.
+    pub fn hp_pct(&self) -> Option<f32> {
+        (self.max_hp > 0).then(|| self.hp as f32 / self.max_hp as f32 * 100.0)
+    }
+}
+
+/// World position from the advanced unit-state block.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Position {
+    pub x:      f32,
+    pub y:      f32,
+    /// UiMapID the coordinates belong to.
+    pub map_id: u32,
+    /// Facing in radians.
+    pub facing: f32,
+}
+
+/// A selected talent from COMBATANT_INFO.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Talent {
+    pub node_id:  u32,
+    pub entry_id: u32,
+    pub rank:     u32,
+}
+
+/// An equipped item from COMBATANT_INFO.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GearItem {
+    pub item_id:    u32,
+    pub item_level: u32,
+}
+
+/// Inventory slots left out of the average item level (0-based: shirt, tabard).
+const COSMETIC_SLOTS: [usize; 2] = [3, 18];
+
+/// Average item level of the equipped gear, as the character sheet counts
+/// it: shirt and tabard excluded, empty slots skipped.  None without gear.
+pub fn average_item_level(gear: &[GearItem]) -> Option<f32> {
+    let levels: Vec<u32> = gear.iter()
+        .enumerate()
+        .filter(|(slot, item)| item.item_id != 0 && !COSMETIC_SLOTS.contains(slot))
+        .map(|(_, item)| item.item_level)
+        .collect();
+    (!levels.is_empty()).then(|| levels.iter().sum::<u32>() as f32 / levels.len() as f32)
 }
 
 impl LogEvent {
@@ -120,6 +436,12 @@ impl LogEvent {
         match self {
             Self::SpellDamage      { timestamp_ms, .. } => *timestamp_ms,
             Self::SwingDamage      { timestamp_ms, .. } => *timestamp_ms,
+            Self::SpellMissed      { timestamp_ms, .. } => *timestamp_ms,
+            Self::SpellAbsorbed    { timestamp_ms, .. } => *timestamp_ms,
+            Self::Energize         { timestamp_ms, .. } => *timestamp_ms,
+            Self::AuraApplied      { timestamp_ms, .. } => *timestamp_ms,
+            Self::AuraRefreshed    { timestamp_ms, .. } => *timestamp_ms,
+            Self::AuraRemoved      { timestamp_ms, .. } => *timestamp_ms,
             Self::SpellCastSuccess { timestamp_ms, .. } => *timestamp_ms,
             Self::SpellHeal        { timestamp_ms, .. } => *timestamp_ms,
             Self::UnitDied         { timestamp_ms, .. } => *timestamp_ms,
@@ -128,6 +450,39 @@ impl LogEvent {
             Self::EncounterEnd     { timestamp_ms, .. } => *timestamp_ms,
             Self::SpellCastFailed  { timestamp_ms, .. } => *timestamp_ms,
             Self::SpellCastStart   { timestamp_ms, .. } => *timestamp_ms,
+            Self::ArenaMatchStart  { timestamp_ms, .. } => *timestamp_ms,
+            Self::ArenaMatchEnd    { timestamp_ms, .. } => *timestamp_ms,
+            Self::ZoneChange       { timestamp_ms, .. } => *timestamp_ms,
+            Self::ChallengeModeStart { timestamp_ms, .. } => *timestamp_ms,
+            Self::ChallengeModeEnd   { timestamp_ms, .. } => *timestamp_ms,
+            Self::CombatantInfo      { timestamp_ms, .. } => *timestamp_ms,
+        }
+    }
+
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Self::SpellDamage        { .. } => EventKind::SpellDamage,
+            Self::SwingDamage        { .. } => EventKind::SwingDamage,
+            Self::SpellMissed        { .. } => EventKind::SpellMissed,
+            Self::SpellAbsorbed      { .. } => EventKind::SpellAbsorbed,
+            Self::Energize           { .. } => EventKind::Energize,
+            Self::AuraApplied        { .. } => EventKind::AuraApplied,
+            Self::AuraRefreshed      { .. } => EventKind::AuraRefreshed,
+            Self::AuraRemoved        { .. } => EventKind::AuraRemoved,
+            Self::SpellCastSuccess   { .. } => EventKind::SpellCastSuccess,
+            Self::SpellHeal          { .. } => EventKind::SpellHeal,
+            Self::UnitDied           { .. } => EventKind::UnitDied,
+            Self::SpellInterrupted   { .. } => EventKind::SpellInterrupted,
+            Self::EncounterStart     { .. } => EventKind::EncounterStart,
+            Self::EncounterEnd       { .. } => EventKind::EncounterEnd,
+            Self::SpellCastFailed    { .. } => EventKind::SpellCastFailed,
+            Self::SpellCastStart     { .. } => EventKind::SpellCastStart,
+            Self::ArenaMatchStart    { .. } => EventKind::ArenaMatchStart,
+            Self::ArenaMatchEnd      { .. } => EventKind::ArenaMatchEnd,
+            Self::ZoneChange         { .. } => EventKind::ZoneChange,
+            Self::ChallengeModeStart { .. } => EventKind::ChallengeModeStart,
+            Self::ChallengeModeEnd   { .. } => EventKind::ChallengeModeEnd,
+            Self::CombatantInfo      { .. } => EventKind::CombatantInfo,
         }
     }
 
@@ -137,14 +492,26 @@ impl LogEvent {
         match self {
             Self::SpellDamage      { source_guid, .. } => Some(source_guid),
             Self::SwingDamage      { source_guid, .. } => Some(source_guid),
+            Self::SpellMissed      { source_guid, .. } => Some(source_guid),
+            Self::SpellAbsorbed    { source_guid, .. } => Some(source_guid),
+            Self::Energize         { source_guid, .. } => Some(source_guid),
+            Self::AuraApplied      { source_guid, .. } => Some(source_guid),
+            Self::AuraRefreshed    { source_guid, .. } => Some(source_guid),
+            Self::AuraRemoved      { source_guid, .. } => Some(source_guid),
             Self::SpellCastSuccess { source_guid, .. } => Some(source_guid),
             Self::SpellHeal        { source_guid, .. } => Some(source_guid),
             Self::SpellInterrupted { source_guid, .. } => Some(source_guid),
             Self::SpellCastFailed  { source_guid, .. } => Some(source_guid),
             Self::SpellCastStart   { source_guid, .. } => Some(source_guid),
+            Self::CombatantInfo    { player_guid, .. } => Some(player_guid),
             Self::UnitDied { .. }
             | Self::EncounterStart { .. }
-            | Self::EncounterEnd { .. }              => None,
+            | Self::EncounterEnd { .. }
+            | Self::ArenaMatchStart { .. }
+            | Self::ArenaMatchEnd { .. }
+            | Self::ZoneChange { .. }
+            | Self::ChallengeModeStart { .. }
+            | Self::ChallengeModeEnd { .. }          => None,
         }
     }
 
@@ -154,14 +521,37 @@ impl LogEvent {
         match self {
             Self::SpellDamage      { dest_guid, .. }   => Some(dest_guid),
             Self::SwingDamage      { dest_guid, .. }   => Some(dest_guid),
+            Self::SpellMissed      { dest_guid, .. }   => Some(dest_guid),
+            Self::SpellAbsorbed    { dest_guid, .. }   => Some(dest_guid),
+            Self::Energize         { dest_guid, .. }   => Some(dest_guid),
+            Self::AuraApplied      { dest_guid, .. }   => Some(dest_guid),
+            Self::AuraRefreshed    { dest_guid, .. }   => Some(dest_guid),
+            Self::AuraRemoved      { dest_guid, .. }   => Some(dest_guid),
             Self::SpellHeal        { dest_guid, .. }   => Some(dest_guid),
             Self::UnitDied         { dest_guid, .. }   => Some(dest_guid),
             Self::SpellInterrupted { target_guid, .. } => Some(target_guid),
-            Self::SpellCastSuccess { .. }
-            | Self::SpellCastFailed { .. }
+            Self::SpellCastSuccess { dest_guid, .. }   => (!dest_guid.is_empty()).then_some(dest_guid),
+            Self::SpellCastFailed { .. }
             | Self::SpellCastStart { .. }
             | Self::EncounterStart { .. }
-            | Self::EncounterEnd { .. }                => None,
+            | Self::EncounterEnd { .. }
+            | Self::ArenaMatchStart { .. }
+            | Self::ArenaMatchEnd { .. }
+            | Self::ZoneChange { .. }
+            | Self::ChallengeModeStart { .. }
+            | Self::ChallengeModeEnd { .. }
+            | Self::CombatantInfo { .. }               => None,
+        }
+    }
+
+    /// Advanced-logging unit state carried by this event, if any.
+    pub fn unit_state(&self) -> Option<&UnitState> {
+        match self {
+            Self::SpellDamage        { unit, .. }
+            | Self::SwingDamage      { unit, .. }
+            | Self::SpellCastSuccess { unit, .. }
+            | Self::SpellHeal        { unit, .. } => unit.as_ref(),
+            _ => None,
         }
     }
 }
@@ -205,6 +595,33 @@ fn csv_fields(s: &str, max: usize) -> Vec<&str> {
     fields
 }
 
+/// Split at the commas outside `[...]` and `(...)` groups (COMBATANT_INFO).
+fn nested_fields(s: &str) -> Vec<&str> {
+    let mut fields = Vec::new();
+    let mut depth  = 0u32;
+    let mut start  = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                fields.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if start < s.len() {
+        fields.push(&s[start..]);
+    }
+    fields
+}
+
+/// Strip one pair of enclosing brackets or parentheses.
+fn ungroup(s: &str) -> &str {
+    s.strip_prefix(['[', '(']).and_then(|s| s.strip_suffix([']', ')'])).unwrap_or(s)
+}
+
 // ---------------------------------------------------------------------------
 // Parsing helpers
 // ---------------------------------------------------------------------------
@@ -249,6 +666,141 @@ fn parse_timestamp(date_time: &str) -> Option<u64> {
     Some((h * 3_600 + m * 60 + s) * 1_000 + ms)
 }
 
+/// Display name for an Enum.PowerType value.
+pub fn power_name(power_type: u8) -> &'static str {
+    match power_type {
+        0  => "Mana",
+        1  => "Rage",
+        2  => "Focus",
+        3  => "Energy",
+        4  => "Combo Points",
+        5  => "Runes",
+        6  => "Runic Power",
+        7  => "Soul Shards",
+        8  => "Astral Power",
+        9  => "Holy Power",
+        11 => "Maelstrom",
+        12 => "Chi",
+        13 => "Insanity",
+        16 => "Arcane Charges",
+        17 => "Fury",
+        18 => "Pain",
+        19 => "Essence",
+        _  => "resource",
+    }
+}
+
+/// Display name for an arena map instance ID (None for unknown maps).
+pub fn arena_map_name(instance_id: u32) -> Option<&'static str> {
+    Some(match instance_id {
+        572  => "Ruins of Lordaeron",
+        617  => "Dalaran Sewers",
+        980  => "Tol'viron Arena",
+        1134 => "Tiger's Peak",
+        1504 => "Black Rook Hold Arena",
+        1505 => "Nagrand Arena",
+        1552 => "Ashamane's Fall",
+        1672 => "Blade's Edge Arena",
+        1825 => "Hook Point",
+        1911 => "Mugambala",
+        2167 => "The Robodrome",
+        2373 => "Empyrean Domain",
+        2509 => "Maldraxxus Coliseum",
+        2547 => "Enigma Crucible",
+        2563 => "Nokhudon Proving Grounds",
+        2759 => "Cage of Carnage",
+        _    => return None,
+    })
+}
+
+/// Display name for a Mythic+ affix ID (None for unknown affixes).
+pub fn affix_name(affix_id: u32) -> Option<&'static str> {
+    Some(match affix_id {
+        9   => "Tyrannical",
+        10  => "Fortified",
+        147 => "Xal'atath's Guile",
+        148 => "Xal'atath's Bargain: Ascendant",
+        152 => "Challenger's Peril",
+        158 => "Xal'atath's Bargain: Voidbound",
+        159 => "Xal'atath's Bargain: Oblivion",
+        160 => "Xal'atath's Bargain: Devour",
+        162 => "Xal'atath's Bargain: Pulsar",
+        _   => return None,
+    })
+}
+
+/// Parse a hex unit-flags field ("0x511") — 0 if malformed.
+fn parse_flags(s: &str) -> u32 {
+    u32::from_str_radix(s.trim_start_matches("0x"), 16).unwrap_or(0)
+}
+
+/// Length of the advanced unit-state block.
+const ADVANCED_FIELDS: usize = 17;
+/// Where the advanced block starts: after the spell prefix, or after the
+/// header for SWING_* events.
+const SPELL_BLOCK: usize = 12;
+const SWING_BLOCK: usize = 9;
+
+/// True if the advanced unit-state block starts at `f[start]` — it opens
+/// with a unit GUID where a plain log has the first suffix number.
+fn has_advanced(f: &[&str], start: usize) -> bool {
+    f.get(start).is_some_and(|s| {
+        s.starts_with(|c: char| c.is_ascii_alphabetic()) || *s == "0000000000000000"
+    })
+}
+
+/// Advanced unit-state block starting at `f[start]`, offsets relative to it:
+///   +0 infoGUID +1 ownerGUID +2 currentHP +3 maxHP +4 attackPower
+///   +5 spellPower +6 armor +7 absorb +8 powerType +9 currentPower
+///   +10 maxPower +11 powerCost +12 positionX +13 positionY +14 uiMapID
+///   +15 facing +16 level
+/// Multi-resource specs log "3|4"-style power lists; the first entry is the
+/// primary resource.  None without advanced logging.
+fn parse_unit_state(f: &[&str], start: usize) -> Option<UnitState> {
+    if !has_advanced(f, start) {
+        return None;
+    }
+    let at    = |i: usize| f.get(start + i).copied();
+    let first = |i: usize| at(i)?.split('|').next();
+    let power = || Some(Power {
+        power_type: first(8)?.parse().ok()?,
+        current:    first(9)?.parse().ok()?,
+        max:        first(10)?.parse().ok()?,
+    });
+    let position = || Some(Position {
+        x:      at(12)?.parse().ok()?,
+        y:      at(13)?.parse().ok()?,
+        map_id: at(14)?.parse().ok()?,
+        facing: at(15)?.parse().ok()?,
+    });
+    Some(UnitState {
+        guid:     at(0)?.to_owned(),
+        hp:       at(2)?.parse().ok()?,
+        max_hp:   at(3)?.parse().ok()?,
+        power:    power(),
+        position: position(),
+    })
+}
+
+/// Numeric suffix field: `plain` in a log without the advanced block, or
+/// `offset` past the end of the block starting at `start`.
+fn suffix_u64(f: &[&str], start: usize, plain: usize, offset: usize) -> u64 {
+    suffix_field(f, start, plain, offset).and_then(|s| s.parse().ok()).unwrap_or(0)
+}
+
+/// `suffix_u64` for amounts logged as decimals ("25.0000"), rounded.
+fn suffix_decimal(f: &[&str], start: usize, plain: usize, offset: usize) -> u64 {
+    suffix_field(f, start, plain, offset)
+        .and_then(|s| s.parse::<f64>().ok())
+        .map_or(0, |v| v.max(0.0).round() as u64)
+}
+
+/// The suffix field itself, located as in `suffix_u64`.
+fn suffix_field<'a>(f: &[&'a str], start: usize, plain: usize, offset: usize) -> Option<&'a str> {
+    let i = if has_advanced(f, start) { start + ADVANCED_FIELDS + offset } else { plain };
+    f.get(i).copied()
+}
+
 /// Strip surrounding double-quotes from a field value.
 #[inline]
 fn unquote(s: &str) -> &str {
@@ -263,53 +815,135 @@ fn split_line(raw: &str) -> Option<(u64, Vec<&str>)> {
     let payload = &raw[sep + 2..];
 
     let ts_ms = parse_timestamp(ts_str)?;
-    let fields = csv_fields(payload, 30);
+    let fields = csv_fields(payload, 40);
 
     Some((ts_ms, fields))
 }
 
+/// Parse only the timestamp prefix of a raw log line (no field splitting).
+/// Used by the tailer to pair line times with file-write times for clock alignment.
+pub fn line_timestamp_ms(raw: &str) -> Option<u64> {
+    let sep = raw.find("  ")?;
+    parse_timestamp(&raw[..sep])
+}
+
 pub fn parse_line(raw: &str) -> Option<LogEvent> {
     let (ts, f) = split_line(raw)?;
 
     let src_guid = unquote(f.get(1)?).to_owned();
     let src_name = unquote(f.get(2)?).to_owned();
+    let src_flags = f.get(3).map_or(0, |s| parse_flags(s));
     // ENCOUNTER_START / ENCOUNTER_END have only 5 fields and no source/dest
     // header, so f[5] and f[6] don't exist.  Use map_or so those events can
     // still reach their match arm instead of returning None here.
     let dst_guid = f.get(5).map_or("", |s| unquote(s)).to_owned();
     let dst_name = f.get(6).map_or("", |s| unquote(s)).to_owned();
+    let dst_flags = f.get(7).map_or(0, |s| parse_flags(s));
+    let dst_raid_flags = f.get(8).map_or(0, |s| parse_flags(s));
 
     match *f.first()? {
         "SPELL_DAMAGE" | "SPELL_PERIODIC_DAMAGE" | "RANGE_DAMAGE" => {
             let spell_id:  u32 = f.get(9)?.parse().ok()?;
             let spell_name     = unquote(f.get(10)?).to_owned();
-            let amount:    u64 = f.get(14).and_then(|s| s.parse().ok()).unwrap_or(0);
+            let amount:    u64 = suffix_u64(&f, SPELL_BLOCK, 14, 0);
             Some(LogEvent::SpellDamage {
-                timestamp_ms: ts, source_guid: src_guid, source_name: src_name,
-                dest_guid: dst_guid, dest_name: dst_name, spell_id, spell_name, amount,
+                timestamp_ms: ts, source_guid: src_guid, source_name: src_name, source_flags: src_flags,
+                dest_guid: dst_guid, dest_name: dst_name, dest_flags: dst_flags,
+                dest_raid_flags: dst_raid_flags,
+                spell_id, spell_name, amount, unit: parse_unit_state(&f, SPELL_BLOCK),
             })
         }
         "SWING_DAMAGE" => {
-            let amount: u64 = f.get(12).and_then(|s| s.parse().ok()).unwrap_or(0);
+            let amount: u64 = suffix_u64(&f, SWING_BLOCK, 12, 0);
             Some(LogEvent::SwingDamage {
-                timestamp_ms: ts, source_guid: src_guid, dest_guid: dst_guid, amount,
+                timestamp_ms: ts, source_guid: src_guid, source_name: src_name, source_flags: src_flags,
+                dest_guid: dst_guid, dest_name: dst_name, dest_flags: dst_flags, dest_raid_flags: dst_raid_flags,
+                amount, unit: parse_unit_state(&f, SWING_BLOCK),
+            })
+        }
+        "SPELL_MISSED" | "SPELL_PERIODIC_MISSED" | "RANGE_MISSED" => {
+            // Misses carry no advanced unit-state block: missType follows spellSchool.
+            let spell_id:  u32 = f.get(9)?.parse().ok()?;
+            let spell_name     = unquote(f.get(10)?).to_owned();
+            let miss_type      = unquote(f.get(12).unwrap_or(&"")).to_owned();
+            Some(LogEvent::SpellMissed {
+                timestamp_ms: ts, source_guid: src_guid, dest_guid: dst_guid,
+                spell_id, spell_name, miss_type,
+            })
+        }
+        "SPELL_ABSORBED" => {
+            // No advanced block.  A spell absorb has the spell prefix before
+            // the absorber; a melee absorb starts with the absorber's GUID:
+            //   [spellId, spellName, spellSchool,] casterGUID, casterName,
+            //   casterFlags, casterRaidFlags, absorbSpellId, absorbSpellName,
+            //   absorbSchool, amount[, baseAmount, critical]
+            let (spell_id, spell_name, at) = match f.get(9)?.parse::<u32>() {
+                Ok(id) => (id, unquote(f.get(10)?).to_owned(), 12),
+                Err(_) => (0, "Melee".to_owned(), 9),
+            };
+            Some(LogEvent::SpellAbsorbed {
+                timestamp_ms: ts, source_guid: src_guid, source_name: src_name,
+                dest_guid: dst_guid, dest_name: dst_name, spell_id, spell_name,
+                absorber_guid:     unquote(f.get(at)?).to_owned(),
+                absorb_spell_id:   f.get(at + 4)?.parse().ok()?,
+                absorb_spell_name: unquote(f.get(at + 5)?).to_owned(),
+                amount:            f.get(at + 7)?.parse().ok()?,
+            })
+        }
+        "SPELL_ENERGIZE" | "SPELL_PERIODIC_ENERGIZE" => {
+            // Suffix: amount, overEnergize, powerType, maxPower.
+            let spell_id:  u32 = f.get(9)?.parse().ok()?;
+            let spell_name     = unquote(f.get(10)?).to_owned();
+            Some(LogEvent::Energize {
+                timestamp_ms: ts, source_guid: src_guid, dest_guid: dst_guid, spell_id, spell_name,
+                amount:     suffix_decimal(&f, SPELL_BLOCK, 12, 0),
+                overflow:   suffix_decimal(&f, SPELL_BLOCK, 13, 1),
+                power_type: suffix_u64(&f, SPELL_BLOCK, 14, 2) as u8,
+                max_power:  suffix_u64(&f, SPELL_BLOCK, 15, 3) as u32,
+            })
+        }
+        "SPELL_AURA_APPLIED" | "SPELL_AURA_REFRESH" | "SPELL_AURA_REMOVED" => {
+            // Fields after spellSchool: auraType, then (applied only) amount.
+            let spell_id:  u32 = f.get(9)?.parse().ok()?;
+            let spell_name     = unquote(f.get(10)?).to_owned();
+            let aura_type      = unquote(f.get(12).unwrap_or(&"")).to_owned();
+            Some(match f[0] {
+                "SPELL_AURA_APPLIED" => LogEvent::AuraApplied {
+                    timestamp_ms: ts, source_guid: src_guid, dest_guid: dst_guid,
+                    spell_id, spell_name, aura_type,
+                },
+                "SPELL_AURA_REFRESH" => LogEvent::AuraRefreshed {
+                    timestamp_ms: ts, source_guid: src_guid, dest_guid: dst_guid,
+                    spell_id, spell_name, aura_type,
+                },
+                _ => LogEvent::AuraRemoved {
+                    timestamp_ms: ts, source_guid: src_guid, dest_guid: dst_guid,
+                    spell_id, spell_name, aura_type,
+                },
             })
         }
         "SPELL_CAST_SUCCESS" => {
             let spell_id:  u32 = f.get(9)?.parse().ok()?;
             let spell_name     = unquote(f.get(10)?).to_owned();
+            let unit           = parse_unit_state(&f, SPELL_BLOCK);
+            // Untargeted casts log an empty GUID ("0000000000000000").
+            let dest_guid = if dst_guid.trim_start_matches('0').is_empty() { String::new() } else { dst_guid };
             Some(LogEvent::SpellCastSuccess {
                 timestamp_ms: ts, source_guid: src_guid, source_name: src_name,
-                spell_id, spell_name,
+                source_flags: src_flags, dest_guid, dest_name: dst_name, spell_id, spell_name,
+                power: unit.as_ref().and_then(|u| u.power), unit,
             })
         }
         "SPELL_HEAL" | "SPELL_PERIODIC_HEAL" => {
             let spell_id:    u32 = f.get(9)?.parse().ok()?;
-            let amount:      u64 = f.get(14).and_then(|s| s.parse().ok()).unwrap_or(0);
-            let overhealing: u64 = f.get(15).and_then(|s| s.parse().ok()).unwrap_or(0);
+            let spell_name       = unquote(f.get(10).unwrap_or(&"")).to_owned();
+            // Advanced suffix: amount, baseAmount, overhealing, absorbed, critical.
+            let amount:      u64 = suffix_u64(&f, SPELL_BLOCK, 14, 0);
+            let overhealing: u64 = suffix_u64(&f, SPELL_BLOCK, 15, 2);
             Some(LogEvent::SpellHeal {
-                timestamp_ms: ts, source_guid: src_guid, dest_guid: dst_guid,
-                spell_id, amount, overhealing,
+                timestamp_ms: ts, source_guid: src_guid, source_name: src_name,
+                dest_guid: dst_guid, dest_name: dst_name, spell_id, spell_name, amount, overhealing,
+                unit: parse_unit_state(&f, SPELL_BLOCK),
             })
         }
         "UNIT_DIED" => {
@@ -341,13 +975,14 @@ pub fn parse_line(raw: &str) -> Option<LogEvent> {
             // ENCOUNTER_END,encounter_id,"Encounter Name",difficulty_id,group_size,success
             let encounter_id:  u32 = f.get(1)?.parse().ok()?;
             let encounter_name     = unquote(f.get(2)?).to_owned();
+            let difficulty_id: u32 = f.get(3).and_then(|s| s.parse().ok()).unwrap_or(0);
             // success: 1 = win, 0 = wipe
             let success: bool = f.get(5)
                 .and_then(|s| s.parse::<u8>().ok())
                 .map(|v| v == 1)
                 .unwrap_or(false);
             Some(LogEvent::EncounterEnd {
-                timestamp_ms: ts, encounter_id, encounter_name, success,
+                timestamp_ms: ts, encounter_id, encounter_name, difficulty_id, success,
             })
         }
         "SPELL_CAST_FAILED" => {
@@ -367,16 +1002,116 @@ pub fn parse_line(raw: &str) -> Option<LogEvent> {
                 spell_id, spell_name,
             })
         }
+        "ARENA_MATCH_START" => {
+            // ARENA_MATCH_START,instance_id,unused,match_type,team_id
+            let instance_id: u32 = f.get(1)?.parse().ok()?;
+            let bracket          = unquote(f.get(3)?).to_owned();
+            let team_id:     u32 = f.get(4)?.parse().unwrap_or(0);
+            Some(LogEvent::ArenaMatchStart {
+                timestamp_ms: ts, instance_id, bracket, team_id,
+            })
+        }
+        "ARENA_MATCH_END" => {
+            // ARENA_MATCH_END,winning_team,duration_s,new_rating_team0,new_rating_team1
+            let winning_team: u32 = f.get(1)?.parse().ok()?;
+            let duration_s:   u32 = f.get(2).and_then(|s| s.parse().ok()).unwrap_or(0);
+            let rating = |i: usize| f.get(i).and_then(|s| s.parse().ok()).unwrap_or(0);
+            Some(LogEvent::ArenaMatchEnd {
+                timestamp_ms: ts, winning_team, duration_s, team_ratings: [rating(3), rating(4)],
+            })
+        }
+        "ZONE_CHANGE" => {
+            // ZONE_CHANGE,instance_id,"Zone Name",difficulty_id
+            let instance_id:   u32 = f.get(1)?.parse().ok()?;
+            let zone_name          = unquote(f.get(2)?).to_owned();
+            let difficulty_id: u32 = f.get(3).and_then(|s| s.parse().ok()).unwrap_or(0);
+            Some(LogEvent::ZoneChange {
+                timestamp_ms: ts, instance_id, zone_name, difficulty_id,
+            })
+        }
+        "CHALLENGE_MODE_START" => {
+            // CHALLENGE_MODE_START,"Zone Name",instance_id,challenge_mode_id,keystone_level,[affix,affix,…]
+            // The bracketed affix list is split across fields by csv_fields.
+            let zone_name           = unquote(f.get(1)?).to_owned();
+            let instance_id:    u32 = f.get(2)?.parse().ok()?;
+            let keystone_level: u32 = f.get(4)?.parse().ok()?;
+            let affix_ids = f.iter().skip(5)
+                .filter_map(|s| s.trim_matches(['[', ']']).parse().ok())
+                .collect();
+            Some(LogEvent::ChallengeModeStart {
+                timestamp_ms: ts, zone_name, instance_id, keystone_level, affix_ids,
+            })
+        }
+        "CHALLENGE_MODE_END" => {
+            // CHALLENGE_MODE_END,instance_id,success,keystone_level,total_time_ms,old_rating,new_rating
+            let instance_id:    u32 = f.get(1)?.parse().ok()?;
+            let success             = f.get(2).is_some_and(|s| *s == "1");
+            let keystone_level: u32 = f.get(3).and_then(|s| s.parse().ok()).unwrap_or(0);
+            let total_time_ms:  u64 = f.get(4).and_then(|s| s.parse().ok()).unwrap_or(0);
+            Some(LogEvent::ChallengeModeEnd {
+                timestamp_ms: ts, instance_id, success, keystone_level, total_time_ms,
+            })
+        }
+        "COMBATANT_INFO" => parse_combatant_info(ts, raw),
         _ => None,
     }
 }
 
-/// Async pipeline task: receive raw lines, parse, forward typed events.
-pub async fn run(mut rx: Receiver<String>, tx: Sender<LogEvent>) -> Result<()> {
-    while let Some(line) = rx.recv().await {
-        if let Some(event) = parse_line(&line) {
-            if tx.send(event).await.is_err() {
-                break;
+/// COMBATANT_INFO,guid,faction,<21 stats>,spec_id,[(talents)],(pvp talents),[(gear)],[auras],…
+///
+/// Talents are `(node_id,entry_id,rank)`; gear items are
+/// `(item_id,item_level,(enchants),(bonus_ids),(gems))`; auras are a flat
+/// `[caster_guid,spell_id,caster_guid,spell_id,…]`.  The lists run past
+/// `split_line`'s field limit and contain commas, so the payload is split
+/// again with `nested_fields`.
+fn parse_combatant_info(ts: u64, raw: &str) -> Option<LogEvent> {
+    let payload = &raw[raw.find("  ")? + 2..];
+    let f = nested_fields(payload);
+    let player_guid  = f.get(1)?.to_string();
+    let spec_id: u32 = f.get(24)?.parse().ok()?;
+    let talents = f.get(25).map_or_else(Vec::new, |list| {
+        nested_fields(ungroup(list)).into_iter()
+            .filter_map(|t| {
+                let t = nested_fields(ungroup(t));
+                Some(Talent {
+                    node_id:  t.first()?.parse().ok()?,
+                    entry_id: t.get(1)?.parse().ok()?,
+                    rank:     t.get(2)?.parse().ok()?,
+                })
+            })
+            .collect()
+    });
+    let gear = f.get(27).map_or_else(Vec::new, |list| {
+        nested_fields(ungroup(list)).into_iter()
+            .filter_map(|item| {
+                let item = nested_fields(ungroup(item));
+                Some(GearItem {
+                    item_id:    item.first()?.parse().ok()?,
+                    item_level: item.get(1)?.parse().ok()?,
+                })
+            })
+            .collect()
+    });
+    let auras = f.get(28).map_or_else(Vec::new, |list| {
+        nested_fields(ungroup(list)).into_iter()
+            .skip(1)
+            .step_by(2)
+            .filter_map(|id| id.parse().ok())
+            .collect()
+    });
+    Some(LogEvent::CombatantInfo { timestamp_ms: ts, player_guid, spec_id, talents, gear, auras })
+}
+
+/// Async pipeline task: receive batches of raw lines from the tailer (one
+/// batch per file read), parse, forward typed events.
+pub async fn run(mut rx: Receiver<Vec<String>>, tx: Sender<LogEvent>) -> Result<()> {
+    while let Some(batch) = rx.recv().await {
+        for line in &batch {
+            advice_debug::capture(line);
+            if let Some(event) = parse_line(line) {
+                if tx.send(event).await.is_err() {
+                    return Ok(());
+                }
             }
         }
     }
@@ -399,6 +1134,15 @@ mod tests {
     const CAST_SUCCESS_LINE: &str =
         r#"5/21 20:14:35.100  SPELL_CAST_SUCCESS,Player-1234-ABCDEF,"Stonebraid",0x511,0x0,0000000000000000,"",0x80,0x0,31884,"Avenging Wrath",0x2"#;
 
+    const SPELL_MISSED_LINE: &str =
+        r#"5/21 20:14:36.000  SPELL_MISSED,Creature-0-4372-ABCD-000,"Boss",0xa48,0x0,Player-1234-ABCDEF,"Stonebraid",0x511,0x0,12345,"Shadow Surge",0x20,IMMUNE,nil"#;
+
+    const AURA_APPLIED_LINE: &str =
+        r#"5/21 20:14:36.000  SPELL_AURA_APPLIED,Player-1234-ABCDEF,"Stonebraid",0x511,0x0,Player-1234-ABCDEF,"Stonebraid",0x511,0x0,432021,"Flask of Alchemical Chaos",0x1,BUFF"#;
+
+    const AURA_REFRESH_LINE: &str =
+        r#"5/21 20:14:48.000  SPELL_AURA_REFRESH,Player-1234-ABCDEF,"Stonebraid",0x511,0x0,Creature-0-4372-ABCD-000,"Boss",0xa48,0x0,589,"Shadow Word: Pain",0x20,DEBUFF"#;
+
     const UNIT_DIED_LINE: &str =
         r#"5/21 20:15:00.000  UNIT_DIED,0000000000000000,"",0x80,0x0,Creature-0-4372-ABCD-000,"Boss",0xa48,0x0,0"#;
 
@@ -417,6 +1161,25 @@ mod tests {
     const CAST_START_LINE: &str =
         r#"5/21 20:14:34.000  SPELL_CAST_START,Creature-0-4372-ABCD-000,"Boss",0xa48,0x0,0000000000000000,"",0x80,0x0,99999,"Void Bolt",0x40"#;
 
+    const ARENA_START_LINE: &str =
+        r#"5/21 21:02:10.000  ARENA_MATCH_START,1672,33,3v3,1"#;
+
+    const ARENA_END_LINE: &str =
+        r#"5/21 21:05:44.000  ARENA_MATCH_END,0,214,1842,1796"#;
+
+    const ZONE_CHANGE_LINE: &str =
+        r#"5/21 19:58:02.000  ZONE_CHANGE,2660,"Ara-Kara, City of Echoes",8"#;
+
+    const CHALLENGE_START_LINE: &str =
+        r#"5/21 19:58:40.000  CHALLENGE_MODE_START,"Ara-Kara, City of Echoes",2660,503,12,[10,9,147]"#;
+
+    const CHALLENGE_END_LINE: &str =
+        r#"5/21 20:28:10.000  CHALLENGE_MODE_END,2660,1,12,1771000,2710.5,2745.0"#;
+
+    // Stats shortened to zeros; two talents, three gear slots (the middle one empty).
+    const COMBATANT_INFO_LINE: &str =
+        r#"5/21 20:14:30.050  COMBATANT_INFO,Player-1234-ABCDEF,1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,70,[(80954,101131,1),(80955,101132,2)],(0,203340,199428,0),[(212056,626,(),(10355,10256,1524),()),(0,0,(),(),()),(212054,619,(7340,0,0),(),())],[Player-1234-ABCDEF,1459],0,0,(0,0,0,0)"#;
+
     // QUOTED_COMMA_LINE has one extra 0 after spellSchool so amount lands at f[14].
     const QUOTED_COMMA_LINE: &str =
         r#"5/21 20:14:33.456  SPELL_DAMAGE,Creature-0-1234-ABCD-000,"Kel'Thuzad, the Undying",0xa48,0x0,Player-1234-ABCDEF,"Stonebraid",0x511,0x0,12345,"Frost Bolt",0x10,0,0,30000,0,0,0,nil,nil,nil"#;
@@ -439,10 +1202,166 @@ mod tests {
     fn parses_cast_success() {
         let e = parse_line(CAST_SUCCESS_LINE).expect("should parse");
         match e {
-            LogEvent::SpellCastSuccess { spell_id, spell_name, source_name, .. } => {
+            LogEvent::SpellCastSuccess { spell_id, spell_name, source_name, source_flags, .. } => {
                 assert_eq!(spell_id,    31884);
                 assert_eq!(spell_name, "Avenging Wrath");
                 assert_eq!(source_name, "Stonebraid");
+                assert_eq!(source_flags, 0x511);
+            }
+            other => panic!("Wrong variant: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_raid_marker_and_cast_target() {
+        // Skull-marked target.
+        let marked = SPELL_DAMAGE_LINE.replace("\"Boss\",0xa48,0x0", "\"Boss\",0xa48,0x80");
+        match parse_line(&marked).expect("should parse") {
+            LogEvent::SpellDamage { dest_raid_flags, .. } => assert_eq!(dest_raid_flags, 0x80),
+            other => panic!("Wrong variant: {:?}", other),
+        }
+        // Untargeted casts carry no destination.
+        match parse_line(CAST_SUCCESS_LINE).expect("should parse") {
+            LogEvent::SpellCastSuccess { dest_guid, .. } => assert!(dest_guid.is_empty()),
+            other => panic!("Wrong variant: {:?}", other),
+        }
+        let targeted = CAST_SUCCESS_LINE.replace("0000000000000000,\"\"", "Creature-0-4372-ABCD-000,\"Boss\"");
+        match parse_line(&targeted).expect("should parse") {
+            LogEvent::SpellCastSuccess { dest_guid, dest_name, .. } => {
+                assert_eq!(dest_guid, "Creature-0-4372-ABCD-000");
+                assert_eq!(dest_name, "Boss");
+            }
+            other => panic!("Wrong variant: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_cast_success_power() {
+        let advanced = r#"5/21 20:14:35.100  SPELL_CAST_SUCCESS,Player-1234-ABCDEF,"Stonebraid",0x511,0x0,Creature-0-4372-ABCD-000,"Boss",0xa48,0x0,23881,"Bloodthirst",0x1,Player-1234-ABCDEF,0000000000000000,812000,812000,9000,1500,30000,0,1|0,100|0,100|0,0,-1820.5,2301.2,2339,3.1,80"#;
+        match parse_line(advanced).expect("should parse") {
+            LogEvent::SpellCastSuccess { power, .. } => {
+                assert_eq!(power, Some(Power { power_type: 1, current: 100, max: 100 }));
+            }
+            other => panic!("Wrong variant: {:?}", other),
+        }
+        match parse_line(CAST_SUCCESS_LINE).expect("should parse") {
+            LogEvent::SpellCastSuccess { power, .. } => assert_eq!(power, None),
+            other => panic!("Wrong variant: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_advanced_unit_state() {
+        let damage = r#"5/21 20:14:36.200  SPELL_DAMAGE,Creature-0-4372-ABCD-000,"Boss",0xa48,0x0,Player-1234-ABCDEF,"Stonebraid",0x511,0x0,12345,"Shadow Surge",0x20,Player-1234-ABCDEF,0000000000000000,162400,812000,9000,1500,30000,0,1|0,40|0,100|0,0,-1820.5,2301.2,2339,3.1,80,55000,60000,-1,32,0,0,0,nil,nil,nil"#;
+        match parse_line(damage).expect("should parse") {
+            LogEvent::SpellDamage { amount, unit: Some(unit), .. } => {
+                assert_eq!(amount, 55000);
+                assert_eq!(unit.guid, "Player-1234-ABCDEF");
+                assert_eq!(unit.hp_pct(), Some(20.0));
+                assert_eq!(unit.power, Some(Power { power_type: 1, current: 40, max: 100 }));
+                let pos = unit.position.expect("position");
+                assert_eq!((pos.x, pos.y, pos.map_id), (-1820.5, 2301.2, 2339));
+            }
+            other => panic!("Wrong variant: {:?}", other),
+        }
+
+        let heal = r#"5/21 20:14:36.300  SPELL_HEAL,Player-1234-ABCDEF,"Stonebraid",0x511,0x0,Player-1234-ABCDEF,"Stonebraid",0x511,0x0,19750,"Flash of Light",0x2,Player-1234-ABCDEF,0000000000000000,812000,812000,9000,1500,30000,0,0,50000,50000,0,-1820.5,2301.2,2339,3.1,80,30000,30000,4000,0,nil"#;
+        match parse_line(heal).expect("should parse") {
+            LogEvent::SpellHeal { dest_name, spell_name, amount, overhealing, unit, .. } => {
+                assert_eq!(dest_name, "Stonebraid");
+                assert_eq!(spell_name, "Flash of Light");
+                assert_eq!((amount, overhealing), (30000, 4000));
+                assert_eq!(unit.map(|u| u.hp), Some(812000));
+            }
+            other => panic!("Wrong variant: {:?}", other),
+        }
+
+        match parse_line(SPELL_DAMAGE_LINE).expect("should parse") {
+            LogEvent::SpellDamage { amount, unit, .. } => {
+                assert_eq!(amount, 55000);
+                assert!(unit.is_none());
+            }
+            other => panic!("Wrong variant: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_spell_missed() {
+        let e = parse_line(SPELL_MISSED_LINE).expect("should parse");
+        match e {
+            LogEvent::SpellMissed { spell_id, miss_type, dest_guid, .. } => {
+                assert_eq!(spell_id,  12345);
+                assert_eq!(miss_type, "IMMUNE");
+                assert_eq!(dest_guid, "Player-1234-ABCDEF");
+            }
+            other => panic!("Wrong variant: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_spell_absorbed() {
+        let spell = r#"5/21 20:14:36.100  SPELL_ABSORBED,Creature-0-4372-ABCD-000,"Boss",0xa48,0x0,Player-1234-ABCDEF,"Stonebraid",0x511,0x0,12345,"Shadow Surge",0x20,Player-1234-BBBBBB,"Lightwell",0x512,0x0,17,"Power Word: Shield",0x2,42000,97000,nil"#;
+        match parse_line(spell).expect("should parse") {
+            LogEvent::SpellAbsorbed { spell_id, spell_name, absorber_guid, absorb_spell_id, amount, .. } => {
+                assert_eq!((spell_id, spell_name.as_str()), (12345, "Shadow Surge"));
+                assert_eq!(absorber_guid, "Player-1234-BBBBBB");
+                assert_eq!((absorb_spell_id, amount), (17, 42000));
+            }
+            other => panic!("Wrong variant: {:?}", other),
+        }
+
+        let melee = r#"5/21 20:14:36.200  SPELL_ABSORBED,Creature-0-4372-ABCD-000,"Boss",0xa48,0x0,Player-1234-ABCDEF,"Stonebraid",0x511,0x0,Player-1234-ABCDEF,"Stonebraid",0x511,0x0,77535,"Blood Shield",0x1,9000,15000,nil"#;
+        match parse_line(melee).expect("should parse") {
+            LogEvent::SpellAbsorbed { spell_id, spell_name, absorb_spell_name, amount, .. } => {
+                assert_eq!((spell_id, spell_name.as_str()), (0, "Melee"));
+                assert_eq!((absorb_spell_name.as_str(), amount), ("Blood Shield", 9000));
+            }
+            other => panic!("Wrong variant: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_energize() {
+        let plain = r#"5/21 20:14:36.300  SPELL_ENERGIZE,Player-1234-ABCDEF,"Stonebraid",0x511,0x0,Player-1234-ABCDEF,"Stonebraid",0x511,0x0,23881,"Bloodthirst",0x1,8.0000,3.0000,1,100"#;
+        match parse_line(plain).expect("should parse") {
+            LogEvent::Energize { spell_id, amount, overflow, power_type, max_power, .. } => {
+                assert_eq!(spell_id, 23881);
+                assert_eq!((amount, overflow), (8, 3));
+                assert_eq!((power_type, max_power), (1, 100));
+            }
+            other => panic!("Wrong variant: {:?}", other),
+        }
+
+        let advanced = r#"5/21 20:14:36.400  SPELL_ENERGIZE,Player-1234-ABCDEF,"Stonebraid",0x511,0x0,Player-1234-ABCDEF,"Stonebraid",0x511,0x0,23881,"Bloodthirst",0x1,Player-1234-ABCDEF,0000000000000000,812000,812000,9000,1500,30000,0,1,100,100,0,-1820.5,2301.2,2339,3.1,80,8.0000,0.0000,1,100"#;
+        match parse_line(advanced).expect("should parse") {
+            LogEvent::Energize { amount, overflow, power_type, .. } => {
+                assert_eq!((amount, overflow, power_type), (8, 0, 1));
+            }
+            other => panic!("Wrong variant: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_aura_applied() {
+        let e = parse_line(AURA_APPLIED_LINE).expect("should parse");
+        match e {
+            LogEvent::AuraApplied { spell_id, spell_name, aura_type, .. } => {
+                assert_eq!(spell_id,   432021);
+                assert_eq!(spell_name, "Flask of Alchemical Chaos");
+                assert_eq!(aura_type,  "BUFF");
+            }
+            other => panic!("Wrong variant: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_aura_refresh() {
+        let e = parse_line(AURA_REFRESH_LINE).expect("should parse");
+        match e {
+            LogEvent::AuraRefreshed { dest_guid, spell_id, aura_type, .. } => {
+                assert_eq!(dest_guid, "Creature-0-4372-ABCD-000");
+                assert_eq!(spell_id,  589);
+                assert_eq!(aura_type, "DEBUFF");
             }
             other => panic!("Wrong variant: {:?}", other),
         }
@@ -474,9 +1393,10 @@ mod tests {
     fn parses_encounter_end_win() {
         let e = parse_line(ENCOUNTER_END_WIN_LINE).expect("should parse");
         match e {
-            LogEvent::EncounterEnd { success, encounter_name, .. } => {
+            LogEvent::EncounterEnd { success, encounter_name, difficulty_id, .. } => {
                 assert!(success);
                 assert_eq!(encounter_name, "The Necrotic Wake");
+                assert_eq!(difficulty_id, 14);
             }
             other => panic!("Wrong variant: {:?}", other),
         }
@@ -516,14 +1436,93 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_arena_match_start() {
+        let e = parse_line(ARENA_START_LINE).expect("should parse");
+        match e {
+            LogEvent::ArenaMatchStart { instance_id, bracket, team_id, .. } => {
+                assert_eq!(instance_id, 1672);
+                assert_eq!(bracket,     "3v3");
+                assert_eq!(team_id,     1);
+                assert_eq!(arena_map_name(instance_id), Some("Blade's Edge Arena"));
+            }
+            other => panic!("Wrong variant: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_arena_match_end() {
+        let e = parse_line(ARENA_END_LINE).expect("should parse");
+        match e {
+            LogEvent::ArenaMatchEnd { winning_team, duration_s, team_ratings, .. } => {
+                assert_eq!(winning_team, 0);
+                assert_eq!(duration_s,   214);
+                assert_eq!(team_ratings, [1842, 1796]);
+            }
+            other => panic!("Wrong variant: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_zone_change() {
+        let e = parse_line(ZONE_CHANGE_LINE).expect("should parse");
+        match e {
+            LogEvent::ZoneChange { instance_id, zone_name, difficulty_id, .. } => {
+                assert_eq!(instance_id,   2660);
+                assert_eq!(zone_name,     "Ara-Kara, City of Echoes");
+                assert_eq!(difficulty_id, 8);
+            }
+            other => panic!("Wrong variant: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_challenge_mode_start_and_end() {
+        match parse_line(CHALLENGE_START_LINE).expect("should parse") {
+            LogEvent::ChallengeModeStart { zone_name, instance_id, keystone_level, affix_ids, .. } => {
+                assert_eq!(zone_name,      "Ara-Kara, City of Echoes");
+                assert_eq!(instance_id,    2660);
+                assert_eq!(keystone_level, 12);
+                assert_eq!(affix_ids,      vec![10, 9, 147]);
+            }
+            other => panic!("Wrong variant: {:?}", other),
+        }
+        match parse_line(CHALLENGE_END_LINE).expect("should parse") {
+            LogEvent::ChallengeModeEnd { instance_id, success, keystone_level, total_time_ms, .. } => {
+                assert_eq!(instance_id,    2660);
+                assert!(success);
+                assert_eq!(keystone_level, 12);
+                assert_eq!(total_time_ms,  1_771_000);
+            }
+            other => panic!("Wrong variant: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_combatant_info() {
+        match parse_line(COMBATANT_INFO_LINE).expect("should parse") {
+            LogEvent::CombatantInfo { player_guid, spec_id, talents, gear, auras, .. } => {
+                assert_eq!(player_guid, "Player-1234-ABCDEF");
+                assert_eq!(auras,       vec![1459]);
+                assert_eq!(spec_id,     70);
+                assert_eq!(talents[1],  Talent { node_id: 80955, entry_id: 101132, rank: 2 });
+                assert_eq!(gear.len(),  3);
+                assert_eq!(gear[2],     GearItem { item_id: 212054, item_level: 619 });
+                assert_eq!(average_item_level(&gear), Some(622.5));
+            }
+            other => panic!("Wrong variant: {:?}", other),
+        }
+    }
+
     #[test]
     fn handles_quoted_comma_in_npc_name() {
         // "Kel'Thuzad, the Undying" has a comma inside the quotes — dest is the
         // player "Stonebraid" and should land at field index 7.
         let e = parse_line(QUOTED_COMMA_LINE).expect("should parse");
         match e {
-            LogEvent::SpellDamage { dest_name, source_name, spell_name, .. } => {
+            LogEvent::SpellDamage { dest_name, dest_flags, source_name, spell_name, .. } => {
                 assert_eq!(dest_name,   "Stonebraid");
+                assert_eq!(dest_flags,  0x511);
                 assert_eq!(source_name, "Kel'Thuzad, the Undying");
                 assert_eq!(spell_name,  "Frost Bolt");
             }
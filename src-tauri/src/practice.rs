@@ -0,0 +1,435 @@
+/// Target-dummy practice mode.
+///
+/// A pull where everything the player fought was a training dummy (by NPC ID,
+/// or by name for dummies not in the list) is a practice pull.  The engine
+/// then runs only the rotation rules (`RuleProfile::Practice`) and, at pull
+/// end, scores the pull instead of storing it as a real attempt:
+///   - opener: the spec's `opener_spell_ids` cast in order within the first
+///     15s (major CDs in any order when the spec has none)
+///   - overcap: share of casts that left the primary resource at its cap
+///     (needs advanced combat logging)
+///   - CD cadence: per major CD, the shortest gap between uses over the
+///     average gap — 100% means every use came back on time
+///   - active time: the ABC metric
+///
+/// Consecutive practice pulls form one practice session; its running report
+/// is shown in the debrief and stored in `practice_sessions`, never in
+/// `pulls`, so dummy time does not skew pull history or progression.
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::AppHandle;
+
+use crate::{guid, parser::Power, spells};
+
+/// Training dummy NPC IDs across the capitals, class halls and Dornogal.
+/// Dummies missing here are still caught by name.
+pub const TRAINING_DUMMY_NPC_IDS: &[u32] = &[
+    31144, 31146, 32666, 32667, 46647, 67127,
+    87317, 87318, 87320, 87321, 87329, 88314, 88316,
+    113963, 113964, 153285, 153292,
+    194643, 194644, 194648, 194649, 197833,
+    225983, 225984, 225985,
+];
+/// The opener is judged on casts within this long of the pull start.
+pub const OPENER_WINDOW_MS: u64 = 15_000;
+/// Practice sessions returned to the UI.
+const REPORT_LIMIT: i64 = 20;
+
+/// True for a training dummy GUID or name.
+pub fn is_training_dummy(unit_guid: &str, name: &str) -> bool {
+    guid::npc_id(unit_guid).is_some_and(|id| TRAINING_DUMMY_NPC_IDS.contains(&id))
+        || name.contains("Training Dummy")
+        || name.contains("Target Dummy")
+}
+
+/// Power types that are wasted at cap.  Mana (0) regenerates into a cap that
+/// casters sit at by design; arcane charges (16) are meant to be held at 4.
+pub fn overcap_counts(power_type: u8) -> bool {
+    !matches!(power_type, 0 | 16)
+}
+
+// ---------------------------------------------------------------------------
+// Per-pull tracker
+// ---------------------------------------------------------------------------
+
+/// What the coached player fought and cast this pull.  Reset on pull start.
+#[derive(Debug, Default)]
+pub struct PracticeTracker {
+    dummy_seen:   bool,
+    other_enemy:  bool,
+    /// (log ms, spell id) of the player's casts.
+    casts:        Vec<(u64, u32)>,
+    /// Casts that reported a countable resource, and those that left it capped.
+    power_casts:  u32,
+    capped_casts: u32,
+}
+
+impl PracticeTracker {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// An NPC the player hit or was hit by.  Non-NPC units are ignored.
+    pub fn record_enemy(&mut self, unit_guid: &str, name: &str) {
+        if !guid::kind(unit_guid).is_npc() {
+            return;
+        }
+        if is_training_dummy(unit_guid, name) {
+            self.dummy_seen = true;
+        } else {
+            self.other_enemy = true;
+        }
+    }
+
+    pub fn record_cast(&mut self, now_ms: u64, spell_id: u32, power: Option<Power>) {
+        self.casts.push((now_ms, spell_id));
+        if let Some(p) = power.filter(|p| p.max > 0 && overcap_counts(p.power_type)) {
+            self.power_casts += 1;
+            if p.current >= p.max {
+                self.capped_casts += 1;
+            }
+        }
+    }
+
+    /// Only dummies were fought this pull.
+    pub fn is_practice(&self) -> bool {
+        self.dummy_seen && !self.other_enemy
+    }
+
+    pub fn cast_count(&self) -> usize {
+        self.casts.len()
+    }
+
+    /// `(log ms, spell ID)` of the player's casts this pull (cast efficiency).
+    pub fn casts(&self) -> &[(u64, u32)] {
+        &self.casts
+    }
+
+    /// Score the pull.  `opener` falls back to `major_cds` (any order) when empty.
+    pub fn score(
+        &self,
+        start_ms:        u64,
+        end_ms:          u64,
+        opener:          &[u32],
+        major_cds:       &[u32],
+        active_time_pct: f32,
+    ) -> PullScore {
+        let opener_pct = if opener.is_empty() {
+            opener_adherence(&self.casts, start_ms, major_cds, false)
+        } else {
+            opener_adherence(&self.casts, start_ms, opener, true)
+        };
+        let cooldowns = major_cds.iter()
+            .filter_map(|&spell_id| {
+                let uses: Vec<u64> = self.casts.iter()
+                    .filter(|(_, id)| *id == spell_id)
+                    .map(|(ts, _)| *ts)
+                    .collect();
+                (!uses.is_empty()).then(|| CdCadence {
+                    spell_id,
+                    spell_name:  spells::name(spell_id).unwrap_or("Unknown").to_owned(),
+                    uses:        uses.len() as u32,
+                    cadence_pct: cadence(&uses),
+                })
+            })
+            .collect();
+        let mut score = PullScore {
+            duration_ms:  end_ms.saturating_sub(start_ms),
+            active_time_pct,
+            opener_pct,
+            casts:        self.casts.len() as u32,
+            power_casts:  self.power_casts,
+            capped_casts: self.capped_casts,
+            cooldowns,
+            score:        0.0,
+        };
+        score.score = overall(&score);
+        score
+    }
+}
+
+/// Share (0–100) of `opener` found among the casts within the opener window:
+/// as an ordered subsequence when `ordered`, else as a set.  None if empty.
+pub fn opener_adherence(casts: &[(u64, u32)], start_ms: u64, opener: &[u32], ordered: bool) -> Option<f32> {
+    if opener.is_empty() {
+        return None;
+    }
+    let window = casts.iter()
+        .filter(|(ts, _)| ts.saturating_sub(start_ms) <= OPENER_WINDOW_MS)
+        .map(|(_, id)| *id);
+    let hit = if ordered {
+        let mut next = 0;
+        for id in window {
+            if opener.get(next) == Some(&id) {
+                next += 1;
+            }
+        }
+        next
+    } else {
+        let used: Vec<u32> = window.collect();
+        opener.iter().filter(|id| used.contains(id)).count()
+    };
+    Some(hit as f32 / opener.len() as f32 * 100.0)
+}
+
+/// Shortest gap over mean gap between uses, 0–100.  None below three uses —
+/// a single gap says nothing about drift.
+pub fn cadence(uses: &[u64]) -> Option<f32> {
+    if uses.len() < 3 {
+        return None;
+    }
+    let gaps: Vec<u64> = uses.windows(2).map(|w| w[1].saturating_sub(w[0])).collect();
+    let mean = gaps.iter().sum::<u64>() as f32 / gaps.len() as f32;
+    let min  = *gaps.iter().min()? as f32;
+    (mean > 0.0).then(|| (min / mean * 100.0).min(100.0))
+}
+
+/// Mean of the available components, 0–100.
+fn overall(s: &PullScore) -> f32 {
+    let mut parts = vec![s.active_time_pct];
+    parts.extend(s.opener_pct);
+    parts.extend(s.overcap_pct().map(|p| 100.0 - p));
+    parts.extend(s.cooldowns.iter().filter_map(|c| c.cadence_pct));
+    parts.iter().sum::<f32>() / parts.len() as f32
+}
+
+// ---------------------------------------------------------------------------
+// Scores and reports
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CdCadence {
+    pub spell_id:    u32,
+    pub spell_name:  String,
+    pub uses:        u32,
+    /// None below three uses.
+    pub cadence_pct: Option<f32>,
+}
+
+/// One scored practice pull.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PullScore {
+    pub duration_ms:     u64,
+    pub active_time_pct: f32,
+    pub opener_pct:      Option<f32>,
+    pub casts:           u32,
+    pub power_casts:     u32,
+    pub capped_casts:    u32,
+    pub cooldowns:       Vec<CdCadence>,
+    pub score:           f32,
+}
+
+impl PullScore {
+    /// None without advanced logging (no resource on any cast).
+    pub fn overcap_pct(&self) -> Option<f32> {
+        (self.power_casts > 0).then(|| self.capped_casts as f32 / self.power_casts as f32 * 100.0)
+    }
+}
+
+/// Consecutive practice pulls.  Ended by the next real pull.
+#[derive(Debug, Clone)]
+pub struct PracticeSession {
+    /// Wall-clock ms of the first pull — the session's key in the DB.
+    pub started_at: u64,
+    pub spec:       String,
+    pub pulls:      Vec<PullScore>,
+}
+
+impl PracticeSession {
+    pub fn new(started_at: u64, spec: String) -> Self {
+        Self { started_at, spec, pulls: Vec::new() }
+    }
+
+    /// Aggregate over all pulls so far.
+    pub fn report(&self) -> PracticeReport {
+        let n = self.pulls.len().max(1) as f32;
+        let duration_ms: u64 = self.pulls.iter().map(|p| p.duration_ms).sum();
+        let openers: Vec<f32> = self.pulls.iter().filter_map(|p| p.opener_pct).collect();
+        let power_casts:  u32 = self.pulls.iter().map(|p| p.power_casts).sum();
+        let capped_casts: u32 = self.pulls.iter().map(|p| p.capped_casts).sum();
+
+        let mut cds: BTreeMap<u32, (String, u32, Vec<f32>)> = BTreeMap::new();
+        for c in self.pulls.iter().flat_map(|p| &p.cooldowns) {
+            let entry = cds.entry(c.spell_id).or_insert_with(|| (c.spell_name.clone(), 0, Vec::new()));
+            entry.1 += c.uses;
+            entry.2.extend(c.cadence_pct);
+        }
+
+        PracticeReport {
+            pulls:           self.pulls.len() as u32,
+            duration_ms,
+            score:           self.pulls.iter().map(|p| p.score).sum::<f32>() / n,
+            best_score:      self.pulls.iter().map(|p| p.score).fold(0.0, f32::max),
+            last_score:      self.pulls.last().map_or(0.0, |p| p.score),
+            opener_pct:      (!openers.is_empty()).then(|| openers.iter().sum::<f32>() / openers.len() as f32),
+            overcap_pct:     (power_casts > 0).then(|| capped_casts as f32 / power_casts as f32 * 100.0),
+            active_time_pct: if duration_ms == 0 {
+                0.0
+            } else {
+                self.pulls.iter().map(|p| p.active_time_pct * p.duration_ms as f32).sum::<f32>() / duration_ms as f32
+            },
+            cooldowns: cds
+                .into_iter()
+                .map(|(spell_id, (spell_name, uses, cadences))| CdCadence {
+                    spell_id,
+                    spell_name,
+                    uses,
+                    cadence_pct: (!cadences.is_empty())
+                        .then(|| cadences.iter().sum::<f32>() / cadences.len() as f32),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Practice session summary — debrief payload and `practice_sessions.report`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PracticeReport {
+    pub pulls:           u32,
+    pub duration_ms:     u64,
+    /// Mean pull score, 0–100.
+    pub score:           f32,
+    pub best_score:      f32,
+    pub last_score:      f32,
+    pub opener_pct:      Option<f32>,
+    pub overcap_pct:     Option<f32>,
+    /// Duration-weighted across pulls.
+    pub active_time_pct: f32,
+    pub cooldowns:       Vec<CdCadence>,
+}
+
+impl PracticeReport {
+    /// "Practice: 82/100 over 3 pulls — opener 75%, overcap 4%, active 91%".
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(p) = self.opener_pct {
+            parts.push(format!("opener {:.0}%", p));
+        }
+        if let Some(p) = self.overcap_pct {
+            parts.push(format!("overcap {:.0}%", p));
+        }
+        parts.push(format!("active {:.0}%", self.active_time_pct));
+        format!(
+            "Practice: {:.0}/100 over {} pull{} — {}",
+            self.score,
+            self.pulls,
+            if self.pulls == 1 { "" } else { "s" },
+            parts.join(", "),
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tauri command
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Serialize)]
+pub struct StoredPracticeSession {
+    pub id:         i64,
+    pub started_at: i64,
+    pub ended_at:   i64,
+    pub spec:       String,
+    pub report:     PracticeReport,
+}
+
+/// Most recent practice sessions, newest first.
+#[tauri::command]
+pub async fn get_practice_reports(app: AppHandle) -> Result<Vec<StoredPracticeSession>, String> {
+    let db_path = crate::sessions_db_path(&app)?;
+    tauri::async_runtime::spawn_blocking(move || -> anyhow::Result<Vec<StoredPracticeSession>> {
+        let conn = rusqlite::Connection::open_with_flags(&db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let mut stmt = conn.prepare(
+            "SELECT id, started_at, ended_at, spec, report FROM practice_sessions
+             ORDER BY started_at DESC LIMIT ?1",
+        )?;
+        let rows = stmt.query_map([REPORT_LIMIT], |row| {
+            let report: String = row.get(4)?;
+            Ok(StoredPracticeSession {
+                id:         row.get(0)?,
+                started_at: row.get(1)?,
+                ended_at:   row.get(2)?,
+                spec:       row.get(3)?,
+                report:     serde_json::from_str(&report).unwrap_or_default(),
+            })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    })
+    .await
+    .map_err(|e| format!("Task error: {}", e))?
+    .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUMMY: &str = "Creature-0-3767-2552-1234-225984-0000123456";
+    const TRASH: &str = "Creature-0-3767-2552-1234-209090-0000123456";
+
+    #[test]
+    fn detects_dummies_by_id_and_name() {
+        assert!(is_training_dummy(DUMMY, ""));
+        assert!(is_training_dummy(TRASH, "Cleave Training Dummy"));
+        assert!(!is_training_dummy(TRASH, "Ravenous Drake"));
+
+        let mut t = PracticeTracker::default();
+        t.record_enemy("Player-1-A", "Someone");
+        assert!(!t.is_practice());
+        t.record_enemy(DUMMY, "Training Dummy");
+        assert!(t.is_practice());
+        t.record_enemy(TRASH, "Ravenous Drake");
+        assert!(!t.is_practice());
+    }
+
+    #[test]
+    fn opener_order_and_window() {
+        let opener = [1, 2, 3, 4];
+        let casts = [(0, 1), (1_000, 9), (2_000, 3), (3_000, 2), (4_000, 3), (20_000, 4)];
+        // 1, 2, 3 in order; 4 only after the window.
+        assert_eq!(opener_adherence(&casts, 0, &opener, true), Some(75.0));
+        assert_eq!(opener_adherence(&casts, 0, &opener, false), Some(75.0));
+        assert_eq!(opener_adherence(&[(0, 3), (1_000, 1)], 0, &[1, 3], true), Some(50.0));
+        assert_eq!(opener_adherence(&casts, 0, &[], true), None);
+    }
+
+    #[test]
+    fn cadence_and_overcap() {
+        assert_eq!(cadence(&[0, 120_000]), None);
+        assert_eq!(cadence(&[0, 120_000, 240_000]), Some(100.0));
+        // Gaps 120s and 180s: 120 / 150.
+        assert_eq!(cadence(&[0, 120_000, 300_000]), Some(80.0));
+
+        let power = |power_type, current| Some(Power { power_type, current, max: 100 });
+        let mut t = PracticeTracker::default();
+        t.record_cast(0, 1, power(1, 100));
+        t.record_cast(1_000, 1, power(1, 40));
+        t.record_cast(2_000, 1, power(0, 100));   // mana: not counted
+        t.record_cast(3_000, 1, None);
+        let s = t.score(0, 10_000, &[], &[], 90.0);
+        assert_eq!(s.casts, 4);
+        assert_eq!(s.overcap_pct(), Some(50.0));
+        assert_eq!(s.score, 70.0);   // mean of active 90 and 100 - 50
+    }
+
+    #[test]
+    fn session_report_aggregates_pulls() {
+        let mut t = PracticeTracker::default();
+        for ts in [0, 60_000, 120_000] {
+            t.record_cast(ts, 7, None);
+        }
+        let mut session = PracticeSession::new(0, "Fury".to_owned());
+        session.pulls.push(t.score(0, 130_000, &[7], &[7], 80.0));
+        session.pulls.push(t.score(0, 70_000, &[8], &[], 50.0));
+
+        let r = session.report();
+        assert_eq!(r.pulls, 2);
+        assert_eq!(r.opener_pct, Some(50.0));   // 100 and 0
+        assert_eq!(r.overcap_pct, None);
+        assert!((r.active_time_pct - 69.5).abs() < 0.01);
+        assert_eq!(r.cooldowns.len(), 1);
+        assert_eq!(r.cooldowns[0].uses, 3);
+        assert_eq!(r.cooldowns[0].cadence_pct, Some(100.0));
+        assert!(r.summary().starts_with("Practice: "));
+        assert!(r.summary().contains("over 2 pulls"));
+    }
+}
@@ -0,0 +1,305 @@
+/// Community strategy presets — shared per-encounter coaching setups.
+///
+/// A curated GitHub repository publishes `index.json` listing the presets and
+/// one TOML file per preset:
+///
+///   [preset]
+///   id          = "ansurek-mythic-kicks"
+///   name        = "Ansurek — Mythic kick rotation"
+///   encounter   = "Queen Ansurek"
+///   author      = "…"
+///   description = "…"
+///
+///   [coaching]                    # → config::EncounterSettings
+///   intensity        = 4
+///   disabled_rules   = ["gcd_gap"]
+///   kick_assignments = [439863]
+///
+///   [[panel_positions]]           # optional, per overlay panel
+///   id = "now_feed"
+///   x = 20
+///   y = 70
+///   visible = true
+///
+/// `list_strategy_presets` fetches the index, `download_strategy_preset`
+/// validates a preset and stores it under `<app config>/presets/<id>.toml`,
+/// and `apply_strategy_preset` writes it into `AppConfig.encounter_settings`
+/// (and moves the overlay panels it positions) in one step.
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    config::{self, AppConfig, EncounterSettings, PanelPosition},
+    rules,
+};
+
+/// Root of the curated preset repository (raw file access).
+const REPO_RAW_URL: &str = "https://raw.githubusercontent.com/MFredin/CombatCoaching-presets/main";
+/// Downloaded presets, inside the app config dir.
+const PRESET_DIR: &str = "presets";
+
+/// One entry of the repository's `index.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresetListing {
+    pub id:          String,
+    pub name:        String,
+    pub encounter:   String,
+    #[serde(default)]
+    pub author:      String,
+    #[serde(default)]
+    pub description: String,
+    /// Path of the preset TOML relative to the repository root.
+    pub file:        String,
+    /// Already downloaded to this machine (filled in locally).
+    #[serde(default, skip_deserializing)]
+    pub installed:   bool,
+}
+
+#[derive(Deserialize)]
+struct Index {
+    presets: Vec<PresetListing>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresetMeta {
+    pub id:          String,
+    pub name:        String,
+    pub encounter:   String,
+    #[serde(default)]
+    pub author:      String,
+    #[serde(default)]
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Preset {
+    pub preset:          PresetMeta,
+    #[serde(default)]
+    pub coaching:        EncounterSettings,
+    #[serde(default)]
+    pub panel_positions: Vec<PanelPosition>,
+}
+
+pub fn parse_index(raw: &str) -> Result<Vec<PresetListing>> {
+    let index: Index = serde_json::from_str(raw)
+        .map_err(|e| anyhow::anyhow!("Preset index parse error: {}", e))?;
+    Ok(index.presets)
+}
+
+/// Parse and validate a preset file.
+pub fn parse_preset(raw: &str) -> Result<Preset> {
+    let preset: Preset = toml::from_str(raw)
+        .map_err(|e| anyhow::anyhow!("Preset parse error: {}", e))?;
+    validate(&preset)?;
+    Ok(preset)
+}
+
+/// Ids become file names, so only `[a-z0-9_-]` is allowed.
+fn valid_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+}
+
+fn validate(p: &Preset) -> Result<()> {
+    if !valid_id(&p.preset.id) {
+        anyhow::bail!("Preset id '{}' must be lowercase letters, digits, '-' or '_'", p.preset.id);
+    }
+    if p.preset.encounter.trim().is_empty() {
+        anyhow::bail!("Preset '{}' names no encounter", p.preset.id);
+    }
+    if let Some(i) = p.coaching.intensity.filter(|i| !(1..=5).contains(i)) {
+        anyhow::bail!("Preset '{}': intensity {} is outside 1–5", p.preset.id, i);
+    }
+    if let Some(rule) = p.coaching.disabled_rules.iter()
+        .find(|r| !rules::RULES.iter().any(|(name, _)| name == r))
+    {
+        anyhow::bail!("Preset '{}': unknown rule '{}'", p.preset.id, rule);
+    }
+    let panels = config::default_panel_positions();
+    if let Some(panel) = p.panel_positions.iter().find(|pp| !panels.iter().any(|d| d.id == pp.id)) {
+        anyhow::bail!("Preset '{}': unknown overlay panel '{}'", p.preset.id, panel.id);
+    }
+    Ok(())
+}
+
+/// Write the preset's settings for its encounter into `cfg`, replacing any
+/// earlier ones, and move the overlay panels it positions.
+pub fn apply(cfg: &mut AppConfig, preset: &Preset) {
+    let settings = EncounterSettings { preset_id: preset.preset.id.clone(), ..preset.coaching.clone() };
+    cfg.encounter_settings.insert(preset.preset.encounter.clone(), settings);
+    for panel in &preset.panel_positions {
+        match cfg.panel_positions.iter_mut().find(|p| p.id == panel.id) {
+            Some(existing) => *existing = panel.clone(),
+            None           => cfg.panel_positions.push(panel.clone()),
+        }
+    }
+}
+
+fn preset_path(dir: &Path, id: &str) -> PathBuf {
+    dir.join(format!("{}.toml", id))
+}
+
+/// Downloaded presets in `dir`; unreadable files are skipped.
+fn load_local(dir: &Path) -> Vec<Preset> {
+    let Ok(entries) = std::fs::read_dir(dir) else { return Vec::new() };
+    let mut presets: Vec<Preset> = entries
+        .flatten()
+        .filter(|e| e.path().extension().is_some_and(|x| x == "toml"))
+        .filter_map(|e| {
+            let raw = std::fs::read_to_string(e.path()).ok()?;
+            parse_preset(&raw)
+                .map_err(|err| tracing::warn!("Presets: skipping {:?}: {}", e.path(), err))
+                .ok()
+        })
+        .collect();
+    presets.sort_by(|a, b| (&a.preset.encounter, &a.preset.name).cmp(&(&b.preset.encounter, &b.preset.name)));
+    presets
+}
+
+fn fetch(url: &str) -> Result<String> {
+    Ok(ureq::get(url).call()?.into_string()?)
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+fn preset_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app.path().app_config_dir().map_err(|e| e.to_string())?.join(PRESET_DIR))
+}
+
+/// Presets published in the curated repository, marked when downloaded.
+#[tauri::command]
+pub async fn list_strategy_presets(app: AppHandle) -> Result<Vec<PresetListing>, String> {
+    let dir = preset_dir(&app)?;
+    tauri::async_runtime::spawn_blocking(move || -> Result<Vec<PresetListing>> {
+        let mut listings = parse_index(&fetch(&format!("{}/index.json", REPO_RAW_URL))?)?;
+        for l in &mut listings {
+            l.installed = preset_path(&dir, &l.id).exists();
+        }
+        Ok(listings)
+    })
+    .await
+    .map_err(|e| format!("Task error: {}", e))?
+    .map_err(|e| format!("Could not load strategy presets: {}", e))
+}
+
+/// Download, validate and store the preset `id` from the repository.
+#[tauri::command]
+pub async fn download_strategy_preset(app: AppHandle, id: String) -> Result<Preset, String> {
+    let dir = preset_dir(&app)?;
+    let preset = tauri::async_runtime::spawn_blocking(move || -> Result<Preset> {
+        let listing = parse_index(&fetch(&format!("{}/index.json", REPO_RAW_URL))?)?
+            .into_iter()
+            .find(|l| l.id == id)
+            .ok_or_else(|| anyhow::anyhow!("no preset '{}' in the repository", id))?;
+        if listing.file.contains("..") {
+            anyhow::bail!("invalid preset path '{}'", listing.file);
+        }
+        let raw = fetch(&format!("{}/{}", REPO_RAW_URL, listing.file.trim_start_matches('/')))?;
+        let preset = parse_preset(&raw)?;
+        if preset.preset.id != id {
+            anyhow::bail!("preset file declares id '{}', expected '{}'", preset.preset.id, id);
+        }
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(preset_path(&dir, &id), raw)?;
+        Ok(preset)
+    })
+    .await
+    .map_err(|e| format!("Task error: {}", e))?
+    .map_err(|e| format!("Preset download failed: {}", e))?;
+    tracing::info!("Strategy preset '{}' downloaded for {}", preset.preset.id, preset.preset.encounter);
+    Ok(preset)
+}
+
+/// Presets downloaded to this machine.
+#[tauri::command]
+pub fn list_local_presets(app: AppHandle) -> Result<Vec<Preset>, String> {
+    Ok(load_local(&preset_dir(&app)?))
+}
+
+/// Apply a downloaded preset to its encounter.  Returns the updated config.
+#[tauri::command]
+pub fn apply_strategy_preset(app: AppHandle, id: String) -> Result<AppConfig, String> {
+    if !valid_id(&id) {
+        return Err(format!("Invalid preset id '{}'", id));
+    }
+    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let raw = std::fs::read_to_string(preset_path(&config_dir.join(PRESET_DIR), &id))
+        .map_err(|_| format!("Preset '{}' is not downloaded", id))?;
+    let preset = parse_preset(&raw).map_err(|e| e.to_string())?;
+
+    let mut cfg = config::load_or_default(&config_dir).map_err(|e| e.to_string())?;
+    apply(&mut cfg, &preset);
+    config::save(&cfg, &config_dir).map_err(|e| e.to_string())?;
+    crate::profile::write_sync_copy(&cfg, &config_dir);
+    crate::push_config_update(&app, cfg.clone());
+    tracing::info!("Strategy preset '{}' applied to {}", id, preset.preset.encounter);
+    Ok(cfg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRESET: &str = r#"
+        [preset]
+        id        = "ansurek-kicks"
+        name      = "Ansurek kicks"
+        encounter = "Queen Ansurek"
+
+        [coaching]
+        intensity        = 4
+        disabled_rules   = ["gcd_gap"]
+        kick_assignments = [439863]
+
+        [[panel_positions]]
+        id      = "now_feed"
+        x       = 40
+        y       = 90
+        visible = true
+    "#;
+
+    #[test]
+    fn parses_index_and_preset() {
+        let index = parse_index(r#"{ "presets": [
+            { "id": "ansurek-kicks", "name": "Ansurek kicks", "encounter": "Queen Ansurek",
+              "file": "presets/ansurek-kicks.toml" }
+        ] }"#).unwrap();
+        assert_eq!(index.len(), 1);
+        assert!(!index[0].installed);
+
+        let preset = parse_preset(PRESET).unwrap();
+        assert_eq!(preset.coaching.intensity, Some(4));
+        assert_eq!(preset.coaching.kick_assignments, vec![439863]);
+        assert_eq!(preset.panel_positions[0].scale, 1.0);
+    }
+
+    #[test]
+    fn rejects_invalid_presets() {
+        let bad_id = PRESET.replace("ansurek-kicks", "../evil");
+        assert!(parse_preset(&bad_id).is_err());
+        let bad_rule = PRESET.replace("\"gcd_gap\"", "\"no_such_rule\"");
+        assert!(parse_preset(&bad_rule).unwrap_err().to_string().contains("no_such_rule"));
+        let bad_intensity = PRESET.replace("intensity        = 4", "intensity = 9");
+        assert!(parse_preset(&bad_intensity).is_err());
+        let bad_panel = PRESET.replace("\"now_feed\"", "\"minimap\"");
+        assert!(parse_preset(&bad_panel).is_err());
+    }
+
+    #[test]
+    fn apply_sets_encounter_settings_and_layout() {
+        let preset = parse_preset(PRESET).unwrap();
+        let mut cfg = AppConfig::default();
+        apply(&mut cfg, &preset);
+
+        let settings = &cfg.encounter_settings["Queen Ansurek"];
+        assert_eq!(settings.preset_id, "ansurek-kicks");
+        assert_eq!(settings.disabled_rules, vec!["gcd_gap".to_owned()]);
+        assert_eq!(cfg.panel_positions.len(), config::default_panel_positions().len());   // other panels kept
+        let feed = cfg.panel_positions.iter().find(|p| p.id == "now_feed").unwrap();
+        assert_eq!((feed.x, feed.y), (40, 90));
+        assert_eq!(cfg.intensity, 3);   // global intensity untouched
+    }
+}
@@ -0,0 +1,327 @@
+/// Portable app profile — export/import and cloud-folder sync.
+///
+/// A profile is the user's settings (coaching options, overlay layout,
+/// hotkeys, audio cues, spec selection) wrapped in a versioned TOML file.
+/// Session data (SQLite) is never included.  Machine-local paths (the WoW
+/// Logs directory, addon SavedVariables, data/log/spectate directories, the
+/// countdown voice pack folder and the sync folder itself), per-machine
+/// bookkeeping (last seen app version) and credentials (the Warcraft Logs API
+/// client) are stripped on export and preserved from the local config on
+/// import, so a profile moves cleanly between a desktop and a laptop with
+/// different install locations and never carries a secret into a shared or
+/// synced file.
+///
+/// The user's rule files — custom rules and rule scripts in
+/// `<app config>/rules` (rules/custom.rs, rules/script.rs) — travel with the
+/// settings; importing writes them back next to the local ones.  Goals are
+/// not part of the app yet, so there is nothing of them to carry.
+///
+/// Sync: when `AppConfig.profile_sync_dir` points at a user-provided cloud
+/// folder (Dropbox, OneDrive, …), every save writes `SYNC_FILE_NAME` there
+/// and a watcher thread polls the file.  A copy that changes while the app
+/// runs is imported when it was exported after the local config was last
+/// saved; the copy found at startup is left alone, so an older file never
+/// reverts local changes.
+use crate::{
+    clock::unix_now_ms,
+    config::{self, AppConfig},
+    rules::custom::RULES_DIR,
+};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Bumped when the profile layout changes incompatibly.
+pub const PROFILE_VERSION: u32 = 1;
+/// File name used inside the sync folder.
+pub const SYNC_FILE_NAME: &str = "combatledger-profile.toml";
+/// How often the watcher checks the sync folder.
+const SYNC_POLL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProfileFile {
+    pub version:     u32,
+    /// Unix-epoch ms when the profile was written.
+    pub exported_at: u64,
+    pub config:      AppConfig,
+    /// User rule files by file name (`*.toml` rules, `*.rhai` scripts).
+    #[serde(default)]
+    pub rules:       BTreeMap<String, String>,
+}
+
+/// Rule files synced with the settings.
+fn is_rule_file(name: &str) -> bool {
+    !name.contains(['/', '\\']) && (name.ends_with(".toml") || name.ends_with(".rhai"))
+}
+
+/// The user rule files in `config_dir`.
+fn read_rules(config_dir: &Path) -> BTreeMap<String, String> {
+    let Ok(entries) = std::fs::read_dir(config_dir.join(RULES_DIR)) else { return BTreeMap::new() };
+    entries.flatten()
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().into_owned();
+            if !is_rule_file(&name) {
+                return None;
+            }
+            Some((name, std::fs::read_to_string(e.path()).ok()?))
+        })
+        .collect()
+}
+
+/// Write a profile's rule files into `config_dir`, replacing same-named ones.
+/// Local rule files the profile does not have are kept.
+pub fn install_rules(rules: &BTreeMap<String, String>, config_dir: &Path) -> Result<()> {
+    if rules.is_empty() {
+        return Ok(());
+    }
+    let dir = config_dir.join(RULES_DIR);
+    std::fs::create_dir_all(&dir)?;
+    for (name, raw) in rules.iter().filter(|(name, _)| is_rule_file(name)) {
+        std::fs::write(dir.join(name), raw)?;
+    }
+    Ok(())
+}
+
+/// Copy of `cfg` with machine-local fields cleared.
+fn portable(cfg: &AppConfig) -> AppConfig {
+    let mut out = cfg.clone();
+    out.version          = config::CONFIG_VERSION;
+    out.wow_log_path     = PathBuf::new();
+    out.addon_sv_path    = PathBuf::new();
+    out.profile_sync_dir = PathBuf::new();
+    out.spectate_dir     = PathBuf::new();
+    out.addon_request_id = 0;
+    out.data_dir         = PathBuf::new();
+    out.log_dir          = PathBuf::new();
+    out.last_seen_version = String::new();
+    out.addon_outdated_notice = String::new();
+    out.stale_data_notice = String::new();
+    out.wcl_client_id     = String::new();
+    out.wcl_client_secret = String::new();
+    out.countdown.voice_pack = PathBuf::new();
+    out
+}
+
+/// Imported settings with this machine's local paths kept.
+pub fn merge_into(local: &AppConfig, imported: AppConfig) -> AppConfig {
+    AppConfig {
+        wow_log_path:     local.wow_log_path.clone(),
+        addon_sv_path:    local.addon_sv_path.clone(),
+        profile_sync_dir: local.profile_sync_dir.clone(),
+        spectate_dir:     local.spectate_dir.clone(),
+        addon_request_id: local.addon_request_id,
+        data_dir:         local.data_dir.clone(),
+        log_dir:          local.log_dir.clone(),
+        last_seen_version: local.last_seen_version.clone(),
+        addon_outdated_notice: local.addon_outdated_notice.clone(),
+        stale_data_notice: local.stale_data_notice.clone(),
+        wcl_client_id:    local.wcl_client_id.clone(),
+        wcl_client_secret: local.wcl_client_secret.clone(),
+        countdown: config::CountdownConfig {
+            voice_pack: local.countdown.voice_pack.clone(),
+            ..imported.countdown
+        },
+        ..imported
+    }
+}
+
+/// Write the portable profile for `cfg`, with the rule files of
+/// `config_dir`, to `path`.
+pub fn write(cfg: &AppConfig, config_dir: &Path, path: &Path) -> Result<()> {
+    let file = ProfileFile {
+        version:     PROFILE_VERSION,
+        exported_at: unix_now_ms(),
+        config:      portable(cfg),
+        rules:       read_rules(config_dir),
+    };
+    let raw = toml::to_string_pretty(&file)
+        .map_err(|e| anyhow::anyhow!("Profile serialize error: {}", e))?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, raw)?;
+    Ok(())
+}
+
+/// Read a profile file, rejecting versions newer than this build understands.
+/// The embedded config is upgraded like `config.toml` (`config::migrate`).
+pub fn read(path: &Path) -> Result<ProfileFile> {
+    let raw = std::fs::read_to_string(path)?;
+    let mut table: toml::Table = toml::from_str(&raw)
+        .map_err(|e| anyhow::anyhow!("Profile parse error: {}", e))?;
+    if let Some(toml::Value::Table(cfg)) = table.get_mut("config") {
+        config::migrate(cfg);
+    }
+    let file: ProfileFile = table.try_into()
+        .map_err(|e| anyhow::anyhow!("Profile parse error: {}", e))?;
+    if file.version > PROFILE_VERSION {
+        anyhow::bail!(
+            "Profile version {} is newer than supported ({}); update the app first",
+            file.version, PROFILE_VERSION
+        );
+    }
+    Ok(file)
+}
+
+/// Write the sync copy if a sync folder is configured (called after every save).
+pub fn write_sync_copy(cfg: &AppConfig, config_dir: &Path) {
+    if cfg.profile_sync_dir.as_os_str().is_empty() {
+        return;
+    }
+    let path = cfg.profile_sync_dir.join(SYNC_FILE_NAME);
+    if let Err(e) = write(cfg, config_dir, &path) {
+        tracing::warn!("Profile sync: write to {:?} failed: {}", path, e);
+    }
+}
+
+/// Poll the sync folder and import profiles written by another machine.
+///
+/// Runs forever on its own thread.  `on_import` receives the merged config
+/// after it and the rule files have been saved locally (used to push them to
+/// the running engine).
+pub fn run_sync_watcher(config_dir: PathBuf, on_import: impl Fn(AppConfig)) {
+    // The copy already there is not news: only changes from now on are.
+    let mut last_seen = config::load_or_default(&config_dir).ok().and_then(|c| sync_file_mtime(&c));
+    loop {
+        std::thread::sleep(SYNC_POLL);
+
+        let Ok(local) = config::load_or_default(&config_dir) else { continue };
+        if local.profile_sync_dir.as_os_str().is_empty() {
+            last_seen = None;
+            continue;
+        }
+        let path = local.profile_sync_dir.join(SYNC_FILE_NAME);
+        let Some(mtime) = sync_file_mtime(&local) else { continue };
+        if last_seen == Some(mtime) {
+            continue;
+        }
+        last_seen = Some(mtime);
+
+        let file = match read(&path) {
+            Ok(f)  => f,
+            Err(e) => { tracing::warn!("Profile sync: {}", e); continue; }
+        };
+        // Written before our last save: importing it would revert local changes.
+        if file.exported_at <= local_saved_ms(&config_dir) {
+            continue;
+        }
+        // Our own write comes back unchanged — nothing to do.
+        let merged = merge_into(&local, file.config);
+        if same_settings(&merged, &local) && file.rules == read_rules(&config_dir) {
+            continue;
+        }
+        let saved = install_rules(&file.rules, &config_dir).and_then(|()| config::save(&merged, &config_dir));
+        match saved {
+            Ok(()) => {
+                tracing::info!("Profile sync: imported settings from {:?}", path);
+                on_import(merged);
+            }
+            Err(e) => tracing::warn!("Profile sync: save failed: {}", e),
+        }
+    }
+}
+
+fn sync_file_mtime(cfg: &AppConfig) -> Option<SystemTime> {
+    if cfg.profile_sync_dir.as_os_str().is_empty() {
+        return None;
+    }
+    std::fs::metadata(cfg.profile_sync_dir.join(SYNC_FILE_NAME)).and_then(|m| m.modified()).ok()
+}
+
+/// When config.toml was last saved on this machine (Unix-epoch ms, 0 when
+/// there is none).
+fn local_saved_ms(config_dir: &Path) -> u64 {
+    std::fs::metadata(config_dir.join("config.toml"))
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+fn same_settings(a: &AppConfig, b: &AppConfig) -> bool {
+    toml::to_string(a).ok() == toml::to_string(b).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn round_trip_strips_and_keeps_local_paths() {
+        let dir = tempdir().unwrap();
+        let mut desktop = AppConfig {
+            wow_log_path: PathBuf::from("D:/Games/WoW/_retail_/Logs"),
+            intensity:    5,
+            ..AppConfig::default()
+        };
+        desktop.countdown.voice_pack = PathBuf::from("D:/Sounds/Countdown");
+        desktop.countdown.from_s     = 5;
+
+        let path = dir.path().join(SYNC_FILE_NAME);
+        write(&desktop, dir.path(), &path).unwrap();
+        let file = read(&path).unwrap();
+        assert_eq!(file.version, PROFILE_VERSION);
+        assert!(file.config.wow_log_path.as_os_str().is_empty());
+        assert!(file.config.countdown.voice_pack.as_os_str().is_empty());
+
+        let mut laptop = AppConfig {
+            wow_log_path: PathBuf::from("C:/WoW/_retail_/Logs"),
+            ..AppConfig::default()
+        };
+        laptop.countdown.voice_pack = PathBuf::from("C:/Sounds/Countdown");
+        let merged = merge_into(&laptop, file.config);
+        assert_eq!(merged.intensity,    5);
+        assert_eq!(merged.wow_log_path, PathBuf::from("C:/WoW/_retail_/Logs"));
+        assert_eq!(merged.countdown.from_s,     5);
+        assert_eq!(merged.countdown.voice_pack, PathBuf::from("C:/Sounds/Countdown"));
+    }
+
+    #[test]
+    fn export_leaves_out_the_warcraft_logs_secret() {
+        let dir = tempdir().unwrap();
+        let local = AppConfig {
+            wcl_client_id:     "client-id".to_owned(),
+            wcl_client_secret: "s3cr3t-value".to_owned(),
+            ..AppConfig::default()
+        };
+        let path = dir.path().join(SYNC_FILE_NAME);
+        write(&local, dir.path(), &path).unwrap();
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("s3cr3t-value") && !raw.contains("client-id"));
+
+        // Importing keeps this machine's credentials.
+        let merged = merge_into(&local, read(&path).unwrap().config);
+        assert_eq!(merged.wcl_client_secret, "s3cr3t-value");
+    }
+
+    #[test]
+    fn rule_files_travel_with_the_profile() {
+        let desktop = tempdir().unwrap();
+        std::fs::create_dir_all(desktop.path().join(RULES_DIR)).unwrap();
+        std::fs::write(desktop.path().join(RULES_DIR).join("eggs.toml"), "[[rule]]").unwrap();
+        std::fs::write(desktop.path().join(RULES_DIR).join("notes.txt"), "not a rule").unwrap();
+        let path = desktop.path().join(SYNC_FILE_NAME);
+        write(&AppConfig::default(), desktop.path(), &path).unwrap();
+
+        let file = read(&path).unwrap();
+        assert_eq!(file.rules.keys().collect::<Vec<_>>(), vec!["eggs.toml"]);
+
+        let laptop = tempdir().unwrap();
+        install_rules(&file.rules, laptop.path()).unwrap();
+        assert_eq!(std::fs::read_to_string(laptop.path().join(RULES_DIR).join("eggs.toml")).unwrap(), "[[rule]]");
+    }
+
+    #[test]
+    fn rejects_newer_version() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("p.toml");
+        write(&AppConfig::default(), dir.path(), &path).unwrap();
+        let raw = std::fs::read_to_string(&path).unwrap()
+            .replace(&format!("version = {}", PROFILE_VERSION), "version = 99");
+        std::fs::write(&path, raw).unwrap();
+        assert!(read(&path).is_err());
+    }
+}
@@ -0,0 +1,263 @@
+/// Pull timeline export as a PNG, for sharing in Discord without the app.
+///
+/// `render_pull_image` reads a pull's archive (`db::load_pull_archive`) and
+/// draws, over the pull's duration:
+///   - damage taken by the coached player per second (3s rolling average)
+///   - advice markers — vertical lines coloured by severity
+///   - major cooldown casts — markers along the top, labelled with the spell
+///   - the session's note and journal entries (journal.rs), as a footer
+///
+/// The image is written to `<app cache>/pull_images/pull-<id>.png` (replaced
+/// on every export) and the path is returned.
+use anyhow::Result;
+use plotters::prelude::*;
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+
+use crate::{config, db, journal, parser::LogEvent, specs};
+
+const WIDTH:  u32 = 1200;
+const HEIGHT: u32 = 420;
+/// Height of the session-notes footer, when there is one.
+const FOOTER: u32 = 28;
+/// Footer text beyond this many characters is cut with an ellipsis.
+const FOOTER_CHARS: usize = 160;
+/// Damage curve smoothing window, in one-second buckets.
+const SMOOTH_BUCKETS: usize = 3;
+
+const BG:      RGBColor = RGBColor(18, 20, 26);
+const TEXT:    RGBColor = RGBColor(220, 222, 228);
+const GRID:    RGBColor = RGBColor(48, 52, 62);
+const DAMAGE:  RGBColor = RGBColor(232, 96, 72);
+const CD:      RGBColor = RGBColor(96, 168, 255);
+const GOOD:    RGBColor = RGBColor(80, 200, 120);
+const WARN:    RGBColor = RGBColor(240, 190, 60);
+const BAD:     RGBColor = RGBColor(230, 70, 70);
+
+/// Everything drawn on the image, in seconds since pull start.
+#[derive(Debug, Default)]
+struct Timeline {
+    duration_s: f64,
+    /// (second, damage taken per second), smoothed.
+    damage:     Vec<(f64, f64)>,
+    /// (second, spell name) of major cooldown casts.
+    cooldowns:  Vec<(f64, String)>,
+    /// (second, severity) of advice that fired live.
+    advice:     Vec<(f64, String)>,
+}
+
+/// Build the timeline from archived events.  `start_ms` is the pull start
+/// (log time), `player_guid` the coached player, `major_cds` the spec's CDs.
+fn build_timeline(
+    events:      &[LogEvent],
+    advice:      &[db::StoredAdvice],
+    start_ms:    u64,
+    player_guid: &str,
+    major_cds:   &[u32],
+) -> Timeline {
+    let end_ms = events.iter().map(LogEvent::timestamp_ms).max().unwrap_or(start_ms);
+    let secs = |ms: u64| ms.saturating_sub(start_ms) as f64 / 1_000.0;
+
+    let buckets = (end_ms.saturating_sub(start_ms) / 1_000) as usize + 1;
+    let mut per_second = vec![0u64; buckets];
+    let mut cooldowns  = Vec::new();
+    for event in events {
+        let ts = event.timestamp_ms();
+        match event {
+            LogEvent::SpellDamage { dest_guid, amount, .. }
+            | LogEvent::SwingDamage { dest_guid, amount, .. } if dest_guid == player_guid => {
+                let i = (ts.saturating_sub(start_ms) / 1_000) as usize;
+                per_second[i.min(buckets - 1)] += amount;
+            }
+            LogEvent::SpellCastSuccess { source_guid, spell_id, spell_name, .. }
+                if source_guid == player_guid && major_cds.contains(spell_id) =>
+            {
+                cooldowns.push((secs(ts), spell_name.clone()));
+            }
+            _ => {}
+        }
+    }
+
+    let damage = (0..buckets)
+        .map(|i| {
+            let window = &per_second[i.saturating_sub(SMOOTH_BUCKETS - 1)..=i];
+            (i as f64, window.iter().sum::<u64>() as f64 / window.len() as f64)
+        })
+        .collect();
+
+    Timeline {
+        duration_s: secs(end_ms).max(1.0),
+        damage,
+        cooldowns,
+        advice: advice.iter().map(|a| (secs(a.fired_at), a.severity.clone())).collect(),
+    }
+}
+
+/// "1.2M" / "450k" axis labels.
+fn short_amount(v: f64) -> String {
+    if v >= 1_000_000.0 {
+        format!("{:.1}M", v / 1_000_000.0)
+    } else if v >= 1_000.0 {
+        format!("{:.0}k", v / 1_000.0)
+    } else {
+        format!("{:.0}", v)
+    }
+}
+
+fn render(path: &Path, title: &str, footer: &str, t: &Timeline) -> Result<()> {
+    let height = if footer.is_empty() { HEIGHT } else { HEIGHT + FOOTER };
+    let image = BitMapBackend::new(path, (WIDTH, height)).into_drawing_area();
+    image.fill(&BG)?;
+    let (root, notes) = image.split_vertically(HEIGHT);
+    if !footer.is_empty() {
+        let mut text: String = footer.chars().take(FOOTER_CHARS).collect();
+        if footer.chars().count() > FOOTER_CHARS {
+            text.push('…');
+        }
+        notes.draw(&Text::new(text, (16, 4), ("sans-serif", 13).into_font().color(&TEXT.mix(0.8))))?;
+    }
+
+    let peak = t.damage.iter().map(|(_, d)| *d).fold(0.0, f64::max).max(1.0);
+    let top  = peak * 1.2;
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, ("sans-serif", 20).into_font().color(&TEXT))
+        .margin(16)
+        .x_label_area_size(32)
+        .y_label_area_size(56)
+        .build_cartesian_2d(0.0..t.duration_s, 0.0..top)?;
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .light_line_style(TRANSPARENT)
+        .bold_line_style(GRID)
+        .axis_style(GRID)
+        .label_style(("sans-serif", 13).into_font().color(&TEXT))
+        .x_desc("seconds")
+        .y_desc("damage taken / s")
+        .y_label_formatter(&|v| short_amount(*v))
+        .draw()?;
+
+    // Advice first so the curve stays readable on top of the markers.
+    chart.draw_series(t.advice.iter().map(|(x, severity)| {
+        let color = match severity.as_str() {
+            "bad"  => BAD,
+            "warn" => WARN,
+            _      => GOOD,
+        };
+        PathElement::new(vec![(*x, 0.0), (*x, top)], color.mix(0.7).stroke_width(2))
+    }))?;
+
+    chart.draw_series(LineSeries::new(t.damage.iter().copied(), DAMAGE.stroke_width(2)))?;
+
+    let label_font = ("sans-serif", 12).into_font().color(&CD);
+    chart.draw_series(t.cooldowns.iter().map(|(x, name)| {
+        EmptyElement::at((*x, top * 0.94))
+            + TriangleMarker::new((0, 0), 5, CD.filled())
+            + Text::new(name.clone(), (6, -6), label_font.clone())
+    }))?;
+
+    image.present()?;
+    Ok(())
+}
+
+/// Major CDs of the pull's player: the selected spec, else the archived
+/// identity's spec, else the manual list — the engine's precedence.
+fn major_cds(cfg: &config::AppConfig, class: &str, spec: &str) -> Vec<u32> {
+    specs::load_by_key(&cfg.selected_spec)
+        .or_else(|| specs::load_spec(class, spec))
+        .map(|p| p.major_cd_spell_ids)
+        .unwrap_or_else(|| cfg.major_cds.clone())
+}
+
+/// Render the PNG for `pull_id`; returns its path.
+fn export(db_path: &Path, out_dir: &Path, cfg: &config::AppConfig, pull_id: i64) -> Result<String> {
+    let archive = db::load_pull_archive(db_path, pull_id)?;
+
+    let conn = rusqlite::Connection::open_with_flags(db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let (session_id, pull_number, started_at, outcome, encounter): (i64, i64, i64, Option<String>, Option<String>) =
+        conn.query_row(
+            "SELECT session_id, pull_number, started_at, outcome, encounter FROM pulls WHERE id = ?1",
+            [pull_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+        )?;
+    let notes = journal::session_context(&conn, session_id)?;
+
+    let cds = major_cds(cfg, &archive.identity.class, &archive.identity.spec);
+    let timeline = build_timeline(
+        &archive.events, &archive.live, started_at as u64, &archive.identity.guid, &cds,
+    );
+    let title = [
+        Some(format!("Pull {}", pull_number)),
+        encounter.filter(|e| !e.is_empty()),
+        outcome,
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" — ");
+
+    std::fs::create_dir_all(out_dir)?;
+    let path = out_dir.join(format!("pull-{}.png", pull_id));
+    render(&path, &title, &notes, &timeline)?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+/// Tauri command: render a stored pull's timeline to a PNG and return its path.
+#[tauri::command]
+pub async fn render_pull_image(app: AppHandle, pull_id: i64) -> Result<String, String> {
+    let db_path = crate::sessions_db_path(&app)?;
+    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let cfg = config::load_or_default(&config_dir).map_err(|e| e.to_string())?;
+    let out_dir = app.path().app_cache_dir().map_err(|e| e.to_string())?.join("pull_images");
+
+    let path = tauri::async_runtime::spawn_blocking(move || export(&db_path, &out_dir, &cfg, pull_id))
+        .await
+        .map_err(|e| format!("Task error: {}", e))?
+        .map_err(|e| e.to_string())?;
+    tracing::info!("Pull {} timeline image written to {}", pull_id, path);
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAYER: &str = "Player-1-A";
+
+    fn hit(ts: u64, amount: u64) -> LogEvent {
+        LogEvent::SwingDamage {
+            timestamp_ms: ts,
+            source_guid:  "Creature-0-1-2-3-4-5".to_owned(),
+            source_name:  String::new(),
+            source_flags: 0xa48,
+            dest_guid:    PLAYER.to_owned(),
+            dest_name:    String::new(),
+            dest_flags:   0,
+            dest_raid_flags: 0,
+            amount,
+            unit:         None,
+        }
+    }
+
+    #[test]
+    fn buckets_and_smooths_damage() {
+        let events = vec![hit(10_000, 300), hit(10_500, 300), hit(12_200, 900)];
+        let advice = vec![db::StoredAdvice {
+            fired_at:   11_500,
+            rule_key:   "avoidable_repeat".to_owned(),
+            severity:   "bad".to_owned(),
+            message:    String::new(),
+            repeat:     0,
+            escalation: 0,
+        }];
+        let t = build_timeline(&events, &advice, 10_000, PLAYER, &[]);
+
+        assert_eq!(t.damage.len(), 3);
+        assert_eq!(t.damage[0], (0.0, 600.0));
+        assert_eq!(t.damage[1], (1.0, 300.0));   // (600 + 0) / 2
+        assert_eq!(t.damage[2], (2.0, 500.0));   // (600 + 0 + 900) / 3
+        assert_eq!(t.advice, vec![(1.5, "bad".to_owned())]);
+        assert!((t.duration_s - 2.2).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,66 @@
+/// Raid buff bundle — embedded at compile time from `data/raid_buffs.toml`.
+///
+/// Each buff names the class that brings it and the aura spell IDs it puts
+/// on a player.  The engine keeps the coached player's auras
+/// (state::AuraTracker, seeded from COMBATANT_INFO at encounter start) and
+/// the classes in the group; `missing` compares the two for the raid_buffs
+/// rule.
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+
+const RAID_BUFFS_TOML: &str = include_str!("../../data/raid_buffs.toml");
+
+#[derive(Debug, Deserialize)]
+pub struct RaidBuff {
+    pub name:      String,
+    /// Class token as in specs.rs ("MAGE").
+    pub class:     String,
+    pub spell_ids: Vec<u32>,
+}
+
+#[derive(Deserialize)]
+struct TomlFile {
+    buffs: Vec<RaidBuff>,
+}
+
+static BUFFS: Lazy<Vec<RaidBuff>> = Lazy::new(|| match toml::from_str::<TomlFile>(RAID_BUFFS_TOML) {
+    Ok(f)  => f.buffs,
+    Err(e) => {
+        tracing::warn!("Failed to parse raid_buffs.toml: {}", e);
+        Vec::new()
+    }
+});
+
+pub fn all() -> &'static [RaidBuff] {
+    &BUFFS
+}
+
+/// Buffs a class in `classes` brings that none of the `active` auras
+/// provide, in data file order.
+pub fn missing(classes: &HashSet<String>, active: &HashMap<u32, String>) -> Vec<&'static str> {
+    all().iter()
+        .filter(|b| classes.contains(&b.class))
+        .filter(|b| !b.spell_ids.iter().any(|id| active.contains_key(id)))
+        .map(|b| b.name.as_str())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_only_counts_classes_in_the_group() {
+        assert!(all().iter().any(|b| b.name == "Arcane Intellect" && b.spell_ids == [1459]));
+
+        let classes: HashSet<String> = ["MAGE", "PRIEST", "EVOKER"].iter().map(|c| c.to_string()).collect();
+        let mut active = HashMap::new();
+        active.insert(1459, "Arcane Intellect".to_owned());
+        assert_eq!(missing(&classes, &active), vec!["Power Word: Fortitude", "Blessing of the Bronze"]);
+
+        active.insert(381748, "Blessing of the Bronze".to_owned());
+        active.insert(21562, "Power Word: Fortitude".to_owned());
+        assert!(missing(&classes, &active).is_empty());
+    }
+}
@@ -0,0 +1,165 @@
+/// Pre-pull readiness checklist.
+///
+/// While the player is out of combat every state snapshot carries a
+/// `Readiness` payload, which the overlay shows during the wait before a
+/// pull.  The combat log has no ready-check or pull-timer event, so
+/// "pre-pull" simply means "not in combat".  Everything is derived from what
+/// the log has shown this session:
+///
+///   - consumables — flask/phial, food and augment rune auras on the player
+///     (SPELL_AURA_APPLIED/REMOVED).  A buff applied before logging started
+///     leaves no trace, so "never seen" is `Unknown`, not `Warn`.
+///   - durability — the log carries no durability; player deaths this
+///     session are the proxy (each death costs 10%).
+///   - spec profile — whether cooldown coaching has major CDs to track.
+///   - cooldowns — major CDs used late in the last pull that may still be
+///     recovering when the next one starts.  Listed by name only: a running
+///     "used 40s ago" would change the checklist with every snapshot.
+///   - assignments — whether interrupt assignments (`kick_assignments`,
+///     imported with strategy presets) exist for any encounter.  The log
+///     does not say which boss is next, so this is not per encounter, and
+///     other raid assignments (positions, soaks) are not modelled.
+///
+/// The engine builds the checklist with every out-of-combat snapshot; ipc.rs
+/// emits it only when it changes.
+use crate::{
+    ipc::{Readiness, ReadinessItem, ReadinessStatus},
+    spells,
+    state::CombatState,
+};
+
+/// Deaths this session after which the durability item warns.
+const REPAIR_WARN_DEATHS: u32 = 5;
+/// Major CDs used within this window count as "may still be recovering".
+const RECENT_CD_MS: u64 = 120_000;
+
+/// (key, label, aura-name fragments) for each consumable category.
+const CONSUMABLES: &[(&str, &str, &[&str])] = &[
+    ("flask", "Flask",        &["Flask of", "Phial of"]),
+    ("food",  "Food buff",    &["Well Fed"]),
+    ("rune",  "Augment rune", &["Augment Rune"]),
+];
+
+/// Build the checklist from the current combat state.  `assigned` is the
+/// number of encounters with interrupt assignments in the config.
+pub fn evaluate(
+    state:     &CombatState,
+    major_cds: &[u32],
+    assigned:  usize,
+    now_ms:    u64,
+) -> Readiness {
+    let mut items = Vec::new();
+
+    for (key, label, fragments) in CONSUMABLES {
+        let find = |auras: &std::collections::HashMap<u32, String>| {
+            auras.values().find(|n| fragments.iter().any(|f| n.contains(f))).cloned()
+        };
+        let (status, detail) = if let Some(name) = find(&state.auras.active) {
+            (ReadinessStatus::Ok, name)
+        } else if let Some(name) = find(&state.auras.expired) {
+            (ReadinessStatus::Warn, format!("{} expired", name))
+        } else {
+            (ReadinessStatus::Unknown, "not seen this session".to_owned())
+        };
+        items.push(item(key, label, status, detail));
+    }
+
+    let deaths = state.player_deaths;
+    items.push(if deaths >= REPAIR_WARN_DEATHS {
+        item("durability", "Durability", ReadinessStatus::Warn,
+             format!("{} deaths this session — check repairs", deaths))
+    } else {
+        item("durability", "Durability", ReadinessStatus::Ok,
+             format!("{} deaths this session", deaths))
+    });
+
+    if major_cds.is_empty() {
+        items.push(item("spec", "Spec profile", ReadinessStatus::Warn,
+                        "no major cooldowns known — pick a spec".to_owned()));
+    } else {
+        items.push(item("spec", "Spec profile", ReadinessStatus::Ok,
+                        format!("{} major cooldowns tracked", major_cds.len())));
+
+        let recent: Vec<&str> = major_cds.iter()
+            .filter(|id| {
+                state.cooldowns.last_used_ms(**id)
+                    .is_some_and(|used| now_ms.saturating_sub(used) < RECENT_CD_MS)
+            })
+            .map(|id| spells::name(*id).unwrap_or("spell"))
+            .collect();
+        items.push(if recent.is_empty() {
+            item("cooldowns", "Major cooldowns", ReadinessStatus::Ok,
+                 "none used recently".to_owned())
+        } else {
+            item("cooldowns", "Major cooldowns", ReadinessStatus::Warn,
+                 format!("may be recovering: {}", recent.join(", ")))
+        });
+    }
+
+    items.push(if assigned == 0 {
+        item("assignments", "Assignments", ReadinessStatus::Unknown,
+             "no interrupt assignments imported".to_owned())
+    } else {
+        item("assignments", "Assignments", ReadinessStatus::Ok,
+             format!("interrupt assignments for {} encounter{}", assigned, if assigned == 1 { "" } else { "s" }))
+    });
+
+    let ready = items.iter().all(|i| i.status != ReadinessStatus::Warn);
+    Readiness { items, ready }
+}
+
+fn item(key: &str, label: &str, status: ReadinessStatus, detail: String) -> ReadinessItem {
+    ReadinessItem { key: key.to_owned(), label: label.to_owned(), status, detail }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_of(r: &Readiness, key: &str) -> ReadinessStatus {
+        r.items.iter().find(|i| i.key == key).expect(key).status
+    }
+
+    #[test]
+    fn consumables_track_applied_and_expired_auras() {
+        let mut state = CombatState::new();
+        state.auras.apply(432021, "Flask of Alchemical Chaos");
+        state.auras.apply(462187, "Well Fed");
+        state.auras.remove(462187, "Well Fed");
+
+        let r = evaluate(&state, &[31884], 0, 0);
+        assert_eq!(status_of(&r, "flask"), ReadinessStatus::Ok);
+        assert_eq!(status_of(&r, "food"),  ReadinessStatus::Warn);
+        assert_eq!(status_of(&r, "rune"),  ReadinessStatus::Unknown);
+        assert!(!r.ready);
+    }
+
+    #[test]
+    fn recent_major_cooldown_warns() {
+        let mut state = CombatState::new();
+        state.cooldowns.record_cast(31884, 100_000);
+
+        let r = evaluate(&state, &[31884], 0, 145_000);
+        let cds = r.items.iter().find(|i| i.key == "cooldowns").unwrap();
+        assert_eq!(cds.status, ReadinessStatus::Warn);
+        assert!(cds.detail.contains("Avenging Wrath"), "{}", cds.detail);
+        // Unchanged as the seconds tick by, so it is not re-emitted.
+        assert_eq!(evaluate(&state, &[31884], 0, 150_000), r);
+
+        let later = evaluate(&state, &[31884], 0, 400_000);
+        assert_eq!(status_of(&later, "cooldowns"), ReadinessStatus::Ok);
+        assert!(later.ready);
+    }
+
+    #[test]
+    fn assignments_report_imported_kicks_without_blocking_ready() {
+        let state = CombatState::new();
+        let none = evaluate(&state, &[31884], 0, 0);
+        assert_eq!(status_of(&none, "assignments"), ReadinessStatus::Unknown);
+        assert!(none.ready);
+
+        let two = evaluate(&state, &[31884], 2, 0);
+        let item = two.items.iter().find(|i| i.key == "assignments").unwrap();
+        assert_eq!((item.status, item.detail.as_str()), (ReadinessStatus::Ok, "interrupt assignments for 2 encounters"));
+    }
+}
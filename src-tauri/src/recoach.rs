@@ -0,0 +1,128 @@
+/// On-demand re-coaching of a stored pull with the current rules.
+///
+/// At pull end the engine archives the pull's raw events in `pull_events`.
+/// `recoach_pull` replays them through `engine::replay_pull` with the current
+/// config and rule code, stores the result as a new advice version linked to
+/// the pull (live advice is version 0 of `advice_events`), and returns both
+/// sets plus a per-rule comparison — so tuned rules can be checked against
+/// what fired at the time.
+///
+/// Pulls recorded before archiving existed have no events and are rejected.
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    config,
+    db::{self, DbWriter, StoredAdvice},
+    engine::{self, AdviceEvent},
+};
+
+#[derive(Debug, Serialize)]
+pub struct RecoachResult {
+    pub pull_id:   i64,
+    /// Version the re-coached advice was stored as (1 for the first re-coach).
+    pub version:   u32,
+    pub live:      Vec<StoredAdvice>,
+    pub recoached: Vec<StoredAdvice>,
+    /// Advice count per rule, live vs re-coached.
+    pub by_rule:   Vec<RuleDelta>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RuleDelta {
+    pub rule_key:  String,
+    pub live:      u32,
+    pub recoached: u32,
+}
+
+/// Per-rule advice counts of both sets, sorted by rule key.
+pub fn compare(live: &[StoredAdvice], recoached: &[StoredAdvice]) -> Vec<RuleDelta> {
+    let mut counts: BTreeMap<&str, (u32, u32)> = BTreeMap::new();
+    for a in live {
+        counts.entry(a.rule_key.as_str()).or_default().0 += 1;
+    }
+    for a in recoached {
+        counts.entry(a.rule_key.as_str()).or_default().1 += 1;
+    }
+    counts
+        .into_iter()
+        .map(|(rule_key, (live, recoached))| RuleDelta { rule_key: rule_key.to_owned(), live, recoached })
+        .collect()
+}
+
+pub fn to_stored(a: AdviceEvent) -> StoredAdvice {
+    StoredAdvice {
+        fired_at:   a.timestamp_ms,
+        rule_key:   a.key,
+        severity:   format!("{:?}", a.severity).to_lowercase(),
+        message:    a.message,
+        repeat:     a.repeat,
+        escalation: a.escalation,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tauri command
+// ---------------------------------------------------------------------------
+
+/// Replay a stored pull through the current rules and store the advice as a
+/// new version of the pull.
+#[tauri::command]
+pub async fn recoach_pull(app: AppHandle, pull_id: i64) -> Result<RecoachResult, String> {
+    let db_path = crate::sessions_db_path(&app)?;
+    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let cfg = config::load_or_default(&config_dir).map_err(|e| e.to_string())?;
+    let writer = app.state::<DbWriter>().inner().clone();
+
+    let replay_db = writer.clone();
+    let (live, recoached) = tauri::async_runtime::spawn_blocking(move || -> Result<_> {
+        let archive = db::load_pull_archive(&db_path, pull_id)?;
+        let advice  = engine::replay_pull(&archive.events, archive.identity, cfg, replay_db);
+        Ok((archive.live, advice.into_iter().map(to_stored).collect::<Vec<_>>()))
+    })
+    .await
+    .map_err(|e| format!("Task error: {}", e))?
+    .map_err(|e| e.to_string())?;
+
+    let version = writer
+        .insert_advice_set(pull_id, recoached.clone())
+        .await
+        .map_err(|e| e.to_string())?;
+    tracing::info!(
+        "Re-coached pull {} → version {} ({} advice live, {} now)",
+        pull_id, version, live.len(), recoached.len()
+    );
+
+    Ok(RecoachResult { pull_id, version, by_rule: compare(&live, &recoached), live, recoached })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn advice(rule_key: &str) -> StoredAdvice {
+        StoredAdvice {
+            fired_at:   0,
+            rule_key:   rule_key.to_owned(),
+            severity:   "warn".to_owned(),
+            message:    String::new(),
+            repeat:     0,
+            escalation: 0,
+        }
+    }
+
+    #[test]
+    fn compares_counts_per_rule() {
+        let live      = vec![advice("gcd_gap"), advice("gcd_gap"), advice("interrupt_miss")];
+        let recoached = vec![advice("gcd_gap"), advice("lust_timing")];
+
+        let delta = |k: &str, live, recoached| RuleDelta { rule_key: k.to_owned(), live, recoached };
+        assert_eq!(compare(&live, &recoached), vec![
+            delta("gcd_gap", 2, 1),
+            delta("interrupt_miss", 1, 0),
+            delta("lust_timing", 0, 1),
+        ]);
+    }
+}
@@ -0,0 +1,247 @@
+/// Personal records — the character's best boss kills per encounter and
+/// difficulty, something to chase beyond the single-pull debrief.
+///
+/// Tracked records (`RecordKind`): fastest kill, fewest avoidable hits,
+/// highest kick efficiency and best pull score (`pull_score`).  Only kills
+/// count: a wipe at 0:40 would otherwise hold "fastest kill" and "fewest
+/// avoidable hits" forever.
+///
+/// At every ENCOUNTER_END kill the engine sends the kill's values to the DB
+/// writer (`personal_records` table, keyed by character GUID, encounter,
+/// difficulty and record), which keeps the better value and reports what
+/// changed.  A beaten record fires the personal_record advisory
+/// (rules/personal_record.rs); the first kill only sets the records.
+use serde::Serialize;
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+use crate::{runs, units::NumberFormat};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RecordKind {
+    FastestKill,
+    FewestAvoidable,
+    BestKickEfficiency,
+    BestPullScore,
+}
+
+impl RecordKind {
+    /// Stable key stored in `personal_records.record`.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::FastestKill        => "fastest_kill",
+            Self::FewestAvoidable    => "fewest_avoidable",
+            Self::BestKickEfficiency => "best_kick_efficiency",
+            Self::BestPullScore      => "best_pull_score",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "fastest_kill"         => Some(Self::FastestKill),
+            "fewest_avoidable"     => Some(Self::FewestAvoidable),
+            "best_kick_efficiency" => Some(Self::BestKickEfficiency),
+            "best_pull_score"      => Some(Self::BestPullScore),
+            _                      => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::FastestKill        => "fastest kill",
+            Self::FewestAvoidable    => "fewest avoidable hits",
+            Self::BestKickEfficiency => "best kick efficiency",
+            Self::BestPullScore      => "best pull score",
+        }
+    }
+
+    /// True when `value` improves on `previous`.
+    pub fn beats(self, value: f64, previous: f64) -> bool {
+        match self {
+            Self::FastestKill | Self::FewestAvoidable => value < previous,
+            Self::BestKickEfficiency | Self::BestPullScore => value > previous,
+        }
+    }
+
+    /// A record value as shown in advice ("3:12", "2", "85%").
+    pub fn format(self, value: f64, fmt: &NumberFormat) -> String {
+        match self {
+            Self::FastestKill        => fmt.duration(value as u64),
+            Self::FewestAvoidable    => format!("{:.0}", value),
+            Self::BestKickEfficiency => format!("{:.0}%", value),
+            Self::BestPullScore      => format!("{:.0}", value),
+        }
+    }
+}
+
+/// Pull score, 0–100: active time (60 points), avoidable damage (25, minus 5
+/// per hit) and kick efficiency (15; full marks without kick opportunities).
+pub fn pull_score(active_time_pct: f32, avoidable_hits: u32, interrupt_efficiency: Option<f32>) -> f32 {
+    let active    = active_time_pct.clamp(0.0, 100.0) * 0.60;
+    let avoidable = (25.0 - 5.0 * avoidable_hits as f32).max(0.0);
+    let kicks     = interrupt_efficiency.unwrap_or(100.0).clamp(0.0, 100.0) * 0.15;
+    active + avoidable + kicks
+}
+
+/// A boss kill to check against the records.
+#[derive(Debug, Clone)]
+pub struct Kill {
+    pub player_guid:   String,
+    pub player_name:   String,
+    pub encounter:     String,
+    pub difficulty_id: u32,
+    pub pull_id:       i64,
+    /// Unix ms of the kill.
+    pub killed_at:     u64,
+    /// The kill's value for each record it has one for (no kick efficiency
+    /// without kick opportunities).
+    pub values:        Vec<(RecordKind, f64)>,
+}
+
+impl Kill {
+    /// "Heroic Sikran", or just the encounter when the difficulty is unnamed.
+    pub fn label(&self) -> String {
+        match runs::difficulty_name(self.difficulty_id) {
+            ""   => self.encounter.clone(),
+            diff => format!("{} {}", diff, self.encounter),
+        }
+    }
+}
+
+/// A record the kill set or improved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordUpdate {
+    pub kind:     RecordKind,
+    pub value:    f64,
+    /// The record it beat; None when this is the first value.
+    pub previous: Option<f64>,
+}
+
+/// Records `values` sets or improves, given the `current` ones.
+pub fn improvements(values: &[(RecordKind, f64)], current: &HashMap<RecordKind, f64>) -> Vec<RecordUpdate> {
+    values
+        .iter()
+        .filter_map(|&(kind, value)| match current.get(&kind) {
+            Some(&previous) if !kind.beats(value, previous) => None,
+            previous => Some(RecordUpdate { kind, value, previous: previous.copied() }),
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tauri command
+// ---------------------------------------------------------------------------
+
+/// One row of the records board.
+#[derive(Debug, Serialize)]
+pub struct PersonalRecord {
+    pub player_name:   String,
+    pub encounter:     String,
+    pub difficulty_id: u32,
+    /// Difficulty display name ("Heroic"), "" when unnamed.
+    pub difficulty:    String,
+    /// RecordKind key, e.g. "fastest_kill".
+    pub record:        String,
+    pub value:         f64,
+    /// Pull that set the record (None once the pull is deleted).
+    pub pull_id:       Option<i64>,
+    /// Unix ms of the kill.
+    pub set_at:        u64,
+}
+
+/// Every personal record, grouped by character, encounter and difficulty.
+#[tauri::command]
+pub async fn get_records(app: AppHandle) -> Result<Vec<PersonalRecord>, String> {
+    let db_path = crate::sessions_db_path(&app)?;
+    if !db_path.exists() {
+        return Ok(vec![]);
+    }
+    tauri::async_runtime::spawn_blocking(move || -> anyhow::Result<Vec<PersonalRecord>> {
+        let conn = rusqlite::Connection::open_with_flags(&db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT player_name, {} AS enc, difficulty_id, record, value, pull_id, set_at
+             FROM personal_records
+             ORDER BY player_name, enc, difficulty_id, record",
+            crate::db::canonical_encounter("encounter"),
+        ))?;
+        let rows = stmt.query_map([], |row| {
+            let difficulty_id: u32 = row.get(2)?;
+            Ok(PersonalRecord {
+                player_name:   row.get(0)?,
+                encounter:     row.get(1)?,
+                difficulty_id,
+                difficulty:    runs::difficulty_name(difficulty_id).to_owned(),
+                record:        row.get(3)?,
+                value:         row.get(4)?,
+                pull_id:       row.get(5)?,
+                set_at:        row.get(6)?,
+            })
+        })?;
+        // A renamed boss's old rows land next to the new ones: keep the best.
+        let mut records: Vec<PersonalRecord> = Vec::new();
+        for row in rows {
+            let row = row?;
+            let beats = |last: &PersonalRecord| RecordKind::from_key(&row.record)
+                .is_some_and(|kind| kind.beats(row.value, last.value));
+            match records.last_mut() {
+                Some(last) if (&last.player_name, &last.encounter, last.difficulty_id, &last.record)
+                    == (&row.player_name, &row.encounter, row.difficulty_id, &row.record) => {
+                    if beats(last) {
+                        *last = row;
+                    }
+                }
+                _ => records.push(row),
+            }
+        }
+        Ok(records)
+    })
+    .await
+    .map_err(|e| format!("Task error: {}", e))?
+    .map_err(|e| e.to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pull_score_weights_and_bounds() {
+        assert!((pull_score(100.0, 0, None) - 100.0).abs() < 1e-3);
+        assert!((pull_score(90.0, 1, Some(50.0)) - (54.0 + 20.0 + 7.5)).abs() < 1e-3);
+        // Avoidable part bottoms out at zero.
+        assert_eq!(pull_score(0.0, 12, Some(0.0)), 0.0);
+    }
+
+    #[test]
+    fn keeps_only_better_values() {
+        let current = HashMap::from([
+            (RecordKind::FastestKill, 200_000.0),
+            (RecordKind::BestPullScore, 80.0),
+        ]);
+        let values = [
+            (RecordKind::FastestKill, 190_000.0),
+            (RecordKind::BestPullScore, 75.0),
+            (RecordKind::FewestAvoidable, 3.0),
+        ];
+        assert_eq!(improvements(&values, &current), vec![
+            RecordUpdate { kind: RecordKind::FastestKill, value: 190_000.0, previous: Some(200_000.0) },
+            RecordUpdate { kind: RecordKind::FewestAvoidable, value: 3.0, previous: None },
+        ]);
+
+        // A tie is not a new record.
+        assert!(improvements(&[(RecordKind::FastestKill, 200_000.0)], &current).is_empty());
+    }
+
+    #[test]
+    fn keys_round_trip() {
+        for kind in [
+            RecordKind::FastestKill, RecordKind::FewestAvoidable,
+            RecordKind::BestKickEfficiency, RecordKind::BestPullScore,
+        ] {
+            assert_eq!(RecordKind::from_key(kind.key()), Some(kind));
+        }
+    }
+}
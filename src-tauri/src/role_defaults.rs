@@ -0,0 +1,92 @@
+/// Role-based coaching defaults for characters seen for the first time.
+///
+/// When the engine first identifies a character (addon identity or
+/// COMBATANT_INFO spec detection) and its role is known, it generates a
+/// `CharacterProfile` here and sends it out to be written into
+/// `AppConfig.character_profiles`.  From then on the profile is the user's:
+/// it is never regenerated while it exists, and edits survive.
+///
+/// What each role gets:
+///   TANK    — the defensive rules (defensive_timing, self_sustain,
+///             death_recap, avoidable_repeat) stay; DoT upkeep and burst
+///             cooldown timing are silenced, since a tank's "major
+///             cooldowns" are mostly defensives held for a reason.
+///   HEALER  — one step quieter, tank healing and kicks stay; cast gaps and
+///             cooldown timing are silenced (mana and reactive cooldowns make
+///             both normal), as is DoT upkeep.
+///   DAMAGER — uptime, cooldown and kick rules stay; praise for defensive
+///             timing is silenced.
+use anyhow::Result;
+use std::collections::btree_map::Entry;
+use tauri::{AppHandle, Manager};
+
+use crate::config::{self, CharacterProfile};
+
+/// A profile the engine generated, with the GUID it belongs to.
+#[derive(Debug, Clone)]
+pub struct GeneratedProfile {
+    pub guid:    String,
+    pub profile: CharacterProfile,
+}
+
+/// Defaults for a newly identified character; None for an unknown role.
+pub fn for_role(role: &str, name: &str) -> Option<CharacterProfile> {
+    let (intensity, disabled): (Option<u8>, &[&str]) = match role {
+        "TANK"    => (None,    &["cooldown_drift", "dot_uptime"]),
+        "HEALER"  => (Some(2), &["cooldown_available", "cooldown_drift", "dot_uptime", "gcd_gap"]),
+        "DAMAGER" => (None,    &["defensive_timing"]),
+        _         => return None,
+    };
+    Some(CharacterProfile {
+        name:           name.to_owned(),
+        role:           role.to_owned(),
+        intensity,
+        disabled_rules: disabled.iter().map(|r| (*r).to_owned()).collect(),
+    })
+}
+
+/// Write a generated profile into config.toml and hand the result to the
+/// running pipeline and the settings window.  A profile that appeared for
+/// the GUID in the meantime (hand edit, synced settings) is left alone;
+/// returns whether it was added.
+pub fn store(app: &AppHandle, generated: GeneratedProfile) -> Result<bool> {
+    let config_dir = app.path().app_config_dir()?;
+    let mut added = false;
+    let cfg = config::update(&config_dir, |cfg| {
+        if let Entry::Vacant(slot) = cfg.character_profiles.entry(generated.guid) {
+            slot.insert(generated.profile);
+            added = true;
+        }
+    })?;
+    if added {
+        crate::profile::write_sync_copy(&cfg, &config_dir);
+        crate::push_background_config_update(app, cfg);
+    }
+    Ok(added)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+
+    #[test]
+    fn every_role_gets_valid_defaults() {
+        let mut cfg = AppConfig::default();
+        for role in ["TANK", "HEALER", "DAMAGER"] {
+            let profile = for_role(role, "Stonebraid").expect("known role");
+            assert_eq!(profile.role, role);
+            cfg.character_profiles.insert(format!("Player-1-{}", role), profile);
+        }
+        assert!(cfg.validate().is_ok());
+        assert!(for_role("", "Stonebraid").is_none());
+
+        let healer = &cfg.character_profiles["Player-1-HEALER"];
+        assert_eq!(healer.intensity, Some(2));
+        assert!(healer.disabled_rules.contains(&"gcd_gap".to_owned()));
+        assert!(!cfg.character_profiles["Player-1-TANK"].disabled_rules.contains(&"defensive_timing".to_owned()));
+    }
+}
@@ -0,0 +1,95 @@
+/// Cast efficiency — casts per minute of the spec's rotation priority spells
+/// (`[spec.rotation] primary_spell_ids`) over a pull, and which of them were
+/// clearly underused ("Roll the Bones: 1 cast in 3:00").
+///
+/// A spell is underused when its rate stays below half its `expected_cpm`
+/// (spec TOML) or, without an expected rate, below one cast a minute — the
+/// listed spells are the spec's core buttons, so even a long cooldown among
+/// them comes up more often than that.  Pulls shorter than `MIN_PULL_MS` are
+/// reported but never flagged: a few seconds of travel swing their rates too
+/// much.
+///
+/// The rates go into the pull debrief and are stored per pull
+/// (`pull_cast_rates`) for trend analysis.
+use serde::{Deserialize, Serialize};
+
+use crate::{specs::PrioritySpell, spells};
+
+/// Shorter pulls are not judged.
+const MIN_PULL_MS: u64 = 60_000;
+/// Underused below this share of the expected rate…
+const UNDERUSE_SHARE: f32 = 0.5;
+/// …or below this rate when the spec gives none.
+const MIN_CPM: f32 = 1.0;
+
+/// One priority spell's cast rate over a pull.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpellCastRate {
+    pub spell_id:     u32,
+    pub spell_name:   String,
+    pub casts:        u32,
+    /// Casts per minute of pull time.
+    pub cpm:          f32,
+    #[serde(default)]
+    pub expected_cpm: Option<f32>,
+    pub underused:    bool,
+}
+
+/// Rate of every priority spell over a pull of `elapsed_ms`, given the
+/// player's `(log ms, spell ID)` casts; in the spec's priority order.
+pub fn cast_rates(casts: &[(u64, u32)], priority: &[PrioritySpell], elapsed_ms: u64) -> Vec<SpellCastRate> {
+    if elapsed_ms == 0 {
+        return Vec::new();
+    }
+    let minutes = elapsed_ms as f32 / 60_000.0;
+    priority.iter()
+        .map(|p| {
+            let count = casts.iter().filter(|(_, id)| *id == p.spell_id).count() as u32;
+            let cpm   = count as f32 / minutes;
+            let floor = p.expected_cpm.map_or(MIN_CPM, |e| e * UNDERUSE_SHARE);
+            SpellCastRate {
+                spell_id:     p.spell_id,
+                spell_name:   spells::name(p.spell_id).unwrap_or("Unknown").to_owned(),
+                casts:        count,
+                cpm,
+                expected_cpm: p.expected_cpm,
+                underused:    elapsed_ms >= MIN_PULL_MS && cpm < floor,
+            }
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spell(spell_id: u32, expected_cpm: Option<f32>) -> PrioritySpell {
+        PrioritySpell { spell_id, expected_cpm }
+    }
+
+    #[test]
+    fn flags_spells_cast_well_below_their_rate() {
+        // 3-minute pull: 30 builders, one finisher, 10 of a spell expected at 8/min.
+        let mut casts: Vec<(u64, u32)> = (0..30).map(|i| (i * 6_000, 1)).collect();
+        casts.push((90_000, 2));
+        casts.extend((0..10).map(|i| (i * 18_000, 3)));
+        let rates = cast_rates(&casts, &[spell(1, None), spell(2, None), spell(3, Some(8.0)), spell(4, None)], 180_000);
+
+        let flagged: Vec<(u32, bool)> = rates.iter().map(|r| (r.spell_id, r.underused)).collect();
+        assert_eq!(flagged, vec![(1, false), (2, true), (3, true), (4, true)]);
+        assert_eq!(rates[0].casts, 30);
+        assert!((rates[0].cpm - 10.0).abs() < 0.01);
+        assert_eq!(rates[3].casts, 0);
+    }
+
+    #[test]
+    fn short_pulls_are_not_judged() {
+        let rates = cast_rates(&[], &[spell(1, None)], 30_000);
+        assert_eq!(rates.len(), 1);
+        assert!(!rates[0].underused);
+        assert!(cast_rates(&[], &[spell(1, None)], 0).is_empty());
+    }
+}
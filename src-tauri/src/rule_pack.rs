@@ -0,0 +1,285 @@
+/// Shareable rule packs — a coach's custom rules bundled for their students.
+///
+/// A pack is a TOML file holding a set of the user's custom rule files
+/// (`<app config>/rules/*.toml`, rules/custom.rs) by file name, with
+/// metadata: pack name, author, pack version and the "CLASS/Spec" keys it
+/// was written for (specs.rs; empty = any spec).
+///
+///   format = 1
+///   [pack]
+///   name    = "Ovinax prog"
+///   author  = "Coach"
+///   version = "1.2"
+///   specs   = ["MAGE/Fire"]
+///   [rules]
+///   "ovinax.toml" = "[[rule]]\n…"
+///
+/// Both directions are validated with `rules::custom::load_dir`: export
+/// refuses rule files that do not load, and import stages the pack next to
+/// the local rule files and rejects it when that adds any load error — bad
+/// TOML, an unknown event kind, a failed rule check, or a rule name that is
+/// already taken.  An accepted pack's files are written into the rules dir,
+/// replacing same-named ones; rule scripts (`*.rhai`) are not packed.
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::{rules::custom, specs};
+
+/// Bumped when the pack layout changes incompatibly.
+pub const PACK_FORMAT: u32 = 1;
+/// Scratch dir inside the app config dir for validating an import.
+const STAGING_DIR: &str = "rule-pack-staging";
+
+/// Who wrote a pack and what it is for.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RulePackMeta {
+    pub name:    String,
+    pub author:  String,
+    /// The author's version of the pack ("1.2"), not the file format.
+    pub version: String,
+    /// Spec keys the rules were written for; empty = any spec.
+    #[serde(default)]
+    pub specs:   Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RulePackFile {
+    pub format: u32,
+    pub pack:   RulePackMeta,
+    /// Rule file contents by file name.
+    pub rules:  BTreeMap<String, String>,
+}
+
+/// What `import` installed, for the UI.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportedPack {
+    pub pack:  RulePackMeta,
+    /// Files written into the rules dir.
+    pub files: Vec<String>,
+    /// Rules those files define.
+    pub rules: usize,
+}
+
+/// Custom rule file names a pack may carry.
+fn is_pack_file(name: &str) -> bool {
+    !name.contains(['/', '\\']) && name.ends_with(".toml")
+}
+
+/// Missing metadata or a spec key this build does not know.
+fn check_meta(meta: &RulePackMeta) -> Result<()> {
+    for (field, value) in [("name", &meta.name), ("author", &meta.author), ("version", &meta.version)] {
+        if value.trim().is_empty() {
+            anyhow::bail!("Rule pack {} is empty", field);
+        }
+    }
+    if let Some(spec) = meta.specs.iter().find(|s| specs::load_by_key(s).is_none()) {
+        anyhow::bail!("Unknown spec '{}' in rule pack", spec);
+    }
+    Ok(())
+}
+
+/// Bundle the custom rule files `files` of `rules_dir` (all of them when
+/// empty) with `meta` and write the pack to `path`.
+pub fn export(rules_dir: &Path, files: &[String], meta: RulePackMeta, path: &Path) -> Result<()> {
+    check_meta(&meta)?;
+    let (_, errors) = custom::load_dir(rules_dir);
+    let mut rules = BTreeMap::new();
+    let entries = std::fs::read_dir(rules_dir).map_err(|_| anyhow::anyhow!("No custom rule files to export"))?;
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !is_pack_file(&name) || !(files.is_empty() || files.contains(&name)) {
+            continue;
+        }
+        if let Some(e) = errors.iter().find(|e| e.starts_with(&format!("{}: ", name))) {
+            anyhow::bail!("Fix the rule file before exporting it — {}", e);
+        }
+        rules.insert(name, std::fs::read_to_string(entry.path())?);
+    }
+    if let Some(missing) = files.iter().find(|f| !rules.contains_key(*f)) {
+        anyhow::bail!("No rule file named {}", missing);
+    }
+    if rules.is_empty() {
+        anyhow::bail!("No custom rule files to export");
+    }
+
+    let file = RulePackFile { format: PACK_FORMAT, pack: meta, rules };
+    let raw = toml::to_string_pretty(&file)
+        .map_err(|e| anyhow::anyhow!("Rule pack serialize error: {}", e))?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, raw)?;
+    Ok(())
+}
+
+/// Read a pack file, rejecting formats newer than this build understands.
+pub fn read(path: &Path) -> Result<RulePackFile> {
+    let raw = std::fs::read_to_string(path)?;
+    let file: RulePackFile = toml::from_str(&raw)
+        .map_err(|e| anyhow::anyhow!("Rule pack parse error: {}", e))?;
+    if file.format > PACK_FORMAT {
+        anyhow::bail!(
+            "Rule pack format {} is newer than supported ({}); update the app first",
+            file.format, PACK_FORMAT
+        );
+    }
+    Ok(file)
+}
+
+/// Validate the pack at `path` against the rule files in
+/// `<config_dir>/rules` and install it there.
+pub fn import(path: &Path, config_dir: &Path) -> Result<ImportedPack> {
+    let file = read(path)?;
+    check_meta(&file.pack)?;
+    if let Some(name) = file.rules.keys().find(|n| !is_pack_file(n)) {
+        anyhow::bail!("Rule pack holds '{}', which is not a rule file name", name);
+    }
+    if file.rules.is_empty() {
+        anyhow::bail!("Rule pack has no rule files");
+    }
+
+    let rules_dir = config_dir.join(custom::RULES_DIR);
+    let staging = config_dir.join(STAGING_DIR);
+    let checked = check_staged(&rules_dir, &file.rules, &staging);
+    let _ = std::fs::remove_dir_all(&staging);
+    let rules = checked?;
+
+    std::fs::create_dir_all(&rules_dir)?;
+    for (name, raw) in &file.rules {
+        std::fs::write(rules_dir.join(name), raw)?;
+    }
+    Ok(ImportedPack { pack: file.pack, files: file.rules.into_keys().collect(), rules })
+}
+
+/// Load the pack in `staging`, first on its own and then on top of the local
+/// rule files.  Returns the number of rules it defines; fails on any load
+/// error it brings.
+fn check_staged(rules_dir: &Path, pack: &BTreeMap<String, String>, staging: &Path) -> Result<usize> {
+    // On its own: bad TOML, unknown event kinds, failed checks, names defined twice.
+    stage(staging, pack, None)?;
+    let (rules, errors) = custom::load_dir(staging);
+    if !errors.is_empty() {
+        anyhow::bail!("Rule pack not imported — {}", errors.join("; "));
+    }
+    // Next to the local files: names already taken.  Errors the local files
+    // had before are not the pack's.
+    stage(staging, pack, Some(rules_dir))?;
+    let (_, local_errors) = custom::load_dir(rules_dir);
+    let (_, errors) = custom::load_dir(staging);
+    let added: Vec<String> = errors.into_iter().filter(|e| !local_errors.contains(e)).collect();
+    if !added.is_empty() {
+        anyhow::bail!("Rule pack not imported — {}", added.join("; "));
+    }
+    Ok(rules.len())
+}
+
+/// Fill `staging` with the pack's files, plus the custom rule files of
+/// `local` that the pack does not replace.
+fn stage(staging: &Path, pack: &BTreeMap<String, String>, local: Option<&Path>) -> Result<()> {
+    let _ = std::fs::remove_dir_all(staging);
+    std::fs::create_dir_all(staging)?;
+    if let Some(entries) = local.and_then(|dir| std::fs::read_dir(dir).ok()) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if is_pack_file(&name) && !pack.contains_key(&name) {
+                std::fs::copy(entry.path(), staging.join(&name))?;
+            }
+        }
+    }
+    for (name, raw) in pack {
+        std::fs::write(staging.join(name), raw)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const EGGS: &str = r#"
+[[rule]]
+name     = "eggs"
+title    = "Egg broken"
+event    = "spell_damage"
+severity = "bad"
+message  = "{spell} hit you"
+"#;
+
+    fn meta() -> RulePackMeta {
+        RulePackMeta {
+            name:    "Ovinax prog".into(),
+            author:  "Coach".into(),
+            version: "1.0".into(),
+            specs:   vec!["MAGE/Fire".into()],
+        }
+    }
+
+    fn rules_dir(config_dir: &Path, files: &[(&str, &str)]) -> std::path::PathBuf {
+        let dir = config_dir.join(custom::RULES_DIR);
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, raw) in files {
+            std::fs::write(dir.join(name), raw).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn exported_pack_imports_on_another_machine() {
+        let coach = tempdir().unwrap();
+        let dir = rules_dir(coach.path(), &[("eggs.toml", EGGS), ("notes.txt", "not a rule")]);
+        let path = coach.path().join("pack.toml");
+        export(&dir, &[], meta(), &path).unwrap();
+        assert_eq!(read(&path).unwrap().rules.keys().collect::<Vec<_>>(), ["eggs.toml"]);
+
+        let student = tempdir().unwrap();
+        let imported = import(&path, student.path()).unwrap();
+        assert_eq!((imported.pack, imported.rules), (meta(), 1));
+        let (loaded, errors) = custom::load_dir(&student.path().join(custom::RULES_DIR));
+        assert_eq!((loaded.len(), errors.len()), (1, 0));
+        assert!(!student.path().join(STAGING_DIR).exists());
+    }
+
+    #[test]
+    fn export_needs_metadata_and_loadable_rules() {
+        let coach = tempdir().unwrap();
+        let dir = rules_dir(coach.path(), &[("eggs.toml", EGGS), ("broken.toml", "[[rule]]\nname = ")]);
+        let path = coach.path().join("pack.toml");
+        assert!(export(&dir, &[], meta(), &path).is_err());
+        assert!(export(&dir, &["eggs.toml".into()], RulePackMeta { author: " ".into(), ..meta() }, &path).is_err());
+        assert!(export(&dir, &["eggs.toml".into()], RulePackMeta { specs: vec!["MAGE/Pyro".into()], ..meta() }, &path).is_err());
+        export(&dir, &["eggs.toml".into()], meta(), &path).unwrap();
+    }
+
+    #[test]
+    fn import_rejects_invalid_rules_and_taken_names() {
+        let student = tempdir().unwrap();
+        let path = student.path().join("pack.toml");
+        let write_pack = |rules: &[(&str, &str)]| {
+            let file = RulePackFile {
+                format: PACK_FORMAT,
+                pack:   meta(),
+                rules:  rules.iter().map(|(n, r)| (n.to_string(), r.to_string())).collect(),
+            };
+            std::fs::write(&path, toml::to_string(&file).unwrap()).unwrap();
+        };
+
+        write_pack(&[("bad.toml", &EGGS.replace("spell_damage", "spell_explode"))]);
+        assert!(import(&path, student.path()).is_err());
+        write_pack(&[("../escape.toml", EGGS)]);
+        assert!(import(&path, student.path()).is_err());
+
+        // "eggs" is already defined locally in another file.
+        let dir = rules_dir(student.path(), &[("mine.toml", EGGS)]);
+        write_pack(&[("pack_eggs.toml", EGGS)]);
+        let err = import(&path, student.path()).unwrap_err().to_string();
+        assert!(err.contains("defined twice"), "{}", err);
+        assert!(!dir.join("pack_eggs.toml").exists());
+
+        // Replacing the local file of the same name is fine.
+        write_pack(&[("mine.toml", EGGS)]);
+        assert_eq!(import(&path, student.path()).unwrap().rules, 1);
+    }
+}
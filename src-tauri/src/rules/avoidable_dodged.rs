@@ -0,0 +1,64 @@
+/// Fires Good when the coached player fully avoids an avoidable mechanic
+/// that hit them earlier in the session.
+///
+/// Positive reinforcement — avoidable_repeat only ever calls out the
+/// failure; this acknowledges the improvement.  A SPELL_MISSED on the player
+/// counts when its miss type means the damage never landed (dodge, parry,
+/// miss, immune, deflect, evade).  ABSORB and RESIST are left out: the
+/// mechanic still connected and a shield or resistance ate it.
+///
+/// With encounter/dungeon data listing avoidable spells (encounters.rs) only
+/// those count; without a list any spell that hit the player before does.
+///
+/// Keyed per spell ("avoidable_dodged:<id>"), so each mechanic gets its own
+/// acknowledgement, spaced by the Good advice cooldown.
+///
+/// Intensity gate: fires at intensity >= 2 (Low or higher).
+use super::{advice, RuleContext, RuleInput, RuleOutput};
+use crate::{engine::Severity, parser::LogEvent};
+
+pub const KEY: &str = "avoidable_dodged";
+const MIN_INTENSITY: u8 = 2;
+/// Miss types where the mechanic did not reach the player.
+const AVOIDED: &[&str] = &["DODGE", "PARRY", "MISS", "IMMUNE", "DEFLECT", "EVADE"];
+
+/// `avoidable`: the known avoidable spells of the current content, if any.
+pub fn evaluate(input: &RuleInput, ctx: &RuleContext, avoidable: Option<&[u32]>) -> RuleOutput {
+    let LogEvent::SpellMissed {
+        dest_guid,
+        spell_id,
+        spell_name,
+        miss_type,
+        ..
+    } = input.event
+    else {
+        return vec![];
+    };
+
+    // Only the coached player avoiding something
+    if Some(dest_guid.as_str()) != ctx.state.player_guid.as_deref() {
+        return vec![];
+    }
+    if ctx.intensity < MIN_INTENSITY || !AVOIDED.contains(&miss_type.as_str()) {
+        return vec![];
+    }
+    if avoidable.is_some_and(|ids| !ids.contains(spell_id)) {
+        return vec![];
+    }
+    if !ctx.state.avoidable.hit_this_session(*spell_id) {
+        return vec![];
+    }
+
+    vec![advice(
+        &format!("{}:{}", KEY, spell_id),
+        "Mechanic avoided",
+        format!("Clean — {} avoided ({}). That one hit you earlier.", spell_name, miss_type.to_lowercase()),
+        Severity::Good,
+        vec![
+            ("spell".to_owned(),     spell_name.clone()),
+            ("spell_id".to_owned(),  spell_id.to_string()),
+            ("miss_type".to_owned(), miss_type.clone()),
+        ],
+        ctx.now_ms,
+    )]
+}
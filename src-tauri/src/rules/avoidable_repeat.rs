@@ -1,16 +1,31 @@
 /// Fires when the coached player is hit by the same spell 2+ times in one pull.
 ///
-/// Phase 0: fires for ANY spell that damages the player (no encounter list needed).
-/// Phase 1: cross-reference against the encounter TOML avoidable_spell_ids list
-///          so only truly avoidable mechanics trigger this rule.
-use super::{advice, RuleContext, RuleInput, RuleOutput};
-use crate::{engine::Severity, parser::LogEvent};
+/// When the active encounter or dungeon has data files listing avoidable
+/// spells (`avoidable_spell_ids`, see encounters.rs), only those count —
+/// unavoidable raid-wide AoE would otherwise train players to ignore the
+/// rule.  Without a list it falls back to any spell that damages the player.
+///
+/// Damage from the player's own side (state::DamageSource) is left out
+/// unless `AppConfig.avoidable_friendly_fire` is set.
+///
+/// Solo content allows one extra hit: delve/follower trash often has
+/// unavoidable-in-practice ground effects while soloing.
+use super::{advice, RuleContext, RuleInput, RuleOutput, RuleProfile};
+use crate::{
+    engine::Severity,
+    parser::LogEvent,
+    state::DamageSource,
+};
 
 pub const KEY: &str = "avoidable_repeat";
 const MIN_HITS: u32 = 2;
+const SOLO_MIN_HITS: u32 = 3;
 
-pub fn evaluate(input: &RuleInput, ctx: &RuleContext) -> RuleOutput {
+/// `avoidable`: the known avoidable spells of the current content, if any.
+pub fn evaluate(input: &RuleInput, ctx: &RuleContext, avoidable: Option<&[u32]>) -> RuleOutput {
     let LogEvent::SpellDamage {
+        source_guid,
+        source_flags,
         dest_guid,
         spell_id,
         spell_name,
@@ -25,9 +40,20 @@ pub fn evaluate(input: &RuleInput, ctx: &RuleContext) -> RuleOutput {
     if Some(dest_guid.as_str()) != ctx.state.player_guid.as_deref() {
         return vec![];
     }
+    if avoidable.is_some_and(|ids| !ids.contains(spell_id)) {
+        return vec![];
+    }
+    // Burning Rush and reflected damage are not mechanics to step out of.
+    if !ctx.state.avoidable_source(DamageSource::of(source_guid, *source_flags, Some(dest_guid))) {
+        return vec![];
+    }
 
     let hit_count = ctx.state.avoidable.hit_count(*spell_id);
-    if hit_count < MIN_HITS {
+    let min_hits = match ctx.profile {
+        RuleProfile::Group | RuleProfile::Practice => MIN_HITS,
+        RuleProfile::Solo                          => SOLO_MIN_HITS,
+    };
+    if hit_count < min_hits {
         return vec![];
     }
 
@@ -36,7 +62,7 @@ pub fn evaluate(input: &RuleInput, ctx: &RuleContext) -> RuleOutput {
         "Avoidable damage repeating",
         format!(
             "{}: {} hits this pull ({} dmg last hit). Adjust position before next overlap.",
-            spell_name, hit_count, amount
+            spell_name, hit_count, ctx.fmt.amount(*amount)
         ),
         Severity::Bad,
         vec![
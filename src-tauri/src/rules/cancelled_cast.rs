@@ -0,0 +1,48 @@
+/// Fires when the coached player abandons a hard cast part way — moved,
+/// pressed escape, or clipped it with the next cast.  The cast time spent is
+/// lost outright.  Cast pairing and the per-pull count come from
+/// state::HardcastTracker; an enemy's kick is not counted.
+///
+/// Only casts abandoned LATE_MS or more in are reported: a cast dropped at
+/// once is a misclick, not lost time.  The count of the pull goes in the
+/// message, and the debrief lists the cancelled spells.
+///
+/// Intensity gate: only fires at intensity >= 3 (Balanced or higher).
+use super::{advice, RuleContext, RuleInput, RuleOutput};
+use crate::{engine::Severity, parser::LogEvent};
+
+pub const KEY: &str = "cancelled_cast";
+const LATE_MS:       u64 = 1_000;
+const MIN_INTENSITY: u8  = 3;
+
+pub fn evaluate(input: &RuleInput, ctx: &RuleContext) -> RuleOutput {
+    if !matches!(input.event, LogEvent::SpellCastStart { .. } | LogEvent::SpellCastFailed { .. }) {
+        return vec![];
+    }
+    if ctx.intensity < MIN_INTENSITY {
+        return vec![];
+    }
+    let casts = &ctx.state.hardcasts;
+    let Some(cancelled) = casts.just_cancelled(ctx.now_ms).filter(|c| c.into_ms >= LATE_MS) else {
+        return vec![];
+    };
+
+    let count = casts.cancelled.len();
+    let this_pull = if count > 1 { format!(" ({} this pull)", count) } else { String::new() };
+    vec![advice(
+        KEY,
+        "Cancelled Cast",
+        format!(
+            "{} cancelled {} into the cast{} — finish the cast or don't start it.",
+            cancelled.spell_name,
+            ctx.fmt.seconds(cancelled.into_ms, 1),
+            this_pull,
+        ),
+        Severity::Warn,
+        vec![
+            ("spell".to_owned(), cancelled.spell_name.clone()),
+            ("cancelled".to_owned(), count.to_string()),
+        ],
+        ctx.now_ms,
+    )]
+}
@@ -0,0 +1,120 @@
+/// Health potions and healthstones — the defensive the player forgets they
+/// carry.  Consumable spell IDs and cooldowns come from data/consumables.toml;
+/// uses and availability from state::ConsumableTracker.
+///
+/// Fires:
+///   - Warn, once per pull, when the player drops below LOW_HP_PCT health
+///     with a potion or healthstone ready and none used this pull.  Without
+///     advanced logging (no HP fields) a damage spike stands in: SPIKE_DAMAGE
+///     taken within WINDOW_MS.  Urgent — there is still time to drink.
+///   - Warn when the player dies without having used either this pull while
+///     one was ready.
+///   - Good when the player uses one under pressure: below PRESSURE_HP_PCT,
+///     or SPIKE_DAMAGE taken within WINDOW_MS.
+///
+/// The warnings need a consumable to be ready: a potion drunk two pulls ago
+/// may still be on its shared cooldown, and a healthstone is only expected
+/// once one was seen this session.
+///
+/// Intensity >= 2, except the death warning.
+use super::{advice, RuleContext, RuleInput, RuleOutput};
+use crate::{
+    consumables,
+    engine::{Priority, Severity},
+    parser::LogEvent,
+};
+
+pub const KEY: &str = "consumable";
+const LOW_HP_KEY:  &str = "consumable_low_hp";
+const DEATH_KEY:   &str = "consumable_death";
+const USED_KEY:    &str = "consumable_used";
+
+/// Health % that calls for a potion or healthstone.
+const LOW_HP_PCT:      f32 = 25.0;
+/// A use at or below this health % counts as under pressure.
+const PRESSURE_HP_PCT: f32 = 50.0;
+/// Damage-spike fallback without HP fields (same scale as defensive_timing).
+const SPIKE_DAMAGE:    u64 = 30_000;
+const WINDOW_MS:       u64 = 5_000;
+const MIN_INTENSITY:   u8  = 2;
+
+pub fn evaluate(input: &RuleInput, ctx: &RuleContext) -> RuleOutput {
+    let Some(player) = ctx.state.player_guid.as_deref() else {
+        return vec![];
+    };
+    let used = &ctx.state.consumables;
+    let used_any = used.potions + used.healthstones > 0;
+    let hp_pct = input.event.unit_state().filter(|u| u.guid == player).and_then(|u| u.hp_pct());
+    let recent_dmg = ctx.state.damage_taken.recent_damage(ctx.now_ms, WINDOW_MS);
+    let ready = || {
+        used.ready(ctx.now_ms)
+            .into_iter()
+            .map(consumables::name)
+            .collect::<Vec<_>>()
+            .join(" or ")
+    };
+
+    match input.event {
+        LogEvent::SpellCastSuccess { source_guid, spell_id, spell_name, .. } if source_guid == player => {
+            if ctx.intensity < MIN_INTENSITY || consumables::kind_of(*spell_id).is_none() {
+                return vec![];
+            }
+            let pressure = match hp_pct {
+                Some(pct) if pct <= PRESSURE_HP_PCT => format!("at {:.0}% health", pct),
+                _ if recent_dmg >= SPIKE_DAMAGE     => {
+                    format!("under pressure — {} damage in the last 5s", ctx.fmt.amount(recent_dmg))
+                }
+                _ => return vec![],
+            };
+            vec![advice(
+                USED_KEY,
+                "Good Consumable Use",
+                format!("{} used {}.", spell_name, pressure),
+                Severity::Good,
+                vec![("spell".to_owned(), spell_name.clone())],
+                ctx.now_ms,
+            )]
+        }
+        LogEvent::SpellDamage { dest_guid, .. } | LogEvent::SwingDamage { dest_guid, .. } if dest_guid == player => {
+            if ctx.intensity < MIN_INTENSITY || used_any || ctx.state.advice_repeats.contains_key(LOW_HP_KEY) {
+                return vec![];
+            }
+            let low = match hp_pct {
+                Some(pct) => pct < LOW_HP_PCT,
+                None      => recent_dmg >= SPIKE_DAMAGE,
+            };
+            let ready = ready();
+            if !low || ready.is_empty() {
+                return vec![];
+            }
+            let state = match hp_pct {
+                Some(pct) => format!("Down to {:.0}% health", pct),
+                None      => format!("{} damage in 5s", ctx.fmt.amount(recent_dmg)),
+            };
+            vec![advice(
+                LOW_HP_KEY,
+                "Use a Consumable",
+                format!("{} and no potion or healthstone yet — {} is ready.", state, ready),
+                Severity::Warn,
+                vec![("ready".to_owned(), ready)],
+                ctx.now_ms,
+            )
+            .with_priority(Priority::Urgent)]
+        }
+        LogEvent::UnitDied { dest_guid, .. } if dest_guid == player => {
+            let ready = ready();
+            if used_any || ready.is_empty() {
+                return vec![];
+            }
+            vec![advice(
+                DEATH_KEY,
+                "Unused Consumable",
+                format!("Died without a potion or healthstone this pull — {} was ready.", ready),
+                Severity::Warn,
+                vec![("ready".to_owned(), ready)],
+                ctx.now_ms,
+            )]
+        }
+        _ => vec![],
+    }
+}
@@ -0,0 +1,60 @@
+/// Fires when a major cooldown has been ready for a while mid-pull and the
+/// player has not pressed it.
+///
+/// "Cooldown Ready" — a cooldown counts as ready `cooldown_s` (spec TOML,
+/// base duration) after its last observed cast this pull:
+///   Warn "Combustion has been ready for 22s — use it."
+///
+/// Only cooldowns already cast this pull are tracked: a spell never seen may
+/// not be talented, and base durations ignore cooldown reduction, so the
+/// rule never claims availability it has no evidence for.  Defensives (spec
+/// `am_spell_ids`) are skipped — holding those is a choice, not a loss.
+///
+/// Time-driven: evaluated in pass 3 (and the engine tick), with the spell ID
+/// in the advice key so each cooldown is called out separately.
+///
+/// Intensity gate: fires at intensity >= 2.
+use super::{advice, RuleContext, RuleOutput};
+use crate::{engine::Severity, spells};
+use std::collections::HashMap;
+
+pub const KEY: &str = "cooldown_available";
+const MIN_INTENSITY: u8 = 2;
+
+/// `unused_ms` is how long a cooldown may sit ready before it is flagged
+/// (`AppConfig.cd_unused_alert_s`); 0 disables the rule.
+pub fn evaluate(
+    ctx:          &RuleContext,
+    major_cd_ids: &[u32],
+    cooldown_s:   &HashMap<u32, u32>,
+    am_spell_ids: &[u32],
+    unused_ms:    u64,
+) -> RuleOutput {
+    if ctx.intensity < MIN_INTENSITY || unused_ms == 0 {
+        return vec![];
+    }
+
+    let mut out = Vec::new();
+    for spell_id in major_cd_ids.iter().filter(|id| !am_spell_ids.contains(id)) {
+        let Some(&secs) = cooldown_s.get(spell_id) else { continue };
+        let Some(last_cast) = ctx.state.cooldowns.last_used_ms(*spell_id) else { continue };
+        let ready_at = last_cast + secs as u64 * 1_000;
+        let ready_for = ctx.now_ms.saturating_sub(ready_at);
+        if ctx.now_ms < ready_at || ready_for < unused_ms {
+            continue;
+        }
+        let name = spells::name(*spell_id).unwrap_or("Your cooldown");
+        out.push(advice(
+            &format!("{}_{}", KEY, spell_id),
+            "Cooldown Ready",
+            format!("{} has been ready for {} — use it.", name, ctx.fmt.seconds(ready_for, 0)),
+            Severity::Warn,
+            vec![
+                ("spell_id".to_owned(),  spell_id.to_string()),
+                ("ready_for".to_owned(), ctx.fmt.seconds(ready_for, 0)),
+            ],
+            ctx.now_ms,
+        ));
+    }
+    out
+}
@@ -10,11 +10,19 @@
 ///
 /// The list of major CD spell IDs comes from the user's spec profile TOML,
 /// loaded into AppConfig.major_cds at startup.
+///
+/// Bloodlust/Heroism (state::LustTracker) changes the verdict on any use:
+///   - cast while lust is up → Good "Avenging Wrath stacked with Heroism."
+///     and no drift warning — holding a cooldown for lust is the plan
+///   - first cast within AFTER_LUST_MS of lust fading → Warn "Avenging Wrath
+///     4s after Heroism faded — stack it with lust." instead of the drift one
 use super::{advice, RuleContext, RuleInput, RuleOutput};
 use crate::{engine::Severity, parser::LogEvent};
 
 pub const KEY: &str = "cooldown_drift";
 const DRIFT_THRESHOLD_MS: u64 = 8_000; // 8 seconds
+/// A major cooldown this soon after lust faded missed the lust window.
+const AFTER_LUST_MS: u64 = 15_000;
 
 pub fn evaluate(input: &RuleInput, ctx: &RuleContext, major_cd_ids: &[u32]) -> RuleOutput {
     let LogEvent::SpellCastSuccess {
@@ -35,6 +43,18 @@ pub fn evaluate(input: &RuleInput, ctx: &RuleContext, major_cd_ids: &[u32]) -> R
         return vec![];
     }
 
+    let lust = &ctx.state.lust;
+    if lust.up_since.is_some() {
+        return vec![advice(
+            &format!("{}_lust_{}", KEY, spell_id),
+            "Cooldown Stacked with Lust",
+            format!("{} stacked with {}.", spell_name, lust.name),
+            Severity::Good,
+            vec![("spell".to_owned(), spell_name.clone())],
+            ctx.now_ms,
+        )];
+    }
+
     let pull_elapsed = ctx.state.pull_elapsed_ms(ctx.now_ms);
 
     // Must be past the threshold to be considered "drift"
@@ -59,18 +79,27 @@ pub fn evaluate(input: &RuleInput, ctx: &RuleContext, major_cd_ids: &[u32]) -> R
         return vec![];
     }
 
-    let drift_s = pull_elapsed as f64 / 1_000.0;
+    if let Some(ago) = lust.faded_ago(ctx.now_ms).filter(|&ago| ago <= AFTER_LUST_MS) {
+        return vec![advice(
+            &format!("{}_after_lust_{}", KEY, spell_id),
+            "Cooldown Missed Lust",
+            format!("{} {} after {} faded — stack it with lust.", spell_name, ctx.fmt.seconds(ago, 0), lust.name),
+            Severity::Warn,
+            vec![("spell".to_owned(), spell_name.clone())],
+            ctx.now_ms,
+        )];
+    }
 
     vec![advice(
         KEY,
         "Major cooldown used late",
         format!(
-            "{} drifted by ~{:.0}s into the pull. Next pull: use on pull, then on cooldown.",
-            spell_name, drift_s
+            "{} drifted by ~{} into the pull. Next pull: use on pull, then on cooldown.",
+            spell_name, ctx.fmt.duration(pull_elapsed)
         ),
         Severity::Warn,
         vec![
-            ("drift".to_owned(), format!("{:.1}s", drift_s)),
+            ("drift".to_owned(), ctx.fmt.seconds(pull_elapsed, 1)),
             ("spell".to_owned(), spell_name.clone()),
         ],
         ctx.now_ms,
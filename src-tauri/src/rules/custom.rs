@@ -0,0 +1,449 @@
+/// User-authored rules — declarative checks read from
+/// `<app config>/rules/*.toml` and run by a small interpreter, for the
+/// encounter-specific checks that need no hand-written Rust.
+///
+/// A file holds one or more `[[rule]]` tables:
+///
+///   [[rule]]
+///   name      = "ovinax_eggs"            # advice key "custom:ovinax_eggs"
+///   title     = "Egg broken"
+///   event     = "spell_damage"           # see `Trigger`
+///   spell_ids = [442432]                 # omitted = any spell
+///   source    = "any"                    # player | other | any (default)
+///   target    = "player"
+///   encounter = "Broodtwister Ovi'nax"   # omitted = everywhere
+///   threshold = 2                        # matching events needed…
+///   window_s  = 10                       # …within this many seconds (0 = the whole pull)
+///   severity  = "bad"                    # good | warn | bad
+///   message   = "{spell} hit you {count} times"
+///
+/// Message placeholders: {spell} {count} {source} {target} {amount} (unit
+/// names; empty for events whose log line has none).
+///
+/// Counts start over at every pull.  Once the threshold is reached the rule
+/// fires on each further match, spaced by the advice cooldowns like the
+/// built-in rules; `disabled_rules = ["custom"]` silences every user rule.
+///
+/// Files are read when the pipeline starts and on every config reload
+/// (`load_dir` + `install`); the engine switches to a new set on its next
+/// event.
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use std::collections::{HashSet, VecDeque};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use super::{advice, RuleContext, RuleInput, RuleOutput};
+use crate::{engine::Severity, parser::LogEvent};
+
+/// Advice key prefix of every user rule.
+pub const KEY: &str = "custom:";
+/// Rule files, inside the app config dir.
+pub const RULES_DIR: &str = "rules";
+
+/// Combat log event a rule counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Trigger {
+    SpellDamage,
+    SpellMissed,
+    SpellHeal,
+    AuraApplied,
+    AuraRemoved,
+    CastStart,
+    CastSuccess,
+    Interrupt,
+    UnitDied,
+}
+
+/// Whose side of the event a filter accepts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Party {
+    /// The coached player.
+    Player,
+    /// Anyone but the coached player.
+    Other,
+    #[default]
+    Any,
+}
+
+/// One `[[rule]]` table.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomRule {
+    pub name:  String,
+    title:     String,
+    event:     Trigger,
+    #[serde(default)]
+    spell_ids: Vec<u32>,
+    #[serde(default)]
+    source:    Party,
+    #[serde(default)]
+    target:    Party,
+    /// Only during this encounter (case-insensitive); empty = everywhere.
+    #[serde(default)]
+    encounter: String,
+    #[serde(default = "one")]
+    threshold: u32,
+    /// Counting window in seconds; 0 = the whole pull.
+    #[serde(default)]
+    window_s:  u32,
+    severity:  Severity,
+    message:   String,
+}
+
+fn one() -> u32 {
+    1
+}
+
+#[derive(Deserialize)]
+struct RuleFile {
+    #[serde(default)]
+    rule: Vec<CustomRule>,
+}
+
+/// The parts of an event a rule looks at.  Names are empty where the log
+/// line has none.
+struct Matched<'a> {
+    source:      &'a str,
+    target:      &'a str,
+    source_name: &'a str,
+    target_name: &'a str,
+    spell_id:    Option<u32>,
+    spell:       &'a str,
+    amount:      u64,
+}
+
+fn matched(trigger: Trigger, event: &LogEvent) -> Option<Matched<'_>> {
+    let m = match (trigger, event) {
+        (Trigger::SpellDamage, LogEvent::SpellDamage { source_guid, source_name, dest_guid, dest_name, spell_id, spell_name, amount, .. })
+        | (Trigger::SpellHeal, LogEvent::SpellHeal { source_guid, source_name, dest_guid, dest_name, spell_id, spell_name, amount, .. }) =>
+            Matched {
+                source: source_guid, target: dest_guid, source_name, target_name: dest_name,
+                spell_id: Some(*spell_id), spell: spell_name, amount: *amount,
+            },
+        (Trigger::CastSuccess, LogEvent::SpellCastSuccess { source_guid, source_name, dest_guid, dest_name, spell_id, spell_name, .. }) =>
+            Matched {
+                source: source_guid, target: dest_guid, source_name, target_name: dest_name,
+                spell_id: Some(*spell_id), spell: spell_name, amount: 0,
+            },
+        (Trigger::SpellMissed, LogEvent::SpellMissed { source_guid, dest_guid, spell_id, spell_name, .. })
+        | (Trigger::AuraApplied, LogEvent::AuraApplied { source_guid, dest_guid, spell_id, spell_name, .. })
+        | (Trigger::AuraRemoved, LogEvent::AuraRemoved { source_guid, dest_guid, spell_id, spell_name, .. }) =>
+            Matched {
+                source: source_guid, target: dest_guid, source_name: "", target_name: "",
+                spell_id: Some(*spell_id), spell: spell_name, amount: 0,
+            },
+        (Trigger::CastStart, LogEvent::SpellCastStart { source_guid, source_name, spell_id, spell_name, .. }) =>
+            Matched {
+                source: source_guid, target: "", source_name, target_name: "",
+                spell_id: Some(*spell_id), spell: spell_name, amount: 0,
+            },
+        (Trigger::Interrupt, LogEvent::SpellInterrupted { source_guid, target_guid, interrupted_spell_id, interrupted_spell, .. }) =>
+            Matched {
+                source: source_guid, target: target_guid, source_name: "", target_name: "",
+                spell_id: Some(*interrupted_spell_id), spell: interrupted_spell, amount: 0,
+            },
+        (Trigger::UnitDied, LogEvent::UnitDied { dest_guid, dest_name, .. }) =>
+            Matched {
+                source: "", target: dest_guid, source_name: "", target_name: dest_name,
+                spell_id: None, spell: "", amount: 0,
+            },
+        _ => return None,
+    };
+    Some(m)
+}
+
+fn party_matches(party: Party, guid: &str, player: Option<&str>) -> bool {
+    match party {
+        Party::Any    => true,
+        Party::Player => Some(guid) == player,
+        Party::Other  => Some(guid) != player,
+    }
+}
+
+impl CustomRule {
+    fn matches<'e>(&self, event: &'e LogEvent, ctx: &RuleContext) -> Option<Matched<'e>> {
+        if !self.encounter.is_empty()
+            && !ctx.state.encounter_name.as_deref().is_some_and(|e| e.eq_ignore_ascii_case(&self.encounter))
+        {
+            return None;
+        }
+        let m = matched(self.event, event)?;
+        let player = ctx.state.player_guid.as_deref();
+        let wanted = self.spell_ids.is_empty() || m.spell_id.is_some_and(|id| self.spell_ids.contains(&id));
+        (wanted && party_matches(self.source, m.source, player) && party_matches(self.target, m.target, player))
+            .then_some(m)
+    }
+
+    fn check(&self) -> Result<(), String> {
+        if self.name.is_empty() || !self.name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("rule name '{}' must be letters, digits and _", self.name));
+        }
+        if self.threshold == 0 {
+            return Err(format!("rule '{}': threshold must be at least 1", self.name));
+        }
+        Ok(())
+    }
+
+    fn render(&self, m: &Matched, count: u32, ctx: &RuleContext) -> String {
+        self.message
+            .replace("{spell}",  m.spell)
+            .replace("{count}",  &count.to_string())
+            .replace("{source}", m.source_name)
+            .replace("{target}", m.target_name)
+            .replace("{amount}", &ctx.fmt.amount(m.amount))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Loading
+// ---------------------------------------------------------------------------
+
+/// Parse every `*.toml` in `dir` (alphabetical).  Returns the valid rules
+/// and one message per file or rule that was left out.
+pub fn load_dir(dir: &Path) -> (Vec<CustomRule>, Vec<String>) {
+    let mut paths: Vec<_> = std::fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|e| e.path()).collect())
+        .unwrap_or_default();
+    paths.retain(|p| p.extension().is_some_and(|ext| ext == "toml"));
+    paths.sort();
+
+    let mut rules  = Vec::new();
+    let mut errors = Vec::new();
+    let mut names  = HashSet::new();
+    for path in paths {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        let parsed = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|raw| toml::from_str::<RuleFile>(&raw).map_err(|e| e.to_string()));
+        let file = match parsed {
+            Ok(f)  => f,
+            Err(e) => { errors.push(format!("{}: {}", file_name, e)); continue; }
+        };
+        for rule in file.rule {
+            if let Err(e) = rule.check() {
+                errors.push(format!("{}: {}", file_name, e));
+            } else if !names.insert(rule.name.clone()) {
+                errors.push(format!("{}: rule '{}' is defined twice", file_name, rule.name));
+            } else {
+                rules.push(rule);
+            }
+        }
+    }
+    (rules, errors)
+}
+
+/// The rule set the engine runs.
+static INSTALLED: Lazy<RwLock<Arc<Vec<CustomRule>>>> = Lazy::new(Default::default);
+/// Bumped by every `install`.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Replace the running rule set.
+pub fn install(rules: Vec<CustomRule>) {
+    if let Ok(mut installed) = INSTALLED.write() {
+        *installed = Arc::new(rules);
+        GENERATION.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Interpreter
+// ---------------------------------------------------------------------------
+
+/// The engine's copy of the installed rules and their per-pull counts.
+#[derive(Default)]
+pub struct CustomRules {
+    rules:      Arc<Vec<CustomRule>>,
+    generation: u64,
+    /// Log timestamps of each rule's matches this pull, oldest first.
+    hits:       Vec<VecDeque<u64>>,
+    /// Start of the pull the counts belong to.
+    pull_start: Option<u64>,
+}
+
+impl CustomRules {
+    pub fn evaluate(&mut self, input: &RuleInput, ctx: &RuleContext) -> RuleOutput {
+        let generation = GENERATION.load(Ordering::Relaxed);
+        if generation != self.generation {
+            if let Ok(installed) = INSTALLED.read() {
+                self.rules = installed.clone();
+            }
+            self.generation = generation;
+            self.hits.clear();
+        }
+        let pull_start = ctx.state.current_pull.as_ref().map(|p| p.start_ms);
+        if pull_start != self.pull_start || self.hits.len() != self.rules.len() {
+            self.pull_start = pull_start;
+            self.hits = vec![VecDeque::new(); self.rules.len()];
+        }
+
+        let mut out = Vec::new();
+        for (rule, hits) in self.rules.iter().zip(&mut self.hits) {
+            let Some(m) = rule.matches(input.event, ctx) else { continue };
+            hits.push_back(ctx.now_ms);
+            if rule.window_s > 0 {
+                let cutoff = ctx.now_ms.saturating_sub(rule.window_s as u64 * 1_000);
+                while hits.front().is_some_and(|&t| t < cutoff) {
+                    hits.pop_front();
+                }
+            }
+            let count = hits.len() as u32;
+            if count < rule.threshold {
+                continue;
+            }
+            out.push(advice(
+                &format!("{}{}", KEY, rule.name),
+                &rule.title,
+                rule.render(&m, count, ctx),
+                rule.severity.clone(),
+                vec![
+                    ("rule".to_owned(),  rule.name.clone()),
+                    ("count".to_owned(), count.to_string()),
+                ],
+                ctx.now_ms,
+            ));
+        }
+        out
+    }
+
+    /// Number of counted matches held (diagnostics).
+    pub fn tracked(&self) -> usize {
+        self.hits.iter().map(VecDeque::len).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{identity::PlayerIdentity, rules::RuleProfile, state::{CombatState, Pull}, units::NumberFormat};
+    use tempfile::tempdir;
+
+    const ME: &str = "Player-1-0000000A";
+
+    fn rules(raw: &str) -> Vec<CustomRule> {
+        toml::from_str::<RuleFile>(raw).unwrap().rule
+    }
+
+    fn hit(source: &str, target: &str, spell_id: u32) -> LogEvent {
+        LogEvent::SpellDamage {
+            timestamp_ms: 0,
+            source_guid: source.into(), source_name: "Ovinax".into(), source_flags: 0,
+            dest_guid: target.into(), dest_name: "Me".into(), dest_flags: 0, dest_raid_flags: 0,
+            spell_id, spell_name: "Egg Burst".into(), amount: 1_000, unit: None,
+        }
+    }
+
+    /// Run `events` (at their log time, ms) through `rules` in one pull and
+    /// return the advice count after each.
+    fn counts(rules: Vec<CustomRule>, events: &[(u64, LogEvent)]) -> Vec<usize> {
+        let mut state = CombatState::new();
+        state.player_guid  = Some(ME.into());
+        state.current_pull = Some(Pull { pull_number: 1, start_ms: 0, end_ms: None, outcome: None });
+        let identity = PlayerIdentity::unknown();
+        let fmt = NumberFormat::default();
+        let mut custom = CustomRules {
+            rules:      Arc::new(rules),
+            generation: GENERATION.load(Ordering::Relaxed),
+            ..Default::default()
+        };
+        events.iter().map(|(now_ms, event)| {
+            let ctx = RuleContext {
+                state: &state, identity: &identity, intensity: 3, now_ms: *now_ms,
+                profile: RuleProfile::Group, fmt: &fmt,
+            };
+            custom.evaluate(&RuleInput { event }, &ctx).len()
+        }).collect()
+    }
+
+    #[test]
+    fn load_dir_reports_bad_files_and_keeps_the_rest() {
+        let dir = tempdir().unwrap();
+        let good = "[[rule]]\nname = \"eggs\"\ntitle = \"Egg\"\nevent = \"spell_damage\"\nseverity = \"bad\"\nmessage = \"{spell}\"\n";
+        std::fs::write(dir.path().join("a_broken.toml"), "[[rule]]\nname = ").unwrap();
+        std::fs::write(dir.path().join("b_kind.toml"), good.replace("spell_damage", "spell_explode")).unwrap();
+        std::fs::write(dir.path().join("c_good.toml"), good).unwrap();
+        std::fs::write(dir.path().join("d_name.toml"), good.replace("eggs", "bad name")).unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "not a rule").unwrap();
+
+        let (loaded, errors) = load_dir(dir.path());
+        assert_eq!(loaded.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(), ["eggs"]);
+        assert_eq!(errors.len(), 3, "{:?}", errors);
+        assert!(errors[0].starts_with("a_broken.toml: "));
+        assert!(errors[1].starts_with("b_kind.toml: ") && errors[1].contains("spell_explode"), "{}", errors[1]);
+        assert!(errors[2].starts_with("d_name.toml: "));
+    }
+
+    #[test]
+    fn threshold_counts_matches_within_the_window() {
+        let rule = rules(r#"
+[[rule]]
+name      = "eggs"
+title     = "Egg"
+event     = "spell_damage"
+spell_ids = [442432]
+threshold = 2
+window_s  = 10
+severity  = "bad"
+message   = "{spell} hit you {count} times"
+"#);
+        let egg = || hit("Creature-0-1-1-1-214506-0", ME, 442432);
+        let other_spell = hit("Creature-0-1-1-1-214506-0", ME, 1);
+        assert_eq!(
+            counts(rule, &[(1_000, egg()), (2_000, other_spell), (5_000, egg()), (6_000, egg()), (30_000, egg())]),
+            [0, 0, 1, 1, 0],
+        );
+    }
+
+    #[test]
+    fn source_and_target_filters_use_the_player_guid() {
+        let rule = rules(r#"
+[[rule]]
+name     = "ff"
+title    = "Friendly fire"
+event    = "spell_damage"
+source   = "other"
+target   = "player"
+severity = "warn"
+message  = "{source} hit you"
+"#);
+        let ally = "Player-1-0000000B";
+        assert_eq!(
+            counts(rule, &[(1_000, hit(ally, ME, 1)), (2_000, hit(ME, ally, 1)), (3_000, hit(ME, ME, 1)), (4_000, hit(ally, ally, 1))]),
+            [1, 0, 0, 0],
+        );
+        assert!(party_matches(Party::Any, "", None));
+        assert!(!party_matches(Party::Player, ME, None));
+        assert!(party_matches(Party::Other, ME, None));
+    }
+
+    #[test]
+    fn malformed_rules_are_skipped_without_panicking() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("mixed.toml"), r#"
+[[rule]]
+name      = "zero"
+title     = "Never"
+event     = "unit_died"
+threshold = 0
+severity  = "bad"
+message   = "{count}"
+
+[[rule]]
+name     = "died"
+title    = "Died"
+event    = "unit_died"
+severity = "bad"
+message  = "{target} died"
+"#).unwrap();
+        std::fs::write(dir.path().join("unknown_field.toml"), "[[rule]]\nname = \"x\"\ncolour = \"red\"\n").unwrap();
+        std::fs::write(dir.path().join("empty.toml"), "").unwrap();
+
+        let (loaded, errors) = load_dir(dir.path());
+        assert_eq!(loaded.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(), ["died"]);
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert!(load_dir(&dir.path().join("missing")).0.is_empty());
+    }
+}
@@ -0,0 +1,143 @@
+/// Death recap — what the coached player's last seconds looked like.
+///
+/// On the player's UNIT_DIED, `build` walks the last RECAP_WINDOW_MS of
+/// state::EventWindow and collects damage taken, effective heals received
+/// and the player's own defensive and consumable casts (spec `am_spell_ids`,
+/// data/consumables.toml) into an ipc::DeathRecap, along with the potion or
+/// healthstone that was ready but not used (state::ConsumableTracker).  The engine sends it to the UI (`coach:death_recap`) and
+/// stores it with the pull.
+///
+/// `evaluate` turns the same recap into one advice card:
+///   Bad "Died to Void Bolt — 1.2M taken in 10s, 300K healed, no defensive used."
+///   Bad "Died to Void Bolt — 1.2M taken in 10s, 300K healed, last defensive 7.5s before."
+///   Bad "Died to Void Bolt — 1.2M taken in 10s, 300K healed, no defensive used; Healthstone ready but unused."
+///
+/// No intensity gate: a death always gets its line.
+use super::{advice, RuleContext, RuleInput, RuleOutput};
+use crate::{
+    consumables,
+    engine::Severity,
+    ipc::{DeathRecap, RecapEntry, RecapKind},
+    parser::{LogEvent, UnitState},
+    state::CombatState,
+};
+
+pub const KEY: &str = "death_recap";
+/// How far back the recap reaches.
+pub const RECAP_WINDOW_MS: u64 = 10_000;
+/// Newest entries kept — a raid boss can land dozens of ticks in 10s.
+const MAX_ENTRIES: usize = 40;
+
+/// Recap of a player death at `now_ms`; None while the player is unknown.
+pub fn build(state: &CombatState, am_spell_ids: &[u32], now_ms: u64) -> Option<DeathRecap> {
+    let player = state.player_guid.as_deref()?;
+    let hp = |unit: &Option<UnitState>| {
+        unit.as_ref().filter(|u| u.guid == player).and_then(|u| u.hp_pct())
+    };
+    let cutoff = now_ms.saturating_sub(RECAP_WINDOW_MS);
+
+    let mut entries = Vec::new();
+    for w in state.event_window.events.iter().filter(|w| w.timestamp_ms >= cutoff) {
+        let entry = |kind, source: &str, ability: &str, amount, hp_pct| RecapEntry {
+            before_ms: now_ms.saturating_sub(w.timestamp_ms),
+            kind,
+            source:    source.to_owned(),
+            ability:   ability.to_owned(),
+            amount,
+            hp_pct,
+        };
+        match &w.event {
+            LogEvent::SpellDamage { source_name, dest_guid, spell_name, amount, unit, .. }
+                if dest_guid == player =>
+            {
+                entries.push(entry(RecapKind::Damage, source_name, spell_name, *amount, hp(unit)));
+            }
+            LogEvent::SwingDamage { source_name, dest_guid, amount, unit, .. } if dest_guid == player => {
+                entries.push(entry(RecapKind::Damage, source_name, "Melee", *amount, hp(unit)));
+            }
+            LogEvent::SpellHeal { source_name, dest_guid, spell_name, amount, overhealing, unit, .. }
+                if dest_guid == player && amount > overhealing =>
+            {
+                entries.push(entry(RecapKind::Heal, source_name, spell_name, amount - overhealing, hp(unit)));
+            }
+            LogEvent::SpellCastSuccess { source_guid, source_name, spell_id, spell_name, .. }
+                if source_guid == player && am_spell_ids.contains(spell_id) =>
+            {
+                entries.push(entry(RecapKind::Defensive, source_name, spell_name, 0, None));
+            }
+            LogEvent::SpellCastSuccess { source_guid, source_name, spell_id, spell_name, .. }
+                if source_guid == player && consumables::kind_of(*spell_id).is_some() =>
+            {
+                entries.push(entry(RecapKind::Consumable, source_name, spell_name, 0, None));
+            }
+            _ => {}
+        }
+    }
+    if entries.len() > MAX_ENTRIES {
+        entries.drain(..entries.len() - MAX_ENTRIES);
+    }
+
+    let total = |kind| entries.iter().filter(|e| e.kind == kind).map(|e| e.amount).sum::<u64>();
+    let last_hit = entries.iter().rev().find(|e| e.kind == RecapKind::Damage);
+    Some(DeathRecap {
+        elapsed_ms:      state.pull_elapsed_ms(now_ms),
+        killing_blow:    last_hit.map_or_else(|| "unknown".to_owned(), |e| e.ability.clone()),
+        killer:          last_hit.map(|e| e.source.clone()).unwrap_or_default(),
+        damage_taken:    total(RecapKind::Damage),
+        heals_received:  total(RecapKind::Heal),
+        defensives_used: entries.iter().filter(|e| e.kind == RecapKind::Defensive).count() as u32,
+        window_ms:       RECAP_WINDOW_MS,
+        unused_consumables: state.consumables.ready(now_ms)
+            .into_iter()
+            .map(|kind| consumables::name(kind).to_owned())
+            .collect(),
+        entries,
+    })
+}
+
+pub fn evaluate(input: &RuleInput, ctx: &RuleContext, am_spell_ids: &[u32]) -> RuleOutput {
+    let LogEvent::UnitDied { dest_guid, .. } = input.event else {
+        return vec![];
+    };
+    if ctx.state.player_guid.as_deref() != Some(dest_guid.as_str()) {
+        return vec![];
+    }
+    // Nothing logged to explain it (fall damage, lava) — no card.
+    let Some(recap) = build(ctx.state, am_spell_ids, ctx.now_ms).filter(|r| r.damage_taken > 0) else {
+        return vec![];
+    };
+
+    let defensive = match recap.entries.iter().rev().find(|e| e.kind == RecapKind::Defensive) {
+        Some(d) => format!("last defensive {} before", ctx.fmt.seconds(d.before_ms, 1)),
+        None if am_spell_ids.is_empty() => String::new(),
+        None => "no defensive used".to_owned(),
+    };
+    let mut message = format!(
+        "Died to {} — {} taken in {}, {} healed",
+        recap.killing_blow,
+        ctx.fmt.amount(recap.damage_taken),
+        ctx.fmt.seconds(recap.window_ms, 0),
+        ctx.fmt.amount(recap.heals_received),
+    );
+    if !defensive.is_empty() {
+        message.push_str(", ");
+        message.push_str(&defensive);
+    }
+    if !recap.unused_consumables.is_empty() {
+        message.push_str(&format!("; {} ready but unused", recap.unused_consumables.join(" and ")));
+    }
+    message.push('.');
+
+    vec![advice(
+        KEY,
+        "Death Recap",
+        message,
+        Severity::Bad,
+        vec![
+            ("killing_blow".to_owned(), recap.killing_blow.clone()),
+            ("killer".to_owned(),       recap.killer.clone()),
+            ("damage_taken".to_owned(), recap.damage_taken.to_string()),
+        ],
+        ctx.now_ms,
+    )]
+}
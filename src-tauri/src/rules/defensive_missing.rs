@@ -0,0 +1,104 @@
+/// Fires Bad when the coached player takes a big hit with no active
+/// mitigation up — the inverse of defensive_timing.
+///
+/// A hit is big when it deals at least `defensive_missing_damage` (config),
+/// or at least `defensive_missing_pct` percent of the damage taken over the
+/// last LOOKBACK_MS (the hit included) while dealing at least a quarter of
+/// the flat threshold and never less than MIN_PCT_HIT, so a chip of damage
+/// after a quiet stretch does not count.  Either threshold can be set to 0
+/// to turn it off.
+///
+/// Fires when:
+///   - the spec has active mitigation spells (`am_ids`)
+///   - none of them was cast in the last ACTIVE_MS — most defensives and
+///     AM buffs last about that long
+///   - at least one of them was ready: cast earlier this session (so the
+///     player has it), with a `cooldown_s` entry it is off, or not cast yet
+///     this pull — a hit with every defensive on cooldown is not the
+///     player's mistake.  Consumables (consumables.rs) never count as ready
+///     defensives.
+///   - Intensity >= 2
+use std::collections::HashMap;
+
+use super::{advice, RuleContext, RuleInput, RuleOutput};
+use crate::{config::AppConfig, consumables, engine::Severity, parser::LogEvent, spells};
+
+pub const KEY: &str = "defensive_missing";
+const LOOKBACK_MS:   u64 = 10_000;
+const ACTIVE_MS:     u64 = 10_000;
+const MIN_INTENSITY: u8  = 2;
+/// Smallest hit the percentage threshold counts, even with the flat
+/// threshold turned off.
+const MIN_PCT_HIT:   u64 = 10_000;
+
+pub fn evaluate(
+    input:      &RuleInput,
+    ctx:        &RuleContext,
+    am_ids:     &[u32],
+    cooldown_s: &HashMap<u32, u32>,
+    config:     &AppConfig,
+) -> RuleOutput {
+    if am_ids.is_empty() || ctx.intensity < MIN_INTENSITY {
+        return vec![];
+    }
+    let Some(player) = ctx.state.player_guid.as_deref() else {
+        return vec![];
+    };
+    let (hit, amount) = match input.event {
+        LogEvent::SpellDamage { dest_guid, spell_name, amount, .. } if dest_guid == player => {
+            (spell_name.as_str(), *amount)
+        }
+        LogEvent::SwingDamage { dest_guid, amount, .. } if dest_guid == player => ("Melee", *amount),
+        _ => return vec![],
+    };
+
+    let flat = config.defensive_missing_damage;
+    let recent = ctx.state.damage_taken.recent_damage(ctx.now_ms, LOOKBACK_MS);
+    let big = (flat > 0 && amount >= flat)
+        || (config.defensive_missing_pct > 0
+            && amount >= (flat / 4).max(MIN_PCT_HIT)
+            && amount * 100 >= recent * config.defensive_missing_pct as u64);
+    if !big {
+        return vec![];
+    }
+
+    let cooldowns = &ctx.state.cooldowns;
+    let since = |id: u32| cooldowns.last_used_ms(id).map(|t| ctx.now_ms.saturating_sub(t));
+    if am_ids.iter().any(|&id| since(id).is_some_and(|ms| ms <= ACTIVE_MS)) {
+        return vec![];
+    }
+    let ready: Vec<String> = am_ids.iter()
+        .filter(|&&id| consumables::kind_of(id).is_none() && cooldowns.knows(id))
+        .filter(|&&id| match (since(id), cooldown_s.get(&id)) {
+            (_, None)             => false,
+            (None, Some(_))       => true,
+            (Some(ms), Some(&cd)) => ms >= cd as u64 * 1_000,
+        })
+        .map(|&id| spells::name(id).map_or_else(|| format!("spell {}", id), str::to_owned))
+        .collect();
+    if ready.is_empty() {
+        return vec![];
+    }
+
+    let hp = input.event.unit_state()
+        .filter(|u| u.guid == player)
+        .and_then(|u| u.hp_pct())
+        .map(|pct| format!(", down to {:.0}% health", pct))
+        .unwrap_or_default();
+    let ready = ready.join(" or ");
+    vec![advice(
+        KEY,
+        "No Defensive Up",
+        format!(
+            "{} hit you for {} with no defensive active{} — {} was ready.",
+            hit, ctx.fmt.amount(amount), hp, ready,
+        ),
+        Severity::Bad,
+        vec![
+            ("hit".to_owned(),    hit.to_owned()),
+            ("amount".to_owned(), ctx.fmt.amount(amount)),
+            ("ready".to_owned(),  ready),
+        ],
+        ctx.now_ms,
+    )]
+}
@@ -6,6 +6,7 @@
 /// Fires when:
 ///   - A spell in `am_ids` is cast by the coached player
 ///   - Damage taken in the last 5 seconds exceeds DAMAGE_THRESHOLD
+///     (shield absorbs included — a soaked hit is still pressure)
 ///   - Intensity >= 2
 ///
 /// The damage threshold (20,000) is a heuristic that scales reasonably
@@ -52,19 +53,21 @@ pub fn evaluate(input: &RuleInput, ctx: &RuleContext, am_ids: &[u32]) -> RuleOut
         return vec![];
     }
 
-    let dmg_k = recent_dmg / 1_000;
+    let dmg = ctx.fmt.amount(recent_dmg);
+    let absorbed = ctx.state.damage_taken.recent_absorbed(ctx.now_ms, WINDOW_MS);
+    let shielded = if absorbed > 0 { format!(" ({} absorbed)", ctx.fmt.amount(absorbed)) } else { String::new() };
 
     vec![advice(
         &format!("am_under_pressure_{}", spell_id),
         "Good AM Timing",
         format!(
-            "{} used under pressure — {}k damage in the last 5s.",
-            spell_name, dmg_k
+            "{} used under pressure — {} damage in the last 5s{}.",
+            spell_name, dmg, shielded
         ),
         Severity::Good,
         vec![
             ("spell".to_owned(),      spell_name.clone()),
-            ("recent_dmg".to_owned(), format!("{}k", dmg_k)),
+            ("recent_dmg".to_owned(), dmg),
         ],
         ctx.now_ms,
     )]
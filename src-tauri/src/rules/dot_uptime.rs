@@ -0,0 +1,77 @@
+/// Fires when one of the spec's primary DoTs falls off a boss mid-pull.
+///
+/// "DoT Dropped" — spec TOMLs list the DoTs worth maintaining
+/// (`[spec.rotation] dot_spell_ids`, aura IDs).  When one the player had on
+/// a boss this pull has been down for more than GRACE_MS:
+///   Warn "Agony fell off Queen Ansurek 4s ago — reapply it (86% uptime)."
+///
+/// Boss units are the encounter's `boss_npc_ids`, or the most-damaged enemy
+/// when the profile names none; trash pulls are not coached.  Coverage comes
+/// from state::BuffTracker, which forgets auras on units that died, so a
+/// dead add of a council fight does not count as a fall-off.
+///
+/// Time-driven rather than event-driven: evaluated on every in-combat event
+/// while the drop is fresh (REPORT_WINDOW_MS past the grace), with the
+/// fall-off timestamp in the advice key.  A key that already fired this pull
+/// (`advice_repeats`) is skipped, so each drop is reported once whatever the
+/// Warn advice cooldown is set to.
+///
+/// Intensity gate: fires at intensity >= 3.
+use super::{advice, RuleContext, RuleOutput};
+use crate::{engine::Severity, spells};
+
+pub const KEY: &str = "dot_uptime";
+const MIN_INTENSITY: u8 = 3;
+/// Down this long before it counts — covers a late pandemic refresh and the
+/// travel time of a reapplication.
+const GRACE_MS: u64 = 3_000;
+/// A drop is only reported this soon after the grace ends.
+const REPORT_WINDOW_MS: u64 = 5_000;
+
+pub fn evaluate(ctx: &RuleContext, dot_spell_ids: &[u32]) -> RuleOutput {
+    if ctx.intensity < MIN_INTENSITY || dot_spell_ids.is_empty() {
+        return vec![];
+    }
+    let buffs = &ctx.state.buffs;
+    let elapsed = ctx.state.pull_elapsed_ms(ctx.now_ms);
+
+    let mut out = Vec::new();
+    for (boss_guid, boss_name) in ctx.state.damage_done.boss_targets() {
+        for &spell_id in dot_spell_ids {
+            let Some(aura) = buffs.auras.get(&(boss_guid.to_owned(), spell_id)) else {
+                continue;
+            };
+            let Some(removed) = aura.removed_ms.filter(|_| aura.up_since.is_none()) else {
+                continue;
+            };
+            let down_ms = ctx.now_ms.saturating_sub(removed);
+            if down_ms <= GRACE_MS || down_ms > GRACE_MS + REPORT_WINDOW_MS {
+                continue;
+            }
+            let key = format!("{}_{}_{}", KEY, spell_id, removed);
+            if ctx.state.advice_repeats.contains_key(&key) {
+                continue;
+            }
+
+            let dot    = spells::name(spell_id).unwrap_or("Your DoT");
+            let boss   = if boss_name.is_empty() { "the boss" } else { boss_name };
+            let uptime = format!("{:.0}%", buffs.unit_uptime_pct(boss_guid, spell_id, elapsed));
+            out.push(advice(
+                &key,
+                "DoT Dropped",
+                format!(
+                    "{} fell off {} {} ago — reapply it ({} uptime).",
+                    dot, boss, ctx.fmt.seconds(down_ms, 0), uptime
+                ),
+                Severity::Warn,
+                vec![
+                    ("dot".to_owned(),    dot.to_owned()),
+                    ("target".to_owned(), boss.to_owned()),
+                    ("uptime".to_owned(), uptime),
+                ],
+                ctx.now_ms,
+            ));
+        }
+    }
+    out
+}
@@ -2,14 +2,22 @@
 ///
 /// The GCD tracker records the time between consecutive SPELL_CAST_SUCCESS events.
 /// A gap > 2.5s suggests the player stopped pressing buttons — either from a
-/// mechanic, positioning, or lost focus.
+/// mechanic, positioning, or lost focus.  The tracker attributes each gap to
+/// its likely cause (state::GapCause) from failed casts and encounter CC auras
+/// during it, and the message says which:
+///   - movement       → "2.8s gap during movement — use a mobile filler."
+///   - silence / CC   → the gap is named, no fix suggested (nothing to press)
+///   - idle           → "3.1s gap — idle, no input."
 ///
 /// Intensity gate: only fires at intensity >= 3 (Balanced or higher).
-use super::{advice, RuleContext, RuleInput, RuleOutput};
-use crate::{engine::Severity, parser::LogEvent};
+/// Solo content uses a longer threshold — without a healer, pausing to
+/// kite or self-heal is often the right call.
+use super::{advice, RuleContext, RuleInput, RuleOutput, RuleProfile};
+use crate::{engine::Severity, parser::LogEvent, state::GapCause};
 
 pub const KEY: &str = "gcd_gap";
 const THRESHOLD_MS: u64 = 2_500;
+const SOLO_THRESHOLD_MS: u64 = 4_000;
 /// Gaps longer than this are not reported — they indicate death + ress,
 /// a long boss mechanic (phase transition, forced downtime), or a missing
 /// data window from WoW's log buffer.  These are not actionable coaching moments.
@@ -30,23 +38,38 @@ pub fn evaluate(input: &RuleInput, ctx: &RuleContext) -> RuleOutput {
         return vec![];
     }
 
+    let threshold_ms = match ctx.profile {
+        RuleProfile::Group | RuleProfile::Practice => THRESHOLD_MS,
+        RuleProfile::Solo                          => SOLO_THRESHOLD_MS,
+    };
     let gap_ms = ctx.state.gcd.current_gap_ms;
-    if gap_ms < THRESHOLD_MS || gap_ms > MAX_GAP_MS {
+    if gap_ms < threshold_ms || gap_ms > MAX_GAP_MS {
         return vec![];
     }
 
-    let gap_s = gap_ms as f64 / 1_000.0;
+    let gap = ctx.fmt.seconds(gap_ms, 1);
+    let cause = ctx.state.gcd.gap_cause;
+    let message = match cause {
+        GapCause::Movement => format!(
+            "You had a {} gap during movement. Pre-position during mechanics and use a mobile filler.",
+            gap
+        ),
+        GapCause::Silenced     => format!("You had a {} gap while silenced.", gap),
+        GapCause::CrowdControl => format!("You had a {} gap under crowd control.", gap),
+        GapCause::Idle => format!(
+            "You had a {} gap — idle, no input. Keep the next button queued.",
+            gap
+        ),
+    };
 
     vec![advice(
         KEY,
         "Large GCD gap",
-        format!(
-            "You had a {:.1}s gap. Pre-position during mechanics and use a mobile filler.",
-            gap_s
-        ),
+        message,
         Severity::Warn,
         vec![
-            ("gap".to_owned(), format!("{:.1}s", gap_s)),
+            ("gap".to_owned(), gap),
+            ("cause".to_owned(), cause.label().to_owned()),
             ("phase".to_owned(), format!("P{}", ctx.state.pull_elapsed_ms(ctx.now_ms) / 60_000 + 1)),
         ],
         ctx.now_ms,
@@ -0,0 +1,89 @@
+/// Group coaching mode — the coached player's avoidable-damage and death
+/// coaching applied to every other player in the group, each advice tagged
+/// with the player it is about (`AdviceEvent.player`).
+///
+/// On when `AppConfig.group_coaching` is set: CombatState then keeps per-GUID
+/// trackers for each group player (`CombatState.members`).  Subscribed with
+/// Scope::Party, so only events on other group players reach it.
+///
+///   Bad "Thrall: Void Nova — 2 hits this pull."  Same thresholds as
+///       avoidable_repeat, but only with a known avoidable spell list:
+///       without one every raid-wide AoE would be blamed on the raid.
+///   Bad "Thrall died to Void Nova at 2:10."  First death of the pull.
+///
+/// Keyed per player and spell ("group_coaching:<guid>:<spell_id>") or per
+/// player ("group_coaching:<guid>:death"), so one member's mistakes never
+/// hold back another's.
+use super::{advice, RuleContext, RuleInput, RuleOutput};
+use crate::{engine::Severity, parser::LogEvent, state::DamageSource};
+
+pub const KEY: &str = "group_coaching";
+const MIN_HITS: u32 = 2;
+
+/// `avoidable`: the known avoidable spells of the current content, if any.
+pub fn evaluate(input: &RuleInput, ctx: &RuleContext, avoidable: Option<&[u32]>) -> RuleOutput {
+    match input.event {
+        LogEvent::SpellDamage { source_guid, source_flags, dest_guid, spell_id, spell_name, .. } => {
+            if !ctx.state.avoidable_source(DamageSource::of(source_guid, *source_flags, Some(dest_guid))) {
+                return vec![];
+            }
+            avoidable_hits(ctx, dest_guid, *spell_id, spell_name, avoidable)
+        }
+        LogEvent::UnitDied { dest_guid, .. } => death(ctx, dest_guid),
+        _ => vec![],
+    }
+}
+
+fn avoidable_hits(
+    ctx:        &RuleContext,
+    guid:       &str,
+    spell_id:   u32,
+    spell_name: &str,
+    avoidable:  Option<&[u32]>,
+) -> RuleOutput {
+    let Some(member) = ctx.state.members.get(guid) else {
+        return vec![];
+    };
+    if !avoidable.is_some_and(|ids| ids.contains(&spell_id)) {
+        return vec![];
+    }
+    let hits = member.avoidable.hit_count(spell_id);
+    if hits < MIN_HITS {
+        return vec![];
+    }
+
+    vec![advice(
+        &format!("{}:{}:{}", KEY, guid, spell_id),
+        "Avoidable damage repeating",
+        format!("{}: {} — {} hits this pull.", member.name, spell_name, hits),
+        Severity::Bad,
+        vec![
+            ("hits".to_owned(),     hits.to_string()),
+            ("spell".to_owned(),    spell_name.to_owned()),
+            ("spell_id".to_owned(), spell_id.to_string()),
+        ],
+        ctx.now_ms,
+    )
+    .with_player(&member.name)]
+}
+
+fn death(ctx: &RuleContext, guid: &str) -> RuleOutput {
+    let Some(member) = ctx.state.members.get(guid) else {
+        return vec![];
+    };
+    // Only the death just recorded — a battle-ressed player dying again
+    // keeps their first death.
+    let Some(death) = member.death.death.as_ref().filter(|d| d.elapsed_ms == ctx.state.pull_elapsed_ms(ctx.now_ms)) else {
+        return vec![];
+    };
+
+    vec![advice(
+        &format!("{}:{}:death", KEY, guid),
+        "Group member died",
+        format!("{} died to {} at {}.", member.name, death.cause, ctx.fmt.duration(death.elapsed_ms)),
+        Severity::Bad,
+        vec![("cause".to_owned(), death.cause.clone())],
+        ctx.now_ms,
+    )
+    .with_player(&member.name)]
+}
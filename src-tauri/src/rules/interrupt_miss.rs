@@ -6,13 +6,26 @@
 /// SpellInterrupted events (built up over the session). This rule only fires
 /// when we have direct evidence the player CAN and HAS kicked this spell before.
 ///
+/// Only blamed when it was the player's turn: their own kick was off
+/// cooldown, and no other group member's was.  Party kicks are estimated from
+/// the members' interrupt casts (InterruptTracker) and count while the member
+/// is still around (GroupTracker); members never seen kicking are assumed to
+/// have none ready.
+///
+/// The `caster` kv tells boss casts from trash casts (guid.rs; "trash" for
+/// every NPC when the encounter lists no `boss_npc_ids`).
+///
+/// With kick assignments for the encounter (`EncounterSettings`), only the
+/// assigned spells are coached — the rest belong to someone else — and an
+/// assigned spell is the player's turn whatever the others' kicks are doing.
+///
 /// Intensity gate: fires at intensity >= 3 (Balanced or higher).
 use super::{advice, RuleContext, RuleInput, RuleOutput};
-use crate::{engine::Severity, parser::LogEvent};
+use crate::{engine::Severity, guid::UnitKind, parser::LogEvent};
 
 const MIN_INTENSITY: u8 = 3;
 
-pub fn evaluate(input: &RuleInput, ctx: &RuleContext) -> RuleOutput {
+pub fn evaluate(input: &RuleInput, ctx: &RuleContext, assigned: &[u32]) -> RuleOutput {
     // We care about enemy SPELL_CAST_SUCCESS for spells we know are interruptible
     let LogEvent::SpellCastSuccess {
         source_guid,
@@ -30,7 +43,8 @@ pub fn evaluate(input: &RuleInput, ctx: &RuleContext) -> RuleOutput {
     }
 
     // Only fire for creature/vehicle (enemy) casts, not party members
-    if !source_guid.starts_with("Creature") && !source_guid.starts_with("Vehicle") {
+    let caster = ctx.state.unit_kind(source_guid);
+    if !caster.is_npc() {
         return vec![];
     }
 
@@ -39,6 +53,22 @@ pub fn evaluate(input: &RuleInput, ctx: &RuleContext) -> RuleOutput {
         return vec![];
     }
 
+    if !assigned.is_empty() && !assigned.contains(spell_id) {
+        return vec![];
+    }
+
+    // Not the player's turn: their kick was on cooldown, or someone else's
+    // was ready too.
+    let kicks = &ctx.state.interrupts;
+    if !kicks.kick_ready(ctx.now_ms) {
+        return vec![];
+    }
+    let others_ready = kicks.party_kicks_ready(ctx.now_ms)
+        .any(|(guid, _)| ctx.state.group.is_present(guid, ctx.now_ms));
+    if assigned.is_empty() && others_ready {
+        return vec![];
+    }
+
     // Only fire while in combat
     if !ctx.state.in_combat {
         return vec![];
@@ -51,11 +81,16 @@ pub fn evaluate(input: &RuleInput, ctx: &RuleContext) -> RuleOutput {
     vec![advice(
         &format!("interrupt_miss_{}", spell_id),
         "Missed Interrupt",
-        format!("{} went through — you can kick this.", spell_name),
+        if kicks.knows_party_kicks() {
+            format!("{} went through — yours was the only kick up.", spell_name)
+        } else {
+            format!("{} went through — you can kick this.", spell_name)
+        },
         Severity::Bad,
         vec![
             ("spell".to_owned(),    spell_name.clone()),
             ("spell_id".to_owned(), spell_id.to_string()),
+            ("caster".to_owned(),   if caster == UnitKind::Boss { "boss" } else { "trash" }.to_owned()),
         ],
         ctx.now_ms,
     )]
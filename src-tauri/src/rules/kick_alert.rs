@@ -0,0 +1,55 @@
+/// Fires Warn (urgent) while a kickable enemy cast is still in progress and
+/// the coached player's interrupt is ready.
+///
+/// "Kick now — [Spell] is casting."  The proactive half of interrupt_miss:
+/// that rule scolds once the cast went through, this one calls the kick
+/// while there is still time to land it.
+///
+/// Enemy casts are tracked from SPELL_CAST_START until the caster's success,
+/// interrupt, failure or death, or until they expire (EnemyCastTracker in
+/// state.rs).  A cast counts when the interrupt tracker has seen the player
+/// kick that spell before and the kick is off cooldown.  Runs on every
+/// in-combat event and on the engine tick, so a kick coming off cooldown
+/// mid-cast is still called.
+///
+/// With kick assignments for the encounter (`EncounterSettings`), only the
+/// assigned spells are called — the rest belong to someone else.
+///
+/// Keyed per spell ("kick_alert_<id>"), like interrupt_miss.
+///
+/// Intensity gate: fires at intensity >= 3 (Balanced or higher).
+use super::{advice, RuleContext, RuleOutput};
+use crate::engine::{Priority, Severity};
+
+pub const KEY: &str = "kick_alert";
+const MIN_INTENSITY: u8 = 3;
+
+pub fn evaluate(ctx: &RuleContext, assigned: &[u32]) -> RuleOutput {
+    if !ctx.state.in_combat || ctx.intensity < MIN_INTENSITY {
+        return vec![];
+    }
+    if !ctx.state.interrupts.kick_ready(ctx.now_ms) {
+        return vec![];
+    }
+
+    ctx.state.enemy_casts.in_progress(ctx.now_ms)
+        .into_iter()
+        .filter(|c| ctx.state.interrupts.is_interruptible(c.spell_id))
+        .filter(|c| assigned.is_empty() || assigned.contains(&c.spell_id))
+        .map(|c| {
+            advice(
+                &format!("{}_{}", KEY, c.spell_id),
+                "Kick Now",
+                format!("{} is casting — kick it.", c.spell_name),
+                Severity::Warn,
+                vec![
+                    ("spell".to_owned(),    c.spell_name.clone()),
+                    ("spell_id".to_owned(), c.spell_id.to_string()),
+                    ("cast_ms".to_owned(),  ctx.now_ms.saturating_sub(c.start_ms).to_string()),
+                ],
+                ctx.now_ms,
+            )
+            .with_priority(Priority::Urgent)
+        })
+        .collect()
+}
@@ -0,0 +1,67 @@
+/// Bloodlust/Heroism timing against the encounter's planned lust phase.
+///
+/// Encounter TOMLs may set `lust_phase = "P2"` (a name from
+/// `[[encounter.phases]]`).  When the lust buff lands on the coached player
+/// during that encounter:
+///   - in the planned phase → Good "Bloodlust in P2 — right on plan."
+///   - in any other phase   → Warn "Bloodlust in P1 — this fight saves it for P2."
+///   - before the first listed phase, or with none listed, the phase clause
+///     gives way to the fight time: "Bloodlust at 12.0s — …".
+///
+/// Lust is a group cooldown, so the signal is the buff on the player
+/// (SPELL_AURA_APPLIED), whoever cast it — drums and pet versions included
+/// (`state::is_lust`).
+///
+/// Intensity gate: fires at intensity >= 2.
+use super::{advice, RuleContext, RuleInput, RuleOutput};
+use crate::{encounters::EncounterProfile, engine::Severity, parser::LogEvent, state};
+
+const MIN_INTENSITY: u8 = 2;
+pub const KEY: &str = "lust_timing";
+
+pub fn evaluate(input: &RuleInput, ctx: &RuleContext, encounter: &EncounterProfile) -> RuleOutput {
+    let LogEvent::AuraApplied { dest_guid, spell_id, spell_name, .. } = input.event else {
+        return vec![];
+    };
+    if !state::is_lust(*spell_id)
+        || Some(dest_guid.as_str()) != ctx.state.player_guid.as_deref()
+    {
+        return vec![];
+    }
+    let Some(planned) = encounter.lust_phase.as_deref() else {
+        return vec![];
+    };
+    let Some(start) = ctx.state.encounter_start_ms else {
+        return vec![];
+    };
+    if ctx.intensity < MIN_INTENSITY {
+        return vec![];
+    }
+
+    let elapsed = ctx.now_ms.saturating_sub(start);
+    let actual  = encounter.phase_at(elapsed);
+    let at      = ctx.fmt.duration(elapsed);
+    let (severity, message) = match actual {
+        Some(phase) if phase.eq_ignore_ascii_case(planned) =>
+            (Severity::Good, format!("{} in {} — right on plan.", spell_name, phase)),
+        Some(phase) =>
+            (Severity::Warn, format!("{} in {} — this fight saves it for {}.", spell_name, phase, planned)),
+        None =>
+            (Severity::Warn, format!("{} at {} — this fight saves it for {}.", spell_name, at, planned)),
+    };
+    let mut metadata = vec![
+        ("planned".to_owned(), planned.to_owned()),
+        ("at".to_owned(),      at),
+    ];
+    if let Some(phase) = actual {
+        metadata.insert(0, ("phase".to_owned(), phase.to_owned()));
+    }
+    vec![advice(
+        KEY,
+        "Lust Timing",
+        message,
+        severity,
+        metadata,
+        ctx.now_ms,
+    )]
+}
@@ -0,0 +1,53 @@
+/// Fires when a melee player has been out of range of the boss for a while
+/// the rest of the group keeps hitting it.
+///
+/// "Out of Melee" — melee uptime is estimated from the cadence of the
+/// player's auto-attacks and boss-targeted casts (state::MeleeTracker).  A
+/// player counts as melee once they have auto-attacked this pull; casters
+/// and ranged hunters never do.  The boss is the encounter TOML's
+/// `boss_npc_ids`, or the most-damaged enemy on an encounter pull — trash
+/// pulls are not coached.
+///   Warn "8s out of melee on Sikran — get back in range."
+///
+/// Healers are not coached: a fistweaver leaves melee to heal.  The debrief
+/// reports the pull's uptime (`PullDebrief.melee_uptime`).
+///
+/// Time-driven: evaluated in pass 3.
+///
+/// Intensity gate: fires at intensity >= 3 (Balanced or higher).
+use super::{advice, RuleContext, RuleOutput};
+use crate::engine::Severity;
+
+pub const KEY: &str = "melee_uptime";
+const MIN_INTENSITY: u8 = 3;
+/// Time without touching the boss before it counts.
+const OUT_OF_RANGE_MS: u64 = 6_000;
+
+pub fn evaluate(ctx: &RuleContext, role: &str) -> RuleOutput {
+    if ctx.intensity < MIN_INTENSITY || role == "HEALER" {
+        return vec![];
+    }
+    let Some(away_ms) = ctx.state.melee.out_of_range_for(ctx.now_ms) else {
+        return vec![];
+    };
+    if away_ms < OUT_OF_RANGE_MS {
+        return vec![];
+    }
+
+    let bosses = ctx.state.damage_done.boss_targets();
+    let boss = bosses.first()
+        .map(|&(_, name)| name)
+        .filter(|name| !name.is_empty())
+        .unwrap_or("the boss");
+    vec![advice(
+        KEY,
+        "Out of Melee",
+        format!("{} out of melee on {} — get back in range.", ctx.fmt.seconds(away_ms, 0), boss),
+        Severity::Warn,
+        vec![
+            ("boss".to_owned(),    boss.to_owned()),
+            ("away_ms".to_owned(), away_ms.to_string()),
+        ],
+        ctx.now_ms,
+    )]
+}
@@ -1,17 +1,103 @@
+pub mod avoidable_dodged;
 pub mod avoidable_repeat;
+pub mod cancelled_cast;
+pub mod consumable_usage;
+pub mod cooldown_available;
 pub mod cooldown_drift;
+pub mod custom;
+pub mod death_recap;
+pub mod defensive_missing;
 pub mod defensive_timing;
+pub mod dot_uptime;
 pub mod gcd_gap;
+pub mod group_coaching;
 pub mod interrupt_miss;
 pub mod interrupt_success;
+pub mod kick_alert;
+pub mod lust_timing;
+pub mod melee_uptime;
+pub mod moving_cast;
+pub mod personal_record;
+pub mod progression_nudge;
+pub mod raid_buffs;
+pub mod raid_callouts;
+pub mod registry;
+pub mod resource_waste;
+pub mod script;
+pub mod self_sustain;
+pub mod spike_forecast;
+pub mod tank_healing;
+pub mod tunnel_vision;
 
 use crate::{
-    engine::{AdviceEvent, Severity},
+    engine::{AdviceEvent, Priority, Severity},
     identity::PlayerIdentity,
     parser::LogEvent,
     state::CombatState,
+    units::NumberFormat,
 };
 
+/// Rule names (settings, strategy presets) and the advice key prefix each
+/// rule fires under.
+pub const RULES: &[(&str, &str)] = &[
+    ("avoidable_dodged",   avoidable_dodged::KEY),
+    ("avoidable_repeat",   avoidable_repeat::KEY),
+    ("cancelled_cast",     cancelled_cast::KEY),
+    ("consumable_usage",   consumable_usage::KEY),
+    ("cooldown_available", cooldown_available::KEY),
+    ("cooldown_drift",     cooldown_drift::KEY),
+    ("custom",             custom::KEY),
+    ("death_recap",        death_recap::KEY),
+    ("defensive_missing",  defensive_missing::KEY),
+    ("defensive_timing",   "am_under_pressure"),
+    ("dot_uptime",         dot_uptime::KEY),
+    ("gcd_gap",            gcd_gap::KEY),
+    ("group_coaching",     group_coaching::KEY),
+    ("interrupt_miss",     "interrupt_miss"),
+    ("interrupt_success",  "interrupt_success"),
+    ("kick_alert",         kick_alert::KEY),
+    ("lust_timing",        lust_timing::KEY),
+    ("melee_uptime",       melee_uptime::KEY),
+    ("moving_cast",        moving_cast::KEY),
+    ("personal_record",    personal_record::KEY),
+    ("progression_nudge",  progression_nudge::KEY),
+    ("raid_buffs",         raid_buffs::KEY),
+    ("raid_callout",       raid_callouts::KEY),
+    ("resource_waste",     resource_waste::KEY),
+    ("script",             script::KEY),
+    ("self_sustain",       self_sustain::KEY),
+    ("spike_forecast",     "spike_forecast"),
+    ("tank_healing",       tank_healing::KEY),
+    ("tunnel_vision",      tunnel_vision::KEY),
+];
+
+/// Name of the rule an advice key was fired under (longest matching prefix).
+pub fn rule_of(advice_key: &str) -> Option<&'static str> {
+    RULES.iter()
+        .filter(|(_, prefix)| advice_key.starts_with(prefix))
+        .max_by_key(|(_, prefix)| prefix.len())
+        .map(|(name, _)| *name)
+}
+
+/// True if `advice_key` belongs to one of the `disabled` rule names.
+pub fn is_disabled(advice_key: &str, disabled: &[String]) -> bool {
+    RULES.iter().any(|(name, prefix)| {
+        advice_key.starts_with(prefix) && disabled.iter().any(|d| d == name)
+    })
+}
+
+/// Which rule set applies to the current content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleProfile {
+    /// Regular 5-man / raid group — the default.
+    Group,
+    /// Player alone with NPC allies (follower dungeons, delves): no interrupt
+    /// blame, more lenient thresholds, self-sustain coaching.
+    Solo,
+    /// Only training dummies fought: rotation rules only (see practice.rs).
+    Practice,
+}
+
 /// Read-only context passed to every rule evaluator.
 pub struct RuleContext<'a> {
     pub state:    &'a CombatState,
@@ -20,6 +106,9 @@ pub struct RuleContext<'a> {
     /// Coaching intensity from user settings (1 = quiet, 5 = aggressive)
     pub intensity: u8,
     pub now_ms:   u64,
+    pub profile:  RuleProfile,
+    /// User's number/duration format — use for every number in advice text.
+    pub fmt:      &'a NumberFormat,
 }
 
 /// The current event being evaluated.
@@ -48,7 +137,14 @@ pub fn advice(
         title:        title.to_owned(),
         message,
         severity,
+        priority:     Priority::Normal,
         kv,
         timestamp_ms: now_ms,
+        wallclock_ms: 0, // stamped by the engine when the advice fires
+        hint:         None,
+        player:       None,
+        repeat:       0, // counted by the engine's dedup
+        escalation:   0,
+        debug_id:     None, // assigned by the engine when the advice is stored
     }
 }
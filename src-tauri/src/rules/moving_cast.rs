@@ -0,0 +1,39 @@
+/// Fires when the coached player keeps trying to cast while moving.  Every
+/// SPELL_CAST_FAILED is counted by reason (state::FailedCastTracker); the
+/// ones failing because the player was moving are the coachable kind —
+/// stop before the cast, or use the spec's instants on the move.
+///
+/// Warns when the movement failures of the pull reach THRESHOLD, and again
+/// at every further THRESHOLD.  The debrief lists the failures by reason.
+///
+/// Intensity >= 2.
+use super::{advice, RuleContext, RuleInput, RuleOutput};
+use crate::{engine::Severity, parser::LogEvent, state::GapCause};
+
+pub const KEY: &str = "moving_cast";
+const THRESHOLD:     u32 = 3;
+const MIN_INTENSITY: u8  = 2;
+
+pub fn evaluate(input: &RuleInput, ctx: &RuleContext) -> RuleOutput {
+    let LogEvent::SpellCastFailed { failed_type, .. } = input.event else {
+        return vec![];
+    };
+    if ctx.intensity < MIN_INTENSITY || GapCause::from_failed_type(failed_type) != Some(GapCause::Movement) {
+        return vec![];
+    }
+    let moving = ctx.state.failed_casts.movement();
+    if moving == 0 || !moving.is_multiple_of(THRESHOLD) {
+        return vec![];
+    }
+    vec![advice(
+        KEY,
+        "Casting While Moving",
+        format!(
+            "{} casts failed because you were moving this pull — stop before casting, or use an instant on the move.",
+            moving,
+        ),
+        Severity::Warn,
+        vec![("moving".to_owned(), moving.to_string())],
+        ctx.now_ms,
+    )]
+}
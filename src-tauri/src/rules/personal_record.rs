@@ -0,0 +1,50 @@
+/// Personal-record advisory after a boss kill.
+///
+/// "New PR" — at ENCOUNTER_END with a kill the engine checks the pull
+/// against the character's records for that encounter and difficulty
+/// (records.rs).  Records the kill beat are summarised in one advisory:
+///   Good "New PR on Heroic Sikran: fastest kill 3:12 (was 3:25)."
+///
+/// A first kill sets the records without an advisory.  Not event-driven:
+/// called by the engine with the DB's answer, like progression_nudge.
+///
+/// Intensity gate: none — at most one per kill.
+use super::{advice, RuleContext, RuleOutput};
+use crate::{
+    engine::Severity,
+    records::{Kill, RecordUpdate},
+};
+
+pub const KEY: &str = "personal_record";
+
+pub fn evaluate(ctx: &RuleContext, kill: &Kill, updates: &[RecordUpdate]) -> RuleOutput {
+    let beaten: Vec<String> = updates
+        .iter()
+        .filter_map(|u| {
+            let previous = u.previous?;
+            Some(format!(
+                "{} {} (was {})",
+                u.kind.label(),
+                u.kind.format(u.value, ctx.fmt),
+                u.kind.format(previous, ctx.fmt),
+            ))
+        })
+        .collect();
+    if beaten.is_empty() {
+        return vec![];
+    }
+
+    let kv = updates
+        .iter()
+        .filter(|u| u.previous.is_some())
+        .map(|u| (u.kind.key().to_owned(), u.kind.format(u.value, ctx.fmt)))
+        .collect();
+    vec![advice(
+        KEY,
+        "New PR",
+        format!("New PR on {}: {}.", kill.label(), beaten.join(", ")),
+        Severity::Good,
+        kv,
+        ctx.now_ms,
+    )]
+}
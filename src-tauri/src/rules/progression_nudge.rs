@@ -0,0 +1,53 @@
+/// Pull-over-pull nudge when the same encounter is attempted again.
+///
+/// "Last Pull" — on ENCOUNTER_START the engine looks up the previous finished
+/// pull of the encounter in this session (`db::last_attempt`) and, if it was
+/// a wipe, summarises it while the player still has a moment to read:
+///   - died   → Warn "Last pull: died to Defile at 2:10 — watch around 2:00."
+///   - alive  → Good "Last pull: wiped at 4:32 and you survived it — same again."
+///
+/// Kills are not summarised.  Not event-driven: called once per pull by the
+/// engine rather than from the rule passes.
+///
+/// Intensity gate: fires at intensity >= 2.
+use super::{advice, RuleContext, RuleOutput};
+use crate::{db::PreviousAttempt, engine::Severity};
+
+const MIN_INTENSITY: u8 = 2;
+/// "Watch around" is this much before the previous death.
+const WATCH_LEAD_MS: u64 = 10_000;
+pub const KEY: &str = "progression_nudge";
+
+pub fn evaluate(ctx: &RuleContext, previous: &PreviousAttempt) -> RuleOutput {
+    if ctx.intensity < MIN_INTENSITY || previous.outcome != "wipe" {
+        return vec![];
+    }
+
+    let attempt = ("attempt".to_owned(), (previous.attempts + 1).to_string());
+    let (severity, message, mut kv) = match &previous.death {
+        Some(death) => (
+            Severity::Warn,
+            format!(
+                "Last pull: died to {} at {} — watch around {}.",
+                death.cause,
+                ctx.fmt.duration(death.elapsed_ms),
+                ctx.fmt.duration(death.elapsed_ms.saturating_sub(WATCH_LEAD_MS)),
+            ),
+            vec![
+                ("died_to".to_owned(), death.cause.clone()),
+                ("at".to_owned(),      ctx.fmt.duration(death.elapsed_ms)),
+            ],
+        ),
+        None => (
+            Severity::Good,
+            format!(
+                "Last pull: wiped at {} and you survived it — same again.",
+                ctx.fmt.duration(previous.duration_ms),
+            ),
+            vec![("wiped_at".to_owned(), ctx.fmt.duration(previous.duration_ms))],
+        ),
+    };
+    kv.push(attempt);
+
+    vec![advice(KEY, "Last Pull", message, severity, kv, ctx.now_ms)]
+}
@@ -0,0 +1,49 @@
+/// Fires a few seconds into a boss pull when the coached player is missing
+/// a raid buff the group could have given them.
+///
+/// "Missing Raid Buffs" — the buffs come from `data/raid_buffs.toml`
+/// (raid_buffs.rs); one is expected when a player of its class is in the
+/// group by the encounter's COMBATANT_INFO lines, which also carry the
+/// player's auras at the pull (state::AuraTracker):
+///   Warn "No Arcane Intellect, no Skyfury on you — ask for them before the next pull."
+///
+/// Encounter pulls only, between CHECK_AFTER_MS and CHECK_UNTIL_MS in: a
+/// buff cast at the pull still lands, and a late one has nothing to fix
+/// until the next pull.  The Warn cooldown keeps it to one advice a pull.
+///
+/// Time-driven: evaluated in pass 3.
+///
+/// Intensity gate: fires at intensity >= 2.
+use super::{advice, RuleContext, RuleOutput};
+use crate::{engine::Severity, raid_buffs};
+
+pub const KEY: &str = "raid_buffs";
+const MIN_INTENSITY:  u8  = 2;
+const CHECK_AFTER_MS: u64 = 3_000;
+const CHECK_UNTIL_MS: u64 = 10_000;
+
+pub fn evaluate(ctx: &RuleContext) -> RuleOutput {
+    if ctx.intensity < MIN_INTENSITY {
+        return vec![];
+    }
+    let Some(start) = ctx.state.encounter_start_ms else {
+        return vec![];
+    };
+    if !(CHECK_AFTER_MS..CHECK_UNTIL_MS).contains(&ctx.now_ms.saturating_sub(start)) {
+        return vec![];
+    }
+    let missing = raid_buffs::missing(&ctx.state.group_classes, &ctx.state.auras.active);
+    if missing.is_empty() {
+        return vec![];
+    }
+
+    let them = if missing.len() > 1 { "them" } else { "it" };
+    vec![advice(
+        KEY,
+        "Missing Raid Buffs",
+        format!("No {} on you — ask for {} before the next pull.", missing.join(", no "), them),
+        Severity::Warn,
+        vec![("missing".to_owned(), missing.join(", "))],
+        ctx.now_ms,
+    )]
+}
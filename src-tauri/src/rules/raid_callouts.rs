@@ -0,0 +1,160 @@
+/// Raid-lead callouts — avoidable hits on anyone in the group, called out in
+/// batches instead of coached one by one, plus a paste-ready per-player
+/// summary at pull end.
+///
+/// On when `AppConfig.raid_lead_mode` is set.  Hits only count when the
+/// active encounter or dungeon lists its avoidable spells (encounters.rs);
+/// without a list every raid-wide AoE would be blamed on the raid.
+///
+/// Hits are gathered for BATCH_MS after the first one, then called out
+/// together, at most every CALLOUT_INTERVAL_MS:
+///   Warn "Void Nova: Brann ×2, Alleria · Shadow Surge: Thrall"
+/// Each callout fires under its own key ("raid_callout:<n>"), so the advice
+/// cooldown never swallows a batch.  The batch also flushes on the engine
+/// tick, so hits at the end of a burst are not held until the next event.
+///
+/// `summary` ranks the pull's members by hits for raid chat or Discord:
+///   Avoidable damage — Pull 3, Broodtwister Ovi'nax (wipe 4:12)
+///   1. Brann — 5 hits, 1.2M: Void Nova ×3, Shadow Surge ×2
+///   2. Alleria — 2 hits, 400K: Void Nova ×2
+use std::collections::HashMap;
+
+use super::{advice, RuleContext, RuleInput, RuleOutput};
+use crate::{engine::Severity, parser::LogEvent, state, units::NumberFormat};
+
+pub const KEY: &str = "raid_callout";
+/// Hits gathered into one callout.
+const BATCH_MS: u64 = 4_000;
+/// Minimum spacing between callouts.
+const CALLOUT_INTERVAL_MS: u64 = 10_000;
+/// Spells named per callout; the rest are left to the summary.
+const MAX_SPELLS: usize = 3;
+
+/// One member's avoidable hits this pull.
+#[derive(Debug, Default)]
+struct MemberHits {
+    name:   String,
+    hits:   u32,
+    amount: u64,
+    /// (spell, hits) in the order first taken.
+    spells: Vec<(String, u32)>,
+}
+
+/// Per-pull raid-lead tracking, owned by the engine.
+#[derive(Debug, Default)]
+pub struct RaidCallouts {
+    /// dest GUID -> hits this pull.
+    members:       HashMap<String, MemberHits>,
+    /// (spell, member name) since the last callout.
+    pending:       Vec<(String, String)>,
+    pending_since: u64,
+    last_callout:  Option<u64>,
+    callouts:      u32,
+    /// An avoidable list was known at some point this pull.
+    known:         bool,
+    /// Start of the pull the counts belong to.
+    pull_start:    Option<u64>,
+}
+
+fn bump(list: &mut Vec<(String, u32)>, name: &str) {
+    match list.iter_mut().find(|(n, _)| n == name) {
+        Some((_, count)) => *count += 1,
+        None             => list.push((name.to_owned(), 1)),
+    }
+}
+
+fn counted(name: &str, count: u32) -> String {
+    if count > 1 { format!("{} ×{}", name, count) } else { name.to_owned() }
+}
+
+impl RaidCallouts {
+    /// Record an avoidable hit on a group member, then flush a due batch.
+    /// `avoidable`: the known avoidable spells of the current content.
+    pub fn evaluate(&mut self, input: &RuleInput, ctx: &RuleContext, avoidable: Option<&[u32]>) -> RuleOutput {
+        let pull_start = ctx.state.current_pull.as_ref().map(|p| p.start_ms);
+        if pull_start != self.pull_start {
+            *self = Self { pull_start, ..Self::default() };
+        }
+        let Some(avoidable) = avoidable else { return self.flush(ctx) };
+        self.known = true;
+
+        if let LogEvent::SpellDamage { dest_guid, dest_name, dest_flags, spell_id, spell_name, amount, .. } = input.event {
+            if state::is_group_player(*dest_flags) && avoidable.contains(spell_id) {
+                let member = self.members.entry(dest_guid.clone()).or_default();
+                if member.name.is_empty() {
+                    member.name = dest_name.split('-').next().unwrap_or(dest_name).to_owned();
+                }
+                member.hits   += 1;
+                member.amount += amount;
+                bump(&mut member.spells, spell_name);
+                if self.pending.is_empty() {
+                    self.pending_since = ctx.now_ms;
+                }
+                self.pending.push((spell_name.clone(), member.name.clone()));
+            }
+        }
+        self.flush(ctx)
+    }
+
+    /// Call out the pending hits once the batch is complete.
+    pub fn flush(&mut self, ctx: &RuleContext) -> RuleOutput {
+        if self.pending.is_empty()
+            || ctx.now_ms.saturating_sub(self.pending_since) < BATCH_MS
+            || self.last_callout.is_some_and(|t| ctx.now_ms.saturating_sub(t) < CALLOUT_INTERVAL_MS)
+        {
+            return vec![];
+        }
+
+        // spell -> [(member, hits)], both in the order first seen.
+        let mut by_spell: Vec<(String, Vec<(String, u32)>)> = Vec::new();
+        let hits = self.pending.len();
+        for (spell, member) in self.pending.drain(..) {
+            match by_spell.iter_mut().find(|(s, _)| *s == spell) {
+                Some((_, members)) => bump(members, &member),
+                None               => by_spell.push((spell, vec![(member, 1)])),
+            }
+        }
+        let message = by_spell.iter()
+            .take(MAX_SPELLS)
+            .map(|(spell, members)| {
+                let names: Vec<String> = members.iter().map(|(n, c)| counted(n, *c)).collect();
+                format!("{}: {}", spell, names.join(", "))
+            })
+            .collect::<Vec<_>>()
+            .join(" · ");
+
+        self.last_callout = Some(ctx.now_ms);
+        self.callouts += 1;
+        vec![advice(
+            &format!("{}:{}", KEY, self.callouts),
+            "Avoidable hits",
+            message,
+            Severity::Warn,
+            vec![("hits".to_owned(), hits.to_string())],
+            ctx.now_ms,
+        )]
+    }
+
+    /// Paste-ready per-member summary of the pull, most hits first; None
+    /// when no avoidable spell list applied.
+    pub fn summary(&self, header: &str, fmt: &NumberFormat) -> Option<String> {
+        if !self.known {
+            return None;
+        }
+        let mut members: Vec<&MemberHits> = self.members.values().collect();
+        members.sort_by(|a, b| b.hits.cmp(&a.hits).then(b.amount.cmp(&a.amount)).then(a.name.cmp(&b.name)));
+        let mut lines = vec![format!("Avoidable damage — {}", header)];
+        if members.is_empty() {
+            lines.push("No avoidable hits.".to_owned());
+        }
+        for (rank, m) in members.iter().enumerate() {
+            let spells: Vec<String> = m.spells.iter().map(|(s, c)| counted(s, *c)).collect();
+            lines.push(format!(
+                "{}. {} — {} hit{}, {}: {}",
+                rank + 1, m.name, m.hits, if m.hits == 1 { "" } else { "s" },
+                fmt.amount(m.amount), spells.join(", "),
+            ));
+        }
+        Some(lines.join("\n"))
+    }
+}
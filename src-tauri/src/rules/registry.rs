@@ -0,0 +1,455 @@
+/// Rule registry — which built-in rule runs on which events.
+///
+/// Each rule declares a subscription instead of being chained by hand in
+/// engine.rs:
+///   - `Trigger::Event` — the LogEvent kinds it reads, and the `Scope` the
+///     event must fall in (coached player, enemy, party member, any)
+///   - `Trigger::Time` — nothing in particular: it runs on every in-combat
+///     event and on the engine tick (DoT fall-offs, neglected adds, …)
+///
+/// plus the rule profiles it runs under and whether it needs combat.
+/// `RuleBus` indexes REGISTRY by event kind once, so an event only reaches
+/// the rules that read it, and rules disabled for the encounter are skipped
+/// before they run.
+///
+/// Engine-owned inputs (spec profile spells, encounter data, thresholds)
+/// reach the rules through `RuleParams`.  Adding a rule: write its module,
+/// add it to `RULES` (mod.rs) and give it a `RuleSpec` here.
+///
+/// User-authored rules, scripts and raid callouts keep state of their own
+/// and stay engine passes (see engine.rs).
+use std::collections::HashMap;
+
+use super::{
+    avoidable_dodged, avoidable_repeat, cancelled_cast, consumable_usage, cooldown_available, cooldown_drift,
+    death_recap, defensive_missing, defensive_timing, dot_uptime, gcd_gap, group_coaching, interrupt_miss,
+    interrupt_success, kick_alert, lust_timing, melee_uptime, moving_cast, raid_buffs, resource_waste,
+    self_sustain, spike_forecast, tank_healing, tunnel_vision, RuleContext, RuleInput, RuleOutput, RuleProfile,
+};
+use crate::{
+    config::AppConfig,
+    encounters::EncounterProfile,
+    guid::{self, UnitKind},
+    parser::{EventKind, LogEvent},
+    state::CombatState,
+};
+
+// ---------------------------------------------------------------------------
+// Subscriptions
+// ---------------------------------------------------------------------------
+
+/// Whose events an event rule wants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    /// The coached player's side of the event: their casts, heals and
+    /// kicks, damage and misses on them, deaths and encounter boundaries.
+    Player,
+    /// Cast or damage by an NPC.
+    Enemy,
+    /// Another player of the group as source or target.
+    Party,
+    Any,
+}
+
+impl Scope {
+    fn matches(self, event: &LogEvent, state: &CombatState) -> bool {
+        let player = state.player_guid.as_deref();
+        match self {
+            Self::Player => is_coached_event(event, player),
+            Self::Enemy  => event.source_guid().is_some_and(|g| guid::kind(g).is_npc()),
+            Self::Party  => [event.source_guid(), event.dest_guid()]
+                .into_iter()
+                .flatten()
+                .any(|g| Some(g) != player && guid::kind(g) == UnitKind::Player),
+            Self::Any    => true,
+        }
+    }
+}
+
+fn is_coached_event(event: &LogEvent, guid: Option<&str>) -> bool {
+    match event {
+        LogEvent::SpellCastSuccess { source_guid, .. } => Some(source_guid.as_str()) == guid,
+        LogEvent::SpellDamage { dest_guid, .. }        => Some(dest_guid.as_str()) == guid,
+        LogEvent::SpellHeal { source_guid, .. }        => Some(source_guid.as_str()) == guid,
+        LogEvent::SwingDamage { dest_guid, .. }        => Some(dest_guid.as_str()) == guid,
+        LogEvent::SpellMissed { dest_guid, .. }        => Some(dest_guid.as_str()) == guid,
+        LogEvent::SpellAbsorbed { dest_guid, .. }      => Some(dest_guid.as_str()) == guid,
+        LogEvent::Energize { dest_guid, .. }           => Some(dest_guid.as_str()) == guid,
+        LogEvent::SpellInterrupted { source_guid, .. } => Some(source_guid.as_str()) == guid,
+        LogEvent::UnitDied { .. }                      => true,
+        LogEvent::EncounterStart { .. }                => true,
+        LogEvent::EncounterEnd { .. }                  => true,
+        LogEvent::ArenaMatchStart { .. }               => true,
+        LogEvent::ArenaMatchEnd { .. }                 => true,
+        LogEvent::ZoneChange { .. }                    => false,
+        LogEvent::ChallengeModeStart { .. }            => false,
+        LogEvent::ChallengeModeEnd { .. }              => false,
+        LogEvent::CombatantInfo { .. }                 => false,
+        LogEvent::SpellCastFailed { source_guid, .. } => Some(source_guid.as_str()) == guid,
+        LogEvent::SpellCastStart { source_guid, .. }  => Some(source_guid.as_str()) == guid,
+        // Aura rules (lust_timing) subscribe with Scope::Any.
+        LogEvent::AuraApplied { .. }
+        | LogEvent::AuraRefreshed { .. }
+        | LogEvent::AuraRemoved { .. } => false,
+    }
+}
+
+/// Engine-owned inputs of the built-in rules.
+pub struct RuleParams<'a> {
+    pub config:           &'a AppConfig,
+    /// Spec profile role ("TANK" / "HEALER" / "DAMAGER"), or empty.
+    pub role:             &'a str,
+    pub major_cds:        &'a [u32],
+    pub am_spells:        &'a [u32],
+    pub dot_spell_ids:    &'a [u32],
+    pub cooldown_s:       &'a HashMap<u32, u32>,
+    /// Avoidable spells of the current content, if any are known.
+    pub avoidable:        Option<&'a [u32]>,
+    pub kick_assignments: &'a [u32],
+    pub encounter:        Option<&'a EncounterProfile>,
+}
+
+pub type EventRule = fn(&RuleInput, &RuleContext, &RuleParams) -> RuleOutput;
+pub type TimeRule  = fn(&RuleContext, &RuleParams) -> RuleOutput;
+
+pub enum Trigger {
+    Event(&'static [EventKind], Scope, EventRule),
+    Time(TimeRule),
+}
+
+pub struct RuleSpec {
+    /// Rule name as in `RULES` — disabled rules are skipped by it.
+    pub name:        &'static str,
+    pub profiles:    &'static [RuleProfile],
+    /// Only while a pull is in progress.  Time rules always are.
+    pub combat_only: bool,
+    pub trigger:     Trigger,
+}
+
+const ALL:          &[RuleProfile] = &[RuleProfile::Group, RuleProfile::Solo, RuleProfile::Practice];
+const NOT_PRACTICE: &[RuleProfile] = &[RuleProfile::Group, RuleProfile::Solo];
+/// Solo content has no interrupt assignments to coach against.
+const GROUP:        &[RuleProfile] = &[RuleProfile::Group];
+const SOLO:         &[RuleProfile] = &[RuleProfile::Solo];
+
+// ---------------------------------------------------------------------------
+// Registry
+// ---------------------------------------------------------------------------
+
+/// Built-in rules in evaluation order: enemy rules, coached player rules,
+/// then time-driven and aura rules.  Practice (training dummy) keeps only
+/// the rotation rules gcd_gap and cooldown_drift.
+pub static REGISTRY: &[RuleSpec] = &[
+    // Enemy events
+    RuleSpec {
+        name:        "interrupt_miss",
+        profiles:    GROUP,
+        combat_only: true,
+        trigger:     Trigger::Event(&[EventKind::SpellCastSuccess], Scope::Enemy,
+            |i, c, p| interrupt_miss::evaluate(i, c, p.kick_assignments)),
+    },
+    RuleSpec {
+        name:        "kick_alert",
+        profiles:    GROUP,
+        combat_only: true,
+        trigger:     Trigger::Time(|c, p| kick_alert::evaluate(c, p.kick_assignments)),
+    },
+    // Coached player events
+    RuleSpec {
+        name:        "avoidable_repeat",
+        profiles:    NOT_PRACTICE,
+        combat_only: false,
+        trigger:     Trigger::Event(&[EventKind::SpellDamage], Scope::Player,
+            |i, c, p| avoidable_repeat::evaluate(i, c, p.avoidable)),
+    },
+    RuleSpec {
+        name:        "avoidable_dodged",
+        profiles:    NOT_PRACTICE,
+        combat_only: false,
+        trigger:     Trigger::Event(&[EventKind::SpellMissed], Scope::Player,
+            |i, c, p| avoidable_dodged::evaluate(i, c, p.avoidable)),
+    },
+    RuleSpec {
+        name:        "resource_waste",
+        profiles:    NOT_PRACTICE,
+        combat_only: false,
+        trigger:     Trigger::Event(&[EventKind::Energize], Scope::Player,
+            |i, c, p| resource_waste::evaluate(i, c, p.config.resource_waste_pct)),
+    },
+    RuleSpec {
+        name:        "gcd_gap",
+        profiles:    ALL,
+        combat_only: false,
+        trigger:     Trigger::Event(&[EventKind::SpellCastSuccess], Scope::Player,
+            |i, c, _| gcd_gap::evaluate(i, c)),
+    },
+    RuleSpec {
+        name:        "cooldown_drift",
+        profiles:    ALL,
+        combat_only: false,
+        trigger:     Trigger::Event(&[EventKind::SpellCastSuccess], Scope::Player,
+            |i, c, p| cooldown_drift::evaluate(i, c, p.major_cds)),
+    },
+    RuleSpec {
+        name:        "cancelled_cast",
+        profiles:    NOT_PRACTICE,
+        combat_only: true,
+        trigger:     Trigger::Event(&[EventKind::SpellCastStart, EventKind::SpellCastFailed], Scope::Player,
+            |i, c, _| cancelled_cast::evaluate(i, c)),
+    },
+    RuleSpec {
+        name:        "moving_cast",
+        profiles:    NOT_PRACTICE,
+        combat_only: true,
+        trigger:     Trigger::Event(&[EventKind::SpellCastFailed], Scope::Player,
+            |i, c, _| moving_cast::evaluate(i, c)),
+    },
+    RuleSpec {
+        name:        "interrupt_success",
+        profiles:    NOT_PRACTICE,
+        combat_only: false,
+        trigger:     Trigger::Event(&[EventKind::SpellInterrupted], Scope::Player,
+            |i, c, _| interrupt_success::evaluate(i, c)),
+    },
+    RuleSpec {
+        name:        "defensive_timing",
+        profiles:    NOT_PRACTICE,
+        combat_only: false,
+        trigger:     Trigger::Event(&[EventKind::SpellCastSuccess], Scope::Player,
+            |i, c, p| defensive_timing::evaluate(i, c, p.am_spells)),
+    },
+    RuleSpec {
+        name:        "defensive_missing",
+        profiles:    NOT_PRACTICE,
+        combat_only: true,
+        trigger:     Trigger::Event(&[EventKind::SpellDamage, EventKind::SwingDamage], Scope::Player,
+            |i, c, p| defensive_missing::evaluate(i, c, p.am_spells, p.cooldown_s, p.config)),
+    },
+    RuleSpec {
+        name:        "self_sustain",
+        profiles:    SOLO,
+        combat_only: false,
+        trigger:     Trigger::Event(&[EventKind::SpellDamage, EventKind::SwingDamage], Scope::Player,
+            |i, c, p| self_sustain::evaluate(i, c, p.am_spells)),
+    },
+    RuleSpec {
+        name:        "consumable_usage",
+        profiles:    NOT_PRACTICE,
+        combat_only: false,
+        trigger:     Trigger::Event(
+            &[EventKind::SpellCastSuccess, EventKind::SpellDamage, EventKind::SwingDamage, EventKind::UnitDied],
+            Scope::Player,
+            |i, c, _| consumable_usage::evaluate(i, c),
+        ),
+    },
+    RuleSpec {
+        name:        "death_recap",
+        profiles:    NOT_PRACTICE,
+        combat_only: false,
+        trigger:     Trigger::Event(&[EventKind::UnitDied], Scope::Player,
+            |i, c, p| death_recap::evaluate(i, c, p.am_spells)),
+    },
+    // Other group members (group coaching mode)
+    RuleSpec {
+        name:        "group_coaching",
+        profiles:    GROUP,
+        combat_only: true,
+        trigger:     Trigger::Event(&[EventKind::SpellDamage, EventKind::UnitDied], Scope::Party,
+            |i, c, p| group_coaching::evaluate(i, c, p.avoidable)),
+    },
+    // Time-driven
+    RuleSpec {
+        name:        "dot_uptime",
+        profiles:    NOT_PRACTICE,
+        combat_only: true,
+        trigger:     Trigger::Time(|c, p| dot_uptime::evaluate(c, p.dot_spell_ids)),
+    },
+    RuleSpec {
+        name:        "tunnel_vision",
+        profiles:    NOT_PRACTICE,
+        combat_only: true,
+        trigger:     Trigger::Time(|c, p| tunnel_vision::evaluate(c, p.role)),
+    },
+    RuleSpec {
+        name:        "melee_uptime",
+        profiles:    NOT_PRACTICE,
+        combat_only: true,
+        trigger:     Trigger::Time(|c, p| melee_uptime::evaluate(c, p.role)),
+    },
+    RuleSpec {
+        name:        "cooldown_available",
+        profiles:    NOT_PRACTICE,
+        combat_only: true,
+        trigger:     Trigger::Time(|c, p| cooldown_available::evaluate(
+            c, p.major_cds, p.cooldown_s, p.am_spells, p.config.cd_unused_alert_s as u64 * 1_000,
+        )),
+    },
+    RuleSpec {
+        name:        "spike_forecast",
+        profiles:    NOT_PRACTICE,
+        combat_only: true,
+        trigger:     Trigger::Time(|c, p| match p.encounter {
+            Some(enc) if p.config.spike_forecast_lead_s > 0 => {
+                let lead_ms = p.config.spike_forecast_lead_s as u64 * 1_000;
+                spike_forecast::evaluate(c, &enc.timeline, p.role, lead_ms)
+            }
+            _ => vec![],
+        }),
+    },
+    RuleSpec {
+        name:        "tank_healing",
+        profiles:    NOT_PRACTICE,
+        combat_only: true,
+        trigger:     Trigger::Time(|c, p| match p.encounter {
+            Some(enc) => tank_healing::evaluate(c, p.role, enc.tank_heal_share_pct),
+            None      => vec![],
+        }),
+    },
+    RuleSpec {
+        name:        "raid_buffs",
+        profiles:    NOT_PRACTICE,
+        combat_only: true,
+        trigger:     Trigger::Time(|c, _| raid_buffs::evaluate(c)),
+    },
+    // The lust buff comes from another player's cast.
+    RuleSpec {
+        name:        "lust_timing",
+        profiles:    NOT_PRACTICE,
+        combat_only: true,
+        trigger:     Trigger::Event(&[EventKind::AuraApplied], Scope::Any, |i, c, p| match p.encounter {
+            Some(enc) => lust_timing::evaluate(i, c, enc),
+            None      => vec![],
+        }),
+    },
+];
+
+// ---------------------------------------------------------------------------
+// Dispatch
+// ---------------------------------------------------------------------------
+
+/// REGISTRY indexed by event kind, built once by the engine.
+pub struct RuleBus {
+    /// Per `EventKind as usize`: the event rules subscribed to it and every
+    /// time rule, in registry order.
+    by_kind: Vec<Vec<&'static RuleSpec>>,
+    time:    Vec<&'static RuleSpec>,
+}
+
+impl RuleBus {
+    pub fn new() -> Self {
+        let by_kind = (0..EventKind::COUNT)
+            .map(|kind| {
+                REGISTRY.iter()
+                    .filter(|spec| match &spec.trigger {
+                        Trigger::Event(kinds, ..) => kinds.iter().any(|k| *k as usize == kind),
+                        Trigger::Time(_)          => true,
+                    })
+                    .collect()
+            })
+            .collect();
+        let time = REGISTRY.iter().filter(|spec| matches!(spec.trigger, Trigger::Time(_))).collect();
+        Self { by_kind, time }
+    }
+
+    /// Run the rules subscribed to `event`.
+    pub fn dispatch(
+        &self,
+        event:    &LogEvent,
+        ctx:      &RuleContext,
+        params:   &RuleParams,
+        disabled: &[String],
+    ) -> RuleOutput {
+        let input = RuleInput { event };
+        self.by_kind[event.kind() as usize]
+            .iter()
+            .filter(|spec| applies(spec, ctx, disabled))
+            .flat_map(|spec| match &spec.trigger {
+                Trigger::Event(_, scope, rule) if scope.matches(event, ctx.state) => rule(&input, ctx, params),
+                Trigger::Event(..)                                               => vec![],
+                Trigger::Time(rule)                                              => rule(ctx, params),
+            })
+            .collect()
+    }
+
+    /// Run the time rules without an event (engine tick).
+    pub fn tick(&self, ctx: &RuleContext, params: &RuleParams, disabled: &[String]) -> RuleOutput {
+        self.time
+            .iter()
+            .filter(|spec| applies(spec, ctx, disabled))
+            .flat_map(|spec| match &spec.trigger {
+                Trigger::Time(rule) => rule(ctx, params),
+                Trigger::Event(..)  => vec![],
+            })
+            .collect()
+    }
+}
+
+fn applies(spec: &RuleSpec, ctx: &RuleContext, disabled: &[String]) -> bool {
+    (ctx.state.in_combat || !spec.combat_only)
+        && spec.profiles.contains(&ctx.profile)
+        && !disabled.iter().any(|d| d == spec.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{identity::PlayerIdentity, units::NumberFormat};
+
+    const ME: &str = "Player-1-0000000A";
+
+    fn names(specs: &[&RuleSpec]) -> Vec<&'static str> {
+        specs.iter().map(|spec| spec.name).collect()
+    }
+
+    #[test]
+    fn bus_indexes_each_rule_under_its_event_kinds() {
+        let bus = RuleBus::new();
+        assert_eq!(bus.by_kind.len(), EventKind::COUNT);
+        let time = names(&bus.time);
+        assert!(time.contains(&"dot_uptime") && !time.contains(&"gcd_gap"));
+
+        for kind in EventKind::ALL {
+            let expected: Vec<_> = REGISTRY.iter()
+                .filter(|spec| match &spec.trigger {
+                    Trigger::Event(kinds, ..) => kinds.contains(&kind),
+                    Trigger::Time(_)          => true,
+                })
+                .map(|spec| spec.name)
+                .collect();
+            assert_eq!(names(&bus.by_kind[kind as usize]), expected, "{:?}", kind);
+        }
+        let failed = names(&bus.by_kind[EventKind::SpellCastFailed as usize]);
+        assert!(failed.contains(&"cancelled_cast") && failed.contains(&"moving_cast"));
+        assert!(!failed.contains(&"gcd_gap"));
+    }
+
+    #[test]
+    fn dispatch_runs_subscribed_rules_in_scope_profile_and_enablement() {
+        let bus = RuleBus::new();
+        let config = AppConfig::default();
+        let identity = PlayerIdentity::unknown();
+        let fmt = NumberFormat::default();
+        let cooldown_s = HashMap::new();
+        let params = RuleParams {
+            config: &config, role: "", major_cds: &[], am_spells: &[], dot_spell_ids: &[],
+            cooldown_s: &cooldown_s, avoidable: None, kick_assignments: &[], encounter: None,
+        };
+        let mut state = CombatState::new();
+        state.player_guid = Some(ME.into());
+        let kick = |source: &str| LogEvent::SpellInterrupted {
+            timestamp_ms: 0, source_guid: source.into(), target_guid: "Creature-0-1".into(),
+            interrupted_spell_id: 123, interrupted_spell: "Shadow Bolt".into(),
+        };
+        let run = |event: &LogEvent, profile: RuleProfile, disabled: &[String]| -> Vec<String> {
+            let ctx = RuleContext { state: &state, identity: &identity, intensity: 3, now_ms: 1_000, profile, fmt: &fmt };
+            bus.dispatch(event, &ctx, &params, disabled).into_iter().map(|a| a.key).collect()
+        };
+
+        assert_eq!(run(&kick(ME), RuleProfile::Group, &[]), ["interrupt_success_123"]);
+        assert!(run(&kick("Player-1-0000000B"), RuleProfile::Group, &[]).is_empty(), "someone else's kick");
+        assert!(run(&kick(ME), RuleProfile::Practice, &[]).is_empty(), "not a practice rule");
+        assert!(run(&kick(ME), RuleProfile::Group, &["interrupt_success".into()]).is_empty());
+        let died = LogEvent::UnitDied { timestamp_ms: 0, dest_guid: "Creature-0-1".into(), dest_name: "Add".into() };
+        assert!(run(&died, RuleProfile::Group, &[]).iter().all(|k| !k.starts_with("interrupt_success")));
+    }
+}
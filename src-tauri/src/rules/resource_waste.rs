@@ -0,0 +1,57 @@
+/// Fires Warn when the coached player keeps generating a resource they are
+/// already capped on.
+///
+/// "Resource Capped" — SPELL_ENERGIZE logs the part of each gain lost to the
+/// cap (ResourceTracker in state.rs).  Rage, energy, focus, holy power and
+/// the other spenders' resources count; mana does not.
+///
+/// Fires when:
+///   - A gain of the coached player overflowed the cap
+///   - This pull at least one full bar of that resource has been wasted
+///   - The wasted share of everything generated exceeds `max_waste_pct`
+///     (`AppConfig.resource_waste_pct`; 0 disables the rule)
+///
+/// Keyed per power type ("resource_waste:<type>").
+use super::{advice, RuleContext, RuleInput, RuleOutput};
+use crate::{
+    engine::Severity,
+    parser::{power_name, LogEvent},
+};
+
+pub const KEY: &str = "resource_waste";
+
+pub fn evaluate(input: &RuleInput, ctx: &RuleContext, max_waste_pct: u32) -> RuleOutput {
+    if max_waste_pct == 0 {
+        return vec![];
+    }
+    let LogEvent::Energize { dest_guid, power_type, overflow, .. } = input.event else {
+        return vec![];
+    };
+    if Some(dest_guid.as_str()) != ctx.state.player_guid.as_deref() || *overflow == 0 {
+        return vec![];
+    }
+    let Some(flow) = ctx.state.resources.flow(*power_type) else {
+        return vec![];
+    };
+    let waste_pct = flow.waste_pct();
+    if flow.wasted < u64::from(flow.max.max(1)) || waste_pct <= max_waste_pct as f32 {
+        return vec![];
+    }
+
+    let resource = power_name(*power_type);
+    vec![advice(
+        &format!("{}:{}", KEY, power_type),
+        "Resource Capped",
+        format!(
+            "{} {} lost to overcapping this pull ({:.0}% of generated) — spend before you cap.",
+            flow.wasted, resource, waste_pct
+        ),
+        Severity::Warn,
+        vec![
+            ("resource".to_owned(),  resource.to_owned()),
+            ("wasted".to_owned(),    flow.wasted.to_string()),
+            ("waste_pct".to_owned(), format!("{:.0}", waste_pct)),
+        ],
+        ctx.now_ms,
+    )]
+}
@@ -0,0 +1,336 @@
+/// Scripted rules — `<app config>/rules/*.rhai` files run by an embedded
+/// Rhai interpreter, for spec-specific logic that the declarative rules
+/// (rules/custom.rs) cannot express and that should not need an app fork.
+///
+/// A script defines
+///
+///   fn evaluate(event, ctx) {
+///       if event.type != "SpellCastSuccess" || event.source_guid != ctx.player_guid { return []; }
+///       if event.spell_id == 315508 { this.rolls = (this.rolls ?? 0) + 1; }
+///       if this.rolls == 4 {
+///           return [#{ key: "rtb", title: "Roll the Bones",
+///                      message: `${this.rolls} rolls this pull`, severity: "warn" }];
+///       }
+///       []
+///   }
+///
+/// and is called with every in-combat event:
+///   event — the parsed LogEvent as a map, `type` naming the variant
+///           ("SpellDamage", "SpellCastSuccess", …) and its fields by name
+///   ctx   — player_guid, player (name, realm, class, spec), encounter,
+///           pull_elapsed_ms, now_ms, intensity, profile ("group" | "solo")
+///   this  — a map the script may keep its own state in; emptied at every
+///           pull
+///
+/// It returns an array of advice maps {key, title, message, severity}; the
+/// advice fires under "script:<file stem>:<key>", spaced by the advice
+/// cooldowns like the built-in rules.  `disabled_rules = ["script"]`
+/// silences every script.
+///
+/// Scripts cannot reach files or the network (`import` resolves no
+/// modules), and each call is capped
+/// (`MAX_OPERATIONS`, sizes below) so a runaway loop cannot stall the engine.
+/// A script whose call fails is switched off until the next reload, with a
+/// warning in the log.  Files are loaded alongside the TOML rules (`load_dir`
+/// + `install`) when the pipeline starts and on every config reload.
+use once_cell::sync::Lazy;
+use rhai::{module_resolvers::DummyModuleResolver, Array, CallFnOptions, Dynamic, Engine, Map, Scope, AST};
+use serde::Deserialize;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use super::{advice, RuleContext, RuleInput, RuleOutput, RuleProfile};
+use crate::engine::Severity;
+
+/// Advice key prefix of every scripted rule.
+pub const KEY: &str = "script:";
+
+/// Interpreter steps one `evaluate` call may take.
+const MAX_OPERATIONS: u64 = 50_000;
+/// Advice a script may return per event; the rest is dropped.
+const MAX_ADVICE: usize = 4;
+
+/// Shared interpreter with the sandbox limits applied.
+static ENGINE: Lazy<Engine> = Lazy::new(|| {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_call_levels(32)
+        .set_max_expr_depths(64, 32)
+        .set_max_string_size(4 * 1024)
+        .set_max_array_size(1_024)
+        .set_max_map_size(1_024)
+        .on_print(|text| tracing::info!("Rule script: {}", text))
+        .on_debug(|text, _, pos| tracing::debug!("Rule script {}: {}", pos, text));
+    engine.disable_symbol("eval");
+    engine.set_module_resolver(DummyModuleResolver::new());
+    engine
+});
+
+/// One compiled script.
+pub struct ScriptRule {
+    /// File stem; advice keys are "script:<name>:<key>".
+    pub name: String,
+    ast:      AST,
+}
+
+/// One advice map returned by a script.
+#[derive(Deserialize)]
+struct ScriptAdvice {
+    key:      String,
+    title:    String,
+    message:  String,
+    severity: Severity,
+}
+
+// ---------------------------------------------------------------------------
+// Loading
+// ---------------------------------------------------------------------------
+
+/// Compile every `*.rhai` in `dir` (alphabetical).  Returns the scripts that
+/// define `evaluate(event, ctx)` and one message per file left out.
+pub fn load_dir(dir: &Path) -> (Vec<ScriptRule>, Vec<String>) {
+    let mut paths: Vec<_> = std::fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|e| e.path()).collect())
+        .unwrap_or_default();
+    paths.retain(|p| p.extension().is_some_and(|ext| ext == "rhai"));
+    paths.sort();
+
+    let mut scripts = Vec::new();
+    let mut errors  = Vec::new();
+    for path in paths {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        let name = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+        let compiled = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|src| ENGINE.compile(src).map_err(|e| e.to_string()));
+        let ast = match compiled {
+            Ok(ast) => ast,
+            Err(e)  => { errors.push(format!("{}: {}", file_name, e)); continue; }
+        };
+        if !ast.iter_functions().any(|f| f.name == "evaluate" && f.params.len() == 2) {
+            errors.push(format!("{}: no fn evaluate(event, ctx)", file_name));
+            continue;
+        }
+        scripts.push(ScriptRule { name, ast });
+    }
+    (scripts, errors)
+}
+
+/// The script set the engine runs.
+static INSTALLED: Lazy<RwLock<Arc<Vec<ScriptRule>>>> = Lazy::new(Default::default);
+/// Bumped by every `install`.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Replace the running script set.
+pub fn install(scripts: Vec<ScriptRule>) {
+    if let Ok(mut installed) = INSTALLED.write() {
+        *installed = Arc::new(scripts);
+        GENERATION.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Runner
+// ---------------------------------------------------------------------------
+
+/// The engine's copy of the installed scripts with their per-pull state.
+#[derive(Default)]
+pub struct ScriptRules {
+    scripts:    Arc<Vec<ScriptRule>>,
+    generation: u64,
+    /// Each script's `this` map.
+    state:      Vec<Dynamic>,
+    /// Scripts switched off after a failed call.
+    failed:     Vec<bool>,
+    /// Start of the pull the state belongs to.
+    pull_start: Option<u64>,
+}
+
+fn context_map(ctx: &RuleContext) -> Map {
+    let state = ctx.state;
+    let pull_start = state.current_pull.as_ref().map(|p| p.start_ms);
+    let mut map = Map::new();
+    map.insert("player_guid".into(), state.player_guid.clone().unwrap_or_default().into());
+    map.insert("player".into(), rhai::serde::to_dynamic(ctx.identity).unwrap_or_default());
+    map.insert("encounter".into(), state.encounter_name.clone().unwrap_or_default().into());
+    map.insert("pull_elapsed_ms".into(), (pull_start.map_or(0, |s| ctx.now_ms.saturating_sub(s)) as i64).into());
+    map.insert("now_ms".into(), (ctx.now_ms as i64).into());
+    map.insert("intensity".into(), (ctx.intensity as i64).into());
+    let profile = match ctx.profile {
+        RuleProfile::Group    => "group",
+        RuleProfile::Solo     => "solo",
+        RuleProfile::Practice => "practice",
+    };
+    map.insert("profile".into(), profile.into());
+    map
+}
+
+impl ScriptRules {
+    pub fn evaluate(&mut self, input: &RuleInput, ctx: &RuleContext) -> RuleOutput {
+        let generation = GENERATION.load(Ordering::Relaxed);
+        if generation != self.generation {
+            if let Ok(installed) = INSTALLED.read() {
+                self.scripts = installed.clone();
+            }
+            self.generation = generation;
+            self.failed = vec![false; self.scripts.len()];
+            self.state.clear();
+        }
+        if self.scripts.is_empty() {
+            return Vec::new();
+        }
+        let pull_start = ctx.state.current_pull.as_ref().map(|p| p.start_ms);
+        if pull_start != self.pull_start || self.state.len() != self.scripts.len() {
+            self.pull_start = pull_start;
+            self.state = vec![Dynamic::from_map(Map::new()); self.scripts.len()];
+        }
+
+        let Ok(event) = rhai::serde::to_dynamic(input.event) else { return Vec::new() };
+        let context = Dynamic::from_map(context_map(ctx));
+        let mut out = Vec::new();
+        for ((script, this), failed) in self.scripts.iter().zip(&mut self.state).zip(&mut self.failed) {
+            if *failed {
+                continue;
+            }
+            let options = CallFnOptions::new().eval_ast(false).bind_this_ptr(this);
+            let result = ENGINE
+                .call_fn_with_options::<Array>(options, &mut Scope::new(), &script.ast, "evaluate", (event.clone(), context.clone()))
+                .map_err(|e| e.to_string())
+                .and_then(|items| {
+                    items.iter()
+                        .map(|item| rhai::serde::from_dynamic::<ScriptAdvice>(item).map_err(|e| e.to_string()))
+                        .collect::<Result<Vec<_>, _>>()
+                });
+            let items = match result {
+                Ok(items) => items,
+                Err(e) => {
+                    tracing::warn!("Rule script '{}' switched off until the next reload: {}", script.name, e);
+                    *failed = true;
+                    continue;
+                }
+            };
+            for item in items.into_iter().take(MAX_ADVICE) {
+                out.push(advice(
+                    &format!("{}{}:{}", KEY, script.name, item.key),
+                    &item.title,
+                    item.message,
+                    item.severity,
+                    vec![("script".to_owned(), script.name.clone())],
+                    ctx.now_ms,
+                ));
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{identity::PlayerIdentity, parser::LogEvent, state::{CombatState, Pull}, units::NumberFormat};
+    use tempfile::{tempdir, TempDir};
+
+    /// Counts the events of the pull; fires on the second one.
+    const COUNTER: &str = r#"
+fn evaluate(event, ctx) {
+    this.seen = (this.seen ?? 0) + 1;
+    if this.seen == 2 {
+        return [#{ key: "second", title: "Second", message: `${this.seen} events`, severity: "warn" }];
+    }
+    []
+}
+"#;
+
+    fn scripts_dir(files: &[(&str, &str)]) -> TempDir {
+        let dir = tempdir().unwrap();
+        for (name, src) in files {
+            std::fs::write(dir.path().join(name), src).unwrap();
+        }
+        dir
+    }
+
+    fn runner(files: &[(&str, &str)]) -> ScriptRules {
+        let (scripts, errors) = load_dir(scripts_dir(files).path());
+        assert!(errors.is_empty(), "{:?}", errors);
+        ScriptRules {
+            failed:     vec![false; scripts.len()],
+            scripts:    Arc::new(scripts),
+            generation: GENERATION.load(Ordering::Relaxed),
+            ..Default::default()
+        }
+    }
+
+    /// Run one event per `(pull start, now)` and return the advice keys of each.
+    fn run(rules: &mut ScriptRules, calls: &[(u64, u64)]) -> Vec<Vec<String>> {
+        let identity = PlayerIdentity::unknown();
+        let fmt = NumberFormat::default();
+        let event = LogEvent::UnitDied { timestamp_ms: 0, dest_guid: "Creature-0-1".into(), dest_name: "Add".into() };
+        calls.iter().map(|&(start_ms, now_ms)| {
+            let mut state = CombatState::new();
+            state.current_pull = Some(Pull { pull_number: 1, start_ms, end_ms: None, outcome: None });
+            let ctx = RuleContext {
+                state: &state, identity: &identity, intensity: 3, now_ms,
+                profile: RuleProfile::Group, fmt: &fmt,
+            };
+            rules.evaluate(&RuleInput { event: &event }, &ctx).into_iter().map(|a| a.key).collect()
+        }).collect()
+    }
+
+    #[test]
+    fn load_dir_reports_compile_errors_and_missing_evaluate() {
+        let dir = scripts_dir(&[
+            ("a_syntax.rhai", "fn evaluate(event, ctx) { let = ; }"),
+            ("b_none.rhai",   "fn check(event) { [] }"),
+            ("c_good.rhai",   COUNTER),
+            ("notes.txt",     "not a script"),
+        ]);
+        let (scripts, errors) = load_dir(dir.path());
+        assert_eq!(scripts.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(), ["c_good"]);
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert!(errors[0].starts_with("a_syntax.rhai: "));
+        assert_eq!(errors[1], "b_none.rhai: no fn evaluate(event, ctx)");
+    }
+
+    #[test]
+    fn this_keeps_state_between_events_until_the_next_pull() {
+        let mut rules = runner(&[("counter.rhai", COUNTER)]);
+        let fired = run(&mut rules, &[(0, 1_000), (0, 2_000), (0, 3_000), (10_000, 11_000), (10_000, 12_000)]);
+        let second = vec!["script:counter:second".to_owned()];
+        assert_eq!(fired, [vec![], second.clone(), vec![], vec![], second]);
+    }
+
+    #[test]
+    fn failing_scripts_are_switched_off_and_stay_off() {
+        let mut rules = runner(&[
+            ("a_runaway.rhai", "fn evaluate(event, ctx) { loop { } }"),
+            ("b_flaky.rhai",   "fn evaluate(event, ctx) { this.n = (this.n ?? 0) + 1; if this.n == 1 { throw \"boom\"; } [] }"),
+            ("c_badmap.rhai",  "fn evaluate(event, ctx) { [#{ key: \"k\" }] }"),
+            ("d_counter.rhai", COUNTER),
+        ]);
+        // The op limit, a thrown error and a malformed advice map each switch
+        // their script off; the healthy one keeps running.
+        let fired = run(&mut rules, &[(0, 1_000), (0, 2_000)]);
+        assert_eq!(fired, [vec![], vec!["script:d_counter:second".to_owned()]]);
+        assert_eq!(rules.failed, [true, true, true, false]);
+
+        // b_flaky would succeed now, and a new pull resets `this`, but a
+        // switched-off script waits for the next reload.
+        run(&mut rules, &[(10_000, 11_000)]);
+        assert_eq!(rules.failed, [true, true, true, false]);
+    }
+
+    #[test]
+    fn scripts_cannot_import_files() {
+        let lib = scripts_dir(&[(
+            "helper.rhai",
+            "fn advice() { [#{ key: \"imported\", title: \"T\", message: \"M\", severity: \"warn\" }] }",
+        )]);
+        let helper = lib.path().join("helper").to_string_lossy().replace('\\', "/");
+        let script = format!("fn evaluate(event, ctx) {{ import \"{}\" as h; h::advice() }}", helper);
+        let mut rules = runner(&[("importer.rhai", &script)]);
+        assert_eq!(run(&mut rules, &[(0, 1_000)]), [Vec::<String>::new()]);
+        assert_eq!(rules.failed, [true]);
+    }
+}
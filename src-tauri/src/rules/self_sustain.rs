@@ -0,0 +1,67 @@
+/// Fires Warn in solo content when the player soaks heavy damage without
+/// using any defensive or self-heal.
+///
+/// "No Healer Here" — in follower dungeons and delves nobody else will top
+/// the player up, so sustained damage must be answered with personal tools.
+///
+/// Fires when:
+///   - The rule profile is Solo
+///   - The coached player takes damage and the last 5 seconds total
+///     DAMAGE_THRESHOLD or more
+///   - No spell in `am_ids` and no potion or healthstone
+///     (data/consumables.toml) has been used in the last QUIET_MS
+///     (without a spec profile, only consumables count)
+///   - Intensity >= 2
+///
+/// Priority: urgent — unanswered damage with no healer is how solo runs end.
+use super::{advice, RuleContext, RuleInput, RuleOutput, RuleProfile};
+use crate::{engine::{Priority, Severity}, parser::LogEvent};
+
+const DAMAGE_THRESHOLD: u64 = 30_000;
+const WINDOW_MS:        u64 = 5_000;
+/// A defensive used within this window counts as "already reacting".
+const QUIET_MS:         u64 = 15_000;
+const MIN_INTENSITY:    u8  = 2;
+
+pub const KEY: &str = "self_sustain";
+
+pub fn evaluate(input: &RuleInput, ctx: &RuleContext, am_ids: &[u32]) -> RuleOutput {
+    if ctx.profile != RuleProfile::Solo || ctx.intensity < MIN_INTENSITY {
+        return vec![];
+    }
+
+    let dest_guid = match input.event {
+        LogEvent::SpellDamage { dest_guid, .. } | LogEvent::SwingDamage { dest_guid, .. } => dest_guid,
+        _ => return vec![],
+    };
+    if Some(dest_guid.as_str()) != ctx.state.player_guid.as_deref() {
+        return vec![];
+    }
+
+    let recent_dmg = ctx.state.damage_taken.recent_damage(ctx.now_ms, WINDOW_MS);
+    if recent_dmg < DAMAGE_THRESHOLD {
+        return vec![];
+    }
+
+    let recent = |t: u64| ctx.now_ms.saturating_sub(t) <= QUIET_MS;
+    let reacted = am_ids.iter().any(|id| ctx.state.cooldowns.last_used_ms(*id).is_some_and(recent))
+        || ctx.state.consumables.last_use_ms().is_some_and(recent);
+    if reacted {
+        return vec![];
+    }
+
+    let dmg = ctx.fmt.amount(recent_dmg);
+
+    vec![advice(
+        KEY,
+        "No Healer Here",
+        format!(
+            "{} damage in 5s and no defensive used — self-heal or mitigate, nobody else will.",
+            dmg
+        ),
+        Severity::Warn,
+        vec![("recent_dmg".to_owned(), dmg)],
+        ctx.now_ms,
+    )
+    .with_priority(Priority::Urgent)]
+}
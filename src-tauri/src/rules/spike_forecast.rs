@@ -0,0 +1,73 @@
+/// Pre-warns healers and tanks before scheduled unavoidable damage.
+///
+/// "Damage Incoming" — proactive counterpart to defensive_timing: instead of
+/// praising a defensive used under pressure, it tells the player a spike is
+/// coming while there is still time to top the group or line up a defensive.
+///
+/// Fires when:
+///   - An encounter with a timeline is active (see encounters.rs)
+///   - A raid_aoe ability (healers + tanks) or tank_buster (tanks only) is
+///     due within `lead_ms` of the current encounter time
+///   - Intensity >= 2
+///
+/// Priority: urgent — the warning is useless once the spike has landed.
+///
+/// Time-driven rather than event-driven: it is evaluated on every in-combat
+/// event, and the advice key includes the cast index so each scheduled cast
+/// is announced exactly once.
+use super::{advice, RuleContext, RuleOutput};
+use crate::{
+    encounters::{AbilityKind, TimelineEntry},
+    engine::{Priority, Severity},
+};
+
+const MIN_INTENSITY: u8 = 2;
+
+pub fn evaluate(ctx: &RuleContext, timeline: &[TimelineEntry], role: &str, lead_ms: u64) -> RuleOutput {
+    if ctx.intensity < MIN_INTENSITY {
+        return vec![];
+    }
+    let is_tank   = role == "TANK";
+    let is_healer = role == "HEALER";
+    if !is_tank && !is_healer {
+        return vec![];
+    }
+    let Some(start) = ctx.state.encounter_start_ms else {
+        return vec![];
+    };
+    let elapsed = ctx.now_ms.saturating_sub(start);
+
+    timeline
+        .iter()
+        .filter(|e| match e.kind {
+            AbilityKind::RaidAoe    => true,
+            AbilityKind::TankBuster => is_tank,
+            AbilityKind::Other      => false,
+        })
+        .filter_map(|e| {
+            let (n, due) = e.next_cast(elapsed)?;
+            let remaining = due.saturating_sub(elapsed);
+            if remaining == 0 || remaining > lead_ms {
+                return None;
+            }
+            let secs = ctx.fmt.seconds(remaining.div_ceil(1_000) * 1_000, 0);
+            let message = match (e.kind, is_tank) {
+                (AbilityKind::TankBuster, _) => format!("{} in {} — have a defensive ready.", e.name, secs),
+                (_, true)                    => format!("{} in {} — be topped and have a defensive ready.", e.name, secs),
+                _                            => format!("{} in {} — get the group topped.", e.name, secs),
+            };
+            Some(advice(
+                &format!("spike_forecast_{}_{}", e.spell_id, n),
+                "Damage Incoming",
+                message,
+                Severity::Warn,
+                vec![
+                    ("spell".to_owned(), e.name.clone()),
+                    ("in".to_owned(),    secs),
+                ],
+                ctx.now_ms,
+            )
+            .with_priority(Priority::Urgent))
+        })
+        .collect()
+}
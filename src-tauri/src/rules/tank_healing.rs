@@ -0,0 +1,60 @@
+/// Fires when the coached healer's healing on the tanks falls below what
+/// the encounter expects.
+///
+/// "Tank Healing" — tanks are inferred from melee damage taken
+/// (state::PartyDamageTracker::tanks); the expectation is the encounter
+/// TOML's `tank_heal_share_pct`, else DEFAULT_SHARE_PCT:
+///   Warn "Only 12% of your healing is on the tanks — this fight wants 30%+."
+///
+/// Healers only, on encounter pulls, and not before MIN_ELAPSED_MS: the
+/// opening seconds are pre-HoTs and spread damage, not a distribution.
+///
+/// Time-driven: evaluated in pass 3.
+///
+/// Intensity gate: fires at intensity >= 2.
+use super::{advice, RuleContext, RuleOutput};
+use crate::engine::Severity;
+
+pub const KEY: &str = "tank_healing";
+const MIN_INTENSITY: u8 = 2;
+/// Expected tank share when the encounter does not set one.
+pub const DEFAULT_SHARE_PCT: f32 = 25.0;
+const MIN_ELAPSED_MS: u64 = 30_000;
+
+pub fn evaluate(ctx: &RuleContext, role: &str, expected_pct: Option<f32>) -> RuleOutput {
+    if ctx.intensity < MIN_INTENSITY || role != "HEALER" {
+        return vec![];
+    }
+    if ctx.state.pull_elapsed_ms(ctx.now_ms) < MIN_ELAPSED_MS {
+        return vec![];
+    }
+    let Some(player) = ctx.state.player_guid.as_deref() else {
+        return vec![];
+    };
+    let tanks = ctx.state.party_damage.tanks();
+    if tanks.is_empty() || tanks.contains(&player) {
+        return vec![];
+    }
+    let expected = expected_pct.unwrap_or(DEFAULT_SHARE_PCT);
+    let Some(share) = ctx.state.healing.split(player, &tanks).tank_pct() else {
+        return vec![];
+    };
+    if share >= expected {
+        return vec![];
+    }
+
+    vec![advice(
+        KEY,
+        "Tank Healing",
+        format!(
+            "Only {:.0}% of your healing is on the tanks — this fight wants {:.0}%+.",
+            share, expected
+        ),
+        Severity::Warn,
+        vec![
+            ("tank_pct".to_owned(), format!("{:.0}", share)),
+            ("expected".to_owned(), format!("{:.0}", expected)),
+        ],
+        ctx.now_ms,
+    )]
+}
@@ -0,0 +1,57 @@
+/// Fires when the player keeps hitting something else while a kill-priority
+/// enemy is up.
+///
+/// "Tunnel Vision" — priority enemies are the encounter TOML's
+/// `priority_npc_ids` or anything skull-marked (state::TargetTracker).  When
+/// one has been up for TUNNEL_MS and the player has spent that long on a
+/// different target:
+///   Warn "Switch to Hungry Grub — 7s on Ulgrax while it is up."
+///
+/// Tanks and healers are not coached: their target is not a choice.  The
+/// current target is inferred from targeted casts and auto-attacks, so a
+/// player who has not hit anything yet is not flagged.
+///
+/// Time-driven: evaluated in pass 3 with the priority enemy's GUID in the
+/// advice key, so each add is called out once per advice cooldown.
+///
+/// Intensity gate: fires at intensity >= 2.
+use super::{advice, RuleContext, RuleOutput};
+use crate::engine::Severity;
+
+pub const KEY: &str = "tunnel_vision";
+const MIN_INTENSITY: u8 = 2;
+/// Time on another target (with the priority enemy up) before it counts.
+const TUNNEL_MS: u64 = 6_000;
+
+pub fn evaluate(ctx: &RuleContext, role: &str) -> RuleOutput {
+    if ctx.intensity < MIN_INTENSITY || role == "TANK" || role == "HEALER" {
+        return vec![];
+    }
+    let targets = &ctx.state.targets;
+    if targets.current.is_none() {
+        return vec![];
+    }
+
+    let mut out = Vec::new();
+    for (guid, priority) in targets.neglected(ctx.now_ms) {
+        let since = priority.active_since.max(targets.current_since);
+        let tunnel_ms = ctx.now_ms.saturating_sub(since);
+        if tunnel_ms < TUNNEL_MS {
+            continue;
+        }
+        let add     = if priority.name.is_empty() { "the priority add" } else { priority.name.as_str() };
+        let current = if targets.current_name.is_empty() { "your target" } else { targets.current_name.as_str() };
+        out.push(advice(
+            &format!("{}_{}", KEY, guid),
+            "Tunnel Vision",
+            format!("Switch to {} — {} on {} while it is up.", add, ctx.fmt.seconds(tunnel_ms, 0), current),
+            Severity::Warn,
+            vec![
+                ("priority".to_owned(), add.to_owned()),
+                ("target".to_owned(),   current.to_owned()),
+            ],
+            ctx.now_ms,
+        ));
+    }
+    out
+}
@@ -0,0 +1,183 @@
+/// Instance runs — segments a session into dungeon runs and raid nights.
+///
+/// ZONE_CHANGE tells us when the player enters or leaves an instance.  Pulls
+/// recorded inside one are tagged with its `instance_runs` row, so the history
+/// tab can group them under the instance name instead of one flat list.
+///
+/// Leaving and re-entering the same instance at the same difficulty continues
+/// the run when the player was out for less than `REENTRY_GRACE_MS` (corpse
+/// runs, a repair trip).  A longer absence from a dungeon means it was most
+/// likely reset, so re-entry starts a new run.  Raids skip the grace: the
+/// weekly lockout puts the player back into the same instance, so one raid is
+/// one run for the whole session.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstanceKind {
+    Dungeon,
+    Raid,
+}
+
+impl InstanceKind {
+    /// Kind of a DifficultyID; None for the open world, scenarios and PvP.
+    pub fn from_difficulty(difficulty_id: u32) -> Option<Self> {
+        match difficulty_id {
+            1 | 2 | 8 | 23 | 24 | 205                => Some(Self::Dungeon),
+            3..=7 | 9 | 14 | 15 | 16 | 17 | 33 | 151 => Some(Self::Raid),
+            _                                        => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Dungeon => "dungeon",
+            Self::Raid    => "raid",
+        }
+    }
+}
+
+/// Re-entering the same dungeon within this long continues the run.
+pub const REENTRY_GRACE_MS: u64 = 5 * 60_000;
+
+/// Short display name of a DifficultyID ("Mythic+", "Heroic", …).
+pub fn difficulty_name(difficulty_id: u32) -> &'static str {
+    match difficulty_id {
+        1 | 14          => "Normal",
+        2 | 15          => "Heroic",
+        8               => "Mythic+",
+        16 | 23         => "Mythic",
+        7 | 17 | 151    => "LFR",
+        24 | 33         => "Timewalking",
+        205             => "Follower",
+        3 | 5           => "10 Player",
+        4 | 6           => "25 Player",
+        9               => "40 Player",
+        _               => "",
+    }
+}
+
+/// The instance a run takes place in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstanceRun {
+    pub instance_id:   u32,
+    pub zone_name:     String,
+    pub difficulty_id: u32,
+    pub kind:          InstanceKind,
+}
+
+impl InstanceRun {
+    pub fn label(&self) -> String {
+        run_label(&self.zone_name, self.difficulty_id)
+    }
+}
+
+/// Display label of a run: "Ara-Kara, City of Echoes (Mythic+)".
+pub fn run_label(zone_name: &str, difficulty_id: u32) -> String {
+    match difficulty_name(difficulty_id) {
+        ""   => zone_name.to_owned(),
+        diff => format!("{} ({})", zone_name, diff),
+    }
+}
+
+/// A zone change that opened a new run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunStart {
+    pub run:      InstanceRun,
+    /// Log ms the previous run ended (when the player left it), if there
+    /// was one still open.
+    pub previous_ended_at: Option<u64>,
+}
+
+// ---------------------------------------------------------------------------
+// Tracker
+// ---------------------------------------------------------------------------
+
+/// The session's current run, fed with every ZONE_CHANGE.
+#[derive(Debug, Default)]
+pub struct RunTracker {
+    current: Option<InstanceRun>,
+    /// Log ms the player stepped out of `current`; None while inside.
+    left_at: Option<u64>,
+}
+
+impl RunTracker {
+    /// True while the player is inside the current run's instance.
+    pub fn inside(&self) -> bool {
+        self.current.is_some() && self.left_at.is_none()
+    }
+
+    /// Process a zone change; returns the run it starts, if any.
+    pub fn zone_changed(&mut self, instance_id: u32, zone_name: &str, difficulty_id: u32, now_ms: u64) -> Option<RunStart> {
+        let Some(kind) = InstanceKind::from_difficulty(difficulty_id) else {
+            // Open world, city, arena: the run stays open for a re-entry.
+            if self.current.is_some() {
+                self.left_at.get_or_insert(now_ms);
+            }
+            return None;
+        };
+
+        if let Some(current) = &self.current {
+            let same_instance = current.instance_id == instance_id && current.difficulty_id == difficulty_id;
+            let resumed = match self.left_at {
+                None          => true,
+                Some(left_at) => kind == InstanceKind::Raid
+                    || now_ms.saturating_sub(left_at) < REENTRY_GRACE_MS,
+            };
+            if same_instance && resumed {
+                self.left_at = None;
+                return None;
+            }
+        }
+
+        let previous_ended_at = self.current.as_ref().map(|_| self.left_at.unwrap_or(now_ms));
+        let run = InstanceRun { instance_id, zone_name: zone_name.to_owned(), difficulty_id, kind };
+        self.current = Some(run.clone());
+        self.left_at = None;
+        Some(RunStart { run, previous_ended_at })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARA_KARA: u32 = 2660;
+    const NERUBAR:  u32 = 2657;
+
+    #[test]
+    fn corpse_run_continues_the_dungeon_run() {
+        let mut t = RunTracker::default();
+        let start = t.zone_changed(ARA_KARA, "Ara-Kara, City of Echoes", 8, 0).expect("new run");
+        assert_eq!(start.previous_ended_at, None);
+        assert_eq!(start.run.label(), "Ara-Kara, City of Echoes (Mythic+)");
+        assert!(t.inside());
+
+        // Released and ran back in.
+        assert_eq!(t.zone_changed(2248, "Isle of Dorn", 0, 60_000), None);
+        assert!(!t.inside());
+        assert_eq!(t.zone_changed(ARA_KARA, "Ara-Kara, City of Echoes", 8, 120_000), None);
+        assert!(t.inside());
+    }
+
+    #[test]
+    fn reset_or_new_instance_starts_a_run() {
+        let mut t = RunTracker::default();
+        t.zone_changed(ARA_KARA, "Ara-Kara, City of Echoes", 23, 0);
+        t.zone_changed(2248, "Isle of Dorn", 0, 100_000);
+        // Back after the grace period: the dungeon was reset.
+        let start = t.zone_changed(ARA_KARA, "Ara-Kara, City of Echoes", 23, 100_000 + REENTRY_GRACE_MS)
+            .expect("new run after a reset");
+        assert_eq!(start.previous_ended_at, Some(100_000));
+
+        // Straight into the raid: the dungeon run ends now.
+        let raid = t.zone_changed(NERUBAR, "Nerub-ar Palace", 15, 900_000).expect("raid run");
+        assert_eq!(raid.run.kind, InstanceKind::Raid);
+        assert_eq!(raid.previous_ended_at, Some(900_000));
+
+        // Raids resume after any break within the session.
+        t.zone_changed(2339, "Dornogal", 0, 1_000_000);
+        assert_eq!(t.zone_changed(NERUBAR, "Nerub-ar Palace", 15, 5_000_000), None);
+    }
+}
@@ -1,13 +1,39 @@
 /// Spec profile library — embedded at compile time from `data/specs/*.toml`.
 ///
 /// Profiles provide the major CD and active mitigation spell IDs used by the
-/// cooldown_drift and defensive_timing coaching rules.  Embedding the files
-/// at compile time means no runtime path resolution is needed.
+/// cooldown_drift and defensive_timing coaching rules, and optionally the
+/// opener scored in target-dummy practice (`[spec.rotation] opener_spell_ids`)
+/// and the DoTs to keep on the boss (`dot_spell_ids`, dot_uptime rule).
+/// `primary_spell_ids` are the rotation's priority spells, whose casts per
+/// minute are checked every pull (rotation.rs); `[spec.rotation.expected_cpm]`
+/// optionally gives the rate each should reach, by spell ID.
+/// `prepull_spell_ids` are the spells a prepared player starts before the
+/// pull (pre-pull check in the encounter debrief).
+/// `[spec.cooldowns.cooldown_s]` gives base cooldowns by spell ID for the
+/// cooldown_available rule — base, because talents only shorten them, so a
+/// cooldown is never reported ready before it really is.
+/// Embedding the files at compile time means no runtime path resolution is
+/// needed.  They are parsed once, on first lookup, and kept in `PROFILES`
+/// with a lowercase "class/spec" index, so per-identity and per-config
+/// lookups are a hash probe and a clone.
 ///
-/// The engine auto-loads a profile when the addon sends an identity update.
+/// The engine auto-loads a profile when the addon sends an identity update,
+/// or when COMBATANT_INFO names the player's spec (`spec_by_id`).
 /// Users can also explicitly select a spec in the settings UI, which saves
 /// the major CD IDs to `AppConfig.major_cds` for persistence.
+///
+/// Inheritance: abilities shared by a whole class live in
+/// `data/specs/classes/<class>.toml` (`[class]`), and those every character
+/// has in `data/specs/global.toml` (`[global]`) — not consumables, which
+/// come from `data/consumables.toml` (consumables.rs).  A
+/// spec's lists are its own IDs followed by the class layer's, then the
+/// global layer's, without duplicates.  A spec can drop inherited IDs with
+/// `exclude_spell_ids = [...]` or opt out entirely with `inherit = false`.
+use once_cell::sync::Lazy;
 use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::spells;
 
 // ---------------------------------------------------------------------------
 // Embedded TOML data — one const per spec, alphabetical by file name
@@ -53,6 +79,34 @@ const WARRIOR_ARMS:              &str = include_str!("../../data/specs/warrior_a
 const WARRIOR_FURY:              &str = include_str!("../../data/specs/warrior_fury.toml");
 const WARRIOR_PROTECTION:        &str = include_str!("../../data/specs/warrior_protection.toml");
 
+const CLASS_DEATH_KNIGHT:        &str = include_str!("../../data/specs/classes/death_knight.toml");
+const CLASS_DEMON_HUNTER:        &str = include_str!("../../data/specs/classes/demon_hunter.toml");
+const CLASS_DRUID:               &str = include_str!("../../data/specs/classes/druid.toml");
+const CLASS_EVOKER:              &str = include_str!("../../data/specs/classes/evoker.toml");
+const CLASS_HUNTER:              &str = include_str!("../../data/specs/classes/hunter.toml");
+const CLASS_MAGE:                &str = include_str!("../../data/specs/classes/mage.toml");
+const CLASS_MONK:                &str = include_str!("../../data/specs/classes/monk.toml");
+const CLASS_PALADIN:             &str = include_str!("../../data/specs/classes/paladin.toml");
+const CLASS_ROGUE:               &str = include_str!("../../data/specs/classes/rogue.toml");
+const CLASS_SHAMAN:              &str = include_str!("../../data/specs/classes/shaman.toml");
+const CLASS_WARLOCK:             &str = include_str!("../../data/specs/classes/warlock.toml");
+const GLOBAL:                    &str = include_str!("../../data/specs/global.toml");
+
+static ALL_LAYER_DATA: &[&str] = &[
+    CLASS_DEATH_KNIGHT,
+    CLASS_DEMON_HUNTER,
+    CLASS_DRUID,
+    CLASS_EVOKER,
+    CLASS_HUNTER,
+    CLASS_MAGE,
+    CLASS_MONK,
+    CLASS_PALADIN,
+    CLASS_ROGUE,
+    CLASS_SHAMAN,
+    CLASS_WARLOCK,
+    GLOBAL,
+];
+
 static ALL_SPEC_DATA: &[&str] = &[
     DEATH_KNIGHT_BLOOD,
     DEATH_KNIGHT_FROST,
@@ -114,13 +168,41 @@ struct TomlSpecMeta {
     description:       String,
     cooldowns:         TomlCooldowns,
     active_mitigation: Option<TomlActiveMitigation>,
-    #[allow(dead_code)]
     rotation:          Option<TomlRotation>,
+    /// false → ignore the class and global layers.
+    #[serde(default = "default_inherit")]
+    inherit:           bool,
+    /// Inherited spell IDs this spec does not use.
+    #[serde(default)]
+    exclude_spell_ids: Vec<u32>,
+}
+
+fn default_inherit() -> bool {
+    true
+}
+
+/// A layer file holds exactly one of `[class]` or `[global]`.
+#[derive(Deserialize)]
+struct TomlLayerFile {
+    class:  Option<TomlLayer>,
+    global: Option<TomlLayer>,
+}
+
+#[derive(Deserialize)]
+struct TomlLayer {
+    /// Class token for `[class]` layers, e.g. "DEATH_KNIGHT".
+    #[serde(default)]
+    name:              String,
+    cooldowns:         Option<TomlCooldowns>,
+    active_mitigation: Option<TomlActiveMitigation>,
 }
 
 #[derive(Deserialize)]
 struct TomlCooldowns {
     major_cd_spell_ids: Vec<u32>,
+    /// Spell ID (as a TOML key) -> base cooldown in seconds.
+    #[serde(default)]
+    cooldown_s:         HashMap<String, u32>,
 }
 
 #[derive(Deserialize)]
@@ -128,10 +210,23 @@ struct TomlActiveMitigation {
     am_spell_ids: Vec<u32>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Default)]
 struct TomlRotation {
-    #[allow(dead_code)]
+    /// Priority spells whose cast rate is checked (rotation.rs).
+    #[serde(default)]
     primary_spell_ids: Vec<u32>,
+    /// Spell ID (as a TOML key) -> expected casts per minute.
+    #[serde(default)]
+    expected_cpm:      HashMap<String, f32>,
+    /// Expected first casts of a pull, in order (practice.rs).
+    #[serde(default)]
+    opener_spell_ids:  Vec<u32>,
+    /// Debuff IDs of the DoTs to keep up on the boss (dot_uptime rule).
+    #[serde(default)]
+    dot_spell_ids:     Vec<u32>,
+    /// Spells cast before ENCOUNTER_START by a prepared player.
+    #[serde(default)]
+    prepull_spell_ids: Vec<u32>,
 }
 
 // ---------------------------------------------------------------------------
@@ -148,6 +243,23 @@ pub struct SpecProfile {
     pub major_cd_spell_ids: Vec<u32>,
     /// Spell IDs of active mitigation / defensive abilities for future rules.
     pub am_spell_ids:       Vec<u32>,
+    /// Opener scored in target-dummy practice; empty = major CDs, any order.
+    pub opener_spell_ids:   Vec<u32>,
+    /// Aura IDs of the DoTs the dot_uptime rule watches on boss targets.
+    pub dot_spell_ids:      Vec<u32>,
+    /// Pre-cast spells, any of which counts as pre-pull preparation.
+    pub prepull_spell_ids:  Vec<u32>,
+    /// Spell ID -> base cooldown in seconds (cooldown_available rule).
+    pub cooldown_s:         HashMap<u32, u32>,
+    /// Rotation priority spells, in file order (cast efficiency).
+    pub priority_spells:    Vec<PrioritySpell>,
+}
+
+/// A rotation priority spell and the cast rate it should reach, if known.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrioritySpell {
+    pub spell_id:     u32,
+    pub expected_cpm: Option<f32>,
 }
 
 impl SpecProfile {
@@ -155,6 +267,38 @@ impl SpecProfile {
     pub fn key(&self) -> String {
         format!("{}/{}", self.class, self.spec_name)
     }
+
+    /// Display names (spells.toml) of every spell the profile references —
+    /// major CDs, active mitigation, opener, DoTs, pre-cast and priority
+    /// spells — in that order, without duplicates.  IDs missing from the
+    /// bundle are left out.
+    pub fn spell_names(&self) -> Vec<(u32, &'static str)> {
+        let mut out: Vec<(u32, &'static str)> = Vec::new();
+        let ids = self.major_cd_spell_ids.iter()
+            .chain(&self.am_spell_ids)
+            .chain(&self.opener_spell_ids)
+            .chain(&self.dot_spell_ids)
+            .chain(&self.prepull_spell_ids)
+            .chain(self.priority_spells.iter().map(|p| &p.spell_id));
+        for &id in ids {
+            if out.iter().all(|(seen, _)| *seen != id) {
+                if let Some(name) = spells::name(id) {
+                    out.push((id, name));
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Class-wide (`class = Some`) or global (`class = None`) ability lists
+/// merged into every matching spec profile.
+#[derive(Debug, Clone)]
+pub struct SpecLayer {
+    pub class:              Option<String>,
+    pub major_cd_spell_ids: Vec<u32>,
+    pub am_spell_ids:       Vec<u32>,
+    pub cooldown_s:         HashMap<u32, u32>,
 }
 
 /// Lightweight spec descriptor returned to the frontend for dropdowns.
@@ -171,39 +315,204 @@ pub struct SpecInfo {
 // Parsing helpers
 // ---------------------------------------------------------------------------
 
+/// Parse one spec TOML document and merge the embedded class and global
+/// layers into it (also used by the data-file validator).
+pub fn parse_str(toml_str: &str) -> Result<SpecProfile, toml::de::Error> {
+    parse_with_layers(toml_str, &embedded_layers())
+}
+
+/// `cooldown_s` / `expected_cpm` keys are spell IDs; anything else is a
+/// data error.
+fn spell_id_map<T>(table: &str, raw: HashMap<String, T>) -> Result<HashMap<u32, T>, toml::de::Error> {
+    raw.into_iter()
+        .map(|(id, value)| match id.parse() {
+            Ok(id) => Ok((id, value)),
+            Err(_) => Err(serde::de::Error::custom(format!("{} key '{}' is not a spell ID", table, id))),
+        })
+        .collect()
+}
+
+fn parse_with_layers(toml_str: &str, layers: &[SpecLayer]) -> Result<SpecProfile, toml::de::Error> {
+    let file: TomlFile = toml::from_str(toml_str)?;
+    let spec = file.spec;
+    let rotation = spec.rotation.unwrap_or_default();
+    let expected_cpm = spell_id_map("expected_cpm", rotation.expected_cpm)?;
+    let mut profile = SpecProfile {
+        class:              spec.class,
+        spec_name:          spec.spec,
+        role:               spec.role,
+        major_cd_spell_ids: spec.cooldowns.major_cd_spell_ids,
+        cooldown_s:         spell_id_map("cooldown_s", spec.cooldowns.cooldown_s)?,
+        am_spell_ids:       spec.active_mitigation
+                                .map(|am| am.am_spell_ids)
+                                .unwrap_or_default(),
+        opener_spell_ids:   rotation.opener_spell_ids,
+        dot_spell_ids:      rotation.dot_spell_ids,
+        prepull_spell_ids:  rotation.prepull_spell_ids,
+        priority_spells:    rotation.primary_spell_ids
+                                .iter()
+                                .map(|&spell_id| PrioritySpell {
+                                    spell_id,
+                                    expected_cpm: expected_cpm.get(&spell_id).copied(),
+                                })
+                                .collect(),
+    };
+    if spec.inherit {
+        let class_layers = layers.iter().filter(|l| {
+            l.class.as_deref().is_some_and(|c| c.eq_ignore_ascii_case(&profile.class))
+        });
+        let global_layers = layers.iter().filter(|l| l.class.is_none());
+        for layer in class_layers.chain(global_layers) {
+            inherit(&mut profile.major_cd_spell_ids, &layer.major_cd_spell_ids, &spec.exclude_spell_ids);
+            inherit(&mut profile.am_spell_ids, &layer.am_spell_ids, &spec.exclude_spell_ids);
+            for (id, secs) in &layer.cooldown_s {
+                if !spec.exclude_spell_ids.contains(id) {
+                    profile.cooldown_s.entry(*id).or_insert(*secs);
+                }
+            }
+        }
+    }
+    Ok(profile)
+}
+
+/// Append inherited IDs not already present and not excluded.
+fn inherit(own: &mut Vec<u32>, inherited: &[u32], exclude: &[u32]) {
+    for id in inherited {
+        if !own.contains(id) && !exclude.contains(id) {
+            own.push(*id);
+        }
+    }
+}
+
+/// Parse one class or global layer TOML document.
+pub fn parse_layer_str(toml_str: &str) -> Result<SpecLayer, toml::de::Error> {
+    let file: TomlLayerFile = toml::from_str(toml_str)?;
+    let (class, layer) = match (file.class, file.global) {
+        (Some(layer), None) => (Some(layer.name.clone()), layer),
+        (None, Some(layer)) => (None, layer),
+        _ => return Err(serde::de::Error::custom("expected exactly one of [class] or [global]")),
+    };
+    if class.as_deref() == Some("") {
+        return Err(serde::de::Error::custom("[class] layer needs a name"));
+    }
+    let (major_cd_spell_ids, cooldown_s) = match layer.cooldowns {
+        Some(c) => (c.major_cd_spell_ids, spell_id_map("cooldown_s", c.cooldown_s)?),
+        None    => (Vec::new(), HashMap::new()),
+    };
+    Ok(SpecLayer {
+        class,
+        major_cd_spell_ids,
+        am_spell_ids:       layer.active_mitigation.map(|am| am.am_spell_ids).unwrap_or_default(),
+        cooldown_s,
+    })
+}
+
+fn embedded_layers() -> Vec<SpecLayer> {
+    ALL_LAYER_DATA
+        .iter()
+        .filter_map(|toml_str| {
+            parse_layer_str(toml_str)
+                .map_err(|e| tracing::warn!("Failed to parse spec layer TOML: {}", e))
+                .ok()
+        })
+        .collect()
+}
+
+/// Every embedded profile, parsed on first use.
+static PROFILES: Lazy<Vec<SpecProfile>> = Lazy::new(parse_all);
+
+/// Lowercase "class/spec" key → index into `PROFILES`.
+static BY_KEY: Lazy<HashMap<String, usize>> = Lazy::new(|| {
+    PROFILES.iter()
+        .enumerate()
+        .map(|(i, p)| (index_key(&p.class, &p.spec_name), i))
+        .collect()
+});
+
+fn index_key(class: &str, spec_name: &str) -> String {
+    format!("{}/{}", class, spec_name).to_ascii_lowercase()
+}
+
 fn parse_all() -> Vec<SpecProfile> {
+    let layers = embedded_layers();
     ALL_SPEC_DATA
         .iter()
         .filter_map(|toml_str| {
-            let file: TomlFile = toml::from_str(toml_str)
+            parse_with_layers(toml_str, &layers)
                 .map_err(|e| tracing::warn!("Failed to parse spec TOML: {}", e))
-                .ok()?;
-            Some(SpecProfile {
-                class:              file.spec.class,
-                spec_name:          file.spec.spec,
-                role:               file.spec.role,
-                major_cd_spell_ids: file.spec.cooldowns.major_cd_spell_ids,
-                am_spell_ids:       file.spec.active_mitigation
-                                        .map(|am| am.am_spell_ids)
-                                        .unwrap_or_default(),
-            })
+                .ok()
         })
         .collect()
 }
 
+/// Raw TOML of every embedded spec (the validator's self-check).
+#[cfg(test)]
+pub fn embedded_sources() -> &'static [&'static str] {
+    ALL_SPEC_DATA
+}
+
+/// Raw TOML of every embedded class/global layer (the validator's self-check).
+#[cfg(test)]
+pub fn embedded_layer_sources() -> &'static [&'static str] {
+    ALL_LAYER_DATA
+}
+
+/// Specialization ID (COMBATANT_INFO) → (class, spec) as named in the profiles.
+static SPEC_IDS: &[(u32, &str, &str)] = &[
+    (250,  "DEATH_KNIGHT", "Blood"),
+    (251,  "DEATH_KNIGHT", "Frost"),
+    (252,  "DEATH_KNIGHT", "Unholy"),
+    (577,  "DEMON_HUNTER", "Havoc"),
+    (581,  "DEMON_HUNTER", "Vengeance"),
+    (102,  "DRUID",        "Balance"),
+    (103,  "DRUID",        "Feral"),
+    (104,  "DRUID",        "Guardian"),
+    (105,  "DRUID",        "Restoration"),
+    (1473, "EVOKER",       "Augmentation"),
+    (1467, "EVOKER",       "Devastation"),
+    (1468, "EVOKER",       "Preservation"),
+    (253,  "HUNTER",       "Beast Mastery"),
+    (254,  "HUNTER",       "Marksmanship"),
+    (255,  "HUNTER",       "Survival"),
+    (62,   "MAGE",         "Arcane"),
+    (63,   "MAGE",         "Fire"),
+    (64,   "MAGE",         "Frost"),
+    (268,  "MONK",         "Brewmaster"),
+    (270,  "MONK",         "Mistweaver"),
+    (269,  "MONK",         "Windwalker"),
+    (65,   "PALADIN",      "Holy"),
+    (66,   "PALADIN",      "Protection"),
+    (70,   "PALADIN",      "Retribution"),
+    (256,  "PRIEST",       "Discipline"),
+    (257,  "PRIEST",       "Holy"),
+    (258,  "PRIEST",       "Shadow"),
+    (259,  "ROGUE",        "Assassination"),
+    (260,  "ROGUE",        "Outlaw"),
+    (261,  "ROGUE",        "Subtlety"),
+    (262,  "SHAMAN",       "Elemental"),
+    (263,  "SHAMAN",       "Enhancement"),
+    (264,  "SHAMAN",       "Restoration"),
+    (265,  "WARLOCK",      "Affliction"),
+    (266,  "WARLOCK",      "Demonology"),
+    (267,  "WARLOCK",      "Destruction"),
+    (71,   "WARRIOR",      "Arms"),
+    (72,   "WARRIOR",      "Fury"),
+    (73,   "WARRIOR",      "Protection"),
+];
+
 // ---------------------------------------------------------------------------
 // Public API
 // ---------------------------------------------------------------------------
 
 /// Return display info for all embedded spec profiles (for the settings UI).
 pub fn list_all() -> Vec<SpecInfo> {
-    parse_all()
-        .into_iter()
+    PROFILES
+        .iter()
         .map(|p| SpecInfo {
             key:   p.key(),
-            class: p.class,
-            spec:  p.spec_name,
-            role:  p.role,
+            class: p.class.clone(),
+            spec:  p.spec_name.clone(),
+            role:  p.role.clone(),
         })
         .collect()
 }
@@ -212,9 +521,7 @@ pub fn list_all() -> Vec<SpecInfo> {
 ///
 /// Returns `None` if no embedded profile matches.
 pub fn load_spec(class: &str, spec_name: &str) -> Option<SpecProfile> {
-    parse_all().into_iter().find(|p| {
-        p.class.eq_ignore_ascii_case(class) && p.spec_name.eq_ignore_ascii_case(spec_name)
-    })
+    BY_KEY.get(&index_key(class, spec_name)).map(|&i| PROFILES[i].clone())
 }
 
 /// Load a spec profile by its canonical "CLASS/Spec" key.
@@ -223,6 +530,13 @@ pub fn load_by_key(key: &str) -> Option<SpecProfile> {
     load_spec(class, spec)
 }
 
+/// Class and spec name for a specialization ID, e.g. 70 → ("PALADIN", "Retribution").
+pub fn spec_by_id(spec_id: u32) -> Option<(&'static str, &'static str)> {
+    SPEC_IDS.iter()
+        .find(|(id, _, _)| *id == spec_id)
+        .map(|&(_, class, spec)| (class, spec))
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -245,12 +559,44 @@ mod tests {
         assert!(keys.contains(&"HUNTER/Beast Mastery"));
     }
 
+    #[test]
+    fn every_spec_id_has_a_profile() {
+        assert_eq!(spec_by_id(70), Some(("PALADIN", "Retribution")));
+        assert_eq!(spec_by_id(0), None);
+        assert_eq!(SPEC_IDS.len(), list_all().len());
+        for &(id, class, spec) in SPEC_IDS {
+            assert!(load_spec(class, spec).is_some(), "spec {} → {}/{} has no profile", id, class, spec);
+        }
+    }
+
     #[test]
     fn loads_paladin_ret() {
         let p = load_spec("PALADIN", "Retribution").expect("should load");
         assert!(!p.major_cd_spell_ids.is_empty());
         assert!(p.major_cd_spell_ids.contains(&31884)); // Avenging Wrath
         assert!(p.am_spell_ids.contains(&498));          // Divine Protection
+        assert!(p.dot_spell_ids.is_empty());
+    }
+
+    #[test]
+    fn loads_cooldown_durations() {
+        let p = load_spec("MAGE", "Fire").unwrap();
+        assert_eq!(p.cooldown_s.get(&190319), Some(&120)); // Combustion
+        assert_eq!(p.cooldown_s.get(&235450), Some(&25));  // Prismatic Barrier (class layer)
+        for spec in PROFILES.iter() {
+            for id in spec.cooldown_s.keys() {
+                assert!(spec.major_cd_spell_ids.contains(id), "{}: cooldown_s {} is not a major CD", spec.key(), id);
+            }
+        }
+        let bad = "[spec]\nclass = \"MAGE\"\nspec = \"T\"\nrole = \"DAMAGER\"\n\
+                   [spec.cooldowns]\nmajor_cd_spell_ids = []\n[spec.cooldowns.cooldown_s]\ncombustion = 120\n";
+        assert!(parse_str(bad).is_err());
+    }
+
+    #[test]
+    fn loads_dot_spells() {
+        let p = load_spec("WARLOCK", "Affliction").expect("should load");
+        assert!(p.dot_spell_ids.contains(&980)); // Agony
     }
 
     #[test]
@@ -265,12 +611,81 @@ mod tests {
         assert!(load_by_key("warrior/protection").is_some());
     }
 
+    #[test]
+    fn lookups_share_the_cached_profiles() {
+        assert_eq!(BY_KEY.len(), PROFILES.len(), "duplicate class/spec in the embedded profiles");
+        let a = load_by_key("MAGE/Fire").unwrap();
+        let b = load_spec("mage", "FIRE").unwrap();
+        assert_eq!((a.key(), a.major_cd_spell_ids), (b.key(), b.major_cd_spell_ids));
+
+        let names = load_spec("PALADIN", "Retribution").unwrap().spell_names();
+        assert!(names.contains(&(31884, "Avenging Wrath")));
+        let mut ids: Vec<u32> = names.iter().map(|(id, _)| *id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), names.len());
+    }
+
     #[test]
     fn returns_none_for_unknown() {
         // "TINKER" is not a WoW class — no spec file will match
         assert!(load_spec("TINKER", "Mechagnome").is_none());
     }
 
+    #[test]
+    fn inherits_class_and_global_layers() {
+        // Icebound Fortitude lives in the DK class layer; consumables are
+        // not active mitigation (consumables.rs).
+        let p = load_spec("DEATH_KNIGHT", "Frost").unwrap();
+        assert!(p.major_cd_spell_ids.contains(&48792));
+        assert!(p.am_spell_ids.contains(&48792));
+        assert!(!p.am_spell_ids.iter().any(|&id| crate::consumables::kind_of(id).is_some()));
+        assert_eq!(ALL_LAYER_DATA.len(), embedded_layers().len(), "every layer must parse");
+    }
+
+    #[test]
+    fn merges_in_order_with_excludes_and_opt_out() {
+        let layers = vec![
+            SpecLayer { class: None, major_cd_spell_ids: vec![], am_spell_ids: vec![6262], cooldown_s: HashMap::new() },
+            SpecLayer {
+                class:              Some("MAGE".to_owned()),
+                major_cd_spell_ids: vec![1, 2],
+                am_spell_ids:       vec![3],
+                cooldown_s:         HashMap::from([(1, 60), (2, 30)]),
+            },
+            SpecLayer { class: Some("PRIEST".to_owned()), major_cd_spell_ids: vec![9], am_spell_ids: vec![], cooldown_s: HashMap::new() },
+        ];
+        let spec = |extra: &str| format!(
+            "[spec]\nclass = \"MAGE\"\nspec = \"Test\"\nrole = \"DAMAGER\"\n{}\n\
+             [spec.cooldowns]\nmajor_cd_spell_ids = [2, 5]\n\
+             [spec.cooldowns.cooldown_s]\n2 = 120\n",
+            extra
+        );
+
+        let p = parse_with_layers(&spec(""), &layers).unwrap();
+        assert_eq!(p.major_cd_spell_ids, vec![2, 5, 1]);
+        assert_eq!(p.am_spell_ids, vec![3, 6262]);
+        // The spec's own cooldown wins over the layer's.
+        assert_eq!(p.cooldown_s, HashMap::from([(1, 60), (2, 120)]));
+
+        let p = parse_with_layers(&spec("exclude_spell_ids = [1, 6262]"), &layers).unwrap();
+        assert_eq!(p.major_cd_spell_ids, vec![2, 5]);
+        assert_eq!(p.am_spell_ids, vec![3]);
+        assert_eq!(p.cooldown_s, HashMap::from([(2, 120)]));
+
+        let p = parse_with_layers(&spec("inherit = false"), &layers).unwrap();
+        assert_eq!(p.major_cd_spell_ids, vec![2, 5]);
+        assert!(p.am_spell_ids.is_empty());
+    }
+
+    #[test]
+    fn layer_needs_exactly_one_section() {
+        assert!(parse_layer_str("[global]\n").unwrap().class.is_none());
+        assert!(parse_layer_str("[class]\nname = \"MAGE\"\n").unwrap().class.is_some());
+        assert!(parse_layer_str("[class]\n").is_err());
+        assert!(parse_layer_str("[class]\nname = \"MAGE\"\n[global]\n").is_err());
+    }
+
     #[test]
     fn key_format() {
         let p = load_spec("PALADIN", "Retribution").unwrap();
@@ -0,0 +1,175 @@
+/// Spectate files — live coaching data as small JSON files for second-screen
+/// dashboards (Rainmeter skins, a tablet browser pointed at a plain file
+/// server) that cannot talk to the app.
+///
+/// When `AppConfig.spectate_dir` is set, ipc::run hands every engine output
+/// to the managed `SpectateWriter`, which keeps these files current:
+///   - `snapshot.json` — the latest StateSnapshot
+///   - `advice.json`   — the last ADVICE_FEED_LEN advice events, newest first
+///   - `debrief.json`  — the last pull debrief
+///
+/// Snapshots arrive several times a second, so writes are debounced: a file
+/// is rewritten at most every WRITE_INTERVAL, and a change held back is
+/// written by the next snapshot after the interval (snapshots keep flowing,
+/// in and out of combat).  Each write goes to a temp file that is renamed
+/// over the target, so a reader never sees a half-written file.
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::{
+    engine::AdviceEvent,
+    ipc::{PullDebrief, StateSnapshot},
+};
+
+const SNAPSHOT_FILE: &str = "snapshot.json";
+const ADVICE_FILE:   &str = "advice.json";
+const DEBRIEF_FILE:  &str = "debrief.json";
+/// Advice events kept in `advice.json`.
+const ADVICE_FEED_LEN: usize = 20;
+/// Minimum time between two writes of the same file.
+const WRITE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// One debounced output file.
+#[derive(Default)]
+struct Slot {
+    /// Serialized content not written yet.
+    pending: Option<String>,
+    written: Option<Instant>,
+}
+
+impl Slot {
+    fn due(&self, now: Instant) -> bool {
+        self.pending.is_some() && self.written.is_none_or(|at| now.duration_since(at) >= WRITE_INTERVAL)
+    }
+}
+
+/// Managed state (lib.rs); the directory follows config updates.
+#[derive(Default)]
+pub struct SpectateWriter {
+    /// Empty = spectate output off.
+    dir:      PathBuf,
+    advice:   VecDeque<AdviceEvent>,
+    snapshot: Slot,
+    feed:     Slot,
+    debrief:  Slot,
+}
+
+impl SpectateWriter {
+    /// Point the output at `dir` (empty = off).  The directory is created on
+    /// the first write.
+    pub fn set_dir(&mut self, dir: &Path) {
+        if self.dir != dir {
+            if !dir.as_os_str().is_empty() {
+                tracing::info!("Spectate files: writing to {:?}", dir);
+            }
+            self.dir = dir.to_owned();
+            self.snapshot = Slot::default();
+            self.feed     = Slot::default();
+            self.debrief  = Slot::default();
+        }
+    }
+
+    fn enabled(&self) -> bool {
+        !self.dir.as_os_str().is_empty()
+    }
+
+    pub fn snapshot(&mut self, snap: &StateSnapshot, now: Instant) {
+        if self.enabled() {
+            self.snapshot.pending = to_json(snap);
+            self.flush(now);
+        }
+    }
+
+    pub fn advice(&mut self, advice: &AdviceEvent, now: Instant) {
+        if !self.enabled() {
+            return;
+        }
+        self.advice.push_front(advice.clone());
+        self.advice.truncate(ADVICE_FEED_LEN);
+        self.feed.pending = to_json(&self.advice);
+        self.flush(now);
+    }
+
+    pub fn debrief(&mut self, debrief: &PullDebrief, now: Instant) {
+        if self.enabled() {
+            self.debrief.pending = to_json(debrief);
+            self.flush(now);
+        }
+    }
+
+    /// Write every file whose debounce interval has passed.
+    fn flush(&mut self, now: Instant) {
+        let dir = self.dir.clone();
+        for (slot, name) in [
+            (&mut self.snapshot, SNAPSHOT_FILE),
+            (&mut self.feed,     ADVICE_FILE),
+            (&mut self.debrief,  DEBRIEF_FILE),
+        ] {
+            if !slot.due(now) {
+                continue;
+            }
+            let Some(json) = slot.pending.take() else { continue };
+            if let Err(e) = write_atomic(&dir, name, &json) {
+                tracing::warn!("Spectate files: {} not written: {}", name, e);
+            }
+            slot.written = Some(now);
+        }
+    }
+}
+
+fn to_json(value: &impl Serialize) -> Option<String> {
+    serde_json::to_string(value)
+        .map_err(|e| tracing::warn!("Spectate files: serialize error: {}", e))
+        .ok()
+}
+
+/// Write `dir/name` through a temp file renamed over it.
+fn write_atomic(dir: &Path, name: &str, content: &str) -> Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let tmp = dir.join(format!(".{}.tmp", name));
+    std::fs::write(&tmp, content)?;
+    std::fs::rename(&tmp, dir.join(name))?;
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{engine::Severity, rules::advice};
+    use tempfile::tempdir;
+
+    #[test]
+    fn writes_debounced_files_atomically() {
+        let dir = tempdir().unwrap();
+        let mut out = SpectateWriter::default();
+        let t0 = Instant::now();
+
+        // Off until a directory is set.
+        let event = |message: &str| advice("a", "A", message.to_owned(), Severity::Warn, vec![], 0);
+        out.advice(&event("first"), t0);
+        assert!(out.advice.is_empty());
+
+        out.set_dir(dir.path());
+        out.advice(&event("first"), t0);
+        out.advice(&event("second"), t0 + Duration::from_millis(100));
+        let read = || std::fs::read_to_string(dir.path().join(ADVICE_FILE)).unwrap();
+        assert!(read().contains("first") && !read().contains("second"), "second write is held back");
+
+        // The held-back feed goes out with a later event once the interval passed.
+        out.flush(t0 + WRITE_INTERVAL);
+        let feed: Vec<serde_json::Value> = serde_json::from_str(&read()).unwrap();
+        assert_eq!(feed[0]["message"], "second");
+        assert_eq!(feed.len(), 2);
+
+        let files: Vec<_> = std::fs::read_dir(dir.path()).unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        assert_eq!(files, vec![ADVICE_FILE.to_owned()], "no temp files left behind");
+    }
+}
@@ -0,0 +1,47 @@
+/// Spell metadata bundle — embedded at compile time from `data/spells.toml`.
+///
+/// Maps spell IDs to display names.  The data-file validator (validate.rs)
+/// uses it to catch unknown or mistyped IDs in community-contributed spec
+/// and encounter TOMLs; rules use it to name spells in advice text, and
+/// `SpecProfile::spell_names` lists the named spells a profile tracks.
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+const SPELLS_TOML: &str = include_str!("../../data/spells.toml");
+
+#[derive(Deserialize)]
+struct TomlFile {
+    spells: HashMap<String, String>,
+}
+
+static SPELLS: Lazy<HashMap<u32, String>> = Lazy::new(|| {
+    let file: TomlFile = match toml::from_str(SPELLS_TOML) {
+        Ok(f)  => f,
+        Err(e) => {
+            tracing::warn!("Failed to parse spells.toml: {}", e);
+            return HashMap::new();
+        }
+    };
+    file.spells
+        .into_iter()
+        .filter_map(|(id, name)| Some((id.parse().ok()?, name)))
+        .collect()
+});
+
+/// Display name of a known spell.
+pub fn name(spell_id: u32) -> Option<&'static str> {
+    SPELLS.get(&spell_id).map(String::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundle_parses() {
+        assert!(SPELLS.len() > 300);
+        assert_eq!(name(31884), Some("Avenging Wrath"));
+        assert_eq!(name(1), None);
+    }
+}
@@ -2,8 +2,13 @@
 ///
 /// All state lives in a single CombatState owned by the engine task.
 /// No locking is needed because the engine is single-threaded.
-use crate::parser::LogEvent;
-use std::collections::{HashMap, HashSet};
+use crate::{
+    consumables::{self, ConsumableKind},
+    guid::{self, UnitKind},
+    parser::{self, LogEvent},
+    practice::{self, PracticeTracker},
+};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 // ---------------------------------------------------------------------------
 // Pull tracking
@@ -15,6 +20,36 @@ pub enum PullOutcome {
     Wipe,
 }
 
+/// Arena match metadata from ARENA_MATCH_START.
+#[derive(Debug, Clone)]
+pub struct ArenaMatch {
+    #[allow(dead_code)] // read by PvP-specific rules in future phases
+    pub instance_id: u32,
+    #[allow(dead_code)]
+    pub bracket:     String,
+    /// The coached player's team (0 or 1), compared against the winner.
+    pub team_id:     u32,
+}
+
+/// Mythic+ key in progress, from CHALLENGE_MODE_START.  Spans every pull of
+/// the run, trash included, until CHALLENGE_MODE_END.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keystone {
+    pub instance_id: u32,
+    pub dungeon:     String,
+    pub level:       u32,
+    pub affix_ids:   Vec<u32>,
+}
+
+impl Keystone {
+    /// Affix display names; unknown IDs as "Affix <id>".
+    pub fn affix_names(&self) -> Vec<String> {
+        self.affix_ids.iter()
+            .map(|&id| parser::affix_name(id).map_or_else(|| format!("Affix {}", id), str::to_owned))
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Pull {
     #[allow(dead_code)] // used by pull history display in future phases
@@ -41,159 +76,2024 @@ pub struct EventWindow {
     pub window_ms: u64,
 }
 
-impl EventWindow {
-    pub fn new(window_ms: u64) -> Self {
-        Self { events: Vec::new(), window_ms }
+impl EventWindow {
+    pub fn new(window_ms: u64) -> Self {
+        Self { events: Vec::new(), window_ms }
+    }
+
+    pub fn push(&mut self, event: LogEvent, now_ms: u64) {
+        self.events.push(WindowedEvent { timestamp_ms: now_ms, event });
+        let cutoff = now_ms.saturating_sub(self.window_ms);
+        self.events.retain(|e| e.timestamp_ms >= cutoff);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Interrupt tracker (persists interruptible spell knowledge across pulls)
+// ---------------------------------------------------------------------------
+
+/// Class interrupt spells and their base (untalented) cooldowns in ms.
+const INTERRUPT_COOLDOWNS_MS: &[(u32, u64)] = &[
+    (1766,   15_000), // Kick
+    (6552,   15_000), // Pummel
+    (96231,  15_000), // Rebuke
+    (47528,  15_000), // Mind Freeze
+    (183752, 15_000), // Disrupt
+    (106839, 15_000), // Skull Bash
+    (116705, 15_000), // Spear Hand Strike
+    (187707, 15_000), // Muzzle
+    (57994,  12_000), // Wind Shear
+    (147362, 24_000), // Counter Shot
+    (2139,   24_000), // Counterspell
+    (119910, 24_000), // Spell Lock (Command Demon)
+    (351338, 40_000), // Quell
+    (15487,  45_000), // Silence
+    (78675,  60_000), // Solar Beam
+];
+
+/// A group member's interrupt, learned from their casts of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartyKick {
+    pub name:    String,
+    cooldown_ms: u64,
+    last_ms:     u64,
+}
+
+#[derive(Debug, Default)]
+pub struct InterruptTracker {
+    /// Spell IDs the coached player has successfully interrupted before.
+    /// Populated from SPELL_INTERRUPT events; persists across pulls (learned knowledge).
+    pub interruptible_spells: HashSet<u32>,
+    /// The player's interrupt (spell_id, cooldown ms), learned from their first use.
+    kick:              Option<(u32, u64)>,
+    last_kick_ms:      Option<u64>,
+    /// Interruptible enemy casts that completed while the kick was ready, this pull.
+    pub missed_ready:  u32,
+    /// Session totals behind the session interrupt efficiency.
+    pub session_landed: u32,
+    pub session_missed: u32,
+    /// Other group members' kicks by GUID (the interrupt rotation).  Learned
+    /// from SPELL_CAST_SUCCESS rather than SPELL_INTERRUPT: a kick that
+    /// finds nothing to stop goes on cooldown all the same.
+    party_kicks:        HashMap<String, PartyKick>,
+}
+
+impl InterruptTracker {
+    /// A kick by the coached player landed on `interrupted_spell_id`.
+    pub fn record_interrupt(&mut self, interrupted_spell_id: u32) {
+        self.interruptible_spells.insert(interrupted_spell_id);
+        self.session_landed += 1;
+    }
+
+    pub fn is_interruptible(&self, spell_id: u32) -> bool {
+        self.interruptible_spells.contains(&spell_id)
+    }
+
+    /// Note a cast by the coached player; interrupt spells start the kick cooldown.
+    pub fn record_player_cast(&mut self, spell_id: u32, timestamp_ms: u64) {
+        if let Some(&(_, cd)) = INTERRUPT_COOLDOWNS_MS.iter().find(|(id, _)| *id == spell_id) {
+            self.kick = Some((spell_id, cd));
+            self.last_kick_ms = Some(timestamp_ms);
+        }
+    }
+
+    /// Note a cast by another group member; interrupt spells start their
+    /// kick cooldown estimate.
+    pub fn record_party_cast(&mut self, guid: &str, name: &str, spell_id: u32, timestamp_ms: u64) {
+        if let Some(&(_, cd)) = INTERRUPT_COOLDOWNS_MS.iter().find(|(id, _)| *id == spell_id) {
+            self.party_kicks.insert(guid.to_owned(), PartyKick {
+                name:        name.to_owned(),
+                cooldown_ms: cd,
+                last_ms:     timestamp_ms,
+            });
+        }
+    }
+
+    /// Group members whose kick is off cooldown at `now_ms`, by GUID.
+    pub fn party_kicks_ready(&self, now_ms: u64) -> impl Iterator<Item = (&str, &PartyKick)> {
+        self.party_kicks
+            .iter()
+            .filter(move |(_, k)| now_ms >= k.last_ms + k.cooldown_ms)
+            .map(|(guid, k)| (guid.as_str(), k))
+    }
+
+    /// Whether any other group member's kick has been seen.
+    pub fn knows_party_kicks(&self) -> bool {
+        !self.party_kicks.is_empty()
+    }
+
+    /// Whether the player's kick is off cooldown.  False until the player has
+    /// used a kick this session — without one we cannot tell they have it.
+    pub fn kick_ready(&self, now_ms: u64) -> bool {
+        match (self.kick, self.last_kick_ms) {
+            (Some((_, cd)), Some(last)) => now_ms >= last + cd,
+            _ => false,
+        }
+    }
+
+    /// An interruptible enemy cast completed; counts against efficiency only
+    /// when the kick was ready.
+    pub fn record_completed_cast(&mut self, spell_id: u32, now_ms: u64) {
+        if self.is_interruptible(spell_id) && self.kick_ready(now_ms) {
+            self.missed_ready   += 1;
+            self.session_missed += 1;
+        }
+    }
+
+    /// Called on pull start — keeps learned spell IDs and the kick (knowledge
+    /// persists); clears the per-pull miss count.
+    pub fn reset_per_pull(&mut self) {
+        self.missed_ready = 0;
+    }
+}
+
+/// Kicks landed as a percentage of kick opportunities (landed + missed while
+/// ready); None when there were no opportunities.
+pub fn interrupt_efficiency(landed: u32, missed: u32) -> Option<f32> {
+    let total = landed + missed;
+    (total > 0).then(|| landed as f32 / total as f32 * 100.0)
+}
+
+// ---------------------------------------------------------------------------
+// Enemy cast tracker (casts in progress → real-time kick alerts)
+// ---------------------------------------------------------------------------
+
+/// A cast open this long is assumed to have ended unseen (caster out of
+/// logging range, or a channel that never logs its end).
+const ENEMY_CAST_EXPIRY_MS: u64 = 8_000;
+
+/// An enemy cast in progress, from SPELL_CAST_START.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnemyCast {
+    pub caster_guid: String,
+    pub spell_id:    u32,
+    pub spell_name:  String,
+    pub start_ms:    u64,
+}
+
+#[derive(Debug, Default)]
+pub struct EnemyCastTracker {
+    /// Open casts by caster GUID — a unit casts one spell at a time.
+    casts: HashMap<String, EnemyCast>,
+}
+
+impl EnemyCastTracker {
+    pub fn start(&mut self, caster_guid: &str, spell_id: u32, spell_name: &str, now_ms: u64) {
+        self.casts.retain(|_, c| now_ms < c.start_ms + ENEMY_CAST_EXPIRY_MS);
+        self.casts.insert(caster_guid.to_owned(), EnemyCast {
+            caster_guid: caster_guid.to_owned(),
+            spell_id,
+            spell_name:  spell_name.to_owned(),
+            start_ms:    now_ms,
+        });
+    }
+
+    /// The caster's cast ended — completed, interrupted, failed or the
+    /// caster died.
+    pub fn end(&mut self, caster_guid: &str) {
+        self.casts.remove(caster_guid);
+    }
+
+    /// Casts still in progress at `now_ms`, oldest first.
+    pub fn in_progress(&self, now_ms: u64) -> Vec<&EnemyCast> {
+        let mut open: Vec<&EnemyCast> = self.casts.values()
+            .filter(|c| now_ms < c.start_ms + ENEMY_CAST_EXPIRY_MS)
+            .collect();
+        open.sort_by_key(|c| c.start_ms);
+        open
+    }
+
+    pub fn reset(&mut self) {
+        self.casts.clear();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Damage taken tracker (rolling window for defensive timing rule)
+// ---------------------------------------------------------------------------
+
+/// Whose side a hit on a player came from.  Damage from the player's own
+/// side — self-inflicted (Burning Rush) or from a party player (reflected
+/// or shared damage) — is real damage but not an avoidable mechanic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageSource {
+    Enemy,
+    SelfInflicted,
+    Party,
+}
+
+impl DamageSource {
+    /// `victim_guid`: the player hit.  A party player is a friendly group
+    /// player by the source's COMBATLOG_OBJECT_* flags.
+    pub fn of(source_guid: &str, source_flags: u32, victim_guid: Option<&str>) -> Self {
+        if Some(source_guid) == victim_guid {
+            DamageSource::SelfInflicted
+        } else if is_group_player(source_flags) {
+            DamageSource::Party
+        } else {
+            DamageSource::Enemy
+        }
+    }
+
+    pub fn is_friendly(self) -> bool {
+        self != DamageSource::Enemy
+    }
+}
+
+/// Friendly damage of one spell on the coached player over a pull.
+#[derive(Debug, Clone, Copy)]
+pub struct FriendlyDamage {
+    pub source: DamageSource,
+    pub hits:   u32,
+    pub amount: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct DamageTakenTracker {
+    /// (timestamp_ms, amount, absorbed) — appended on every hit and every
+    /// shield absorb, cleared on pull start.
+    pub events: Vec<(u64, u64, bool)>,
+    /// The friendly part of the hits, by spell name (debrief line).
+    pub friendly: HashMap<String, FriendlyDamage>,
+}
+
+impl DamageTakenTracker {
+    pub fn record(&mut self, timestamp_ms: u64, amount: u64) {
+        self.events.push((timestamp_ms, amount, false));
+    }
+
+    /// A hit recorded with `record` that came from the player's own side.
+    pub fn record_friendly(&mut self, source: DamageSource, spell_name: &str, amount: u64) {
+        let f = self.friendly.entry(spell_name.to_owned())
+            .or_insert(FriendlyDamage { source, hits: 0, amount: 0 });
+        f.hits   += 1;
+        f.amount += amount;
+    }
+
+    /// Friendly damage of this pull from `source`.
+    pub fn friendly_total(&self, source: DamageSource) -> u64 {
+        self.friendly.values().filter(|f| f.source == source).map(|f| f.amount).sum()
+    }
+
+    /// Damage a shield soaked (SPELL_ABSORBED) — still pressure on the
+    /// player, just not visible on their health bar.
+    pub fn record_absorbed(&mut self, timestamp_ms: u64, amount: u64) {
+        self.events.push((timestamp_ms, amount, true));
+    }
+
+    /// Sum of damage taken in the last `window_ms` milliseconds, absorbed
+    /// damage included.
+    /// Read-only — pruning deferred to pull reset (bounded by pull duration).
+    pub fn recent_damage(&self, now_ms: u64, window_ms: u64) -> u64 {
+        self.recent(now_ms, window_ms, |_| true)
+    }
+
+    /// The absorbed part of `recent_damage`.
+    pub fn recent_absorbed(&self, now_ms: u64, window_ms: u64) -> u64 {
+        self.recent(now_ms, window_ms, |absorbed| absorbed)
+    }
+
+    fn recent(&self, now_ms: u64, window_ms: u64, keep: impl Fn(bool) -> bool) -> u64 {
+        let cutoff = now_ms.saturating_sub(window_ms);
+        self.events.iter()
+            .filter(|(ts, _, absorbed)| *ts >= cutoff && keep(*absorbed))
+            .map(|(_, amt, _)| *amt)
+            .sum()
+    }
+
+    pub fn reset(&mut self) {
+        self.events.clear();
+        self.friendly.clear();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Death tracker (first death per pull → progression nudges)
+// ---------------------------------------------------------------------------
+
+/// The coached player's first death in a pull.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullDeath {
+    /// Milliseconds since pull start.
+    pub elapsed_ms: u64,
+    /// Last ability that hit the player before dying ("Melee" for swings).
+    pub cause:      String,
+}
+
+#[derive(Debug, Default)]
+pub struct DeathTracker {
+    last_hit:  Option<String>,
+    pub death: Option<PullDeath>,
+}
+
+impl DeathTracker {
+    pub fn record_hit(&mut self, ability: &str) {
+        self.last_hit = Some(ability.to_owned());
+    }
+
+    /// Only the first death counts — after a battle res the pull is
+    /// usually lost already.
+    pub fn record_death(&mut self, elapsed_ms: u64) {
+        if self.death.is_none() {
+            let cause = self.last_hit.clone().unwrap_or_else(|| "unknown".to_owned());
+            self.death = Some(PullDeath { elapsed_ms, cause });
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Group deaths and battle resurrections (how a pull fell apart)
+// ---------------------------------------------------------------------------
+
+/// In-combat resurrections, which share the group's battle-res charges.
+const BATTLE_RES_SPELL_IDS: &[u32] = &[
+    20484,  // Rebirth
+    61999,  // Raise Ally
+    391054, // Intercession
+    20707,  // Soulstone
+];
+
+pub fn is_battle_res(spell_id: u32) -> bool {
+    BATTLE_RES_SPELL_IDS.contains(&spell_id)
+}
+
+/// A group player's death, in pull order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupDeath {
+    pub name:       String,
+    /// Milliseconds since pull start.
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BattleRes {
+    pub caster:     String,
+    pub target:     String,
+    pub elapsed_ms: u64,
+}
+
+/// Every group player's death this pull (coached player included) and the
+/// battle resurrections cast.
+#[derive(Debug, Default)]
+pub struct GroupDeathTracker {
+    pub deaths:     Vec<GroupDeath>,
+    pub battle_res: Vec<BattleRes>,
+}
+
+impl GroupDeathTracker {
+    pub fn record_death(&mut self, name: &str, elapsed_ms: u64) {
+        self.deaths.push(GroupDeath { name: name.to_owned(), elapsed_ms });
+    }
+
+    /// A group player's cast; kept when it is a battle res.
+    pub fn record_cast(&mut self, spell_id: u32, caster: &str, target: &str, elapsed_ms: u64) {
+        if is_battle_res(spell_id) {
+            self.battle_res.push(BattleRes { caster: caster.to_owned(), target: target.to_owned(), elapsed_ms });
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Defensive consumables (health potions, healthstones)
+// ---------------------------------------------------------------------------
+
+/// The coached player's potion and healthstone uses.  Last uses persist
+/// across pulls (the cooldowns do); counts are per pull.
+#[derive(Debug, Default)]
+pub struct ConsumableTracker {
+    /// Log timestamp (ms) of the last use per kind.
+    last_used:        HashMap<ConsumableKind, u64>,
+    /// A healthstone was used this session, so someone hands them out.
+    has_healthstones: bool,
+    pub potions:      u32,
+    pub healthstones: u32,
+}
+
+impl ConsumableTracker {
+    /// A cast by the coached player; counted when it is a consumable.
+    pub fn record_use(&mut self, spell_id: u32, now_ms: u64) {
+        let Some(kind) = consumables::kind_of(spell_id) else { return };
+        self.last_used.insert(kind, now_ms);
+        match kind {
+            ConsumableKind::Potion      => self.potions += 1,
+            ConsumableKind::Healthstone => {
+                self.healthstones += 1;
+                self.has_healthstones = true;
+            }
+        }
+    }
+
+    /// The player most likely had one of `kind` ready: off its shared
+    /// cooldown, and for healthstones only once one was seen this session.
+    pub fn available(&self, kind: ConsumableKind, now_ms: u64) -> bool {
+        if kind == ConsumableKind::Healthstone && !self.has_healthstones {
+            return false;
+        }
+        self.last_used
+            .get(&kind)
+            .is_none_or(|&at| now_ms.saturating_sub(at) >= consumables::cooldown_ms(kind))
+    }
+
+    /// Log time of the latest potion or healthstone, if any.
+    pub fn last_use_ms(&self) -> Option<u64> {
+        self.last_used.values().max().copied()
+    }
+
+    /// Kinds ready at `now_ms`.
+    pub fn ready(&self, now_ms: u64) -> Vec<ConsumableKind> {
+        ConsumableKind::ALL.into_iter().filter(|&k| self.available(k, now_ms)).collect()
+    }
+
+    pub fn start_pull(&mut self) {
+        self.potions = 0;
+        self.healthstones = 0;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Pre-pull preparation (the coached player's casts before ENCOUNTER_START)
+// ---------------------------------------------------------------------------
+
+/// How long before ENCOUNTER_START a cast counts as pre-pull preparation.
+pub const PREPULL_WINDOW_MS: u64 = 30_000;
+
+/// The coached player's casts outside encounters over the last
+/// PREPULL_WINDOW_MS, kept across pull boundaries: the pre-cast itself
+/// usually starts a pull moments before ENCOUNTER_START, and the pre-pot
+/// can come at the end of the previous trash pull.
+#[derive(Debug, Default)]
+pub struct PrepullTracker {
+    /// (log timestamp ms, spell ID), oldest first.
+    recent:           VecDeque<(u64, u32)>,
+    /// Spells cast in the window before this pull's ENCOUNTER_START; None
+    /// for pulls without one.
+    pub at_encounter: Option<Vec<u32>>,
+}
+
+impl PrepullTracker {
+    /// A cast started or completed by the coached player outside an encounter.
+    pub fn record(&mut self, spell_id: u32, now_ms: u64) {
+        self.recent.push_back((now_ms, spell_id));
+        while self.recent.front().is_some_and(|&(at, _)| now_ms.saturating_sub(at) > PREPULL_WINDOW_MS) {
+            self.recent.pop_front();
+        }
+    }
+
+    /// ENCOUNTER_START: keep what was cast in the window before it.
+    pub fn encounter_start(&mut self, now_ms: u64) {
+        let casts = self.recent.iter()
+            .filter(|&&(at, _)| now_ms.saturating_sub(at) <= PREPULL_WINDOW_MS)
+            .map(|&(_, id)| id)
+            .collect();
+        self.at_encounter = Some(casts);
+        self.recent.clear();
+    }
+
+    pub fn start_pull(&mut self) {
+        self.at_encounter = None;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Damage done tracker (per-target outgoing damage → boss-damage share)
+// ---------------------------------------------------------------------------
+
+/// Damage one enemy unit took this pull.
+#[derive(Debug, Clone, Default)]
+pub struct TargetDamage {
+    pub name:     String,
+    /// Dealt by the coached player (pets excluded).
+    pub player:   u64,
+    /// Dealt by anyone — used to find the boss when no NPC IDs are known.
+    pub everyone: u64,
+}
+
+/// The coached player's damage on the boss vs everything else.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BossShare {
+    pub boss_name:    String,
+    pub boss_damage:  u64,
+    pub total_damage: u64,
+}
+
+impl BossShare {
+    pub fn pct(&self) -> f32 {
+        self.boss_damage as f32 * 100.0 / self.total_damage.max(1) as f32
+    }
+}
+
+/// Per-target outgoing damage for the boss-damage share.  Boss units are the
+/// encounter TOML's `boss_npc_ids`; without them, the enemy that took the most
+/// damage from everyone is assumed to be the boss.
+#[derive(Debug, Default)]
+pub struct DamageDoneTracker {
+    /// Set by the engine on ENCOUNTER_START — trash pulls report no share.
+    encounter:    bool,
+    boss_npc_ids: Vec<u32>,
+    /// dest GUID -> damage taken; enemy units only.
+    pub targets:  HashMap<String, TargetDamage>,
+}
+
+impl DamageDoneTracker {
+    pub fn set_encounter(&mut self, boss_npc_ids: &[u32]) {
+        self.encounter    = true;
+        self.boss_npc_ids = boss_npc_ids.to_vec();
+    }
+
+    /// `dest_name` may be empty (SWING_DAMAGE); a later spell hit fills it in.
+    pub fn record(&mut self, dest_guid: &str, dest_name: &str, amount: u64, from_player: bool) {
+        if !guid::kind(dest_guid).is_npc() {
+            return;
+        }
+        let t = self.targets.entry(dest_guid.to_owned()).or_default();
+        if t.name.is_empty() {
+            t.name = dest_name.to_owned();
+        }
+        t.everyone += amount;
+        if from_player {
+            t.player += amount;
+        }
+    }
+
+    /// Boss units of an encounter pull: the `boss_npc_ids` targets, or the
+    /// most-damaged enemy when the profile names none.  Empty on trash.
+    fn bosses(&self) -> Vec<(&String, &TargetDamage)> {
+        if !self.encounter {
+            return Vec::new();
+        }
+        if self.boss_npc_ids.is_empty() {
+            self.targets.iter().max_by_key(|(_, t)| t.everyone).into_iter().collect()
+        } else {
+            self.targets
+                .iter()
+                .filter(|(g, _)| guid::classify(g, &self.boss_npc_ids) == UnitKind::Boss)
+                .collect()
+        }
+    }
+
+    /// GUID and name of each boss unit (see `bosses`).
+    pub fn boss_targets(&self) -> Vec<(&str, &str)> {
+        self.bosses().into_iter().map(|(g, t)| (g.as_str(), t.name.as_str())).collect()
+    }
+
+    /// Whether `guid` is one of the boss units (see `bosses`).
+    pub fn is_boss(&self, guid: &str) -> bool {
+        self.bosses().iter().any(|(g, _)| g.as_str() == guid)
+    }
+
+    /// Everyone's damage on the boss units this pull (wipe forecast).
+    pub fn group_boss_damage(&self) -> u64 {
+        self.bosses().iter().map(|(_, t)| t.everyone).sum()
+    }
+
+    /// Boss share for an encounter pull with player damage, else None.
+    pub fn boss_share(&self) -> Option<BossShare> {
+        let total_damage: u64 = self.targets.values().map(|t| t.player).sum();
+        if total_damage == 0 {
+            return None;
+        }
+        let bosses = self.bosses();
+        let (_, first) = bosses.first()?;
+        Some(BossShare {
+            boss_name:    first.name.clone(),
+            boss_damage:  bosses.iter().map(|(_, t)| t.player).sum(),
+            total_damage,
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.encounter = false;
+        self.boss_npc_ids.clear();
+        self.targets.clear();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Burst tracker (rolling player DPS vs major cooldown windows)
+// ---------------------------------------------------------------------------
+
+/// Assumed length of a burst window from a major cooldown cast.  Spec
+/// profiles carry no durations; most throughput cooldowns last 15–20s.
+pub const BURST_WINDOW_MS: u64 = 20_000;
+/// Rolling DPS windows reported in the debrief.
+const DPS_SHORT_S: usize = 10;
+const DPS_LONG_S: usize = 30;
+
+/// Rolling-DPS peaks and burst alignment over a pull.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BurstSummary {
+    /// Best 10s / 30s average DPS (the whole pull when it was shorter).
+    pub peak_dps_short: u64,
+    pub peak_dps_long:  u64,
+    /// Share of the player's damage that landed inside a burst window, in %.
+    pub in_window_pct:  f32,
+    pub windows:        u32,
+    /// Whether the best 10s was mostly inside a burst window.
+    pub peak_in_window: bool,
+}
+
+/// The coached player's damage per second of the pull and the windows
+/// opened by their major cooldowns.
+#[derive(Debug, Default)]
+pub struct BurstTracker {
+    start_ms:       u64,
+    /// Player damage bucketed by second since pull start.
+    pub per_second: Vec<u64>,
+    /// (start, end) of each burst window; overlapping casts are merged.
+    windows:        Vec<(u64, u64)>,
+}
+
+impl BurstTracker {
+    pub fn start_pull(&mut self, start_ms: u64) {
+        self.start_ms = start_ms;
+        self.per_second.clear();
+        self.windows.clear();
+    }
+
+    pub fn record_damage(&mut self, now_ms: u64, amount: u64) {
+        let second = (now_ms.saturating_sub(self.start_ms) / 1_000) as usize;
+        if second >= self.per_second.len() {
+            self.per_second.resize(second + 1, 0);
+        }
+        self.per_second[second] += amount;
+    }
+
+    /// A major cooldown was cast: open (or extend) a burst window.
+    pub fn cooldown_used(&mut self, now_ms: u64) {
+        let end = now_ms + BURST_WINDOW_MS;
+        match self.windows.last_mut() {
+            Some(last) if now_ms <= last.1 => last.1 = last.1.max(end),
+            _ => self.windows.push((now_ms, end)),
+        }
+    }
+
+    /// Whether the middle of pull second `second` is inside a burst window.
+    fn in_window(&self, second: usize) -> bool {
+        let ms = self.start_ms + second as u64 * 1_000 + 500;
+        self.windows.iter().any(|&(start, end)| (start..end).contains(&ms))
+    }
+
+    /// Best `span`-second damage total and the second it starts at.
+    fn peak(&self, span: usize) -> (u64, usize) {
+        let span = span.clamp(1, self.per_second.len().max(1));
+        self.per_second
+            .windows(span)
+            .enumerate()
+            .map(|(i, w)| (w.iter().sum::<u64>() / span as u64, i))
+            .max_by_key(|&(dps, i)| (dps, std::cmp::Reverse(i)))
+            .unwrap_or_default()
+    }
+
+    /// None without player damage or without a major cooldown cast.
+    pub fn summary(&self) -> Option<BurstSummary> {
+        let total: u64 = self.per_second.iter().sum();
+        if total == 0 || self.windows.is_empty() {
+            return None;
+        }
+        let inside: u64 = self.per_second
+            .iter()
+            .enumerate()
+            .filter(|&(s, _)| self.in_window(s))
+            .map(|(_, d)| d)
+            .sum();
+        let (peak_dps_short, peak_start) = self.peak(DPS_SHORT_S);
+        let span = DPS_SHORT_S.min(self.per_second.len());
+        let peak_inside = (peak_start..peak_start + span).filter(|&s| self.in_window(s)).count();
+        Some(BurstSummary {
+            peak_dps_short,
+            peak_dps_long:  self.peak(DPS_LONG_S).0,
+            in_window_pct:  inside as f32 / total as f32 * 100.0,
+            windows:        self.windows.len() as u32,
+            peak_in_window: peak_inside * 2 > span,
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Target tracker (current target + kill-priority enemies → tunnel vision)
+// ---------------------------------------------------------------------------
+
+/// COMBATLOG_OBJECT_RAIDTARGET8 — the skull marker.
+const RAID_FLAG_SKULL: u32 = 0x80;
+/// A priority enemy not seen in any damage event for this long is assumed
+/// gone (despawned, evaded) even without UNIT_DIED.
+const PRIORITY_SEEN_WINDOW_MS: u64 = 10_000;
+
+/// One kill-priority enemy alive this pull.
+#[derive(Debug, Clone)]
+pub struct PriorityTarget {
+    pub name:         String,
+    /// First damage event involving it.
+    pub active_since: u64,
+    pub last_seen:    u64,
+    /// When the coached player first hit it (None = not yet).
+    pub engaged_ms:   Option<u64>,
+}
+
+/// The coached player's current target and the kill-priority enemies
+/// (encounter TOML `priority_npc_ids`, or anything skull-marked) alive.
+///
+/// The log has no target field, so "current target" is the destination of
+/// the player's last targeted cast or auto-attack.  Spell damage is ignored:
+/// DoT ticks and cleave hit units the player is not focused on.
+#[derive(Debug, Default)]
+pub struct TargetTracker {
+    /// Set by the engine on ENCOUNTER_START and cleared on ENCOUNTER_END;
+    /// kept across `reset`, which also runs when a pull starts mid-encounter.
+    priority_npc_ids:     Vec<u32>,
+    pub current:          Option<String>,
+    pub current_name:     String,
+    /// When the player moved to `current`.
+    pub current_since:    u64,
+    /// GUID -> priority enemy; removed on UNIT_DIED.
+    pub priority:         HashMap<String, PriorityTarget>,
+    /// Priority enemy appearing → first player hit on it, per enemy (ms).
+    pub switch_latencies: Vec<u64>,
+}
+
+impl TargetTracker {
+    pub fn set_priority_npcs(&mut self, npc_ids: &[u32]) {
+        self.priority_npc_ids = npc_ids.to_vec();
+    }
+
+    /// An enemy took or dealt damage; registers it when it is kill-priority.
+    pub fn see_enemy(&mut self, guid: &str, name: &str, raid_flags: u32, now_ms: u64) {
+        if let Some(t) = self.priority.get_mut(guid) {
+            t.last_seen = now_ms;
+            if t.name.is_empty() {
+                t.name = name.to_owned();
+            }
+            return;
+        }
+        let listed = guid::npc_id(guid).is_some_and(|id| self.priority_npc_ids.contains(&id));
+        if listed || raid_flags & RAID_FLAG_SKULL != 0 {
+            self.priority.insert(guid.to_owned(), PriorityTarget {
+                name:         name.to_owned(),
+                active_since: now_ms,
+                last_seen:    now_ms,
+                engaged_ms:   None,
+            });
+        }
+    }
+
+    /// The coached player cast at or swung at an enemy.
+    pub fn player_hit(&mut self, guid: &str, name: &str, now_ms: u64) {
+        if self.current.as_deref() != Some(guid) {
+            self.current       = Some(guid.to_owned());
+            self.current_since = now_ms;
+            self.current_name.clear();
+        }
+        if self.current_name.is_empty() {
+            self.current_name = name.to_owned();
+        }
+        if let Some(t) = self.priority.get_mut(guid) {
+            if t.engaged_ms.is_none() {
+                t.engaged_ms = Some(now_ms);
+                self.switch_latencies.push(now_ms.saturating_sub(t.active_since));
+            }
+        }
+    }
+
+    pub fn unit_died(&mut self, guid: &str) {
+        self.priority.remove(guid);
+        if self.current.as_deref() == Some(guid) {
+            self.current = None;
+            self.current_name.clear();
+        }
+    }
+
+    /// Priority enemies still around that the player is not attacking.
+    pub fn neglected(&self, now_ms: u64) -> Vec<(&str, &PriorityTarget)> {
+        self.priority
+            .iter()
+            .filter(|(g, t)| {
+                self.current.as_deref() != Some(g.as_str())
+                    && now_ms.saturating_sub(t.last_seen) <= PRIORITY_SEEN_WINDOW_MS
+            })
+            .map(|(g, t)| (g.as_str(), t))
+            .collect()
+    }
+
+    pub fn reset(&mut self) {
+        self.current = None;
+        self.current_name.clear();
+        self.current_since = 0;
+        self.priority.clear();
+        self.switch_latencies.clear();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Melee uptime tracker (time in range of the boss for melee players)
+// ---------------------------------------------------------------------------
+
+/// Longest pause between the player's auto-attacks / boss-targeted casts that
+/// still counts as standing in melee (a slow two-hander swings every ~3.6s).
+const MELEE_GAP_MS: u64 = 4_000;
+/// Auto-attacks this pull before the player counts as melee.
+const MIN_SWINGS: u32 = 3;
+
+/// Melee uptime on the boss, estimated from contact cadence.  The boss
+/// taking damage from anyone is the clock: each such moment counts as in
+/// range when the player touched the boss within MELEE_GAP_MS, out of range
+/// otherwise — so intermissions where nobody can hit the boss count for
+/// neither.
+#[derive(Debug, Default)]
+pub struct MeleeTracker {
+    swings:             u32,
+    last_contact_ms:    Option<u64>,
+    last_boss_hit_ms:   Option<u64>,
+    pub in_range_ms:    u64,
+    pub out_range_ms:   u64,
+    /// Longest stretch between two contacts beyond MELEE_GAP_MS.
+    pub longest_gap_ms: u64,
+}
+
+impl MeleeTracker {
+    /// The coached player auto-attacked anything.
+    pub fn record_swing(&mut self) {
+        self.swings += 1;
+    }
+
+    /// The coached player auto-attacked or cast at the boss.
+    pub fn record_contact(&mut self, now_ms: u64) {
+        if let Some(last) = self.last_contact_ms {
+            let gap = now_ms.saturating_sub(last);
+            if gap > MELEE_GAP_MS {
+                self.longest_gap_ms = self.longest_gap_ms.max(gap);
+            }
+        }
+        self.last_contact_ms = Some(now_ms);
+    }
+
+    /// Anyone damaged the boss.  Samples further apart than MELEE_GAP_MS
+    /// (boss untargetable) count only MELEE_GAP_MS.
+    pub fn record_boss_hit(&mut self, now_ms: u64) {
+        if let Some(prev) = self.last_boss_hit_ms {
+            let step = now_ms.saturating_sub(prev).min(MELEE_GAP_MS);
+            if self.in_range(now_ms) {
+                self.in_range_ms += step;
+            } else {
+                self.out_range_ms += step;
+            }
+        }
+        self.last_boss_hit_ms = Some(now_ms);
+    }
+
+    fn in_range(&self, now_ms: u64) -> bool {
+        self.last_contact_ms.is_some_and(|c| now_ms.saturating_sub(c) <= MELEE_GAP_MS)
+    }
+
+    /// Whether the coached player fights in melee this pull.
+    pub fn is_melee(&self) -> bool {
+        self.swings >= MIN_SWINGS
+    }
+
+    /// Share of boss-attackable time spent in melee range, for melee players.
+    pub fn uptime_pct(&self) -> Option<f32> {
+        let total = self.in_range_ms + self.out_range_ms;
+        (self.is_melee() && total > 0).then(|| self.in_range_ms as f32 * 100.0 / total as f32)
+    }
+
+    /// How long a melee player has been away from a boss that others are
+    /// still hitting; None while in range, or before their first contact.
+    pub fn out_of_range_for(&self, now_ms: u64) -> Option<u64> {
+        let boss_active = self.last_boss_hit_ms.is_some_and(|h| now_ms.saturating_sub(h) <= MELEE_GAP_MS);
+        let last = self.last_contact_ms?;
+        (self.is_melee() && boss_active && !self.in_range(now_ms)).then(|| now_ms.saturating_sub(last))
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Player aura tracker (buffs on the coached player — readiness checklist)
+// ---------------------------------------------------------------------------
+
+/// Auras on the coached player, from SPELL_AURA_APPLIED/REMOVED.  Kept across
+/// pulls — flasks and food outlive any single pull.
+#[derive(Debug, Default)]
+pub struct AuraTracker {
+    /// spell_id -> name of auras currently on the player.
+    pub active:  HashMap<u32, String>,
+    /// spell_id -> name of auras removed this session and not re-applied.
+    pub expired: HashMap<u32, String>,
+}
+
+impl AuraTracker {
+    pub fn apply(&mut self, spell_id: u32, spell_name: &str) {
+        self.expired.remove(&spell_id);
+        self.active.insert(spell_id, spell_name.to_owned());
+    }
+
+    pub fn remove(&mut self, spell_id: u32, spell_name: &str) {
+        self.active.remove(&spell_id);
+        self.expired.insert(spell_id, spell_name.to_owned());
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Bloodlust / Heroism windows
+// ---------------------------------------------------------------------------
+
+/// Haste buffs that share the Sated/Exhaustion lockout.
+const LUST_SPELL_IDS: &[u32] = &[
+    2825,   // Bloodlust
+    32182,  // Heroism
+    80353,  // Time Warp
+    264667, // Primal Rage
+    390386, // Fury of the Aspects
+    444257, // Thunderous Drums
+    381301, // Feral Hide Drums
+];
+
+pub fn is_lust(spell_id: u32) -> bool {
+    LUST_SPELL_IDS.contains(&spell_id)
+}
+
+/// Lust on the coached player, whoever cast it (cooldown_drift rule).
+#[derive(Debug, Default)]
+pub struct LustTracker {
+    /// When the current lust went up; None while none is on the player.
+    pub up_since:    Option<u64>,
+    /// Name of the last lust buff ("Heroism").
+    pub name:        String,
+    /// Start and end of the last lust that faded this pull.
+    pub last_window: Option<(u64, u64)>,
+}
+
+impl LustTracker {
+    /// An aura applied to (or refreshed on) the player.
+    pub fn apply(&mut self, spell_id: u32, spell_name: &str, now_ms: u64) {
+        if is_lust(spell_id) && self.up_since.is_none() {
+            self.up_since = Some(now_ms);
+            self.name = spell_name.to_owned();
+        }
+    }
+
+    pub fn remove(&mut self, spell_id: u32, now_ms: u64) {
+        if is_lust(spell_id) {
+            if let Some(start) = self.up_since.take() {
+                self.last_window = Some((start, now_ms));
+            }
+        }
+    }
+
+    /// Milliseconds since the last lust faded, if one did this pull.
+    pub fn faded_ago(&self, now_ms: u64) -> Option<u64> {
+        self.last_window.map(|(_, end)| now_ms.saturating_sub(end))
+    }
+
+    /// A lust still up carries into the pull; a faded one does not.
+    pub fn start_pull(&mut self) {
+        self.last_window = None;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Buff / debuff uptime tracker
+// ---------------------------------------------------------------------------
+
+/// Coverage of one aura on one unit.
+#[derive(Debug, Default, Clone)]
+pub struct AuraUptime {
+    /// When the aura last went up; None while it is down.
+    pub up_since:     Option<u64>,
+    /// Covered time this pull, excluding the interval still running.
+    pub covered_ms:   u64,
+    /// Last SPELL_AURA_REFRESH.
+    pub refreshed_ms: Option<u64>,
+    /// Last SPELL_AURA_REMOVED.
+    pub removed_ms:   Option<u64>,
+}
+
+/// Uptime of auras cast by or on the coached player, per (unit GUID,
+/// spell_id), from SPELL_AURA_APPLIED/REFRESH/REMOVED.
+///
+/// Covered time is per pull: `start_pull` zeroes it and re-anchors auras
+/// that are still up to the pull start (a pre-pot or a DoT applied on the
+/// pull timer counts from the first second).  Entries that are down are
+/// dropped at the same time, so trash GUIDs do not accumulate.
+#[derive(Debug, Default)]
+pub struct BuffTracker {
+    pub auras:     HashMap<(String, u32), AuraUptime>,
+    /// Origin of the current accounting window (last pull start).
+    pub origin_ms: u64,
+}
+
+impl BuffTracker {
+    pub fn apply(&mut self, unit: &str, spell_id: u32, now_ms: u64) {
+        let aura = self.auras.entry((unit.to_owned(), spell_id)).or_default();
+        aura.up_since.get_or_insert(now_ms);
+    }
+
+    /// A refresh of an aura we never saw applied (logging started after it)
+    /// still proves it is up.
+    pub fn refresh(&mut self, unit: &str, spell_id: u32, now_ms: u64) {
+        let aura = self.auras.entry((unit.to_owned(), spell_id)).or_default();
+        aura.up_since.get_or_insert(now_ms);
+        aura.refreshed_ms = Some(now_ms);
+    }
+
+    pub fn remove(&mut self, unit: &str, spell_id: u32, now_ms: u64) {
+        let origin = self.origin_ms;
+        let aura = self.auras.entry((unit.to_owned(), spell_id)).or_default();
+        if let Some(since) = aura.up_since.take() {
+            aura.covered_ms += now_ms.saturating_sub(since.max(origin));
+        }
+        aura.removed_ms = Some(now_ms);
+    }
+
+    pub fn start_pull(&mut self, start_ms: u64) {
+        self.origin_ms = start_ms;
+        self.auras.retain(|_, a| a.up_since.is_some());
+        for aura in self.auras.values_mut() {
+            aura.covered_ms   = 0;
+            aura.up_since     = Some(start_ms);
+            aura.refreshed_ms = None;
+            aura.removed_ms   = None;
+        }
+    }
+
+    /// Auras on a dead unit are gone; its entries must not read as fall-offs.
+    pub fn unit_died(&mut self, unit: &str) {
+        self.auras.retain(|(u, _), _| u != unit);
+    }
+
+    #[allow(dead_code)] // queried by uptime rules in future phases
+    pub fn is_up(&self, unit: &str, spell_id: u32) -> bool {
+        self.auras
+            .get(&(unit.to_owned(), spell_id))
+            .is_some_and(|a| a.up_since.is_some())
+    }
+
+    /// Covered ms of `spell_id` on `unit` from the pull start to `now_ms`.
+    pub fn covered_ms(&self, unit: &str, spell_id: u32, now_ms: u64) -> u64 {
+        self.auras
+            .get(&(unit.to_owned(), spell_id))
+            .map(|a| self.covered(a, now_ms))
+            .unwrap_or(0)
+    }
+
+    fn covered(&self, aura: &AuraUptime, now_ms: u64) -> u64 {
+        let running = aura.up_since
+            .map(|since| now_ms.saturating_sub(since.max(self.origin_ms)))
+            .unwrap_or(0);
+        aura.covered_ms + running
+    }
+
+    /// Uptime % of `spell_id` on `unit` over the pull so far, where
+    /// `pull_elapsed_ms` is the time since the pull started.
+    pub fn unit_uptime_pct(&self, unit: &str, spell_id: u32, pull_elapsed_ms: u64) -> f32 {
+        if pull_elapsed_ms == 0 {
+            return 0.0;
+        }
+        let covered = self.covered_ms(unit, spell_id, self.origin_ms + pull_elapsed_ms);
+        (covered.min(pull_elapsed_ms) as f32 / pull_elapsed_ms as f32) * 100.0
+    }
+
+    /// Uptime % of `spell_id` this pull on whichever unit it covered best —
+    /// the player for a self-buff, the main target for a DoT.
+    #[allow(dead_code)] // queried by uptime rules in future phases
+    pub fn uptime_pct(&self, spell_id: u32, pull_elapsed_ms: u64) -> f32 {
+        self.auras
+            .keys()
+            .filter(|(_, id)| *id == spell_id)
+            .map(|(unit, _)| self.unit_uptime_pct(unit, spell_id, pull_elapsed_ms))
+            .fold(0.0, f32::max)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Avoidable damage tracker
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+pub struct AvoidableTracker {
+    /// spell_id -> hit count this pull
+    pub hit_counts:     HashMap<u32, u32>,
+    /// spell_id -> timestamps of each hit
+    pub hit_timestamps: HashMap<u32, Vec<u64>>,
+    /// Spell IDs that hit the player this session; kept across pulls.
+    session_spells:     HashSet<u32>,
+}
+
+impl AvoidableTracker {
+    pub fn record_hit(&mut self, spell_id: u32, timestamp_ms: u64) {
+        *self.hit_counts.entry(spell_id).or_insert(0) += 1;
+        self.hit_timestamps.entry(spell_id).or_default().push(timestamp_ms);
+        self.session_spells.insert(spell_id);
+    }
+
+    /// Whether `spell_id` has hit the player in any pull this session.
+    pub fn hit_this_session(&self, spell_id: u32) -> bool {
+        self.session_spells.contains(&spell_id)
+    }
+
+    pub fn hit_count(&self, spell_id: u32) -> u32 {
+        self.hit_counts.get(&spell_id).copied().unwrap_or(0)
+    }
+
+    pub fn total_hits(&self) -> u32 {
+        self.hit_counts.values().sum()
+    }
+
+    /// Called on pull start — keeps the session's hit spells.
+    pub fn reset(&mut self) {
+        self.hit_counts.clear();
+        self.hit_timestamps.clear();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Resource tracker — generated vs lost to the cap (SPELL_ENERGIZE)
+// ---------------------------------------------------------------------------
+
+/// One resource's generation this pull.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceFlow {
+    /// Generated, overflow included.
+    pub gained: u64,
+    /// Lost to overcapping.
+    pub wasted: u64,
+    /// The resource's cap as last logged.
+    pub max:    u32,
+}
+
+impl ResourceFlow {
+    /// Share of the generated resource that was wasted, 0–100.
+    pub fn waste_pct(&self) -> f32 {
+        if self.gained == 0 { 0.0 } else { self.wasted as f32 / self.gained as f32 * 100.0 }
+    }
+}
+
+/// The coached player's resource generation per power type this pull.
+/// Mana is left out (see `practice::overcap_counts`).
+#[derive(Debug, Default)]
+pub struct ResourceTracker {
+    pub flows: HashMap<u8, ResourceFlow>,
+}
+
+impl ResourceTracker {
+    pub fn record(&mut self, power_type: u8, amount: u64, overflow: u64, max: u32) {
+        if !practice::overcap_counts(power_type) {
+            return;
+        }
+        let flow = self.flows.entry(power_type).or_default();
+        flow.gained += amount.max(overflow);
+        flow.wasted += overflow;
+        if max > 0 {
+            flow.max = max;
+        }
+    }
+
+    pub fn flow(&self, power_type: u8) -> Option<&ResourceFlow> {
+        self.flows.get(&power_type)
+    }
+
+    pub fn reset(&mut self) {
+        self.flows.clear();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Dodge-rate tracker — avoided vs hit, per avoidable mechanic
+// ---------------------------------------------------------------------------
+
+/// A cast of an avoidable mechanic resolves as "hit" if it damages the player
+/// within this window, otherwise as "avoided".  Casts landing inside an open
+/// window belong to the same volley (several adds casting together).
+const DODGE_WINDOW_MS: u64 = 4_000;
+
+/// Outcomes of one avoidable mechanic this pull.
+#[derive(Debug, Clone, Default)]
+pub struct MechanicDodge {
+    pub spell_name: String,
+    pub hits:       u32,
+    pub avoids:     u32,
+    /// Log time of the latest cast that has not hit the player yet.
+    open_cast_ms:   Option<u64>,
+    /// Log time of the last counted hit — later ticks of the same cast are ignored.
+    last_hit_ms:    Option<u64>,
+}
+
+impl MechanicDodge {
+    /// Count an expired open cast as avoided.
+    fn resolve(&mut self, now_ms: u64) {
+        if let Some(t) = self.open_cast_ms {
+            if now_ms.saturating_sub(t) > DODGE_WINDOW_MS {
+                self.avoids += 1;
+                self.open_cast_ms = None;
+            }
+        }
+    }
+
+    /// Avoided / (avoided + hit) as a percentage; None before any occurrence.
+    pub fn dodge_pct(&self) -> Option<f32> {
+        let total = self.hits + self.avoids;
+        (total > 0).then(|| self.avoids as f32 * 100.0 / total as f32)
+    }
+}
+
+/// Tracks the encounter's avoidable mechanics (encounter TOML
+/// `avoidable_spell_ids`).  An occurrence is a cast of the mechanic
+/// (SPELL_CAST_SUCCESS) at the player or untargeted (AoE, ground effects);
+/// a cast at someone else is theirs to dodge.  It counts as a hit if the
+/// player takes damage from it within `DODGE_WINDOW_MS`, and as avoided
+/// otherwise — or immediately on a SPELL_MISSED against the player.  Hits
+/// with no logged cast (ground effects spawned by another spell) still count
+/// as occurrences.
+#[derive(Debug, Default)]
+pub struct DodgeTracker {
+    /// Spell IDs being tracked — set by the engine when an encounter loads,
+    /// kept across pull resets.
+    tracked:       HashSet<u32>,
+    pub mechanics: HashMap<u32, MechanicDodge>,
+}
+
+impl DodgeTracker {
+    pub fn set_tracked(&mut self, spell_ids: &[u32]) {
+        self.tracked = spell_ids.iter().copied().collect();
+    }
+
+    fn entry(&mut self, spell_id: u32, spell_name: &str, now_ms: u64) -> Option<&mut MechanicDodge> {
+        if !self.tracked.contains(&spell_id) {
+            return None;
+        }
+        let m = self.mechanics.entry(spell_id).or_default();
+        if m.spell_name.is_empty() {
+            m.spell_name = spell_name.to_owned();
+        }
+        m.resolve(now_ms);
+        Some(m)
+    }
+
+    /// A cast of a mechanic at `dest_guid` (empty when untargeted).
+    pub fn record_cast(
+        &mut self,
+        spell_id:    u32,
+        spell_name:  &str,
+        dest_guid:   &str,
+        player_guid: Option<&str>,
+        now_ms:      u64,
+    ) {
+        if !dest_guid.is_empty() && Some(dest_guid) != player_guid {
+            return;
+        }
+        if let Some(m) = self.entry(spell_id, spell_name, now_ms) {
+            // Still-open casts are the same volley; keep the earliest.
+            m.open_cast_ms.get_or_insert(now_ms);
+        }
+    }
+
+    pub fn record_hit(&mut self, spell_id: u32, spell_name: &str, now_ms: u64) {
+        if let Some(m) = self.entry(spell_id, spell_name, now_ms) {
+            let from_cast = m.open_cast_ms.take().is_some();
+            if from_cast || m.last_hit_ms.is_none_or(|t| now_ms.saturating_sub(t) > DODGE_WINDOW_MS) {
+                m.hits += 1;
+                m.last_hit_ms = Some(now_ms);
+            }
+        }
+    }
+
+    pub fn record_miss(&mut self, spell_id: u32, spell_name: &str, now_ms: u64) {
+        if let Some(m) = self.entry(spell_id, spell_name, now_ms) {
+            if m.open_cast_ms.take().is_some() {
+                m.avoids += 1;
+            }
+        }
+    }
+
+    /// Per-mechanic totals with still-open casts counted as avoided
+    /// (called at pull end), sorted by spell ID.
+    pub fn summary(&self) -> Vec<(u32, MechanicDodge)> {
+        let mut out: Vec<(u32, MechanicDodge)> = self
+            .mechanics
+            .iter()
+            .map(|(id, m)| {
+                let mut m = m.clone();
+                if m.open_cast_ms.take().is_some() {
+                    m.avoids += 1;
+                }
+                (*id, m)
+            })
+            .collect();
+        out.sort_by_key(|(id, _)| *id);
+        out
+    }
+
+    pub fn reset(&mut self) {
+        self.mechanics.clear();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Cooldown tracker (inferred from observed SPELL_CAST_SUCCESS)
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+pub struct CooldownTracker {
+    /// spell_id -> last observed use timestamp
+    pub last_used: HashMap<u32, u64>,
+    /// Spells the player has cast this session; kept across pulls.
+    known:         HashSet<u32>,
+}
+
+impl CooldownTracker {
+    pub fn record_cast(&mut self, spell_id: u32, timestamp_ms: u64) {
+        self.last_used.insert(spell_id, timestamp_ms);
+        self.known.insert(spell_id);
+    }
+
+    /// Whether the player has cast `spell_id` in any pull this session —
+    /// the log's only sign that they have it talented.
+    pub fn knows(&self, spell_id: u32) -> bool {
+        self.known.contains(&spell_id)
+    }
+
+    /// How long ago was this spell last cast? None = never seen this pull.
+    #[allow(dead_code)] // used by cooldown-drift rule in future phases
+    pub fn elapsed_since_last(&self, spell_id: u32, now_ms: u64) -> Option<u64> {
+        self.last_used.get(&spell_id).map(|&t| now_ms.saturating_sub(t))
+    }
+
+    pub fn last_used_ms(&self, spell_id: u32) -> Option<u64> {
+        self.last_used.get(&spell_id).copied()
+    }
+
+    /// How long a cooldown sat ready before its use at `now_ms`: since the
+    /// pull started for its first use this pull, since it came off
+    /// `cooldown_ms` for a later one (0 when the cooldown is not known).
+    /// Read before `record_cast` records the use.
+    pub fn drift_ms(&self, spell_id: u32, cooldown_ms: Option<u64>, pull_start_ms: u64, now_ms: u64) -> u64 {
+        let ready_at = match self.last_used_ms(spell_id).filter(|&t| t >= pull_start_ms) {
+            None       => pull_start_ms,
+            Some(last) => match cooldown_ms {
+                Some(cd) => last + cd,
+                None     => return 0,
+            },
+        };
+        now_ms.saturating_sub(ready_at)
+    }
+
+    /// Called on pull start — keeps the spells known this session.
+    pub fn reset(&mut self) {
+        self.last_used.clear();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// GCD gap tracker
+// ---------------------------------------------------------------------------
+
+/// Likely reason for a GCD gap, from what happened to the player during it.
+/// Ordered by how well it explains the gap: the strongest cause seen wins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum GapCause {
+    /// No failed cast or crowd control — the player simply pressed nothing.
+    #[default]
+    Idle,
+    /// A cast failed because the player was moving.
+    Movement,
+    /// A cast failed because the player was silenced or pacified.
+    Silenced,
+    /// Stunned, feared, etc. — a failed cast said so, or an encounter CC
+    /// aura (`cc_spell_ids`) was on the player.
+    CrowdControl,
+}
+
+impl GapCause {
+    /// Cause named by a SPELL_CAST_FAILED failed type — either the log token
+    /// ("MOVING") or the client message ("Can't do that while moving").
+    pub fn from_failed_type(failed_type: &str) -> Option<Self> {
+        const CC_WORDS: &[&str] = &[
+            "stunned", "feared", "fleeing", "charmed", "confused", "incapacitated",
+            "asleep", "horrified", "polymorphed", "possessed",
+        ];
+        let t = failed_type.to_ascii_lowercase();
+        if t.contains("moving") {
+            Some(Self::Movement)
+        } else if t.contains("silenced") || t.contains("pacified") {
+            Some(Self::Silenced)
+        } else if CC_WORDS.iter().any(|w| t.contains(w)) {
+            Some(Self::CrowdControl)
+        } else {
+            None
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Idle         => "idle — no input",
+            Self::Movement     => "movement",
+            Self::Silenced     => "silence",
+            Self::CrowdControl => "crowd control",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct GcdTracker {
+    pub last_cast_ms:    Option<u64>,
+    /// Gap in ms between the last two casts
+    pub current_gap_ms:  u64,
+    /// Likely cause of `current_gap_ms`.
+    pub gap_cause:       GapCause,
+    /// Longest gap since the last reset (the pull so far).
+    pub longest_gap_ms:  u64,
+    /// Strongest cause seen since the last cast.
+    pending_cause:       GapCause,
+    /// Encounter CC auras currently on the player.
+    cc_auras:            HashSet<u32>,
+}
+
+impl GcdTracker {
+    pub fn record_cast(&mut self, timestamp_ms: u64) {
+        if let Some(last) = self.last_cast_ms {
+            self.current_gap_ms = timestamp_ms.saturating_sub(last);
+            self.longest_gap_ms = self.longest_gap_ms.max(self.current_gap_ms);
+        }
+        self.last_cast_ms  = Some(timestamp_ms);
+        self.gap_cause     = self.pending_cause;
+        self.pending_cause = if self.cc_auras.is_empty() { GapCause::Idle } else { GapCause::CrowdControl };
+    }
+
+    /// A player cast failed; `failed_type` may explain the gap in progress.
+    pub fn record_failed(&mut self, failed_type: &str) {
+        if let Some(cause) = GapCause::from_failed_type(failed_type) {
+            self.pending_cause = self.pending_cause.max(cause);
+        }
+    }
+
+    pub fn cc_applied(&mut self, spell_id: u32) {
+        self.cc_auras.insert(spell_id);
+        self.pending_cause = GapCause::CrowdControl;
+    }
+
+    pub fn cc_removed(&mut self, spell_id: u32) {
+        self.cc_auras.remove(&spell_id);
+    }
+
+    pub fn reset(&mut self) {
+        self.last_cast_ms   = None;
+        self.current_gap_ms = 0;
+        self.longest_gap_ms = 0;
+        self.gap_cause      = GapCause::Idle;
+        self.pending_cause  = GapCause::Idle;
+        self.cc_auras.clear();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Active time ("always be casting") tracker
+// ---------------------------------------------------------------------------
+
+/// Nominal GCD credited to instant casts (and as the minimum for hard casts).
+const NOMINAL_GCD_MS: u64 = 1_500;
+/// Idle stretches longer than this are forced downtime (phase transitions,
+/// intermissions, death) and are excluded from the active-time denominator.
+/// Matches gcd_gap's MAX_GAP_MS so both metrics agree on what is actionable.
+const FORCED_DOWNTIME_MS: u64 = 30_000;
+
+/// Cast/channel model for active-time percentage.
+///
+/// Every player cast covers `[start, max(success, start + GCD)]`, where
+/// `start` is the matching SPELL_CAST_START for hard casts or the success
+/// timestamp for instants.  Overlapping coverage is only counted once.
+#[derive(Debug, Default)]
+pub struct ActivityTracker {
+    /// Hard cast in progress: (spell_id, cast start ms).
+    pending_cast:  Option<(u32, u64)>,
+    /// End of the furthest active interval counted so far.
+    busy_until_ms: Option<u64>,
+    /// Total ms covered by casts/GCDs this pull.
+    pub active_ms:   u64,
+    /// Total ms of forced downtime excluded from the denominator.
+    pub downtime_ms: u64,
+}
+
+impl ActivityTracker {
+    pub fn record_cast_start(&mut self, spell_id: u32, timestamp_ms: u64) {
+        self.pending_cast = Some((spell_id, timestamp_ms));
+    }
+
+    /// Cast of `spell_id` interrupted / failed — the partial cast time is not
+    /// credited.  A failed off-GCD press during a hard cast leaves it pending.
+    pub fn cancel_cast(&mut self, spell_id: u32) {
+        if self.pending_cast.is_some_and(|(id, _)| id == spell_id) {
+            self.pending_cast = None;
+        }
+    }
+
+    pub fn record_cast_success(&mut self, spell_id: u32, timestamp_ms: u64) {
+        let start = match self.pending_cast.take() {
+            Some((id, start)) if id == spell_id => start,
+            _ => timestamp_ms,
+        };
+        let end = timestamp_ms.max(start + NOMINAL_GCD_MS);
+
+        let from = match self.busy_until_ms {
+            Some(busy) if start > busy => {
+                let idle = start - busy;
+                if idle > FORCED_DOWNTIME_MS {
+                    self.downtime_ms += idle;
+                }
+                start
+            }
+            Some(busy) => busy,
+            None       => start,
+        };
+        if end > from {
+            self.active_ms += end - from;
+        }
+        self.busy_until_ms = Some(self.busy_until_ms.map_or(end, |b| b.max(end)));
+    }
+
+    /// Active time as a percentage of `pull_elapsed_ms` minus forced downtime.
+    pub fn active_pct(&self, pull_elapsed_ms: u64) -> f32 {
+        let denom = pull_elapsed_ms.saturating_sub(self.downtime_ms);
+        if denom == 0 {
+            return 0.0;
+        }
+        (self.active_ms as f32 / denom as f32 * 100.0).min(100.0)
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Hard-cast completion (cancelled and clipped casts)
+// ---------------------------------------------------------------------------
+
+/// SPELL_CAST_FAILED messages that leave the cast in progress: a button
+/// pressed while casting fails with these, the cast itself goes on.
+const CAST_GOES_ON: &[&str] = &["in progress", "not yet recovered", "not ready"];
+
+/// A hard cast the coached player abandoned part way.
+#[derive(Debug, Clone)]
+pub struct CancelledCast {
+    pub spell_name: String,
+    /// Log timestamp (ms) the cast ended.
+    pub at_ms:      u64,
+    /// Cast time spent on it.
+    pub into_ms:    u64,
+}
+
+/// The coached player's SPELL_CAST_START events, each matched with the
+/// SUCCESS or FAILED that ends it.  A cast ends as cancelled when:
+///   - its FAILED arrives (moved, pressed escape, target lost) — unless an
+///     enemy kicked it (SPELL_INTERRUPT on the player), which is not the
+///     player's doing
+///   - a new CAST_START arrives first (clipped by the next cast)
+///
+/// Off-GCD instants cast during the hard cast leave it pending.
+#[derive(Debug, Default)]
+pub struct HardcastTracker {
+    /// Cast in progress: (spell_id, spell name, cast start ms).
+    pending:       Option<(u32, String, u64)>,
+    /// Hard casts started this pull.
+    pub started:   u32,
+    /// Cancelled this pull, oldest first.
+    pub cancelled: Vec<CancelledCast>,
+}
+
+impl HardcastTracker {
+    pub fn record_start(&mut self, spell_id: u32, spell_name: &str, now_ms: u64) {
+        self.cancel(now_ms);
+        self.started += 1;
+        self.pending = Some((spell_id, spell_name.to_owned(), now_ms));
+    }
+
+    pub fn record_success(&mut self, spell_id: u32) {
+        if self.pending.as_ref().is_some_and(|&(id, _, _)| id == spell_id) {
+            self.pending = None;
+        }
+    }
+
+    pub fn record_failed(&mut self, spell_id: u32, failed_type: &str, now_ms: u64) {
+        let failed_type = failed_type.to_ascii_lowercase();
+        if CAST_GOES_ON.iter().any(|m| failed_type.contains(m)) {
+            return;
+        }
+        if self.pending.as_ref().is_some_and(|&(id, _, _)| id == spell_id) {
+            self.cancel(now_ms);
+        }
+    }
+
+    /// An enemy interrupted the player: the cast ends without counting.
+    pub fn kicked(&mut self) {
+        self.pending = None;
+    }
+
+    fn cancel(&mut self, now_ms: u64) {
+        if let Some((_, spell_name, start_ms)) = self.pending.take() {
+            self.cancelled.push(CancelledCast {
+                spell_name,
+                at_ms:   now_ms,
+                into_ms: now_ms.saturating_sub(start_ms),
+            });
+        }
+    }
+
+    /// The cast cancelled by the event at `now_ms`, if any.
+    pub fn just_cancelled(&self, now_ms: u64) -> Option<&CancelledCast> {
+        self.cancelled.last().filter(|c| c.at_ms == now_ms)
+    }
+
+    /// Cast time lost to cancelled casts this pull.
+    pub fn lost_ms(&self) -> u64 {
+        self.cancelled.iter().map(|c| c.into_ms).sum()
+    }
+
+    /// A cast started before the pull may still complete in it.
+    pub fn start_pull(&mut self) {
+        self.started = 0;
+        self.cancelled.clear();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Failed casts (SPELL_CAST_FAILED reasons)
+// ---------------------------------------------------------------------------
+
+/// Reason of a SPELL_CAST_FAILED, for counting: movement, silence and crowd
+/// control as GapCause labels them, "interrupted", or the message itself.
+/// None for a button pressed during a cast or the GCD — too common to mean
+/// anything.
+pub fn failed_reason(failed_type: &str) -> Option<String> {
+    let t = failed_type.trim().to_ascii_lowercase();
+    if t.is_empty() || CAST_GOES_ON.iter().any(|m| t.contains(m)) {
+        return None;
+    }
+    Some(match GapCause::from_failed_type(&t) {
+        Some(cause)                     => cause.label().to_owned(),
+        None if t.contains("interrupt") => "interrupted".to_owned(),
+        None                            => t,
+    })
+}
+
+/// The coached player's failed casts this pull, by reason.
+#[derive(Debug, Default)]
+pub struct FailedCastTracker {
+    pub by_reason: HashMap<String, u32>,
+}
+
+impl FailedCastTracker {
+    /// Count a failed cast; returns its reason unless it was not counted.
+    pub fn record(&mut self, failed_type: &str) -> Option<String> {
+        let reason = failed_reason(failed_type)?;
+        *self.by_reason.entry(reason.clone()).or_default() += 1;
+        Some(reason)
+    }
+
+    /// Casts that failed because the player was moving.
+    pub fn movement(&self) -> u32 {
+        self.by_reason.get(GapCause::Movement.label()).copied().unwrap_or(0)
+    }
+
+    /// (reason, count), most frequent first.
+    pub fn breakdown(&self) -> Vec<(String, u32)> {
+        let mut reasons: Vec<(String, u32)> = self.by_reason.iter().map(|(r, &n)| (r.clone(), n)).collect();
+        reasons.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        reasons
+    }
+
+    pub fn reset(&mut self) {
+        self.by_reason.clear();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Group composition tracker (solo / follower content detection)
+// ---------------------------------------------------------------------------
+
+/// COMBATLOG_OBJECT_* unit flag bits used for group detection.
+const FLAG_AFFILIATION_OUTSIDER: u32 = 0x0000_0008;
+const FLAG_REACTION_FRIENDLY:    u32 = 0x0000_0010;
+const FLAG_TYPE_PLAYER:          u32 = 0x0000_0400;
+const FLAG_TYPE_NPC:             u32 = 0x0000_0800;
+/// Allies not seen casting for this long no longer count as present.
+const GROUP_SEEN_WINDOW_MS: u64 = 60_000;
+
+/// True for a friendly player in the group (coached player included).
+pub fn is_group_player(flags: u32) -> bool {
+    flags & FLAG_TYPE_PLAYER != 0
+        && flags & FLAG_REACTION_FRIENDLY != 0
+        && flags & FLAG_AFFILIATION_OUTSIDER == 0
+}
+
+/// Who is casting on the player's side: other players vs. NPC allies.
+///
+/// Follower dungeons and delves put the player in a "party" of one plus
+/// friendly NPCs (followers, Brann), which the group-oriented rules misread.
+/// Persists across pulls; entries age out after `GROUP_SEEN_WINDOW_MS`.
+#[derive(Debug, Default)]
+pub struct GroupTracker {
+    /// Friendly in-group player GUID -> last cast ms (includes the coached player).
+    players:    HashMap<String, u64>,
+    /// Friendly in-group NPC GUID -> last cast ms (pets/guardians excluded).
+    npc_allies: HashMap<String, u64>,
+}
+
+impl GroupTracker {
+    /// Tracked GUIDs (players + NPC allies), for self-monitoring.
+    pub fn len(&self) -> usize {
+        self.players.len() + self.npc_allies.len()
+    }
+
+    pub fn record_cast(&mut self, guid: &str, flags: u32, timestamp_ms: u64) {
+        if flags & FLAG_REACTION_FRIENDLY == 0 || flags & FLAG_AFFILIATION_OUTSIDER != 0 {
+            return;
+        }
+        if flags & FLAG_TYPE_PLAYER != 0 {
+            self.players.insert(guid.to_owned(), timestamp_ms);
+        } else if flags & FLAG_TYPE_NPC != 0 {
+            self.npc_allies.insert(guid.to_owned(), timestamp_ms);
+        }
+    }
+
+    /// Whether a group player was seen casting within GROUP_SEEN_WINDOW_MS.
+    pub fn is_present(&self, guid: &str, now_ms: u64) -> bool {
+        self.players.get(guid).is_some_and(|&t| now_ms.saturating_sub(t) <= GROUP_SEEN_WINDOW_MS)
+    }
+
+    /// True when the player is alone with NPC allies (follower dungeon / delve).
+    pub fn is_solo_content(&self, now_ms: u64) -> bool {
+        let recent = |m: &HashMap<String, u64>| {
+            m.values().filter(|&&t| now_ms.saturating_sub(t) <= GROUP_SEEN_WINDOW_MS).count()
+        };
+        recent(&self.players) <= 1 && recent(&self.npc_allies) >= 1
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Party damage-taken tracker (squad mode)
+// ---------------------------------------------------------------------------
+
+/// One group member's damage taken this pull.
+#[derive(Debug, Clone, Default)]
+pub struct MemberDamage {
+    pub name:           String,
+    pub total:          u64,
+    pub avoidable:      u64,
+    pub avoidable_hits: u32,
+    /// Melee swings taken — tanking inference (`tanks`).
+    pub melee:          u64,
+}
+
+/// Damage taken by every friendly in-group player (coached player included),
+/// split into total and avoidable.  Avoidable means the active encounter's
+/// and dungeon's `avoidable_spell_ids`, so content without data files only
+/// reports totals.
+#[derive(Debug, Default)]
+pub struct PartyDamageTracker {
+    /// Set by the engine when an encounter or dungeon loads, kept across
+    /// pull resets.
+    avoidable:   HashSet<u32>,
+    /// dest GUID -> damage taken this pull.
+    pub members: HashMap<String, MemberDamage>,
+}
+
+impl PartyDamageTracker {
+    pub fn set_avoidable(&mut self, spell_ids: &[u32]) {
+        self.avoidable = spell_ids.iter().copied().collect();
+    }
+
+    /// `spell_id` is None for melee swings.  `dest_name` may be empty
+    /// (SWING_DAMAGE); a later spell hit fills it in.
+    pub fn record(&mut self, dest_guid: &str, dest_name: &str, dest_flags: u32, spell_id: Option<u32>, amount: u64) {
+        if !is_group_player(dest_flags) {
+            return;
+        }
+        let m = self.members.entry(dest_guid.to_owned()).or_default();
+        if m.name.is_empty() {
+            m.name = dest_name.to_owned();
+        }
+        m.total += amount;
+        if spell_id.is_none() {
+            m.melee += amount;
+        }
+        if spell_id.is_some_and(|id| self.avoidable.contains(&id)) {
+            m.avoidable      += amount;
+            m.avoidable_hits += 1;
+        }
+    }
+
+    /// Avoidable hits on the whole group this pull.
+    pub fn avoidable_hits(&self) -> u32 {
+        self.members.values().map(|m| m.avoidable_hits).sum()
+    }
+
+    /// (GUID, damage) sorted by avoidable damage, then total, highest first.
+    pub fn leaderboard(&self) -> Vec<(&str, &MemberDamage)> {
+        let mut rows: Vec<(&str, &MemberDamage)> = self.members
+            .iter()
+            .map(|(guid, m)| (guid.as_str(), m))
+            .collect();
+        rows.sort_by(|a, b| b.1.avoidable.cmp(&a.1.avoidable).then(b.1.total.cmp(&a.1.total)));
+        rows
+    }
+
+    /// GUIDs of the members tanking this pull: the log names no roles, so
+    /// it is whoever takes melee swings — everyone within TANK_MELEE_SHARE
+    /// of the most-hit member (two raid tanks swap).
+    pub fn tanks(&self) -> Vec<&str> {
+        let top = self.members.values().map(|m| m.melee).max().unwrap_or(0);
+        if top == 0 {
+            return Vec::new();
+        }
+        self.members
+            .iter()
+            .filter(|(_, m)| m.melee as f32 >= top as f32 * TANK_MELEE_SHARE)
+            .map(|(guid, _)| guid.as_str())
+            .collect()
     }
 
-    pub fn push(&mut self, event: LogEvent, now_ms: u64) {
-        self.events.push(WindowedEvent { timestamp_ms: now_ms, event });
-        let cutoff = now_ms.saturating_sub(self.window_ms);
-        self.events.retain(|e| e.timestamp_ms >= cutoff);
+    pub fn reset(&mut self) {
+        self.members.clear();
     }
 }
 
 // ---------------------------------------------------------------------------
-// Interrupt tracker (persists interruptible spell knowledge across pulls)
+// Healing tracker (the coached player's effective healing per target)
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Default)]
-pub struct InterruptTracker {
-    /// Spell IDs the coached player has successfully interrupted before.
-    /// Populated from SPELL_INTERRUPT events; persists across pulls (learned knowledge).
-    pub interruptible_spells: HashSet<u32>,
+/// Members taking at least this share of the top member's melee damage
+/// count as tanks.
+const TANK_MELEE_SHARE: f32 = 0.5;
+
+/// Healing done by the coached player to one target this pull.
+#[derive(Debug, Clone, Default)]
+pub struct HealedTarget {
+    pub name:        String,
+    /// amount − overhealing.
+    pub effective:   u64,
+    pub overhealing: u64,
 }
 
-impl InterruptTracker {
-    pub fn record_interrupt(&mut self, interrupted_spell_id: u32) {
-        self.interruptible_spells.insert(interrupted_spell_id);
-    }
+/// Effective healing split by recipient (amounts, not %).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HealingSplit {
+    pub tank:        u64,
+    pub party:       u64,
+    pub self_heal:   u64,
+    pub overhealing: u64,
+}
 
-    pub fn is_interruptible(&self, spell_id: u32) -> bool {
-        self.interruptible_spells.contains(&spell_id)
+impl HealingSplit {
+    pub fn effective(&self) -> u64 {
+        self.tank + self.party + self.self_heal
     }
 
-    /// Called on pull start — keeps learned spell IDs (knowledge persists).
-    pub fn reset_per_pull(&mut self) {
-        // intentionally no-op: interruptible_spells carries over between pulls
+    /// Tank share of effective healing, in %; None without healing.
+    pub fn tank_pct(&self) -> Option<f32> {
+        let total = self.effective();
+        (total > 0).then(|| self.tank as f32 / total as f32 * 100.0)
     }
 }
 
-// ---------------------------------------------------------------------------
-// Damage taken tracker (rolling window for defensive timing rule)
-// ---------------------------------------------------------------------------
-
+/// The coached player's healing per friendly target this pull.
 #[derive(Debug, Default)]
-pub struct DamageTakenTracker {
-    /// (timestamp_ms, amount) pairs — appended on every hit, cleared on pull start.
-    pub events: Vec<(u64, u64)>,
+pub struct HealingTracker {
+    /// dest GUID -> healing received from the player.
+    pub targets: HashMap<String, HealedTarget>,
 }
 
-impl DamageTakenTracker {
-    pub fn record(&mut self, timestamp_ms: u64, amount: u64) {
-        self.events.push((timestamp_ms, amount));
+impl HealingTracker {
+    pub fn record(&mut self, dest_guid: &str, dest_name: &str, amount: u64, overhealing: u64) {
+        let t = self.targets.entry(dest_guid.to_owned()).or_default();
+        if t.name.is_empty() {
+            t.name = dest_name.to_owned();
+        }
+        t.effective   += amount.saturating_sub(overhealing);
+        t.overhealing += overhealing.min(amount);
     }
 
-    /// Sum of damage taken in the last `window_ms` milliseconds.
-    /// Read-only — pruning deferred to pull reset (bounded by pull duration).
-    pub fn recent_damage(&self, now_ms: u64, window_ms: u64) -> u64 {
-        let cutoff = now_ms.saturating_sub(window_ms);
-        self.events.iter()
-            .filter(|(ts, _)| *ts >= cutoff)
-            .map(|(_, amt)| *amt)
-            .sum()
+    /// Split by recipient: the player, the `tanks` (PartyDamageTracker::tanks),
+    /// everyone else.  The player counts as self even when tanking.
+    pub fn split(&self, player_guid: &str, tanks: &[&str]) -> HealingSplit {
+        let mut split = HealingSplit::default();
+        for (guid, t) in &self.targets {
+            split.overhealing += t.overhealing;
+            if guid == player_guid {
+                split.self_heal += t.effective;
+            } else if tanks.contains(&guid.as_str()) {
+                split.tank += t.effective;
+            } else {
+                split.party += t.effective;
+            }
+        }
+        split
     }
 
     pub fn reset(&mut self) {
-        self.events.clear();
+        self.targets.clear();
     }
 }
 
 // ---------------------------------------------------------------------------
-// Avoidable damage tracker
+// Outgoing throughput tracker (DPS / HPS on the stat widgets)
 // ---------------------------------------------------------------------------
 
+/// Span of the rolling DPS/HPS figures.
+pub const THROUGHPUT_WINDOW_MS: u64 = 10_000;
+
+/// The coached player's damage and effective healing this pull: totals for
+/// the pull average plus the hits of the last THROUGHPUT_WINDOW_MS.  The
+/// averages stay readable after the pull ends, until the next one starts.
 #[derive(Debug, Default)]
-pub struct AvoidableTracker {
-    /// spell_id -> hit count this pull
-    pub hit_counts:     HashMap<u32, u32>,
-    /// spell_id -> timestamps of each hit
-    pub hit_timestamps: HashMap<u32, Vec<u64>>,
+pub struct OutgoingTracker {
+    start_ms:          u64,
+    end_ms:            Option<u64>,
+    pub damage_total:  u64,
+    pub healing_total: u64,
+    /// (timestamp_ms, amount) inside the rolling window.
+    damage:            VecDeque<(u64, u64)>,
+    healing:           VecDeque<(u64, u64)>,
 }
 
-impl AvoidableTracker {
-    pub fn record_hit(&mut self, spell_id: u32, timestamp_ms: u64) {
-        *self.hit_counts.entry(spell_id).or_insert(0) += 1;
-        self.hit_timestamps.entry(spell_id).or_default().push(timestamp_ms);
+impl OutgoingTracker {
+    pub fn start_pull(&mut self, start_ms: u64) {
+        *self = Self { start_ms, ..Self::default() };
     }
 
-    pub fn hit_count(&self, spell_id: u32) -> u32 {
-        self.hit_counts.get(&spell_id).copied().unwrap_or(0)
+    pub fn end_pull(&mut self, end_ms: u64) {
+        self.end_ms = Some(end_ms);
     }
 
-    pub fn total_hits(&self) -> u32 {
-        self.hit_counts.values().sum()
+    pub fn record_damage(&mut self, now_ms: u64, amount: u64) {
+        self.damage_total += amount;
+        Self::push(&mut self.damage, now_ms, amount);
     }
 
-    pub fn reset(&mut self) {
-        self.hit_counts.clear();
-        self.hit_timestamps.clear();
+    /// Healing without overhealing.
+    pub fn record_healing(&mut self, now_ms: u64, effective: u64) {
+        self.healing_total += effective;
+        Self::push(&mut self.healing, now_ms, effective);
     }
-}
 
-// ---------------------------------------------------------------------------
-// Cooldown tracker (inferred from observed SPELL_CAST_SUCCESS)
-// ---------------------------------------------------------------------------
+    fn push(window: &mut VecDeque<(u64, u64)>, now_ms: u64, amount: u64) {
+        window.push_back((now_ms, amount));
+        let cutoff = now_ms.saturating_sub(THROUGHPUT_WINDOW_MS);
+        while window.front().is_some_and(|&(ts, _)| ts < cutoff) {
+            window.pop_front();
+        }
+    }
 
-#[derive(Debug, Default)]
-pub struct CooldownTracker {
-    /// spell_id -> last observed use timestamp
-    pub last_used: HashMap<u32, u64>,
-}
+    /// Per second over the rolling window — over the time since pull start
+    /// while the pull is younger than the window.
+    fn rolling(&self, window: &VecDeque<(u64, u64)>, now_ms: u64) -> f32 {
+        let cutoff = now_ms.saturating_sub(THROUGHPUT_WINDOW_MS);
+        let sum: u64 = window.iter().filter(|&&(ts, _)| ts >= cutoff).map(|&(_, a)| a).sum();
+        let span = THROUGHPUT_WINDOW_MS.min(now_ms.saturating_sub(self.start_ms)).max(1_000);
+        sum as f32 * 1_000.0 / span as f32
+    }
 
-impl CooldownTracker {
-    pub fn record_cast(&mut self, spell_id: u32, timestamp_ms: u64) {
-        self.last_used.insert(spell_id, timestamp_ms);
+    fn average(&self, total: u64, now_ms: u64) -> f32 {
+        let elapsed = self.end_ms.unwrap_or(now_ms).saturating_sub(self.start_ms).max(1_000);
+        total as f32 * 1_000.0 / elapsed as f32
     }
 
-    /// How long ago was this spell last cast? None = never seen this pull.
-    #[allow(dead_code)] // used by cooldown-drift rule in future phases
-    pub fn elapsed_since_last(&self, spell_id: u32, now_ms: u64) -> Option<u64> {
-        self.last_used.get(&spell_id).map(|&t| now_ms.saturating_sub(t))
+    pub fn rolling_dps(&self, now_ms: u64) -> f32 {
+        self.rolling(&self.damage, now_ms)
     }
 
-    pub fn last_used_ms(&self, spell_id: u32) -> Option<u64> {
-        self.last_used.get(&spell_id).copied()
+    pub fn rolling_hps(&self, now_ms: u64) -> f32 {
+        self.rolling(&self.healing, now_ms)
     }
 
-    pub fn reset(&mut self) {
-        self.last_used.clear();
+    pub fn pull_dps(&self, now_ms: u64) -> f32 {
+        self.average(self.damage_total, now_ms)
+    }
+
+    pub fn pull_hps(&self, now_ms: u64) -> f32 {
+        self.average(self.healing_total, now_ms)
     }
 }
 
 // ---------------------------------------------------------------------------
-// GCD gap tracker
+// Group member trackers (group coaching mode)
 // ---------------------------------------------------------------------------
 
+/// Per-pull trackers of one other group player, so the coached player's rules
+/// can be applied to everyone (rules/group_coaching.rs).
 #[derive(Debug, Default)]
-pub struct GcdTracker {
-    pub last_cast_ms:    Option<u64>,
-    /// Gap in ms between the last two casts
-    pub current_gap_ms:  u64,
-}
-
-impl GcdTracker {
-    pub fn record_cast(&mut self, timestamp_ms: u64) {
-        if let Some(last) = self.last_cast_ms {
-            self.current_gap_ms = timestamp_ms.saturating_sub(last);
-        }
-        self.last_cast_ms = Some(timestamp_ms);
-    }
-
-    pub fn reset(&mut self) {
-        self.last_cast_ms   = None;
-        self.current_gap_ms = 0;
-    }
+pub struct MemberTracker {
+    pub name:      String,
+    pub avoidable: AvoidableTracker,
+    pub death:     DeathTracker,
 }
 
 // ---------------------------------------------------------------------------
@@ -206,22 +2106,93 @@ pub struct CombatState {
     pub pull_history:    Vec<Pull>,
     pub event_window:    EventWindow,
     pub avoidable:       AvoidableTracker,
+    /// Avoided vs hit per avoidable mechanic of the active encounter.
+    pub dodge:           DodgeTracker,
     pub cooldowns:       CooldownTracker,
     pub gcd:             GcdTracker,
     pub in_combat:       bool,
     pub player_guid:     Option<String>,
     /// Number of successful interrupts cast by the coached player this pull.
     pub interrupt_count: u32,
+    /// Resource generated and overcapped by the coached player this pull.
+    pub resources:       ResourceTracker,
+    /// Fires per advice key this pull (repeat-offense escalation).
+    pub advice_repeats:  HashMap<String, u32>,
+    /// Group coaching mode (`AppConfig.group_coaching`, set by the engine):
+    /// every other player in the group gets `members` trackers.
+    pub group_coaching:  bool,
+    /// `AppConfig.avoidable_friendly_fire` (set by the engine): friendly
+    /// damage counts toward the avoidable trackers and rules.
+    pub friendly_avoidable: bool,
+    /// Other group players' trackers by GUID this pull (group coaching only).
+    pub members:         HashMap<String, MemberTracker>,
     /// Active encounter name from ENCOUNTER_START/END (None between pulls).
     pub encounter_name:  Option<String>,
+    /// Boss NPC IDs of the active encounter (TOML `boss_npc_ids`), set by the
+    /// engine on ENCOUNTER_START — see `unit_kind`.
+    pub boss_npc_ids:    Vec<u32>,
+    /// Crowd-control auras of the active encounter (TOML `cc_spell_ids`) —
+    /// GCD gaps under them are attributed to CC.
+    pub cc_spell_ids:    Vec<u32>,
+    /// Friendly players/NPCs seen recently — drives the solo rule profile.
+    pub group:           GroupTracker,
+    /// Log timestamp of ENCOUNTER_START — origin of the encounter timeline.
+    pub encounter_start_ms: Option<u64>,
+    /// Classes in the group by this encounter's COMBATANT_INFO lines
+    /// (raid_buffs rule).
+    pub group_classes:   HashSet<String>,
     /// Tracks known interruptible spell IDs (learned from past SpellInterrupted events).
     pub interrupts:      InterruptTracker,
+    /// Enemy casts in progress this pull (kick_alert).
+    pub enemy_casts:     EnemyCastTracker,
     /// Rolling per-pull damage taken (used by defensive_timing rule).
     pub damage_taken:    DamageTakenTracker,
+    /// Per-target damage dealt this pull (boss-damage share).
+    pub damage_done:     DamageDoneTracker,
+    /// Damage taken by each group member this pull (squad mode leaderboard).
+    pub party_damage:    PartyDamageTracker,
+    /// The coached player's healing per target this pull.
+    pub healing:         HealingTracker,
+    /// The coached player's damage and healing per second (stat widgets).
+    pub outgoing:        OutgoingTracker,
+    /// Current target and kill-priority enemies this pull (tunnel vision).
+    pub targets:         TargetTracker,
+    /// Player damage per second and major cooldown windows this pull.
+    pub burst:           BurstTracker,
+    /// Melee uptime on the boss this pull (melee players only).
+    pub melee:           MeleeTracker,
+    /// Buffs on the coached player (not reset per pull).
+    pub auras:           AuraTracker,
+    /// Bloodlust/Heroism on the coached player.
+    pub lust:            LustTracker,
+    /// Per-pull uptime of auras cast by or on the coached player.
+    pub buffs:           BuffTracker,
+    /// Coached player deaths since the app started (durability proxy).
+    pub player_deaths:   u32,
+    /// First death of the coached player this pull and what caused it.
+    pub death:           DeathTracker,
+    /// Deaths of everyone in the group and battle resurrections this pull.
+    pub group_deaths:    GroupDeathTracker,
+    /// Potion and healthstone uses (cooldowns persist across pulls).
+    pub consumables:     ConsumableTracker,
+    /// Casts before ENCOUNTER_START (kept across pull boundaries).
+    pub prepull:         PrepullTracker,
     /// Log timestamp (ms) of the last player cast, DoT tick, or auto-attack.
     /// Used for the open-world combat timeout: end the pull if the player
     /// has had no activity for 10+ seconds and there is no ENCOUNTER_END.
     pub last_player_cast_ms: Option<u64>,
+    /// Cast-time coverage for the active-time (ABC) metric.
+    pub activity:        ActivityTracker,
+    /// Hard casts started vs cancelled this pull.
+    pub hardcasts:       HardcastTracker,
+    /// Failed casts by reason this pull.
+    pub failed_casts:    FailedCastTracker,
+    /// Current arena match from ARENA_MATCH_START (None outside arenas).
+    pub arena:           Option<ArenaMatch>,
+    /// Mythic+ key in progress (None outside keystone runs).
+    pub keystone:        Option<Keystone>,
+    /// Dummy detection and rotation data for practice scoring.
+    pub practice:        PracticeTracker,
 }
 
 impl CombatState {
@@ -231,15 +2202,48 @@ impl CombatState {
             pull_history:    Vec::new(),
             event_window:    EventWindow::new(30_000),
             avoidable:       AvoidableTracker::default(),
+            dodge:           DodgeTracker::default(),
             cooldowns:       CooldownTracker::default(),
             gcd:             GcdTracker::default(),
             in_combat:       false,
             player_guid:     None,
             interrupt_count: 0,
+            resources:       ResourceTracker::default(),
+            advice_repeats:  HashMap::new(),
+            group_coaching:  false,
+            friendly_avoidable: false,
+            members:         HashMap::new(),
             encounter_name:  None,
+            boss_npc_ids:    Vec::new(),
+            cc_spell_ids:    Vec::new(),
+            group:           GroupTracker::default(),
+            encounter_start_ms: None,
+            group_classes:   HashSet::new(),
             interrupts:      InterruptTracker::default(),
+            enemy_casts:     EnemyCastTracker::default(),
             damage_taken:    DamageTakenTracker::default(),
+            damage_done:     DamageDoneTracker::default(),
+            party_damage:    PartyDamageTracker::default(),
+            healing:         HealingTracker::default(),
+            outgoing:        OutgoingTracker::default(),
+            targets:         TargetTracker::default(),
+            burst:           BurstTracker::default(),
+            melee:           MeleeTracker::default(),
+            auras:           AuraTracker::default(),
+            lust:            LustTracker::default(),
+            buffs:           BuffTracker::default(),
+            player_deaths:   0,
+            death:           DeathTracker::default(),
+            group_deaths:    GroupDeathTracker::default(),
+            consumables:     ConsumableTracker::default(),
+            prepull:         PrepullTracker::default(),
             last_player_cast_ms:   None,
+            activity:        ActivityTracker::default(),
+            hardcasts:       HardcastTracker::default(),
+            failed_casts:    FailedCastTracker::default(),
+            arena:           None,
+            keystone:        None,
+            practice:        PracticeTracker::default(),
         }
     }
 
@@ -252,12 +2256,34 @@ impl CombatState {
             outcome:     None,
         });
         self.avoidable.reset();
+        self.dodge.reset();
         self.cooldowns.reset();
         self.gcd.reset();
         self.interrupt_count = 0;
+        self.resources.reset();
+        self.advice_repeats.clear();
+        self.members.clear();
         self.damage_taken.reset();
+        self.damage_done.reset();
+        self.party_damage.reset();
+        self.healing.reset();
+        self.outgoing.start_pull(timestamp_ms);
+        self.targets.reset();
+        self.burst.start_pull(timestamp_ms);
+        self.melee.reset();
+        self.death.reset();
+        self.group_deaths.reset();
+        self.consumables.start_pull();
+        self.prepull.start_pull();
         self.interrupts.reset_per_pull();
+        self.enemy_casts.reset();
         self.last_player_cast_ms = None;
+        self.activity.reset();
+        self.hardcasts.start_pull();
+        self.failed_casts.reset();
+        self.practice.reset();
+        self.buffs.start_pull(timestamp_ms);
+        self.lust.start_pull();
         self.in_combat = true;
         tracing::info!("Pull {} started at {}ms", n, timestamp_ms);
     }
@@ -268,6 +2294,7 @@ impl CombatState {
             pull.outcome = Some(outcome.clone());
             self.pull_history.push(pull);
         }
+        self.outgoing.end_pull(timestamp_ms);
         self.in_combat = false;
         tracing::info!("Pull ended: {:?}", outcome);
     }
@@ -279,6 +2306,35 @@ impl CombatState {
             .map(|p| now_ms.saturating_sub(p.start_ms))
             .unwrap_or(0)
     }
+
+    /// Whether a hit from `source` may count as avoidable damage.
+    pub fn avoidable_source(&self, source: DamageSource) -> bool {
+        !source.is_friendly() || self.friendly_avoidable
+    }
+
+    /// Trackers of another group player (`flags` of the unit), created on
+    /// first sight.  None outside group coaching mode and for the coached
+    /// player, whose trackers are the top-level ones.
+    pub fn member(&mut self, guid: &str, name: &str, flags: u32) -> Option<&mut MemberTracker> {
+        if !self.group_coaching || !is_group_player(flags) || Some(guid) == self.player_guid.as_deref() {
+            return None;
+        }
+        let member = self.members.entry(guid.to_owned()).or_default();
+        if member.name.is_empty() {
+            member.name = name.to_owned();
+        }
+        Some(member)
+    }
+
+    /// Kind of a unit, with the active encounter's boss NPCs reported as `Boss`.
+    pub fn unit_kind(&self, guid: &str) -> UnitKind {
+        guid::classify(guid, &self.boss_npc_ids)
+    }
+
+    /// Active-time percentage for the current pull (0.0 when not in a pull).
+    pub fn active_time_pct(&self, now_ms: u64) -> f32 {
+        self.activity.active_pct(self.pull_elapsed_ms(now_ms))
+    }
 }
 
 #[cfg(test)]
@@ -300,6 +2356,91 @@ mod tests {
         assert_eq!(state.pull_history[0].outcome, Some(PullOutcome::Wipe));
     }
 
+    #[test]
+    fn members_tracked_only_in_group_coaching() {
+        let mut state = CombatState::new();
+        state.player_guid = Some("Player-1-A".to_owned());
+        assert!(state.member("Player-1-B", "Thrall", 0x512).is_none(), "mode off");
+
+        state.group_coaching = true;
+        assert!(state.member("Player-1-A", "Me", 0x511).is_none(), "coached player");
+        assert!(state.member("Creature-0-1-1-1-100-A", "Boss", 0xa48).is_none(), "not a player");
+        state.member("Player-1-B", "Thrall", 0x512).unwrap().avoidable.record_hit(1001, 5_000);
+        assert_eq!(state.members["Player-1-B"].name, "Thrall");
+        assert_eq!(state.members["Player-1-B"].avoidable.hit_count(1001), 1);
+
+        state.start_pull(10_000);
+        assert!(state.members.is_empty());
+    }
+
+    #[test]
+    fn buff_uptime_per_pull() {
+        let mut buffs = BuffTracker::default();
+        buffs.apply("Player-1", 1000, 500);          // pre-pull buff
+        buffs.apply("Creature-1", 589, 9_000);       // stale DoT, dropped at pull start
+        buffs.remove("Creature-1", 589, 9_500);
+        buffs.start_pull(10_000);
+        assert!(!buffs.auras.contains_key(&("Creature-1".to_owned(), 589)));
+
+        buffs.apply("Creature-2", 589, 12_000);
+        buffs.refresh("Creature-2", 589, 16_000);
+        buffs.remove("Creature-2", 589, 17_000);
+        buffs.remove("Player-1", 1000, 15_000);
+
+        // 10 s into the pull: DoT up 12–17 s, buff up 10–15 s.
+        assert_eq!(buffs.uptime_pct(589, 10_000), 50.0);
+        assert_eq!(buffs.uptime_pct(1000, 10_000), 50.0);
+        buffs.apply("Creature-3", 589, 18_000);
+        assert_eq!(buffs.unit_uptime_pct("Creature-3", 589, 10_000), 20.0);
+        assert!(!buffs.is_up("Creature-2", 589));
+
+        // A refresh without a seen apply counts from the refresh.
+        buffs.refresh("Player-1", 2000, 18_000);
+        assert!(buffs.is_up("Player-1", 2000));
+        assert_eq!(buffs.covered_ms("Player-1", 2000, 20_000), 2_000);
+    }
+
+    #[test]
+    fn gcd_gap_cause() {
+        let mut gcd = GcdTracker::default();
+        gcd.record_cast(1_000);
+        gcd.record_failed("Not yet recovered");
+        gcd.record_cast(4_000);
+        assert_eq!(gcd.gap_cause, GapCause::Idle);
+
+        gcd.record_failed("MOVING");
+        gcd.record_cast(8_000);
+        assert_eq!((gcd.current_gap_ms, gcd.gap_cause), (4_000, GapCause::Movement));
+        assert_eq!(gcd.longest_gap_ms, 4_000);
+
+        // CC outranks movement and carries over while the aura is up.
+        gcd.record_failed("Can't do that while moving");
+        gcd.cc_applied(12345);
+        gcd.record_cast(12_000);
+        assert_eq!(gcd.gap_cause, GapCause::CrowdControl);
+        gcd.record_cast(13_000);
+        assert_eq!(gcd.gap_cause, GapCause::CrowdControl);
+        gcd.cc_removed(12345);
+        gcd.record_cast(14_000);
+        gcd.record_cast(15_000);
+        assert_eq!(gcd.gap_cause, GapCause::Idle);
+
+        assert_eq!(GapCause::from_failed_type("SILENCED"), Some(GapCause::Silenced));
+        assert_eq!(GapCause::from_failed_type("Can't do that while stunned"), Some(GapCause::CrowdControl));
+    }
+
+    #[test]
+    fn cooldown_drift_counts_time_spent_ready() {
+        let mut cds = CooldownTracker::default();
+        // Pull at 10s; first use 15s in sat ready since the pull started.
+        assert_eq!(cds.drift_ms(31884, Some(120_000), 10_000, 25_000), 15_000);
+        cds.record_cast(31884, 25_000);
+        // Back up at 145s, used at 150s: 5s late.
+        assert_eq!(cds.drift_ms(31884, Some(120_000), 10_000, 150_000), 5_000);
+        // Unknown cooldown: a repeat use has no measurable drift.
+        assert_eq!(cds.drift_ms(31884, None, 10_000, 150_000), 0);
+    }
+
     #[test]
     fn avoidable_tracker() {
         let mut tracker = AvoidableTracker::default();
@@ -308,6 +2449,174 @@ mod tests {
         assert_eq!(tracker.hit_count(12345), 2);
         tracker.reset();
         assert_eq!(tracker.hit_count(12345), 0);
+        assert!(tracker.hit_this_session(12345));
+        assert!(!tracker.hit_this_session(999));
+    }
+
+    #[test]
+    fn boss_share_uses_npc_ids_or_most_damaged_enemy() {
+        const BOSS: &str = "Creature-0-1469-2549-12530-209090-00002C5C8D";
+        const ADD:  &str = "Creature-0-1469-2549-12530-214000-00002C5C8E";
+        let mut done = DamageDoneTracker::default();
+        done.record(BOSS, "Ulgrax", 600, true);
+        done.record(BOSS, "Ulgrax", 5_000, false);
+        done.record(ADD,  "Hungry Grub", 400, true);
+        done.record("Player-1234-ABCDEF", "Stonebraid", 999, true);
+        assert!(done.boss_share().is_none(), "trash pulls report no share");
+
+        done.set_encounter(&[]);
+        let share = done.boss_share().expect("share");
+        assert_eq!(share.boss_name, "Ulgrax");
+        assert_eq!((share.boss_damage, share.total_damage), (600, 1_000));
+        assert!((share.pct() - 60.0).abs() < 0.01);
+
+        done.set_encounter(&[214000]);
+        assert_eq!(done.boss_share().unwrap().boss_damage, 400);
+    }
+
+    #[test]
+    fn target_tracker_records_switch_latency() {
+        const BOSS:  &str = "Creature-0-1469-2549-12530-209090-00002C5C8D";
+        const ADD:   &str = "Creature-0-1469-2549-12530-214000-00002C5C8E";
+        const SKULL: &str = "Creature-0-1469-2549-12530-99999-00002C5C8F";
+        let mut targets = TargetTracker::default();
+        targets.set_priority_npcs(&[214000]);
+        targets.player_hit(BOSS, "Ulgrax", 1_000);
+        targets.see_enemy(BOSS, "Ulgrax", 0, 1_000);
+        assert!(targets.priority.is_empty());
+
+        targets.see_enemy(ADD, "Hungry Grub", 0, 5_000);
+        targets.see_enemy(SKULL, "Marked", RAID_FLAG_SKULL, 5_000);
+        assert_eq!(targets.neglected(6_000).len(), 2);
+        assert_eq!(targets.neglected(16_000).len(), 0, "not seen for 11s");
+
+        targets.player_hit(ADD, "Hungry Grub", 7_500);
+        targets.player_hit(BOSS, "Ulgrax", 9_000);
+        targets.player_hit(ADD, "Hungry Grub", 9_500);   // re-engage: no new sample
+        assert_eq!(targets.switch_latencies, vec![2_500]);
+        assert_eq!(targets.current_since, 9_500);
+
+        targets.unit_died(ADD);
+        assert!(targets.current.is_none());
+        assert_eq!(targets.neglected(10_000).len(), 1);
+
+        // A new pull of the same encounter still knows its priority adds.
+        targets.reset();
+        targets.see_enemy(ADD, "Hungry Grub", 0, 20_000);
+        assert_eq!(targets.neglected(20_000).len(), 1);
+    }
+
+    #[test]
+    fn burst_tracker_scores_damage_inside_cd_windows() {
+        let mut burst = BurstTracker::default();
+        burst.start_pull(100_000);
+        assert!(burst.summary().is_none());
+
+        // 40s pull: 1k/s, 5k/s for the 20s after a cooldown at 10s.
+        burst.cooldown_used(110_000);
+        burst.cooldown_used(115_000); // overlaps → merged
+        for s in 0..40u64 {
+            let amount = if (10..25).contains(&s) { 5_000 } else { 1_000 };
+            burst.record_damage(100_000 + s * 1_000 + 200, amount);
+        }
+
+        let summary = burst.summary().unwrap();
+        assert_eq!(summary.windows, 1);
+        assert_eq!(summary.peak_dps_short, 5_000);
+        assert!(summary.peak_in_window);
+        // 15 × 5k + 10 × 1k inside (window 110s..135s) of 100k total.
+        assert_eq!(summary.in_window_pct, 85.0);
+        assert_eq!(summary.peak_dps_long, (15 * 5_000 + 15 * 1_000) / 30);
+    }
+
+    #[test]
+    fn melee_tracker_samples_uptime_while_boss_is_hit() {
+        let mut melee = MeleeTracker::default();
+        // Boss hit every second for 20s; the player leaves melee from 10s to 16s.
+        for s in 0..=20u64 {
+            let now = s * 1_000;
+            if s <= 10 || s >= 16 {
+                melee.record_swing();
+                melee.record_contact(now);
+            }
+            melee.record_boss_hit(now);
+            if s == 15 {
+                assert_eq!(melee.out_of_range_for(now), Some(5_000));
+            }
+        }
+        // In range until 4s after the last swing: only the 15s sample is out.
+        assert_eq!((melee.in_range_ms, melee.out_range_ms), (19_000, 1_000));
+        assert_eq!(melee.uptime_pct(), Some(95.0));
+        assert_eq!(melee.longest_gap_ms, 6_000);
+        assert_eq!(melee.out_of_range_for(20_000), None);
+
+        // A boss nobody can hit (intermission) is not time out of range.
+        melee.record_boss_hit(60_000);
+        assert_eq!(melee.out_range_ms, 1_000 + MELEE_GAP_MS);
+        assert_eq!(melee.out_of_range_for(90_000), None);
+
+        // Never swung: a caster gets no uptime.
+        let mut caster = MeleeTracker::default();
+        caster.record_contact(0);
+        caster.record_boss_hit(0);
+        caster.record_boss_hit(1_000);
+        assert_eq!(caster.uptime_pct(), None);
+    }
+
+    #[test]
+    fn resource_tracker_sums_waste_per_power_type() {
+        let mut res = ResourceTracker::default();
+        res.record(1, 20, 0, 100);
+        res.record(1, 20, 5, 100);
+        res.record(0, 2_000, 2_000, 250_000);   // mana never counts
+        let rage = res.flow(1).copied().unwrap_or_default();
+        assert_eq!((rage.gained, rage.wasted, rage.max), (40, 5, 100));
+        assert!((rage.waste_pct() - 12.5).abs() < 0.01);
+        assert!(res.flow(0).is_none());
+    }
+
+    const ME: Option<&str> = Some("Player-1-ME");
+
+    #[test]
+    fn dodge_tracker_counts_avoids_and_hits() {
+        let mut dodge = DodgeTracker::default();
+        dodge.set_tracked(&[1]);
+        dodge.record_cast(1, "Void Nova", "", ME, 1_000);
+        dodge.record_hit(1, "Void Nova", 2_000);            // hit
+        dodge.record_hit(1, "Void Nova", 2_500);            // second tick, same cast
+        dodge.record_cast(1, "Void Nova", "", ME, 10_000);  // never hits → avoided
+        dodge.record_cast(1, "Void Nova", "", ME, 20_000);
+        dodge.record_miss(1, "Void Nova", 20_500);          // immune → avoided
+        dodge.record_cast(1, "Void Nova", "", ME, 30_000);  // open at pull end → avoided
+        dodge.record_hit(2, "Untracked", 30_100);
+        let summary = dodge.summary();
+        assert_eq!(summary.len(), 1);
+        let (id, m) = &summary[0];
+        assert_eq!(*id, 1);
+        assert_eq!((m.hits, m.avoids), (1, 3));
+        assert_eq!(m.dodge_pct(), Some(75.0));
+    }
+
+    #[test]
+    fn dodge_tracker_counts_casts_at_the_player_or_untargeted() {
+        let mut dodge = DodgeTracker::default();
+        dodge.set_tracked(&[1]);
+        dodge.record_cast(1, "Void Bolt", "Player-1-ME", ME, 1_000);  // at the player
+        dodge.record_cast(1, "Void Bolt", "", ME, 10_000);            // untargeted
+        dodge.record_cast(1, "Void Bolt", "Player-1-TANK", ME, 20_000);// at the tank — ignored
+        dodge.record_cast(1, "Void Bolt", "Player-1-TANK", ME, 30_000);
+        let summary = dodge.summary();
+        assert_eq!((summary[0].1.hits, summary[0].1.avoids), (0, 2));
+    }
+
+    #[test]
+    fn cooldown_tracker_knows_spells_across_pulls() {
+        let mut cooldowns = CooldownTracker::default();
+        assert!(!cooldowns.knows(48792));
+        cooldowns.record_cast(48792, 1_000);
+        cooldowns.reset();
+        assert!(cooldowns.knows(48792));
+        assert_eq!(cooldowns.last_used_ms(48792), None);
     }
 
     #[test]
@@ -327,6 +2636,53 @@ mod tests {
         assert!(tracker.is_interruptible(12345), "knowledge should persist");
     }
 
+    #[test]
+    fn interrupt_efficiency_counts_only_ready_misses() {
+        let mut tracker = InterruptTracker::default();
+        tracker.record_interrupt(12345);
+        // Kick never seen: completed casts are not opportunities.
+        tracker.record_completed_cast(12345, 1_000);
+        assert_eq!(tracker.missed_ready, 0);
+
+        tracker.record_player_cast(1766, 2_000); // Kick, 15s
+        tracker.record_completed_cast(12345, 10_000); // on cooldown
+        tracker.record_completed_cast(12345, 17_000); // ready
+        tracker.record_completed_cast(99999, 18_000); // not known interruptible
+        assert_eq!(tracker.missed_ready, 1);
+        assert_eq!(interrupt_efficiency(1, tracker.missed_ready), Some(50.0));
+        assert_eq!(interrupt_efficiency(0, 0), None);
+
+        tracker.reset_per_pull();
+        assert_eq!(tracker.missed_ready, 0);
+        assert_eq!(tracker.session_missed, 1);
+    }
+
+    #[test]
+    fn interrupt_tracker_estimates_party_kick_cooldowns() {
+        let mut tracker = InterruptTracker::default();
+        tracker.record_party_cast("Player-1-A", "Thrall", 57994, 1_000);  // Wind Shear, 12s
+        tracker.record_party_cast("Player-1-B", "Jaina", 2139, 2_000);    // Counterspell, 24s
+        tracker.record_party_cast("Player-1-B", "Jaina", 133, 3_000);     // Fireball: not a kick
+        assert!(tracker.knows_party_kicks());
+        assert_eq!(tracker.party_kicks_ready(10_000).count(), 0);
+        let ready: Vec<&str> = tracker.party_kicks_ready(14_000).map(|(_, k)| k.name.as_str()).collect();
+        assert_eq!(ready, vec!["Thrall"]);
+        assert_eq!(tracker.party_kicks_ready(26_000).count(), 2);
+    }
+
+    #[test]
+    fn enemy_casts_end_or_expire() {
+        let mut tracker = EnemyCastTracker::default();
+        tracker.start("Creature-0-1-1-1-100-A", 12345, "Shadow Bolt", 1_000);
+        tracker.start("Creature-0-1-1-1-101-B", 23456, "Frost Nova", 2_000);
+        let open: Vec<u32> = tracker.in_progress(2_500).iter().map(|c| c.spell_id).collect();
+        assert_eq!(open, vec![12345, 23456]);
+
+        tracker.end("Creature-0-1-1-1-100-A");
+        assert_eq!(tracker.in_progress(2_500).len(), 1);
+        assert!(tracker.in_progress(2_000 + ENEMY_CAST_EXPIRY_MS).is_empty(), "stale casts expire");
+    }
+
     #[test]
     fn damage_taken_recent_window() {
         let mut tracker = DamageTakenTracker::default();
@@ -337,5 +2693,286 @@ mod tests {
         assert_eq!(tracker.recent_damage(7000, 5_000), 18_000);
         // only event at 6000 qualifies with a 2s window
         assert_eq!(tracker.recent_damage(7000, 2_000), 8_000);
+        // shield absorbs count toward the total and are reported apart
+        tracker.record_absorbed(6500, 4_000);
+        assert_eq!(tracker.recent_damage(7000, 2_000), 12_000);
+        assert_eq!(tracker.recent_absorbed(7000, 2_000), 4_000);
+    }
+
+    #[test]
+    fn friendly_damage_is_classified_by_source() {
+        let me = Some("Player-1-A");
+        assert_eq!(DamageSource::of("Player-1-A", 0x511, me), DamageSource::SelfInflicted);
+        assert_eq!(DamageSource::of("Player-1-B", 0x512, me), DamageSource::Party);
+        assert_eq!(DamageSource::of("Creature-0-1-2-3-4-5", 0xa48, me), DamageSource::Enemy);
+
+        let mut tracker = DamageTakenTracker::default();
+        tracker.record_friendly(DamageSource::SelfInflicted, "Burning Rush", 2_000);
+        tracker.record_friendly(DamageSource::SelfInflicted, "Burning Rush", 2_000);
+        tracker.record_friendly(DamageSource::Party, "Spiteful Reflection", 9_000);
+        assert_eq!(tracker.friendly_total(DamageSource::SelfInflicted), 4_000);
+        assert_eq!(tracker.friendly_total(DamageSource::Party), 9_000);
+        assert_eq!(tracker.friendly["Burning Rush"].hits, 2);
+
+        let mut state = CombatState::new();
+        assert!(!state.avoidable_source(DamageSource::Party));
+        state.friendly_avoidable = true;
+        assert!(state.avoidable_source(DamageSource::Party));
+    }
+
+    #[test]
+    fn activity_counts_hard_casts_and_gcds() {
+        let mut act = ActivityTracker::default();
+        act.record_cast_success(1, 0);          // instant: 0–1500
+        act.record_cast_start(2, 1_500);
+        act.record_cast_success(2, 4_000);      // hard cast: 1500–4000
+        act.record_cast_success(3, 6_000);      // instant after 2s idle: 6000–7500
+        assert_eq!(act.active_ms, 5_500);
+        assert!((act.active_pct(10_000) - 55.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn activity_cancels_only_the_failed_cast() {
+        let mut act = ActivityTracker::default();
+        act.record_cast_start(2, 0);
+        act.cancel_cast(9);                      // failed off-GCD press mid-cast
+        act.record_cast_success(2, 2_500);       // hard cast still counts: 0–2500
+        assert_eq!(act.active_ms, 2_500);
+
+        act.record_cast_start(2, 5_000);
+        act.cancel_cast(2);                      // the hard cast itself failed
+        act.record_cast_success(2, 8_000);       // credited as an instant: 8000–9500
+        assert_eq!(act.active_ms, 4_000);
+    }
+
+    #[test]
+    fn activity_excludes_forced_downtime() {
+        let mut act = ActivityTracker::default();
+        act.record_cast_success(1, 0);
+        act.record_cast_success(1, 41_500);     // 40s intermission
+        assert_eq!(act.downtime_ms, 40_000);
+        assert_eq!(act.active_ms, 3_000);
+        assert!((act.active_pct(43_000) - 100.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn group_tracker_detects_follower_content() {
+        let mut group = GroupTracker::default();
+        // Coached player (mine, friendly, player) + follower NPC (party, friendly, npc)
+        group.record_cast("Player-1-A", 0x511, 1_000);
+        group.record_cast("Creature-0-1-B", 0xa12, 1_500);
+        assert!(group.is_solo_content(2_000));
+
+        // A second real player joins → regular group
+        group.record_cast("Player-1-C", 0x512, 3_000);
+        assert!(!group.is_solo_content(3_000));
+
+        // Hostile NPCs never count as allies
+        let mut open_world = GroupTracker::default();
+        open_world.record_cast("Player-1-A", 0x511, 1_000);
+        open_world.record_cast("Creature-0-1-D", 0xa48, 1_000);
+        assert!(!open_world.is_solo_content(1_000));
+    }
+
+    #[test]
+    fn party_damage_ranks_by_avoidable() {
+        let mut party = PartyDamageTracker::default();
+        party.set_avoidable(&[1001]);
+        party.record("Player-1-A", "Me",   0x511, Some(1001), 40_000);
+        party.record("Player-1-A", "Me",   0x511, None,       90_000);
+        party.record("Player-1-B", "Tank", 0x512, Some(2002), 500_000);
+        party.record("Player-1-C", "",     0x512, Some(1001), 60_000);
+        party.record("Player-1-C", "Heal", 0x512, Some(1001), 30_000);
+        // Enemies and non-group players are ignored.
+        party.record("Creature-0-1-D", "Add", 0xa48, Some(1001), 99_000);
+        party.record("Player-1-E", "Stranger", 0x518, Some(1001), 99_000);
+
+        let board = party.leaderboard();
+        let names: Vec<&str> = board.iter().map(|(_, m)| m.name.as_str()).collect();
+        assert_eq!(names, vec!["Heal", "Me", "Tank"]);
+        assert_eq!(board[0].1.avoidable, 90_000);
+        assert_eq!(board[0].1.avoidable_hits, 2);
+        assert_eq!(board[1].1.total, 130_000);
+
+        party.reset();
+        assert!(party.leaderboard().is_empty());
+    }
+
+    #[test]
+    fn healing_split_by_inferred_tanks() {
+        let mut party = PartyDamageTracker::default();
+        party.record("Player-1-T1", "Tank",    0x512, None,       800_000);
+        party.record("Player-1-T2", "OffTank", 0x512, None,       500_000);
+        party.record("Player-1-D",  "Dps",     0x512, None,       100_000);
+        party.record("Player-1-D",  "Dps",     0x512, Some(1001), 900_000);
+        let mut tanks = party.tanks();
+        tanks.sort();
+        assert_eq!(tanks, vec!["Player-1-T1", "Player-1-T2"]);
+
+        let mut healing = HealingTracker::default();
+        healing.record("Player-1-T1", "Tank", 50_000, 10_000);
+        healing.record("Player-1-T2", "OffTank", 20_000, 0);
+        healing.record("Player-1-D",  "Dps", 30_000, 30_000);
+        healing.record("Player-1-D",  "Dps", 20_000, 0);
+        healing.record("Player-1-H",  "Me",  10_000, 0);
+
+        let split = healing.split("Player-1-H", &tanks);
+        assert_eq!(split, HealingSplit { tank: 60_000, party: 20_000, self_heal: 10_000, overhealing: 40_000 });
+        assert_eq!(split.tank_pct(), Some(60_000.0 / 90_000.0 * 100.0));
+        assert_eq!(HealingSplit::default().tank_pct(), None);
+    }
+
+    #[test]
+    fn death_tracker_keeps_first_death_and_its_cause() {
+        let mut deaths = DeathTracker::default();
+        deaths.record_death(5_000);
+        assert_eq!(deaths.death.as_ref().unwrap().cause, "unknown");
+
+        deaths.reset();
+        deaths.record_hit("Melee");
+        deaths.record_hit("Defile");
+        deaths.record_death(130_000);
+        deaths.record_hit("Soul Reaper");
+        deaths.record_death(150_000);
+        assert_eq!(deaths.death, Some(PullDeath { elapsed_ms: 130_000, cause: "Defile".to_owned() }));
+    }
+
+    #[test]
+    fn outgoing_tracker_rolls_and_averages_throughput() {
+        let mut out = OutgoingTracker::default();
+        out.start_pull(100_000);
+        out.record_damage(100_500, 20_000);
+        // Younger than the window: per second since the pull started.
+        assert_eq!(out.rolling_dps(102_000), 10_000.0);
+
+        out.record_damage(115_000, 50_000);
+        out.record_healing(115_000, 30_000);
+        assert_eq!(out.rolling_dps(120_000), 5_000.0);
+        assert_eq!(out.rolling_hps(120_000), 3_000.0);
+        assert_eq!(out.pull_dps(120_000), 3_500.0);
+
+        // The average holds after the pull; the rolling figure decays.
+        out.end_pull(120_000);
+        assert_eq!(out.pull_dps(200_000), 3_500.0);
+        assert_eq!(out.rolling_dps(200_000), 0.0);
+        assert_eq!(out.damage.len(), 1);
+
+        out.start_pull(300_000);
+        assert_eq!((out.damage_total, out.pull_hps(310_000)), (0, 0.0));
+    }
+
+    #[test]
+    fn group_deaths_keep_order_and_battle_res_casts() {
+        let mut group = GroupDeathTracker::default();
+        group.record_death("Tank", 95_000);
+        group.record_cast(1459, "Mage", "Mage", 96_000);
+        group.record_cast(20484, "Druid", "Tank", 99_000);
+        group.record_death("Me", 130_000);
+
+        let names: Vec<_> = group.deaths.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["Tank", "Me"]);
+        assert_eq!(group.battle_res, vec![BattleRes {
+            caster: "Druid".to_owned(), target: "Tank".to_owned(), elapsed_ms: 99_000,
+        }]);
+        group.reset();
+        assert!(group.deaths.is_empty() && group.battle_res.is_empty());
+    }
+
+    #[test]
+    fn lust_windows_follow_the_buff() {
+        let mut lust = LustTracker::default();
+        lust.apply(1459, "Arcane Intellect", 1_000);
+        assert_eq!(lust.up_since, None);
+
+        lust.apply(32182, "Heroism", 10_000);
+        lust.apply(444257, "Thunderous Drums", 12_000);
+        assert_eq!((lust.up_since, lust.name.as_str()), (Some(10_000), "Heroism"));
+        lust.remove(32182, 50_000);
+        assert_eq!(lust.up_since, None);
+        assert_eq!(lust.faded_ago(53_000), Some(3_000));
+
+        lust.start_pull();
+        assert_eq!(lust.faded_ago(60_000), None);
+    }
+
+    #[test]
+    fn consumables_model_shared_cooldowns() {
+        let mut used = ConsumableTracker::default();
+        // Potions are assumed carried; healthstones only once one was seen.
+        assert_eq!(used.ready(0), vec![ConsumableKind::Potion]);
+
+        used.record_use(431416, 10_000);
+        used.record_use(6262, 20_000);
+        used.record_use(1459, 21_000);
+        assert_eq!((used.potions, used.healthstones), (1, 1));
+        assert!(used.ready(60_000).is_empty(), "both on cooldown");
+        assert_eq!(used.ready(80_000), vec![ConsumableKind::Healthstone]);
+        // Any potion shares the cooldown of the one drunk.
+        assert!(!used.available(ConsumableKind::Potion, 300_000));
+        assert!(used.available(ConsumableKind::Potion, 310_000));
+
+        // Counts reset per pull; cooldowns carry over.
+        used.start_pull();
+        assert_eq!((used.potions, used.healthstones), (0, 0));
+        assert!(!used.available(ConsumableKind::Potion, 100_000));
+    }
+
+    #[test]
+    fn prepull_casts_survive_the_trash_pull_before_the_boss() {
+        let mut prepull = PrepullTracker::default();
+        prepull.record(133, 1_000);
+        prepull.record(431932, 45_000);
+        // The pre-cast starts a pull just before ENCOUNTER_START.
+        prepull.record(11366, 58_000);
+        prepull.start_pull();
+        prepull.encounter_start(60_000);
+        assert_eq!(prepull.at_encounter, Some(vec![431932, 11366]), "Fireball is outside the window");
+
+        // The next pull without ENCOUNTER_START has no pre-pull check.
+        prepull.start_pull();
+        assert_eq!(prepull.at_encounter, None);
+    }
+
+    #[test]
+    fn hardcasts_count_cancels_but_not_kicks() {
+        let mut casts = HardcastTracker::default();
+        casts.record_start(116858, "Chaos Bolt", 1_000);
+        // Button pressed mid-cast, then an off-GCD instant: the cast goes on.
+        casts.record_failed(17962, "Another action is in progress", 1_500);
+        casts.record_success(1122);
+        casts.record_success(116858);
+        assert!(casts.cancelled.is_empty());
+
+        casts.record_start(116858, "Chaos Bolt", 5_000);
+        casts.record_failed(116858, "Interrupted", 6_800);
+        assert_eq!(casts.just_cancelled(6_800).map(|c| c.into_ms), Some(1_800));
+
+        // Clipped by the next cast.
+        casts.record_start(29722, "Incinerate", 7_000);
+        casts.record_start(116858, "Chaos Bolt", 7_400);
+        // Kicked by an enemy.
+        casts.kicked();
+        casts.record_failed(116858, "Interrupted", 8_000);
+        assert!(casts.just_cancelled(8_000).is_none());
+
+        assert_eq!((casts.started, casts.cancelled.len(), casts.lost_ms()), (4, 2, 2_200));
+        casts.start_pull();
+        assert_eq!((casts.started, casts.lost_ms()), (0, 0));
+    }
+
+    #[test]
+    fn failed_casts_count_by_reason() {
+        let mut failed = FailedCastTracker::default();
+        for t in ["MOVING", "Can't do that while moving", "Interrupted", "Another action is in progress",
+                  "Not yet recovered", "Out of range", "Can't do that while stunned"] {
+            failed.record(t);
+        }
+        assert_eq!(failed.movement(), 2);
+        assert_eq!(failed.breakdown(), vec![
+            ("movement".to_owned(), 2),
+            ("crowd control".to_owned(), 1),
+            ("interrupted".to_owned(), 1),
+            ("out of range".to_owned(), 1),
+        ]);
     }
 }
@@ -13,19 +13,158 @@
 ///
 /// ## Rotation handling
 /// If the active file shrinks (WoW rewrote it), the offset resets to 0 and the
-/// file is read from the beginning.
+/// file is read from the beginning.  Some rotators and network drives hand
+/// back the old content again on that re-read; `RecentLines` remembers the
+/// hashes of the last `RECENT_LINES` lines sent and drops the re-read lines
+/// that match, up to the first new one, so pulls are not counted twice.
+///
+/// ## Compressed archives
+/// Some log rotators replace a finished WoWCombatLog*.txt with a gzip archive
+/// (`<name>.txt.gz` or `<name>.gz`).  When the active file disappears and such
+/// an archive exists, the lines past our offset are read from the archive and
+/// the tailer moves on to the next log.  `open_log` decompresses by magic
+/// bytes, so replay takes archives directly.
+///
+/// ## Batching
+/// All lines read in one wake-up are sent to the parser as a single
+/// `Vec<String>` (chunked at `MAX_BATCH_LINES`) instead of one channel message
+/// per line.  During dense AoE logging a single flush holds thousands of lines;
+/// one blocking_send per line made the tailer and parser ping-pong on the
+/// channel and delayed the newest events — and therefore advice — the most.
+///
+/// ## Game build
+/// The heartbeat reads the COMBAT_LOG_VERSION header of each new log once it
+/// is on disk and checks its build against the coaching data
+/// (data_version.rs).
+///
+/// ## Offline replay
+/// `replay_log_file` feeds a saved log on its own thread (`replay`), paced by
+/// the line timestamps at 1x to 10x, or as fast as the pipeline takes it.
+/// The lines go to a parser, engine and IPC relay started for the replay
+/// (`spawn_replay_pipeline`), so the overlay and debriefs behave as they did
+/// in game — a paced replay can be watched like a VOD — while the engine's
+/// detached DbWriter keeps sessions, pulls and records out of history.  The
+/// live tailer skips what WoW writes during a replay and picks up at the
+/// live end afterwards.  Idle stretches are shortened to `MAX_REPLAY_GAP_MS`.
+/// `ReplayPacing` carries the play / pause / speed / seek controls of the
+/// running replay.
 use anyhow::Result;
+use flate2::read::MultiGzDecoder;
 use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
 use std::fs::File;
-use std::io::{BufRead, BufReader, Seek, SeekFrom};
-use std::path::PathBuf;
-use std::sync::mpsc as std_mpsc;
-use std::time::Duration;
-use tauri::AppHandle;
-use tokio::sync::mpsc::Sender;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::collections::{hash_map::DefaultHasher, HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    mpsc as std_mpsc, Arc, Condvar, Mutex,
+};
+use std::time::{Duration, Instant, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+use tokio::sync::mpsc::{self, Sender};
+
+use crate::clock::{unix_now_ms, ClockAligner};
+use crate::config::{self, find_latest_log, AppConfig};
+use crate::data_version;
+use crate::db;
+use crate::engine;
+use crate::ipc;
+use crate::parser;
+
+/// Upper bound on lines per channel message.  Large bursts are forwarded in
+/// chunks so the parser can start on the first chunk while the rest is read.
+const MAX_BATCH_LINES: usize = 512;
+/// First two bytes of every gzip member.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+/// Lines remembered for duplicate suppression after a rotation re-read —
+/// a few minutes of raid logging.
+const RECENT_LINES: usize = 50_000;
+
+/// Open a combat log for sequential reading, decompressing it if it is a
+/// gzip archive (detected by content, not by name).
+pub fn open_log(path: &Path) -> Result<Box<dyn BufRead + Send>> {
+    let mut file = File::open(path)?;
+    let mut magic = [0u8; 2];
+    let gzip = file.read_exact(&mut magic).is_ok() && magic == GZIP_MAGIC;
+    file.seek(SeekFrom::Start(0))?;
+    Ok(if gzip {
+        Box::new(BufReader::new(MultiGzDecoder::new(file)))
+    } else {
+        Box::new(BufReader::new(file))
+    })
+}
+
+/// Compressed archive a rotator left in place of `path`, if any.
+fn archived_copy(path: &Path) -> Option<PathBuf> {
+    let mut appended = path.as_os_str().to_owned();
+    appended.push(".gz");
+    [PathBuf::from(appended), path.with_extension("gz")]
+        .into_iter()
+        .find(|p| p.is_file())
+}
+
+// ---------------------------------------------------------------------------
+// Duplicate suppression
+// ---------------------------------------------------------------------------
+
+/// Rolling multiset of hashes of the last `RECENT_LINES` lines sent.
+///
+/// Every line carries a timestamp to the millisecond, so an exact repeat of
+/// a recent line is a re-read, not a new event.  Lines are only checked
+/// while `rereading`: from a rotation reset until the first line not seen
+/// before, which is where the duplicated block ends.
+#[derive(Default)]
+struct RecentLines {
+    order:     VecDeque<u64>,
+    counts:    HashMap<u64, u32>,
+    rereading: bool,
+    dropped:   usize,
+}
 
-use crate::config::find_latest_log;
-use crate::ipc::{self, ConnectionStatus};
+impl RecentLines {
+    /// Start checking lines against the recent ones (rotation reset).
+    fn start_reread(&mut self) {
+        self.rereading = true;
+        self.dropped   = 0;
+    }
+
+    /// False for a line that duplicates one already sent.
+    fn admit(&mut self, line: &str) -> bool {
+        let mut hasher = DefaultHasher::new();
+        line.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        if self.rereading {
+            if self.counts.contains_key(&hash) {
+                self.dropped += 1;
+                return false;
+            }
+            self.end_reread();
+        }
+        self.order.push_back(hash);
+        *self.counts.entry(hash).or_insert(0) += 1;
+        if self.order.len() > RECENT_LINES {
+            if let Some(old) = self.order.pop_front() {
+                if let Some(n) = self.counts.get_mut(&old) {
+                    *n -= 1;
+                    if *n == 0 {
+                        self.counts.remove(&old);
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    fn end_reread(&mut self) {
+        if self.rereading && self.dropped > 0 {
+            tracing::info!("Tailer: dropped {} duplicate lines re-read after rotation", self.dropped);
+        }
+        self.rereading = false;
+    }
+}
 
 // ---------------------------------------------------------------------------
 // Active-file state
@@ -38,6 +177,14 @@ struct TailerState {
     active_file: Option<PathBuf>,
     /// Byte offset of the next unread byte in `active_file`.
     position: u64,
+    /// Log-to-wallclock aligner fed with (last line timestamp, file mtime)
+    /// after every read.  None in unit tests that don't care about alignment.
+    clock: Option<Arc<ClockAligner>>,
+    /// Set while an offline replay runs: new lines are skipped instead of
+    /// sent, so live tailing resumes at the end of the file.
+    suspended: Option<Arc<AtomicBool>>,
+    /// Lines already sent, to drop the ones a rotation re-read repeats.
+    recent: RecentLines,
 }
 
 impl TailerState {
@@ -48,7 +195,7 @@ impl TailerState {
         } else {
             tracing::info!("Tailer: no WoWCombatLog*.txt found yet in {:?}", logs_dir);
         }
-        Self { logs_dir, active_file, position: 0 }
+        Self { logs_dir, active_file, position: 0, clock: None, suspended: None, recent: RecentLines::default() }
     }
 
     /// Called on directory Create events.  If a newer WoWCombatLog*.txt has
@@ -68,7 +215,7 @@ impl TailerState {
     }
 
     /// Read any new lines from the active file since `self.position`.
-    fn read_new_lines(&mut self, tx: &Sender<String>) -> Result<()> {
+    fn read_new_lines(&mut self, tx: &Sender<Vec<String>>) -> Result<()> {
         let path = match &self.active_file {
             Some(p) => p.clone(),
             None => {
@@ -84,14 +231,24 @@ impl TailerState {
 
         let metadata = match std::fs::metadata(&path) {
             Ok(m) => m,
-            Err(_) => return Ok(()), // File doesn't exist yet — wait
+            Err(_) => {
+                if let Some(archive) = archived_copy(&path) {
+                    self.finish_from_archive(&path, &archive, tx)?;
+                }
+                return Ok(()); // File doesn't exist yet — wait
+            }
         };
         let file_len = metadata.len();
+        if self.suspended.as_ref().is_some_and(|s| s.load(Ordering::Relaxed)) {
+            self.position = file_len;
+            return Ok(());
+        }
 
         // Rotation: file shrank since our last read
         if file_len < self.position {
             tracing::info!("CombatLog rotation detected — restarting from byte 0");
             self.position = 0;
+            self.recent.start_reread();
         }
 
         if file_len == self.position {
@@ -101,11 +258,17 @@ impl TailerState {
         let mut file = File::open(&path)?;
         file.seek(SeekFrom::Start(self.position))?;
 
+        let mut last_line_ts: Option<u64> = None;
+        let mut batch: Vec<String> = Vec::new();
         let reader = BufReader::new(&file);
         for line in reader.lines() {
             match line {
-                Ok(l) if !l.is_empty() => {
-                    if tx.blocking_send(l).is_err() {
+                Ok(l) if !l.is_empty() && self.recent.admit(&l) => {
+                    if self.clock.is_some() {
+                        last_line_ts = parser::line_timestamp_ms(&l).or(last_line_ts);
+                    }
+                    batch.push(l);
+                    if batch.len() >= MAX_BATCH_LINES && !send_batch(tx, &mut batch) {
                         return Ok(()); // Receiver gone — pipeline shutting down
                     }
                 }
@@ -116,14 +279,69 @@ impl TailerState {
                 }
             }
         }
+        if !send_batch(tx, &mut batch) {
+            return Ok(());
+        }
 
         // Update position to end of file (handles partial line writes gracefully;
         // partial lines won't be returned by BufRead, so we re-read them next time).
         self.position = file_len;
+
+        // Clock alignment sample: the file's mtime is when WoW wrote the last
+        // line we just read, so (line_ts, mtime) bounds the log→wallclock offset.
+        if let (Some(clock), Some(line_ts)) = (&self.clock, last_line_ts) {
+            if let Some(mtime_ms) = metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_millis() as u64)
+            {
+                clock.observe(line_ts, mtime_ms);
+            }
+        }
+        Ok(())
+    }
+
+    /// The active file was compressed away: read the lines we had not seen yet
+    /// from `archive`, then move on to the newest remaining log.
+    fn finish_from_archive(&mut self, path: &Path, archive: &Path, tx: &Sender<Vec<String>>) -> Result<()> {
+        tracing::warn!("Tailer: {:?} was replaced by archive {:?} — reading its tail from there", path, archive);
+        let mut reader = open_log(archive)?;
+        // Skip what was already read from the uncompressed file.
+        std::io::copy(&mut (&mut reader).take(self.position), &mut std::io::sink())?;
+        let mut batch: Vec<String> = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.is_empty() || !self.recent.admit(&line) {
+                continue;
+            }
+            batch.push(line);
+            if batch.len() >= MAX_BATCH_LINES && !send_batch(tx, &mut batch) {
+                return Ok(());
+            }
+        }
+        send_batch(tx, &mut batch);
+
+        self.active_file = None;
+        self.check_for_new_log();
+        // A log older than the archive was already done before it: skip to its
+        // end instead of replaying it.
+        let archived_at = std::fs::metadata(archive).and_then(|m| m.modified()).ok();
+        if let Some(next) = &self.active_file {
+            let meta = std::fs::metadata(next)?;
+            if archived_at.is_some_and(|at| meta.modified().is_ok_and(|m| m <= at)) {
+                self.position = meta.len();
+            }
+        }
         Ok(())
     }
 }
 
+/// Send the pending batch (if any).  Returns false once the receiver is gone.
+fn send_batch(tx: &Sender<Vec<String>>, batch: &mut Vec<String>) -> bool {
+    batch.is_empty() || tx.blocking_send(std::mem::take(batch)).is_ok()
+}
+
 // ---------------------------------------------------------------------------
 // Public entry point
 // ---------------------------------------------------------------------------
@@ -131,15 +349,19 @@ impl TailerState {
 /// `logs_dir`    — the WoW Logs directory (e.g. `..\World of Warcraft\_retail_\Logs`).
 /// `app_handle`  — used to emit `coach:connection` status events to the frontend.
 /// `wow_path_str`— human-readable path shown in the settings Connection panel.
+/// `clock`       — shared log→wallclock aligner, sampled after every read.
+/// `replaying`   — `ReplayFeed::active`; lines written while it is set are skipped.
 /// NOTE: this is a plain (non-async) blocking function — it must be spawned on a
 /// dedicated OS thread (std::thread::spawn), NOT via tauri::async_runtime::spawn.
 /// Using blocking_send from within a tokio async context panics when the channel
 /// fills up; running on a plain thread avoids that entirely.
 pub fn run(
     logs_dir:     PathBuf,
-    tx:           Sender<String>,
+    tx:           Sender<Vec<String>>,
     app_handle:   AppHandle,
     wow_path_str: String,
+    clock:        Arc<ClockAligner>,
+    replaying:    Arc<AtomicBool>,
 ) -> Result<()> {
     tracing::info!("Tailer starting, watching directory: {:?}", logs_dir);
 
@@ -152,21 +374,25 @@ pub fn run(
         Ok(w) => w,
         Err(e) => {
             tracing::error!("Tailer: failed to create filesystem watcher: {}", e);
-            ipc::emit_connection(&app_handle, &ConnectionStatus {
-                log_tailing: false, addon_connected: false, wow_path: wow_path_str,
+            ipc::update_connection(&app_handle, |s| {
+                s.log_tailing = false;
+                s.wow_path    = wow_path_str;
             });
             return Err(e.into());
         }
     };
     if let Err(e) = watcher.watch(&logs_dir, RecursiveMode::NonRecursive) {
         tracing::error!("Tailer: cannot watch {:?}: {}", logs_dir, e);
-        ipc::emit_connection(&app_handle, &ConnectionStatus {
-            log_tailing: false, addon_connected: false, wow_path: wow_path_str,
+        ipc::update_connection(&app_handle, |s| {
+            s.log_tailing = false;
+            s.wow_path    = wow_path_str;
         });
         return Err(e.into());
     }
 
     let mut state = TailerState::new(logs_dir);
+    state.clock = Some(clock);
+    state.suspended = Some(replaying);
 
     // Skip pre-existing content — only process lines written after the app starts.
     // Placed here (not in TailerState::new) so unit tests can call new() directly
@@ -181,16 +407,18 @@ pub fn run(
         .unwrap_or(0);
 
     // Emit initial connection status so the settings UI reflects reality immediately.
+    // The addon fields are left to the identity watcher.
     let tailing_now = state.active_file.is_some();
-    ipc::emit_connection(&app_handle, &ConnectionStatus {
-        log_tailing:     tailing_now,
-        addon_connected: false,   // updated by identity watcher
-        wow_path:        wow_path_str.clone(),
+    ipc::update_connection(&app_handle, |s| {
+        s.log_tailing = tailing_now;
+        s.wow_path    = wow_path_str.clone();
     });
 
     // Initial read — handles any lines written between position-setting and watcher
     // start (a very small window, but worth covering for correctness).
     state.read_new_lines(&tx)?;
+    // Log whose game build was compared with the coaching data.
+    let mut build_checked: Option<PathBuf> = None;
 
     loop {
         // recv_timeout of 250 ms serves two purposes:
@@ -232,10 +460,9 @@ pub fn run(
                             state.check_for_new_log();
                             // Emit updated status when we first pick up a log file
                             if !was_tailing && state.active_file.is_some() {
-                                ipc::emit_connection(&app_handle, &ConnectionStatus {
-                                    log_tailing:     true,
-                                    addon_connected: false,
-                                    wow_path:        wow_path_str.clone(),
+                                ipc::update_connection(&app_handle, |s| {
+                                    s.log_tailing = true;
+                                    s.wow_path    = wow_path_str.clone();
                                 });
                             }
                             if let Err(e) = state.read_new_lines(&tx) {
@@ -265,11 +492,17 @@ pub fn run(
                 if let Err(e) = state.read_new_lines(&tx) {
                     tracing::warn!("Tailer poll-read error: {}", e);
                 }
-                ipc::emit_connection(&app_handle, &ConnectionStatus {
-                    log_tailing:     state.active_file.is_some(),
-                    addon_connected: false,
-                    wow_path:        wow_path_str.clone(),
+                ipc::update_connection(&app_handle, |s| {
+                    s.log_tailing = state.active_file.is_some();
+                    s.wow_path    = wow_path_str.clone();
                 });
+                // Once the header line is on disk (data_version.rs).
+                if build_checked != state.active_file {
+                    if let Some(build) = state.active_file.as_deref().and_then(data_version::log_build) {
+                        data_version::check_log_build(&app_handle, &build);
+                        build_checked = state.active_file.clone();
+                    }
+                }
             }
             Err(std_mpsc::RecvTimeoutError::Disconnected) => {
                 tracing::warn!("Watcher channel closed — tailer exiting");
@@ -280,6 +513,399 @@ pub fn run(
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// Offline replay
+// ---------------------------------------------------------------------------
+
+/// Idle stretches (between pulls, AFK) are shortened to this during a paced
+/// replay.  Longer than the open-world combat timeout, so pulls still end the
+/// way they did live.
+const MAX_REPLAY_GAP_MS: u64 = 15_000;
+/// A paced replay only sleeps once it is at least this far ahead of the log.
+const REPLAY_MIN_SLEEP: Duration = Duration::from_millis(20);
+
+/// Playback speed of `replay_log_file`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReplaySpeed {
+    #[serde(rename = "1x")]
+    Realtime,
+    #[serde(rename = "2x")]
+    Double,
+    #[serde(rename = "5x")]
+    Fast,
+    #[serde(rename = "10x")]
+    Faster,
+    #[serde(rename = "max")]
+    Max,
+}
+
+impl ReplaySpeed {
+    /// Wallclock time that `gap_ms` of log time takes; None at max speed.
+    fn wait(self, gap_ms: u64) -> Option<Duration> {
+        let gap_ms = gap_ms.min(MAX_REPLAY_GAP_MS);
+        match self {
+            Self::Realtime => Some(Duration::from_millis(gap_ms)),
+            Self::Double   => Some(Duration::from_millis(gap_ms / 2)),
+            Self::Fast     => Some(Duration::from_millis(gap_ms / 5)),
+            Self::Faster   => Some(Duration::from_millis(gap_ms / 10)),
+            Self::Max      => None,
+        }
+    }
+}
+
+/// Where a running replay is, as reported by `get_replay_status`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayStatus {
+    /// Log time since the first line of the file.
+    pub position_ms: u64,
+    pub paused:      bool,
+    pub speed:       ReplaySpeed,
+    /// Position a seek is fast-forwarding to, if one is in progress.
+    pub seeking_to:  Option<u64>,
+}
+
+impl ReplayStatus {
+    /// Log ms the replay engine's clock advances per wallclock ms between
+    /// lines (`EngineCommand::LogRate`).  0 while paused, seeking or at max
+    /// speed, where log time only moves with the lines.
+    fn log_rate(&self) -> u64 {
+        if self.paused || self.seeking_to.is_some() {
+            return 0;
+        }
+        match self.speed {
+            ReplaySpeed::Realtime => 1,
+            ReplaySpeed::Double   => 2,
+            ReplaySpeed::Fast     => 5,
+            ReplaySpeed::Faster   => 10,
+            ReplaySpeed::Max      => 0,
+        }
+    }
+}
+
+/// Play / pause / speed / seek controls of a replay, shared by the replay
+/// thread and the replay commands.  The thread waits on `changed`, so a
+/// control change takes effect at once instead of after the current sleep;
+/// the replay engine is sent the new log clock rate.
+#[derive(Debug)]
+pub struct ReplayPacing {
+    status:  Mutex<ReplayStatus>,
+    changed: Condvar,
+    /// Control channel of the replay engine; None in tests.
+    engine:  Option<Sender<engine::EngineCommand>>,
+}
+
+impl ReplayPacing {
+    pub fn new(speed: ReplaySpeed) -> Self {
+        Self {
+            status:  Mutex::new(ReplayStatus { position_ms: 0, paused: false, speed, seeking_to: None }),
+            changed: Condvar::new(),
+            engine:  None,
+        }
+    }
+
+    /// Keep the log clock of the engine behind `ctl` in step with the replay.
+    fn with_engine(mut self, ctl: Sender<engine::EngineCommand>) -> Self {
+        let _ = ctl.try_send(engine::EngineCommand::LogRate(self.status().log_rate()));
+        self.engine = Some(ctl);
+        self
+    }
+
+    pub fn status(&self) -> ReplayStatus {
+        self.status.lock().unwrap().clone()
+    }
+
+    /// Apply a control change, wake the replay thread and tell the engine
+    /// when its log clock rate changed.
+    fn update(&self, f: impl FnOnce(&mut ReplayStatus)) {
+        let mut status = self.status.lock().unwrap();
+        let rate = status.log_rate();
+        f(&mut status);
+        if let Some(ctl) = self.engine.as_ref().filter(|_| status.log_rate() != rate) {
+            let _ = ctl.try_send(engine::EngineCommand::LogRate(status.log_rate()));
+        }
+        drop(status);
+        self.changed.notify_all();
+    }
+
+    /// Sleep up to `timeout`; returns early when a control changes.
+    fn wait(&self, timeout: Duration) {
+        let guard = self.status.lock().unwrap();
+        let _ = self.changed.wait_timeout(guard, timeout);
+    }
+}
+
+/// Placeholder until the first replay starts.
+impl Default for ReplayPacing {
+    fn default() -> Self {
+        Self::new(ReplaySpeed::Max)
+    }
+}
+
+/// Replay state shared with the live tailer.  Stored in managed state by
+/// `try_start_pipeline`.
+#[derive(Clone, Default)]
+pub struct ReplayFeed {
+    /// True while a replay runs; cleared by `stop_log_replay` to cancel it.
+    /// The live tailer skips new lines while it is set.
+    pub active: Arc<AtomicBool>,
+    /// Controls of the running replay; replaced at every start.
+    pub pacing: Arc<Mutex<Arc<ReplayPacing>>>,
+}
+
+/// Feed the log at `path` (plain or gzip) into `tx` as if WoW were writing it
+/// now, paced by the line timestamps at the speed in `pacing`.  Runs until
+/// the end of the file or until `active` is cleared; returns the number of
+/// lines sent.  The clock is re-based on every batch so advice wallclock
+/// times match the replay.
+///
+/// While paused nothing is sent and the engine's log clock holds (see
+/// `ReplayStatus::log_rate`), so a pull in progress does not time out.  The
+/// clock runs at the replay speed otherwise.  A seek fast-forwards: the lines
+/// up to the target are still sent, unpaced, so trackers and pull history
+/// stay consistent — which is also why seeking only goes forward.
+///
+/// Blocking (blocking_send) — call it from a plain OS thread, like `run`.
+pub fn replay(
+    path:   &Path,
+    tx:     &Sender<Vec<String>>,
+    clock:  &ClockAligner,
+    active: &AtomicBool,
+    pacing: &ReplayPacing,
+) -> Result<usize> {
+    let reader = open_log(path)?;
+    let mut batch: Vec<String> = Vec::new();
+    let mut sent = 0;
+    let mut last_ts: Option<u64> = None;
+    let mut position_ms = 0;
+    // When the current line is due on the wallclock.
+    let mut due = Instant::now();
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+        if !active.load(Ordering::Relaxed) {
+            tracing::info!("Replay cancelled");
+            break;
+        }
+        if let Some(ts) = parser::line_timestamp_ms(&line) {
+            // Midnight rollover reads as a zero gap.
+            let gap_ms = last_ts.map_or(0, |prev| ts.saturating_sub(prev));
+            position_ms += gap_ms;
+            if pacing.status().paused {
+                // Deliver what is due, then hold until resumed or cancelled.
+                if !send_replay_batch(tx, &mut batch, clock, last_ts) {
+                    return Ok(sent);
+                }
+                while pacing.status().paused && active.load(Ordering::Relaxed) {
+                    pacing.wait(Duration::from_millis(250));
+                }
+                due = Instant::now();
+            }
+
+            let status = pacing.status();
+            let seeking = status.seeking_to.is_some_and(|to| position_ms <= to);
+            pacing.update(|s| {
+                s.position_ms = position_ms;
+                // Target reached (unless a newer seek came in meanwhile).
+                if s.seeking_to.is_some_and(|to| position_ms >= to) {
+                    s.seeking_to = None;
+                }
+            });
+
+            if let Some(wait) = speed_wait(&status, seeking, gap_ms) {
+                due += wait;
+                let ahead = due.saturating_duration_since(Instant::now());
+                if ahead >= REPLAY_MIN_SLEEP {
+                    // Deliver everything up to now before waiting.
+                    if !send_replay_batch(tx, &mut batch, clock, last_ts) {
+                        return Ok(sent);
+                    }
+                    pacing.wait(ahead);
+                    // Woken early by a control change: re-pace from here.
+                    due = due.min(Instant::now());
+                }
+            }
+            last_ts = Some(ts);
+        }
+        batch.push(line);
+        sent += 1;
+        if batch.len() >= MAX_BATCH_LINES && !send_replay_batch(tx, &mut batch, clock, last_ts) {
+            return Ok(sent);
+        }
+    }
+    send_replay_batch(tx, &mut batch, clock, last_ts);
+    Ok(sent)
+}
+
+/// Wallclock wait before a line `gap_ms` after the previous one; None while
+/// seeking or at max speed.
+fn speed_wait(status: &ReplayStatus, seeking: bool, gap_ms: u64) -> Option<Duration> {
+    if seeking {
+        None
+    } else {
+        status.speed.wait(gap_ms)
+    }
+}
+
+/// Re-base the clock on the batch's last line, then send it.  Returns false
+/// once the receiver is gone.
+fn send_replay_batch(tx: &Sender<Vec<String>>, batch: &mut Vec<String>, clock: &ClockAligner, last_ts: Option<u64>) -> bool {
+    if let Some(ts) = last_ts.filter(|_| !batch.is_empty()) {
+        let now_ms = unix_now_ms();
+        clock.rebase(ts, now_ms);
+    }
+    send_batch(tx, batch)
+}
+
+/// Start a parser, an engine and an IPC relay for one replay and return the
+/// raw-line sender that feeds them and the engine's control sender; they
+/// shut down once the line sender is dropped.  The
+/// engine writes to a detached DbWriter, so the replay adds no session, pulls
+/// or records to history.  It gets no identity or config updates, and the
+/// role defaults and encounter downloads it asks for are dropped.
+fn spawn_replay_pipeline(
+    app:    &AppHandle,
+    config: AppConfig,
+    clock:  Arc<ClockAligner>,
+) -> (Sender<Vec<String>>, Sender<engine::EngineCommand>) {
+    let (raw_tx,     raw_rx)     = mpsc::channel::<Vec<String>>(64);
+    let (event_tx,   event_rx)   = mpsc::channel(1024);
+    let (advice_tx,  advice_rx)  = mpsc::channel(128);
+    let (snap_tx,    snap_rx)    = mpsc::channel(128);
+    let (debrief_tx, debrief_rx) = mpsc::channel(16);
+    let (recap_tx,   recap_rx)   = mpsc::channel(16);
+    let (key_tx,     key_rx)     = mpsc::channel(4);
+    let (_,          id_rx)      = mpsc::channel(1);
+    let (_,          config_rx)  = mpsc::channel(1);
+    let (ctl_tx,     ctl_rx)     = mpsc::channel(16);
+    let (_,          profile_rx) = mpsc::channel(1);
+    let (profile_tx, _)          = mpsc::channel(1);
+    let (fetch_tx,   _)          = mpsc::channel(1);
+
+    tauri::async_runtime::spawn(parser::run(raw_rx, event_tx));
+    tauri::async_runtime::spawn(engine::run(event_rx, id_rx, config_rx, ctl_rx, advice_tx, snap_tx, debrief_tx, recap_tx, key_tx, profile_tx, fetch_tx, config, db::detached_db_writer(), clock));
+    tauri::async_runtime::spawn(ipc::run(advice_rx, snap_rx, debrief_rx, recap_rx, key_rx, profile_rx, app.clone()));
+    (raw_tx, ctl_tx)
+}
+
+/// Replay a saved combat log at "1x", "2x", "5x", "10x" or "max" speed on a
+/// pipeline of its own (`spawn_replay_pipeline`), with live tailing suspended
+/// until it ends.  Returns once the replay has started; progress shows up as
+/// regular advice, snapshots and debriefs, `ConnectionStatus.replaying` names
+/// the file until it ends, and `get_replay_status` reports the position.
+#[tauri::command]
+pub fn replay_log_file(app: AppHandle, path: String, speed: ReplaySpeed) -> Result<(), String> {
+    let path = PathBuf::from(path);
+    if !path.is_file() {
+        return Err(format!("{} is not a file", path.display()));
+    }
+    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let cfg = config::load_or_default(&config_dir).map_err(|e| e.to_string())?;
+    let feed = replay_feed(&app)?;
+    if feed.active.swap(true, Ordering::SeqCst) {
+        return Err("A replay is already running".into());
+    }
+    let clock = Arc::new(ClockAligner::new());
+    let (tx, ctl) = spawn_replay_pipeline(&app, cfg, clock.clone());
+    let pacing = Arc::new(ReplayPacing::new(speed).with_engine(ctl));
+    *feed.pacing.lock().map_err(|e| e.to_string())? = pacing.clone();
+
+    let name = path.file_name().map_or_else(String::new, |n| n.to_string_lossy().into_owned());
+    tracing::info!("Replay of {} starting ({:?})", name, speed);
+    ipc::update_connection(&app, |s| s.replaying = name.clone());
+
+    let active = feed.active.clone();
+    let handle = app.clone();
+    std::thread::Builder::new()
+        .name("combatlog-replay".into())
+        .spawn(move || {
+            match replay(&path, &tx, &clock, &feed.active, &pacing) {
+                Ok(lines) => tracing::info!("Replay of {} finished ({} lines)", name, lines),
+                Err(e)    => tracing::warn!("Replay of {} failed: {}", name, e),
+            }
+            // Dropping the sender shuts the replay pipeline down.
+            drop(tx);
+            feed.active.store(false, Ordering::SeqCst);
+            ipc::update_connection(&handle, |s| s.replaying.clear());
+        })
+        .map_err(|e| {
+            active.store(false, Ordering::SeqCst);
+            ipc::update_connection(&app, |s| s.replaying.clear());
+            format!("Cannot start replay: {}", e)
+        })?;
+    Ok(())
+}
+
+/// Cancel the running replay; no-op when none is running.
+#[tauri::command]
+pub fn stop_log_replay(app: AppHandle) -> Result<(), String> {
+    let feed = app.state::<Mutex<Option<ReplayFeed>>>();
+    let feed = feed.lock().map_err(|e| e.to_string())?;
+    if let Some(feed) = feed.as_ref() {
+        feed.active.store(false, Ordering::SeqCst);
+        // Wake a paused replay so it sees the cancel.
+        if let Ok(pacing) = feed.pacing.lock() {
+            pacing.update(|s| s.paused = false);
+        }
+    }
+    Ok(())
+}
+
+/// Position and controls of the running replay; None when none is running.
+#[tauri::command]
+pub fn get_replay_status(app: AppHandle) -> Result<Option<ReplayStatus>, String> {
+    let feed = replay_feed(&app)?;
+    if !feed.active.load(Ordering::SeqCst) {
+        return Ok(None);
+    }
+    let pacing = feed.pacing.lock().map_err(|e| e.to_string())?;
+    Ok(Some(pacing.status()))
+}
+
+/// Pause or resume the running replay.
+#[tauri::command]
+pub fn pause_log_replay(app: AppHandle, paused: bool) -> Result<(), String> {
+    running_pacing(&app)?.update(|s| s.paused = paused);
+    Ok(())
+}
+
+/// Change the speed of the running replay.
+#[tauri::command]
+pub fn set_replay_speed(app: AppHandle, speed: ReplaySpeed) -> Result<(), String> {
+    running_pacing(&app)?.update(|s| s.speed = speed);
+    Ok(())
+}
+
+/// Fast-forward the running replay to `position_ms` of log time.
+#[tauri::command]
+pub fn seek_log_replay(app: AppHandle, position_ms: u64) -> Result<(), String> {
+    let pacing = running_pacing(&app)?;
+    if position_ms <= pacing.status().position_ms {
+        return Err("A replay can only seek forward — restart it to go back".into());
+    }
+    pacing.update(|s| s.seeking_to = Some(position_ms));
+    Ok(())
+}
+
+fn replay_feed(app: &AppHandle) -> Result<ReplayFeed, String> {
+    app.state::<Mutex<Option<ReplayFeed>>>()
+        .lock()
+        .map_err(|e| e.to_string())?
+        .clone()
+        .ok_or_else(|| "Pipeline is not running — set the WoW Logs folder first".into())
+}
+
+fn running_pacing(app: &AppHandle) -> Result<Arc<ReplayPacing>, String> {
+    let feed = replay_feed(app)?;
+    if !feed.active.load(Ordering::SeqCst) {
+        return Err("No replay is running".into());
+    }
+    let pacing = feed.pacing.lock().map_err(|e| e.to_string())?;
+    Ok(pacing.clone())
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -294,10 +920,11 @@ mod tests {
     // read_new_lines() is entirely synchronous — it uses blocking_send() which
     // must NOT be called from inside a tokio runtime.  We use a std::sync::mpsc
     // channel here so these are plain synchronous tests with no runtime at all.
-    fn make_channel() -> (tokio::sync::mpsc::Sender<String>, std_mpsc::Receiver<String>) {
+    fn make_channel() -> (tokio::sync::mpsc::Sender<Vec<String>>, std_mpsc::Receiver<String>) {
         // Bridge: tokio sender (what TailerState expects) → std receiver for assertions.
-        let (tok_tx, mut tok_rx) = tokio::sync::mpsc::channel::<String>(64);
-        let (std_tx, std_rx)     = std_mpsc::sync_channel::<String>(64);
+        // Batches are flattened so tests can assert line by line.
+        let (tok_tx, mut tok_rx) = tokio::sync::mpsc::channel::<Vec<String>>(64);
+        let (std_tx, std_rx)     = std_mpsc::sync_channel::<String>(1024);
 
         // Drain the tokio channel into the std channel synchronously.
         // We do this lazily by spinning a thread that forwards messages.
@@ -306,9 +933,11 @@ mod tests {
                 .build()
                 .unwrap();
             rt.block_on(async move {
-                while let Some(msg) = tok_rx.recv().await {
-                    if std_tx.send(msg).is_err() {
-                        break;
+                while let Some(batch) = tok_rx.recv().await {
+                    for line in batch {
+                        if std_tx.send(line).is_err() {
+                            return;
+                        }
                     }
                 }
             });
@@ -334,6 +963,29 @@ mod tests {
         assert_eq!(rx.recv().unwrap(), "line two");
     }
 
+    #[test]
+    fn suspended_tailer_resumes_at_the_live_end() {
+        let dir = tempdir().unwrap();
+        let log_path = dir.path().join("WoWCombatLog.txt");
+        let mut f = std::fs::File::create(&log_path).unwrap();
+        writeln!(f, "during replay").unwrap();
+        f.flush().unwrap();
+
+        let (tx, rx) = make_channel();
+        let replaying = Arc::new(AtomicBool::new(true));
+        let mut state = TailerState::new(dir.path().to_path_buf());
+        state.suspended = Some(replaying.clone());
+        state.read_new_lines(&tx).unwrap();
+
+        replaying.store(false, Ordering::SeqCst);
+        writeln!(f, "after replay").unwrap();
+        f.flush().unwrap();
+        state.read_new_lines(&tx).unwrap();
+
+        assert_eq!(rx.recv().unwrap(), "after replay");
+        assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+    }
+
     #[test]
     fn detects_rotation() {
         let dir = tempdir().unwrap();
@@ -361,6 +1013,23 @@ mod tests {
         assert_eq!(rx.recv().unwrap(), "new");
     }
 
+    #[test]
+    fn drops_lines_repeated_by_rotation_reread() {
+        let dir = tempdir().unwrap();
+        let log_path = dir.path().join("WoWCombatLog.txt");
+        std::fs::write(&log_path, "20:01:02.100  a\n20:01:02.200  b\n20:01:02.300  c\n20:01:02.350  c\n").unwrap();
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<Vec<String>>(8);
+        let mut state = TailerState::new(dir.path().to_path_buf());
+        state.read_new_lines(&tx).unwrap();
+        assert_eq!(rx.try_recv().unwrap().len(), 4);
+
+        // Rewritten shorter, starting with part of the old block again.
+        std::fs::write(&log_path, "20:01:02.200  b\n20:01:02.400  d\n20:01:02.200  b\n").unwrap();
+        state.read_new_lines(&tx).unwrap();
+        assert_eq!(rx.try_recv().unwrap(), vec!["20:01:02.400  d", "20:01:02.200  b"]);
+    }
+
     #[test]
     fn switches_to_newer_log_file() {
         let dir = tempdir().unwrap();
@@ -395,6 +1064,167 @@ mod tests {
         assert_eq!(state.active_file.as_deref(), Some(new_path.as_path()));
     }
 
+    #[test]
+    fn samples_clock_after_read() {
+        let dir = tempdir().unwrap();
+        let log_path = dir.path().join("WoWCombatLog.txt");
+        let mut f = std::fs::File::create(&log_path).unwrap();
+        writeln!(f, "5/21 20:14:33.456  SPELL_CAST_SUCCESS,Player-1234-ABCDEF").unwrap();
+        f.flush().unwrap();
+
+        let (tx, rx) = make_channel();
+        let clock = Arc::new(ClockAligner::new());
+        let mut state = TailerState::new(dir.path().to_path_buf());
+        state.clock = Some(clock.clone());
+        state.read_new_lines(&tx).unwrap();
+        let _ = rx.recv();
+
+        assert!(clock.offset_ms().is_some());
+    }
+
+    #[test]
+    fn sends_one_batch_per_read() {
+        let dir = tempdir().unwrap();
+        let log_path = dir.path().join("WoWCombatLog.txt");
+        let mut f = std::fs::File::create(&log_path).unwrap();
+        for i in 0..(MAX_BATCH_LINES + 10) {
+            writeln!(f, "line {}", i).unwrap();
+        }
+        f.flush().unwrap();
+
+        // blocking_send/try_recv work without a runtime — inspect batches directly.
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<Vec<String>>(8);
+        let mut state = TailerState::new(dir.path().to_path_buf());
+        state.read_new_lines(&tx).unwrap();
+
+        assert_eq!(rx.try_recv().unwrap().len(), MAX_BATCH_LINES);
+        assert_eq!(rx.try_recv().unwrap().len(), 10);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn replay_sends_whole_file_and_rebases_clock() {
+        let dir = tempdir().unwrap();
+        let log_path = dir.path().join("WoWCombatLog_2024_06_14_201500.txt");
+        let mut f = std::fs::File::create(&log_path).unwrap();
+        for i in 0..(MAX_BATCH_LINES + 3) {
+            writeln!(f, "6/14 20:15:{:02}.000  SPELL_CAST_SUCCESS,Player-1234-ABCDEF", i % 60).unwrap();
+        }
+        f.flush().unwrap();
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<Vec<String>>(8);
+        let clock = ClockAligner::new();
+        let active = AtomicBool::new(true);
+        let pacing = ReplayPacing::new(ReplaySpeed::Max);
+        let sent = replay(&log_path, &tx, &clock, &active, &pacing).unwrap();
+
+        assert_eq!(sent, MAX_BATCH_LINES + 3);
+        assert_eq!(rx.try_recv().unwrap().len(), MAX_BATCH_LINES);
+        assert_eq!(rx.try_recv().unwrap().len(), 3);
+        assert!(clock.offset_ms().is_some());
+
+        // Cancelled before the first line: nothing is sent.
+        active.store(false, Ordering::SeqCst);
+        assert_eq!(replay(&log_path, &tx, &clock, &active, &pacing).unwrap(), 0);
+    }
+
+    fn write_gzip(path: &Path, lines: &[&str]) {
+        let mut gz = flate2::write::GzEncoder::new(
+            std::fs::File::create(path).unwrap(),
+            flate2::Compression::default(),
+        );
+        for line in lines {
+            writeln!(gz, "{}", line).unwrap();
+        }
+        gz.finish().unwrap();
+    }
+
+    #[test]
+    fn replay_reads_gzip_archive() {
+        let dir = tempdir().unwrap();
+        // Named .txt on purpose: detection goes by content.
+        let log_path = dir.path().join("WoWCombatLog_2024_06_14_201500.txt");
+        write_gzip(&log_path, &["6/14 20:15:00.000  A", "6/14 20:15:01.000  B"]);
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<Vec<String>>(8);
+        let pacing = ReplayPacing::new(ReplaySpeed::Max);
+        let sent = replay(&log_path, &tx, &ClockAligner::new(), &AtomicBool::new(true), &pacing).unwrap();
+        assert_eq!(sent, 2);
+        assert_eq!(rx.try_recv().unwrap(), vec!["6/14 20:15:00.000  A", "6/14 20:15:01.000  B"]);
+    }
+
+    #[test]
+    fn reads_tail_from_compressed_archive() {
+        let dir = tempdir().unwrap();
+        let log_path = dir.path().join("WoWCombatLog.txt");
+        {
+            let mut f = std::fs::File::create(&log_path).unwrap();
+            writeln!(f, "seen").unwrap();
+        }
+
+        let (tx, rx) = make_channel();
+        let mut state = TailerState::new(dir.path().to_path_buf());
+        state.read_new_lines(&tx).unwrap();
+        assert_eq!(rx.recv().unwrap(), "seen");
+
+        // WoW wrote one more line, then a rotator compressed the file away.
+        std::fs::remove_file(&log_path).unwrap();
+        write_gzip(&dir.path().join("WoWCombatLog.txt.gz"), &["seen", "unseen"]);
+
+        state.read_new_lines(&tx).unwrap();
+        assert_eq!(rx.recv().unwrap(), "unseen");
+        assert!(state.active_file.is_none());
+    }
+
+    #[test]
+    fn replay_pacing_caps_idle_gaps() {
+        assert_eq!(ReplaySpeed::Realtime.wait(1_500), Some(Duration::from_millis(1_500)));
+        assert_eq!(ReplaySpeed::Fast.wait(1_500), Some(Duration::from_millis(300)));
+        assert_eq!(ReplaySpeed::Realtime.wait(600_000), Some(Duration::from_millis(MAX_REPLAY_GAP_MS)));
+        assert_eq!(ReplaySpeed::Max.wait(1_500), None);
+    }
+
+    #[test]
+    fn replay_seek_skips_the_pacing() {
+        let dir = tempdir().unwrap();
+        let log_path = dir.path().join("WoWCombatLog.txt");
+        let mut f = std::fs::File::create(&log_path).unwrap();
+        for s in [0, 10, 20, 30] {
+            writeln!(f, "6/14 20:15:{:02}.000  SPELL_CAST_SUCCESS,Player-1234-ABCDEF", s).unwrap();
+        }
+        f.flush().unwrap();
+
+        // At 1x this file takes 30 s; seeking to its end takes no time at all.
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<Vec<String>>(8);
+        let pacing = ReplayPacing::new(ReplaySpeed::Realtime);
+        pacing.update(|s| s.seeking_to = Some(30_000));
+        let started = Instant::now();
+        assert_eq!(replay(&log_path, &tx, &ClockAligner::new(), &AtomicBool::new(true), &pacing).unwrap(), 4);
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(rx.try_recv().unwrap().len(), 4);
+
+        let status = pacing.status();
+        assert_eq!((status.position_ms, status.seeking_to), (30_000, None));
+    }
+
+    #[test]
+    fn replay_controls_set_the_engine_log_rate() {
+        let (ctl, mut ctl_rx) = tokio::sync::mpsc::channel(8);
+        let pacing = ReplayPacing::new(ReplaySpeed::Fast).with_engine(ctl);
+        pacing.update(|s| s.paused = true);
+        pacing.update(|s| s.position_ms = 1_000); // no rate change, nothing sent
+        pacing.update(|s| s.paused = false);
+        pacing.update(|s| s.speed = ReplaySpeed::Max);
+
+        let mut rates = Vec::new();
+        while let Ok(cmd) = ctl_rx.try_recv() {
+            if let engine::EngineCommand::LogRate(rate) = cmd {
+                rates.push(rate);
+            }
+        }
+        assert_eq!(rates, [5, 0, 5, 0]);
+    }
+
     /// Regression: tailer should not panic or error when the directory has no
     /// combat log yet (e.g. player hasn't enabled /combatlog).
     #[test]
@@ -0,0 +1,121 @@
+/// Locale-aware formatting of numbers and durations for advice text.
+///
+/// Rules used to build their own strings (`format!("{}k", dmg / 1000)`,
+/// `format!("{:.1}s", gap)`), which hard-coded English separators and made
+/// the "55k" vs "55 000" choice per rule.  All user-facing numbers now go
+/// through a `NumberFormat` taken from `AppConfig.number_format`, handed to
+/// rules via `RuleContext::fmt`.
+use crate::config::bool_true;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NumberFormat {
+    /// Abbreviate large amounts ("55k", "1.2M") instead of full digits.
+    #[serde(default = "bool_true")]
+    pub compact:       bool,
+    /// Thousands separator for full-digit amounts: ",", ".", " " or "".
+    #[serde(default = "default_thousands_sep")]
+    pub thousands_sep: String,
+    /// Decimal separator: "." or ",".
+    #[serde(default = "default_decimal_sep")]
+    pub decimal_sep:   String,
+}
+
+fn default_thousands_sep() -> String { ",".to_owned() }
+fn default_decimal_sep()   -> String { ".".to_owned() }
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            compact:       true,
+            thousands_sep: default_thousands_sep(),
+            decimal_sep:   default_decimal_sep(),
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Damage/healing amount: "55k" / "1.2M" when compact, else "55,000".
+    pub fn amount(&self, n: u64) -> String {
+        if self.compact {
+            if n >= 1_000_000 {
+                return format!("{}M", self.decimal(n as f64 / 1_000_000.0, 1));
+            }
+            if n >= 1_000 {
+                return format!("{}k", n / 1_000);
+            }
+            return n.to_string();
+        }
+        self.grouped(n)
+    }
+
+    /// Full digits with the configured thousands separator.
+    pub fn grouped(&self, n: u64) -> String {
+        let digits = n.to_string();
+        let lead = digits.len() % 3;
+        let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+        for (i, c) in digits.chars().enumerate() {
+            // A separator goes before every digit with a multiple of 3 digits after it.
+            if i > 0 && i % 3 == lead {
+                out.push_str(&self.thousands_sep);
+            }
+            out.push(c);
+        }
+        out
+    }
+
+    /// Seconds with `places` decimals: "2.5s".
+    pub fn seconds(&self, ms: u64, places: usize) -> String {
+        format!("{}s", self.decimal(ms as f64 / 1_000.0, places))
+    }
+
+    /// Duration that switches to minutes past one minute: "4.5s" / "1:05".
+    pub fn duration(&self, ms: u64) -> String {
+        if ms < 60_000 {
+            return self.seconds(ms, 1);
+        }
+        let total_s = ms / 1_000;
+        format!("{}:{:02}", total_s / 60, total_s % 60)
+    }
+
+    fn decimal(&self, v: f64, places: usize) -> String {
+        let s = format!("{:.*}", places, v);
+        if self.decimal_sep == "." { s } else { s.replace('.', &self.decimal_sep) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn european() -> NumberFormat {
+        NumberFormat {
+            compact:       false,
+            thousands_sep: " ".to_owned(),
+            decimal_sep:   ",".to_owned(),
+        }
+    }
+
+    #[test]
+    fn compact_amounts() {
+        let f = NumberFormat::default();
+        assert_eq!(f.amount(950),       "950");
+        assert_eq!(f.amount(55_300),    "55k");
+        assert_eq!(f.amount(1_240_000), "1.2M");
+    }
+
+    #[test]
+    fn grouped_amounts() {
+        let f = european();
+        assert_eq!(f.amount(55_000),    "55 000");
+        assert_eq!(f.amount(1_234_567), "1 234 567");
+        assert_eq!(f.amount(999),       "999");
+    }
+
+    #[test]
+    fn durations_honor_decimal_separator() {
+        assert_eq!(NumberFormat::default().seconds(2_500, 1), "2.5s");
+        assert_eq!(european().seconds(2_500, 1),              "2,5s");
+        assert_eq!(european().duration(65_400),               "1:05");
+    }
+}
@@ -0,0 +1,379 @@
+/// Data-file validator for spec, spec layer, encounter and dungeon TOMLs.
+///
+/// Community contributions add files under `data/specs/`,
+/// `data/encounters/` and `data/dungeons/`; a typo in a spell ID silently disables a rule, and a
+/// missing section makes the whole file fail to load at runtime (logged as a
+/// warning nobody sees).  The validator catches both before a file is merged:
+///
+///   - parses the file with the same code the app uses (specs::parse_str /
+///     specs::parse_layer_str / encounters::parse_str /
+///     encounters::parse_dungeon_str), so required sections
+///     are enforced exactly — spec previews include inherited layer IDs
+///   - checks every referenced spell ID against the spell metadata bundle
+///     (spells.rs) and reports unknown IDs
+///   - previews the resulting profile with spell names
+///
+/// Exposed as the `validate_spec_file` command and as a dev CLI:
+///   combat-ledger-live-coach --validate data/specs/paladin_holy.toml [--watch]
+/// `--watch` re-validates whenever the file is saved.
+use crate::{encounters, spells, specs};
+use anyhow::Result;
+use serde::Serialize;
+use std::path::Path;
+use std::time::Duration;
+
+const VALID_ROLES: &[&str] = &["TANK", "HEALER", "DAMAGER"];
+const WATCH_POLL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Default, Serialize)]
+pub struct ValidationReport {
+    /// "spec", "layer" (`[class]` / `[global]`), "encounter", "dungeon", or "unknown"
+    /// when no known section is present.
+    pub kind:              String,
+    /// Problems that stop the app from loading the file.
+    pub errors:            Vec<String>,
+    /// Suspicious but loadable content (unknown spell IDs, empty lists, …).
+    pub warnings:          Vec<String>,
+    pub unknown_spell_ids: Vec<u32>,
+    /// Human-readable summary of the parsed profile (empty on parse failure).
+    pub preview:           Vec<String>,
+}
+
+impl ValidationReport {
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+impl std::fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "kind: {}", self.kind)?;
+        for line in &self.preview {
+            writeln!(f, "  {}", line)?;
+        }
+        for e in &self.errors {
+            writeln!(f, "ERROR: {}", e)?;
+        }
+        for w in &self.warnings {
+            writeln!(f, "warning: {}", w)?;
+        }
+        let verdict = if self.is_ok() { "OK" } else { "FAILED" };
+        writeln!(f, "{} ({} errors, {} warnings)", verdict, self.errors.len(), self.warnings.len())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Validation
+// ---------------------------------------------------------------------------
+
+/// Validate a spec, encounter or dungeon TOML document.
+pub fn validate_str(raw: &str) -> ValidationReport {
+    let mut report = ValidationReport::default();
+
+    let value: toml::Value = match toml::from_str(raw) {
+        Ok(v)  => v,
+        Err(e) => {
+            report.kind = "unknown".to_owned();
+            report.errors.push(format!("TOML syntax: {}", e));
+            return report;
+        }
+    };
+
+    if value.get("spec").is_some() {
+        report.kind = "spec".to_owned();
+        validate_spec(raw, &mut report);
+    } else if value.get("class").is_some() || value.get("global").is_some() {
+        report.kind = "layer".to_owned();
+        validate_layer(raw, &mut report);
+    } else if value.get("encounter").is_some() {
+        report.kind = "encounter".to_owned();
+        validate_encounter(raw, &mut report);
+    } else if value.get("dungeon").is_some() {
+        report.kind = "dungeon".to_owned();
+        validate_dungeon(raw, &mut report);
+    } else {
+        report.kind = "unknown".to_owned();
+        report.errors.push("missing [spec], [class], [global], [encounter] or [dungeon] section".to_owned());
+        return report;
+    }
+
+    let mut ids = Vec::new();
+    collect_spell_ids(&value, "", &mut ids);
+    ids.sort_unstable();
+    ids.dedup();
+    report.unknown_spell_ids = ids.into_iter().filter(|id| spells::name(*id).is_none()).collect();
+    if !report.unknown_spell_ids.is_empty() {
+        report.warnings.push(format!(
+            "spell IDs not in data/spells.toml (typo, or add them there): {:?}",
+            report.unknown_spell_ids
+        ));
+    }
+    report
+}
+
+/// Read and validate a file from disk.
+pub fn validate_file(path: &Path) -> Result<ValidationReport> {
+    let raw = std::fs::read_to_string(path)?;
+    // Editors on Windows often save with a BOM; the embedded files have one too.
+    Ok(validate_str(raw.trim_start_matches('\u{feff}')))
+}
+
+fn validate_spec(raw: &str, report: &mut ValidationReport) {
+    let profile = match specs::parse_str(raw) {
+        Ok(p)  => p,
+        Err(e) => { report.errors.push(format!("spec: {}", e)); return; }
+    };
+
+    if !VALID_ROLES.contains(&profile.role.as_str()) {
+        report.errors.push(format!(
+            "role '{}' must be one of {}", profile.role, VALID_ROLES.join(", ")
+        ));
+    }
+    let known_class = specs::list_all().iter().any(|s| s.class == profile.class);
+    if !known_class {
+        report.warnings.push(format!("class '{}' matches no embedded spec", profile.class));
+    }
+    if profile.major_cd_spell_ids.is_empty() {
+        report.warnings.push("major_cd_spell_ids is empty — cooldown_drift will never fire".to_owned());
+    }
+    let mut untracked: Vec<u32> = profile.cooldown_s
+        .keys()
+        .filter(|id| !profile.major_cd_spell_ids.contains(id))
+        .copied()
+        .collect();
+    if !untracked.is_empty() {
+        untracked.sort_unstable();
+        report.warnings.push(format!(
+            "cooldown_s IDs not in major_cd_spell_ids (never checked): {:?}", untracked
+        ));
+    }
+    let expected_keys: Vec<String> = toml::from_str::<toml::Value>(raw).ok()
+        .and_then(|v| {
+            let table = v.get("spec")?.get("rotation")?.get("expected_cpm")?.as_table()?.clone();
+            Some(table.into_iter().map(|(k, _)| k).collect())
+        })
+        .unwrap_or_default();
+    let unchecked: Vec<&String> = expected_keys.iter()
+        .filter(|k| !profile.priority_spells.iter().any(|p| p.spell_id.to_string() == **k))
+        .collect();
+    if !unchecked.is_empty() {
+        report.warnings.push(format!(
+            "expected_cpm IDs not in primary_spell_ids (never checked): {:?}", unchecked
+        ));
+    }
+    if profile.am_spell_ids.is_empty() && profile.role == "TANK" {
+        report.warnings.push("tank spec without [spec.active_mitigation]".to_owned());
+    }
+
+    report.preview.push(format!("{} ({})", profile.key(), profile.role));
+    report.preview.push(format!("major CDs: {}", spell_list(&profile.major_cd_spell_ids)));
+    report.preview.push(format!("active mitigation: {}", spell_list(&profile.am_spell_ids)));
+    if !profile.cooldown_s.is_empty() {
+        report.preview.push(format!("cooldown durations: {} CDs", profile.cooldown_s.len()));
+    }
+    if !profile.opener_spell_ids.is_empty() {
+        report.preview.push(format!("opener: {}", spell_list(&profile.opener_spell_ids)));
+    }
+    if !profile.dot_spell_ids.is_empty() {
+        report.preview.push(format!("DoTs: {}", spell_list(&profile.dot_spell_ids)));
+    }
+    if !profile.prepull_spell_ids.is_empty() {
+        report.preview.push(format!("pre-cast: {}", spell_list(&profile.prepull_spell_ids)));
+    }
+    if !profile.priority_spells.is_empty() {
+        let ids: Vec<u32> = profile.priority_spells.iter().map(|p| p.spell_id).collect();
+        report.preview.push(format!("priority spells: {}", spell_list(&ids)));
+    }
+}
+
+fn validate_layer(raw: &str, report: &mut ValidationReport) {
+    let layer = match specs::parse_layer_str(raw) {
+        Ok(l)  => l,
+        Err(e) => { report.errors.push(format!("layer: {}", e)); return; }
+    };
+
+    match &layer.class {
+        Some(class) => {
+            if !specs::list_all().iter().any(|s| &s.class == class) {
+                report.warnings.push(format!("class '{}' matches no embedded spec", class));
+            }
+            report.preview.push(format!("class layer: {}", class));
+        }
+        None => report.preview.push("global layer".to_owned()),
+    }
+    // The global layer may be empty: consumables come from consumables.toml.
+    if layer.class.is_some() && layer.major_cd_spell_ids.is_empty() && layer.am_spell_ids.is_empty() {
+        report.warnings.push("layer adds no spell IDs".to_owned());
+    }
+    report.preview.push(format!("major CDs: {}", spell_list(&layer.major_cd_spell_ids)));
+    report.preview.push(format!("active mitigation: {}", spell_list(&layer.am_spell_ids)));
+}
+
+fn validate_encounter(raw: &str, report: &mut ValidationReport) {
+    let (encounter_id, profile) = match encounters::parse_str(raw) {
+        Ok(p)  => p,
+        Err(e) => { report.errors.push(format!("encounter: {}", e)); return; }
+    };
+
+    if encounter_id == 0 {
+        report.warnings.push("no encounter_id — matched by name only".to_owned());
+    }
+    for entry in &profile.timeline {
+        if entry.repeat_s.is_some_and(|r| r <= 0.0) {
+            report.errors.push(format!("timeline '{}': repeat_s must be > 0", entry.name));
+        }
+        if entry.first_s < 0.0 {
+            report.errors.push(format!("timeline '{}': first_s must be >= 0", entry.name));
+        }
+    }
+
+    if let Some(lust) = &profile.lust_phase {
+        if !profile.phases.iter().any(|p| p.name.eq_ignore_ascii_case(lust)) {
+            report.errors.push(format!("lust_phase '{}' matches no [[encounter.phases]] name", lust));
+        }
+    }
+
+    if profile.tank_heal_share_pct.is_some_and(|p| !(0.0..=100.0).contains(&p)) {
+        report.errors.push("tank_heal_share_pct must be between 0 and 100".to_owned());
+    }
+
+    report.preview.push(format!("{} (id {})", profile.name, encounter_id));
+    report.preview.push(format!("avoidable: {}", spell_list(&profile.avoidable_spell_ids)));
+    if !profile.boss_npc_ids.is_empty() {
+        report.preview.push(format!("boss NPCs: {:?}", profile.boss_npc_ids));
+    }
+    if !profile.cc_spell_ids.is_empty() {
+        report.preview.push(format!("crowd control: {}", spell_list(&profile.cc_spell_ids)));
+    }
+    if !profile.priority_npc_ids.is_empty() {
+        report.preview.push(format!("priority adds: {:?}", profile.priority_npc_ids));
+    }
+    if let Some(pct) = profile.tank_heal_share_pct {
+        report.preview.push(format!("tank healing share: {}%+", pct));
+    }
+    report.preview.push(format!(
+        "timeline: {} entries, {} phases", profile.timeline.len(), profile.phases.len()
+    ));
+    if let Some(lust) = &profile.lust_phase {
+        report.preview.push(format!("lust in: {}", lust));
+    }
+}
+
+fn validate_dungeon(raw: &str, report: &mut ValidationReport) {
+    let dungeon = match encounters::parse_dungeon_str(raw) {
+        Ok(d)  => d,
+        Err(e) => { report.errors.push(format!("dungeon: {}", e)); return; }
+    };
+
+    if dungeon.instance_id == 0 {
+        report.warnings.push("instance_id is 0 — the file never matches".to_owned());
+    }
+    report.preview.push(format!("{} (instance {})", dungeon.name, dungeon.instance_id));
+    report.preview.push(format!("avoidable: {}", spell_list(&dungeon.avoidable_spell_ids)));
+}
+
+/// Every spell ID referenced by the document: `*spell_ids` arrays,
+/// `*spell_id` integers (0 = unset), and numeric keys (`471809 = "name"`).
+fn collect_spell_ids(value: &toml::Value, key: &str, out: &mut Vec<u32>) {
+    match value {
+        toml::Value::Table(t) => {
+            for (k, v) in t {
+                if let Ok(id) = k.parse::<u32>() {
+                    out.push(id);
+                }
+                collect_spell_ids(v, k, out);
+            }
+        }
+        toml::Value::Array(a) if key.ends_with("spell_ids") => {
+            out.extend(a.iter().filter_map(|v| v.as_integer()).map(|i| i as u32));
+        }
+        toml::Value::Array(a) => {
+            for v in a {
+                collect_spell_ids(v, key, out);
+            }
+        }
+        toml::Value::Integer(i) if key.ends_with("spell_id") && *i > 0 => out.push(*i as u32),
+        _ => {}
+    }
+}
+
+fn spell_list(ids: &[u32]) -> String {
+    if ids.is_empty() {
+        return "—".to_owned();
+    }
+    ids.iter()
+        .map(|id| format!("{} {}", id, spells::name(*id).unwrap_or("?")))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// ---------------------------------------------------------------------------
+// Entry points
+// ---------------------------------------------------------------------------
+
+/// Tauri command: validate a spec, spec layer, encounter or dungeon TOML at `path`.
+#[tauri::command]
+pub fn validate_spec_file(path: String) -> Result<ValidationReport, String> {
+    validate_file(Path::new(&path)).map_err(|e| e.to_string())
+}
+
+/// Dev CLI: print the report; with `watch`, re-validate on every save.
+/// Returns the process exit code (0 ok, 1 validation errors, 2 unreadable).
+pub fn run_cli(path: &str, watch: bool) -> i32 {
+    let path = Path::new(path);
+    let mut last_mtime = None;
+    loop {
+        let mtime = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        if !watch || mtime != last_mtime {
+            last_mtime = mtime;
+            let code = match validate_file(path) {
+                Ok(report) => { print!("{}", report); if report.is_ok() { 0 } else { 1 } }
+                Err(e)     => { eprintln!("{}: {}", path.display(), e); 2 }
+            };
+            if !watch {
+                return code;
+            }
+            println!("-- watching {} (Ctrl+C to stop)", path.display());
+        }
+        std::thread::sleep(WATCH_POLL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_data_is_valid() {
+        let sources = specs::embedded_sources().iter()
+            .chain(specs::embedded_layer_sources())
+            .chain(encounters::embedded_sources())
+            .chain(encounters::embedded_dungeon_sources());
+        for raw in sources {
+            let report = validate_str(raw.trim_start_matches('\u{feff}'));
+            assert!(report.is_ok(), "{}", report);
+            assert!(report.unknown_spell_ids.is_empty(), "{}", report);
+        }
+    }
+
+    #[test]
+    fn reports_missing_section_and_unknown_ids() {
+        let report = validate_str("[spec]\nclass = \"PALADIN\"\nspec = \"Test\"\nrole = \"DAMAGER\"\n");
+        assert_eq!(report.kind, "spec");
+        assert!(!report.is_ok(), "missing [spec.cooldowns] must be an error");
+
+        let report = validate_str(
+            "[spec]\nclass = \"PALADIN\"\nspec = \"Test\"\nrole = \"DPS\"\n\
+             [spec.cooldowns]\nmajor_cd_spell_ids = [31884, 999999999]\n",
+        );
+        assert!(report.errors.iter().any(|e| e.contains("role")));
+        assert_eq!(report.unknown_spell_ids, vec![999999999]);
+    }
+
+    #[test]
+    fn rejects_files_without_known_section() {
+        let report = validate_str("[boss]\nname = \"x\"\n");
+        assert_eq!(report.kind, "unknown");
+        assert!(!report.is_ok());
+    }
+}
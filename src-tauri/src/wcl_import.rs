@@ -0,0 +1,330 @@
+/// Quick setup from Warcraft Logs — one character lookup fills in what the
+/// first-run settings otherwise ask for field by field.
+///
+/// `import_wcl_character(name, realm, region)` asks the Warcraft Logs v2 API
+/// (GraphQL, client-credentials OAuth) for the character's class and its
+/// rankings in the current raid tier, then:
+///   - sets `player_focus` to "Name-Realm"
+///   - selects the spec profile of the spec most kills were logged as
+///     (`config::select_spec`, as the spec picker does)
+///   - downloads the tier's encounter files (encounter_download.rs), when
+///     encounter downloads are on
+///
+/// It returns the updated config with the tier progress for the setup
+/// screen.
+///
+/// Warcraft Logs only serves the API to registered clients: the user creates
+/// one at warcraftlogs.com/api/clients and enters its ID and secret
+/// (`AppConfig.wcl_client_id` / `wcl_client_secret`).  Only public data is
+/// read.
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    config::{self, AppConfig},
+    encounter_download, specs,
+};
+
+const TOKEN_URL: &str = "https://www.warcraftlogs.com/oauth/token";
+const API_URL:   &str = "https://www.warcraftlogs.com/api/v2/client";
+
+/// Class and current-tier rankings; `zoneRankings` defaults to the latest
+/// raid zone.
+const QUERY: &str = "query($name: String!, $server: String!, $region: String!) {
+  characterData { character(name: $name, serverSlug: $server, serverRegion: $region) {
+    name classID zoneRankings
+  } }
+}";
+
+/// Warcraft Logs class IDs (alphabetical, unlike the game's) → profile class.
+const CLASSES: &[(u32, &str)] = &[
+    (1,  "DEATH_KNIGHT"),
+    (2,  "DRUID"),
+    (3,  "HUNTER"),
+    (4,  "MAGE"),
+    (5,  "MONK"),
+    (6,  "PALADIN"),
+    (7,  "PRIEST"),
+    (8,  "ROGUE"),
+    (9,  "SHAMAN"),
+    (10, "WARLOCK"),
+    (11, "WARRIOR"),
+    (12, "DEMON_HUNTER"),
+    (13, "EVOKER"),
+];
+
+/// Warcraft Logs raid difficulty IDs.
+fn difficulty_name(difficulty: u32) -> &'static str {
+    match difficulty {
+        1 => "LFR",
+        3 => "Normal",
+        4 => "Heroic",
+        5 => "Mythic",
+        _ => "",
+    }
+}
+
+// ---------------------------------------------------------------------------
+// API response
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+struct Response {
+    data:   Option<ResponseData>,
+    #[serde(default)]
+    errors: Vec<ResponseError>,
+}
+
+#[derive(Deserialize)]
+struct ResponseError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct ResponseData {
+    #[serde(rename = "characterData")]
+    character_data: CharacterData,
+}
+
+#[derive(Deserialize)]
+struct CharacterData {
+    character: Option<Character>,
+}
+
+#[derive(Deserialize)]
+struct Character {
+    name:          String,
+    #[serde(rename = "classID")]
+    class_id:      u32,
+    #[serde(rename = "zoneRankings", default)]
+    zone_rankings: Option<ZoneRankings>,
+}
+
+#[derive(Deserialize, Default)]
+struct ZoneRankings {
+    #[serde(default)]
+    difficulty: u32,
+    #[serde(default)]
+    rankings:   Vec<Ranking>,
+}
+
+#[derive(Deserialize)]
+struct Ranking {
+    encounter:   RankedEncounter,
+    #[serde(rename = "totalKills", default)]
+    total_kills: u32,
+    #[serde(default)]
+    spec:        Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RankedEncounter {
+    id: u32,
+}
+
+/// What the lookup found about a character.
+#[derive(Debug, PartialEq)]
+struct WclCharacter {
+    name:          String,
+    class:         &'static str,
+    /// Spec most kills were logged as (Warcraft Logs spelling).
+    spec:          Option<String>,
+    /// Bosses of the tier, in the API's order.
+    encounter_ids: Vec<u32>,
+    killed:        usize,
+    difficulty:    u32,
+}
+
+fn parse_character(json: &str) -> Result<WclCharacter> {
+    let response: Response = serde_json::from_str(json)?;
+    if let Some(e) = response.errors.first() {
+        anyhow::bail!("{}", e.message);
+    }
+    let c = response.data
+        .and_then(|d| d.character_data.character)
+        .ok_or_else(|| anyhow::anyhow!("character not found on Warcraft Logs"))?;
+    let class = CLASSES.iter()
+        .find(|(id, _)| *id == c.class_id)
+        .map(|&(_, class)| class)
+        .ok_or_else(|| anyhow::anyhow!("unknown class ID {}", c.class_id))?;
+
+    let zone = c.zone_rankings.unwrap_or_default();
+    let mut kills_by_spec: BTreeMap<&str, u32> = BTreeMap::new();
+    for r in &zone.rankings {
+        if let Some(spec) = r.spec.as_deref().filter(|_| r.total_kills > 0) {
+            *kills_by_spec.entry(spec).or_default() += r.total_kills;
+        }
+    }
+    let spec = kills_by_spec.into_iter()
+        .max_by_key(|&(_, kills)| kills)
+        .map(|(spec, _)| spec.to_owned());
+
+    Ok(WclCharacter {
+        name: c.name,
+        class,
+        spec,
+        encounter_ids: zone.rankings.iter().map(|r| r.encounter.id).collect(),
+        killed:        zone.rankings.iter().filter(|r| r.total_kills > 0).count(),
+        difficulty:    zone.difficulty,
+    })
+}
+
+/// Profile key for a Warcraft Logs spec name ("BeastMastery" → "HUNTER/Beast Mastery").
+fn spec_key(class: &str, spec: &str) -> Option<String> {
+    let wanted = spec.replace(' ', "");
+    specs::list_all()
+        .into_iter()
+        .find(|s| s.class == class && s.spec.replace(' ', "").eq_ignore_ascii_case(&wanted))
+        .map(|s| s.key)
+}
+
+/// Realm name → Warcraft Logs server slug ("Twisting Nether" → "twisting-nether").
+fn server_slug(realm: &str) -> String {
+    realm.trim()
+        .to_lowercase()
+        .replace('\'', "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+// ---------------------------------------------------------------------------
+// HTTP
+// ---------------------------------------------------------------------------
+
+fn access_token(client_id: &str, client_secret: &str) -> Result<String> {
+    #[derive(Deserialize)]
+    struct Token {
+        access_token: String,
+    }
+    let body = ureq::post(TOKEN_URL)
+        .send_form(&[
+            ("grant_type",    "client_credentials"),
+            ("client_id",     client_id),
+            ("client_secret", client_secret),
+        ])?
+        .into_string()?;
+    Ok(serde_json::from_str::<Token>(&body)?.access_token)
+}
+
+fn fetch_character(cfg: &AppConfig, name: &str, realm: &str, region: &str) -> Result<WclCharacter> {
+    let token = access_token(&cfg.wcl_client_id, &cfg.wcl_client_secret)
+        .map_err(|e| anyhow::anyhow!("Warcraft Logs sign-in failed ({}) — check the API client ID and secret", e))?;
+    let request = serde_json::json!({
+        "query": QUERY,
+        "variables": { "name": name, "server": server_slug(realm), "region": region.to_uppercase() },
+    });
+    let body = ureq::post(API_URL)
+        .set("Authorization", &format!("Bearer {}", token))
+        .set("Content-Type", "application/json")
+        .send_string(&request.to_string())?
+        .into_string()?;
+    parse_character(&body)
+}
+
+// ---------------------------------------------------------------------------
+// Tauri command
+// ---------------------------------------------------------------------------
+
+/// Result of the quick-setup import.
+#[derive(Debug, Serialize)]
+pub struct QuickSetup {
+    /// The config with the character and spec applied (already saved).
+    pub config:            AppConfig,
+    pub class:             String,
+    /// Applied spec profile key; None when no kill named a spec we have.
+    pub spec_key:          Option<String>,
+    /// "5/8 Heroic"; empty when the character has no rankings this tier.
+    pub progress:          String,
+    pub encounters_loaded: usize,
+}
+
+/// Look a character up on Warcraft Logs and pre-fill the coaching setup.
+#[tauri::command]
+pub async fn import_wcl_character(
+    app:    AppHandle,
+    name:   String,
+    realm:  String,
+    region: String,
+) -> Result<QuickSetup, String> {
+    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let mut cfg = config::load_or_default(&config_dir).map_err(|e| e.to_string())?;
+    if cfg.wcl_client_id.is_empty() || cfg.wcl_client_secret.is_empty() {
+        return Err("Add your Warcraft Logs API client ID and secret first (warcraftlogs.com/api/clients).".to_owned());
+    }
+    if name.trim().is_empty() || realm.trim().is_empty() {
+        return Err("Enter the character name and realm.".to_owned());
+    }
+
+    let lookup = cfg.clone();
+    let (lookup_realm, lookup_region) = (realm.clone(), region.clone());
+    let character = tauri::async_runtime::spawn_blocking(move || {
+        fetch_character(&lookup, name.trim(), &lookup_realm, &lookup_region)
+    })
+    .await
+    .map_err(|e| format!("Task error: {}", e))?
+    .map_err(|e| format!("Warcraft Logs import failed: {}", e))?;
+
+    cfg.player_focus = format!("{}-{}", character.name, realm.trim().replace(' ', ""));
+    let spec_key = character.spec.as_deref().and_then(|s| spec_key(character.class, s));
+    if let Some(key) = &spec_key {
+        config::select_spec(&mut cfg, key.clone())?;
+    }
+    config::save(&cfg, &config_dir).map_err(|e| e.to_string())?;
+
+    let encounters_loaded = if cfg.encounter_downloads && !character.encounter_ids.is_empty() {
+        let ids = character.encounter_ids.clone();
+        let dir = config_dir.clone();
+        tauri::async_runtime::spawn_blocking(move || encounter_download::prefetch(&dir, &ids))
+            .await
+            .unwrap_or(0)
+    } else {
+        0
+    };
+
+    let progress = if character.encounter_ids.is_empty() {
+        String::new()
+    } else {
+        format!("{}/{} {}", character.killed, character.encounter_ids.len(), difficulty_name(character.difficulty))
+            .trim_end()
+            .to_owned()
+    };
+    tracing::info!(
+        "Quick setup from Warcraft Logs: {} ({}, {:?}), {} — {} encounter file(s) loaded",
+        cfg.player_focus, character.class, spec_key, progress, encounters_loaded
+    );
+    Ok(QuickSetup { config: cfg, class: character.class.to_owned(), spec_key, progress, encounters_loaded })
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_class_spec_and_progress() {
+        let json = r#"{"data":{"characterData":{"character":{
+            "name":"Stonebraid","classID":3,
+            "zoneRankings":{"difficulty":4,"rankings":[
+                {"encounter":{"id":2902,"name":"Ulgrax the Devourer"},"totalKills":6,"spec":"BeastMastery"},
+                {"encounter":{"id":2917,"name":"The Bloodbound Horror"},"totalKills":2,"spec":"Marksmanship"},
+                {"encounter":{"id":2898,"name":"Sikran"},"totalKills":0,"spec":null}
+            ]}
+        }}}}"#;
+        let c = parse_character(json).expect("parses");
+        assert_eq!(c.class, "HUNTER");
+        assert_eq!(c.spec.as_deref(), Some("BeastMastery"));
+        assert_eq!(c.encounter_ids, vec![2902, 2917, 2898]);
+        assert_eq!((c.killed, difficulty_name(c.difficulty)), (2, "Heroic"));
+        assert_eq!(spec_key(c.class, "BeastMastery").as_deref(), Some("HUNTER/Beast Mastery"));
+
+        let missing = r#"{"data":{"characterData":{"character":null}}}"#;
+        assert!(parse_character(missing).is_err());
+        assert_eq!(server_slug("Twisting Nether"), "twisting-nether");
+        assert_eq!(server_slug("Mal'Ganis"), "malganis");
+    }
+}
@@ -0,0 +1,176 @@
+/// Wipe forecast — a live estimate of how likely the current encounter pull
+/// is to end in a wipe, for raid leads who want to call a dead pull early.
+///
+/// The model is a handful of numbers learned from the stored pulls of the
+/// same encounter (db `encounter_pulls`, loaded at ENCOUNTER_START):
+///   - the smoothed share of wipes, as the starting point
+///   - the most group deaths a kill survived
+///   - the highest group avoidable-hit rate of a kill
+///   - the boss damage per second that was enough: the slowest kill's, or
+///     without a kill the best attempt's
+///
+/// The live pull is compared with each and the differences are added up in
+/// log-odds, so no single signal decides alone.  Avoidable rate and pace
+/// only count once the pull has settled (SETTLE_MS).  The estimate is a
+/// heuristic, not a calibrated probability: the overlay shows it dimmed
+/// next to the pull clock.
+use crate::db::PullSample;
+
+/// Stored pulls needed before there is a forecast.
+const MIN_SAMPLES: usize = 3;
+/// Pulls shorter than this were reset on purpose and teach nothing.
+const MIN_PULL_MS: u64 = 10_000;
+/// Rates are noise before this much of the pull.
+const SETTLE_MS: u64 = 20_000;
+/// A group is assumed at least this large when few members took damage yet.
+const MIN_GROUP: u32 = 5;
+
+/// Log-odds per death beyond the most a kill survived.
+const DEATH_WEIGHT: f32 = 1.2;
+/// Log-odds for the whole group dead, without a kill to compare with.
+const DEATH_SHARE_WEIGHT: f32 = 5.0;
+const AVOIDABLE_WEIGHT: f32 = 0.8;
+const PACE_WEIGHT: f32 = 3.0;
+
+/// The encounter pull so far.
+#[derive(Debug, Clone, Default)]
+pub struct LiveProgress {
+    /// Since ENCOUNTER_START.
+    pub elapsed_ms:     u64,
+    pub group_deaths:   u32,
+    /// Group players seen this pull.
+    pub group_size:     u32,
+    pub avoidable_hits: u32,
+    /// Everyone's damage on the boss units.
+    pub boss_damage:    u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WipeModel {
+    /// (wipes + 1) / (pulls + 2).
+    prior:             f32,
+    /// Most group deaths a kill survived; None without a kill.
+    kill_deaths:       Option<u32>,
+    /// Highest group avoidable hits per minute of a kill.
+    kill_avoidable_pm: Option<f32>,
+    /// Boss damage per second that was enough (see module docs).
+    reference_dps:     Option<f32>,
+}
+
+fn per_minute(count: u32, ms: u64) -> f32 {
+    count as f32 * 60_000.0 / ms.max(1) as f32
+}
+
+fn boss_dps(damage: u64, ms: u64) -> f32 {
+    (damage as f64 * 1_000.0 / ms.max(1) as f64) as f32
+}
+
+impl WipeModel {
+    /// Learn from an encounter's stored pulls; None with too few of them.
+    pub fn train(samples: &[PullSample]) -> Option<Self> {
+        let samples: Vec<&PullSample> = samples.iter().filter(|s| s.duration_ms >= MIN_PULL_MS).collect();
+        if samples.len() < MIN_SAMPLES {
+            return None;
+        }
+        let kills: Vec<&PullSample> = samples.iter().copied().filter(|s| s.kill).collect();
+        let wipes = samples.len() - kills.len();
+        let dps = |s: &&PullSample| s.boss_damage.filter(|&d| d > 0).map(|d| boss_dps(d, s.duration_ms));
+        let reference_dps = if kills.is_empty() {
+            samples.iter().filter_map(dps).reduce(f32::max)
+        } else {
+            kills.iter().filter_map(dps).reduce(f32::min)
+        };
+        Some(Self {
+            prior:             (wipes as f32 + 1.0) / (samples.len() as f32 + 2.0),
+            kill_deaths:       kills.iter().map(|k| k.group_deaths).max(),
+            kill_avoidable_pm: kills.iter()
+                .filter_map(|k| k.avoidable_hits.map(|h| per_minute(h, k.duration_ms)))
+                .reduce(f32::max),
+            reference_dps,
+        })
+    }
+
+    /// Chance the pull ends in a wipe, 0.0–1.0.
+    pub fn wipe_chance(&self, live: &LiveProgress) -> f32 {
+        let mut logit = (self.prior / (1.0 - self.prior)).ln();
+
+        let deaths = live.group_deaths as f32;
+        logit += match self.kill_deaths {
+            Some(survived) => DEATH_WEIGHT * (deaths - survived as f32).max(-1.0),
+            None           => DEATH_SHARE_WEIGHT * deaths / live.group_size.max(MIN_GROUP) as f32,
+        };
+
+        if live.elapsed_ms >= SETTLE_MS {
+            if let Some(kill_pm) = self.kill_avoidable_pm {
+                let ratio = per_minute(live.avoidable_hits, live.elapsed_ms) / kill_pm.max(1.0);
+                logit += AVOIDABLE_WEIGHT * (ratio - 1.0).clamp(-1.0, 2.0);
+            }
+            if let Some(reference) = self.reference_dps.filter(|_| live.boss_damage > 0) {
+                let pace = boss_dps(live.boss_damage, live.elapsed_ms) / reference;
+                logit += PACE_WEIGHT * (1.0 - pace).clamp(-0.5, 1.0);
+            }
+        }
+        1.0 / (1.0 + (-logit).exp())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(kill: bool, duration_s: u64, group_deaths: u32, avoidable_hits: u32, boss_damage: u64) -> PullSample {
+        PullSample {
+            kill,
+            duration_ms:    duration_s * 1_000,
+            group_deaths,
+            avoidable_hits: Some(avoidable_hits),
+            boss_damage:    Some(boss_damage),
+        }
+    }
+
+    fn live(elapsed_s: u64, group_deaths: u32, avoidable_hits: u32, boss_damage: u64) -> LiveProgress {
+        LiveProgress { elapsed_ms: elapsed_s * 1_000, group_deaths, group_size: 20, avoidable_hits, boss_damage }
+    }
+
+    #[test]
+    fn deaths_hits_and_pace_raise_the_wipe_chance() {
+        assert_eq!(WipeModel::train(&[sample(false, 60, 20, 5, 1_000), sample(false, 5, 0, 0, 0)]), None);
+
+        let model = WipeModel::train(&[
+            sample(false, 120, 20, 40, 100_000_000),
+            sample(false, 200, 20, 30, 180_000_000),
+            sample(true, 300, 2, 10, 300_000_000), // 1M boss DPS, 2 avoidable hits a minute
+            sample(false, 4, 0, 0, 0),             // reset, ignored
+        ]).unwrap();
+        assert_eq!(model.kill_deaths, Some(2));
+        assert_eq!(model.reference_dps, Some(1_000_000.0));
+
+        let on_pace = model.wipe_chance(&live(60, 0, 2, 60_000_000));
+        assert!(on_pace < 0.5, "{}", on_pace);
+        let deaths = model.wipe_chance(&live(60, 5, 2, 60_000_000));
+        assert!(deaths > 0.9, "{}", deaths);
+        let sloppy = model.wipe_chance(&live(60, 0, 8, 60_000_000));
+        assert!(sloppy > on_pace, "{} vs {}", sloppy, on_pace);
+        let slow = model.wipe_chance(&live(60, 0, 2, 30_000_000));
+        assert!(slow > 0.6, "{}", slow);
+        // Early in the pull only deaths count.
+        assert_eq!(model.wipe_chance(&live(10, 0, 8, 1)), model.wipe_chance(&live(10, 0, 0, 0)));
+    }
+
+    #[test]
+    fn without_a_kill_deaths_count_against_group_size() {
+        let model = WipeModel::train(&[
+            sample(false, 100, 20, 20, 50_000_000),
+            sample(false, 150, 20, 20, 90_000_000),
+            sample(false, 200, 20, 20, 100_000_000),
+        ]).unwrap();
+        assert_eq!(model.kill_deaths, None);
+        assert_eq!(model.reference_dps, Some(600_000.0));
+        let clean = model.wipe_chance(&live(30, 0, 0, 20_000_000));
+        let half_dead = model.wipe_chance(&live(30, 10, 0, 20_000_000));
+        assert!(clean < 0.8 && half_dead > 0.9, "{} {}", clean, half_dead);
+    }
+}
@@ -10,16 +10,17 @@ export function ConnectionStatus({ status }: Props) {
   return (
     <div className={styles.wrap}>
       <Pill label="LOG" on={status.log_tailing} />
+      {status.replaying && <Pill label="REPLAY" on text={status.replaying} />}
     </div>
   );
 }
 
-function Pill({ label, on }: { label: string; on: boolean }) {
+function Pill({ label, on, text }: { label: string; on: boolean; text?: string }) {
   return (
     <div className={`${styles.pill} ${on ? styles.on : ""}`}>
       <span className={styles.led} />
       <span className={styles.label}>{label}</span>
-      <span className={styles.state}>{on ? "Connected" : "Disconnected"}</span>
+      <span className={styles.state}>{text ?? (on ? "Connected" : "Disconnected")}</span>
     </div>
   );
 }
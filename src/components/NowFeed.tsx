@@ -1,12 +1,35 @@
 // The 1–3 prioritized coaching cards panel.
 // Matches the .now / .alert / .sev design from the mockup exactly.
-import type { AdviceEvent } from "../types/events";
+import type { AdviceEvent, HintDirection, PositionHint } from "../types/events";
 import styles from "./NowFeed.module.css";
 
 interface Props {
   advice: AdviceEvent[];
 }
 
+const HINT_ARROWS: Record<HintDirection, string> = {
+  out:    "↗",
+  in:     "↙",
+  behind: "↓",
+  front:  "↑",
+  spread: "↔",
+  stack:  "→←",
+};
+
+function HintBadge({ hint }: { hint: PositionHint }) {
+  const label = [
+    hint.direction,
+    hint.distance_yd != null ? `${hint.distance_yd}yd` : null,
+    hint.zone,
+  ].filter(Boolean).join(" · ");
+  return (
+    <div className={styles.hint}>
+      <span className={styles.arrow}>{HINT_ARROWS[hint.direction]}</span>
+      <span>{label}</span>
+    </div>
+  );
+}
+
 export function NowFeed({ advice }: Props) {
   if (advice.length === 0) {
     return (
@@ -19,11 +42,17 @@ export function NowFeed({ advice }: Props) {
   return (
     <div className={styles.feed}>
       {advice.map((a) => (
-        <div key={a.key} className={styles.card}>
+        <div key={a.key} className={a.priority === "urgent" ? `${styles.card} ${styles.urgent}` : styles.card}>
           <div className={`${styles.sev} ${styles[a.severity]}`} />
           <div className={styles.body}>
-            <div className={styles.title}>{a.title}</div>
+            <div className={styles.title}>
+              {a.priority === "urgent" && <span className={styles.urgentTag}>NOW</span>}
+              {a.player && <span className={styles.playerTag}>{a.player}</span>}
+              {a.title}
+              {!!a.escalation && <span className={styles.repeatTag}>×{a.repeat}</span>}
+            </div>
             <div className={styles.message}>{a.message}</div>
+            {a.hint && <HintBadge hint={a.hint} />}
             {a.kv.length > 0 && (
               <div className={styles.kvs}>
                 {a.kv.map(([k, v]) => (
@@ -20,6 +20,7 @@ const PANEL_LABELS: Record<string, string> = {
   now_feed:     "Now Feed",
   timeline:     "Timeline",
   stat_widgets: "Stats",
+  readiness:    "Ready Check",
 };
 
 const PANEL_COLORS: Record<string, string> = {
@@ -27,6 +28,7 @@ const PANEL_COLORS: Record<string, string> = {
   now_feed:     "rgba( 43,213,118,0.45)",
   timeline:     "rgba(255,204,102,0.45)",
   stat_widgets: "rgba(255, 92,119,0.45)",
+  readiness:    "rgba( 92,184,255,0.45)",
 };
 
 interface Props {
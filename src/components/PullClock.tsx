@@ -1,14 +1,26 @@
 // Pull timer — shows MM:SS elapsed since pull start.
 // Shows "-- : --" when not in combat.
-// Optionally shows the active encounter name below the timer.
+// Optionally shows the active encounter name below the timer (the Mythic+
+// key, "Ara-Kara, City of Echoes +12", on trash), and — when the encounter
+// has a timeline — the phase and a next-event countdown ("P2 — Breath in 00:08").
+// On encounter pulls with enough history, a dimmed wipe estimate sits next to
+// the timer; it turns red once the pull looks lost.
+import type { KeystoneInfo, ScheduledEvent } from "../types/events";
 import styles from "./PullClock.module.css";
 
 interface Props {
   elapsedMs:      number;
   inCombat:       boolean;
   encounterName?: string | null;
+  phaseLabel?:    string | null;
+  nextEvent?:     ScheduledEvent | null;
+  keystone?:      KeystoneInfo | null;
+  wipePct?:       number | null;
 }
 
+/** Wipe estimate at which the pull is shown as lost. */
+const WIPE_CALL_PCT = 85;
+
 function fmt(ms: number): string {
   const totalS = Math.floor(ms / 1000);
   const m = Math.floor(totalS / 60);
@@ -16,15 +28,32 @@ function fmt(ms: number): string {
   return `${String(m).padStart(2, "0")}:${String(s).padStart(2, "0")}`;
 }
 
-export function PullClock({ elapsedMs, inCombat, encounterName }: Props) {
+export function PullClock({ elapsedMs, inCombat, encounterName, phaseLabel, nextEvent, keystone, wipePct }: Props) {
+  const context = encounterName ?? (keystone ? `${keystone.dungeon} +${keystone.level}` : null);
+  const upcoming = inCombat && nextEvent
+    ? `${nextEvent.name} in ${fmt(nextEvent.seconds * 1000)}`
+    : null;
+  const timelineLine = [inCombat ? phaseLabel : null, upcoming].filter(Boolean).join(" — ");
+
   return (
     <div className={`${styles.clock} ${inCombat ? styles.active : ""}`}>
       <div className={styles.row}>
         <span className={styles.label}>PULL</span>
         <span className={styles.time}>{inCombat ? fmt(elapsedMs) : "-- : --"}</span>
+        {inCombat && wipePct != null && (
+          <span
+            className={`${styles.wipe} ${wipePct >= WIPE_CALL_PCT ? styles.wipeCall : ""}`}
+            title="Estimated wipe chance from your earlier pulls of this boss"
+          >
+            WIPE {wipePct.toFixed(0)}%
+          </span>
+        )}
       </div>
-      {encounterName && (
-        <div className={styles.encounter}>{encounterName}</div>
+      {context && (
+        <div className={styles.encounter}>{context}</div>
+      )}
+      {timelineLine && (
+        <div className={styles.next}>{timelineLine}</div>
       )}
     </div>
   );
@@ -1,18 +1,27 @@
 // End-of-pull debrief panel — shows a quick summary on the overlay after each pull.
 // Auto-dismisses after DISPLAY_MS. Replaces itself immediately if a new pull ends.
+// A recalled debrief (show_last_debrief hotkey) is sticky: it stays up until
+// combat starts again.
 //
 // Shows:
 //   - Pull # and outcome (Kill / Wipe)
 //   - Pull duration (MM:SS)
-//   - Avoidable hits, interrupts, total advice fired, GCD gap events
+//   - Avoidable hits, interrupts, total advice fired, GCD gap events (by cause)
+//   - Dodge rate per avoidable mechanic (when the encounter defines them)
+//   - Switch time onto kill-priority adds (when any appeared)
+//   - Rotation priority spells cast far too rarely ("1 cast in 03:00")
+//   - Squad mode: the player's rank in avoidable damage taken + top of the party
+//   - Training dummy: the practice score, opener, overcap and CD cadence instead
 import React, { useEffect, useState } from "react";
-import type { PullDebrief } from "../types/events";
+import type { GapCauses, PracticeReport, PullDebrief } from "../types/events";
 import styles from "./PullDebriefPanel.module.css";
 
 const DISPLAY_MS = 10_000; // auto-dismiss after 10 seconds
+const LEADERBOARD_ROWS = 5;
 
 interface Props {
-  debrief: PullDebrief | null;
+  debrief:  PullDebrief | null;
+  inCombat: boolean;
 }
 
 function fmtElapsed(ms: number): string {
@@ -22,17 +31,75 @@ function fmtElapsed(ms: number): string {
   return `${String(m).padStart(2, "0")}:${String(s).padStart(2, "0")}`;
 }
 
-export function PullDebriefPanel({ debrief }: Props) {
+function fmtAmount(n: number): string {
+  if (n >= 1_000_000) return `${(n / 1_000_000).toFixed(1)}M`;
+  if (n >= 1_000) return `${Math.round(n / 1_000)}k`;
+  return n.toString();
+}
+
+function pctColor(pct: number, good: number, bad: number): string | undefined {
+  return pct >= good ? "var(--good)" : pct < bad ? "var(--warn)" : undefined;
+}
+
+type Row = { label: string; value: string; color?: string };
+
+/** "3 (2 movement, 1 idle)" — the count alone when no causes were recorded. */
+function fmtGaps(count: number, causes?: GapCauses): string {
+  if (!causes || count === 0) return count.toString();
+  const parts = ([
+    [causes.movement,      "movement"],
+    [causes.silence,       "silence"],
+    [causes.crowd_control, "CC"],
+    [causes.idle,          "idle"],
+  ] as const).filter(([n]) => n > 0).map(([n, label]) => `${n} ${label}`);
+  return parts.length ? `${count} (${parts.join(", ")})` : count.toString();
+}
+
+function practiceRows(debrief: PullDebrief, p: PracticeReport): Row[] {
+  return [
+    { label: "Pull time",   value: fmtElapsed(debrief.pull_elapsed_ms) },
+    { label: "Score",       value: `${p.last_score.toFixed(0)}/100`,
+      color: pctColor(p.last_score, 85, 60) },
+    ...(p.pulls > 1 ? [{
+      label: `Session (${p.pulls} pulls)`,
+      value: `${p.score.toFixed(0)} avg, ${p.best_score.toFixed(0)} best`,
+    }] : []),
+    ...(p.opener_pct != null ? [{
+      label: "Opener",      value: `${p.opener_pct.toFixed(0)}%`, color: pctColor(p.opener_pct, 100, 75),
+    }] : []),
+    ...(p.overcap_pct != null ? [{
+      label: "Overcapped",  value: `${p.overcap_pct.toFixed(0)}% of casts`,
+      color: p.overcap_pct <= 5 ? "var(--good)" : p.overcap_pct > 15 ? "var(--warn)" : undefined,
+    }] : []),
+    { label: "Active time", value: `${debrief.active_time_pct.toFixed(0)}%`,
+      color: pctColor(debrief.active_time_pct, 90, 75) },
+    { label: "GCD gaps",    value: fmtGaps(debrief.gcd_gap_count, debrief.gcd_gap_causes),
+      color: debrief.gcd_gap_count > 0 ? "var(--warn)" : undefined },
+    ...p.cooldowns.map((c) => ({
+      label: c.spell_name,
+      value: `${c.uses}×` + (c.cadence_pct != null ? ` (${c.cadence_pct.toFixed(0)}% on time)` : ""),
+      color: c.cadence_pct != null ? pctColor(c.cadence_pct, 90, 70) : undefined,
+    })),
+  ];
+}
+
+export function PullDebriefPanel({ debrief, inCombat }: Props) {
   const [visible, setVisible] = useState(false);
 
-  // Show on new debrief, auto-hide after DISPLAY_MS
+  // Show on new debrief, auto-hide after DISPLAY_MS unless sticky
   useEffect(() => {
     if (!debrief) return;
     setVisible(true);
+    if (debrief.sticky) return;
     const id = setTimeout(() => setVisible(false), DISPLAY_MS);
     return () => clearTimeout(id);
   }, [debrief]);
 
+  // A sticky debrief goes away when the next pull starts
+  useEffect(() => {
+    if (inCombat && debrief?.sticky) setVisible(false);
+  }, [inCombat, debrief]);
+
   if (!visible || !debrief) return null;
 
   const isKill    = debrief.outcome === "kill";
@@ -40,7 +107,11 @@ export function PullDebriefPanel({ debrief }: Props) {
   const outcomeColor = isKill ? "var(--good)" : isWipe ? "var(--bad)" : "var(--muted)";
   const outcomeLabel = isKill ? "KILL" : isWipe ? "WIPE" : "UNKNOWN";
 
-  const rows: { label: string; value: string; color?: string }[] = [
+  const board = debrief.damage_leaderboard ?? [];
+  const playerRank = board.findIndex((m) => m.is_player);
+
+  const practice = debrief.practice ?? null;
+  const rows: Row[] = practice ? practiceRows(debrief, practice) : [
     { label: "Pull time",     value: fmtElapsed(debrief.pull_elapsed_ms) },
     {
       label: "Avoidable hits",
@@ -52,18 +123,138 @@ export function PullDebriefPanel({ debrief }: Props) {
       value: debrief.interrupt_count.toString(),
       color: debrief.interrupt_count > 0 ? "var(--good)" : undefined,
     },
-    { label: "GCD gaps",      value: debrief.gcd_gap_count.toString(),
+    ...(debrief.interrupt_efficiency != null ? [{
+      label: "Kick efficiency",
+      value: `${debrief.interrupt_efficiency.toFixed(0)}%` +
+        (debrief.session_interrupt_efficiency != null
+          ? ` (session ${debrief.session_interrupt_efficiency.toFixed(0)}%)` : ""),
+      color: debrief.interrupt_efficiency >= 80 ? "var(--good)"
+        : debrief.interrupt_efficiency < 50 ? "var(--bad)" : "var(--warn)",
+    }] : []),
+    { label: "Active time",   value: `${debrief.active_time_pct.toFixed(0)}%`,
+      color: debrief.active_time_pct >= 90 ? "var(--good)" : debrief.active_time_pct < 75 ? "var(--warn)" : undefined },
+    { label: "GCD gaps",      value: fmtGaps(debrief.gcd_gap_count, debrief.gcd_gap_causes),
       color: debrief.gcd_gap_count > 0 ? "var(--warn)" : undefined },
     { label: "Advice fired",  value: debrief.total_advice_fired.toString() },
+    ...(debrief.boss_damage ? [{
+      label: `On ${debrief.boss_damage.boss_name || "boss"}`,
+      value: `${debrief.boss_damage.share_pct.toFixed(0)}% of damage`,
+    }] : []),
+    ...(debrief.target_switch ? [{
+      label: "Add switch",
+      value: `${(debrief.target_switch.avg_ms / 1000).toFixed(1)}s avg, ` +
+        `${(debrief.target_switch.worst_ms / 1000).toFixed(1)}s worst (${debrief.target_switch.switches})`,
+      color: debrief.target_switch.worst_ms > 6_000 ? "var(--warn)" : undefined,
+    }] : []),
+    ...(debrief.burst ? [{
+      label: "In burst windows",
+      value: `${debrief.burst.in_window_pct.toFixed(0)}% of damage (${debrief.burst.windows})`,
+      color: pctColor(debrief.burst.in_window_pct, 50, 30),
+    }, {
+      label: "Peak DPS",
+      value: `${fmtAmount(debrief.burst.peak_dps_10s)} 10s, ${fmtAmount(debrief.burst.peak_dps_30s)} 30s` +
+        (debrief.burst.peak_in_window ? "" : " (outside CDs)"),
+      color: debrief.burst.peak_in_window ? undefined : "var(--warn)",
+    }] : []),
+    ...(debrief.melee_uptime ? [{
+      label: "Melee uptime",
+      value: `${debrief.melee_uptime.uptime_pct.toFixed(0)}%` +
+        (debrief.melee_uptime.longest_gap_ms > 0
+          ? ` (longest gap ${(debrief.melee_uptime.longest_gap_ms / 1000).toFixed(0)}s)` : ""),
+      color: pctColor(debrief.melee_uptime.uptime_pct, 90, 75),
+    }] : []),
+    ...((debrief.deaths ?? []).length > 0 ? [{
+      label: "Deaths",
+      // First few in order: who went down first is how the pull fell apart.
+      value: (debrief.deaths ?? []).slice(0, 4)
+        .map((d) => `${d.name.split("-")[0]} ${fmtElapsed(d.elapsed_ms)}`)
+        .join(", ") +
+        ((debrief.deaths ?? []).length > 4 ? ` +${(debrief.deaths ?? []).length - 4}` : "") +
+        ((debrief.battle_res ?? []).length > 0 ? ` · ${(debrief.battle_res ?? []).length} b-res` : ""),
+      color: "var(--bad)",
+    }] : []),
+    ...(debrief.consumables && debrief.consumables.potions + debrief.consumables.healthstones > 0 ? [{
+      label: "Consumables",
+      value: [
+        debrief.consumables.potions > 0 ? `${debrief.consumables.potions} potion${debrief.consumables.potions === 1 ? "" : "s"}` : "",
+        debrief.consumables.healthstones > 0 ? `${debrief.consumables.healthstones} healthstone${debrief.consumables.healthstones === 1 ? "" : "s"}` : "",
+      ].filter(Boolean).join(", "),
+    }] : []),
+    ...(debrief.prepull ? [{
+      label: "Pre-pull",
+      value: debrief.prepull.missing.length > 0
+        ? `No ${debrief.prepull.missing.join(", no ")}`
+        : ["potion", ...debrief.prepull.precasts].join(", "),
+      color: debrief.prepull.missing.length > 0 ? "var(--warn)" : "var(--good)",
+    }] : []),
+    ...(debrief.cancelled_casts ? [{
+      label: "Cancelled casts",
+      value: `${debrief.cancelled_casts.count} of ${debrief.cancelled_casts.started} ` +
+        `(${(debrief.cancelled_casts.lost_ms / 1000).toFixed(1)}s lost) · ${debrief.cancelled_casts.spells.join(", ")}`,
+      color: "var(--warn)",
+    }] : []),
+    ...((debrief.failed_casts ?? []).length > 0 ? [{
+      label: "Failed casts",
+      value: (debrief.failed_casts ?? []).map((f) => `${f.count} ${f.reason}`).join(", "),
+      color: (debrief.failed_casts ?? []).some((f) => f.reason === "movement") ? "var(--warn)" : undefined,
+    }] : []),
+    ...(debrief.friendly_fire ? [{
+      label: "Friendly fire",
+      value: [
+        debrief.friendly_fire.self_inflicted > 0 ? `${fmtAmount(debrief.friendly_fire.self_inflicted)} self` : "",
+        debrief.friendly_fire.from_party > 0 ? `${fmtAmount(debrief.friendly_fire.from_party)} party` : "",
+      ].filter(Boolean).join(", ") + ` · ${debrief.friendly_fire.spells.join(", ")}` +
+        (debrief.friendly_fire.counted ? " (counted as avoidable)" : ""),
+    }] : []),
+    ...(debrief.healing ? [{
+      label: "Healing split",
+      value: `${debrief.healing.tank_pct.toFixed(0)}% tanks, ${debrief.healing.party_pct.toFixed(0)}% party, ` +
+        `${debrief.healing.self_pct.toFixed(0)}% self`,
+    }, {
+      label: "Overhealing",
+      value: `${debrief.healing.overheal_pct.toFixed(0)}% of ${fmtAmount(debrief.healing.effective)} effective`,
+      color: debrief.healing.overheal_pct > 40 ? "var(--warn)" : undefined,
+    }] : []),
+    ...(debrief.cast_rates ?? []).filter((r) => r.underused).map((r) => ({
+      label: `Underused ${r.spell_name}`,
+      value: `${r.casts} cast${r.casts === 1 ? "" : "s"} in ${fmtElapsed(debrief.pull_elapsed_ms)}`,
+      color: "var(--warn)",
+    })),
+    ...(debrief.dodge_stats ?? []).map((d) => ({
+      label: `Dodged ${d.spell_name}`,
+      value: `${d.avoided}/${d.avoided + d.hit} (${d.dodge_pct.toFixed(0)}%)`,
+      color: d.hit === 0 ? "var(--good)" : d.dodge_pct < 50 ? "var(--bad)" : "var(--warn)",
+    })),
+    ...(board.length > 1 && playerRank >= 0 ? [{
+      label: "Avoidable taken",
+      value: `You: #${playerRank + 1} of ${board.length}`,
+      color: playerRank === 0 && board[0].avoidable_taken > 0 ? "var(--bad)" : undefined,
+    }] : []),
+    ...(board.length > 1 ? board.slice(0, LEADERBOARD_ROWS).map((m, i) => ({
+      label: `#${i + 1} ${m.name || "?"}`,
+      value: `${fmtAmount(m.avoidable_taken)} avoidable / ${fmtAmount(m.damage_taken)}`,
+      color: m.is_player ? "var(--accent)" : undefined,
+    })) : []),
   ];
 
   return (
     <div className={styles.panel}>
       <div className={styles.header}>
-        <span className={styles.pullNum}>Pull #{debrief.pull_number}</span>
-        <span className={styles.outcome} style={{ color: outcomeColor }}>
-          {outcomeLabel}
-        </span>
+        {practice ? (
+          <>
+            <span className={styles.pullNum}>Practice #{practice.pulls}</span>
+            <span className={styles.outcome} style={{ color: "var(--accent)" }}>
+              DUMMY
+            </span>
+          </>
+        ) : (
+          <>
+            <span className={styles.pullNum}>Pull #{debrief.pull_number}</span>
+            <span className={styles.outcome} style={{ color: outcomeColor }}>
+              {outcomeLabel}
+            </span>
+          </>
+        )}
       </div>
 
       <div className={styles.grid}>
@@ -0,0 +1,41 @@
+// Pre-pull readiness checklist — shown between pulls from the readiness
+// payload of the polled state snapshot (readiness.rs).
+//
+// One row per item: a status mark, the label and the detail
+// ("Flask of Alchemical Chaos", "may be recovering: Avenging Wrath").
+// Hidden in combat, when the engine sends no checklist.
+import type { Readiness, ReadinessItem } from "../types/events";
+import styles from "./ReadinessPanel.module.css";
+
+interface Props {
+  readiness?: Readiness | null;
+}
+
+const MARKS: Record<ReadinessItem["status"], string> = {
+  ok:      "✓",
+  warn:    "!",
+  unknown: "?",
+};
+
+export function ReadinessPanel({ readiness }: Props) {
+  if (!readiness) return null;
+  const warnings = readiness.items.filter((i) => i.status === "warn").length;
+
+  return (
+    <div className={styles.panel}>
+      <div className={styles.header}>
+        <span className={styles.title}>Ready check</span>
+        <span className={readiness.ready ? styles.ready : styles.notReady}>
+          {readiness.ready ? "READY" : `${warnings} TO CHECK`}
+        </span>
+      </div>
+      {readiness.items.map((item) => (
+        <div key={item.key} className={`${styles.item} ${styles[item.status]}`}>
+          <span className={styles.mark}>{MARKS[item.status]}</span>
+          <span className={styles.label}>{item.label}</span>
+          <span className={styles.detail}>{item.detail}</span>
+        </div>
+      ))}
+    </div>
+  );
+}
@@ -1,17 +1,33 @@
-// Three spark stat boxes: GCD gap, avoidable hits, and interrupts.
+// Spark stat boxes: GCD gap, avoidable hits, interrupts and throughput.
 import styles from "./StatWidgets.module.css";
 
 interface Props {
   gcdGapMs:       number;
   avoidableCount: number;
   interruptCount: number;
+  /** Rolling 10s and pull-average damage / effective healing per second. */
+  dps10s:         number;
+  hps10s:         number;
+  pullDps:        number;
+  pullHps:        number;
 }
 
-export function StatWidgets({ gcdGapMs, avoidableCount, interruptCount }: Props) {
+// 1234 -> "1.2k", 1234567 -> "1.23M"
+function shortAmount(v: number): string {
+  if (v >= 1_000_000) return `${(v / 1_000_000).toFixed(2)}M`;
+  if (v >= 1_000)     return `${(v / 1_000).toFixed(1)}k`;
+  return v.toFixed(0);
+}
+
+export function StatWidgets({
+  gcdGapMs, avoidableCount, interruptCount, dps10s, hps10s, pullDps, pullHps,
+}: Props) {
   const gapS    = (gcdGapMs / 1000).toFixed(1);
   const gapBad  = gcdGapMs >= 2500;
   const hitsBad = avoidableCount >= 2;
   const intGood = interruptCount > 0;
+  // Healers see HPS: whichever the pull was mostly spent on.
+  const healing = pullHps > pullDps;
 
   return (
     <div className={styles.row}>
@@ -27,6 +43,12 @@ export function StatWidgets({ gcdGapMs, avoidableCount, interruptCount }: Props)
         <div className={styles.big}>{interruptCount}</div>
         <div className={styles.small}>Interrupts</div>
       </div>
+      <div className={styles.spark}>
+        <div className={styles.big}>{shortAmount(healing ? hps10s : dps10s)}</div>
+        <div className={styles.small}>
+          {healing ? "HPS" : "DPS"} · avg {shortAmount(healing ? pullHps : pullDps)}
+        </div>
+      </div>
     </div>
   );
 }
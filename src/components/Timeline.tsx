@@ -14,10 +14,10 @@ export function Timeline({ advice, windowMs }: Props) {
 
   const ticks = useMemo(() => {
     return advice
-      .filter((a) => now - a.timestamp_ms < windowMs)
+      .filter((a) => now - a.wallclock_ms < windowMs)
       .map((a) => ({
         key:    a.key + a.timestamp_ms,
-        xPct:   ((a.timestamp_ms - (now - windowMs)) / windowMs) * 100,
+        xPct:   ((a.wallclock_ms - (now - windowMs)) / windowMs) * 100,
         height: a.severity === "bad" ? 80 : a.severity === "warn" ? 55 : 35,
         sev:    a.severity,
       }));
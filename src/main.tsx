@@ -3,19 +3,36 @@
 import React, { useState, useEffect, useCallback, useRef } from "react";
 import { createRoot } from "react-dom/client";
 import { invoke }     from "@tauri-apps/api/core";
-import { open }       from "@tauri-apps/plugin-dialog";
+import { open, save as saveDialog } from "@tauri-apps/plugin-dialog";
 import { ConnectionStatus }    from "./components/ConnectionStatus";
 import { OverlayLayoutEditor } from "./components/OverlayLayoutEditor";
 import { useTauriEvents }      from "./hooks/useTauriEvents";
 import type {
+  AdviceCooldowns,
   AppConfig,
   AudioCue,
   AdviceEvent,
   ConnectionStatus as ConnStatus,
+  CountdownConfig,
+  CrashReport,
+  DodgeTrendRow,
+  HardwareCue,
+  HotkeyConfig,
+  MechanicStat,
+  NumberFormat,
   PanelPosition,
+  PersonalRecord,
+  PresetListing,
   PullHistoryRow,
+  QuickSetup,
+  RecoachResult,
+  ReplaySpeed,
+  ReplayStatus,
+  SessionJournal,
   SpecInfo,
   StateSnapshot,
+  StoredPracticeSession,
+  StrategyPreset,
   UpdateInfo,
   WtfCharacter,
 } from "./types/events";
@@ -26,6 +43,9 @@ import "./styles/settings.css";
 // ---------------------------------------------------------------------------
 type Tab = "home" | "livefeed" | "audio" | "hotkeys" | "history";
 
+// Mirrors config::AdviceCooldowns::default() for configs saved before it existed.
+const DEFAULT_ADVICE_COOLDOWNS: AdviceCooldowns = { bad_s: 8, warn_s: 12, good_s: 20, rules: {} };
+
 // ---------------------------------------------------------------------------
 // Root settings app
 // ---------------------------------------------------------------------------
@@ -34,12 +54,16 @@ function SettingsApp() {
   const [config, setConfig]           = useState<AppConfig | null>(null);
   const [connStatus, setConnStatus]   = useState<ConnStatus>({
     log_tailing: false, addon_connected: false, wow_path: "",
+    addon_version: "", addon_outdated: false, replaying: "",
+    config_reloads: 0,
   });
   const [saving, setSaving]           = useState(false);
   const [detectMsg, setDetectMsg]     = useState("");
   const [wtfChars, setWtfChars]       = useState<WtfCharacter[]>([]);
   const [updateInfo, setUpdateInfo]   = useState<UpdateInfo | null>(null);
   const [updateChecking, setChecking] = useState(false);
+  // Crash reports left by earlier runs (panic hook → crash.rs)
+  const [crashes, setCrashes]         = useState<CrashReport[]>([]);
   // Spec profiles for the spec-selector dropdown
   const [specs, setSpecs]             = useState<SpecInfo[]>([]);
   // Live Feed state
@@ -63,8 +87,18 @@ function SettingsApp() {
     invoke<SpecInfo[]>("list_specs").then(setSpecs).catch(() => setSpecs([]));
     invoke<{ width: number; height: number }>("get_screen_size")
       .then(setScreenSize).catch(() => {});
+    invoke<CrashReport[]>("get_crash_reports").then(setCrashes).catch(() => setCrashes([]));
   }, []);
 
+  // Re-read the config after a change made outside this window (hand edit,
+  // in-game /cc command, synced settings), so the next save from this window
+  // doesn't overwrite it.
+  useEffect(() => {
+    if (connStatus.config_reloads > 0) {
+      invoke<AppConfig>("get_config").then(setConfig).catch(console.error);
+    }
+  }, [connStatus.config_reloads]);
+
   // Reload WTF character list when log path changes
   useEffect(() => {
     if (config?.wow_log_path) {
@@ -116,6 +150,21 @@ function SettingsApp() {
   // -------------------------------------------------------------------------
   // Config helpers
   // -------------------------------------------------------------------------
+  async function sendCrash(report: CrashReport) {
+    try {
+      const url = await invoke<string>("send_crash_report", { fileName: report.file_name });
+      await invoke("open_url", { url });
+    } catch (e) {
+      console.error(e);
+    }
+    setCrashes((prev) => prev.filter((c) => c.file_name !== report.file_name));
+  }
+
+  async function dismissCrash(report: CrashReport) {
+    await invoke("dismiss_crash_report", { fileName: report.file_name }).catch(console.error);
+    setCrashes((prev) => prev.filter((c) => c.file_name !== report.file_name));
+  }
+
   async function save(updated: AppConfig) {
     setSaving(true);
     try {
@@ -248,6 +297,30 @@ function SettingsApp() {
         </div>
       </header>
 
+      {/* ── Crash report from an earlier run ── */}
+      {crashes.length > 0 && (
+        <div style={{
+          display: "flex", alignItems: "center", gap: 10, padding: "8px 16px",
+          background: "var(--bg-panel)", borderBottom: "1px solid var(--stroke)", fontSize: 12,
+        }}>
+          <span style={{ color: "var(--bad)" }}>
+            ⚠ The coach crashed on {new Date(crashes[0].crashed_at).toLocaleString()}
+            {crashes.length > 1 ? ` (+${crashes.length - 1} more)` : ""}:
+          </span>
+          <span style={{ color: "var(--muted)", flex: 1, overflow: "hidden", textOverflow: "ellipsis", whiteSpace: "nowrap" }}
+                title={crashes[0].summary}>
+            {crashes[0].summary}
+          </span>
+          <button className="primary" style={{ fontSize: 11, padding: "5px 12px" }}
+                  onClick={() => void sendCrash(crashes[0])}>
+            Send crash report
+          </button>
+          <button style={{ fontSize: 11, padding: "5px 12px" }} onClick={() => void dismissCrash(crashes[0])}>
+            Dismiss
+          </button>
+        </div>
+      )}
+
       {/* ── Tab content ── */}
       <div style={{ flex: 1, overflow: "auto" }}>
         {tab === "home"     && (
@@ -279,7 +352,7 @@ function SettingsApp() {
             overlayOn={overlayOn} toggleOverlay={toggleOverlay}
           />
         )}
-        {tab === "history"  && <HistoryTab />}
+        {tab === "history"  && <HistoryTab replaying={connStatus.replaying} />}
       </div>
     </div>
   );
@@ -336,10 +409,40 @@ function HomeTab({
           {detectMsg && (
             <div style={{ fontSize: 11, color: "var(--muted)", marginTop: 6 }}>{detectMsg}</div>
           )}
+          {connStatus.log_build && (
+            <div style={{ fontSize: 10, color: "var(--muted)", marginTop: 6 }}>Game build {connStatus.log_build}</div>
+          )}
+          {connStatus.stale_data && (
+            <div style={{
+              background: "rgba(255,204,102,0.08)",
+              border: "1px solid var(--warn)",
+              borderRadius: "var(--radius)",
+              padding: "8px 10px",
+              fontSize: 11,
+              color: "var(--warn)",
+              marginTop: 6,
+            }}>
+              ⚠ {connStatus.stale_data}
+            </div>
+          )}
         </div>
 
         <div className="section">
           <h3>CombatCoach Addon</h3>
+          {connStatus.addon_connected && connStatus.addon_outdated && (
+            <div style={{
+              background: "rgba(255,204,102,0.08)",
+              border: "1px solid var(--warn)",
+              borderRadius: "var(--radius)",
+              padding: "8px 10px",
+              fontSize: 11,
+              color: "var(--warn)",
+              marginBottom: 6,
+            }}>
+              ⚠ Addon {connStatus.addon_version ? `v${connStatus.addon_version}` : "version unknown"} is
+              outdated — install the latest CombatCoach addon and <code>/reload</code>.
+            </div>
+          )}
           {connStatus.addon_connected ? (
             <div style={{
               background: "rgba(43,213,118,0.08)",
@@ -373,6 +476,8 @@ function HomeTab({
           )}
         </div>
 
+        <WclQuickSetup config={config} setConfig={setConfig} save={save} />
+
         {/* Hide character + spec selectors when the addon is providing identity */}
         {!connStatus.addon_connected && (
           <div className="section">
@@ -423,6 +528,263 @@ function HomeTab({
           </div>
         </div>
 
+        <div className="section">
+          <h3>Repeat Cooldowns</h3>
+          <div style={{ fontSize: 10, color: "var(--muted)", marginBottom: 6, fontStyle: "italic" }}>
+            How long the same tip stays quiet after it fires. Per-rule overrides live under advice_cooldowns.rules in config.toml.
+          </div>
+          {(["bad_s", "warn_s", "good_s"] as const).map((field) => {
+            const cooldowns = config.advice_cooldowns ?? DEFAULT_ADVICE_COOLDOWNS;
+            const label = { bad_s: "Errors", warn_s: "Warnings", good_s: "Praise" }[field];
+            return (
+              <div key={field} style={{ display: "flex", alignItems: "center", gap: 8, marginBottom: 4 }}>
+                <span style={{ fontSize: 11, width: 64 }}>{label}</span>
+                <input
+                  type="range" min={0} max={60} step={1}
+                  value={cooldowns[field]}
+                  onChange={(e) => {
+                    const updated = { ...config, advice_cooldowns: { ...cooldowns, [field]: Number(e.target.value) } };
+                    setConfig(updated);
+                    void save(updated);
+                  }}
+                />
+                <span style={{ fontSize: 11, color: "var(--muted)", width: 32 }}>{cooldowns[field]}s</span>
+              </div>
+            );
+          })}
+        </div>
+
+        <div className="section">
+          <h3>Damage Forecast</h3>
+          <div style={{ fontSize: 10, color: "var(--muted)", marginBottom: 6, fontStyle: "italic" }}>
+            Healers and tanks get a warning this many seconds before scheduled boss damage. 0 = off.
+          </div>
+          <input
+            type="range" min={0} max={10} step={1}
+            value={config.spike_forecast_lead_s ?? 5}
+            onChange={(e) => {
+              const updated = { ...config, spike_forecast_lead_s: Number(e.target.value) };
+              setConfig(updated);
+              void save(updated);
+            }}
+          />
+          <div style={{ fontSize: 11, color: "var(--muted)", marginTop: 4 }}>
+            {(config.spike_forecast_lead_s ?? 5) > 0 ? `${config.spike_forecast_lead_s ?? 5}s lead time` : "Off"}
+          </div>
+        </div>
+
+        <div className="section">
+          <h3>Unused Cooldowns</h3>
+          <div style={{ fontSize: 10, color: "var(--muted)", marginBottom: 6, fontStyle: "italic" }}>
+            Call out a major cooldown that has been ready this long mid-pull. 0 = off.
+          </div>
+          <input
+            type="range" min={0} max={60} step={5}
+            value={config.cd_unused_alert_s ?? 20}
+            onChange={(e) => {
+              const updated = { ...config, cd_unused_alert_s: Number(e.target.value) };
+              setConfig(updated);
+              void save(updated);
+            }}
+          />
+          <div style={{ fontSize: 11, color: "var(--muted)", marginTop: 4 }}>
+            {(config.cd_unused_alert_s ?? 20) > 0 ? `After ${config.cd_unused_alert_s ?? 20}s ready` : "Off"}
+          </div>
+        </div>
+
+        <div className="section">
+          <h3>Resource Waste</h3>
+          <div style={{ fontSize: 10, color: "var(--muted)", marginBottom: 6, fontStyle: "italic" }}>
+            Call out rage, energy, focus, holy power… lost to overcapping past this share of what you generated. 0 = off.
+          </div>
+          <input
+            type="range" min={0} max={50} step={5}
+            value={config.resource_waste_pct ?? 10}
+            onChange={(e) => {
+              const updated = { ...config, resource_waste_pct: Number(e.target.value) };
+              setConfig(updated);
+              void save(updated);
+            }}
+          />
+          <div style={{ fontSize: 11, color: "var(--muted)", marginTop: 4 }}>
+            {(config.resource_waste_pct ?? 10) > 0 ? `Over ${config.resource_waste_pct ?? 10}% wasted` : "Off"}
+          </div>
+        </div>
+
+        <div className="section">
+          <h3>Missing Defensive</h3>
+          <div style={{ fontSize: 10, color: "var(--muted)", marginBottom: 6, fontStyle: "italic" }}>
+            Call out a big hit taken with no defensive up while one was ready: a hit of at least this much damage, or this share of your last 10s of damage taken. 0 = off.
+          </div>
+          <input
+            type="number" min={0} step={5000}
+            value={config.defensive_missing_damage ?? 40000}
+            onChange={(e) => {
+              const updated = { ...config, defensive_missing_damage: Math.max(0, Number(e.target.value)) };
+              setConfig(updated);
+              void save(updated);
+            }}
+          />
+          <input
+            type="range" min={0} max={100} step={10}
+            value={config.defensive_missing_pct ?? 60}
+            onChange={(e) => {
+              const updated = { ...config, defensive_missing_pct: Number(e.target.value) };
+              setConfig(updated);
+              void save(updated);
+            }}
+          />
+          <div style={{ fontSize: 11, color: "var(--muted)", marginTop: 4 }}>
+            {(config.defensive_missing_pct ?? 60) > 0 ? `Or ${config.defensive_missing_pct ?? 60}% of recent damage` : "Share off"}
+          </div>
+        </div>
+
+        <div className="section">
+          <h3>Number Format</h3>
+          <div style={{ fontSize: 10, color: "var(--muted)", marginBottom: 6, fontStyle: "italic" }}>
+            How damage amounts and durations appear in advice text.
+          </div>
+          <select
+            value={numberFormatKey(config.number_format)}
+            onChange={(e) => {
+              const updated = { ...config, number_format: NUMBER_FORMATS[e.target.value].format };
+              setConfig(updated);
+              void save(updated);
+            }}
+            style={{ width: "100%", fontSize: 12 }}
+          >
+            {Object.entries(NUMBER_FORMATS).map(([key, { label }]) => (
+              <option key={key} value={key}>{label}</option>
+            ))}
+          </select>
+        </div>
+
+        <div className="section">
+          <h3>Pull Summary</h3>
+          <div style={{ fontSize: 10, color: "var(--muted)", marginBottom: 6, fontStyle: "italic" }}>
+            Copy a one-line summary to the clipboard when each pull ends, ready to paste into raid chat or Discord.
+          </div>
+          <label style={{ display: "flex", alignItems: "center", gap: 6, cursor: "pointer", margin: 0 }}>
+            <input
+              type="checkbox"
+              checked={config.pull_summary_clipboard ?? false}
+              onChange={(e) => {
+                const updated = { ...config, pull_summary_clipboard: e.target.checked };
+                setConfig(updated);
+                void save(updated);
+              }}
+              style={{ width: "auto", accentColor: "var(--accent)", cursor: "pointer" }}
+            />
+            <span style={{ fontSize: 12 }}>Copy to clipboard at pull end</span>
+          </label>
+        </div>
+
+        <div className="section">
+          <h3>Squad Mode</h3>
+          <div style={{ fontSize: 10, color: "var(--muted)", marginBottom: 6, fontStyle: "italic" }}>
+            Adds a party damage-taken leaderboard to the pull debrief, ranked by avoidable damage.
+          </div>
+          <label style={{ display: "flex", alignItems: "center", gap: 6, cursor: "pointer", margin: 0 }}>
+            <input
+              type="checkbox"
+              checked={config.squad_mode ?? false}
+              onChange={(e) => {
+                const updated = { ...config, squad_mode: e.target.checked };
+                setConfig(updated);
+                void save(updated);
+              }}
+              style={{ width: "auto", accentColor: "var(--accent)", cursor: "pointer" }}
+            />
+            <span style={{ fontSize: 12 }}>Show party leaderboard in debriefs</span>
+          </label>
+        </div>
+
+        <div className="section">
+          <h3>Advice Debug Capture</h3>
+          <div style={{ fontSize: 10, color: "var(--muted)", marginBottom: 6, fontStyle: "italic" }}>
+            Keeps the log lines and coaching state behind every advice, so a wrong advice can be reported with "Copy debug" in the live feed. Adds some work per log line; leave off unless reporting a problem.
+          </div>
+          <label style={{ display: "flex", alignItems: "center", gap: 6, cursor: "pointer", margin: 0 }}>
+            <input
+              type="checkbox"
+              checked={config.advice_debug ?? false}
+              onChange={(e) => {
+                const updated = { ...config, advice_debug: e.target.checked };
+                setConfig(updated);
+                void save(updated);
+              }}
+              style={{ width: "auto", accentColor: "var(--accent)", cursor: "pointer" }}
+            />
+            <span style={{ fontSize: 12 }}>Capture advice debug data</span>
+          </label>
+        </div>
+
+        <div className="section">
+          <h3>Raid Lead Mode</h3>
+          <div style={{ fontSize: 10, color: "var(--muted)", marginBottom: 6, fontStyle: "italic" }}>
+            Calls out avoidable hits on anyone in the group in short batches, and ranks the raid by avoidable hits at pull end. With clipboard copy on, that ranking is copied instead of the one-line summary. Needs encounter or dungeon data listing the avoidable spells.
+          </div>
+          <label style={{ display: "flex", alignItems: "center", gap: 6, cursor: "pointer", margin: 0 }}>
+            <input
+              type="checkbox"
+              checked={config.raid_lead_mode ?? false}
+              onChange={(e) => {
+                const updated = { ...config, raid_lead_mode: e.target.checked };
+                setConfig(updated);
+                void save(updated);
+              }}
+              style={{ width: "auto", accentColor: "var(--accent)", cursor: "pointer" }}
+            />
+            <span style={{ fontSize: 12 }}>Call out the group's avoidable hits</span>
+          </label>
+          <label style={{ display: "flex", alignItems: "center", gap: 6, cursor: "pointer", margin: "6px 0 0" }}>
+            <input
+              type="checkbox"
+              checked={config.group_coaching ?? false}
+              onChange={(e) => {
+                const updated = { ...config, group_coaching: e.target.checked };
+                setConfig(updated);
+                void save(updated);
+              }}
+              style={{ width: "auto", accentColor: "var(--accent)", cursor: "pointer" }}
+            />
+            <span style={{ fontSize: 12 }}>Coach every player: repeated avoidable hits and deaths, tagged by name</span>
+          </label>
+          <label style={{ display: "flex", alignItems: "center", gap: 6, cursor: "pointer", margin: "6px 0 0" }}>
+            <input
+              type="checkbox"
+              checked={config.avoidable_friendly_fire ?? false}
+              onChange={(e) => {
+                const updated = { ...config, avoidable_friendly_fire: e.target.checked };
+                setConfig(updated);
+                void save(updated);
+              }}
+              style={{ width: "auto", accentColor: "var(--accent)", cursor: "pointer" }}
+            />
+            <span style={{ fontSize: 12 }}>Count self-inflicted and party damage as avoidable</span>
+          </label>
+        </div>
+
+        <div className="section">
+          <h3>Encounter Data</h3>
+          <div style={{ fontSize: 10, color: "var(--muted)", marginBottom: 6, fontStyle: "italic" }}>
+            When a boss has no local encounter file, fetch a signed one from the community repository so its mechanics are coached from the next moment on.
+          </div>
+          <label style={{ display: "flex", alignItems: "center", gap: 6, cursor: "pointer", margin: 0 }}>
+            <input
+              type="checkbox"
+              checked={config.encounter_downloads ?? false}
+              onChange={(e) => {
+                const updated = { ...config, encounter_downloads: e.target.checked };
+                setConfig(updated);
+                void save(updated);
+              }}
+              style={{ width: "auto", accentColor: "var(--accent)", cursor: "pointer" }}
+            />
+            <span style={{ fontSize: 12 }}>Download missing encounter files</span>
+          </label>
+        </div>
+
         {/* Hide spec selector when addon manages identity */}
         {!connStatus.addon_connected && (
           <div className="section">
@@ -450,6 +812,93 @@ function HomeTab({
           </div>
         )}
 
+        <StrategyPresets config={config} setConfig={setConfig} />
+
+        <div className="section">
+          <h3>Settings Profile</h3>
+          <div style={{ fontSize: 10, color: "var(--muted)", marginBottom: 6, fontStyle: "italic" }}>
+            Move settings and overlay layout between machines. Folder paths stay per machine.
+          </div>
+          <div style={{ display: "flex", gap: 6, flexWrap: "wrap", marginBottom: 8 }}>
+            <button onClick={() => void exportProfile()}>Export…</button>
+            <button onClick={() => void importProfile(setConfig)}>Import…</button>
+          </div>
+          <div style={{ fontSize: 11, color: "var(--muted)", wordBreak: "break-all", marginBottom: 6 }}>
+            {config.profile_sync_dir
+              ? <span style={{ color: "var(--text)" }}>Syncing via {config.profile_sync_dir}</span>
+              : "Cloud sync off"}
+          </div>
+          <div style={{ display: "flex", gap: 6, flexWrap: "wrap" }}>
+            <button
+              onClick={async () => {
+                const selected = await open({ directory: true, title: "Select Cloud Sync Folder" });
+                if (typeof selected === "string") await save({ ...config, profile_sync_dir: selected });
+              }}
+            >
+              Sync Folder…
+            </button>
+            {config.profile_sync_dir && (
+              <button onClick={() => void save({ ...config, profile_sync_dir: "" })}>Stop Sync</button>
+            )}
+          </div>
+        </div>
+
+        <RulePacks config={config} />
+
+        <div className="section">
+          <h3>Second Screen</h3>
+          <div style={{ fontSize: 10, color: "var(--muted)", marginBottom: 6, fontStyle: "italic" }}>
+            Keeps snapshot.json, advice.json and debrief.json current in a folder, for Rainmeter skins or a
+            tablet browser reading the files through a simple file server.
+          </div>
+          <div style={{ fontSize: 11, color: "var(--muted)", wordBreak: "break-all", marginBottom: 6 }}>
+            {config.spectate_dir
+              ? <span style={{ color: "var(--text)" }}>Writing to {config.spectate_dir}</span>
+              : "Spectate files off"}
+          </div>
+          <div style={{ display: "flex", gap: 6, flexWrap: "wrap" }}>
+            <button
+              onClick={async () => {
+                const selected = await open({ directory: true, title: "Select Spectate Folder" });
+                if (typeof selected === "string") await save({ ...config, spectate_dir: selected });
+              }}
+            >
+              Spectate Folder…
+            </button>
+            {config.spectate_dir && (
+              <button onClick={() => void save({ ...config, spectate_dir: "" })}>Stop</button>
+            )}
+          </div>
+        </div>
+
+        <div className="section">
+          <h3>Storage</h3>
+          <div style={{ fontSize: 10, color: "var(--muted)", marginBottom: 6, fontStyle: "italic" }}>
+            Move session history to another drive. The database is copied; the old file is kept.
+            A new log folder applies after restart.
+          </div>
+          <div style={{ fontSize: 11, color: "var(--muted)", wordBreak: "break-all", marginBottom: 6 }}>
+            Data: {config.data_dir || "default (app data folder)"}<br />
+            Logs: {config.log_dir || "default (app data folder)"}
+          </div>
+          <div style={{ display: "flex", gap: 6, flexWrap: "wrap" }}>
+            <button onClick={() => void moveDataDir(setConfig)}>Move Data Folder…</button>
+            <button
+              onClick={async () => {
+                const selected = await open({ directory: true, title: "Select Log Folder" });
+                if (typeof selected !== "string") return;
+                try {
+                  await save({ ...config, log_dir: selected });
+                } catch (e) {
+                  alert(`Log folder not usable: ${e}`);
+                }
+              }}
+            >
+              Log Folder…
+            </button>
+          </div>
+        </div>
+
         <div className="section">
           <h3>Updates</h3>
           {updateInfo?.available ? (
@@ -462,6 +911,11 @@ function HomeTab({
                   {updateInfo.notes}
                 </div>
               )}
+              {(updateInfo.changes ?? []).filter((c) => c.new_rules.length > 0).map((c) => (
+                <div key={c.version} style={{ color: "var(--muted)", marginBottom: 4 }}>
+                  v{c.version} adds: {c.new_rules.join(", ")}
+                </div>
+              ))}
               <button
                 style={{ fontSize: 11, padding: "5px 12px", marginBottom: 6, width: "100%" }}
                 className="primary"
@@ -500,73 +954,364 @@ function HomeTab({
   );
 }
 
-// ===========================================================================
-// LIVE FEED TAB
-// ===========================================================================
-interface LiveFeedTabProps {
-  advice:       AdviceEvent[];
-  snapshot:     StateSnapshot;
-  eventCount:   number;
-  connStatus:   ConnStatus;
-  playerFocus:  string;
-  eventLog:     string[];
-}
-
-function LiveFeedTab({ advice, snapshot, eventCount, connStatus, playerFocus, eventLog }: LiveFeedTabProps) {
-  const feedRef    = useRef<HTMLDivElement>(null);
-  const eventFeedRef = useRef<HTMLDivElement>(null);
-  const [subTab, setSubTab] = useState<"advice" | "eventfeed">("advice");
-
-  useEffect(() => {
-    if (feedRef.current) feedRef.current.scrollTop = 0;
-  }, [advice.length]);
-
-  // Auto-scroll event feed to bottom when new entries arrive
-  useEffect(() => {
-    if (eventFeedRef.current && subTab === "eventfeed") {
-      eventFeedRef.current.scrollTop = eventFeedRef.current.scrollHeight;
+// Quick setup from a Warcraft Logs character (see wcl_import.rs): fills in
+// the coached character and spec and downloads the tier's encounter files.
+function WclQuickSetup({ config, setConfig, save }: {
+  config: AppConfig;
+  setConfig: (c: AppConfig) => void;
+  save: (c: AppConfig) => Promise<void>;
+}) {
+  const [name, setName]     = useState("");
+  const [realm, setRealm]   = useState("");
+  const [region, setRegion] = useState("US");
+  const [busy, setBusy]     = useState(false);
+  const [result, setResult] = useState("");
+  const [error, setError]   = useState("");
+
+  async function runImport() {
+    setBusy(true);
+    setError("");
+    setResult("");
+    try {
+      const setup = await invoke<QuickSetup>("import_wcl_character", { name, realm, region });
+      setConfig(setup.config);
+      setResult([
+        `✓ ${setup.config.player_focus}`,
+        setup.spec_key ?? setup.class,
+        setup.progress,
+        setup.encounters_loaded > 0 ? `${setup.encounters_loaded} boss files loaded` : "",
+      ].filter(Boolean).join(" · "));
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
     }
-  }, [eventLog.length, subTab]);
+  }
 
-  // Smooth pull timer: extrapolates pull_elapsed_ms between log-event batches.
-  // WoW writes the combat log in chunks (every ~0.5–2 s); without this, the
-  // timer only jumps on each batch and appears frozen between flushes.
-  const snapRef     = useRef(snapshot);
-  const snapTimeRef = useRef(Date.now());
-  const [liveElapsed, setLiveElapsed] = useState(0);
+  return (
+    <div className="section">
+      <h3>Quick Setup from Warcraft Logs</h3>
+      <div style={{ fontSize: 10, color: "var(--muted)", marginBottom: 6, fontStyle: "italic" }}>
+        Fills in your character, spec and current raid's boss data. Needs your own API client from warcraftlogs.com/api/clients.
+      </div>
+      <div style={{ display: "flex", gap: 6, marginBottom: 6 }}>
+        <input placeholder="Client ID" value={config.wcl_client_id ?? ""} style={{ flex: 1, fontSize: 11 }}
+               onChange={(e) => void save({ ...config, wcl_client_id: e.target.value.trim() })} />
+        <input placeholder="Client secret" type="password" value={config.wcl_client_secret ?? ""} style={{ flex: 1, fontSize: 11 }}
+               onChange={(e) => void save({ ...config, wcl_client_secret: e.target.value.trim() })} />
+      </div>
+      <div style={{ display: "flex", gap: 6 }}>
+        <input placeholder="Character" value={name} onChange={(e) => setName(e.target.value)} style={{ flex: 1, fontSize: 11 }} />
+        <input placeholder="Realm" value={realm} onChange={(e) => setRealm(e.target.value)} style={{ flex: 1, fontSize: 11 }} />
+        <select value={region} onChange={(e) => setRegion(e.target.value)} style={{ fontSize: 11 }}>
+          {["US", "EU", "KR", "TW", "CN"].map((r) => <option key={r} value={r}>{r}</option>)}
+        </select>
+        <button disabled={busy || !name || !realm} onClick={() => void runImport()}>
+          {busy ? "Importing…" : "Import"}
+        </button>
+      </div>
+      {result && <div style={{ fontSize: 11, color: "var(--good)", marginTop: 4 }}>{result}</div>}
+      {error && <div style={{ fontSize: 11, color: "var(--bad)", marginTop: 4 }}>{error}</div>}
+    </div>
+  );
+}
 
-  useEffect(() => {
-    snapRef.current     = snapshot;
-    snapTimeRef.current = Date.now();
-  }, [snapshot]);
+// Community strategy presets (see presets.rs): browse the curated repository,
+// download, and apply one to its encounter.
+function StrategyPresets({ config, setConfig }: {
+  config: AppConfig;
+  setConfig: (c: AppConfig) => void;
+}) {
+  const [listings, setListings] = useState<PresetListing[] | null>(null);
+  const [local, setLocal]       = useState<StrategyPreset[]>([]);
+  const [busy, setBusy]         = useState("");
+  const [error, setError]       = useState("");
 
   useEffect(() => {
-    const id = setInterval(() => {
-      const s = snapRef.current;
-      if (s.in_combat && s.pull_elapsed_ms > 0) {
-        // Extrapolate: add wall-clock time elapsed since the last snapshot
-        setLiveElapsed(s.pull_elapsed_ms + (Date.now() - snapTimeRef.current));
-      } else {
-        setLiveElapsed(s.pull_elapsed_ms);
-      }
-    }, 200);
-    return () => clearInterval(id);
+    invoke<StrategyPreset[]>("list_local_presets").then(setLocal).catch(() => setLocal([]));
   }, []);
 
-  const elapsed    = liveElapsed;
-  const mins       = Math.floor(elapsed / 60000);
-  const secs       = Math.floor((elapsed % 60000) / 1000);
-  const elapsedStr = elapsed > 0 ? `${mins}:${secs.toString().padStart(2, "0")}` : "—";
+  function browse() {
+    setBusy("browse");
+    setError("");
+    invoke<PresetListing[]>("list_strategy_presets")
+      .then(setListings)
+      .catch((e) => setError(String(e)))
+      .finally(() => setBusy(""));
+  }
 
-  const sevColor: Record<string, string> = {
-    good: "var(--good)",
-    warn: "var(--warn)",
-    bad:  "var(--bad)",
-  };
+  async function download(id: string) {
+    setBusy(id);
+    setError("");
+    try {
+      const preset = await invoke<StrategyPreset>("download_strategy_preset", { id });
+      setLocal((l) => [...l.filter((p) => p.preset.id !== id), preset]);
+      setListings((l) => l?.map((p) => (p.id === id ? { ...p, installed: true } : p)) ?? null);
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy("");
+    }
+  }
+
+  async function apply(id: string) {
+    setBusy(id);
+    setError("");
+    try {
+      setConfig(await invoke<AppConfig>("apply_strategy_preset", { id }));
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy("");
+    }
+  }
+
+  const applied = config.encounter_settings ?? {};
+  const isApplied = (p: StrategyPreset) => applied[p.preset.encounter]?.preset_id === p.preset.id;
 
   return (
-    <div style={{ display: "grid", gridTemplateColumns: "200px 1fr", height: "100%" }}>
-      {/* Left: stat sidebar */}
+    <div className="section">
+      <h3>Strategy Presets</h3>
+      <div style={{ fontSize: 10, color: "var(--muted)", marginBottom: 6, fontStyle: "italic" }}>
+        Shared per-boss setups: kick assignments, muted rules, intensity and panel layout.
+      </div>
+      {local.map((p) => (
+        <div key={p.preset.id} style={{ display: "flex", alignItems: "center", gap: 6, fontSize: 11, marginBottom: 4 }}>
+          <span style={{ flex: 1, minWidth: 0, overflow: "hidden", textOverflow: "ellipsis", whiteSpace: "nowrap" }}
+                title={p.preset.description}>
+            <span style={{ color: "var(--muted)" }}>{p.preset.encounter}:</span> {p.preset.name}
+          </span>
+          {isApplied(p) ? (
+            <span style={{ color: "var(--good)" }}>✓ Applied</span>
+          ) : (
+            <button style={{ fontSize: 11 }} disabled={busy !== ""} onClick={() => void apply(p.preset.id)}>
+              Apply
+            </button>
+          )}
+        </div>
+      ))}
+      {listings?.filter((l) => !l.installed).map((l) => (
+        <div key={l.id} style={{ display: "flex", alignItems: "center", gap: 6, fontSize: 11, marginBottom: 4 }}>
+          <span style={{ flex: 1, minWidth: 0, overflow: "hidden", textOverflow: "ellipsis", whiteSpace: "nowrap" }}
+                title={l.description}>
+            <span style={{ color: "var(--muted)" }}>{l.encounter}:</span> {l.name}
+            {l.author && <span style={{ color: "var(--muted)" }}> · {l.author}</span>}
+          </span>
+          <button style={{ fontSize: 11 }} disabled={busy !== ""} onClick={() => void download(l.id)}>
+            {busy === l.id ? "…" : "Download"}
+          </button>
+        </div>
+      ))}
+      {listings?.length === 0 && (
+        <div style={{ fontSize: 11, color: "var(--muted)", marginBottom: 6 }}>No presets published yet.</div>
+      )}
+      {error && <div style={{ fontSize: 11, color: "var(--bad)", marginBottom: 6 }}>{error}</div>}
+      <button onClick={browse} disabled={busy !== ""}>
+        {busy === "browse" ? "Loading…" : listings ? "Refresh" : "Browse Presets"}
+      </button>
+    </div>
+  );
+}
+
+// Settings profile export/import (see profile.rs). Paths are machine-local
+// and are kept on import, so the returned config is shown as-is.
+const PROFILE_FILTERS = [{ name: "CombatLedger Profile", extensions: ["toml"] }];
+
+async function exportProfile() {
+  const path = await saveDialog({
+    filters: PROFILE_FILTERS,
+    defaultPath: "combatledger-profile.toml",
+    title: "Export Settings Profile",
+  });
+  if (!path) return;
+  try {
+    await invoke("export_profile", { path });
+  } catch (e) {
+    alert(`Export failed: ${e}`);
+  }
+}
+
+async function importProfile(setConfig: (c: AppConfig) => void) {
+  const path = await open({ filters: PROFILE_FILTERS, title: "Import Settings Profile" });
+  if (typeof path !== "string") return;
+  try {
+    setConfig(await invoke<AppConfig>("import_profile", { path }));
+  } catch (e) {
+    alert(`Import failed: ${e}`);
+  }
+}
+
+// Custom rule packs (see rule_pack.rs): a coach bundles their rules/*.toml
+// files with pack metadata; importing validates them against local rules.
+const RULE_PACK_FILTERS = [{ name: "CombatLedger Rule Pack", extensions: ["toml"] }];
+
+interface ImportedPack {
+  pack: { name: string; author: string; version: string; specs: string[] };
+  files: string[];
+  rules: number;
+}
+
+function RulePacks({ config }: { config: AppConfig }) {
+  const [name, setName]         = useState("");
+  const [author, setAuthor]     = useState("");
+  const [version, setVersion]   = useState("1.0");
+  const [specOnly, setSpecOnly] = useState(false);
+  const [status, setStatus]     = useState("");
+
+  async function exportPack() {
+    const path = await saveDialog({ filters: RULE_PACK_FILTERS, defaultPath: "rule-pack.toml", title: "Export Rule Pack" });
+    if (!path) return;
+    const specs = specOnly && config.selected_spec ? [config.selected_spec] : [];
+    try {
+      await invoke("export_rule_pack", { path, pack: { name, author, version, specs }, files: [] });
+      setStatus("Rule pack exported");
+    } catch (e) {
+      setStatus(`Export failed: ${e}`);
+    }
+  }
+
+  async function importPack() {
+    const path = await open({ filters: RULE_PACK_FILTERS, title: "Import Rule Pack" });
+    if (typeof path !== "string") return;
+    try {
+      const imported = await invoke<ImportedPack>("import_rule_pack", { path });
+      const { pack } = imported;
+      setStatus(`${pack.name} ${pack.version} by ${pack.author}: ${imported.rules} rules`
+        + (pack.specs.length ? ` (for ${pack.specs.join(", ")})` : ""));
+    } catch (e) {
+      setStatus(`Import failed: ${e}`);
+    }
+  }
+
+  return (
+    <div className="section">
+      <h3>Rule Packs</h3>
+      <div style={{ fontSize: 10, color: "var(--muted)", marginBottom: 6, fontStyle: "italic" }}>
+        Share your custom rules (rules/*.toml) with students, or install a coach's pack.
+      </div>
+      <div style={{ display: "flex", gap: 6, flexWrap: "wrap", marginBottom: 6 }}>
+        <input placeholder="Pack name" value={name} onChange={(e) => setName(e.target.value)} style={{ flex: 2 }} />
+        <input placeholder="Author" value={author} onChange={(e) => setAuthor(e.target.value)} style={{ flex: 2 }} />
+        <input placeholder="Version" value={version} onChange={(e) => setVersion(e.target.value)} style={{ flex: 1 }} />
+      </div>
+      {config.selected_spec && (
+        <label style={{ fontSize: 11, display: "block", marginBottom: 6 }}>
+          <input type="checkbox" checked={specOnly} onChange={(e) => setSpecOnly(e.target.checked)} />
+          {" "}Written for {config.selected_spec} only
+        </label>
+      )}
+      <div style={{ display: "flex", gap: 6, flexWrap: "wrap" }}>
+        <button onClick={() => void exportPack()}>Export Pack…</button>
+        <button onClick={() => void importPack()}>Import Pack…</button>
+      </div>
+      {status && <div style={{ fontSize: 11, color: "var(--muted)", marginTop: 6 }}>{status}</div>}
+    </div>
+  );
+}
+
+async function moveDataDir(setConfig: (c: AppConfig) => void) {
+  const path = await open({ directory: true, title: "Select Data Folder" });
+  if (typeof path !== "string") return;
+  try {
+    setConfig(await invoke<AppConfig>("move_data_dir", { path }));
+  } catch (e) {
+    alert(`Move failed: ${e}`);
+  }
+}
+
+// Number format presets offered in the Home tab (value = units::NumberFormat).
+const NUMBER_FORMATS: Record<string, { label: string; format: NumberFormat }> = {
+  compact: { label: "55k · 2.5s",     format: { compact: true,  thousands_sep: ",", decimal_sep: "." } },
+  en:      { label: "55,000 · 2.5s",  format: { compact: false, thousands_sep: ",", decimal_sep: "." } },
+  de:      { label: "55.000 · 2,5s",  format: { compact: false, thousands_sep: ".", decimal_sep: "," } },
+  fr:      { label: "55 000 · 2,5s",  format: { compact: false, thousands_sep: " ", decimal_sep: "," } },
+};
+
+function numberFormatKey(f: NumberFormat | undefined): string {
+  if (!f) return "compact";
+  const hit = Object.entries(NUMBER_FORMATS).find(([, { format }]) =>
+    format.compact === f.compact && format.thousands_sep === f.thousands_sep && format.decimal_sep === f.decimal_sep);
+  return hit ? hit[0] : "compact";
+}
+
+// ===========================================================================
+// LIVE FEED TAB
+// ===========================================================================
+/** Copy an advice's debug row (log lines + state) as JSON for a bug report. */
+async function copyAdviceDebug(adviceId: string) {
+  try {
+    const debug = await invoke<unknown>("get_advice_debug", { adviceId });
+    await navigator.clipboard.writeText(
+      debug ? JSON.stringify(debug, null, 2) : `No debug data stored for advice ${adviceId}`);
+  } catch (e) {
+    console.error("get_advice_debug failed:", e);
+  }
+}
+
+interface LiveFeedTabProps {
+  advice:       AdviceEvent[];
+  snapshot:     StateSnapshot;
+  eventCount:   number;
+  connStatus:   ConnStatus;
+  playerFocus:  string;
+  eventLog:     string[];
+}
+
+function LiveFeedTab({ advice, snapshot, eventCount, connStatus, playerFocus, eventLog }: LiveFeedTabProps) {
+  const feedRef    = useRef<HTMLDivElement>(null);
+  const eventFeedRef = useRef<HTMLDivElement>(null);
+  const [subTab, setSubTab] = useState<"advice" | "eventfeed">("advice");
+
+  useEffect(() => {
+    if (feedRef.current) feedRef.current.scrollTop = 0;
+  }, [advice.length]);
+
+  // Auto-scroll event feed to bottom when new entries arrive
+  useEffect(() => {
+    if (eventFeedRef.current && subTab === "eventfeed") {
+      eventFeedRef.current.scrollTop = eventFeedRef.current.scrollHeight;
+    }
+  }, [eventLog.length, subTab]);
+
+  // Smooth pull timer: extrapolates pull_elapsed_ms between log-event batches.
+  // WoW writes the combat log in chunks (every ~0.5–2 s); without this, the
+  // timer only jumps on each batch and appears frozen between flushes.
+  const snapRef     = useRef(snapshot);
+  const snapTimeRef = useRef(Date.now());
+  const [liveElapsed, setLiveElapsed] = useState(0);
+
+  useEffect(() => {
+    snapRef.current     = snapshot;
+    snapTimeRef.current = Date.now();
+  }, [snapshot]);
+
+  useEffect(() => {
+    const id = setInterval(() => {
+      const s = snapRef.current;
+      if (s.in_combat && s.pull_elapsed_ms > 0) {
+        // Extrapolate: add wall-clock time elapsed since the last snapshot
+        setLiveElapsed(s.pull_elapsed_ms + (Date.now() - snapTimeRef.current));
+      } else {
+        setLiveElapsed(s.pull_elapsed_ms);
+      }
+    }, 200);
+    return () => clearInterval(id);
+  }, []);
+
+  const elapsed    = liveElapsed;
+  const mins       = Math.floor(elapsed / 60000);
+  const secs       = Math.floor((elapsed % 60000) / 1000);
+  const elapsedStr = elapsed > 0 ? `${mins}:${secs.toString().padStart(2, "0")}` : "—";
+
+  const sevColor: Record<string, string> = {
+    good: "var(--good)",
+    warn: "var(--warn)",
+    bad:  "var(--bad)",
+  };
+
+  return (
+    <div style={{ display: "grid", gridTemplateColumns: "200px 1fr", height: "100%" }}>
+      {/* Left: stat sidebar */}
       <aside style={{
         borderRight: "1px solid var(--stroke)",
         padding: "16px 16px",
@@ -578,10 +1323,25 @@ function LiveFeedTab({ advice, snapshot, eventCount, connStatus, playerFocus, ev
           value={connStatus.log_tailing ? "Active" : "Inactive"}
           color={connStatus.log_tailing ? "var(--good)" : "var(--bad)"} />
         <StatBlock label="Addon"
-          value={connStatus.addon_connected ? "Connected" : "Disconnected"}
-          color={connStatus.addon_connected ? "var(--good)" : "var(--muted)"} />
+          value={!connStatus.addon_connected ? "Disconnected"
+            : connStatus.addon_outdated ? "Outdated" : "Connected"}
+          color={!connStatus.addon_connected ? "var(--muted)"
+            : connStatus.addon_outdated ? "var(--warn)" : "var(--good)"} />
         <StatBlock label="Advice fired"
           value={eventCount.toLocaleString()} />
+        <StatBlock label="Coaching"
+          value={snapshot.coaching_paused ? "Paused" : "Active"}
+          color={snapshot.coaching_paused ? "var(--warn)" : "var(--good)"} />
+        <button
+          disabled={!connStatus.log_tailing}
+          onClick={() => {
+            invoke(snapshot.coaching_paused ? "resume_coaching" : "pause_coaching")
+              .catch((e) => console.error(e));
+          }}
+          style={{ fontSize: 11, marginBottom: 10 }}
+        >
+          {snapshot.coaching_paused ? "▶ Resume coaching" : "⏸ Pause coaching"}
+        </button>
 
         {/* Warning: log active but no coached character set */}
         {connStatus.log_tailing && !playerFocus && (
@@ -607,6 +1367,8 @@ function LiveFeedTab({ advice, snapshot, eventCount, connStatus, playerFocus, ev
         <StatBlock label="Pull time"    value={elapsedStr} />
         <StatBlock label="GCD gap"
           value={snapshot.gcd_gap_ms > 0 ? `${snapshot.gcd_gap_ms} ms` : "—"} />
+        <StatBlock label="Active time"
+          value={snapshot.in_combat ? `${(snapshot.active_time_pct ?? 0).toFixed(0)}%` : "—"} />
         <StatBlock label="Avoidable hits"
           value={snapshot.avoidable_count.toString()} />
         <StatBlock label="Interrupts"
@@ -672,8 +1434,17 @@ function LiveFeedTab({ advice, snapshot, eventCount, connStatus, playerFocus, ev
                   </span>
                   <span style={{ fontSize: 13, fontWeight: 600 }}>{a.title}</span>
                   <span style={{ marginLeft: "auto", fontSize: 10, color: "var(--muted)" }}>
-                    {new Date(a.timestamp_ms).toLocaleTimeString()}
+                    {new Date(a.wallclock_ms).toLocaleTimeString()}
                   </span>
+                  {a.debug_id && (
+                    <button
+                      style={{ fontSize: 10, padding: "1px 6px" }}
+                      title="Copy the log lines and state behind this advice, for a bug report"
+                      onClick={() => copyAdviceDebug(a.debug_id!)}
+                    >
+                      Copy debug
+                    </button>
+                  )}
                 </div>
                 <div style={{ fontSize: 12, color: "var(--muted)", marginBottom: a.kv.length > 0 ? 6 : 0 }}>
                   {a.message}
@@ -781,6 +1552,17 @@ function AudioTab({ config, save }: AudioTabProps) {
     if (typeof selected === "string") updateCue(severity, { sound_path: selected });
   }
 
+  const countdown: CountdownConfig = config.countdown ?? { enabled: true, from_s: 3, voice_pack: "" };
+
+  function updateCountdown(patch: Partial<CountdownConfig>) {
+    void save({ ...config, countdown: { ...countdown, ...patch } });
+  }
+
+  async function browseVoicePack() {
+    const selected = await open({ directory: true, title: "Select Countdown Voice Pack Folder" });
+    if (typeof selected === "string") updateCountdown({ voice_pack: selected });
+  }
+
   function testBeep(severity: string, volume: number) {
     const ctx  = new AudioContext();
     const osc  = ctx.createOscillator();
@@ -803,6 +1585,19 @@ function AudioTab({ config, save }: AudioTabProps) {
         Audio cues work even when the overlay is hidden.
       </p>
 
+      <label style={{ display: "flex", alignItems: "center", gap: 8, cursor: "pointer", margin: "0 0 18px 0" }}>
+        <input
+          type="checkbox"
+          checked={config.audio_muted ?? false}
+          onChange={(e) => save({ ...config, audio_muted: e.target.checked })}
+          style={{ width: "auto", accentColor: "var(--accent)", cursor: "pointer" }}
+        />
+        <span style={{ fontSize: 13 }}>Mute all cues</span>
+        <span style={{ fontSize: 11, color: "var(--muted)" }}>
+          — also /cc mute and /cc unmute in-game (applied at the next /reload)
+        </span>
+      </label>
+
       <div style={{ display: "flex", flexDirection: "column", gap: 14 }}>
         {(["good", "warn", "bad"] as const).map((sev) => {
           const cue  = cues.find((c) => c.severity === sev) ?? { severity: sev, enabled: true, volume: 0.7, sound_path: "" };
@@ -881,10 +1676,167 @@ function AudioTab({ config, save }: AudioTabProps) {
           );
         })}
       </div>
+
+      <h2 style={{ margin: "28px 0 6px 0", fontSize: 16 }}>Boss Countdowns</h2>
+      <p style={{ margin: "0 0 14px 0", fontSize: 12, color: "var(--muted)" }}>
+        Count down "3, 2, 1" before raid-wide damage on the encounter timeline (and tank busters
+        when playing a tank). A voice pack is a folder with one file per number — 1.ogg, 2.ogg, …
+        (.mp3 and .wav work too). Numbers without a file use the built-in tone.
+      </p>
+      <div style={{
+        background: "var(--bg-card)", border: "1px solid var(--stroke)",
+        borderRadius: "var(--radius-lg)", padding: "14px 18px",
+        display: "flex", flexDirection: "column", gap: 10,
+      }}>
+        <label style={{ display: "flex", alignItems: "center", gap: 8, cursor: "pointer", margin: 0 }}>
+          <input
+            type="checkbox"
+            checked={countdown.enabled}
+            onChange={(e) => updateCountdown({ enabled: e.target.checked })}
+            style={{ width: "auto", accentColor: "var(--accent)", cursor: "pointer" }}
+          />
+          <span style={{ fontSize: 13 }}>Play countdowns</span>
+        </label>
+
+        <div style={{
+          display: "flex", flexDirection: "column", gap: 10,
+          opacity: countdown.enabled ? 1 : 0.35,
+          pointerEvents: countdown.enabled ? "auto" : "none",
+        }}>
+          <div style={{ display: "flex", alignItems: "center", gap: 10 }}>
+            <span style={{ fontSize: 11, color: "var(--muted)", minWidth: 72 }}>Count from</span>
+            <select
+              value={countdown.from_s}
+              onChange={(e) => updateCountdown({ from_s: parseInt(e.target.value, 10) })}
+              style={{ fontSize: 12 }}
+            >
+              {[1, 2, 3, 4, 5].map((n) => <option key={n} value={n}>{n}</option>)}
+            </select>
+          </div>
+
+          <div style={{ display: "flex", alignItems: "center", gap: 8 }}>
+            <span style={{ fontSize: 11, color: "var(--muted)", minWidth: 72 }}>Voice pack</span>
+            <div style={{
+              flex: 1, fontSize: 11,
+              color: countdown.voice_pack ? "var(--text)" : "var(--muted)",
+              fontStyle: countdown.voice_pack ? "normal" : "italic",
+              overflow: "hidden", textOverflow: "ellipsis", whiteSpace: "nowrap",
+            }}>
+              {countdown.voice_pack || "Built-in tones"}
+            </div>
+            {countdown.voice_pack && (
+              <button style={{ fontSize: 11, padding: "4px 8px" }} onClick={() => updateCountdown({ voice_pack: "" })}>
+                ✕
+              </button>
+            )}
+            <button style={{ fontSize: 11, padding: "4px 10px" }} onClick={() => void browseVoicePack()}>
+              Browse…
+            </button>
+          </div>
+        </div>
+      </div>
+
+      <HardwareCuesSection config={config} save={save} />
     </div>
   );
 }
 
+const CUE_TARGET_HINT: Record<HardwareCue["transport"], string> = {
+  udp:    "127.0.0.1:9000",
+  osc:    "127.0.0.1:8000",
+  serial: "COM3",
+};
+
+/** Non-audio cues: UDP/OSC/serial messages to keyboards, Stream Decks, LED strips. */
+function HardwareCuesSection({ config, save }: AudioTabProps) {
+  const cues = config.hardware_cues ?? [];
+  const [status, setStatus] = useState("");
+
+  function update(index: number, patch: Partial<HardwareCue>) {
+    void save({ ...config, hardware_cues: cues.map((c, i) => i === index ? { ...c, ...patch } : c) });
+  }
+
+  function add() {
+    const cue: HardwareCue = { severity: "bad", enabled: true, transport: "udp", target: "", message: "{severity}" };
+    void save({ ...config, hardware_cues: [...cues, cue] });
+  }
+
+  async function test(cue: HardwareCue) {
+    try {
+      await invoke("test_hardware_cue", { cue });
+      setStatus(`Sent to ${cue.target}`);
+    } catch (e) {
+      setStatus(`Not sent: ${e}`);
+    }
+  }
+
+  return (
+    <>
+      <h2 style={{ margin: "28px 0 6px 0", fontSize: 16 }}>Hardware Cues</h2>
+      <p style={{ margin: "0 0 14px 0", fontSize: 12, color: "var(--muted)" }}>
+        Signal keyboards, Stream Decks or LED strips when advice fires — a cue that is neither a sound
+        nor the overlay. UDP sends the message as text ({"{severity}"}, {"{title}"} and {"{key}"} are
+        filled in), OSC sends the advice title to the address, serial writes the message and a newline.
+      </p>
+      <div style={{ display: "flex", flexDirection: "column", gap: 8 }}>
+        {cues.map((cue, i) => (
+          <div key={i} style={{
+            background: "var(--bg-card)", border: "1px solid var(--stroke)",
+            borderRadius: "var(--radius-lg)", padding: "10px 14px",
+            display: "flex", alignItems: "center", gap: 8, flexWrap: "wrap",
+          }}>
+            <input
+              type="checkbox"
+              checked={cue.enabled}
+              onChange={(e) => update(i, { enabled: e.target.checked })}
+              style={{ width: "auto", accentColor: "var(--accent)", cursor: "pointer" }}
+            />
+            <select value={cue.severity} onChange={(e) => update(i, { severity: e.target.value })} style={{ fontSize: 12 }}>
+              {(["good", "warn", "bad"] as const).map((sev) => (
+                <option key={sev} value={sev}>{SEVERITY_META[sev].icon} {sev}</option>
+              ))}
+            </select>
+            <select
+              value={cue.transport}
+              onChange={(e) => update(i, { transport: e.target.value as HardwareCue["transport"] })}
+              style={{ fontSize: 12 }}
+            >
+              <option value="udp">UDP</option>
+              <option value="osc">OSC</option>
+              <option value="serial">Serial</option>
+            </select>
+            <input
+              key={`target-${i}-${cue.target}`}
+              defaultValue={cue.target}
+              placeholder={CUE_TARGET_HINT[cue.transport]}
+              onBlur={(e) => e.target.value !== cue.target && update(i, { target: e.target.value.trim() })}
+              style={{ width: 130, fontSize: 12 }}
+            />
+            <input
+              key={`message-${i}-${cue.message}`}
+              defaultValue={cue.message}
+              placeholder={cue.transport === "osc" ? "/keyboard/flash" : "{severity}"}
+              onBlur={(e) => e.target.value !== cue.message && update(i, { message: e.target.value })}
+              style={{ flex: 1, minWidth: 100, fontSize: 12 }}
+            />
+            <button style={{ fontSize: 11, padding: "4px 10px" }} onClick={() => void test(cue)}>Test ▶</button>
+            <button
+              style={{ fontSize: 11, padding: "4px 8px" }}
+              onClick={() => void save({ ...config, hardware_cues: cues.filter((_, j) => j !== i) })}
+            >
+              ✕
+            </button>
+          </div>
+        ))}
+      </div>
+      <div style={{ display: "flex", alignItems: "center", gap: 10, marginTop: 8 }}>
+        <button style={{ fontSize: 11, padding: "4px 10px" }} onClick={add}>Add Cue</button>
+        {status && <span style={{ fontSize: 11, color: "var(--muted)" }}>{status}</span>}
+      </div>
+    </>
+  );
+}
+
 // ===========================================================================
 // HOTKEYS TAB
 // ===========================================================================
@@ -895,35 +1847,22 @@ interface HotkeysTabProps {
   toggleOverlay: () => void;
 }
 
+type HotkeyAction = keyof HotkeyConfig;
+
 function HotkeysTab({ config, save, overlayOn, toggleOverlay }: HotkeysTabProps) {
-  const [recording, setRecording] = useState(false);
-  const [combo, setCombo]         = useState(config.hotkeys?.toggle_overlay ?? "");
+  const hotkeys: HotkeyConfig = { toggle_overlay: "", show_last_debrief: "", ...config.hotkeys };
+  const [debriefError, setDebriefError] = useState("");
 
-  useEffect(() => {
-    if (!recording) return;
-    function onKey(e: KeyboardEvent) {
-      e.preventDefault();
-      const parts: string[] = [];
-      if (e.ctrlKey)  parts.push("Ctrl");
-      if (e.altKey)   parts.push("Alt");
-      if (e.shiftKey) parts.push("Shift");
-      const key = e.key;
-      if (["Control", "Alt", "Shift", "Meta"].includes(key)) return;
-      parts.push(key.length === 1 ? key.toUpperCase() : key);
-      const newCombo = parts.join("+");
-      setCombo(newCombo);
-      setRecording(false);
-      void save({ ...config, hotkeys: { ...(config.hotkeys ?? { toggle_overlay: "" }), toggle_overlay: newCombo } });
-      void invoke("register_hotkey", { combo: newCombo });
-    }
-    window.addEventListener("keydown", onKey);
-    return () => window.removeEventListener("keydown", onKey);
-  }, [recording, config, save]);
+  function bind(action: HotkeyAction, combo: string) {
+    const next = { ...hotkeys, [action]: combo };
+    void save({ ...config, hotkeys: next });
+    void invoke("register_hotkey", { hotkeys: next });
+  }
 
-  function clearHotkey() {
-    setCombo("");
-    void save({ ...config, hotkeys: { ...(config.hotkeys ?? { toggle_overlay: "" }), toggle_overlay: "" } });
-    void invoke("register_hotkey", { combo: "" });
+  function showLastDebrief() {
+    invoke("show_last_debrief")
+      .then(() => setDebriefError(""))
+      .catch((e) => setDebriefError(String(e)));
   }
 
   return (
@@ -936,59 +1875,111 @@ function HotkeysTab({ config, save, overlayOn, toggleOverlay }: HotkeysTabProps)
         background: "rgba(43,213,118,0.06)", border: "1px solid var(--good)",
         borderRadius: 8, padding: "10px 14px", marginBottom: 24, fontSize: 12, color: "var(--good)",
       }}>
-        ✓ Global hotkeys are active. The recorded shortcut works system-wide
+        ✓ Global hotkeys are active. The recorded shortcuts work system-wide
         while the app is running, including when WoW is in the foreground.
         Changes take effect immediately — no restart needed.
       </div>
 
-      {/* Toggle overlay hotkey */}
+      <HotkeyBinding
+        title="Toggle Overlay"
+        description="Show or hide the in-game overlay."
+        combo={hotkeys.toggle_overlay}
+        onChange={(combo) => bind("toggle_overlay", combo)}
+      />
+      <HotkeyBinding
+        title="Show Last Debrief"
+        description="Bring back the last pull's debrief panel — it stays up until the next pull."
+        combo={hotkeys.show_last_debrief ?? ""}
+        onChange={(combo) => bind("show_last_debrief", combo)}
+      />
+
+      {/* Quick-action */}
       <div style={{
         background: "var(--bg-card)", border: "1px solid var(--stroke)",
-        borderRadius: "var(--radius-lg)", padding: "16px 20px", marginBottom: 16,
+        borderRadius: "var(--radius-lg)", padding: "16px 20px",
       }}>
-        <div style={{ fontWeight: 600, fontSize: 13, marginBottom: 4 }}>Toggle Overlay</div>
+        <div style={{ fontWeight: 600, fontSize: 13, marginBottom: 4 }}>Quick Actions</div>
         <div style={{ fontSize: 11, color: "var(--muted)", marginBottom: 14 }}>
-          Show or hide the in-game overlay.
+          Immediate effect — mirrors the top bar toggle.
         </div>
-
-        <div style={{ display: "flex", alignItems: "center", gap: 10 }}>
-          <div style={{
-            flex: 1, padding: "8px 12px", borderRadius: "var(--radius)",
-            border: recording ? "1px solid var(--gold)" : "1px solid var(--stroke)",
-            background: recording ? "rgba(232,168,32,0.1)" : "var(--bg-input)",
-            fontSize: 13, fontFamily: "var(--mono)",
-            color: combo ? "var(--text)" : "var(--muted)",
-            fontStyle: combo ? "normal" : "italic",
-            minHeight: 36, display: "flex", alignItems: "center",
-          }}>
-            {recording ? "Press your key combo…" : (combo || "Not set")}
-          </div>
+        <div style={{ display: "flex", gap: 10 }}>
           <button
-            onClick={() => setRecording((r) => !r)}
-            className={recording ? "primary" : ""}
+            onClick={toggleOverlay}
+            className={overlayOn ? "primary" : ""}
+            style={{ fontSize: 13, padding: "10px 20px" }}
           >
-            {recording ? "Cancel" : "Record"}
+            {overlayOn ? "🟢 Overlay ON — click to hide" : "🔴 Overlay OFF — click to show"}
+          </button>
+          <button onClick={showLastDebrief} style={{ fontSize: 13, padding: "10px 20px" }}>
+            📋 Show last debrief
           </button>
-          {combo && <button onClick={clearHotkey}>Clear</button>}
         </div>
+        {debriefError && (
+          <div style={{ fontSize: 11, color: "var(--muted)", marginTop: 8 }}>{debriefError}</div>
+        )}
       </div>
+    </div>
+  );
+}
 
-      {/* Quick-action */}
-      <div style={{
-        background: "var(--bg-card)", border: "1px solid var(--stroke)",
-        borderRadius: "var(--radius-lg)", padding: "16px 20px",
-      }}>
-        <div style={{ fontWeight: 600, fontSize: 13, marginBottom: 4 }}>Quick Actions</div>
-        <div style={{ fontSize: 11, color: "var(--muted)", marginBottom: 14 }}>
-          Immediate effect — mirrors the top bar toggle.
+interface HotkeyBindingProps {
+  title:       string;
+  description: string;
+  combo:       string;
+  onChange:    (combo: string) => void;
+}
+
+/** One recordable hotkey; an empty combo means unbound. */
+function HotkeyBinding({ title, description, combo, onChange }: HotkeyBindingProps) {
+  const [recording, setRecording] = useState(false);
+
+  useEffect(() => {
+    if (!recording) return;
+    function onKey(e: KeyboardEvent) {
+      e.preventDefault();
+      const parts: string[] = [];
+      if (e.ctrlKey)  parts.push("Ctrl");
+      if (e.altKey)   parts.push("Alt");
+      if (e.shiftKey) parts.push("Shift");
+      const key = e.key;
+      if (["Control", "Alt", "Shift", "Meta"].includes(key)) return;
+      parts.push(key.length === 1 ? key.toUpperCase() : key);
+      setRecording(false);
+      onChange(parts.join("+"));
+    }
+    window.addEventListener("keydown", onKey);
+    return () => window.removeEventListener("keydown", onKey);
+  }, [recording, onChange]);
+
+  return (
+    <div style={{
+      background: "var(--bg-card)", border: "1px solid var(--stroke)",
+      borderRadius: "var(--radius-lg)", padding: "16px 20px", marginBottom: 16,
+    }}>
+      <div style={{ fontWeight: 600, fontSize: 13, marginBottom: 4 }}>{title}</div>
+      <div style={{ fontSize: 11, color: "var(--muted)", marginBottom: 14 }}>
+        {description}
+      </div>
+
+      <div style={{ display: "flex", alignItems: "center", gap: 10 }}>
+        <div style={{
+          flex: 1, padding: "8px 12px", borderRadius: "var(--radius)",
+          border: recording ? "1px solid var(--gold)" : "1px solid var(--stroke)",
+          background: recording ? "rgba(232,168,32,0.1)" : "var(--bg-input)",
+          fontSize: 13, fontFamily: "var(--mono)",
+          color: combo ? "var(--text)" : "var(--muted)",
+          fontStyle: combo ? "normal" : "italic",
+          minHeight: 36, display: "flex", alignItems: "center",
+        }}>
+          {recording ? "Press your key combo…" : (combo || "Not set")}
         </div>
         <button
-          onClick={toggleOverlay}
-          className={overlayOn ? "primary" : ""}
-          style={{ fontSize: 13, padding: "10px 20px" }}
+          onClick={() => setRecording((r) => !r)}
+          className={recording ? "primary" : ""}
         >
-          {overlayOn ? "🟢 Overlay ON — click to hide" : "🔴 Overlay OFF — click to show"}
+          {recording ? "Cancel" : "Record"}
         </button>
+        {combo && <button onClick={() => onChange("")}>Clear</button>}
       </div>
     </div>
   );
@@ -998,9 +1989,73 @@ function HotkeysTab({ config, save, overlayOn, toggleOverlay }: HotkeysTabProps)
 // HISTORY TAB
 // ===========================================================================
 
-function HistoryTab() {
+function HistoryTab({ replaying }: { replaying: string }) {
   const [rows, setRows]       = useState<PullHistoryRow[] | null>(null);
+  const [dodges, setDodges]   = useState<DodgeTrendRow[]>([]);
+  const [mechanics, setMechanics] = useState<MechanicStat[]>([]);
+  const [practice, setPractice] = useState<StoredPracticeSession[]>([]);
+  const [records, setRecords]   = useState<PersonalRecord[]>([]);
   const [loading, setLoading] = useState(false);
+  const [recoach, setRecoach] = useState<RecoachResult | null>(null);
+  const [recoachError, setRecoachError] = useState("");
+  const [recoaching, setRecoaching]     = useState<number | null>(null);
+  const [imagePath, setImagePath]       = useState("");
+  const [replaySpeed, setReplaySpeed]   = useState<ReplaySpeed>("5x");
+  const [replayError, setReplayError]   = useState("");
+  const [replayStatus, setReplayStatus] = useState<ReplayStatus | null>(null);
+
+  // Replay position for the transport controls, polled while one runs.
+  useEffect(() => {
+    if (!replaying) { setReplayStatus(null); return; }
+    const poll = () => {
+      invoke<ReplayStatus | null>("get_replay_status")
+        .then(setReplayStatus)
+        .catch(() => {});
+    };
+    poll();
+    const id = setInterval(poll, 500);
+    return () => clearInterval(id);
+  }, [replaying]);
+
+  function replayControl(command: string, args: Record<string, unknown>) {
+    setReplayError("");
+    invoke(command, args).catch((e) => setReplayError(String(e)));
+  }
+
+  function changeReplaySpeed(speed: ReplaySpeed) {
+    setReplaySpeed(speed);
+    if (replaying) replayControl("set_replay_speed", { speed });
+  }
+
+  async function startReplay() {
+    const path = await open({
+      filters: [{ name: "Combat Log", extensions: ["txt", "gz"] }],
+      title: "Select a WoWCombatLog file to replay",
+    });
+    if (typeof path !== "string") return;
+    setReplayError("");
+    invoke("replay_log_file", { path, speed: replaySpeed })
+      .catch((e) => setReplayError(String(e)));
+  }
+
+  function runRecoach(pullId: number) {
+    setRecoaching(pullId);
+    setRecoachError("");
+    invoke<RecoachResult>("recoach_pull", { pullId })
+      .then((r) => setRecoach(r))
+      .catch((e) => { setRecoach(null); setRecoachError(String(e)); })
+      .finally(() => setRecoaching(null));
+  }
+
+  function exportImage(pullId: number) {
+    setRecoachError("");
+    invoke<string>("render_pull_image", { pullId })
+      .then((path) => {
+        setImagePath(path);
+        void invoke("open_url", { url: path });
+      })
+      .catch((e) => setRecoachError(String(e)));
+  }
 
   function load() {
     setLoading(true);
@@ -1008,6 +2063,33 @@ function HistoryTab() {
       .then((r) => setRows(r))
       .catch(() => setRows([]))
       .finally(() => setLoading(false));
+    invoke<DodgeTrendRow[]>("get_dodge_trends")
+      .then((d) => setDodges(d))
+      .catch(() => setDodges([]));
+    invoke<MechanicStat[]>("get_mechanic_stats")
+      .then((m) => setMechanics(m.filter((s) => s.characters.length > 1)))
+      .catch(() => setMechanics([]));
+    invoke<StoredPracticeSession[]>("get_practice_reports")
+      .then((p) => setPractice(p))
+      .catch(() => setPractice([]));
+    invoke<PersonalRecord[]>("get_records")
+      .then((r) => setRecords(r))
+      .catch(() => setRecords([]));
+  }
+
+  // Records board rows: one per character / encounter / difficulty.
+  const recordBoard = Object.values(
+    records.reduce<Record<string, { head: PersonalRecord; values: Record<string, number> }>>((acc, r) => {
+      const key = `${r.player_name}|${r.encounter}|${r.difficulty_id}`;
+      acc[key] ??= { head: r, values: {} };
+      acc[key].values[r.record] = r.value;
+      return acc;
+    }, {}),
+  );
+
+  function pct(avoided: number, hit: number): string {
+    const total = avoided + hit;
+    return total > 0 ? `${Math.round((avoided * 100) / total)}%` : "—";
   }
 
   useEffect(() => { load(); }, []);
@@ -1028,6 +2110,14 @@ function HistoryTab() {
     });
   }
 
+  // Pulls in the consecutive block of the same run starting at rows[start].
+  function runPullCount(start: number): number {
+    if (!rows) return 0;
+    let n = 1;
+    while (start + n < rows.length && rows[start + n].run_id === rows[start].run_id) n++;
+    return n;
+  }
+
   const outcomeColor: Record<string, string> = {
     kill:    "var(--good)",
     wipe:    "var(--bad)",
@@ -1041,7 +2131,7 @@ function HistoryTab() {
         <div>
           <h2 style={{ margin: "0 0 4px 0", fontSize: 16 }}>Pull History</h2>
           <p style={{ margin: 0, fontSize: 12, color: "var(--muted)" }}>
-            Last 25 pulls across all sessions. Advice count includes all severities.
+            Last 25 pulls across all sessions, grouped by dungeon run or raid. Advice count includes all severities.
           </p>
         </div>
         <button onClick={load} disabled={loading} style={{ flexShrink: 0 }}>
@@ -1049,6 +2139,90 @@ function HistoryTab() {
         </button>
       </div>
 
+      {/* Offline replay: feed a saved log through the coach */}
+      <div style={{ display: "flex", alignItems: "center", gap: 8, flexShrink: 0, fontSize: 12 }}>
+        <span style={{ color: "var(--muted)" }}>Replay a saved combat log</span>
+        <select
+          value={replaySpeed}
+          onChange={(e) => changeReplaySpeed(e.target.value as ReplaySpeed)}
+        >
+          <option value="1x">1x</option>
+          <option value="2x">2x</option>
+          <option value="5x">5x</option>
+          <option value="10x">10x</option>
+          <option value="max">Max</option>
+        </select>
+        {replaying ? (
+          <>
+            <span style={{ fontFamily: "var(--mono)" }}>
+              {replayStatus?.paused ? "Paused" : "Replaying"} {replaying}
+              {replayStatus && ` — ${fmtDuration(0, replayStatus.position_ms)}`}
+              {replayStatus?.seeking_to != null && ` → ${fmtDuration(0, replayStatus.seeking_to)}`}
+            </span>
+            <button
+              onClick={() => replayControl("pause_log_replay", { paused: !replayStatus?.paused })}
+              disabled={!replayStatus}
+              style={{ fontSize: 11 }}
+            >
+              {replayStatus?.paused ? "Play ▶" : "Pause ❚❚"}
+            </button>
+            {[30_000, 120_000].map((skip) => (
+              <button
+                key={skip}
+                onClick={() => replayControl("seek_log_replay", { positionMs: (replayStatus?.position_ms ?? 0) + skip })}
+                disabled={!replayStatus}
+                style={{ fontSize: 11 }}
+              >
+                +{skip / 1_000 >= 60 ? `${skip / 60_000}m` : `${skip / 1_000}s`}
+              </button>
+            ))}
+            <button onClick={() => void invoke("stop_log_replay")} style={{ fontSize: 11 }}>Stop</button>
+          </>
+        ) : (
+          <button onClick={() => void startReplay()} style={{ fontSize: 11 }}>Choose log…</button>
+        )}
+      </div>
+      {replayError && (
+        <div style={{ fontSize: 12, color: "var(--bad)", flexShrink: 0 }}>{replayError}</div>
+      )}
+
+      {/* Re-coach result: live advice vs the current rules */}
+      {recoachError && (
+        <div style={{ fontSize: 12, color: "var(--bad)", flexShrink: 0 }}>{recoachError}</div>
+      )}
+      {imagePath && (
+        <div style={{ fontSize: 11, color: "var(--muted)", flexShrink: 0 }}>
+          Timeline image saved to <code>{imagePath}</code>
+        </div>
+      )}
+      {recoach && (
+        <div style={{
+          background: "var(--bg-card)", border: "1px solid var(--stroke)",
+          borderRadius: "var(--radius-lg)", padding: "12px 16px", flexShrink: 0,
+        }}>
+          <div style={{ display: "flex", justifyContent: "space-between", alignItems: "center", marginBottom: 6 }}>
+            <span style={{ fontSize: 13, fontWeight: 600 }}>
+              Re-coached with current rules (version {recoach.version})
+            </span>
+            <button onClick={() => setRecoach(null)} style={{ fontSize: 11 }}>Close</button>
+          </div>
+          <div style={{ fontSize: 12, color: "var(--muted)", marginBottom: 6 }}>
+            Live: {recoach.live.length} advice · Now: {recoach.recoached.length} advice
+          </div>
+          {recoach.by_rule.map((d) => (
+            <div key={d.rule_key} style={{ display: "flex", justifyContent: "space-between", fontSize: 12 }}>
+              <span style={{ fontFamily: "var(--mono)" }}>{d.rule_key}</span>
+              <span style={{
+                fontFamily: "var(--mono)",
+                color: d.live === d.recoached ? "var(--muted)" : "var(--warn)",
+              }}>
+                {d.live} → {d.recoached}
+              </span>
+            </div>
+          ))}
+        </div>
+      )}
+
       {/* Content */}
       {loading || rows === null ? (
         <div style={{ fontSize: 12, color: "var(--muted)", fontStyle: "italic" }}>Loading history…</div>
@@ -1076,45 +2250,328 @@ function HistoryTab() {
                 <th style={{ textAlign: "left",  padding: "6px 12px", fontWeight: 600 }}>Encounter</th>
                 <th style={{ textAlign: "left",  padding: "6px 12px", fontWeight: 600 }}>Outcome</th>
                 <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>Duration</th>
+                <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>Active</th>
+                <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>On Boss</th>
+                <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>Kicks</th>
                 <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>Advice</th>
                 <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>When</th>
+                <th style={{ padding: "6px 12px" }} />
               </tr>
             </thead>
             <tbody>
               {rows.map((r, i) => (
+                <React.Fragment key={r.pull_id}>
+                  {(i === 0 || rows[i - 1].run_id !== r.run_id) && (
+                    <tr style={{ borderBottom: "1px solid var(--stroke)" }}>
+                      <td colSpan={11} style={{
+                        padding: "10px 12px 4px", fontSize: 11, fontWeight: 600,
+                        color: r.run_label ? "var(--text)" : "var(--muted)",
+                      }}>
+                        {r.run_label ?? r.key_dungeon ?? "Open world"}
+                        {r.key_level != null && (
+                          <span style={{ color: "var(--accent)", marginLeft: 8 }}>
+                            +{r.key_level}{r.key_affixes ? ` · ${r.key_affixes}` : ""}
+                          </span>
+                        )}
+                        <span style={{ color: "var(--muted)", fontWeight: 400, marginLeft: 8 }}>
+                          {runPullCount(i)} pull{runPullCount(i) === 1 ? "" : "s"}
+                        </span>
+                        {r.session_note && (
+                          <span style={{ color: "var(--muted)", fontWeight: 400, fontStyle: "italic", marginLeft: 8 }}>
+                            “{r.session_note}”
+                          </span>
+                        )}
+                      </td>
+                    </tr>
+                  )}
+                  <tr
+                    style={{
+                      background: i % 2 === 0 ? "transparent" : "rgba(255,255,255,0.02)",
+                      borderBottom: "1px solid rgba(255,255,255,0.04)",
+                    }}
+                  >
+                    <td style={{ padding: "8px 12px", color: "var(--muted)" }}>{r.pull_number}</td>
+                    <td style={{ padding: "8px 12px" }}>{r.player_name || "—"}</td>
+                    <td style={{
+                      padding: "8px 12px", maxWidth: 200,
+                      overflow: "hidden", textOverflow: "ellipsis", whiteSpace: "nowrap",
+                    }}>
+                      {r.encounter || (r.key_level != null ? "Trash" : "—")}
+                    </td>
+                    <td style={{ padding: "8px 12px" }}>
+                      <span style={{
+                        color: outcomeColor[r.outcome ?? "unknown"] ?? "var(--muted)",
+                        fontWeight: 600, textTransform: "capitalize",
+                      }}>
+                        {r.outcome ?? "—"}
+                      </span>
+                      {!!r.group_deaths && (
+                        <span
+                          title={`${r.group_deaths} group death(s), ${r.battle_res ?? 0} battle res`}
+                          style={{ color: "var(--muted)", marginLeft: 6, fontSize: 11 }}
+                        >
+                          {r.group_deaths}☠{r.battle_res ? ` ${r.battle_res}↺` : ""}
+                        </span>
+                      )}
+                    </td>
+                    <td style={{ padding: "8px 12px", textAlign: "right", fontFamily: "var(--mono)" }}>
+                      {fmtDuration(r.started_at, r.ended_at)}
+                    </td>
+                    <td style={{ padding: "8px 12px", textAlign: "right", fontFamily: "var(--mono)" }}>
+                      {r.active_time_pct != null ? `${r.active_time_pct.toFixed(0)}%` : "—"}
+                    </td>
+                    <td style={{ padding: "8px 12px", textAlign: "right", fontFamily: "var(--mono)" }}>
+                      {r.boss_damage_pct != null ? `${r.boss_damage_pct.toFixed(0)}%` : "—"}
+                    </td>
+                    <td style={{ padding: "8px 12px", textAlign: "right", fontFamily: "var(--mono)" }}>
+                      {r.interrupt_efficiency != null ? `${r.interrupt_efficiency.toFixed(0)}%` : "—"}
+                    </td>
+                    <td style={{ padding: "8px 12px", textAlign: "right" }}>
+                      <span style={{ color: r.advice_count > 0 ? "var(--text)" : "var(--muted)" }}>
+                        {r.advice_count}
+                      </span>
+                    </td>
+                    <td style={{ padding: "8px 12px", textAlign: "right", color: "var(--muted)", fontSize: 11 }}>
+                      {fmtWhen(r.started_at)}
+                    </td>
+                    <td style={{ padding: "8px 12px", textAlign: "right" }}>
+                      <button
+                        onClick={() => runRecoach(r.pull_id)}
+                        disabled={recoaching !== null}
+                        title="Replay this pull through the current rules"
+                        style={{ fontSize: 11 }}
+                      >
+                        {recoaching === r.pull_id ? "…" : "Re-coach"}
+                      </button>
+                      <button
+                        onClick={() => exportImage(r.pull_id)}
+                        title="Save this pull's timeline as a PNG for sharing"
+                        style={{ fontSize: 11, marginLeft: 4 }}
+                      >
+                        PNG
+                      </button>
+                    </td>
+                  </tr>
+                </React.Fragment>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      )}
+
+      {/* Dodge rates per avoidable mechanic */}
+      {dodges.length > 0 && (
+        <div style={{ flexShrink: 0 }}>
+          <h2 style={{ margin: "0 0 4px 0", fontSize: 16 }}>Dodge Rates</h2>
+          <p style={{ margin: "0 0 8px 0", fontSize: 12, color: "var(--muted)" }}>
+            How often you avoided each encounter mechanic. "Recent" covers its last 5 pulls.
+          </p>
+          <table style={{ width: "100%", borderCollapse: "collapse", fontSize: 12 }}>
+            <thead>
+              <tr style={{
+                borderBottom: "1px solid var(--stroke)",
+                color: "var(--muted)", fontSize: 10,
+                textTransform: "uppercase", letterSpacing: 0.5,
+              }}>
+                <th style={{ textAlign: "left",  padding: "6px 12px", fontWeight: 600 }}>Encounter</th>
+                <th style={{ textAlign: "left",  padding: "6px 12px", fontWeight: 600 }}>Mechanic</th>
+                <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>Pulls</th>
+                <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>Dodged</th>
+                <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>Overall</th>
+                <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>Recent</th>
+              </tr>
+            </thead>
+            <tbody>
+              {dodges.map((d, i) => (
                 <tr
-                  key={r.pull_id}
+                  key={`${d.encounter}-${d.spell_id}`}
                   style={{
                     background: i % 2 === 0 ? "transparent" : "rgba(255,255,255,0.02)",
                     borderBottom: "1px solid rgba(255,255,255,0.04)",
                   }}
                 >
-                  <td style={{ padding: "8px 12px", color: "var(--muted)" }}>{r.pull_number}</td>
-                  <td style={{ padding: "8px 12px" }}>{r.player_name || "—"}</td>
-                  <td style={{
-                    padding: "8px 12px", maxWidth: 200,
-                    overflow: "hidden", textOverflow: "ellipsis", whiteSpace: "nowrap",
-                  }}>
-                    {r.encounter ?? "—"}
+                  <td style={{ padding: "8px 12px" }}>{d.encounter || "—"}</td>
+                  <td style={{ padding: "8px 12px" }}>{d.spell_name}</td>
+                  <td style={{ padding: "8px 12px", textAlign: "right", color: "var(--muted)" }}>{d.pulls}</td>
+                  <td style={{ padding: "8px 12px", textAlign: "right", fontFamily: "var(--mono)" }}>
+                    {d.avoided}/{d.avoided + d.hit}
+                  </td>
+                  <td style={{ padding: "8px 12px", textAlign: "right", fontFamily: "var(--mono)" }}>
+                    {pct(d.avoided, d.hit)}
                   </td>
+                  <td style={{ padding: "8px 12px", textAlign: "right", fontFamily: "var(--mono)", color: "var(--good)" }}>
+                    {pct(d.recent_avoided, d.recent_hit)}
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      )}
+
+      {/* The same mechanics across the account's characters */}
+      {mechanics.length > 0 && (
+        <div style={{ flexShrink: 0 }}>
+          <h2 style={{ margin: "0 0 4px 0", fontSize: 16 }}>Across Your Characters</h2>
+          <p style={{ margin: "0 0 8px 0", fontSize: 12, color: "var(--muted)" }}>
+            Mechanics recorded on more than one character of this account. Flagged ones hit you at least half the time on every character with 3+ pulls of them.
+          </p>
+          <table style={{ width: "100%", borderCollapse: "collapse", fontSize: 12 }}>
+            <thead>
+              <tr style={{
+                borderBottom: "1px solid var(--stroke)",
+                color: "var(--muted)", fontSize: 10,
+                textTransform: "uppercase", letterSpacing: 0.5,
+              }}>
+                <th style={{ textAlign: "left",  padding: "6px 12px", fontWeight: 600 }}>Mechanic</th>
+                <th style={{ textAlign: "left",  padding: "6px 12px", fontWeight: 600 }}>Characters</th>
+                <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>Dodged</th>
+                <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>Overall</th>
+              </tr>
+            </thead>
+            <tbody>
+              {mechanics.map((m, i) => (
+                <tr
+                  key={m.spell_name}
+                  title={m.encounters.join(", ")}
+                  style={{
+                    background: i % 2 === 0 ? "transparent" : "rgba(255,255,255,0.02)",
+                    borderBottom: "1px solid rgba(255,255,255,0.04)",
+                  }}
+                >
                   <td style={{ padding: "8px 12px" }}>
-                    <span style={{
-                      color: outcomeColor[r.outcome ?? "unknown"] ?? "var(--muted)",
-                      fontWeight: 600, textTransform: "capitalize",
-                    }}>
-                      {r.outcome ?? "—"}
-                    </span>
+                    {m.spell_name}
+                    {m.fails_everywhere && (
+                      <span style={{ marginLeft: 8, color: "var(--bad)", fontSize: 11 }}>
+                        hit on every character
+                      </span>
+                    )}
+                  </td>
+                  <td style={{ padding: "8px 12px", color: "var(--muted)" }}>
+                    {m.characters.map((c) => `${c.player_name} ${pct(c.avoided, c.hit)}`).join(" · ")}
+                  </td>
+                  <td style={{ padding: "8px 12px", textAlign: "right", fontFamily: "var(--mono)" }}>
+                    {m.avoided}/{m.avoided + m.hit}
+                  </td>
+                  <td style={{ padding: "8px 12px", textAlign: "right", fontFamily: "var(--mono)" }}>
+                    {pct(m.avoided, m.hit)}
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      )}
+
+      {/* Training-dummy practice sessions (not part of pull history) */}
+      {practice.length > 0 && (
+        <div style={{ flexShrink: 0 }}>
+          <h2 style={{ margin: "0 0 4px 0", fontSize: 16 }}>Practice Sessions</h2>
+          <p style={{ margin: "0 0 8px 0", fontSize: 12, color: "var(--muted)" }}>
+            Consecutive target-dummy pulls, scored on opener, resource overcap, cooldown cadence and active time.
+          </p>
+          <table style={{ width: "100%", borderCollapse: "collapse", fontSize: 12 }}>
+            <thead>
+              <tr style={{
+                borderBottom: "1px solid var(--stroke)",
+                color: "var(--muted)", fontSize: 10,
+                textTransform: "uppercase", letterSpacing: 0.5,
+              }}>
+                <th style={{ textAlign: "left",  padding: "6px 12px", fontWeight: 600 }}>Spec</th>
+                <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>Pulls</th>
+                <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>Score</th>
+                <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>Best</th>
+                <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>Opener</th>
+                <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>Overcap</th>
+                <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>Active</th>
+                <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>When</th>
+              </tr>
+            </thead>
+            <tbody>
+              {practice.map((p, i) => (
+                <tr
+                  key={p.id}
+                  style={{
+                    background: i % 2 === 0 ? "transparent" : "rgba(255,255,255,0.02)",
+                    borderBottom: "1px solid rgba(255,255,255,0.04)",
+                  }}
+                >
+                  <td style={{ padding: "8px 12px" }}>{p.spec || "—"}</td>
+                  <td style={{ padding: "8px 12px", textAlign: "right", color: "var(--muted)" }}>{p.report.pulls}</td>
+                  <td style={{ padding: "8px 12px", textAlign: "right", fontFamily: "var(--mono)" }}>
+                    {p.report.score.toFixed(0)}
+                  </td>
+                  <td style={{ padding: "8px 12px", textAlign: "right", fontFamily: "var(--mono)", color: "var(--good)" }}>
+                    {p.report.best_score.toFixed(0)}
                   </td>
                   <td style={{ padding: "8px 12px", textAlign: "right", fontFamily: "var(--mono)" }}>
-                    {fmtDuration(r.started_at, r.ended_at)}
+                    {p.report.opener_pct != null ? `${p.report.opener_pct.toFixed(0)}%` : "—"}
                   </td>
-                  <td style={{ padding: "8px 12px", textAlign: "right" }}>
-                    <span style={{ color: r.advice_count > 0 ? "var(--text)" : "var(--muted)" }}>
-                      {r.advice_count}
-                    </span>
+                  <td style={{ padding: "8px 12px", textAlign: "right", fontFamily: "var(--mono)" }}>
+                    {p.report.overcap_pct != null ? `${p.report.overcap_pct.toFixed(0)}%` : "—"}
+                  </td>
+                  <td style={{ padding: "8px 12px", textAlign: "right", fontFamily: "var(--mono)" }}>
+                    {p.report.active_time_pct.toFixed(0)}%
                   </td>
                   <td style={{ padding: "8px 12px", textAlign: "right", color: "var(--muted)", fontSize: 11 }}>
-                    {fmtWhen(r.started_at)}
+                    {fmtWhen(p.started_at)}
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      )}
+
+      {/* Best boss kills per encounter and difficulty (records.rs) */}
+      {recordBoard.length > 0 && (
+        <div style={{ flexShrink: 0 }}>
+          <h2 style={{ margin: "0 0 4px 0", fontSize: 16 }}>Personal Records</h2>
+          <p style={{ margin: "0 0 8px 0", fontSize: 12, color: "var(--muted)" }}>
+            Best kills per encounter and difficulty. Beating one shows a "New PR" advisory.
+          </p>
+          <table style={{ width: "100%", borderCollapse: "collapse", fontSize: 12 }}>
+            <thead>
+              <tr style={{
+                borderBottom: "1px solid var(--stroke)",
+                color: "var(--muted)", fontSize: 10,
+                textTransform: "uppercase", letterSpacing: 0.5,
+              }}>
+                <th style={{ textAlign: "left",  padding: "6px 12px", fontWeight: 600 }}>Encounter</th>
+                <th style={{ textAlign: "left",  padding: "6px 12px", fontWeight: 600 }}>Player</th>
+                <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>Fastest</th>
+                <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>Fewest Hits</th>
+                <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>Kicks</th>
+                <th style={{ textAlign: "right", padding: "6px 12px", fontWeight: 600 }}>Score</th>
+              </tr>
+            </thead>
+            <tbody>
+              {recordBoard.map(({ head, values }, i) => (
+                <tr
+                  key={`${head.player_name}|${head.encounter}|${head.difficulty_id}`}
+                  style={{
+                    background: i % 2 === 0 ? "transparent" : "rgba(255,255,255,0.02)",
+                    borderBottom: "1px solid rgba(255,255,255,0.04)",
+                  }}
+                >
+                  <td style={{ padding: "8px 12px" }}>
+                    {head.encounter}
+                    {head.difficulty && (
+                      <span style={{ color: "var(--muted)", marginLeft: 6 }}>{head.difficulty}</span>
+                    )}
+                  </td>
+                  <td style={{ padding: "8px 12px", color: "var(--muted)" }}>{head.player_name || "—"}</td>
+                  <td style={{ padding: "8px 12px", textAlign: "right", fontFamily: "var(--mono)" }}>
+                    {values.fastest_kill != null ? fmtDuration(0, values.fastest_kill) : "—"}
+                  </td>
+                  <td style={{ padding: "8px 12px", textAlign: "right", fontFamily: "var(--mono)" }}>
+                    {values.fewest_avoidable ?? "—"}
+                  </td>
+                  <td style={{ padding: "8px 12px", textAlign: "right", fontFamily: "var(--mono)" }}>
+                    {values.best_kick_efficiency != null ? `${values.best_kick_efficiency.toFixed(0)}%` : "—"}
+                  </td>
+                  <td style={{ padding: "8px 12px", textAlign: "right", fontFamily: "var(--mono)", color: "var(--good)" }}>
+                    {values.best_pull_score != null ? values.best_pull_score.toFixed(0) : "—"}
                   </td>
                 </tr>
               ))}
@@ -1122,6 +2579,135 @@ function HistoryTab() {
           </table>
         </div>
       )}
+
+      <JournalSection />
+    </div>
+  );
+}
+
+/** "1h 05m" / "25m". */
+function fmtHours(ms: number): string {
+  const mins = Math.floor(ms / 60_000);
+  return mins < 60 ? `${mins}m` : `${Math.floor(mins / 60)}h ${(mins % 60).toString().padStart(2, "0")}m`;
+}
+
+// Session notes and raid-night journal entries (journal.rs).
+function JournalSection() {
+  const [journals, setJournals] = useState<SessionJournal[]>([]);
+  const [drafts, setDrafts]     = useState<Record<number, string>>({});
+  const [error, setError]       = useState("");
+
+  function load() {
+    invoke<SessionJournal[]>("get_session_journals")
+      .then(setJournals)
+      .catch(() => setJournals([]));
+  }
+
+  useEffect(() => { load(); }, []);
+
+  function setNote(sessionId: number, note: string) {
+    setJournals((js) => js.map((j) => (j.session_id === sessionId ? { ...j, note } : j)));
+  }
+
+  function saveNote(sessionId: number, text: string) {
+    invoke("set_session_note", { sessionId, text }).catch((e) => setError(String(e)));
+  }
+
+  function addEntry(sessionId: number) {
+    setError("");
+    invoke("add_journal_entry", { sessionId, text: drafts[sessionId] ?? "" })
+      .then(() => {
+        setDrafts((d) => ({ ...d, [sessionId]: "" }));
+        load();
+      })
+      .catch((e) => setError(String(e)));
+  }
+
+  function deleteEntry(entryId: number) {
+    invoke("delete_journal_entry", { entryId })
+      .then(() => setTimeout(load, 100))
+      .catch((e) => setError(String(e)));
+  }
+
+  async function exportJournal() {
+    const path = await saveDialog({
+      filters: [{ name: "Markdown", extensions: ["md"] }],
+      defaultPath: "raid-journal.md",
+      title: "Export Raid-Night Journal",
+    });
+    if (!path) return;
+    invoke("export_journal", { path }).catch((e) => setError(String(e)));
+  }
+
+  if (journals.length === 0) return null;
+
+  return (
+    <div style={{ flexShrink: 0 }}>
+      <div style={{ display: "flex", alignItems: "flex-start", justifyContent: "space-between" }}>
+        <div>
+          <h2 style={{ margin: "0 0 4px 0", fontSize: 16 }}>Journal</h2>
+          <p style={{ margin: "0 0 8px 0", fontSize: 12, color: "var(--muted)" }}>
+            Notes on what was different about a session — a new strat, lag, a new trinket — so odd stats explain themselves.
+          </p>
+        </div>
+        <button onClick={() => void exportJournal()} style={{ fontSize: 11, flexShrink: 0 }}>Export…</button>
+      </div>
+      {error && <div style={{ fontSize: 12, color: "var(--bad)", marginBottom: 8 }}>{error}</div>}
+      {journals.map((j) => (
+        <div
+          key={j.session_id}
+          style={{
+            background: "var(--bg-card)", border: "1px solid var(--stroke)",
+            borderRadius: "var(--radius-lg)", padding: "10px 14px", marginBottom: 8,
+          }}
+        >
+          <div style={{ fontSize: 12, fontWeight: 600, marginBottom: 6 }}>
+            {new Date(j.started_at).toLocaleString(undefined, {
+              weekday: "short", month: "short", day: "numeric", hour: "2-digit", minute: "2-digit",
+            })}
+            {j.player_name && (
+              <span style={{ color: "var(--muted)", fontWeight: 400, marginLeft: 8 }}>{j.player_name}</span>
+            )}
+            {!!j.active_ms && (
+              <span style={{ color: "var(--muted)", fontWeight: 400, marginLeft: 8 }}>
+                {fmtHours(j.active_ms)} active
+                {j.paused_ms >= 60_000 && ` (${fmtHours(j.paused_ms)} paused)`}
+                {` · ${j.pulls} pulls, ${((j.pulls * 3_600_000) / j.active_ms).toFixed(1)}/h`}
+              </span>
+            )}
+          </div>
+          <input
+            type="text"
+            value={j.note}
+            placeholder="Session note"
+            maxLength={500}
+            onChange={(e) => setNote(j.session_id, e.target.value)}
+            onBlur={(e) => saveNote(j.session_id, e.target.value)}
+            style={{ width: "100%", fontSize: 12, marginBottom: 6 }}
+          />
+          {j.entries.map((e) => (
+            <div key={e.id} style={{ display: "flex", alignItems: "center", gap: 8, fontSize: 12, padding: "2px 0" }}>
+              <span style={{ color: "var(--muted)", fontFamily: "var(--mono)", fontSize: 11 }}>
+                {new Date(e.created_at).toLocaleTimeString(undefined, { hour: "2-digit", minute: "2-digit" })}
+              </span>
+              <span style={{ flex: 1 }}>{e.text}</span>
+              <button onClick={() => deleteEntry(e.id)} title="Delete entry" style={{ fontSize: 11 }}>✕</button>
+            </div>
+          ))}
+          <div style={{ display: "flex", gap: 6, marginTop: 4 }}>
+            <input
+              type="text"
+              value={drafts[j.session_id] ?? ""}
+              placeholder="Add a journal entry…"
+              maxLength={500}
+              onChange={(e) => setDrafts((d) => ({ ...d, [j.session_id]: e.target.value }))}
+              onKeyDown={(e) => { if (e.key === "Enter") addEntry(j.session_id); }}
+              style={{ flex: 1, fontSize: 12 }}
+            />
+            <button onClick={() => addEntry(j.session_id)} style={{ fontSize: 11 }}>Add</button>
+          </div>
+        </div>
+      ))}
     </div>
   );
 }
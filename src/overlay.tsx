@@ -10,6 +10,7 @@ import { Timeline }          from "./components/Timeline";
 import { StatWidgets }       from "./components/StatWidgets";
 import { PullClock }         from "./components/PullClock";
 import { PullDebriefPanel }  from "./components/PullDebriefPanel";
+import { ReadinessPanel }    from "./components/ReadinessPanel";
 import { useTauriEvents }    from "./hooks/useTauriEvents";
 import type {
   AdviceEvent,
@@ -107,6 +108,45 @@ function playAudioCue(severity: string, cues: AudioCue[]): void {
   }
 }
 
+/**
+ * Queue one countdown number `delayS` seconds ahead on the audio clock.
+ * Uses the voice pack file when it has been decoded; otherwise a short tone,
+ * higher and longer on "1" so the last beat stands out.
+ */
+function playCountdownStep(number: number, soundPath: string, delayS: number): void {
+  try {
+    const ctx = getAudioCtx();
+    const play = () => {
+      const at   = ctx.currentTime + Math.max(0, delayS);
+      const gain = ctx.createGain();
+      gain.connect(ctx.destination);
+      gain.gain.value = 0.8;
+
+      const cached = soundPath ? _audioBufferCache.get(soundPath) : undefined;
+      if (cached) {
+        const source  = ctx.createBufferSource();
+        source.buffer = cached;
+        source.connect(gain);
+        source.start(at);
+      } else {
+        const osc = ctx.createOscillator();
+        osc.connect(gain);
+        osc.frequency.value = number === 1 ? 1320 : 990;
+        osc.type            = "square";
+        osc.start(at);
+        osc.stop(at + (number === 1 ? 0.35 : 0.12));
+      }
+    };
+    if (ctx.state === "suspended") {
+      ctx.resume().then(play).catch(() => {});
+    } else {
+      play();
+    }
+  } catch {
+    // Audio not available — silently ignore
+  }
+}
+
 // ---------------------------------------------------------------------------
 // Overlay app
 // ---------------------------------------------------------------------------
@@ -125,6 +165,11 @@ function OverlayApp() {
   const [panels, setPanels]     = useState<PanelPosition[]>([]);
   // Audio cues kept in a ref — no re-renders needed when config reloads
   const audioCuesRef = useRef<AudioCue[]>([]);
+  // Global mute — polled with the panel positions so /cc mute applies live
+  const mutedRef = useRef(false);
+  // Countdown steps already queued ("<countdown key>:<number>") — every
+  // snapshot repeats the pending steps, each is played once.
+  const queuedStepsRef = useRef<Set<string>>(new Set());
 
   // Load audio cues once on mount and pre-decode any custom files.
   // Audio files are cached in _audioBufferCache so re-running on interval
@@ -143,11 +188,13 @@ function OverlayApp() {
 
   // Poll for panel position changes every second so edits made in the
   // Settings window (Overlay Layout tab) are reflected in real time.
+  // The audio mute flag rides along on the same poll.
   // Uses a JSON-equality check to skip re-renders when nothing has changed.
   useEffect(() => {
     const syncPanels = () => {
       invoke<AppConfig>("get_config")
         .then((cfg) => {
+          mutedRef.current = cfg.audio_muted ?? false;
           const next = cfg.panel_positions ?? [];
           setPanels((prev) =>
             JSON.stringify(prev) === JSON.stringify(next) ? prev : next
@@ -164,16 +211,33 @@ function OverlayApp() {
   useTauriEvents({
     onAdvice: useCallback((incoming: AdviceEvent) => {
       setAdvice((prev) => {
-        // Replace card with same key (dedup), prepend new card, cap at MAX_CARDS
+        // Replace card with same key (dedup), prepend new card, cap at MAX_CARDS.
+        // Urgent cards stay on top so routine advice can't push them out.
         const filtered = prev.filter((a) => a.key !== incoming.key);
-        return [incoming, ...filtered].slice(0, MAX_CARDS);
+        const next = [incoming, ...filtered];
+        const urgent = (a: AdviceEvent) => a.priority === "urgent";
+        return [...next.filter(urgent), ...next.filter((a) => !urgent(a))].slice(0, MAX_CARDS);
       });
       // Play audio cue for this severity
-      playAudioCue(incoming.severity, audioCuesRef.current);
+      if (!mutedRef.current) playAudioCue(incoming.severity, audioCuesRef.current);
     }, []),
 
     onStateSnapshot: useCallback((snap: StateSnapshot) => {
       setSnapshot(snap);
+      const countdown = snap.countdown;
+      if (!countdown) {
+        if (!snap.in_combat) queuedStepsRef.current.clear();
+        return;
+      }
+      for (const step of countdown.steps) {
+        // Decode voice files as soon as they are announced; queue a step once
+        // it is under a second away so its file has had time to load.
+        if (step.sound_path) void preloadAudioBuffer(step.sound_path);
+        const id = `${countdown.key}:${step.number}`;
+        if (step.in_ms > 1_000 || queuedStepsRef.current.has(id)) continue;
+        queuedStepsRef.current.add(id);
+        if (!mutedRef.current) playCountdownStep(step.number, step.sound_path, step.in_ms / 1000);
+      }
     }, []),
 
     onDebrief: useCallback((d: PullDebrief) => {
@@ -185,13 +249,13 @@ function OverlayApp() {
   useEffect(() => {
     const id = setInterval(() => {
       const cutoff = Date.now() - CARD_TTL;
-      setAdvice((prev) => prev.filter((a) => a.timestamp_ms > cutoff));
+      setAdvice((prev) => prev.filter((a) => a.wallclock_ms > cutoff));
     }, 1_000);
     return () => clearInterval(id);
   }, []);
 
-  const pos = (id: string): PanelPosition =>
-    panels.find((p) => p.id === id) ?? { id, x: 20, y: 80, visible: true };
+  const pos = (id: string, x = 20, y = 80): PanelPosition =>
+    panels.find((p) => p.id === id) ?? { id, x, y, visible: true };
 
   // Quiet mode: fade the entire overlay out when out of combat so it doesn't
   // clutter the screen while navigating, looting, etc.  A slow transition
@@ -199,6 +263,7 @@ function OverlayApp() {
   const quietOpacity = snapshot.in_combat ? 1.0 : 0.15;
 
   return (
+    <>
     <div
       style={{
         position:   "fixed",
@@ -213,6 +278,10 @@ function OverlayApp() {
           elapsedMs={snapshot.pull_elapsed_ms}
           inCombat={snapshot.in_combat}
           encounterName={snapshot.encounter_name}
+          phaseLabel={snapshot.phase_label}
+          nextEvent={snapshot.next_scheduled_event}
+          keystone={snapshot.keystone}
+          wipePct={snapshot.wipe_pct}
         />
       </AbsPanel>
 
@@ -229,14 +298,27 @@ function OverlayApp() {
           gcdGapMs={snapshot.gcd_gap_ms}
           avoidableCount={snapshot.avoidable_count}
           interruptCount={snapshot.interrupt_count}
+          dps10s={snapshot.dps_10s ?? 0}
+          hps10s={snapshot.hps_10s ?? 0}
+          pullDps={snapshot.pull_dps ?? 0}
+          pullHps={snapshot.pull_hps ?? 0}
         />
       </AbsPanel>
 
       {/* Debrief panel — auto-positioned bottom-right-ish, auto-dismisses */}
       <AbsPanel pos={pos("debrief") ?? { id: "debrief", x: 20, y: 400, visible: true }}>
-        <PullDebriefPanel debrief={debrief} />
+        <PullDebriefPanel debrief={debrief} inCombat={snapshot.in_combat} />
+      </AbsPanel>
+    </div>
+
+    {/* Readiness checklist — between pulls only, so it sits outside the
+        quiet-mode fade. */}
+    <div style={{ position: "fixed", inset: 0, pointerEvents: "none" }}>
+      <AbsPanel pos={pos("readiness", 20, 300)}>
+        <ReadinessPanel readiness={snapshot.in_combat ? null : snapshot.readiness} />
       </AbsPanel>
     </div>
+    </>
   );
 }
 
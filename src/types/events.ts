@@ -7,13 +7,38 @@
 
 export type Severity = "good" | "warn" | "bad";
 
+/** Delivery priority, independent of severity. Mirrors engine::Priority. */
+export type Priority = "info" | "normal" | "urgent";
+
+export type HintDirection = "out" | "in" | "behind" | "front" | "spread" | "stack";
+
+/** Optional positioning hint. Mirrors engine::PositionHint on the Rust side. */
+export interface PositionHint {
+  direction:   HintDirection;
+  distance_yd: number | null;
+  zone:        string | null;
+}
+
 export interface AdviceEvent {
   key:          string;
   title:        string;
   message:      string;
   severity:     Severity;
+  /** Urgent advice is delivered first and pinned above other cards. */
+  priority?:    Priority;
   kv:           [string, string][];
   timestamp_ms: number;
+  /** Unix-epoch ms of the triggering log line (clock-aligned when possible). */
+  wallclock_ms: number;
+  hint?:        PositionHint | null;
+  /** Group member the advice is about (group coaching mode). */
+  player?:      string | null;
+  /** Nth fire of this key in the pull (0 when not counted). */
+  repeat?:      number;
+  /** Repeat-offense escalation: 0 none, 1 severity raised, 2 also urgent. */
+  escalation?:  number;
+  /** Key for get_advice_debug; set only while advice debug capture is on. */
+  debug_id?:    string | null;
 }
 
 export interface StateSnapshot {
@@ -24,6 +49,81 @@ export interface StateSnapshot {
   interrupt_count: number;
   /** Active encounter name from ENCOUNTER_START, or null between pulls. */
   encounter_name?: string | null;
+  /** Unix-epoch ms of the log event behind this snapshot (clock-aligned). */
+  wallclock_ms?:   number;
+  /** Active-time ("always be casting") % for the current pull. */
+  active_time_pct?: number;
+  /** Damage / effective healing per second over the last 10s. */
+  dps_10s?:  number;
+  hps_10s?:  number;
+  /** Pull averages; after the pull they hold its final values. */
+  pull_dps?: number;
+  pull_hps?: number;
+  /** Group players dead and battle resurrections cast this pull. */
+  group_deaths?: number;
+  battle_res?:   number;
+  /** True while coaching is paused via pause_coaching(). */
+  coaching_paused?: boolean;
+  /** Current encounter phase from the encounter timeline (e.g. "P2"). */
+  phase_label?: string | null;
+  /** Soonest scheduled boss ability from the encounter timeline. */
+  next_scheduled_event?: ScheduledEvent | null;
+  /** Pre-pull readiness checklist (null while in combat), shown by the
+   *  overlay's ReadinessPanel.  Only in the polled snapshot; the coach:state
+   *  event leaves it out, and EVENT_READINESS carries it when it changes. */
+  readiness?: Readiness | null;
+  /** Mythic+ key in progress (null outside keystone runs). */
+  keystone?: KeystoneInfo | null;
+  /** Audio countdown to the next raid-wide / tank ability (null when none is close). */
+  countdown?: Countdown | null;
+  /** Encounter pulls: estimated wipe chance in % (null until enough stored pulls). */
+  wipe_pct?: number | null;
+}
+
+/** Active Mythic+ key. Mirrors ipc::KeystoneInfo. */
+export interface KeystoneInfo {
+  dungeon: string;
+  level:   number;
+  /** Affix display names, e.g. "Fortified". */
+  affixes: string[];
+}
+
+/** Upcoming timeline ability. Mirrors ipc::ScheduledEvent. */
+export interface ScheduledEvent {
+  name:    string;
+  seconds: number;
+}
+
+/** Boss-mod style "3, 2, 1" countdown. Mirrors ipc::Countdown. */
+export interface Countdown {
+  /** Stable per ability cast, e.g. "448888_1" — steps are queued once per key. */
+  key:       string;
+  ability:   string;
+  due_in_ms: number;
+  steps:     CountdownStep[];
+}
+
+export interface CountdownStep {
+  number:     number;
+  /** Delay from this snapshot until the number should be spoken. */
+  in_ms:      number;
+  /** Voice pack file for this number; "" plays the built-in tone. */
+  sound_path: string;
+}
+
+/** Pre-pull checklist, present only out of combat. Mirrors ipc::Readiness. */
+export interface Readiness {
+  items: ReadinessItem[];
+  /** True when no item is "warn". */
+  ready: boolean;
+}
+
+export interface ReadinessItem {
+  /** "flask" | "food" | "rune" | "durability" | "spec" | "cooldowns" | "assignments" */
+  key:    string;
+  label:  string;
+  status: "ok" | "warn" | "unknown";
+  detail: string;
 }
 
 /** A spec profile available for selection. Mirrors specs::SpecInfo on the Rust side. */
@@ -43,10 +143,35 @@ export interface PlayerIdentity {
   version: string;
 }
 
+/** Playback speed of the replay_log_file command (tailer::ReplaySpeed). */
+export type ReplaySpeed = "1x" | "2x" | "5x" | "10x" | "max";
+
+/** Running replay, from get_replay_status. Mirrors tailer::ReplayStatus. */
+export interface ReplayStatus {
+  /** Log time since the first line of the file. */
+  position_ms: number;
+  paused:      boolean;
+  speed:       ReplaySpeed;
+  /** Position a seek is fast-forwarding to, or null. */
+  seeking_to:  number | null;
+}
+
 export interface ConnectionStatus {
   log_tailing:     boolean;
   addon_connected: boolean;
   wow_path:        string;
+  /** addonVersion from SavedVariables ("" before the first read). */
+  addon_version:   string;
+  /** Addon is older than the app supports — prompt an update. */
+  addon_outdated:  boolean;
+  /** File name of the combat log being replayed ("" when not replaying). */
+  replaying:       string;
+  /** Config changes made outside the settings window since startup — re-read the config on change. */
+  config_reloads:  number;
+  /** Game build of the tailed log ("" before its header is read). */
+  log_build?:      string;
+  /** Coaching data predates the log's build — why spell IDs stopped matching. */
+  stale_data?:     string;
 }
 
 export interface PanelPosition {
@@ -60,11 +185,31 @@ export interface PanelPosition {
   scale?:   number;
 }
 
+/** Boss ability countdowns. Mirrors config::CountdownConfig. */
+export interface CountdownConfig {
+  enabled:    boolean;
+  /** First number spoken (1–5). */
+  from_s:     number;
+  /** Folder with 1.ogg … 5.ogg (or .mp3/.wav); "" = built-in tones. */
+  voice_pack: string;
+}
+
+/** Repeat hold-back of advice in seconds. Mirrors config::AdviceCooldowns. */
+export interface AdviceCooldowns {
+  bad_s:  number;
+  warn_s: number;
+  good_s: number;
+  /** Rule name → seconds, overriding the severity's. */
+  rules:  Record<string, number>;
+}
+
 // All fields are optional here because:
 // - Rust fills them in with #[serde(default)] before sending to the frontend
 // - Spread-updates in the settings UI only supply the field being changed
 // - config closures capture AppConfig | null so spreading produces optional fields
 export interface AppConfig {
+  /** Config file shape (backend migrations); written by the backend on save. */
+  version?:         number;
   wow_log_path?:    string;
   addon_sv_path?:   string;
   intensity?:       number;
@@ -75,6 +220,111 @@ export interface AppConfig {
   audio_cues?:      AudioCue[];
   hotkeys?:         HotkeyConfig;
   overlay_visible?: boolean;
+  clock_alignment?: boolean;
+  /** Seconds of pre-warning before scheduled encounter damage (0 = off). */
+  spike_forecast_lead_s?: number;
+  countdown?:       CountdownConfig;
+  /** Seconds a major cooldown may sit ready before it is called out (0 = off). */
+  cd_unused_alert_s?: number;
+  /** Percent of generated resource lost to overcapping before it is called out (0 = off). */
+  resource_waste_pct?: number;
+  /** Damage of a single hit that calls for a defensive (0 = off). */
+  defensive_missing_damage?: number;
+  /** Share of the last 10s of damage taken that makes a hit a spike (0 = off). */
+  defensive_missing_pct?: number;
+  number_format?:   NumberFormat;
+  /** Cloud folder for settings sync between machines ("" = off). */
+  profile_sync_dir?: string;
+  /** Folder for the live spectate JSON files read by second-screen dashboards ("" = off). */
+  spectate_dir?: string;
+  /** UDP/OSC/serial messages to external hardware per advice severity. */
+  hardware_cues?: HardwareCue[];
+  /** Silence all audio cues (also /cc mute in-game). */
+  audio_muted?:     boolean;
+  /** Last in-game request applied by the addon bridge. */
+  addon_request_id?: number;
+  /** Copy the one-line pull summary to the clipboard at pull end. */
+  pull_summary_clipboard?: boolean;
+  /** Include a party damage-taken leaderboard in pull debriefs. */
+  squad_mode?: boolean;
+  /** Store the raw log lines and state behind each advice (get_advice_debug). */
+  advice_debug?: boolean;
+  /** Batch callouts of avoidable hits on the whole group, plus a per-member pull summary. */
+  raid_lead_mode?: boolean;
+  /** Coach every player in the group, each advice tagged with its player. */
+  group_coaching?: boolean;
+  /** Count self-inflicted and party-sourced damage as avoidable. */
+  avoidable_friendly_fire?: boolean;
+  /** How long the same advice stays quiet after firing. */
+  advice_cooldowns?: AdviceCooldowns;
+  /** Download signed community encounter files for bosses with none locally. */
+  encounter_downloads?: boolean;
+  /** Own Warcraft Logs API client for the quick-setup import ("" = off). */
+  wcl_client_id?:     string;
+  wcl_client_secret?: string;
+  /** Session database folder ("" = app data folder). Change via move_data_dir. */
+  data_dir?:        string;
+  /** Rolling log folder ("" = default); applies after restart. */
+  log_dir?:         string;
+  /** App version that last ran here (drives the one-time "what's new" notice). */
+  last_seen_version?: string;
+  /** Outdated addon version the update advisory last fired for. */
+  addon_outdated_notice?: string;
+  /** Coaching overrides per encounter name (usually from strategy presets). */
+  encounter_settings?: Record<string, EncounterSettings>;
+  /** Coaching defaults per character GUID, generated from the role on first sight. */
+  character_profiles?: Record<string, CharacterProfile>;
+}
+
+/** Per-character coaching defaults. Mirrors config::CharacterProfile. */
+export interface CharacterProfile {
+  name:            string;
+  /** Role the defaults were generated for. */
+  role:            string;
+  intensity?:      number | null;
+  disabled_rules:  string[];
+}
+
+/** Coaching overrides for one encounter. Mirrors config::EncounterSettings. */
+export interface EncounterSettings {
+  /** Strategy preset these came from ("" when set by hand). */
+  preset_id?:        string;
+  intensity?:        number | null;
+  disabled_rules?:   string[];
+  /** Enemy spell IDs the player is assigned to kick. */
+  kick_assignments?: number[];
+}
+
+/** A preset published in the curated repository. Mirrors presets::PresetListing. */
+export interface PresetListing {
+  id:          string;
+  name:        string;
+  encounter:   string;
+  author:      string;
+  description: string;
+  file:        string;
+  /** Already downloaded to this machine. */
+  installed:   boolean;
+}
+
+/** A downloaded strategy preset. Mirrors presets::Preset. */
+export interface StrategyPreset {
+  preset: {
+    id:          string;
+    name:        string;
+    encounter:   string;
+    author:      string;
+    description: string;
+  };
+  coaching:        EncounterSettings;
+  panel_positions: PanelPosition[];
+}
+
+/** Number/duration formatting for advice text. Mirrors units::NumberFormat. */
+export interface NumberFormat {
+  compact:       boolean;
+  thousands_sep: string;
+  decimal_sep:   string;
 }
 
 export interface UpdateInfo {
@@ -82,6 +332,27 @@ export interface UpdateInfo {
   current_version: string;
   new_version:     string | null;
   notes:           string | null;
+  /** Changelog entries newer than the running version. */
+  changes?:        ChangelogEntry[];
+}
+
+/** A crash report from an earlier run. Mirrors crash::CrashReport. */
+export interface CrashReport {
+  file_name:  string;
+  /** Unix ms of the crash. */
+  crashed_at: number;
+  /** "location: message" line. */
+  summary:    string;
+  contents:   string;
+}
+
+/** One release in the changelog feed. Mirrors changelog::ChangelogEntry. */
+export interface ChangelogEntry {
+  version:   string;
+  date:      string;
+  /** Display names of coaching rules added in this release. */
+  new_rules: string[];
+  notes:     string[];
 }
 
 export interface AudioCue {
@@ -91,8 +362,21 @@ export interface AudioCue {
   sound_path: string;   // empty = built-in beep
 }
 
+/** Mirrors config::HardwareCue. */
+export interface HardwareCue {
+  severity:  string;   // "good" | "warn" | "bad"
+  enabled:   boolean;
+  transport: "udp" | "osc" | "serial";
+  /** "host:port" (udp, osc) or serial device ("COM3"). */
+  target:    string;
+  /** Payload ({severity}, {title}, {key} filled in) or the OSC address. */
+  message:   string;
+}
+
 export interface HotkeyConfig {
-  toggle_overlay: string; // e.g. "Ctrl+Shift+O", empty = none
+  toggle_overlay:     string; // e.g. "Ctrl+Shift+O", empty = none
+  /** Brings back the last pull debrief (show_last_debrief). */
+  show_last_debrief?: string;
 }
 
 /// A character found in the WTF directory tree.
@@ -115,6 +399,49 @@ export interface PullHistoryRow {
   encounter?:   string | null;
   player_name:  string;
   advice_count: number;
+  /** Active-time % recorded at pull end (null for pulls stored before it was tracked). */
+  active_time_pct?: number | null;
+  /** Player damage on the boss vs all targets (null for trash / older pulls). */
+  boss_damage_pct?: number | null;
+  /** Kick efficiency % (null without kick opportunities / older pulls). */
+  interrupt_efficiency?: number | null;
+  /** Dungeon run / raid the pull belongs to (null outside instances). */
+  run_id?:      number | null;
+  /** e.g. "Ara-Kara, City of Echoes (Mythic+)" */
+  run_label?:   string | null;
+  /** Mythic+ key active at pull start (null outside keys / older pulls). */
+  key_dungeon?: string | null;
+  key_level?:   number | null;
+  /** Comma-separated affix names. */
+  key_affixes?: string | null;
+  /** The session's note ("" when none was written). */
+  session_note?: string;
+  /** Group deaths and battle resurrections (null for older pulls). */
+  group_deaths?: number | null;
+  battle_res?:   number | null;
+}
+
+/** One raid-night journal entry. Mirrors journal::JournalEntry on the Rust side. */
+export interface JournalEntry {
+  id:         number;
+  session_id: number;
+  /** Unix epoch milliseconds */
+  created_at: number;
+  text:       string;
+}
+
+/** A session with its note and journal entries. Mirrors journal::SessionJournal. */
+export interface SessionJournal {
+  session_id:  number;
+  started_at:  number;
+  ended_at?:   number | null;
+  player_name: string;
+  note:        string;
+  entries:     JournalEntry[];
+  /** Session start to its end (last pull or app close), paused time excluded; null before the first pull ends. */
+  active_ms:   number | null;
+  paused_ms:   number;
+  pulls:       number;
 }
 
 /** End-of-pull summary emitted by the engine. Mirrors ipc::PullDebrief on the Rust side. */
@@ -125,8 +452,373 @@ export interface PullDebrief {
   outcome:             string;
   avoidable_count:     number;
   interrupt_count:     number;
+  /** Kicks landed vs kick opportunities, % (null without opportunities). */
+  interrupt_efficiency?: number | null;
+  /** The same ratio over the whole session. */
+  session_interrupt_efficiency?: number | null;
   total_advice_fired:  number;
   gcd_gap_count:       number;
+  /** The same GCD gaps by likely cause. */
+  gcd_gap_causes?:     GapCauses;
+  active_time_pct:     number;
+  /** Avoided vs hit per avoidable mechanic (empty without an encounter profile). */
+  dodge_stats?:        DodgeStat[];
+  /** Summed first-use drift of major cooldowns. */
+  cd_drift_ms?:        number;
+  /** One-line shareable summary ("Pull 14 — wipe 4:32 — …"). */
+  summary?:            string;
+  /** Boss-damage share (encounter pulls only). */
+  boss_damage?:        BossDamage | null;
+  /** Switch time onto kill-priority adds (null when none appeared). */
+  target_switch?:      TargetSwitch | null;
+  /** Damage inside major cooldown windows (null without a major CD cast). */
+  burst?:              BurstAlignment | null;
+  /** Melee players only: time in range of the boss (encounter pulls). */
+  melee_uptime?:       MeleeUptime | null;
+  /** Group players' deaths in order (coached player included). */
+  deaths?:             GroupDeathEntry[];
+  /** Battle resurrections cast during the pull. */
+  battle_res?:         BattleResEntry[];
+  /** The coached player's health potions and healthstones this pull. */
+  consumables?:        ConsumableUse;
+  /** Encounter pulls only: pre-potion and pre-cast before ENCOUNTER_START. */
+  prepull?:            PrepullCheck | null;
+  /** Hard casts the coached player cancelled or clipped. */
+  cancelled_casts?:    CancelledCasts | null;
+  /** Failed casts by reason, most frequent first. */
+  failed_casts?:       FailedCastCount[];
+  /** Damage taken from the player's own side. */
+  friendly_fire?:      FriendlyFire | null;
+  /** Healers only: effective healing by recipient. */
+  healing?:            HealingDistribution | null;
+  /** Damage taken per party member, most avoidable first (squad mode only). */
+  damage_leaderboard?: PartyDamage[];
+  /** Running practice-session report when the pull was on a training dummy. */
+  practice?:           PracticeReport | null;
+  /** Casts per minute of the spec's rotation priority spells. */
+  cast_rates?:         SpellCastRate[];
+  /** Recalled via show_last_debrief: stays up until the next pull. */
+  sticky?:             boolean;
+  /** Raid-lead mode: per-member avoidable hits, ready to paste. */
+  raid_summary?:       string | null;
+}
+
+/** Defensive consumables used over a pull. Mirrors ipc::ConsumableUse. */
+export interface ConsumableUse {
+  potions:      number;
+  healthstones: number;
+}
+
+/** Self-inflicted and party-sourced damage over a pull. Mirrors ipc::FriendlyFire. */
+export interface FriendlyFire {
+  self_inflicted: number;
+  from_party:     number;
+  /** Whether it counted as avoidable (avoidable_friendly_fire). */
+  counted:        boolean;
+  /** Spells behind it, most damage first. */
+  spells:         string[];
+}
+
+/** Failed casts of one reason over a pull. Mirrors ipc::FailedCastCount. */
+export interface FailedCastCount {
+  /** "movement", "interrupted", "crowd control", or the log's message. */
+  reason: string;
+  count:  number;
+}
+
+/** Hard casts abandoned part way over a pull. Mirrors ipc::CancelledCasts. */
+export interface CancelledCasts {
+  count:   number;
+  /** Hard casts started, cancelled ones included. */
+  started: number;
+  /** Cast time spent on the cancelled casts. */
+  lost_ms: number;
+  /** Cancelled spells, most often first ("Chaos Bolt ×2"). */
+  spells:  string[];
+}
+
+/** Preparation in the 30s before ENCOUNTER_START. Mirrors ipc::PrepullCheck. */
+export interface PrepullCheck {
+  potion:   boolean;
+  /** The spec's pre-cast spells that were cast. */
+  precasts: string[];
+  /** What was left out ("pre-potion", "pre-cast (Frostbolt)"); empty when ready. */
+  missing:  string[];
+}
+
+/** GCD gaps over a pull by likely cause. Mirrors ipc::GapCauses. */
+export interface GapCauses {
+  movement:      number;
+  silence:       number;
+  crowd_control: number;
+  idle:          number;
+}
+
+/** Training-dummy practice session summary. Mirrors practice::PracticeReport. */
+export interface PracticeReport {
+  pulls:           number;
+  duration_ms:     number;
+  /** Mean pull score, 0–100. */
+  score:           number;
+  best_score:      number;
+  last_score:      number;
+  /** Opener adherence, % (null when the spec has no opener or major CDs). */
+  opener_pct:      number | null;
+  /** Casts that left the resource capped, % (null without advanced logging). */
+  overcap_pct:     number | null;
+  active_time_pct: number;
+  cooldowns:       CdCadence[];
+}
+
+/** Use cadence of one major cooldown. Mirrors practice::CdCadence. */
+export interface CdCadence {
+  spell_id:    number;
+  spell_name:  string;
+  uses:        number;
+  /** Shortest gap over mean gap, % (null below three uses). */
+  cadence_pct: number | null;
+}
+
+/** A stored practice session. Mirrors practice::StoredPracticeSession. */
+export interface StoredPracticeSession {
+  id:         number;
+  started_at: number;
+  ended_at:   number;
+  spec:       string;
+  report:     PracticeReport;
+}
+
+/** One personal record from get_records. Mirrors records::PersonalRecord. */
+export interface PersonalRecord {
+  player_name:   string;
+  encounter:     string;
+  difficulty_id: number;
+  /** e.g. "Heroic"; "" when unnamed. */
+  difficulty:    string;
+  /** "fastest_kill" | "fewest_avoidable" | "best_kick_efficiency" | "best_pull_score" */
+  record:        string;
+  /** ms for fastest_kill, hits, % or score points. */
+  value:         number;
+  pull_id:       number | null;
+  /** Unix epoch milliseconds */
+  set_at:        number;
+}
+
+/** One party member's damage taken over a pull. Mirrors ipc::PartyDamage. */
+export interface PartyDamage {
+  name:            string;
+  /** True for the coached player. */
+  is_player:       boolean;
+  damage_taken:    number;
+  avoidable_taken: number;
+  avoidable_hits:  number;
+}
+
+/** Target-switch latency onto kill-priority adds. Mirrors ipc::TargetSwitch. */
+export interface TargetSwitch {
+  switches: number;
+  avg_ms:   number;
+  worst_ms: number;
+}
+
+/** A group player's death. Mirrors ipc::GroupDeathEntry. */
+export interface GroupDeathEntry {
+  name:       string;
+  /** ms since pull start */
+  elapsed_ms: number;
+}
+
+/** A battle resurrection cast. Mirrors ipc::BattleResEntry. */
+export interface BattleResEntry {
+  caster:     string;
+  target:     string;
+  elapsed_ms: number;
+}
+
+/** A melee player's time in range of the boss. Mirrors ipc::MeleeUptime. */
+export interface MeleeUptime {
+  /** In range / time the boss was being hit, %. */
+  uptime_pct:      number;
+  out_of_range_ms: number;
+  longest_gap_ms:  number;
+}
+
+/** Player damage vs major cooldown windows. Mirrors ipc::BurstAlignment. */
+export interface BurstAlignment {
+  /** Share of damage inside a burst window, %. */
+  in_window_pct:  number;
+  windows:        number;
+  peak_dps_10s:   number;
+  peak_dps_30s:   number;
+  peak_in_window: boolean;
+}
+
+/** A healer's effective healing by recipient. Mirrors ipc::HealingDistribution. */
+export interface HealingDistribution {
+  effective:    number;
+  overheal_pct: number;
+  /** Shares of effective healing, %. */
+  tank_pct:     number;
+  party_pct:    number;
+  self_pct:     number;
+  tank_names:   string[];
+}
+
+/** The coached player's boss-damage share. Mirrors ipc::BossDamage. */
+export interface BossDamage {
+  boss_name:    string;
+  boss_damage:  number;
+  total_damage: number;
+  share_pct:    number;
+}
+
+/** One avoidable mechanic's dodge rate over a pull. Mirrors ipc::DodgeStat. */
+export interface DodgeStat {
+  spell_id:   number;
+  spell_name: string;
+  avoided:    number;
+  hit:        number;
+  dodge_pct:  number;
+}
+
+/** A rotation priority spell's casts per minute over a pull. Mirrors rotation::SpellCastRate. */
+export interface SpellCastRate {
+  spell_id:      number;
+  spell_name:    string;
+  casts:         number;
+  cpm:           number;
+  expected_cpm?: number | null;
+  /** Well below the expected rate (or 1/min) over a pull of a minute or more. */
+  underused:     boolean;
+}
+
+/** The coached player's last seconds before a death. Mirrors ipc::DeathRecap. */
+export interface DeathRecap {
+  /** ms since pull start. */
+  elapsed_ms:      number;
+  killing_blow:    string;
+  killer:          string;
+  damage_taken:    number;
+  /** Effective healing (overhealing excluded). */
+  heals_received:  number;
+  defensives_used: number;
+  window_ms:       number;
+  /** Potion / healthstone most likely ready but not used (display names). */
+  unused_consumables?: string[];
+  /** Oldest first. */
+  entries:         RecapEntry[];
+}
+
+/** Mythic+ run summary sent at CHALLENGE_MODE_END. Mirrors ipc::KeyDebrief. */
+export interface KeyDebrief {
+  dungeon:          string;
+  level:            number;
+  affixes:          string[];
+  /** Completed (in time or not); false when abandoned. */
+  success:          boolean;
+  /** Game-reported run time, incl. death penalties. */
+  total_time_ms:    number;
+  pulls:            number;
+  deaths:           number;
+  avoidable_count:  number;
+  interrupt_count:  number;
+  /** Longest gaps between casts, longest first (one per pull). */
+  gcd_gaps:         { pull_number: number; gap_ms: number }[];
+  out_of_combat_ms: number;
+  summary:          string;
+}
+
+/** One line of a death recap. Mirrors ipc::RecapEntry. */
+export interface RecapEntry {
+  /** ms before the death. */
+  before_ms: number;
+  kind:      "damage" | "heal" | "defensive" | "consumable";
+  source:    string;
+  ability:   string;
+  amount:    number;
+  /** Player health after the event (advanced logging only). */
+  hp_pct:    number | null;
+}
+
+/** One row from the get_dodge_trends command. Mirrors lib::DodgeTrendRow. */
+export interface DodgeTrendRow {
+  encounter:      string;
+  spell_id:       number;
+  spell_name:     string;
+  pulls:          number;
+  avoided:        number;
+  hit:            number;
+  /** Totals over the mechanic's 5 most recent pulls. */
+  recent_avoided: number;
+  recent_hit:     number;
+}
+
+/** One character's totals for a mechanic. Mirrors mechanic_stats::CharacterMechanicStat. */
+export interface CharacterMechanicStat {
+  player_guid: string;
+  player_name: string;
+  pulls:       number;
+  avoided:     number;
+  hit:         number;
+}
+
+/** A mechanic across the account's characters (get_mechanic_stats). Mirrors mechanic_stats::MechanicStat. */
+export interface MechanicStat {
+  spell_name:       string;
+  spell_ids:        number[];
+  encounters:       string[];
+  avoided:          number;
+  hit:              number;
+  /** Most pulls first. */
+  characters:       CharacterMechanicStat[];
+  /** Two or more characters with enough pulls, all hit at least half the time. */
+  fails_everywhere: boolean;
+}
+
+/** One attempt of a mechanic's learning curve (get_mechanic_learning_curve). Mirrors mechanic_stats::LearningPoint. */
+export interface LearningPoint {
+  /** 1-based, over the encounter's pulls that saw the mechanic. */
+  attempt:     number;
+  pull_id:     number;
+  started_at:  number;
+  outcome:     string;
+  player_name: string;
+  hits:        number;
+  avoided:     number;
+  /** Mean hits over this attempt and up to four before it. */
+  trend:       number;
+}
+
+/** One stored advice row of a pull. Mirrors db::StoredAdvice. */
+export interface StoredAdvice {
+  fired_at:   number;
+  rule_key:   string;
+  severity:   Severity;
+  message:    string;
+  repeat:     number;
+  escalation: number;
+}
+
+/** Result of the import_wcl_character command. Mirrors wcl_import::QuickSetup. */
+export interface QuickSetup {
+  /** Saved config with player_focus and the spec applied. */
+  config:            AppConfig;
+  class:             string;
+  spec_key:          string | null;
+  /** "5/8 Heroic"; "" without rankings this tier. */
+  progress:          string;
+  encounters_loaded: number;
+}
+
+/** Result of the recoach_pull command. Mirrors recoach::RecoachResult. */
+export interface RecoachResult {
+  pull_id:   number;
+  /** Advice version the re-coached set was stored as (live advice is 0). */
+  version:   number;
+  live:      StoredAdvice[];
+  recoached: StoredAdvice[];
+  /** Advice count per rule, live vs re-coached. */
+  by_rule:   { rule_key: string; live: number; recoached: number }[];
 }
 
 // IPC event name constants — must match ipc.rs
@@ -135,6 +827,9 @@ export const EVENT_STATE:      string = "coach:state";
 export const EVENT_CONNECTION: string = "coach:connection";
 export const EVENT_IDENTITY:   string = "coach:identity";
 export const EVENT_DEBRIEF:    string = "coach:debrief";
+export const EVENT_DEATH_RECAP: string = "coach:death_recap";
+export const EVENT_KEY_DEBRIEF: string = "coach:key_debrief";
+export const EVENT_READINESS:  string = "coach:readiness";
 
 // Known panel IDs
 export const PANEL_PULL_CLOCK:   string = "pull_clock";